@@ -1,88 +1,411 @@
 use std::env;
 
+use skeplib::docgen::DocFormat;
+use skeplib::error_codes;
+use skeplib::exit_codes::ExitReason;
+use skeplib::lint::{Lint, LintConfig, LintLevel, LintTarget};
+use skeplib::resolver::CAPABILITY_PACKAGES;
+
 use crate::commands::{
-    build_llvm_ir_file, build_native_file, build_object_file, check_file, run_native_file,
+    build_llvm_ir_file, build_native_file, build_object_file, check_directory, check_file,
+    doc_file, init_lints, parse_file, run_native_file, test_file,
 };
+use crate::output;
+use crate::watch::watch_build;
+
+const USAGE_TOP: &str = "Usage: skepac check [--all] <entry.sk|dir> | skepac run <entry.sk> | skepac test <entry.sk> | skepac build-native <entry.sk> <out.exe> | skepac build-obj <entry.sk> <out.obj> | skepac build-llvm-ir <entry.sk> <out.ll> | skepac doc [--html] <entry.sk> <outdir> | skepac parse [--json] <file.sk> | skepac explain <code> (any command accepts --no-color and --format human|json)";
+const USAGE_FORMAT: &str = "Usage: --format expects `human` or `json`";
+const CAPABILITY_HELP: &str = "  --no-fs, --no-os, --no-net, --no-ffi, --no-store, --no-task  Reject programs that use these host packages";
+const WATCH_HELP: &str =
+    "  --watch  Rebuild whenever a .sk file under the entry's directory changes";
+
+/// One subcommand: its usage line, help text, and the flags it accepts.
+struct CommandSpec {
+    name: &'static str,
+    usage: &'static str,
+    about: &'static str,
+    /// Flags without a value.
+    switches: &'static [&'static str],
+    /// Flags that take a value, as `--flag value` or `--flag=value`.
+    options: &'static [&'static str],
+    /// Whether `--no-<package>` capability flags are accepted.
+    capabilities: bool,
+    /// Help lines for `switches` and `options`.
+    flag_help: &'static [&'static str],
+}
 
-pub const EXIT_OK: u8 = 0;
-pub const EXIT_USAGE: u8 = 2;
-pub const EXIT_IO: u8 = 3;
-pub const EXIT_PARSE: u8 = 10;
-pub const EXIT_SEMA: u8 = 11;
-pub const EXIT_CODEGEN: u8 = 12;
-pub const EXIT_RESOLVE: u8 = 15;
-
-const USAGE_TOP: &str = "Usage: skepac check <entry.sk> | skepac run <entry.sk> | skepac build-native <entry.sk> <out.exe> | skepac build-obj <entry.sk> <out.obj> | skepac build-llvm-ir <entry.sk> <out.ll>";
-const USAGE_CHECK: &str = "Usage: skepac check <file.sk>";
-const USAGE_RUN: &str = "Usage: skepac run <in.sk>";
-const USAGE_BUILD_NATIVE: &str = "Usage: skepac build-native <in.sk> <out.exe>";
-const USAGE_BUILD_OBJ: &str = "Usage: skepac build-obj <in.sk> <out.obj>";
-const USAGE_BUILD_LLVM_IR: &str = "Usage: skepac build-llvm-ir <in.sk> <out.ll>";
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "check",
+        usage: "Usage: skepac check <file.sk> | skepac check --all <dir>",
+        about: "Resolve and type-check a program without building it.",
+        switches: &["--all"],
+        options: &[],
+        capabilities: false,
+        flag_help: &["  --all  Check every .sk file under <dir> as one project"],
+    },
+    CommandSpec {
+        name: "run",
+        usage: "Usage: skepac run [--no-fs] [--no-os] [--no-net] [--no-ffi] [--no-store] [--no-task] [--entry <fn>] <in.sk>",
+        about: "Build a program natively and run it, exiting with its exit code.",
+        switches: &[],
+        options: &["--entry"],
+        capabilities: true,
+        flag_help: &[
+            "  --entry <fn>                          Run another zero-argument function of the entry module instead of `main`",
+            CAPABILITY_HELP,
+        ],
+    },
+    CommandSpec {
+        name: "test",
+        usage: "Usage: skepac test <entry.sk>",
+        about: "Run every `@test` function of a project and report which fail.",
+        switches: &[],
+        options: &[],
+        capabilities: false,
+        flag_help: &[],
+    },
+    CommandSpec {
+        name: "build-native",
+        usage: "Usage: skepac build-native [--watch] [--no-fs] [--no-os] [--no-net] [--no-ffi] [--no-store] [--no-task] <in.sk> <out.exe>",
+        about: "Build a native executable.",
+        switches: &["--watch"],
+        options: &[],
+        capabilities: true,
+        flag_help: &[WATCH_HELP, CAPABILITY_HELP],
+    },
+    CommandSpec {
+        name: "build-obj",
+        usage: "Usage: skepac build-obj [--watch] <in.sk> <out.obj>",
+        about: "Build a native object file.",
+        switches: &["--watch"],
+        options: &[],
+        capabilities: false,
+        flag_help: &[WATCH_HELP],
+    },
+    CommandSpec {
+        name: "build-llvm-ir",
+        usage: "Usage: skepac build-llvm-ir [--watch] <in.sk> <out.ll>",
+        about: "Write the LLVM IR for a program.",
+        switches: &["--watch"],
+        options: &[],
+        capabilities: false,
+        flag_help: &[WATCH_HELP],
+    },
+    CommandSpec {
+        name: "doc",
+        usage: "Usage: skepac doc [--html] <entry.sk> <outdir>",
+        about: "Write API documentation pages for every module of a project.",
+        switches: &["--html"],
+        options: &[],
+        capabilities: false,
+        flag_help: &["  --html  Write HTML pages instead of Markdown"],
+    },
+    CommandSpec {
+        name: "parse",
+        usage: "Usage: skepac parse [--json] <file.sk>",
+        about: "Dump the parse tree of one file.",
+        switches: &["--json"],
+        options: &[],
+        capabilities: false,
+        flag_help: &["  --json  Print the tree and declaration positions as JSON"],
+    },
+    CommandSpec {
+        name: "explain",
+        usage: "Usage: skepac explain <code>",
+        about: "Explain a diagnostic code such as E-SEMA-001.",
+        switches: &[],
+        options: &[],
+        capabilities: false,
+        flag_help: &[],
+    },
+];
+
+/// Flags and positional arguments of one command line, checked against a
+/// [`CommandSpec`].
+struct ParsedArgs {
+    switches: Vec<&'static str>,
+    options: Vec<(&'static str, String)>,
+    denied: Vec<String>,
+    positional: Vec<String>,
+}
+
+impl ParsedArgs {
+    fn has(&self, switch: &str) -> bool {
+        self.switches.contains(&switch)
+    }
+
+    /// The last value given for `option`.
+    fn option(&self, option: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(name, _)| *name == option)
+            .map(|(_, value)| value.as_str())
+    }
+}
 
 pub fn run() -> Result<i32, String> {
-    let mut args = env::args().skip(1);
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let no_color = args.iter().any(|arg| arg == "--no-color");
+    args.retain(|arg| arg != "--no-color");
+    let json = take_format_flag(&mut args)?;
+    output::init(no_color, json);
+    init_lints(take_lint_flags(&mut args)?);
+    let mut args = args.into_iter();
     let Some(cmd) = args.next() else {
         return Err(USAGE_TOP.to_string());
     };
 
     match cmd.as_str() {
+        "-h" | "--help" => {
+            print!("{}", top_help());
+            return Ok(ExitReason::Ok.code());
+        }
+        "-V" | "--version" => {
+            println!("skepac {}", env!("CARGO_PKG_VERSION"));
+            return Ok(ExitReason::Ok.code());
+        }
+        "help" => {
+            match args.next() {
+                None => print!("{}", top_help()),
+                Some(name) => print!("{}", command_help(find_command(&name)?)),
+            }
+            return Ok(ExitReason::Ok.code());
+        }
+        _ => {}
+    }
+
+    let spec = find_command(&cmd)?;
+    let args = args.collect::<Vec<_>>();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print!("{}", command_help(spec));
+        return Ok(ExitReason::Ok.code());
+    }
+    let parsed = parse_command_args(spec, args)?;
+    let usage = || spec.usage.to_string();
+
+    match spec.name {
         "check" => {
-            let Some(path) = args.next() else {
-                return Err(USAGE_CHECK.to_string());
+            let [path] = parsed.positional.as_slice() else {
+                return Err(usage());
             };
-            if args.next().is_some() {
-                return Err(USAGE_CHECK.to_string());
+            if parsed.has("--all") {
+                check_directory(path)
+            } else {
+                check_file(path)
             }
-            check_file(&path)
         }
         "run" => {
-            let Some(input) = args.next() else {
-                return Err(USAGE_RUN.to_string());
+            let [input] = parsed.positional.as_slice() else {
+                return Err(usage());
             };
-            if args.next().is_some() {
-                return Err(USAGE_RUN.to_string());
-            }
-            run_native_file(&input)
+            let entry_fn = parsed.option("--entry").unwrap_or("main");
+            run_native_file(input, &parsed.denied, entry_fn)
         }
-        "build-native" => {
-            let Some(input) = args.next() else {
-                return Err(USAGE_BUILD_NATIVE.to_string());
+        "test" => {
+            let [input] = parsed.positional.as_slice() else {
+                return Err(usage());
             };
-            let Some(output) = args.next() else {
-                return Err(USAGE_BUILD_NATIVE.to_string());
+            test_file(input)
+        }
+        "build-native" => {
+            let [input, output] = parsed.positional.as_slice() else {
+                return Err(usage());
             };
-            if args.next().is_some() {
-                return Err(USAGE_BUILD_NATIVE.to_string());
+            if parsed.has("--watch") {
+                watch_build(input, || build_native_file(input, output, &parsed.denied));
             }
-            build_native_file(&input, &output)
+            build_native_file(input, output, &parsed.denied)
         }
         "build-llvm-ir" => {
-            let Some(input) = args.next() else {
-                return Err(USAGE_BUILD_LLVM_IR.to_string());
-            };
-            let Some(output) = args.next() else {
-                return Err(USAGE_BUILD_LLVM_IR.to_string());
+            let [input, output] = parsed.positional.as_slice() else {
+                return Err(usage());
             };
-            if args.next().is_some() {
-                return Err(USAGE_BUILD_LLVM_IR.to_string());
+            if parsed.has("--watch") {
+                watch_build(input, || build_llvm_ir_file(input, output));
             }
-            build_llvm_ir_file(&input, &output)
+            build_llvm_ir_file(input, output)
         }
         "build-obj" => {
-            let Some(input) = args.next() else {
-                return Err(USAGE_BUILD_OBJ.to_string());
+            let [input, output] = parsed.positional.as_slice() else {
+                return Err(usage());
+            };
+            if parsed.has("--watch") {
+                watch_build(input, || build_object_file(input, output));
+            }
+            build_object_file(input, output)
+        }
+        "doc" => {
+            let [input, outdir] = parsed.positional.as_slice() else {
+                return Err(usage());
+            };
+            let format = if parsed.has("--html") {
+                DocFormat::Html
+            } else {
+                DocFormat::Markdown
+            };
+            doc_file(input, outdir, format)
+        }
+        "parse" => {
+            let [input] = parsed.positional.as_slice() else {
+                return Err(usage());
+            };
+            parse_file(input, parsed.has("--json"))
+        }
+        "explain" => {
+            let [code] = parsed.positional.as_slice() else {
+                return Err(usage());
             };
-            let Some(output) = args.next() else {
-                return Err(USAGE_BUILD_OBJ.to_string());
+            let Some(entry) = error_codes::lookup(code) else {
+                return Err(format!(
+                    "Unknown error code `{code}`. Codes look like `E-SEMA-001` or `E-MOD-CYCLE`."
+                ));
             };
-            if args.next().is_some() {
-                return Err(USAGE_BUILD_OBJ.to_string());
+            print!("{}", entry.render());
+            Ok(ExitReason::Ok.code())
+        }
+        _ => unreachable!("every command in COMMANDS is dispatched"),
+    }
+}
+
+fn find_command(name: &str) -> Result<&'static CommandSpec, String> {
+    COMMANDS
+        .iter()
+        .find(|spec| spec.name == name)
+        .ok_or_else(|| {
+            let names = COMMANDS.iter().map(|spec| spec.name).collect::<Vec<_>>();
+            format!("Unknown command. Supported: {}", names.join(", "))
+        })
+}
+
+fn top_help() -> String {
+    let mut out = format!(
+        "skepac {} - compiler for the Skepa language\n\nUsage: skepac <command> [options]\n\nCommands:\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    for spec in COMMANDS {
+        out.push_str(&format!("  {:<14} {}\n", spec.name, spec.about));
+    }
+    out.push_str(
+        "\nOptions:\n  --format <human|json>  Diagnostic output format\n  --no-color             Disable colored diagnostics\n  --allow <lint>         Silence a lint\n  --warn <lint>          Report a lint as a warning\n  --deny <lint>          Report a lint as an error; `--deny warnings` denies every warning\n  -h, --help             Print help; `skepac <command> --help` for one command\n  -V, --version          Print the version\n",
+    );
+    out
+}
+
+fn command_help(spec: &CommandSpec) -> String {
+    let mut out = format!("{}\n\n{}\n", spec.about, spec.usage);
+    if !spec.flag_help.is_empty() {
+        out.push_str("\nOptions:\n");
+        for line in spec.flag_help {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Splits `args` into the switches, options, capability flags, and positional
+/// arguments `spec` accepts. Unknown flags and missing values are usage errors.
+fn parse_command_args(spec: &CommandSpec, args: Vec<String>) -> Result<ParsedArgs, String> {
+    let mut parsed = ParsedArgs {
+        switches: Vec::new(),
+        options: Vec::new(),
+        denied: Vec::new(),
+        positional: Vec::new(),
+    };
+    let error = |message: String| format!("{message}\n{}", spec.usage);
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            parsed.positional.push(arg);
+            continue;
+        }
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg.clone(), None),
+        };
+        if let Some(&switch) = spec.switches.iter().find(|s| **s == flag) {
+            if inline_value.is_some() {
+                return Err(error(format!("Flag `{flag}` does not take a value")));
             }
-            build_object_file(&input, &output)
+            parsed.switches.push(switch);
+        } else if let Some(&option) = spec.options.iter().find(|o| **o == flag) {
+            let Some(value) = inline_value.or_else(|| args.next()) else {
+                return Err(error(format!("Flag `{flag}` expects a value")));
+            };
+            parsed.options.push((option, value));
+        } else if let Some(package) = flag
+            .strip_prefix("--no-")
+            .filter(|package| spec.capabilities && CAPABILITY_PACKAGES.contains(package))
+        {
+            if inline_value.is_some() {
+                return Err(error(format!("Flag `{flag}` does not take a value")));
+            }
+            if !parsed.denied.iter().any(|p| p == package) {
+                parsed.denied.push(package.to_string());
+            }
+        } else {
+            return Err(error(format!(
+                "Unknown flag `{arg}` for `skepac {}`",
+                spec.name
+            )));
         }
-        _ => Err(
-            "Unknown command. Supported: check, run, build-native, build-obj, build-llvm-ir"
-                .to_string(),
-        ),
+    }
+    Ok(parsed)
+}
+
+/// Removes every `--allow`, `--warn`, and `--deny <lint>` (or `--deny=<lint>`)
+/// from `args`, applying them in order so a later flag wins. The lint may be
+/// `warnings` for every lint that would otherwise warn.
+fn take_lint_flags(args: &mut Vec<String>) -> Result<LintConfig, String> {
+    let mut config = LintConfig::new();
+    let mut idx = 0;
+    while idx < args.len() {
+        let (flag, inline_value) = match args[idx].split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (args[idx].clone(), None),
+        };
+        let Some(level) = flag.strip_prefix("--").and_then(LintLevel::from_name) else {
+            idx += 1;
+            continue;
+        };
+        args.remove(idx);
+        let value = match inline_value {
+            Some(value) => value,
+            None if idx < args.len() => args.remove(idx),
+            None => return Err(format!("Flag `{flag}` expects a lint name")),
+        };
+        let Some(target) = LintTarget::from_name(&value) else {
+            let known = Lint::ALL.iter().map(|lint| lint.name()).collect::<Vec<_>>();
+            return Err(format!(
+                "Unknown lint `{value}`; known lints are `warnings`, `{}`",
+                known.join("`, `")
+            ));
+        };
+        config.set(target, level);
+    }
+    Ok(config)
+}
+
+/// Removes `--format <human|json>` (or `--format=...`) from `args`, returning
+/// whether JSON diagnostics were requested.
+fn take_format_flag(args: &mut Vec<String>) -> Result<bool, String> {
+    let Some(pos) = args
+        .iter()
+        .position(|arg| arg == "--format" || arg.starts_with("--format="))
+    else {
+        return Ok(false);
+    };
+    let flag = args.remove(pos);
+    let value = match flag.strip_prefix("--format=") {
+        Some(value) => value.to_string(),
+        None if pos < args.len() => args.remove(pos),
+        None => return Err(USAGE_FORMAT.to_string()),
+    };
+    match value.as_str() {
+        "human" => Ok(false),
+        "json" => Ok(true),
+        _ => Err(USAGE_FORMAT.to_string()),
     }
 }
@@ -1,53 +1,78 @@
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
 use std::{
     collections::HashSet,
     collections::hash_map::DefaultHasher,
     time::{Instant, UNIX_EPOCH},
 };
 
+use skeplib::ast::Program;
 use skeplib::codegen;
+use skeplib::docgen::{self, DocFormat};
+use skeplib::driver::{CompileOutput, Compiler, DriverDiagnostic};
+use skeplib::exit_codes::ExitReason;
+use skeplib::index::{SymbolIndex, collect_symbols};
 use skeplib::ir;
-use skeplib::ir::{FunctionId, GlobalId};
-use skeplib::resolver::{ModuleGraph, ResolveError, resolve_project};
-use skeplib::sema::analyze_project_graph_phased;
+use skeplib::ir::{FunctionId, GlobalId, IrInterpreter, IrValue};
+use skeplib::lint::LintConfig;
+use skeplib::parser::Parser;
+use skeplib::resolver::{ModuleGraph, check_capabilities, resolve_project};
 
-use crate::cli::{EXIT_CODEGEN, EXIT_IO, EXIT_OK, EXIT_PARSE, EXIT_RESOLVE, EXIT_SEMA};
-use crate::output::{print_diag, print_resolve_errors};
+use crate::output::{print_diag, print_error, print_resolve_errors};
+
+static LINTS: OnceLock<LintConfig> = OnceLock::new();
+
+/// Lint levels from `--allow`, `--warn`, and `--deny`, used by every frontend run.
+pub fn init_lints(config: LintConfig) {
+    let _ = LINTS.set(config);
+}
 
 pub fn check_file(path: &str) -> Result<i32, String> {
-    let graph = match resolve_project_or_report(path) {
-        Ok(graph) => graph,
-        Err(code) => return Ok(code),
-    };
-    match analyze_project_graph_phased(&graph) {
-        Ok((_sema, parse_diags, sema_diags)) => {
-            if parse_diags.is_empty() && sema_diags.is_empty() {
-                println!("ok: {path}");
-                return Ok(EXIT_OK as i32);
-            }
-            if !parse_diags.is_empty() {
-                for d in parse_diags.as_slice() {
-                    print_diag("parse", d);
-                }
-                return Ok(EXIT_PARSE as i32);
-            }
-            for d in sema_diags.as_slice() {
-                print_diag("sema", d);
-            }
-            Ok(EXIT_SEMA as i32)
-        }
-        Err(errs) => {
-            if has_io_resolve_error(&errs) {
-                print_resolve_errors(&errs);
-                return Ok(EXIT_IO as i32);
-            }
-            print_resolve_errors(&errs);
-            Ok(EXIT_RESOLVE as i32)
-        }
+    let output = run_frontend(Compiler::entry(path));
+    if let Some(graph) = &output.graph {
+        write_symbol_index(Path::new(path), graph);
     }
+    check_result(&output, &format!("ok: {path}"))
+}
+
+/// `skepac check --all <dir>`: every `.sk` file under `dir` is a root of one shared
+/// graph, so library modules no entry imports are checked too.
+pub fn check_directory(dir: &str) -> Result<i32, String> {
+    let output = run_frontend(Compiler::directory(dir));
+    let modules = output.graph.as_ref().map_or(0, |graph| graph.modules.len());
+    check_result(&output, &format!("ok: {dir} ({modules} modules)"))
+}
+
+fn check_result(output: &CompileOutput, success: &str) -> Result<i32, String> {
+    if let Some(code) = failure_exit_code(output) {
+        return Ok(code);
+    }
+    println!("{success}");
+    Ok(ExitReason::Ok.code())
+}
+
+/// Runs `compiler` with diagnostics printed as each phase reports them.
+fn run_frontend(compiler: Compiler) -> CompileOutput {
+    compiler
+        .lints(LINTS.get().cloned().unwrap_or_default())
+        .diagnostic_sink(print_driver_diag)
+        .run()
+}
+
+fn print_driver_diag(diag: &DriverDiagnostic) {
+    match diag {
+        DriverDiagnostic::Resolve(err) => print_resolve_errors(std::slice::from_ref(err)),
+        DriverDiagnostic::Sema(d) => print_diag("sema", d),
+        DriverDiagnostic::Codegen(message) => print_error("E-CODEGEN", "codegen", message),
+    }
+}
+
+fn failure_exit_code(output: &CompileOutput) -> Option<i32> {
+    output.failed?;
+    Some(ExitReason::of_output(output).code())
 }
 
 pub fn build_object_file(input: &str, output: &str) -> Result<i32, String> {
@@ -68,7 +93,7 @@ pub fn build_object_file(input: &str, output: &str) -> Result<i32, String> {
         timings.record("copy_cached_object", copy_start.elapsed());
         println!("built object (cached): {output}");
         timings.finish_and_print();
-        return Ok(EXIT_OK as i32);
+        return Ok(ExitReason::Ok.code());
     }
     let lower_start = Instant::now();
     let program = match compile_project_graph_or_report(&graph, input) {
@@ -86,7 +111,7 @@ pub fn build_object_file(input: &str, output: &str) -> Result<i32, String> {
         timings.record("reuse_cached_ir_object", copy_start.elapsed());
         println!("built object (cached ir): {output}");
         timings.finish_and_print();
-        return Ok(EXIT_OK as i32);
+        return Ok(ExitReason::Ok.code());
     }
     if let Some(parent) = cache_object.parent() {
         fs::create_dir_all(parent).map_err(|err| err.to_string())?;
@@ -96,8 +121,8 @@ pub fn build_object_file(input: &str, output: &str) -> Result<i32, String> {
     }
     let codegen_start = Instant::now();
     if let Err(err) = codegen::compile_program_to_object_file(&program, &ir_cache_object) {
-        eprintln!("[E-CODEGEN][codegen] {err}");
-        return Ok(EXIT_CODEGEN as i32);
+        print_error("E-CODEGEN", "codegen", &err.to_string());
+        return Ok(ExitReason::Codegen.code());
     }
     timings.record("object_codegen", codegen_start.elapsed());
     write_object_identity(&ir_cache_object, &ir_fingerprint);
@@ -108,16 +133,19 @@ pub fn build_object_file(input: &str, output: &str) -> Result<i32, String> {
     timings.record("copy_output", copy_start.elapsed());
     println!("built object: {output}");
     timings.finish_and_print();
-    Ok(EXIT_OK as i32)
+    Ok(ExitReason::Ok.code())
 }
 
-pub fn build_native_file(input: &str, output: &str) -> Result<i32, String> {
+pub fn build_native_file(input: &str, output: &str, denied: &[String]) -> Result<i32, String> {
     let mut timings = BuildTimings::new("build-native");
     let phase_start = Instant::now();
     let graph = match load_frontend_valid_graph(input) {
         Ok(graph) => graph,
         Err(code) => return Ok(code),
     };
+    if let Err(code) = check_capabilities_or_report(&graph, denied) {
+        return Ok(code);
+    }
     timings.record("frontend", phase_start.elapsed());
     let input_path = Path::new(input);
     let output_path = Path::new(output);
@@ -154,8 +182,8 @@ pub fn build_native_file(input: &str, output: &str) -> Result<i32, String> {
 
     let fingerprint_start = Instant::now();
     let Some(runtime_inputs) = runtime_link_inputs() else {
-        eprintln!("[E-CODEGEN][codegen] native runtime library missing");
-        return Ok(EXIT_CODEGEN as i32);
+        print_error("E-CODEGEN", "codegen", "native runtime library missing");
+        return Ok(ExitReason::Codegen.code());
     };
     let artifact_fingerprint = if let Some(ir_identity) = &ir_artifact_identity {
         native_ir_artifact_fingerprint(ir_identity, &runtime_inputs)
@@ -170,7 +198,7 @@ pub fn build_native_file(input: &str, output: &str) -> Result<i32, String> {
         codegen::sync_runtime_sidecars_for_output(output_path).map_err(|err| err.to_string())?;
         println!("built native (cached): {output}");
         timings.finish_and_print();
-        return Ok(EXIT_OK as i32);
+        return Ok(ExitReason::Ok.code());
     }
 
     let cached_native = cached_native_path(input_path, &artifact_fingerprint);
@@ -182,24 +210,30 @@ pub fn build_native_file(input: &str, output: &str) -> Result<i32, String> {
         timings.record("restore_cached_link", copy_start.elapsed());
         println!("built native (cached link): {output}");
         timings.finish_and_print();
-        return Ok(EXIT_OK as i32);
+        return Ok(ExitReason::Ok.code());
     }
 
     if let Some(program) = lowered_program {
         let codegen_start = Instant::now();
-        prepare_output_path(output_path).map_err(|err| err.to_string())?;
-        if let Err(err) = codegen::compile_program_to_executable(&program, output_path) {
-            eprintln!("[E-CODEGEN][codegen] {err}");
-            return Ok(EXIT_CODEGEN as i32);
+        let staging = staging_path(output_path);
+        prepare_output_path(&staging).map_err(|err| err.to_string())?;
+        if let Err(err) = codegen::compile_program_to_executable(&program, &staging) {
+            let _ = fs::remove_file(&staging);
+            print_error("E-CODEGEN", "codegen", &err.to_string());
+            return Ok(ExitReason::Codegen.code());
         }
+        commit_staged_output(&staging, output_path).map_err(|err| err.to_string())?;
         timings.record("native_codegen", codegen_start.elapsed());
     } else {
         let link_start = Instant::now();
-        prepare_output_path(output_path).map_err(|err| err.to_string())?;
-        if let Err(err) = codegen::link_object_file_to_executable(&object_for_build, output_path) {
-            eprintln!("[E-CODEGEN][codegen] {err}");
-            return Ok(EXIT_CODEGEN as i32);
+        let staging = staging_path(output_path);
+        prepare_output_path(&staging).map_err(|err| err.to_string())?;
+        if let Err(err) = codegen::link_object_file_to_executable(&object_for_build, &staging) {
+            let _ = fs::remove_file(&staging);
+            print_error("E-CODEGEN", "codegen", &err.to_string());
+            return Ok(ExitReason::Codegen.code());
         }
+        commit_staged_output(&staging, output_path).map_err(|err| err.to_string())?;
         timings.record("native_link", link_start.elapsed());
     }
     let copy_start = Instant::now();
@@ -212,7 +246,7 @@ pub fn build_native_file(input: &str, output: &str) -> Result<i32, String> {
         println!("built native: {output}");
     }
     timings.finish_and_print();
-    Ok(EXIT_OK as i32)
+    Ok(ExitReason::Ok.code())
 }
 
 fn build_native_multi_module(
@@ -237,8 +271,8 @@ fn build_native_multi_module(
     let runtime_inputs = match runtime_link_inputs() {
         Some(inputs) => inputs,
         None => {
-            eprintln!("[E-CODEGEN][codegen] native runtime library missing");
-            return Ok(EXIT_CODEGEN as i32);
+            print_error("E-CODEGEN", "codegen", "native runtime library missing");
+            return Ok(ExitReason::Codegen.code());
         }
     };
     timings.record("partition_plan", partition_start.elapsed());
@@ -257,8 +291,8 @@ fn build_native_multi_module(
         ) {
             Ok(ir) => ir,
             Err(err) => {
-                eprintln!("[E-CODEGEN][codegen] {err}");
-                return Ok(EXIT_CODEGEN as i32);
+                print_error("E-CODEGEN", "codegen", &err.to_string());
+                return Ok(ExitReason::Codegen.code());
             }
         };
         let fingerprint = text_fingerprint(&llvm_ir);
@@ -276,8 +310,8 @@ fn build_native_multi_module(
             fs::create_dir_all(parent).map_err(|err| err.to_string())?;
         }
         if let Err(err) = codegen::compile_llvm_ir_to_object_file(&llvm_ir, &cache_object) {
-            eprintln!("[E-CODEGEN][codegen] {err}");
-            return Ok(EXIT_CODEGEN as i32);
+            print_error("E-CODEGEN", "codegen", &err.to_string());
+            return Ok(ExitReason::Codegen.code());
         }
         compiled += compile_start.elapsed();
         object_paths.push(cache_object);
@@ -299,7 +333,7 @@ fn build_native_multi_module(
         codegen::sync_runtime_sidecars_for_output(output_path).map_err(|err| err.to_string())?;
         println!("built native (cached): {}", output_path.display());
         timings.finish_and_print();
-        return Ok(EXIT_OK as i32);
+        return Ok(ExitReason::Ok.code());
     }
 
     let cached_native = cached_native_path(input_path, &artifact_fingerprint);
@@ -311,15 +345,18 @@ fn build_native_multi_module(
         timings.record("restore_cached_link", copy_start.elapsed());
         println!("built native (cached link): {}", output_path.display());
         timings.finish_and_print();
-        return Ok(EXIT_OK as i32);
+        return Ok(ExitReason::Ok.code());
     }
 
     let link_start = Instant::now();
-    prepare_output_path(output_path).map_err(|err| err.to_string())?;
-    if let Err(err) = codegen::link_object_files_to_executable(&object_paths, output_path) {
-        eprintln!("[E-CODEGEN][codegen] {err}");
-        return Ok(EXIT_CODEGEN as i32);
+    let staging = staging_path(output_path);
+    prepare_output_path(&staging).map_err(|err| err.to_string())?;
+    if let Err(err) = codegen::link_object_files_to_executable(&object_paths, &staging) {
+        let _ = fs::remove_file(&staging);
+        print_error("E-CODEGEN", "codegen", &err.to_string());
+        return Ok(ExitReason::Codegen.code());
     }
+    commit_staged_output(&staging, output_path).map_err(|err| err.to_string())?;
     timings.record("native_link", link_start.elapsed());
 
     let copy_start = Instant::now();
@@ -328,7 +365,7 @@ fn build_native_multi_module(
     timings.record("store_cached_link", copy_start.elapsed());
     println!("built native (partitioned): {}", output_path.display());
     timings.finish_and_print();
-    Ok(EXIT_OK as i32)
+    Ok(ExitReason::Ok.code())
 }
 
 pub fn build_llvm_ir_file(input: &str, output: &str) -> Result<i32, String> {
@@ -340,37 +377,137 @@ pub fn build_llvm_ir_file(input: &str, output: &str) -> Result<i32, String> {
         Ok(program) => program,
         Err(code) => return Ok(code),
     };
-    if let Err(err) = codegen::write_program_llvm_ir(&program, Path::new(output)) {
-        eprintln!("[E-CODEGEN][codegen] {err}");
-        return Ok(EXIT_CODEGEN as i32);
+    let output_path = Path::new(output);
+    let staging = staging_path(output_path);
+    let written = prepare_output_path(&staging)
+        .map_err(codegen::CodegenError::from)
+        .and_then(|()| codegen::write_program_llvm_ir(&program, &staging))
+        .and_then(|()| commit_staged_output(&staging, output_path).map_err(Into::into));
+    if let Err(err) = written {
+        let _ = fs::remove_file(&staging);
+        print_error("E-CODEGEN", "codegen", &err.to_string());
+        return Ok(ExitReason::Codegen.code());
     }
     println!("built llvm ir: {output}");
-    Ok(EXIT_OK as i32)
+    Ok(ExitReason::Ok.code())
 }
 
-pub fn run_native_file(input: &str) -> Result<i32, String> {
+pub fn doc_file(input: &str, outdir: &str, format: DocFormat) -> Result<i32, String> {
     let graph = match load_frontend_valid_graph(input) {
         Ok(graph) => graph,
         Err(code) => return Ok(code),
     };
-    let program = match compile_project_graph_or_report(&graph, input) {
-        Ok(program) => program,
+    let modules = match docgen::collect_docs(&graph) {
+        Ok(modules) => modules,
+        Err(errs) => {
+            print_resolve_errors(&errs);
+            return Ok(ExitReason::Resolve.code());
+        }
+    };
+    let outdir = Path::new(outdir);
+    fs::create_dir_all(outdir).map_err(|err| err.to_string())?;
+    for module in &modules {
+        let page = outdir.join(format!("{}.{}", module.id, format.extension()));
+        fs::write(&page, module.render(format)).map_err(|err| err.to_string())?;
+    }
+    println!(
+        "built docs: {} ({} module{})",
+        outdir.display(),
+        modules.len(),
+        if modules.len() == 1 { "" } else { "s" }
+    );
+    Ok(ExitReason::Ok.code())
+}
+
+/// `skepac parse`: dumps the parse tree of one file. The file is parsed as part of
+/// its project when that resolves, so operators it imports keep their precedence;
+/// otherwise it is parsed on its own.
+pub fn parse_file(input: &str, json: bool) -> Result<i32, String> {
+    let path = Path::new(input);
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(err) => {
+            print_error(
+                "E-MOD-IO",
+                "resolve",
+                &format!("Failed to read {input}: {err}"),
+            );
+            return Ok(ExitReason::Io.code());
+        }
+    };
+    let program = match project_program(path) {
+        Some(program) => program,
+        None => {
+            let (program, diags) = Parser::parse_source(&source);
+            if !diags.is_empty() {
+                for d in diags.as_slice() {
+                    print_diag("parse", &d.clone().with_path(path));
+                }
+                return Ok(ExitReason::Parse.code());
+            }
+            program
+        }
+    };
+    if json {
+        let tree = serde_json::json!({
+            "file": input,
+            "declarations": collect_symbols(&program, &source),
+            "program": program,
+        });
+        println!("{tree}");
+    } else {
+        println!("{program:#?}");
+    }
+    Ok(ExitReason::Ok.code())
+}
+
+fn project_program(entry: &Path) -> Option<Program> {
+    let graph = resolve_project(entry).ok()?;
+    let entry = entry.canonicalize().ok()?;
+    graph
+        .modules
+        .into_values()
+        .find(|unit| unit.path.canonicalize().is_ok_and(|p| p == entry))
+        .map(|unit| unit.program)
+}
+
+pub fn run_native_file(input: &str, denied: &[String], entry_fn: &str) -> Result<i32, String> {
+    let graph = match load_frontend_valid_graph(input) {
+        Ok(graph) => graph,
         Err(code) => return Ok(code),
     };
+    if let Err(code) = check_capabilities_or_report(&graph, denied) {
+        return Ok(code);
+    }
+    let program = match ir::lowering::compile_project_graph_for_entry_fn(
+        &graph,
+        Path::new(input),
+        entry_fn,
+    ) {
+        Ok(program) => program,
+        Err(message) => {
+            print_error("E-CODEGEN", "codegen", &message);
+            return Ok(ExitReason::Codegen.code());
+        }
+    };
     let exe_path = temp_native_path();
     if let Some(parent) = exe_path.parent() {
         fs::create_dir_all(parent).map_err(|err| err.to_string())?;
     }
     let _cleanup = TempPathGuard::new(exe_path.clone());
     if let Err(err) = codegen::compile_program_to_executable(&program, &exe_path) {
-        eprintln!("[E-CODEGEN][codegen] {err}");
-        return Ok(EXIT_CODEGEN as i32);
+        print_error("E-CODEGEN", "codegen", &err.to_string());
+        return Ok(ExitReason::Codegen.code());
     }
-    let output = Command::new(&exe_path).output();
+    let output = Command::new(&exe_path).stdin(Stdio::inherit()).output();
     let output = match output {
         Ok(output) => output,
         Err(err) => {
-            eprintln!("[E-RUNTIME][runtime] failed to run native executable: {err}");
+            print_error(
+                "E-RUNTIME",
+                "runtime",
+                &format!("failed to run native executable: {err}"),
+            );
             return Ok(1);
         }
     };
@@ -382,66 +519,99 @@ pub fn run_native_file(input: &str) -> Result<i32, String> {
     }
     let status = output.status;
     let Some(code) = status.code() else {
-        eprintln!("[E-RUNTIME][runtime] native executable terminated without an exit code");
+        print_error(
+            "E-RUNTIME",
+            "runtime",
+            "native executable terminated without an exit code",
+        );
         return Ok(1);
     };
     Ok(code)
 }
 
-fn load_frontend_valid_graph(input: &str) -> Result<ModuleGraph, i32> {
-    let graph = resolve_project_or_report(input)?;
-    match analyze_project_graph_phased(&graph) {
-        Ok((_sema, parse_diags, sema_diags)) => {
-            if !parse_diags.is_empty() {
-                for d in parse_diags.as_slice() {
-                    print_diag("parse", d);
-                }
-                return Err(EXIT_PARSE as i32);
-            }
-            if !sema_diags.is_empty() {
-                for d in sema_diags.as_slice() {
-                    print_diag("sema", d);
-                }
-                return Err(EXIT_SEMA as i32);
-            }
-            Ok(graph)
+/// `skepac test <entry.sk>`: runs every `@test` function of the project through
+/// the IR interpreter, each with freshly initialized globals. A test passes when
+/// it returns without a runtime error and, if it returns `Int`, returns `0`.
+pub fn test_file(input: &str) -> Result<i32, String> {
+    let graph = match load_frontend_valid_graph(input) {
+        Ok(graph) => graph,
+        Err(code) => return Ok(code),
+    };
+    let mut module_ids = graph.modules.keys().collect::<Vec<_>>();
+    module_ids.sort();
+    let tests = module_ids
+        .into_iter()
+        .flat_map(|id| {
+            graph.modules[id]
+                .program
+                .functions
+                .iter()
+                .filter(|f| f.has_attribute("test"))
+                .map(move |f| format!("{id}::{}", f.name))
+        })
+        .collect::<Vec<_>>();
+    if tests.is_empty() {
+        println!("no @test functions in {input}");
+        return Ok(ExitReason::Ok.code());
+    }
+    let program = match ir::lowering::compile_project_graph_without_entry(&graph) {
+        Ok(program) => program,
+        Err(message) => {
+            print_error("E-CODEGEN", "codegen", &message);
+            return Ok(ExitReason::Codegen.code());
         }
-        Err(errs) => {
-            if has_io_resolve_error(&errs) {
-                print_resolve_errors(&errs);
-                return Err(EXIT_IO as i32);
+    };
+    let mut failed = 0usize;
+    for name in &tests {
+        let failure = match IrInterpreter::new(&program).call(name, Vec::new()) {
+            Ok(IrValue::Int(code)) if code != 0 => Some(format!("returned {code}")),
+            Ok(_) => None,
+            Err(err) => Some(err.to_string()),
+        };
+        match failure {
+            None => println!("test {name} ... ok"),
+            Some(reason) => {
+                failed += 1;
+                println!("test {name} ... FAILED: {reason}");
             }
-            print_resolve_errors(&errs);
-            Err(EXIT_RESOLVE as i32)
         }
     }
+    let status = if failed == 0 { "ok" } else { "FAILED" };
+    println!(
+        "test result: {status}. {} passed; {failed} failed",
+        tests.len() - failed
+    );
+    if failed > 0 {
+        return Ok(ExitReason::TestFailed.code());
+    }
+    Ok(ExitReason::Ok.code())
 }
 
-fn has_io_resolve_error(errs: &[ResolveError]) -> bool {
-    errs.iter().any(|err| err.code == "E-MOD-IO")
+fn load_frontend_valid_graph(input: &str) -> Result<ModuleGraph, i32> {
+    let output = run_frontend(Compiler::entry(input));
+    if let Some(code) = failure_exit_code(&output) {
+        return Err(code);
+    }
+    Ok(output
+        .graph
+        .expect("a successful frontend run keeps its graph"))
 }
 
-fn resolve_project_or_report(input: &str) -> Result<ModuleGraph, i32> {
-    match resolve_project(Path::new(input)) {
-        Ok(graph) => Ok(graph),
-        Err(errs) => {
-            if has_io_resolve_error(&errs) {
-                print_resolve_errors(&errs);
-                Err(EXIT_IO as i32)
-            } else {
-                print_resolve_errors(&errs);
-                Err(EXIT_RESOLVE as i32)
-            }
-        }
+fn check_capabilities_or_report(graph: &ModuleGraph, denied: &[String]) -> Result<(), i32> {
+    let errs = check_capabilities(graph, denied);
+    if errs.is_empty() {
+        return Ok(());
     }
+    print_resolve_errors(&errs);
+    Err(ExitReason::Resolve.code())
 }
 
 fn compile_project_graph_or_report(graph: &ModuleGraph, input: &str) -> Result<ir::IrProgram, i32> {
     match ir::lowering::compile_project_graph_after_frontend(graph, Path::new(input)) {
         Ok(program) => Ok(program),
         Err(message) => {
-            eprintln!("[E-CODEGEN][codegen] {message}");
-            Err(EXIT_CODEGEN as i32)
+            print_error("E-CODEGEN", "codegen", &message);
+            Err(ExitReason::Codegen.code())
         }
     }
 }
@@ -453,8 +623,8 @@ fn compile_project_graph_unoptimized_or_report(
     match ir::lowering::compile_project_graph_after_frontend_unoptimized(graph, Path::new(input)) {
         Ok(program) => Ok(program),
         Err(message) => {
-            eprintln!("[E-CODEGEN][codegen] {message}");
-            Err(EXIT_CODEGEN as i32)
+            print_error("E-CODEGEN", "codegen", &message);
+            Err(ExitReason::Codegen.code())
         }
     }
 }
@@ -634,14 +804,35 @@ fn prepare_output_path(path: &Path) -> std::io::Result<()> {
 }
 
 fn materialize_cached_artifact(source: &Path, destination: &Path) -> std::io::Result<()> {
-    prepare_output_path(destination)?;
-    match fs::hard_link(source, destination) {
-        Ok(()) => Ok(()),
-        Err(_) => {
-            fs::copy(source, destination)?;
-            Ok(())
-        }
+    let staging = staging_path(destination);
+    prepare_output_path(&staging)?;
+    if fs::hard_link(source, &staging).is_err()
+        && let Err(err) = fs::copy(source, &staging)
+    {
+        let _ = fs::remove_file(&staging);
+        return Err(err);
     }
+    commit_staged_output(&staging, destination)
+}
+
+/// Hidden sibling of `destination` that a build writes first, keeping the
+/// extension so toolchains treat it like the real output.
+fn staging_path(destination: &Path) -> PathBuf {
+    let stem = destination
+        .file_stem()
+        .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+    let extension = destination
+        .extension()
+        .map_or_else(String::new, |ext| format!(".{}", ext.to_string_lossy()));
+    destination.with_file_name(format!(".{stem}.skepac-tmp{extension}"))
+}
+
+/// Moves a finished staged output over `destination` in one rename, so a watcher
+/// or a running program never sees a half-written artifact.
+fn commit_staged_output(staging: &Path, destination: &Path) -> std::io::Result<()> {
+    fs::rename(staging, destination).inspect_err(|_| {
+        let _ = fs::remove_file(staging);
+    })
 }
 
 fn store_cached_artifact(source: &Path, cache_path: &Path) -> std::io::Result<()> {
@@ -722,6 +913,19 @@ fn build_output_cache_path(output: &Path) -> PathBuf {
         .join(format!("{safe_name}_{:016x}.fingerprint", hasher.finish()))
 }
 
+/// Persists the project symbol index next to the other cache artifacts so
+/// editor tooling can answer queries before a full re-analysis finishes.
+/// Index write failures never fail the check itself.
+fn write_symbol_index(input: &Path, graph: &ModuleGraph) {
+    if let Ok(index) = SymbolIndex::build(graph) {
+        let _ = index.write_to(&symbol_index_path(input));
+    }
+}
+
+fn symbol_index_path(input: &Path) -> PathBuf {
+    cache_root_for_input(input).join("symbols.idx")
+}
+
 fn cached_object_path(input: &Path, source_fingerprint: &str) -> PathBuf {
     cache_root_for_input(input)
         .join("objects")
@@ -906,6 +1110,7 @@ fn canonicalize_llvm_ssa_names(text: &str) -> String {
 struct BuildTimings {
     label: &'static str,
     enabled: bool,
+    json: bool,
     started: Instant,
     phases: Vec<(&'static str, u128)>,
     counters: Vec<(&'static str, usize)>,
@@ -913,9 +1118,11 @@ struct BuildTimings {
 
 impl BuildTimings {
     fn new(label: &'static str) -> Self {
+        let setting = std::env::var_os("SKEPAC_TIMINGS");
         Self {
             label,
-            enabled: std::env::var_os("SKEPAC_TIMINGS").is_some(),
+            enabled: setting.is_some(),
+            json: setting.is_some_and(|value| value == "json"),
             started: Instant::now(),
             phases: Vec::new(),
             counters: Vec::new(),
@@ -938,6 +1145,10 @@ impl BuildTimings {
         if !self.enabled {
             return;
         }
+        if self.json {
+            println!("{}", self.to_json(self.started.elapsed().as_micros()));
+            return;
+        }
         for (phase, micros) in &self.phases {
             println!("timing[{}] {}={}us", self.label, phase, micros);
         }
@@ -950,6 +1161,26 @@ impl BuildTimings {
             self.started.elapsed().as_micros()
         );
     }
+
+    /// One JSON object per build so timing logs can be collected line by line.
+    fn to_json(&self, total_micros: u128) -> serde_json::Value {
+        let phases = self
+            .phases
+            .iter()
+            .map(|(phase, micros)| serde_json::json!({ "phase": phase, "us": micros }))
+            .collect::<Vec<_>>();
+        let counters = self
+            .counters
+            .iter()
+            .map(|(name, value)| (name.to_string(), serde_json::json!(value)))
+            .collect::<serde_json::Map<_, _>>();
+        serde_json::json!({
+            "timings": self.label,
+            "phases": phases,
+            "counters": counters,
+            "total_us": total_micros,
+        })
+    }
 }
 
 struct TempPathGuard(PathBuf);
@@ -984,12 +1215,12 @@ fn temp_native_path() -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::{
-        canonicalize_llvm_ssa_names, materialize_cached_artifact, prepare_output_path,
-        store_cached_artifact, text_fingerprint,
+        BuildTimings, canonicalize_llvm_ssa_names, materialize_cached_artifact,
+        prepare_output_path, staging_path, store_cached_artifact, text_fingerprint,
     };
     use std::fs;
-    use std::path::PathBuf;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use std::path::{Path, PathBuf};
+    use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
     fn temp_test_dir(name: &str) -> PathBuf {
         let nanos = SystemTime::now()
@@ -1016,10 +1247,20 @@ mod tests {
 
         assert_eq!(fs::read(&destination).expect("destination read"), b"fresh");
         assert_eq!(fs::read(&cache).expect("cache read"), b"fresh");
+        assert!(!staging_path(&destination).exists());
 
         let _ = fs::remove_dir_all(dir);
     }
 
+    #[test]
+    fn staging_path_is_a_hidden_sibling_with_the_same_extension() {
+        assert_eq!(
+            staging_path(Path::new("out/app.exe")),
+            Path::new("out/.app.skepac-tmp.exe")
+        );
+        assert_eq!(staging_path(Path::new("app")), Path::new(".app.skepac-tmp"));
+    }
+
     #[test]
     fn prepare_output_path_removes_existing_file() {
         let dir = temp_test_dir("prepare_output_path");
@@ -1042,4 +1283,20 @@ mod tests {
         );
         assert_eq!(text_fingerprint(first), text_fingerprint(second));
     }
+
+    #[test]
+    fn build_timings_json_keeps_phase_order_and_counters() {
+        let timings = BuildTimings {
+            label: "build-native",
+            enabled: true,
+            json: true,
+            started: Instant::now(),
+            phases: vec![("frontend", 12), ("native_link", 340)],
+            counters: vec![("modules", 3)],
+        };
+        assert_eq!(
+            timings.to_json(400).to_string(),
+            r#"{"counters":{"modules":3},"phases":[{"phase":"frontend","us":12},{"phase":"native_link","us":340}],"timings":"build-native","total_us":400}"#
+        );
+    }
 }
@@ -1,13 +1,16 @@
 mod cli;
 mod commands;
 mod output;
+mod watch;
+
+use skeplib::exit_codes::ExitReason;
 
 fn main() {
     match cli::run() {
         Ok(code) => std::process::exit(code),
         Err(message) => {
             eprintln!("{message}");
-            std::process::exit(cli::EXIT_USAGE as i32)
+            std::process::exit(ExitReason::Usage.code())
         }
     }
 }
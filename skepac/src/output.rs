@@ -1,61 +1,205 @@
-use skeplib::diagnostic::Diagnostic;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+use skeplib::diagnostic::{Diagnostic, DiagnosticLevel, Span, render_snippet};
 use skeplib::resolver::ResolveError;
 
+static COLOR: AtomicBool = AtomicBool::new(false);
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Colors are on when stderr is a terminal, `NO_COLOR` is unset or empty, and
+/// `--no-color` was not passed. JSON output is never colored.
+pub fn init(no_color_flag: bool, json: bool) {
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let enabled = !json && !no_color_flag && !no_color_env && std::io::stderr().is_terminal();
+    COLOR.store(enabled, Ordering::Relaxed);
+    JSON.store(json, Ordering::Relaxed);
+}
+
+fn color() -> bool {
+    COLOR.load(Ordering::Relaxed)
+}
+
+fn json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
 pub fn print_diag(phase: &str, d: &Diagnostic) {
+    let code = d.code.unwrap_or_else(|| phase_code(phase));
+    if json() {
+        let severity = match d.level {
+            DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+        };
+        emit_json(&JsonDiag {
+            code,
+            severity,
+            phase,
+            file: d.path.as_deref().map(|p| p.display().to_string()),
+            span: (d.span.line > 0).then(|| JsonSpan::from(d.span)),
+            message: &d.message,
+            suggestion: d.suggestion.as_deref(),
+            labels: d
+                .labels
+                .iter()
+                .map(|l| JsonLabel {
+                    span: JsonSpan::from(l.span),
+                    message: &l.message,
+                })
+                .collect(),
+        });
+        return;
+    }
+    let tag = match d.level {
+        DiagnosticLevel::Error => tag(code, phase),
+        DiagnosticLevel::Warning => format!("{} warning:", warning_tag(code, phase)),
+    };
     if let Some(path) = &d.path {
         if d.span.line > 0 && d.span.col > 0 {
             eprintln!(
-                "[{}][{}] {}:{}:{}: {}",
-                phase_code(phase),
-                phase,
+                "{tag} {}:{}:{}: {}",
                 path.display(),
                 d.span.line,
                 d.span.col,
                 d.message
             );
         } else {
-            eprintln!(
-                "[{}][{}] {}: {}",
-                phase_code(phase),
-                phase,
-                path.display(),
-                d.message
-            );
+            eprintln!("{tag} {}: {}", path.display(), d.message);
+        }
+        if let Some(source) = read_source(path) {
+            eprint!("{}", d.render_snippet(&source, color()));
         }
     } else if d.span.line > 0 && d.span.col > 0 {
-        eprintln!(
-            "[{}][{}] {}:{}: {}",
-            phase_code(phase),
-            phase,
-            d.span.line,
-            d.span.col,
-            d.message
-        );
+        eprintln!("{tag} {}:{}: {}", d.span.line, d.span.col, d.message);
     } else {
-        eprintln!("[{}][{}] {}", phase_code(phase), phase, d.message);
+        eprintln!("{tag} {}", d.message);
     }
 }
 
 pub fn print_resolve_errors(errs: &[ResolveError]) {
     for e in errs {
-        if let Some(path) = &e.path {
-            match (e.line, e.col) {
-                (Some(line), Some(col)) => eprintln!(
-                    "[{}][resolve] {}:{}:{}: {}",
-                    e.code,
-                    path.display(),
+        if json() {
+            emit_json(&JsonDiag {
+                code: e.code,
+                severity: "error",
+                phase: "resolve",
+                file: e.path.as_deref().map(|p| p.display().to_string()),
+                span: e.line.zip(e.col).map(|(line, col)| JsonSpan {
                     line,
                     col,
-                    e.message
-                ),
-                _ => eprintln!("[{}][resolve] {}: {}", e.code, path.display(), e.message),
+                    start: None,
+                    end: None,
+                }),
+                message: &e.message,
+                suggestion: e.suggestion.as_deref(),
+                labels: Vec::new(),
+            });
+            continue;
+        }
+        let tag = tag(e.code, "resolve");
+        if let Some(path) = &e.path {
+            match (e.line, e.col) {
+                (Some(line), Some(col)) => {
+                    eprintln!("{tag} {}:{}:{}: {}", path.display(), line, col, e.message);
+                    if let Some(source) = read_source(path) {
+                        let span = Span::new(0, 1, line, col);
+                        eprint!("{}", render_snippet(&source, span, &[], color()));
+                    }
+                }
+                _ => eprintln!("{tag} {}: {}", path.display(), e.message),
             }
         } else {
-            eprintln!("[{}][resolve] {}", e.code, e.message);
+            eprintln!("{tag} {}", e.message);
+        }
+    }
+}
+
+/// Reports an error that has no source location, such as a codegen failure.
+pub fn print_error(code: &str, phase: &str, message: &str) {
+    if json() {
+        emit_json(&JsonDiag {
+            code,
+            severity: "error",
+            phase,
+            file: None,
+            span: None,
+            message,
+            suggestion: None,
+            labels: Vec::new(),
+        });
+    } else {
+        eprintln!("{} {message}", tag(code, phase));
+    }
+}
+
+/// One diagnostic as a single-line JSON object for `--format json`.
+#[derive(Serialize)]
+struct JsonDiag<'a> {
+    code: &'a str,
+    severity: &'a str,
+    phase: &'a str,
+    file: Option<String>,
+    span: Option<JsonSpan>,
+    message: &'a str,
+    suggestion: Option<&'a str>,
+    labels: Vec<JsonLabel<'a>>,
+}
+
+/// 1-based line and column plus source offsets; resolver errors only know
+/// the line and column.
+#[derive(Serialize)]
+struct JsonSpan {
+    line: usize,
+    col: usize,
+    start: Option<usize>,
+    end: Option<usize>,
+}
+
+impl From<Span> for JsonSpan {
+    fn from(span: Span) -> Self {
+        Self {
+            line: span.line,
+            col: span.col,
+            start: Some(span.start),
+            end: Some(span.end),
         }
     }
 }
 
+#[derive(Serialize)]
+struct JsonLabel<'a> {
+    span: JsonSpan,
+    message: &'a str,
+}
+
+fn emit_json(diag: &JsonDiag<'_>) {
+    let line = serde_json::to_string(diag).expect("diagnostics serialize to JSON");
+    eprintln!("{line}");
+}
+
+fn tag(code: &str, phase: &str) -> String {
+    if color() {
+        format!("\x1b[1;31m[{code}]\x1b[0m[{phase}]")
+    } else {
+        format!("[{code}][{phase}]")
+    }
+}
+
+fn warning_tag(code: &str, phase: &str) -> String {
+    if color() {
+        format!("\x1b[1;33m[{code}]\x1b[0m[{phase}]")
+    } else {
+        format!("[{code}][{phase}]")
+    }
+}
+
+fn read_source(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok()
+}
+
 fn phase_code(phase: &str) -> &'static str {
     match phase {
         "parse" => "E-PARSE",
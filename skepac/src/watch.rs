@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// `--watch` for the build commands: runs `build` once, then again whenever a
+/// `.sk` file under the entry's directory is added, removed, or modified. Each
+/// rebuild goes through the same object and link caches as a one-off build, so
+/// only changed partitions are recompiled. Runs until interrupted.
+pub fn watch_build(input: &str, mut build: impl FnMut() -> Result<i32, String>) -> ! {
+    let root = watch_root(Path::new(input));
+    let mut snapshot = source_snapshot(&root);
+    loop {
+        let started = Instant::now();
+        let code = match build() {
+            Ok(code) => code,
+            Err(message) => {
+                eprintln!("{message}");
+                1
+            }
+        };
+        println!(
+            "watch: build finished in {}ms (exit {code}); watching {} for changes",
+            started.elapsed().as_millis(),
+            root.display()
+        );
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = source_snapshot(&root);
+            if next != snapshot {
+                snapshot = next;
+                break;
+            }
+        }
+        println!("watch: change detected, rebuilding");
+    }
+}
+
+fn watch_root(input: &Path) -> PathBuf {
+    match input.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// Modification times of every `.sk` file under `root`, skipping hidden
+/// directories such as `.skepac-cache`.
+fn source_snapshot(root: &Path) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    let mut snapshot = BTreeMap::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if path.is_dir() {
+                if !hidden {
+                    pending.push(path);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "sk") {
+                let modified = entry.metadata().and_then(|m| m.modified()).ok();
+                snapshot.insert(path, modified);
+            }
+        }
+    }
+    snapshot
+}
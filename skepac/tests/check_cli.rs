@@ -1,7 +1,7 @@
 use std::fs;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::net::TcpListener;
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::thread;
 
 mod common;
@@ -206,6 +206,114 @@ fn ffi_test_call0_void_symbol_name() -> &'static str {
     "tzset"
 }
 
+#[test]
+fn check_reports_builtin_arity_and_argument_type_misuse() {
+    let tmp = make_temp_dir("skepac_builtin_misuse");
+    let file = write_temp_file(
+        &tmp,
+        "main.sk",
+        r#"
+import fs;
+import io;
+import os;
+fn main() -> Int {
+  io.print();
+  os.sleep(true);
+  fs.exists(1);
+  return 0;
+}
+"#,
+    );
+
+    let output = Command::new(skepac_bin())
+        .arg("check")
+        .arg(&file)
+        .output()
+        .expect("run skepac");
+    assert_cli_failure_class(&output, CliFailureClass::Sema);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_diag_code_and_message(
+        &stderr,
+        "[E-SEMA-004]",
+        "io.print expects 1 argument(s), got 0",
+    );
+    assert_diag_code_and_message(
+        &stderr,
+        "[E-SEMA-010]",
+        "os.sleep argument 1 expects Int, got Bool",
+    );
+    assert_diag_code_and_message(&stderr, "", "fs.exists argument 1 expects String, got Int");
+}
+
+#[test]
+fn check_warns_about_unreachable_code_without_failing() {
+    let tmp = make_temp_dir("skepac_unreachable");
+    let file = write_temp_file(
+        &tmp,
+        "dead.sk",
+        r#"
+fn main() -> Int {
+  return 0;
+  let dead = 1;
+}
+"#,
+    );
+
+    let output = Command::new(skepac_bin())
+        .arg("check")
+        .arg(&file)
+        .output()
+        .expect("run skepac");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stdout.contains("ok:"), "{stdout}");
+    assert!(
+        stderr.contains("[E-SEMA-021][sema] warning:"),
+        "stderr was: {stderr}"
+    );
+    assert!(
+        stderr.contains("dead.sk:4:3: Unreachable statement after `return`"),
+        "stderr was: {stderr}"
+    );
+}
+
+#[test]
+fn check_rejects_entry_main_with_invalid_signature() {
+    let tmp = make_temp_dir("skepac_main_sig");
+    let file = write_temp_file(
+        &tmp,
+        "main.sk",
+        r#"
+fn main(flag: Bool) -> String {
+  return "done";
+}
+"#,
+    );
+
+    let output = Command::new(skepac_bin())
+        .arg("check")
+        .arg(&file)
+        .output()
+        .expect("run skepac");
+    assert_cli_failure_class(&output, CliFailureClass::Sema);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("[E-SEMA-022][sema]")
+            && stderr.contains("main.sk:2:1: `main` must take no parameters, found 1")
+            && stderr.contains("`main` must return `Int` or `Void`, found `String`"),
+        "stderr was: {stderr}"
+    );
+
+    let void_main = write_temp_file(&tmp, "void.sk", "fn main() -> Void {\n  return;\n}\n");
+    let output = Command::new(skepac_bin())
+        .arg("check")
+        .arg(&void_main)
+        .output()
+        .expect("run skepac");
+    assert!(output.status.success(), "{output:?}");
+}
+
 #[test]
 fn check_valid_program_returns_zero() {
     let tmp = make_temp_dir("skepac_ok");
@@ -230,6 +338,261 @@ fn main() -> Int {
     assert!(stdout.contains("ok:"));
 }
 
+#[test]
+fn check_persists_symbol_index_even_when_sema_fails() {
+    let tmp = make_temp_dir("skepac_check_symbol_index");
+    let file = write_temp_file(
+        &tmp,
+        "main.sk",
+        r#"
+fn helper() -> Int {
+  return true;
+}
+
+fn main() -> Int {
+  return helper();
+}
+"#,
+    );
+
+    let output = Command::new(skepac_bin())
+        .arg("check")
+        .arg(&file)
+        .output()
+        .expect("run skepac");
+    assert_eq!(output.status.code(), Some(11));
+    let index = fs::read_to_string(tmp.join(".skepac-cache").join("symbols.idx"))
+        .expect("symbol index should be written");
+    assert!(index.starts_with("skepa-symbol-index v3\n"));
+    assert!(index.contains("symbol\tfn\thelper\t2\t1\n"));
+    assert!(index.contains("symbol\tfn\tmain\t6\t1\n"));
+}
+
+#[test]
+fn check_all_checks_modules_no_entry_imports() {
+    let tmp = make_temp_dir("skepac_check_all");
+    fs::create_dir_all(tmp.join("lib")).expect("create lib dir");
+    write_temp_file(
+        &tmp,
+        "lib/used.sk",
+        r#"
+fn one() -> Int {
+  return 1;
+}
+
+export { one };
+"#,
+    );
+    write_temp_file(
+        &tmp,
+        "lib/orphan.sk",
+        r#"
+fn broken() -> Int {
+  return missing;
+}
+"#,
+    );
+    let entry = write_temp_file(
+        &tmp,
+        "main.sk",
+        r#"
+from lib.used import one;
+
+fn main() -> Int {
+  return one();
+}
+"#,
+    );
+
+    let single = Command::new(skepac_bin())
+        .arg("check")
+        .arg(&entry)
+        .output()
+        .expect("run skepac check");
+    assert_eq!(single.status.code(), Some(0), "{single:?}");
+
+    let all = Command::new(skepac_bin())
+        .arg("check")
+        .arg("--all")
+        .arg(&tmp)
+        .output()
+        .expect("run skepac check --all");
+    assert_cli_failure_class(&all, CliFailureClass::Sema);
+    let stderr = String::from_utf8_lossy(&all.stderr);
+    assert!(stderr.contains("orphan.sk"), "stderr was: {stderr}");
+    assert!(
+        stderr.contains("Unknown variable `missing`"),
+        "stderr was: {stderr}"
+    );
+
+    write_temp_file(
+        &tmp,
+        "lib/orphan.sk",
+        r#"
+fn fixed() -> Int {
+  return 0;
+}
+"#,
+    );
+    let all = Command::new(skepac_bin())
+        .arg("check")
+        .arg("--all")
+        .arg(&tmp)
+        .output()
+        .expect("run skepac check --all");
+    assert_eq!(all.status.code(), Some(0), "{all:?}");
+    let stdout = String::from_utf8_lossy(&all.stdout);
+    assert!(stdout.contains("(3 modules)"), "{stdout}");
+}
+
+#[test]
+fn check_all_requires_a_directory() {
+    let output = Command::new(skepac_bin())
+        .arg("check")
+        .arg("--all")
+        .output()
+        .expect("run skepac check --all");
+    assert_cli_failure_class(&output, CliFailureClass::Usage);
+
+    let tmp = make_temp_dir("skepac_check_all_missing");
+    let output = Command::new(skepac_bin())
+        .arg("check")
+        .arg("--all")
+        .arg(tmp.join("nope"))
+        .output()
+        .expect("run skepac check --all");
+    assert_eq!(output.status.code(), Some(15), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Directory not found"), "{stderr}");
+}
+
+#[test]
+fn parse_json_dumps_the_tree_with_imported_operator_precedence() {
+    let tmp = make_temp_dir("skepac_parse_json");
+    write_temp_file(
+        &tmp,
+        "ops.sk",
+        r#"
+opr xoxo(lhs: Int, rhs: Int) -> Int precedence 9 {
+  return lhs * 10 + rhs;
+}
+
+export { xoxo };
+"#,
+    );
+    let entry = write_temp_file(
+        &tmp,
+        "main.sk",
+        r#"from ops import xoxo;
+
+fn main() -> Int {
+  return 5 `xoxo` 4 + 1;
+}
+"#,
+    );
+
+    let output = Command::new(skepac_bin())
+        .arg("parse")
+        .arg("--json")
+        .arg(&entry)
+        .output()
+        .expect("run skepac parse");
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(r#"{"col":1,"kind":"fn","line":3,"name":"main"}"#),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains(r#"{"Binary":{"left":{"CustomInfix":{"left":{"IntLit":5},"operator":"xoxo","right":{"IntLit":4}}},"op":"Add","right":{"IntLit":1}}}"#),
+        "{stdout}"
+    );
+
+    let broken = write_temp_file(&tmp, "broken.sk", "fn main( {");
+    let output = Command::new(skepac_bin())
+        .arg("parse")
+        .arg(&broken)
+        .arg("--json")
+        .output()
+        .expect("run skepac parse");
+    assert_cli_failure_class(&output, CliFailureClass::Parse);
+
+    let output = Command::new(skepac_bin())
+        .arg("parse")
+        .output()
+        .expect("run skepac parse");
+    assert_cli_failure_class(&output, CliFailureClass::Usage);
+}
+
+#[test]
+fn doc_writes_markdown_and_html_pages_per_module() {
+    let tmp = make_temp_dir("skepac_doc");
+    fs::create_dir_all(tmp.join("utils")).expect("create utils dir");
+    write_temp_file(
+        &tmp,
+        "utils/math.sk",
+        r#"
+/// Adds two numbers.
+fn add(a: Int, b: Int) -> Int {
+  return a + b;
+}
+
+export { add };
+"#,
+    );
+    let entry = write_temp_file(
+        &tmp,
+        "main.sk",
+        r#"
+from utils.math import add;
+
+fn main() -> Int {
+  return add(1, 2);
+}
+"#,
+    );
+
+    let out = tmp.join("docs");
+    let output = Command::new(skepac_bin())
+        .arg("doc")
+        .arg(&entry)
+        .arg(&out)
+        .output()
+        .expect("run skepac doc");
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let page = fs::read_to_string(out.join("utils.math.md")).expect("module page");
+    assert!(page.contains("fn add(a: Int, b: Int) -> Int"));
+    assert!(page.contains("Adds two numbers."));
+    let main_page = fs::read_to_string(out.join("main.md")).expect("entry page");
+    assert!(main_page.contains("This module exports nothing."));
+
+    let html_out = tmp.join("html");
+    let output = Command::new(skepac_bin())
+        .args(["doc", "--html"])
+        .arg(&entry)
+        .arg(&html_out)
+        .output()
+        .expect("run skepac doc --html");
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let page = fs::read_to_string(html_out.join("utils.math.html")).expect("html page");
+    assert!(page.contains("fn add(a: Int, b: Int) -&gt; Int"));
+}
+
+#[test]
+fn doc_reports_sema_errors_instead_of_writing_pages() {
+    let tmp = make_temp_dir("skepac_doc_sema_error");
+    let entry = write_temp_file(&tmp, "main.sk", "fn main() -> Int { return true; }\n");
+    let out = tmp.join("docs");
+    let output = Command::new(skepac_bin())
+        .arg("doc")
+        .arg(&entry)
+        .arg(&out)
+        .output()
+        .expect("run skepac doc");
+    assert_eq!(output.status.code(), Some(11), "{output:?}");
+    assert!(!out.exists());
+}
+
 #[test]
 fn shipped_examples_check_and_run_through_cli() {
     let hello = example_entry("hello/main.sk");
@@ -298,7 +661,11 @@ fn main() -> Int {
         .expect("run skepac");
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert_cli_failure_class(&output, CliFailureClass::Parse);
-    assert_diag_code_and_message(&stderr, "[E-PARSE]", "Expected `;` after return statement");
+    assert_diag_code_and_message(
+        &stderr,
+        "[E-PARSE-004]",
+        "Expected `;` after return statement",
+    );
 }
 
 #[test]
@@ -322,7 +689,171 @@ fn main() -> Int {
         .expect("run skepac");
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert_cli_failure_class(&output, CliFailureClass::Sema);
-    assert_diag_code_and_message(&stderr, "[E-SEMA][sema]", "Return type mismatch");
+    assert_diag_code_and_message(&stderr, "[E-SEMA-005][sema]", "Return type mismatch");
+}
+
+#[test]
+fn check_reports_task_isolation_errors_with_their_own_code() {
+    let tmp = make_temp_dir("skepac_task_isolation_code");
+    let file = write_temp_file(
+        &tmp,
+        "main.sk",
+        r#"
+import task;
+let hits: Int = 0;
+fn bump() -> Int {
+  hits = hits + 1;
+  return hits;
+}
+fn main() -> Int {
+  return task.join(task.spawn(bump));
+}
+"#,
+    );
+
+    let output = Command::new(skepac_bin())
+        .arg("check")
+        .arg(&file)
+        .output()
+        .expect("run skepac");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_cli_failure_class(&output, CliFailureClass::Sema);
+    assert_diag_code_and_message(&stderr, "[E-SEMA-023][sema]", "assigns global `hits`");
+}
+
+#[test]
+fn check_renders_source_snippet_with_secondary_label() {
+    let tmp = make_temp_dir("skepac_snippet");
+    let file = write_temp_file(
+        &tmp,
+        "main.sk",
+        "fn value() -> Int { return 1; }\nfn value() -> Int { return 2; }\nfn main() -> Int { return value(); }\n",
+    );
+
+    let output = Command::new(skepac_bin())
+        .args(["check", "--no-color"])
+        .arg(&file)
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("run skepac");
+    assert_cli_failure_class(&output, CliFailureClass::Sema);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains('\x1b'), "{stderr:?}");
+    assert!(
+        stderr.contains(
+            "2 | fn value() -> Int { return 2; }\n  | ^^^^^^^^\n  |\n1 | fn value() -> Int { return 1; }\n  | -------- first declared here\n"
+        ),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn check_format_json_emits_one_object_per_diagnostic() {
+    let tmp = make_temp_dir("skepac_json_diags");
+    let file = write_temp_file(
+        &tmp,
+        "main.sk",
+        "fn value() -> Int { return 1; }\nfn value() -> Int { return \"two\"; }\nfn main() -> Int { return value(); }\n",
+    );
+
+    let output = Command::new(skepac_bin())
+        .args(["check", "--format", "json"])
+        .arg(&file)
+        .output()
+        .expect("run skepac");
+    assert_eq!(output.status.code(), Some(11), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines = stderr.lines().collect::<Vec<_>>();
+    assert!(lines.len() >= 2, "{stderr}");
+    assert!(lines.iter().all(|l| l.starts_with('{') && l.ends_with('}')));
+    let path = file.display().to_string().replace('\\', "\\\\");
+    assert!(
+        lines.contains(&format!(
+            "{{\"code\":\"E-SEMA-003\",\"severity\":\"error\",\"phase\":\"sema\",\"file\":\"{path}\",\"span\":{{\"line\":2,\"col\":1,\"start\":32,\"end\":40}},\"message\":\"Duplicate function declaration `value`\",\"suggestion\":null,\"labels\":[{{\"span\":{{\"line\":1,\"col\":1,\"start\":0,\"end\":8}},\"message\":\"first declared here\"}}]}}"
+        ).as_str()),
+        "{stderr}"
+    );
+    assert!(
+        !stderr.contains(" | "),
+        "no human snippets in json mode: {stderr}"
+    );
+}
+
+#[test]
+fn check_format_json_carries_did_you_mean_suggestions_as_a_field() {
+    let tmp = make_temp_dir("skepac_json_suggestion");
+    let file = write_temp_file(
+        &tmp,
+        "main.sk",
+        "fn main() -> Int {\n  let total = 1;\n  return totl;\n}\n",
+    );
+
+    let output = Command::new(skepac_bin())
+        .args(["check", "--format", "json"])
+        .arg(&file)
+        .output()
+        .expect("run skepac");
+    assert_eq!(output.status.code(), Some(11), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("did you mean `total`?"), "{stderr}");
+    assert!(stderr.contains("\"suggestion\":\"total\","), "{stderr}");
+}
+
+#[test]
+fn check_format_json_reports_resolve_errors_without_offsets() {
+    let tmp = make_temp_dir("skepac_json_resolve");
+    let file = write_temp_file(&tmp, "main.sk", "fn main() -> Int {\n  return 0\n}\n");
+
+    let output = Command::new(skepac_bin())
+        .args(["check", "--format=json"])
+        .arg(&file)
+        .output()
+        .expect("run skepac");
+    assert_eq!(output.status.code(), Some(15), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("{\"code\":\"E-PARSE-004\","), "{stderr}");
+    assert!(
+        stderr.contains("\"span\":{\"line\":3,\"col\":1,\"start\":null,\"end\":null}"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn unknown_format_is_a_usage_error() {
+    let output = Command::new(skepac_bin())
+        .args(["check", "--format", "xml", "main.sk"])
+        .output()
+        .expect("run skepac");
+    assert_cli_failure_class(&output, CliFailureClass::Usage);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--format expects `human` or `json`"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn check_reports_body_errors_at_their_declaration_without_a_caret() {
+    let tmp = make_temp_dir("skepac_snippet_approx");
+    let file = write_temp_file(
+        &tmp,
+        "main.sk",
+        "fn main() -> Int {\n  let name: String = 1;\n  return 0;\n}\n",
+    );
+
+    let output = Command::new(skepac_bin())
+        .args(["check", "--no-color"])
+        .arg(&file)
+        .output()
+        .expect("run skepac");
+    assert_cli_failure_class(&output, CliFailureClass::Sema);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(":1:1: Type mismatch in let `name`"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains('^'), "{stderr}");
+    assert!(!stderr.contains("1 | fn main()"), "{stderr}");
 }
 
 #[test]
@@ -330,7 +861,7 @@ fn check_without_arguments_shows_usage_and_fails() {
     let output = Command::new(skepac_bin()).output().expect("run skepac");
     assert_cli_failure_class(&output, CliFailureClass::Usage);
     let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("Usage: skepac check <entry.sk> | skepac run <entry.sk> | skepac build-native <entry.sk> <out.exe> | skepac build-obj <entry.sk> <out.obj> | skepac build-llvm-ir <entry.sk> <out.ll>"));
+    assert!(stderr.contains("Usage: skepac check [--all] <entry.sk|dir> | skepac run <entry.sk> | skepac test <entry.sk> | skepac build-native <entry.sk> <out.exe> | skepac build-obj <entry.sk> <out.obj> | skepac build-llvm-ir <entry.sk> <out.ll>"));
 }
 
 #[test]
@@ -344,6 +875,30 @@ fn unknown_command_fails() {
     assert!(stderr.contains("Unknown command"));
 }
 
+#[test]
+fn explain_prints_registered_code_description() {
+    let output = Command::new(skepac_bin())
+        .args(["explain", "E-SEMA-005"])
+        .output()
+        .expect("run skepac explain");
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("E-SEMA-005 (sema): return type mismatch or missing return"));
+    assert!(stdout.contains("Example:"));
+    assert!(stdout.contains("Fix:"));
+}
+
+#[test]
+fn explain_rejects_unknown_code_as_usage_error() {
+    let output = Command::new(skepac_bin())
+        .args(["explain", "E-NOPE"])
+        .output()
+        .expect("run skepac explain");
+    assert_cli_failure_class(&output, CliFailureClass::Usage);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unknown error code `E-NOPE`"), "{stderr}");
+}
+
 #[test]
 fn run_executes_native_temp_binary_and_returns_exit_code() {
     let tmp = make_temp_dir("skepac_run_native");
@@ -367,6 +922,179 @@ fn main() -> Int {
     assert_eq!(output.status.code(), Some(7), "{:?}", output);
 }
 
+#[test]
+fn run_entry_flag_selects_entry_function_and_rejects_invalid_ones() {
+    let tmp = make_temp_dir("skepac_run_entry");
+    let source = tmp.join("main.sk");
+    fs::write(
+        &source,
+        r#"
+fn migrate() -> Int {
+  return 9;
+}
+
+fn greet(name: String) -> Int {
+  return 0;
+}
+
+fn main() -> Int {
+  return 7;
+}
+"#,
+    )
+    .expect("write source");
+
+    let output = Command::new(skepac_bin())
+        .args(["run", "--entry", "migrate"])
+        .arg(&source)
+        .output()
+        .expect("run skepac run --entry");
+    assert_eq!(output.status.code(), Some(9), "{:?}", output);
+
+    for (entry, expected) in [
+        ("missing", "does not define function `missing`"),
+        ("greet", "must take no parameters"),
+    ] {
+        let output = Command::new(skepac_bin())
+            .args(["run", "--entry", entry])
+            .arg(&source)
+            .output()
+            .expect("run skepac run --entry");
+        assert_cli_failure_class(&output, CliFailureClass::Codegen);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(expected), "stderr was: {stderr}");
+    }
+
+    let output = Command::new(skepac_bin())
+        .args(["run", "--entry"])
+        .output()
+        .expect("run skepac run --entry");
+    assert_cli_failure_class(&output, CliFailureClass::Usage);
+}
+
+#[test]
+fn test_command_runs_attribute_tests_across_modules() {
+    let tmp = make_temp_dir("skepac_test_cmd");
+    fs::write(
+        tmp.join("util.sk"),
+        r#"
+let counter = 0;
+
+fn bump() -> Int {
+  counter = counter + 1;
+  return counter;
+}
+
+@test
+fn bump_starts_from_zero() -> Int {
+  return bump() - 1;
+}
+
+@test
+fn bump_again_starts_from_zero() -> Int {
+  return bump() - 1;
+}
+
+export { bump };
+"#,
+    )
+    .expect("write util");
+    let source = tmp.join("main.sk");
+    fs::write(
+        &source,
+        r#"
+from util import bump;
+
+@test
+fn off_by_one() -> Int {
+  return bump();
+}
+
+@test
+fn passes_without_result() {
+  bump();
+}
+
+fn main() -> Int {
+  return bump();
+}
+"#,
+    )
+    .expect("write main");
+
+    let output = Command::new(skepac_bin())
+        .arg("test")
+        .arg(&source)
+        .output()
+        .expect("run skepac test");
+    assert_eq!(output.status.code(), Some(20), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("test main::off_by_one ... FAILED: returned 1"));
+    assert!(stdout.contains("test main::passes_without_result ... ok"));
+    assert!(stdout.contains("test util::bump_starts_from_zero ... ok"));
+    assert!(stdout.contains("test util::bump_again_starts_from_zero ... ok"));
+    assert!(stdout.contains("test result: FAILED. 3 passed; 1 failed"));
+
+    let passing = tmp.join("passing.sk");
+    fs::write(
+        &passing,
+        "@test\nfn ok() -> Int { return 0; }\nfn main() -> Int { return 0; }\n",
+    )
+    .expect("write passing");
+    let output = Command::new(skepac_bin())
+        .arg("test")
+        .arg(&passing)
+        .output()
+        .expect("run skepac test");
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("test result: ok. 1 passed; 0 failed")
+    );
+}
+
+#[test]
+fn run_forwards_piped_stdin_to_native_program() {
+    let tmp = make_temp_dir("skepac_run_stdin");
+    let source = tmp.join("main.sk");
+    fs::write(
+        &source,
+        r#"
+import io;
+import option;
+import vec;
+
+fn main() -> Int {
+  let header = io.tryReadLine();
+  let rows = io.readLines();
+  if (option.isSome(io.tryReadLine())) {
+    return 99;
+  }
+  io.println(option.unwrapSome(header));
+  return vec.len(rows);
+}
+"#,
+    )
+    .expect("write source");
+
+    let mut child = Command::new(skepac_bin())
+        .arg("run")
+        .arg(&source)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("spawn skepac run");
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(b"name\nana\nbo\n")
+        .expect("write stdin");
+    let output = child.wait_with_output().expect("wait skepac run");
+
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "name\n");
+}
+
 #[test]
 fn run_reports_runtime_failure_for_division_by_zero() {
     let tmp = make_temp_dir("skepac_run_div_zero");
@@ -1956,7 +2684,7 @@ fn main() -> Int { return add("x", 1); }
         .expect("run check");
     assert_cli_failure_class(&output, CliFailureClass::Sema);
     let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("[E-SEMA][sema]"));
+    assert!(stderr.contains("[E-SEMA-010][sema]"));
     assert!(stderr.contains(&format!("{}:3:", main.display())));
     assert!(stderr.contains("Argument 1 for `add`: expected Int, got String"));
     assert!(!stderr.contains(&format!("{}: {}", main.display(), main.display())));
@@ -3065,10 +3793,10 @@ fn main() -> Int {
 
     common::assert_cli_failure_class(&output, common::CliFailureClass::Sema);
     let stderr = String::from_utf8_lossy(&output.stderr);
-    common::assert_diag_code_and_message(&stderr, "[E-SEMA][sema]", "Unknown function `some`");
-    common::assert_diag_code_and_message(&stderr, "[E-SEMA][sema]", "Unknown function `none`");
-    common::assert_diag_code_and_message(&stderr, "[E-SEMA][sema]", "Unknown function `ok`");
-    common::assert_diag_code_and_message(&stderr, "[E-SEMA][sema]", "Unknown function `err`");
+    common::assert_diag_code_and_message(&stderr, "[E-SEMA-001][sema]", "Unknown function `some`");
+    common::assert_diag_code_and_message(&stderr, "[E-SEMA-001][sema]", "Unknown function `none`");
+    common::assert_diag_code_and_message(&stderr, "[E-SEMA-001][sema]", "Unknown function `ok`");
+    common::assert_diag_code_and_message(&stderr, "[E-SEMA-001][sema]", "Unknown function `err`");
 }
 
 #[test]
@@ -3144,3 +3872,83 @@ fn main() -> Int {
 
     assert_eq!(output.status.code(), Some(0), "{:?}", output);
 }
+
+#[test]
+fn run_rejects_denied_capability_packages_before_codegen() {
+    let tmp = make_temp_dir("skepac_run_capability_denied");
+    let source = write_temp_file(
+        &tmp,
+        "main.sk",
+        r#"
+import fs;
+fn main() -> Int {
+  let _ = fs.exists("x");
+  return 0;
+}
+"#,
+    );
+
+    let denied = Command::new(skepac_bin())
+        .args(["run", "--no-os", "--no-fs"])
+        .arg(&source)
+        .output()
+        .expect("run skepac run");
+    assert_eq!(denied.status.code(), Some(15), "{denied:?}");
+    let stderr = String::from_utf8_lossy(&denied.stderr);
+    assert_diag_code_and_message(&stderr, "[E-CAP-DENIED][resolve]", "imports `fs`");
+
+    let allowed = Command::new(skepac_bin())
+        .args(["run", "--no-net"])
+        .arg(&source)
+        .output()
+        .expect("run skepac run");
+    assert_eq!(allowed.status.code(), Some(0), "{allowed:?}");
+}
+
+#[test]
+fn run_rejects_store_import_when_fs_is_denied() {
+    let tmp = make_temp_dir("skepac_run_capability_store");
+    let db = tmp.join("state.db");
+    let source = write_temp_file(
+        &tmp,
+        "main.sk",
+        &r#"
+import store;
+fn main() -> Int {
+  let _ = store.open("PATH");
+  return 0;
+}
+"#
+        .replace("PATH", &db.to_string_lossy().replace('\\', "/")),
+    );
+
+    let denied = Command::new(skepac_bin())
+        .args(["run", "--no-fs"])
+        .arg(&source)
+        .output()
+        .expect("run skepac run");
+    assert_eq!(denied.status.code(), Some(15), "{denied:?}");
+    let stderr = String::from_utf8_lossy(&denied.stderr);
+    assert_diag_code_and_message(&stderr, "[E-CAP-DENIED][resolve]", "imports `store`");
+    assert!(
+        !db.exists(),
+        "denied program must not create the store file"
+    );
+}
+
+#[test]
+fn build_native_rejects_unknown_capability_flag_as_usage_error() {
+    let tmp = make_temp_dir("skepac_build_native_bad_capability");
+    let source = write_temp_file(&tmp, "main.sk", "fn main() -> Int { return 0; }\n");
+    let out = tmp.join(format!("main.{}", exe_ext()));
+
+    let output = Command::new(skepac_bin())
+        .args(["build-native", "--no-io"])
+        .arg(&source)
+        .arg(&out)
+        .output()
+        .expect("run skepac build-native");
+    assert_cli_failure_class(&output, CliFailureClass::Usage);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--no-fs"), "stderr was: {stderr}");
+}
@@ -0,0 +1,409 @@
+mod common;
+
+use std::fs;
+use std::process::Command;
+
+use common::{make_temp_dir, obj_ext, run_skepac, skepac_bin, write_temp_file};
+
+const VALID: &str = "fn main() -> Int {\n  return 0;\n}\n";
+
+#[test]
+fn every_subcommand_reports_its_usage_on_bad_arguments() {
+    let cases: &[(&[&str], &str)] = &[
+        (&["check"], "Usage: skepac check <file.sk>"),
+        (&["check", "a.sk", "b.sk"], "Usage: skepac check <file.sk>"),
+        (&["check", "--all"], "Usage: skepac check <file.sk>"),
+        (&["run"], "Usage: skepac run"),
+        (&["run", "--entry"], "Usage: skepac run"),
+        (&["run", "--trace", "a.sk"], "Usage: skepac run"),
+        (&["run", "a.sk", "b.sk"], "Usage: skepac run"),
+        (&["build-native", "a.sk"], "Usage: skepac build-native"),
+        (
+            &["build-native", "--no-disk", "a.sk", "a.out"],
+            "Usage: skepac build-native",
+        ),
+        (&["build-obj", "a.sk"], "Usage: skepac build-obj"),
+        (
+            &["build-obj", "a.sk", "a.o", "b.o"],
+            "Usage: skepac build-obj",
+        ),
+        (&["build-llvm-ir", "a.sk"], "Usage: skepac build-llvm-ir"),
+        (
+            &["build-llvm-ir", "a.sk", "a.ll", "b.ll"],
+            "Usage: skepac build-llvm-ir",
+        ),
+        (&["doc", "a.sk"], "Usage: skepac doc"),
+        (&["doc", "--pdf", "a.sk", "out"], "Usage: skepac doc"),
+        (&["parse"], "Usage: skepac parse"),
+        (&["parse", "--yaml", "a.sk"], "Usage: skepac parse"),
+        (&["explain"], "Usage: skepac explain"),
+        (
+            &["explain", "E-SEMA-001", "E-SEMA-002"],
+            "Usage: skepac explain",
+        ),
+        (&["check", "a.sk", "--format"], "--format expects"),
+        (&["--format=xml", "check", "a.sk"], "--format expects"),
+        (&[], "Usage: skepac check"),
+        (&["compile", "a.sk"], "Unknown command"),
+    ];
+    for (args, usage) in cases {
+        let output = run_skepac(args);
+        assert_eq!(output.status.code(), Some(2), "{args:?}: {output:?}");
+        assert!(output.stdout.is_empty(), "{args:?}: {output:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(usage), "{args:?}: stderr was: {stderr}");
+    }
+}
+
+#[test]
+fn exit_codes_distinguish_each_failure_phase() {
+    let tmp = make_temp_dir("skepac_cli_exit_codes");
+    let valid = write_temp_file(&tmp, "valid.sk", VALID);
+    let parse = write_temp_file(
+        &tmp,
+        "parse.sk",
+        "fn main() -> Int {\n  let x = 1\n  return x;\n}\n",
+    );
+    let sema = write_temp_file(
+        &tmp,
+        "sema.sk",
+        "fn main() -> Int {\n  let s: String = 1;\n  return 0;\n}\n",
+    );
+    let resolve = write_temp_file(
+        &tmp,
+        "resolve.sk",
+        "from nowhere import thing;\nfn main() -> Int { return 0; }\n",
+    );
+    let directory = tmp.join("folder.sk");
+    fs::create_dir_all(&directory).expect("create directory named like a module");
+    let path = |p: &std::path::Path| p.to_str().expect("utf-8 path").to_string();
+
+    let cases: Vec<(Vec<String>, i32, &str)> = vec![
+        (vec!["check".into(), path(&valid)], 0, ""),
+        (
+            vec!["parse".into(), path(&tmp.join("absent.sk"))],
+            3,
+            "[E-MOD-IO][resolve]",
+        ),
+        (
+            vec!["check".into(), path(&directory)],
+            3,
+            "[E-MOD-IO][resolve]",
+        ),
+        (vec!["parse".into(), path(&parse)], 10, "][parse]"),
+        (
+            vec!["check".into(), path(&parse)],
+            15,
+            "[E-PARSE-004][resolve]",
+        ),
+        (vec!["check".into(), path(&sema)], 11, "[E-SEMA-010][sema]"),
+        (
+            vec!["doc".into(), path(&sema), path(&tmp.join("docs"))],
+            11,
+            "[E-SEMA-010][sema]",
+        ),
+        (
+            vec![
+                "build-llvm-ir".into(),
+                path(&sema),
+                path(&tmp.join("sema.ll")),
+            ],
+            11,
+            "[E-SEMA-010][sema]",
+        ),
+        (
+            vec!["build-llvm-ir".into(), path(&valid), path(&directory)],
+            12,
+            "[E-CODEGEN][codegen]",
+        ),
+        (
+            vec!["check".into(), path(&resolve)],
+            15,
+            "[E-MOD-NOT-FOUND][resolve]",
+        ),
+        (
+            vec!["check".into(), path(&tmp.join("absent.sk"))],
+            15,
+            "[E-MOD-NOT-FOUND][resolve]",
+        ),
+        (vec!["explain".into(), "E-SEMA-010".into()], 0, ""),
+    ];
+    for (args, code, tag) in cases {
+        let output = Command::new(skepac_bin())
+            .args(&args)
+            .output()
+            .expect("run skepac");
+        assert_eq!(output.status.code(), Some(code), "{args:?}: {output:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if code == 0 {
+            assert!(stderr.is_empty(), "{args:?}: stderr was: {stderr}");
+        } else {
+            assert!(stderr.contains(tag), "{args:?}: stderr was: {stderr}");
+        }
+    }
+    assert!(!tmp.join("sema.ll").exists());
+    assert!(!tmp.join("docs").exists());
+}
+
+#[test]
+fn format_json_covers_errors_reported_outside_the_driver() {
+    let tmp = make_temp_dir("skepac_cli_json_io");
+    let output = run_skepac(&[
+        "--format",
+        "json",
+        "parse",
+        tmp.join("absent.sk").to_str().expect("utf-8 path"),
+    ]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line = stderr.lines().next().expect("one diagnostic line");
+    let diag: serde_json::Value = serde_json::from_str(line).expect("diagnostic is JSON");
+    assert_eq!(diag["code"], "E-MOD-IO");
+    assert_eq!(diag["phase"], "resolve");
+    assert_eq!(diag["span"], serde_json::Value::Null);
+}
+
+#[test]
+fn no_color_flag_is_accepted_anywhere_on_the_command_line() {
+    let tmp = make_temp_dir("skepac_cli_no_color");
+    let source = write_temp_file(&tmp, "main.sk", VALID);
+    let source = source.to_str().expect("utf-8 path");
+    for args in [
+        ["--no-color", "check", source],
+        ["check", "--no-color", source],
+        ["check", source, "--no-color"],
+    ] {
+        let output = Command::new(skepac_bin())
+            .args(args)
+            .env("NO_COLOR", "1")
+            .output()
+            .expect("run skepac");
+        assert_eq!(output.status.code(), Some(0), "{args:?}: {output:?}");
+    }
+}
+
+#[test]
+fn timing_env_vars_emit_json_objects_for_build_obj() {
+    let tmp = make_temp_dir("skepac_cli_timings_json");
+    let source = write_temp_file(&tmp, "main.sk", VALID);
+    let out = tmp.join(format!("main.{}", obj_ext()));
+    let output = Command::new(skepac_bin())
+        .arg("build-obj")
+        .arg(&source)
+        .arg(&out)
+        .env("SKEPAC_TIMINGS", "json")
+        .env("SKEPA_CODEGEN_TIMINGS", "json")
+        .output()
+        .expect("run skepac build-obj with JSON timings");
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let objects = stdout
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("timing JSON"))
+        .collect::<Vec<_>>();
+    let labels = objects
+        .iter()
+        .map(|object| object["timings"].as_str().expect("timing label"))
+        .collect::<Vec<_>>();
+    assert!(labels.contains(&"build-obj"), "stdout was: {stdout}");
+    assert!(labels.contains(&"codegen:object"), "stdout was: {stdout}");
+    assert!(
+        objects.iter().all(|object| object["total_us"].is_u64()),
+        "stdout was: {stdout}"
+    );
+}
+
+#[test]
+fn help_and_version_print_to_stdout_and_succeed() {
+    let top = run_skepac(&["--help"]);
+    assert_eq!(top.status.code(), Some(0), "{top:?}");
+    let stdout = String::from_utf8_lossy(&top.stdout);
+    for command in [
+        "check",
+        "run",
+        "build-native",
+        "build-obj",
+        "build-llvm-ir",
+        "doc",
+        "parse",
+        "explain",
+    ] {
+        assert!(stdout.contains(&format!("  {command} ")), "{stdout}");
+        for args in [vec![command, "--help"], vec!["help", command]] {
+            let output = run_skepac(&args);
+            assert_eq!(output.status.code(), Some(0), "{args:?}: {output:?}");
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            assert!(
+                stdout.contains(&format!("Usage: skepac {command}")),
+                "{args:?}: {stdout}"
+            );
+        }
+    }
+    assert_eq!(run_skepac(&["-h"]).stdout, top.stdout);
+    assert_eq!(run_skepac(&["help"]).stdout, top.stdout);
+
+    let version = run_skepac(&["--version"]);
+    assert_eq!(version.status.code(), Some(0), "{version:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&version.stdout),
+        format!("skepac {}\n", env!("CARGO_PKG_VERSION"))
+    );
+    assert_eq!(run_skepac(&["-V"]).stdout, version.stdout);
+}
+
+#[test]
+fn flags_accept_inline_values_and_report_unknown_flags_by_name() {
+    let tmp = make_temp_dir("skepac_cli_flag_forms");
+    let source = write_temp_file(&tmp, "main.sk", VALID);
+    let source = source.to_str().expect("utf-8 path");
+
+    let output = run_skepac(&["check", source, "--format=json"]);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let output = run_skepac(&["parse", source, "--json"]);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+
+    let cases: &[(&[&str], &str)] = &[
+        (
+            &["check", "--verbose", source],
+            "Unknown flag `--verbose` for `skepac check`",
+        ),
+        (
+            &["check", "--all=yes", source],
+            "Flag `--all` does not take a value",
+        ),
+        (&["run", "--entry"], "Flag `--entry` expects a value"),
+        (
+            &["run", "--no-fs=1", source],
+            "Flag `--no-fs` does not take a value",
+        ),
+        (
+            &["build-obj", "--no-fs", source, "out.o"],
+            "Unknown flag `--no-fs`",
+        ),
+        (&["help", "compile"], "Unknown command"),
+    ];
+    for (args, message) in cases {
+        let output = run_skepac(args);
+        assert_eq!(output.status.code(), Some(2), "{args:?}: {output:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(message), "{args:?}: stderr was: {stderr}");
+    }
+}
+
+#[test]
+fn lint_flags_set_warning_levels_and_file_directives_override_them() {
+    let tmp = make_temp_dir("skepac_cli_lints");
+    let dead = "fn main() -> Int {\n  return 0;\n  let x = 1;\n}\n";
+    let source = write_temp_file(&tmp, "main.sk", dead);
+    let source = source.to_str().expect("utf-8 path");
+    let allowed = write_temp_file(
+        &tmp,
+        "allowed.sk",
+        &format!("#allow(unreachable_code)\n{dead}"),
+    );
+    let allowed = allowed.to_str().expect("utf-8 path");
+
+    let output = run_skepac(&["check", source]);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning:"));
+
+    for args in [
+        &["check", "--deny", "warnings", source][..],
+        &["--deny=unreachable-code", "check", source][..],
+    ] {
+        let output = run_skepac(args);
+        assert_eq!(output.status.code(), Some(11), "{args:?}: {output:?}");
+    }
+
+    let output = run_skepac(&["check", "--allow", "unreachable_code", source]);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    assert!(output.stderr.is_empty(), "{output:?}");
+    let output = run_skepac(&["check", "--deny", "warnings", allowed]);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+
+    let output = run_skepac(&["check", "--warn", "unused_variable", source]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown lint `unused_variable`"));
+}
+
+#[test]
+fn build_watch_rebuilds_after_a_source_change() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let tmp = make_temp_dir("skepac_cli_watch");
+    let source = write_temp_file(&tmp, "main.sk", VALID);
+    let out = tmp.join("main.ll");
+    let mut child = Command::new(skepac_bin())
+        .args(["build-llvm-ir", "--watch"])
+        .arg(&source)
+        .arg(&out)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn skepac build-llvm-ir --watch");
+    let stdout = child.stdout.take().expect("piped stdout");
+    let (lines_tx, lines) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if lines_tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let wait_for = |needle: &str| {
+        loop {
+            let line = lines
+                .recv_timeout(Duration::from_secs(30))
+                .unwrap_or_else(|_| panic!("watch never printed `{needle}`"));
+            if line.contains(needle) {
+                return line;
+            }
+        }
+    };
+
+    let first = wait_for("watch: build finished in");
+    assert!(first.contains("(exit 0)"), "{first}");
+    assert!(
+        !fs::read_to_string(&out)
+            .expect("first ir")
+            .contains("helper")
+    );
+
+    fs::write(
+        &source,
+        "fn helper(x: Int) -> Int {\n  if (x > 0) { return helper(x - 1); }\n  return 0;\n}\nfn main() -> Int {\n  return helper(3);\n}\n",
+    )
+    .expect("edit source");
+    wait_for("watch: change detected, rebuilding");
+    let second = wait_for("watch: build finished in");
+    assert!(second.contains("(exit 0)"), "{second}");
+    assert!(
+        fs::read_to_string(&out)
+            .expect("rebuilt ir")
+            .contains("helper")
+    );
+
+    fs::write(&source, "fn main() -> Int {\n  return true;\n}\n").expect("break source");
+    wait_for("watch: change detected, rebuilding");
+    let failed = wait_for("watch: build finished in");
+    assert!(failed.contains("(exit 11)"), "{failed}");
+    assert!(
+        fs::read_to_string(&out)
+            .expect("kept ir")
+            .contains("helper")
+    );
+
+    child.kill().expect("stop watcher");
+    let _ = child.wait();
+    let leftovers = fs::read_dir(&tmp)
+        .expect("read temp dir")
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().contains("skepac-tmp"))
+        .count();
+    assert_eq!(leftovers, 0);
+}
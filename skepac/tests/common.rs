@@ -80,13 +80,17 @@ pub fn assert_cli_failure_class(output: &Output, class: CliFailureClass) {
                 "{output:?}"
             );
             assert!(
-                stderr.contains("[E-PARSE][parse]") || stderr.contains("[E-PARSE][resolve]"),
+                stderr.contains("[E-PARSE")
+                    && (stderr.contains("][parse]") || stderr.contains("][resolve]")),
                 "stderr was: {stderr}"
             );
         }
         CliFailureClass::Sema => {
             assert_eq!(output.status.code(), Some(11), "{output:?}");
-            assert!(stderr.contains("[E-SEMA][sema]"), "stderr was: {stderr}");
+            assert!(
+                stderr.contains("[E-SEMA") && stderr.contains("][sema]"),
+                "stderr was: {stderr}"
+            );
         }
         CliFailureClass::Codegen => {
             assert_eq!(output.status.code(), Some(12), "{output:?}");
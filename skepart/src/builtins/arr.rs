@@ -1,4 +1,5 @@
-use crate::{RtArray, RtOption, RtResult, RtString, RtValue};
+use super::BuiltinContext;
+use crate::{RtArray, RtFunctionRef, RtOption, RtResult, RtString, RtValue, RtVec};
 
 pub fn len(array: &RtArray) -> i64 {
     array.len() as i64
@@ -8,6 +9,23 @@ pub fn is_empty(array: &RtArray) -> bool {
     array.is_empty()
 }
 
+/// Element lookups use `==` semantics: structs and nested arrays compare by value.
+pub fn contains(array: &RtArray, needle: &RtValue) -> bool {
+    array.iter().any(|item| item == *needle)
+}
+
+/// Index of the first element equal to `needle`, or `-1`.
+pub fn index_of(array: &RtArray, needle: &RtValue) -> i64 {
+    array
+        .iter()
+        .position(|item| item == *needle)
+        .map_or(-1, |index| index as i64)
+}
+
+pub fn count(array: &RtArray, needle: &RtValue) -> i64 {
+    array.iter().filter(|item| item == needle).count() as i64
+}
+
 pub fn first(array: &RtArray) -> RtValue {
     match array.get(0) {
         Ok(value) => RtValue::Option(RtOption::some(value)),
@@ -32,3 +50,71 @@ pub fn join(array: &RtArray, sep: &RtString) -> RtResult<RtString> {
     }
     Ok(RtString::from(out.join(sep.as_str())))
 }
+
+pub fn map(ctx: &mut dyn BuiltinContext, array: &RtArray, f: RtFunctionRef) -> RtResult<RtValue> {
+    let items = array
+        .iter()
+        .map(|item| ctx.call_function(f, &[item]))
+        .collect::<RtResult<Vec<_>>>()?;
+    Ok(RtValue::Array(RtArray::new(items)))
+}
+
+/// Keeps the elements `predicate` accepts; the result is a `Vec` because its
+/// length is only known at runtime.
+pub fn filter(
+    ctx: &mut dyn BuiltinContext,
+    array: &RtArray,
+    predicate: RtFunctionRef,
+) -> RtResult<RtValue> {
+    let out = RtVec::new();
+    for item in array.iter() {
+        if ctx
+            .call_function(predicate, std::slice::from_ref(&item))?
+            .expect_bool()?
+        {
+            out.push(item);
+        }
+    }
+    Ok(RtValue::Vec(out))
+}
+
+/// Returns a copy of `array` ordered by `cmp`, which returns a negative,
+/// zero or positive `Int` like a three-way comparison.
+pub fn sort_by(
+    ctx: &mut dyn BuiltinContext,
+    array: &RtArray,
+    cmp: RtFunctionRef,
+) -> RtResult<RtValue> {
+    let items = stable_sort(ctx, array.iter().collect(), cmp)?;
+    Ok(RtValue::Array(RtArray::new(items)))
+}
+
+/// Merge sort driven by a user comparator. Equal elements keep their input
+/// order, and an inconsistent comparator still yields a permutation of the
+/// input rather than panicking as `slice::sort_by` may.
+pub(super) fn stable_sort(
+    ctx: &mut dyn BuiltinContext,
+    mut items: Vec<RtValue>,
+    cmp: RtFunctionRef,
+) -> RtResult<Vec<RtValue>> {
+    if items.len() < 2 {
+        return Ok(items);
+    }
+    let right = items.split_off(items.len() / 2);
+    let left = stable_sort(ctx, items, cmp)?;
+    let right = stable_sort(ctx, right, cmp)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        let order = ctx
+            .call_function(cmp, &[l.clone(), r.clone()])?
+            .expect_int()?;
+        let next = if order > 0 { right.next() } else { left.next() };
+        merged.extend(next);
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
@@ -63,3 +63,109 @@ pub fn push(value: &RtBytes, byte: i64) -> RtResult<RtValue> {
 pub fn append(left: &RtBytes, right: &RtBytes) -> RtValue {
     RtValue::Bytes(left.append(right))
 }
+
+pub fn to_hex(value: &RtBytes) -> RtValue {
+    let mut out = String::with_capacity(value.len() * 2);
+    for byte in value.as_slice() {
+        out.push_str(&format!("{byte:02x}"));
+    }
+    RtValue::String(RtString::from(out))
+}
+
+pub fn from_hex(text: &str) -> RtResult<RtValue> {
+    Ok(match decode_hex("bytes.fromHex", text) {
+        Ok(data) => RtValue::Result(crate::RtResultValue::ok(RtValue::Bytes(RtBytes::from(
+            data,
+        )))),
+        Err(message) => bytes_err(message),
+    })
+}
+
+pub fn encode(text: &str, encoding: &str) -> RtResult<RtValue> {
+    let data = match Encoding::parse(encoding) {
+        Some(Encoding::Utf8) => Ok(text.as_bytes().to_vec()),
+        Some(Encoding::Ascii) => text
+            .chars()
+            .map(|ch| u8::try_from(ch).ok().filter(u8::is_ascii))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| "bytes.encode found a character outside ascii".to_string()),
+        Some(Encoding::Latin1) => text
+            .chars()
+            .map(|ch| u8::try_from(ch).ok())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| "bytes.encode found a character outside latin1".to_string()),
+        None => Err(format!(
+            "bytes.encode does not support encoding `{encoding}`"
+        )),
+    };
+    Ok(match data {
+        Ok(data) => RtValue::Result(crate::RtResultValue::ok(RtValue::Bytes(RtBytes::from(
+            data,
+        )))),
+        Err(message) => bytes_err(message),
+    })
+}
+
+pub fn decode(value: &RtBytes, encoding: &str) -> RtResult<RtValue> {
+    let data = value.as_slice();
+    let text = match Encoding::parse(encoding) {
+        Some(Encoding::Utf8) => std::str::from_utf8(data)
+            .map(ToString::to_string)
+            .map_err(|_| "bytes.decode expected valid utf-8 data".to_string()),
+        Some(Encoding::Ascii) if data.is_ascii() => {
+            Ok(data.iter().map(|b| char::from(*b)).collect())
+        }
+        Some(Encoding::Ascii) => Err("bytes.decode found a byte outside ascii".to_string()),
+        Some(Encoding::Latin1) => Ok(data.iter().map(|b| char::from(*b)).collect()),
+        None => Err(format!(
+            "bytes.decode does not support encoding `{encoding}`"
+        )),
+    };
+    Ok(match text {
+        Ok(text) => RtValue::Result(crate::RtResultValue::ok(RtValue::String(RtString::from(
+            text,
+        )))),
+        Err(message) => bytes_err(message),
+    })
+}
+
+#[derive(Clone, Copy)]
+enum Encoding {
+    Utf8,
+    Ascii,
+    Latin1,
+}
+
+impl Encoding {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Self::Utf8),
+            "ascii" | "us-ascii" => Some(Self::Ascii),
+            "latin1" | "latin-1" | "iso-8859-1" => Some(Self::Latin1),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes upper- or lowercase hex digits, naming `builtin` in errors.
+pub(super) fn decode_hex(builtin: &str, text: &str) -> Result<Vec<u8>, String> {
+    if !text.len().is_multiple_of(2) {
+        return Err(format!("{builtin} expected an even number of hex digits"));
+    }
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let digits = std::str::from_utf8(pair).unwrap_or_default();
+            u8::from_str_radix(digits, 16)
+                .ok()
+                .filter(|_| pair.iter().all(u8::is_ascii_hexdigit))
+                .ok_or_else(|| format!("{builtin} found invalid hex digits `{digits}`"))
+        })
+        .collect()
+}
+
+fn bytes_err(message: String) -> RtValue {
+    RtValue::Result(crate::RtResultValue::err(RtValue::String(RtString::from(
+        message,
+    ))))
+}
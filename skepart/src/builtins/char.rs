@@ -0,0 +1,36 @@
+use crate::{RtOption, RtString, RtValue};
+
+pub fn to_int(value: char) -> RtValue {
+    RtValue::Int(i64::from(u32::from(value)))
+}
+
+pub fn from_int(value: i64) -> RtValue {
+    match u32::try_from(value).ok().and_then(char::from_u32) {
+        Some(value) => RtValue::Option(RtOption::some(RtValue::Char(value))),
+        None => RtValue::Option(RtOption::none()),
+    }
+}
+
+pub fn to_string(value: char) -> RtValue {
+    RtValue::String(RtString::from(value.to_string()))
+}
+
+pub fn from_string(value: &str) -> RtValue {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(value), None) => RtValue::Option(RtOption::some(RtValue::Char(value))),
+        _ => RtValue::Option(RtOption::none()),
+    }
+}
+
+pub fn is_digit(value: char) -> RtValue {
+    RtValue::Bool(value.is_ascii_digit())
+}
+
+pub fn is_alpha(value: char) -> RtValue {
+    RtValue::Bool(value.is_alphabetic())
+}
+
+pub fn is_whitespace(value: char) -> RtValue {
+    RtValue::Bool(value.is_whitespace())
+}
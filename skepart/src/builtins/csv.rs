@@ -0,0 +1,149 @@
+//! RFC 4180 CSV behind the `csv` builtins: comma-separated fields, fields
+//! quoted with `"` and quotes inside them doubled, records ended by `\n` or
+//! `\r\n`.
+
+use crate::{RtMap, RtResult, RtResultValue, RtString, RtValue, RtVec};
+
+pub fn parse(text: &str) -> RtResult<RtValue> {
+    Ok(match parse_records(text) {
+        Ok(records) => {
+            let rows = RtVec::new();
+            for record in records {
+                rows.push(RtValue::Vec(string_vec(record)));
+            }
+            RtValue::Result(RtResultValue::ok(RtValue::Vec(rows)))
+        }
+        Err(message) => csv_err(message),
+    })
+}
+
+/// Uses the first record as column names; every later record must have one
+/// field per column.
+pub fn parse_with_header(text: &str) -> RtResult<RtValue> {
+    let records = match parse_records(text) {
+        Ok(records) => records,
+        Err(message) => return Ok(csv_err(message)),
+    };
+    let mut records = records.into_iter();
+    let header = records.next().unwrap_or_default();
+    for (index, name) in header.iter().enumerate() {
+        if header[..index].contains(name) {
+            return Ok(csv_err(format!("duplicate column `{name}` in header")));
+        }
+    }
+    let rows = RtVec::new();
+    for (index, record) in records.enumerate() {
+        if record.len() != header.len() {
+            return Ok(csv_err(format!(
+                "record {} has {} field(s), but the header has {}",
+                index + 2,
+                record.len(),
+                header.len()
+            )));
+        }
+        let row = RtMap::new();
+        for (name, field) in header.iter().zip(record) {
+            row.insert(name.clone(), RtValue::String(RtString::from(field)));
+        }
+        rows.push(RtValue::Map(row));
+    }
+    Ok(RtValue::Result(RtResultValue::ok(RtValue::Vec(rows))))
+}
+
+/// Writes each row as one `\n`-terminated record, quoting only the fields
+/// that need it.
+pub fn stringify(rows: &RtVec) -> RtResult<RtValue> {
+    let mut out = String::new();
+    for row in rows.items() {
+        for (index, field) in row.expect_vec()?.items().into_iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            let field = field.expect_string()?;
+            let field = field.as_str();
+            if field.contains([',', '"', '\n', '\r']) {
+                out.push('"');
+                out.push_str(&field.replace('"', "\"\""));
+                out.push('"');
+            } else {
+                out.push_str(field);
+            }
+        }
+        out.push('\n');
+    }
+    Ok(RtValue::String(RtString::from(out)))
+}
+
+/// Every line is a record, an empty one included, except that a final line
+/// ending does not start another record.
+fn parse_records(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_record = false;
+    let mut line = 1;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\n' || (ch == '\r' && chars.peek() == Some(&'\n')) {
+            if ch == '\r' {
+                chars.next();
+            }
+            record.push(std::mem::take(&mut field));
+            records.push(std::mem::take(&mut record));
+            in_record = false;
+            line += 1;
+            continue;
+        }
+        in_record = true;
+        match ch {
+            // A closing quote must be followed by `,` or a line ending, so an
+            // empty field here is always at the start of a field.
+            '"' if field.is_empty() => {
+                let start = line;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(ch) => {
+                            line += usize::from(ch == '\n');
+                            field.push(ch);
+                        }
+                        None => return Err(format!("unterminated quoted field on line {start}")),
+                    }
+                }
+                if !matches!(chars.peek(), None | Some(',' | '\n' | '\r')) {
+                    return Err(format!(
+                        "unexpected text after closing quote on line {line}"
+                    ));
+                }
+            }
+            '"' => {
+                return Err(format!(
+                    "unexpected quote inside unquoted field on line {line}"
+                ))
+            }
+            ',' => record.push(std::mem::take(&mut field)),
+            ch => field.push(ch),
+        }
+    }
+    if in_record {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn string_vec(fields: Vec<String>) -> RtVec {
+    let out = RtVec::new();
+    for field in fields {
+        out.push(RtValue::String(RtString::from(field)));
+    }
+    out
+}
+
+fn csv_err(message: String) -> RtValue {
+    RtValue::Result(RtResultValue::err(RtValue::String(RtString::from(message))))
+}
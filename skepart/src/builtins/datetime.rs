@@ -1,4 +1,5 @@
-use crate::{RtHost, RtResult, RtValue};
+use crate::host::{days_in_month, format_with_pattern};
+use crate::{RtError, RtErrorKind, RtHost, RtResult, RtValue};
 
 pub fn now_unix(host: &mut dyn RtHost) -> RtResult<RtValue> {
     Ok(RtValue::Int(host.datetime_now_unix()?))
@@ -25,6 +26,101 @@ pub fn parse_unix(host: &mut dyn RtHost, value: &str) -> RtResult<RtValue> {
     }
 }
 
+pub fn format(host: &mut dyn RtHost, value: i64, pattern: &str) -> RtResult<RtValue> {
+    Ok(RtValue::String(host.datetime_format(value, pattern)?))
+}
+
+pub fn parse(host: &mut dyn RtHost, pattern: &str, value: &str) -> RtResult<RtValue> {
+    match host.datetime_parse(pattern, value) {
+        Ok(ts) => Ok(RtValue::Result(crate::RtResultValue::ok(RtValue::Int(ts)))),
+        Err(err) => Ok(RtValue::Result(crate::RtResultValue::err(RtValue::String(
+            crate::RtString::from(err.to_string()),
+        )))),
+    }
+}
+
+/// ISO-8601 with the numeric UTC offset, e.g. `2024-01-01T13:34:56+01:00`.
+const LOCAL_ISO_PATTERN: &str = "YYYY-MM-DD'T'hh:mm:ssZ";
+
+pub fn now_local(host: &mut dyn RtHost) -> RtResult<RtValue> {
+    let now = host.datetime_now_unix()?;
+    format_local(host, "datetime.nowLocal", now, LOCAL_ISO_PATTERN)
+}
+
+pub fn to_local(host: &mut dyn RtHost, value: i64) -> RtResult<RtValue> {
+    format_local(host, "datetime.toLocal", value, LOCAL_ISO_PATTERN)
+}
+
+pub fn offset_minutes(host: &mut dyn RtHost) -> RtResult<RtValue> {
+    let now = host.datetime_now_unix()?;
+    Ok(RtValue::Int(host.datetime_local_offset_minutes(now)?))
+}
+
+pub fn format_local(
+    host: &mut dyn RtHost,
+    builtin: &str,
+    value: i64,
+    pattern: &str,
+) -> RtResult<RtValue> {
+    let offset = host.datetime_local_offset_minutes(value)?;
+    Ok(RtValue::String(format_with_pattern(
+        builtin, value, offset, pattern,
+    )?))
+}
+
 pub fn component(host: &mut dyn RtHost, name: &str, value: i64) -> RtResult<RtValue> {
     Ok(RtValue::Int(host.datetime_component(name, value)?))
 }
+
+pub fn add_days(value: i64, days: i64) -> RtResult<RtValue> {
+    shift(value, days, 86_400, "datetime.addDays")
+}
+
+pub fn add_hours(value: i64, hours: i64) -> RtResult<RtValue> {
+    shift(value, hours, 3_600, "datetime.addHours")
+}
+
+fn shift(value: i64, count: i64, unit: i64, name: &str) -> RtResult<RtValue> {
+    count
+        .checked_mul(unit)
+        .and_then(|delta| value.checked_add(delta))
+        .map(RtValue::Int)
+        .ok_or_else(|| {
+            RtError::new(
+                RtErrorKind::InvalidArgument,
+                format!("{name} overflowed the Int timestamp range"),
+            )
+        })
+}
+
+pub fn diff_seconds(later: i64, earlier: i64) -> RtResult<RtValue> {
+    later.checked_sub(earlier).map(RtValue::Int).ok_or_else(|| {
+        RtError::new(
+            RtErrorKind::InvalidArgument,
+            "datetime.diffSeconds overflowed the Int range",
+        )
+    })
+}
+
+/// ISO weekday of `value`: 1 for Monday through 7 for Sunday.
+pub fn weekday(value: i64) -> RtResult<RtValue> {
+    // 1970-01-01 was a Thursday.
+    let days = value.div_euclid(86_400);
+    Ok(RtValue::Int((days + 3).rem_euclid(7) + 1))
+}
+
+pub fn days_in_month_of(year: i64, month: i64) -> RtResult<RtValue> {
+    let year = i32::try_from(year).map_err(|_| {
+        RtError::new(
+            RtErrorKind::InvalidArgument,
+            "datetime.daysInMonth year is out of range",
+        )
+    })?;
+    if !(1..=12).contains(&month) {
+        return Err(RtError::new(
+            RtErrorKind::InvalidArgument,
+            format!("datetime.daysInMonth month must be in 1..=12, got {month}"),
+        ));
+    }
+    Ok(RtValue::Int(days_in_month(year, month as u32) as i64))
+}
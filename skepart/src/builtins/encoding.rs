@@ -0,0 +1,150 @@
+use crate::{RtBytes, RtResultValue, RtString, RtValue};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn base64_encode(value: &RtBytes) -> RtValue {
+    let data = value.as_slice();
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (index, byte)| {
+            group | u32::from(*byte) << (16 - 8 * index)
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (group >> (18 - 6 * index)) & 0x3f;
+                out.push(char::from(BASE64_ALPHABET[sextet as usize]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    RtValue::String(RtString::from(out))
+}
+
+/// Decodes standard (`+/`) base64. Padding is optional, but when present the
+/// input must be a whole number of 4-character groups.
+pub fn base64_decode(text: &str) -> RtValue {
+    decoded(decode_base64(text).map(|data| RtValue::Bytes(RtBytes::from(data))))
+}
+
+fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    let data = text.trim_end_matches('=');
+    let padding = text.len() - data.len();
+    if padding > 2 || (padding > 0 && !text.len().is_multiple_of(4)) {
+        return Err(decode_error("base64Decode", "has misplaced `=` padding"));
+    }
+    if data.len() % 4 == 1 {
+        return Err(decode_error("base64Decode", "has a truncated final group"));
+    }
+    let mut out = Vec::with_capacity(data.len() / 4 * 3 + 2);
+    let mut group = 0u32;
+    let mut bits = 0;
+    for (offset, byte) in data.bytes().enumerate() {
+        let Some(sextet) = BASE64_ALPHABET.iter().position(|digit| *digit == byte) else {
+            return Err(decode_error(
+                "base64Decode",
+                &format!(
+                    "has invalid character {} at offset {offset}",
+                    describe(text, offset)
+                ),
+            ));
+        };
+        group = group << 6 | sextet as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((group >> bits) as u8);
+            group &= (1 << bits) - 1;
+        }
+    }
+    Ok(out)
+}
+
+pub fn hex_encode(value: &RtBytes) -> RtValue {
+    super::bytes::to_hex(value)
+}
+
+/// Accepts upper- and lowercase digits.
+pub fn hex_decode(text: &str) -> RtValue {
+    decoded(
+        super::bytes::decode_hex("encoding.hexDecode", text)
+            .map(|data| RtValue::Bytes(RtBytes::from(data))),
+    )
+}
+
+/// Percent-encodes the UTF-8 bytes of `text`, leaving only the RFC 3986
+/// unreserved characters (`A-Z a-z 0-9 - _ . ~`) as they are.
+pub fn url_encode(text: &str) -> RtValue {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(char::from(byte));
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    RtValue::String(RtString::from(out))
+}
+
+/// Reverses `url_encode`. `+` is kept as a literal `+`, not a space.
+pub fn url_decode(text: &str) -> RtValue {
+    decoded(decode_url(text).map(|text| RtValue::String(RtString::from(text))))
+}
+
+fn decode_url(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut offset = 0;
+    while offset < bytes.len() {
+        if bytes[offset] != b'%' {
+            out.push(bytes[offset]);
+            offset += 1;
+            continue;
+        }
+        let byte = (offset + 3 <= bytes.len())
+            .then(|| hex_byte(text, offset + 1))
+            .flatten()
+            .ok_or_else(|| {
+                decode_error(
+                    "urlDecode",
+                    &format!("has a malformed `%` escape at offset {offset}"),
+                )
+            })?;
+        out.push(byte);
+        offset += 3;
+    }
+    String::from_utf8(out)
+        .map_err(|_| decode_error("urlDecode", "escapes bytes that are not valid UTF-8"))
+}
+
+pub fn is_valid_utf8(value: &RtBytes) -> RtValue {
+    RtValue::Bool(std::str::from_utf8(value.as_slice()).is_ok())
+}
+
+fn hex_byte(text: &str, offset: usize) -> Option<u8> {
+    let digits = text.as_bytes().get(offset..offset + 2)?;
+    if !digits.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+    u8::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()
+}
+
+/// The character starting at byte `offset`, quoted for an error message.
+fn describe(text: &str, offset: usize) -> String {
+    match text.get(offset..).and_then(|rest| rest.chars().next()) {
+        Some(ch) => format!("`{ch}`"),
+        None => format!("byte 0x{:02x}", text.as_bytes()[offset]),
+    }
+}
+
+fn decode_error(builtin: &str, problem: &str) -> String {
+    format!("encoding.{builtin} input {problem}")
+}
+
+fn decoded(result: Result<RtValue, String>) -> RtValue {
+    RtValue::Result(match result {
+        Ok(value) => RtResultValue::ok(value),
+        Err(message) => RtResultValue::err(RtValue::String(RtString::from(message))),
+    })
+}
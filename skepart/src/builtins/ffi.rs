@@ -82,6 +82,10 @@ pub fn call(
         ("i64,i64->i64", [left, right]) => {
             call_2_int_int(host, symbol, left.expect_int()?, right.expect_int()?)
         }
+        ("f64->f64", [value]) => call_1_float(host, symbol, value.expect_float()?),
+        ("f64,f64->f64", [left, right]) => {
+            call_2_float_float(host, symbol, left.expect_float()?, right.expect_float()?)
+        }
         ("bytes->usize", [value]) => call_1_bytes_usize(host, symbol, &value.expect_bytes()?),
         ("bytes,usize->usize", [value, right]) => {
             call_2_bytes_usize_usize(host, symbol, &value.expect_bytes()?, right.expect_int()?)
@@ -280,6 +284,25 @@ pub fn call_2_int_int(
     Ok(RtValue::Int(host.ffi_call_2_int_int(symbol, left, right)?))
 }
 
+pub fn call_1_float(
+    host: &mut dyn RtHost,
+    symbol: crate::RtHandle,
+    value: f64,
+) -> RtResult<RtValue> {
+    Ok(RtValue::Float(host.ffi_call_1_float(symbol, value)?))
+}
+
+pub fn call_2_float_float(
+    host: &mut dyn RtHost,
+    symbol: crate::RtHandle,
+    left: f64,
+    right: f64,
+) -> RtResult<RtValue> {
+    Ok(RtValue::Float(
+        host.ffi_call_2_float_float(symbol, left, right)?,
+    ))
+}
+
 pub fn call_2_bytes_usize_usize(
     host: &mut dyn RtHost,
     symbol: crate::RtHandle,
@@ -1,4 +1,4 @@
-use crate::{RtHost, RtResult, RtResultValue, RtString, RtValue};
+use crate::{RtBytes, RtHost, RtResult, RtResultValue, RtString, RtValue};
 
 pub fn exists(host: &mut dyn RtHost, path: &str) -> RtResult<RtValue> {
     match host.fs_exists(path) {
@@ -65,6 +65,42 @@ pub fn remove_dir_all(host: &mut dyn RtHost, path: &str) -> RtResult<RtValue> {
     }
 }
 
+pub fn copy_file(host: &mut dyn RtHost, from: &str, to: &str) -> RtResult<RtValue> {
+    match host.fs_copy_file(from, to) {
+        Ok(()) => Ok(RtValue::Result(RtResultValue::ok(RtValue::Unit))),
+        Err(err) => Ok(RtValue::Result(RtResultValue::err(RtValue::String(
+            RtString::from(err.to_string()),
+        )))),
+    }
+}
+
+pub fn move_file(host: &mut dyn RtHost, from: &str, to: &str) -> RtResult<RtValue> {
+    match host.fs_move_file(from, to) {
+        Ok(()) => Ok(RtValue::Result(RtResultValue::ok(RtValue::Unit))),
+        Err(err) => Ok(RtValue::Result(RtResultValue::err(RtValue::String(
+            RtString::from(err.to_string()),
+        )))),
+    }
+}
+
+pub fn read_bytes(host: &mut dyn RtHost, path: &str) -> RtResult<RtValue> {
+    match host.fs_read_bytes(path) {
+        Ok(data) => Ok(RtValue::Result(RtResultValue::ok(RtValue::Bytes(data)))),
+        Err(err) => Ok(RtValue::Result(RtResultValue::err(RtValue::String(
+            RtString::from(err.to_string()),
+        )))),
+    }
+}
+
+pub fn write_bytes(host: &mut dyn RtHost, path: &str, data: &RtBytes) -> RtResult<RtValue> {
+    match host.fs_write_bytes(path, data) {
+        Ok(()) => Ok(RtValue::Result(RtResultValue::ok(RtValue::Unit))),
+        Err(err) => Ok(RtValue::Result(RtResultValue::err(RtValue::String(
+            RtString::from(err.to_string()),
+        )))),
+    }
+}
+
 pub fn join(host: &mut dyn RtHost, left: &str, right: &str) -> RtResult<RtValue> {
     Ok(RtValue::String(host.fs_join(left, right)?))
 }
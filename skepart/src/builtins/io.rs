@@ -1,4 +1,5 @@
-use crate::{RtHost, RtResult, RtValue};
+use crate::display::display_value;
+use crate::{RtHost, RtOption, RtResult, RtValue, RtVec};
 
 pub fn print(host: &mut dyn RtHost, value: &RtValue) -> RtResult<()> {
     host.io_print(&display_value(value))
@@ -8,10 +9,42 @@ pub fn println(host: &mut dyn RtHost, value: &RtValue) -> RtResult<()> {
     host.io_println(&display_value(value))
 }
 
+pub fn eprint(host: &mut dyn RtHost, value: &RtValue) -> RtResult<()> {
+    host.io_eprint(&display_value(value))
+}
+
+pub fn eprintln(host: &mut dyn RtHost, value: &RtValue) -> RtResult<()> {
+    host.io_eprintln(&display_value(value))
+}
+
+/// Writes the literal-like rendering of `value` and a newline to standard error.
+pub fn debug(host: &mut dyn RtHost, value: &RtValue) -> RtResult<()> {
+    host.io_eprintln(&crate::display::debug_value(value))
+}
+
 pub fn read_line(host: &mut dyn RtHost) -> RtResult<RtValue> {
     Ok(RtValue::String(host.io_read_line()?))
 }
 
+pub fn try_read_line(host: &mut dyn RtHost) -> RtResult<RtValue> {
+    Ok(RtValue::Option(match host.io_try_read_line()? {
+        Some(line) => RtOption::some(RtValue::String(line)),
+        None => RtOption::none(),
+    }))
+}
+
+pub fn read_all(host: &mut dyn RtHost) -> RtResult<RtValue> {
+    Ok(RtValue::String(host.io_read_all()?))
+}
+
+pub fn read_lines(host: &mut dyn RtHost) -> RtResult<RtValue> {
+    let lines = RtVec::new();
+    while let Some(line) = host.io_try_read_line()? {
+        lines.push(RtValue::String(line));
+    }
+    Ok(RtValue::Vec(lines))
+}
+
 pub fn format(args: &[RtValue]) -> RtResult<RtValue> {
     if args.is_empty() {
         return Err(crate::RtError::new(
@@ -34,31 +67,6 @@ pub fn printf(host: &mut dyn RtHost, args: &[RtValue]) -> RtResult<RtValue> {
     Ok(RtValue::Unit)
 }
 
-fn display_value(value: &RtValue) -> String {
-    match value {
-        RtValue::Int(value) => value.to_string(),
-        RtValue::Float(value) => value.to_string(),
-        RtValue::Bool(value) => value.to_string(),
-        RtValue::String(value) => value.as_str().to_owned(),
-        RtValue::Bytes(value) => format!("[bytes len={}]", value.len()),
-        RtValue::Option(value) => match &value.0 {
-            Some(inner) => format!("Some({})", display_value(inner)),
-            None => "None".to_owned(),
-        },
-        RtValue::Result(value) => match value {
-            crate::RtResultValue::Ok(inner) => format!("Ok({})", display_value(inner)),
-            crate::RtResultValue::Err(inner) => format!("Err({})", display_value(inner)),
-        },
-        RtValue::Array(_) => "[array]".to_owned(),
-        RtValue::Vec(_) => "[vec]".to_owned(),
-        RtValue::Map(_) => "[map]".to_owned(),
-        RtValue::Function(_) => "[function]".to_owned(),
-        RtValue::Handle(value) => format!("[handle {:?}#{}]", value.kind, value.id),
-        RtValue::Struct(value) => format!("[struct {}]", value.layout.name),
-        RtValue::Unit => String::new(),
-    }
-}
-
 fn apply_format(fmt: &str, args: &[RtValue]) -> RtResult<String> {
     let mut out = String::new();
     let mut chars = fmt.chars().peekable();
@@ -91,6 +99,7 @@ fn apply_format(fmt: &str, args: &[RtValue]) -> RtResult<String> {
             'f' => out.push_str(&value.expect_float()?.to_string()),
             'b' => out.push_str(&value.expect_bool()?.to_string()),
             's' => out.push_str(value.expect_string()?.as_str()),
+            'v' => out.push_str(&display_value(value)),
             _ => {
                 return Err(crate::RtError::new(
                     crate::RtErrorKind::InvalidArgument,
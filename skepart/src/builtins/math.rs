@@ -0,0 +1,26 @@
+use crate::{RtOption, RtValue};
+
+pub fn wrapping_add(left: i64, right: i64) -> RtValue {
+    RtValue::Int(left.wrapping_add(right))
+}
+
+pub fn wrapping_sub(left: i64, right: i64) -> RtValue {
+    RtValue::Int(left.wrapping_sub(right))
+}
+
+pub fn wrapping_mul(left: i64, right: i64) -> RtValue {
+    RtValue::Int(left.wrapping_mul(right))
+}
+
+/// `Some(left / right)`, or `None` when `right` is zero or the quotient
+/// overflows (`i64::MIN / -1`).
+pub fn checked_div(left: i64, right: i64) -> RtValue {
+    RtValue::Option(match left.checked_div(right) {
+        Some(value) => RtOption::some(RtValue::Int(value)),
+        None => RtOption::none(),
+    })
+}
+
+pub fn div_or(left: i64, right: i64, default: i64) -> RtValue {
+    RtValue::Int(left.checked_div(right).unwrap_or(default))
+}
@@ -1,20 +1,32 @@
 pub mod arr;
 pub mod bytes;
+pub mod char;
+pub mod csv;
 pub mod datetime;
+pub mod encoding;
 pub mod ffi;
 pub mod fs;
+pub mod hash;
 pub mod io;
 pub mod map;
+pub mod math;
 pub mod net;
 pub mod option;
 pub mod os;
 pub mod random;
 pub mod result;
+pub mod serde;
+pub mod store;
 pub mod str;
 pub mod task;
 pub mod vec;
 
-use crate::{NoopHost, RtError, RtErrorKind, RtFunctionRef, RtHost, RtResult, RtValue};
+use std::sync::Arc;
+
+use crate::{
+    NoopHost, RtError, RtErrorKind, RtFunctionRef, RtFunctionRegistry, RtHost, RtResult,
+    RtStructLayout, RtValue,
+};
 
 pub trait BuiltinRuntime {
     fn call_function(&mut self, function: RtFunctionRef, args: &[RtValue]) -> RtResult<RtValue>;
@@ -38,6 +50,12 @@ pub trait BuiltinContext {
         function: RtFunctionRef,
         args: &[RtValue],
     ) -> RtResult<crate::RtHandle>;
+
+    /// Layout of the program's struct `id`, for builtins that build structs.
+    /// Native code has none; its structs are named `Struct{id}`.
+    fn struct_layout(&mut self, _id: usize) -> Option<Arc<RtStructLayout>> {
+        None
+    }
 }
 
 struct NoopRuntime;
@@ -71,6 +89,30 @@ impl BuiltinContext for SplitContext<'_> {
     }
 }
 
+struct FunctionsContext<'a> {
+    host: &'a mut dyn RtHost,
+    functions: &'a RtFunctionRegistry,
+}
+
+impl BuiltinContext for FunctionsContext<'_> {
+    fn host(&mut self) -> &mut dyn RtHost {
+        self.host
+    }
+
+    fn call_function(&mut self, function: RtFunctionRef, args: &[RtValue]) -> RtResult<RtValue> {
+        self.functions.call(self.host, function, args)
+    }
+
+    fn spawn_function(
+        &mut self,
+        function: RtFunctionRef,
+        args: &[RtValue],
+    ) -> RtResult<crate::RtHandle> {
+        let value = self.call_function(function, args)?;
+        self.host.task_store_completed(value)
+    }
+}
+
 pub fn call(package: &str, name: &str, args: &[RtValue]) -> RtResult<RtValue> {
     let mut host = NoopHost::default();
     let mut runtime = NoopRuntime;
@@ -98,6 +140,19 @@ pub fn call_with_host_runtime(
     call_with_context(&mut ctx, package, name, args)
 }
 
+/// Calls a builtin whose function arguments (e.g. the mapper of `vec.map`) are
+/// refs from `functions`.
+pub fn call_with_functions(
+    host: &mut dyn RtHost,
+    functions: &RtFunctionRegistry,
+    package: &str,
+    name: &str,
+    args: &[RtValue],
+) -> RtResult<RtValue> {
+    let mut ctx = FunctionsContext { host, functions };
+    call_with_context(&mut ctx, package, name, args)
+}
+
 pub fn call_with_context(
     ctx: &mut dyn BuiltinContext,
     package: &str,
@@ -105,6 +160,71 @@ pub fn call_with_context(
     args: &[RtValue],
 ) -> RtResult<RtValue> {
     match (package, name, args) {
+        ("core", "panic", [message]) => Err(RtError::panic(message.expect_string()?.as_str())),
+        ("math", "wrappingAdd", [left, right]) => {
+            Ok(math::wrapping_add(left.expect_int()?, right.expect_int()?))
+        }
+        ("math", "wrappingSub", [left, right]) => {
+            Ok(math::wrapping_sub(left.expect_int()?, right.expect_int()?))
+        }
+        ("math", "wrappingMul", [left, right]) => {
+            Ok(math::wrapping_mul(left.expect_int()?, right.expect_int()?))
+        }
+        ("math", "checkedDiv", [left, right]) => {
+            Ok(math::checked_div(left.expect_int()?, right.expect_int()?))
+        }
+        ("math", "divOr", [left, right, default]) => Ok(math::div_or(
+            left.expect_int()?,
+            right.expect_int()?,
+            default.expect_int()?,
+        )),
+        ("serde", "toJson", [value, schema]) => {
+            serde::to_json(value, schema.expect_string()?.as_str())
+        }
+        ("serde", "fromJson", [text, schema]) => serde::from_json(
+            ctx,
+            text.expect_string()?.as_str(),
+            schema.expect_string()?.as_str(),
+        ),
+        ("serde", "encode", [value, schema]) => {
+            serde::encode(value, schema.expect_string()?.as_str())
+        }
+        ("serde", "decode", [data, schema]) => {
+            serde::decode(ctx, &data.expect_bytes()?, schema.expect_string()?.as_str())
+        }
+        ("char", "toInt", [value]) => Ok(char::to_int(value.expect_char()?)),
+        ("char", "fromInt", [value]) => Ok(char::from_int(value.expect_int()?)),
+        ("char", "toString", [value]) => Ok(char::to_string(value.expect_char()?)),
+        ("char", "fromString", [value]) => Ok(char::from_string(value.expect_string()?.as_str())),
+        ("char", "isDigit", [value]) => Ok(char::is_digit(value.expect_char()?)),
+        ("char", "isAlpha", [value]) => Ok(char::is_alpha(value.expect_char()?)),
+        ("char", "isWhitespace", [value]) => Ok(char::is_whitespace(value.expect_char()?)),
+        ("encoding", "base64Encode", [value]) => {
+            Ok(encoding::base64_encode(&value.expect_bytes()?))
+        }
+        ("encoding", "base64Decode", [text]) => {
+            Ok(encoding::base64_decode(text.expect_string()?.as_str()))
+        }
+        ("encoding", "hexEncode", [value]) => Ok(encoding::hex_encode(&value.expect_bytes()?)),
+        ("encoding", "hexDecode", [text]) => {
+            Ok(encoding::hex_decode(text.expect_string()?.as_str()))
+        }
+        ("encoding", "urlEncode", [text]) => {
+            Ok(encoding::url_encode(text.expect_string()?.as_str()))
+        }
+        ("encoding", "urlDecode", [text]) => {
+            Ok(encoding::url_decode(text.expect_string()?.as_str()))
+        }
+        ("encoding", "isValidUtf8", [value]) => Ok(encoding::is_valid_utf8(&value.expect_bytes()?)),
+        ("csv", "parse", [text]) => csv::parse(text.expect_string()?.as_str()),
+        ("csv", "parseWithHeader", [text]) => {
+            csv::parse_with_header(text.expect_string()?.as_str())
+        }
+        ("csv", "stringify", [rows]) => csv::stringify(&rows.expect_vec()?),
+        ("hash", "sha256", [value]) => Ok(hash::sha256(&hash::input(value)?)),
+        ("hash", "md5", [value]) => Ok(hash::md5(&hash::input(value)?)),
+        ("hash", "crc32", [value]) => Ok(hash::crc32(&hash::input(value)?)),
+        ("hash", "fnv64", [value]) => Ok(hash::fnv64(&hash::input(value)?)),
         ("bytes", "fromString", [value]) => bytes::from_string(value.expect_string()?.as_str()),
         ("bytes", "toString", [value]) => bytes::to_string(&value.expect_bytes()?),
         ("bytes", "len", [value]) => Ok(bytes::len(&value.expect_bytes()?)),
@@ -123,6 +243,15 @@ pub fn call_with_context(
         ("bytes", "append", [left, right]) => {
             Ok(bytes::append(&left.expect_bytes()?, &right.expect_bytes()?))
         }
+        ("bytes", "toHex", [value]) => Ok(bytes::to_hex(&value.expect_bytes()?)),
+        ("bytes", "fromHex", [text]) => bytes::from_hex(text.expect_string()?.as_str()),
+        ("bytes", "encode", [text, encoding]) => bytes::encode(
+            text.expect_string()?.as_str(),
+            encoding.expect_string()?.as_str(),
+        ),
+        ("bytes", "decode", [value, encoding]) => {
+            bytes::decode(&value.expect_bytes()?, encoding.expect_string()?.as_str())
+        }
         ("option", "some", [value]) => Ok(option::some(value)),
         ("option", "none", []) => Ok(option::none()),
         ("option", "isSome", [value]) => Ok(option::is_some(&value.expect_option()?)),
@@ -172,14 +301,39 @@ pub fn call_with_context(
             usize::try_from(end.expect_int()?)
                 .map_err(|_| RtError::new(RtErrorKind::IndexOutOfBounds, "negative slice end"))?,
         ),
+        ("str", "__index", [value, index]) => Ok(RtValue::String(str::char_at(
+            &value.expect_string()?,
+            index.expect_int()?,
+        )?)),
+        ("str", "__slice", [value, start, end]) => Ok(RtValue::String(str::slice_strict(
+            &value.expect_string()?,
+            start.expect_int()?,
+            end.expect_int()?,
+        )?)),
         ("arr", "len", [array]) => Ok(RtValue::Int(arr::len(&array.expect_array()?))),
         ("arr", "isEmpty", [array]) => Ok(RtValue::Bool(arr::is_empty(&array.expect_array()?))),
+        ("arr", "contains", [array, needle]) => {
+            Ok(RtValue::Bool(arr::contains(&array.expect_array()?, needle)))
+        }
+        ("arr", "indexOf", [array, needle]) => {
+            Ok(RtValue::Int(arr::index_of(&array.expect_array()?, needle)))
+        }
+        ("arr", "count", [array, needle]) => {
+            Ok(RtValue::Int(arr::count(&array.expect_array()?, needle)))
+        }
         ("arr", "first", [array]) => Ok(arr::first(&array.expect_array()?)),
         ("arr", "last", [array]) => Ok(arr::last(&array.expect_array()?)),
         ("arr", "join", [array, sep]) => Ok(RtValue::String(arr::join(
             &array.expect_array()?,
             &sep.expect_string()?,
         )?)),
+        ("arr", "map", [array, f]) => arr::map(ctx, &array.expect_array()?, f.expect_function()?),
+        ("arr", "filter", [array, predicate]) => {
+            arr::filter(ctx, &array.expect_array()?, predicate.expect_function()?)
+        }
+        ("arr", "sortBy", [array, cmp]) => {
+            arr::sort_by(ctx, &array.expect_array()?, cmp.expect_function()?)
+        }
         ("vec", "new", []) => Ok(RtValue::Vec(vec::new())),
         ("vec", "len", [value]) => Ok(RtValue::Int(vec::len(&value.expect_vec()?))),
         ("vec", "push", [vec_value, value]) => {
@@ -199,6 +353,22 @@ pub fn call_with_context(
             )?;
             Ok(RtValue::Unit)
         }
+        ("vec", "map", [vec_value, f]) => {
+            vec::map(ctx, &vec_value.expect_vec()?, f.expect_function()?)
+        }
+        ("vec", "filter", [vec_value, predicate]) => {
+            vec::filter(ctx, &vec_value.expect_vec()?, predicate.expect_function()?)
+        }
+        ("vec", "reduce", [vec_value, init, f]) => vec::reduce(
+            ctx,
+            &vec_value.expect_vec()?,
+            init.clone(),
+            f.expect_function()?,
+        ),
+        ("vec", "sort", [vec_value, cmp]) => {
+            vec::sort(ctx, &vec_value.expect_vec()?, cmp.expect_function()?)?;
+            Ok(RtValue::Unit)
+        }
         ("vec", "delete", [vec_value, index]) => vec::delete(
             &vec_value.expect_vec()?,
             usize::try_from(index.expect_int()?)
@@ -212,6 +382,14 @@ pub fn call_with_context(
             io::println(ctx.host(), value)?;
             Ok(RtValue::Unit)
         }
+        ("io", "eprint", [value]) => {
+            io::eprint(ctx.host(), value)?;
+            Ok(RtValue::Unit)
+        }
+        ("io", "eprintln", [value]) => {
+            io::eprintln(ctx.host(), value)?;
+            Ok(RtValue::Unit)
+        }
         ("io", "printInt", [value]) => {
             io::print(ctx.host(), &RtValue::Int(value.expect_int()?))?;
             Ok(RtValue::Unit)
@@ -228,9 +406,20 @@ pub fn call_with_context(
             io::print(ctx.host(), &RtValue::String(value.expect_string()?))?;
             Ok(RtValue::Unit)
         }
+        ("io", "printAny", [value]) => {
+            io::print(ctx.host(), value)?;
+            Ok(RtValue::Unit)
+        }
+        ("io", "debug", [value]) => {
+            io::debug(ctx.host(), value)?;
+            Ok(RtValue::Unit)
+        }
         ("io", "format", args) => io::format(args),
         ("io", "printf", args) => io::printf(ctx.host(), args),
         ("io", "readLine", []) => io::read_line(ctx.host()),
+        ("io", "tryReadLine", []) => io::try_read_line(ctx.host()),
+        ("io", "readAll", []) => io::read_all(ctx.host()),
+        ("io", "readLines", []) => io::read_lines(ctx.host()),
         ("datetime", "nowUnix", []) => datetime::now_unix(ctx.host()),
         ("datetime", "nowMillis", []) => datetime::now_millis(ctx.host()),
         ("datetime", "fromUnix", [value]) => datetime::from_unix(ctx.host(), value.expect_int()?),
@@ -240,6 +429,38 @@ pub fn call_with_context(
         ("datetime", "parseUnix", [value]) => {
             datetime::parse_unix(ctx.host(), value.expect_string()?.as_str())
         }
+        ("datetime", "format", [value, pattern]) => datetime::format(
+            ctx.host(),
+            value.expect_int()?,
+            pattern.expect_string()?.as_str(),
+        ),
+        ("datetime", "parse", [pattern, value]) => datetime::parse(
+            ctx.host(),
+            pattern.expect_string()?.as_str(),
+            value.expect_string()?.as_str(),
+        ),
+        ("datetime", "nowLocal", []) => datetime::now_local(ctx.host()),
+        ("datetime", "toLocal", [value]) => datetime::to_local(ctx.host(), value.expect_int()?),
+        ("datetime", "offsetMinutes", []) => datetime::offset_minutes(ctx.host()),
+        ("datetime", "formatLocal", [value, pattern]) => datetime::format_local(
+            ctx.host(),
+            "datetime.formatLocal",
+            value.expect_int()?,
+            pattern.expect_string()?.as_str(),
+        ),
+        ("datetime", "addDays", [value, days]) => {
+            datetime::add_days(value.expect_int()?, days.expect_int()?)
+        }
+        ("datetime", "addHours", [value, hours]) => {
+            datetime::add_hours(value.expect_int()?, hours.expect_int()?)
+        }
+        ("datetime", "diffSeconds", [later, earlier]) => {
+            datetime::diff_seconds(later.expect_int()?, earlier.expect_int()?)
+        }
+        ("datetime", "weekday", [value]) => datetime::weekday(value.expect_int()?),
+        ("datetime", "daysInMonth", [year, month]) => {
+            datetime::days_in_month_of(year.expect_int()?, month.expect_int()?)
+        }
         ("datetime", "year", [value]) => {
             datetime::component(ctx.host(), "year", value.expect_int()?)
         }
@@ -261,6 +482,12 @@ pub fn call_with_context(
             random::int(ctx.host(), min.expect_int()?, max.expect_int()?)
         }
         ("random", "float", []) => random::float(ctx.host()),
+        ("random", "choice", [array]) => random::choice(ctx.host(), &array.expect_array()?),
+        ("random", "shuffle", [array]) => random::shuffle(ctx.host(), &array.expect_array()?),
+        ("random", "bool", [probability]) => random::bool(ctx.host(), probability.expect_float()?),
+        ("random", "gaussian", [mean, std_dev]) => {
+            random::gaussian(ctx.host(), mean.expect_float()?, std_dev.expect_float()?)
+        }
         ("fs", "exists", [path]) => fs::exists(ctx.host(), path.expect_string()?.as_str()),
         ("fs", "readText", [path]) => fs::read_text(ctx.host(), path.expect_string()?.as_str()),
         ("fs", "writeText", [path, text]) => fs::write_text(
@@ -278,6 +505,22 @@ pub fn call_with_context(
         ("fs", "removeDirAll", [path]) => {
             fs::remove_dir_all(ctx.host(), path.expect_string()?.as_str())
         }
+        ("fs", "copyFile", [from, to]) => fs::copy_file(
+            ctx.host(),
+            from.expect_string()?.as_str(),
+            to.expect_string()?.as_str(),
+        ),
+        ("fs", "moveFile", [from, to]) => fs::move_file(
+            ctx.host(),
+            from.expect_string()?.as_str(),
+            to.expect_string()?.as_str(),
+        ),
+        ("fs", "readBytes", [path]) => fs::read_bytes(ctx.host(), path.expect_string()?.as_str()),
+        ("fs", "writeBytes", [path, data]) => fs::write_bytes(
+            ctx.host(),
+            path.expect_string()?.as_str(),
+            &data.expect_bytes()?,
+        ),
         ("fs", "join", [left, right]) => fs::join(
             ctx.host(),
             left.expect_string()?.as_str(),
@@ -445,6 +688,73 @@ pub fn call_with_context(
             ctx.host(),
             listener.expect_handle_kind(crate::RtHandleKind::Listener)?,
         ),
+        ("net", "bindUdp", [address]) => {
+            net::bind_udp(ctx.host(), address.expect_string()?.as_str())
+        }
+        ("net", "udpConnect", [socket, address]) => net::udp_connect(
+            ctx.host(),
+            socket.expect_handle_kind(crate::RtHandleKind::UdpSocket)?,
+            address.expect_string()?.as_str(),
+        ),
+        ("net", "send", [socket, data]) => net::send(
+            ctx.host(),
+            socket.expect_handle_kind(crate::RtHandleKind::UdpSocket)?,
+            &data.expect_bytes()?,
+        ),
+        ("net", "sendTo", [socket, address, data]) => net::send_to(
+            ctx.host(),
+            socket.expect_handle_kind(crate::RtHandleKind::UdpSocket)?,
+            address.expect_string()?.as_str(),
+            &data.expect_bytes()?,
+        ),
+        ("net", "recv", [socket]) => net::recv(
+            ctx.host(),
+            socket.expect_handle_kind(crate::RtHandleKind::UdpSocket)?,
+        ),
+        ("net", "recvFrom", [socket, max, tuple_id]) => {
+            let tuple_id = tuple_id.expect_int()? as usize;
+            let layout = ctx.struct_layout(tuple_id);
+            net::recv_from(
+                ctx.host(),
+                socket.expect_handle_kind(crate::RtHandleKind::UdpSocket)?,
+                max.expect_int()?,
+                tuple_id,
+                layout,
+            )
+        }
+        ("net", "udpLocalAddr", [socket]) => net::udp_local_addr(
+            ctx.host(),
+            socket.expect_handle_kind(crate::RtHandleKind::UdpSocket)?,
+        ),
+        ("net", "closeUdp", [socket]) => net::close_udp(
+            ctx.host(),
+            socket.expect_handle_kind(crate::RtHandleKind::UdpSocket)?,
+        ),
+        ("store", "open", [path]) => store::open(ctx.host(), path.expect_string()?.as_str()),
+        ("store", "get", [handle, key]) => store::get(
+            ctx.host(),
+            handle.expect_handle_kind(crate::RtHandleKind::Store)?,
+            key.expect_string()?.as_str(),
+        ),
+        ("store", "set", [handle, key, value]) => store::set(
+            ctx.host(),
+            handle.expect_handle_kind(crate::RtHandleKind::Store)?,
+            key.expect_string()?.as_str(),
+            value.expect_string()?.as_str(),
+        ),
+        ("store", "delete", [handle, key]) => store::delete(
+            ctx.host(),
+            handle.expect_handle_kind(crate::RtHandleKind::Store)?,
+            key.expect_string()?.as_str(),
+        ),
+        ("store", "keys", [handle]) => store::keys(
+            ctx.host(),
+            handle.expect_handle_kind(crate::RtHandleKind::Store)?,
+        ),
+        ("store", "close", [handle]) => store::close(
+            ctx.host(),
+            handle.expect_handle_kind(crate::RtHandleKind::Store)?,
+        ),
         ("task", "__testTask", [value]) => task::test_task(ctx.host(), value),
         ("task", "__testChannel", []) => task::test_channel(ctx.host()),
         ("task", "channel", []) => task::channel(ctx.host()),
@@ -486,9 +796,12 @@ pub fn call_with_context(
             program.expect_string()?.as_str(),
             &args.expect_string_vec()?,
         ),
-        _ => Err(RtError::new(
-            RtErrorKind::UnsupportedBuiltin,
-            format!("unsupported builtin `{package}.{name}`"),
-        )),
+        ("os", "execCapture", [program, args, options]) => os::exec_capture(
+            ctx.host(),
+            program.expect_string()?.as_str(),
+            &args.expect_string_vec()?,
+            &options.expect_map()?,
+        ),
+        _ => ctx.host().call_custom(package, name, args),
     }
 }
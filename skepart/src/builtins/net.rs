@@ -1,4 +1,6 @@
-use crate::{RtHost, RtResult, RtResultValue, RtString, RtValue};
+use std::sync::Arc;
+
+use crate::{RtHost, RtResult, RtResultValue, RtString, RtStruct, RtStructLayout, RtValue};
 
 pub fn test_socket(host: &mut dyn RtHost) -> RtResult<RtValue> {
     Ok(RtValue::Handle(
@@ -186,3 +188,99 @@ pub fn close_listener(host: &mut dyn RtHost, listener: crate::RtHandle) -> RtRes
     host.net_close_handle(listener)?;
     Ok(RtValue::Unit)
 }
+
+pub fn bind_udp(host: &mut dyn RtHost, address: &str) -> RtResult<RtValue> {
+    Ok(match host.net_bind_udp(address) {
+        Ok(handle) => RtValue::Result(RtResultValue::ok(RtValue::Handle(handle))),
+        Err(err) => RtValue::Result(RtResultValue::err(RtValue::String(RtString::from(
+            err.to_string(),
+        )))),
+    })
+}
+
+pub fn udp_connect(
+    host: &mut dyn RtHost,
+    socket: crate::RtHandle,
+    address: &str,
+) -> RtResult<RtValue> {
+    Ok(match host.net_udp_connect(socket, address) {
+        Ok(()) => RtValue::Result(RtResultValue::ok(RtValue::Unit)),
+        Err(err) => RtValue::Result(RtResultValue::err(RtValue::String(RtString::from(
+            err.to_string(),
+        )))),
+    })
+}
+
+pub fn send(
+    host: &mut dyn RtHost,
+    socket: crate::RtHandle,
+    data: &crate::RtBytes,
+) -> RtResult<RtValue> {
+    Ok(match host.net_send(socket, data) {
+        Ok(()) => RtValue::Result(RtResultValue::ok(RtValue::Unit)),
+        Err(err) => RtValue::Result(RtResultValue::err(RtValue::String(RtString::from(
+            err.to_string(),
+        )))),
+    })
+}
+
+pub fn send_to(
+    host: &mut dyn RtHost,
+    socket: crate::RtHandle,
+    address: &str,
+    data: &crate::RtBytes,
+) -> RtResult<RtValue> {
+    Ok(match host.net_send_to(socket, address, data) {
+        Ok(()) => RtValue::Result(RtResultValue::ok(RtValue::Unit)),
+        Err(err) => RtValue::Result(RtResultValue::err(RtValue::String(RtString::from(
+            err.to_string(),
+        )))),
+    })
+}
+
+pub fn recv(host: &mut dyn RtHost, socket: crate::RtHandle) -> RtResult<RtValue> {
+    Ok(match host.net_recv(socket) {
+        Ok(value) => RtValue::Result(RtResultValue::ok(RtValue::Bytes(value))),
+        Err(err) => RtValue::Result(RtResultValue::err(RtValue::String(RtString::from(
+            err.to_string(),
+        )))),
+    })
+}
+
+/// Returns `Ok((data, sender))` as the program's `(Bytes, String)` tuple
+/// struct `tuple_id`.
+pub fn recv_from(
+    host: &mut dyn RtHost,
+    socket: crate::RtHandle,
+    max: i64,
+    tuple_id: usize,
+    layout: Option<Arc<RtStructLayout>>,
+) -> RtResult<RtValue> {
+    Ok(match host.net_recv_from(socket, max) {
+        Ok((data, sender)) => {
+            let fields = vec![RtValue::Bytes(data), RtValue::String(sender)];
+            let tuple = match layout {
+                Some(layout) => RtStruct::new(layout, fields)?,
+                None => RtStruct::named(format!("Struct{tuple_id}"), fields)?,
+            };
+            RtValue::Result(RtResultValue::ok(RtValue::Struct(tuple)))
+        }
+        Err(err) => RtValue::Result(RtResultValue::err(RtValue::String(RtString::from(
+            err.to_string(),
+        )))),
+    })
+}
+
+pub fn udp_local_addr(host: &mut dyn RtHost, socket: crate::RtHandle) -> RtResult<RtValue> {
+    Ok(match host.net_udp_local_addr(socket) {
+        Ok(value) => RtValue::Result(RtResultValue::ok(RtValue::String(value))),
+        Err(err) => RtValue::Result(RtResultValue::err(RtValue::String(RtString::from(
+            err.to_string(),
+        )))),
+    })
+}
+
+pub fn close_udp(host: &mut dyn RtHost, socket: crate::RtHandle) -> RtResult<RtValue> {
+    host.net_close_handle(socket)?;
+    Ok(RtValue::Unit)
+}
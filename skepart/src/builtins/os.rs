@@ -59,3 +59,17 @@ pub fn exec_out(host: &mut dyn RtHost, program: &str, args: &[String]) -> RtResu
         Err(err) => RtResultValue::err(RtValue::String(err.to_string().into())),
     }))
 }
+
+pub fn exec_capture(
+    host: &mut dyn RtHost,
+    program: &str,
+    args: &[String],
+    options: &crate::RtMap,
+) -> RtResult<RtValue> {
+    Ok(RtValue::Result(
+        match host.os_exec_capture(program, args, options) {
+            Ok(response) => RtResultValue::ok(RtValue::Map(response)),
+            Err(err) => RtResultValue::err(RtValue::String(err.to_string().into())),
+        },
+    ))
+}
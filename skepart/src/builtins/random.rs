@@ -1,4 +1,4 @@
-use crate::{RtHost, RtResult, RtValue};
+use crate::{RtArray, RtError, RtErrorKind, RtHost, RtOption, RtResult, RtValue};
 
 pub fn seed(host: &mut dyn RtHost, value: i64) -> RtResult<RtValue> {
     host.random_seed(value)?;
@@ -12,3 +12,48 @@ pub fn int(host: &mut dyn RtHost, min: i64, max: i64) -> RtResult<RtValue> {
 pub fn float(host: &mut dyn RtHost) -> RtResult<RtValue> {
     Ok(RtValue::Float(host.random_float()?))
 }
+
+pub fn choice(host: &mut dyn RtHost, array: &RtArray) -> RtResult<RtValue> {
+    if array.is_empty() {
+        return Ok(RtValue::Option(RtOption::none()));
+    }
+    let index = host.random_int(0, array.len() as i64 - 1)?;
+    Ok(RtValue::Option(RtOption::some(array.get(index as usize)?)))
+}
+
+/// Fisher-Yates shuffle into a new array; `array` itself is left untouched.
+pub fn shuffle(host: &mut dyn RtHost, array: &RtArray) -> RtResult<RtValue> {
+    let mut items = array.items();
+    for i in (1..items.len()).rev() {
+        let j = host.random_int(0, i as i64)? as usize;
+        items.swap(i, j);
+    }
+    Ok(RtValue::Array(RtArray::new(items)))
+}
+
+pub fn bool(host: &mut dyn RtHost, probability: f64) -> RtResult<RtValue> {
+    if !(0.0..=1.0).contains(&probability) {
+        return Err(RtError::new(
+            RtErrorKind::InvalidArgument,
+            format!("random.bool probability must be in 0.0..=1.0, got {probability}"),
+        ));
+    }
+    Ok(RtValue::Bool(host.random_float()? < probability))
+}
+
+/// Box-Muller transform over two host draws.
+pub fn gaussian(host: &mut dyn RtHost, mean: f64, std_dev: f64) -> RtResult<RtValue> {
+    if !mean.is_finite() || !std_dev.is_finite() || std_dev < 0.0 {
+        return Err(RtError::new(
+            RtErrorKind::InvalidArgument,
+            format!(
+                "random.gaussian expects a finite mean and a finite, non-negative std, got {mean} and {std_dev}"
+            ),
+        ));
+    }
+    // `1 - u` keeps the logarithm's argument in (0, 1].
+    let u1 = 1.0 - host.random_float()?;
+    let u2 = host.random_float()?;
+    let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+    Ok(RtValue::Float(mean + std_dev * z))
+}
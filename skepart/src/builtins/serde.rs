@@ -0,0 +1,1225 @@
+//! `serde` builtins: JSON and a compact binary encoding of Skepa values.
+//!
+//! Runtime values carry no static type (native structs do not even know their
+//! field names), so lowering passes a schema describing the value's type as a
+//! hidden last argument:
+//!
+//! ```text
+//! schema := type ('|' def)*
+//! type   := 'i' | 'f' | 'b' | 'c' | 's' | 'y'        Int Float Bool Char String Bytes
+//!         | 'o' type | 'r' type type                  Option, Result
+//!         | 'v' type | 'm' type | 'a' N '.' type      Vec, Map, [T; N]
+//!         | '#' K '.'                                 the K-th struct def
+//! def    := ID '{' [name ':' type (',' name ':' type)*] '}'
+//!         | ID '(' [type (',' type)*] ')'             tuple
+//! ```
+//!
+//! `ID` is the program's struct id, used to build decoded structs with the same
+//! layout the program itself would give them.
+
+use super::BuiltinContext;
+use crate::{
+    RtArray, RtBytes, RtError, RtErrorKind, RtMap, RtOption, RtResult, RtResultValue, RtString,
+    RtStruct, RtValue, RtVec,
+};
+
+const BINARY_MAGIC: &[u8; 4] = b"SKS1";
+
+/// Most items a `Vec` of values that encode to no bytes (such as `[Int; 0]`) may
+/// hold. Other lengths are bounded by the input size, but these are not.
+const MAX_EMPTY_ITEMS: usize = 1 << 16;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    Int,
+    Float,
+    Bool,
+    Char,
+    String,
+    Bytes,
+    Option(Box<Shape>),
+    Result(Box<Shape>, Box<Shape>),
+    Vec(Box<Shape>),
+    Map(Box<Shape>),
+    Array(usize, Box<Shape>),
+    Struct(usize),
+}
+
+#[derive(Debug, Clone)]
+struct StructDef {
+    id: usize,
+    /// Field names, or `None` for a tuple.
+    names: Option<Vec<String>>,
+    fields: Vec<Shape>,
+}
+
+#[derive(Debug, Clone)]
+struct Schema {
+    root: Shape,
+    structs: Vec<StructDef>,
+}
+
+impl Schema {
+    fn parse(text: &str) -> RtResult<Self> {
+        let mut parser = SchemaParser {
+            text: text.as_bytes(),
+            pos: 0,
+        };
+        let root = parser.shape()?;
+        let mut structs = Vec::new();
+        while parser.eat(b'|') {
+            structs.push(parser.def()?);
+        }
+        if parser.pos != parser.text.len() {
+            return Err(malformed_schema());
+        }
+        let schema = Self { root, structs };
+        if !schema.refs_valid(&schema.root)
+            || schema
+                .structs
+                .iter()
+                .any(|def| !def.fields.iter().all(|field| schema.refs_valid(field)))
+        {
+            return Err(malformed_schema());
+        }
+        Ok(schema)
+    }
+
+    /// Fewest bytes a value of `shape` encodes to.
+    fn min_encoded_len(&self, shape: &Shape) -> usize {
+        match shape {
+            Shape::Float => 8,
+            Shape::Array(len, inner) => len.saturating_mul(self.min_encoded_len(inner)),
+            Shape::Struct(index) => self.structs[*index]
+                .fields
+                .iter()
+                .map(|field| self.min_encoded_len(field))
+                .fold(0, usize::saturating_add),
+            _ => 1,
+        }
+    }
+
+    fn refs_valid(&self, shape: &Shape) -> bool {
+        match shape {
+            Shape::Option(inner) | Shape::Vec(inner) | Shape::Map(inner) => self.refs_valid(inner),
+            Shape::Array(_, inner) => self.refs_valid(inner),
+            Shape::Result(ok, err) => self.refs_valid(ok) && self.refs_valid(err),
+            Shape::Struct(index) => *index < self.structs.len(),
+            _ => true,
+        }
+    }
+
+    /// The schema without struct ids: the same for one type across programs,
+    /// so binary data written by one build can be read by the next.
+    fn fingerprint(&self) -> String {
+        let mut out = String::new();
+        write_shape(&self.root, &mut out);
+        for def in &self.structs {
+            out.push('|');
+            match &def.names {
+                Some(names) => {
+                    out.push('{');
+                    for (index, (name, field)) in names.iter().zip(&def.fields).enumerate() {
+                        if index > 0 {
+                            out.push(',');
+                        }
+                        out.push_str(name);
+                        out.push(':');
+                        write_shape(field, &mut out);
+                    }
+                    out.push('}');
+                }
+                None => {
+                    out.push('(');
+                    for (index, field) in def.fields.iter().enumerate() {
+                        if index > 0 {
+                            out.push(',');
+                        }
+                        write_shape(field, &mut out);
+                    }
+                    out.push(')');
+                }
+            }
+        }
+        out
+    }
+
+    fn make_struct(
+        &self,
+        ctx: &mut dyn BuiltinContext,
+        def: &StructDef,
+        fields: Vec<RtValue>,
+    ) -> RtResult<RtValue> {
+        let value = match ctx.struct_layout(def.id) {
+            Some(layout) => RtStruct::new(layout, fields)?,
+            None => RtStruct::named(format!("Struct{}", def.id), fields)?,
+        };
+        Ok(RtValue::Struct(value))
+    }
+}
+
+fn write_shape(shape: &Shape, out: &mut String) {
+    match shape {
+        Shape::Int => out.push('i'),
+        Shape::Float => out.push('f'),
+        Shape::Bool => out.push('b'),
+        Shape::Char => out.push('c'),
+        Shape::String => out.push('s'),
+        Shape::Bytes => out.push('y'),
+        Shape::Option(inner) => {
+            out.push('o');
+            write_shape(inner, out);
+        }
+        Shape::Result(ok, err) => {
+            out.push('r');
+            write_shape(ok, out);
+            write_shape(err, out);
+        }
+        Shape::Vec(inner) => {
+            out.push('v');
+            write_shape(inner, out);
+        }
+        Shape::Map(inner) => {
+            out.push('m');
+            write_shape(inner, out);
+        }
+        Shape::Array(size, inner) => {
+            out.push_str(&format!("a{size}."));
+            write_shape(inner, out);
+        }
+        Shape::Struct(index) => out.push_str(&format!("#{index}.")),
+    }
+}
+
+fn malformed_schema() -> RtError {
+    RtError::new(RtErrorKind::InvalidArgument, "malformed serde schema")
+}
+
+struct SchemaParser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl SchemaParser<'_> {
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.text.get(self.pos) == Some(&byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> RtResult<()> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(malformed_schema())
+        }
+    }
+
+    fn number(&mut self) -> RtResult<usize> {
+        let start = self.pos;
+        while self.text.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.text[start..self.pos])
+            .ok()
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(malformed_schema)
+    }
+
+    fn shape(&mut self) -> RtResult<Shape> {
+        let Some(&tag) = self.text.get(self.pos) else {
+            return Err(malformed_schema());
+        };
+        self.pos += 1;
+        Ok(match tag {
+            b'i' => Shape::Int,
+            b'f' => Shape::Float,
+            b'b' => Shape::Bool,
+            b'c' => Shape::Char,
+            b's' => Shape::String,
+            b'y' => Shape::Bytes,
+            b'o' => Shape::Option(Box::new(self.shape()?)),
+            b'r' => Shape::Result(Box::new(self.shape()?), Box::new(self.shape()?)),
+            b'v' => Shape::Vec(Box::new(self.shape()?)),
+            b'm' => Shape::Map(Box::new(self.shape()?)),
+            b'a' => {
+                let size = self.number()?;
+                self.expect(b'.')?;
+                Shape::Array(size, Box::new(self.shape()?))
+            }
+            b'#' => {
+                let index = self.number()?;
+                self.expect(b'.')?;
+                Shape::Struct(index)
+            }
+            _ => return Err(malformed_schema()),
+        })
+    }
+
+    fn def(&mut self) -> RtResult<StructDef> {
+        let id = self.number()?;
+        let mut names = Vec::new();
+        let mut fields = Vec::new();
+        if self.eat(b'(') {
+            if !self.eat(b')') {
+                loop {
+                    fields.push(self.shape()?);
+                    if self.eat(b')') {
+                        break;
+                    }
+                    self.expect(b',')?;
+                }
+            }
+            return Ok(StructDef {
+                id,
+                names: None,
+                fields,
+            });
+        }
+        self.expect(b'{')?;
+        if !self.eat(b'}') {
+            loop {
+                let start = self.pos;
+                while self
+                    .text
+                    .get(self.pos)
+                    .is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'_')
+                {
+                    self.pos += 1;
+                }
+                if start == self.pos {
+                    return Err(malformed_schema());
+                }
+                names.push(String::from_utf8_lossy(&self.text[start..self.pos]).into_owned());
+                self.expect(b':')?;
+                fields.push(self.shape()?);
+                if self.eat(b'}') {
+                    break;
+                }
+                self.expect(b',')?;
+            }
+        }
+        Ok(StructDef {
+            id,
+            names: Some(names),
+            fields,
+        })
+    }
+}
+
+fn serde_ok(value: RtValue) -> RtValue {
+    RtValue::Result(RtResultValue::ok(value))
+}
+
+fn serde_err(message: String) -> RtValue {
+    RtValue::Result(RtResultValue::err(RtValue::String(RtString::from(message))))
+}
+
+/// A value that does not have the shape the schema promises; lowering only
+/// passes a value together with the schema of its static type.
+fn shape_mismatch(expected: &str, got: &RtValue) -> RtError {
+    RtError::type_mismatch(format!(
+        "serde expected {expected} value, got {}",
+        got.type_name()
+    ))
+}
+
+fn struct_fields(value: &RtValue, def: &StructDef) -> RtResult<Vec<RtValue>> {
+    let RtValue::Struct(value) = value else {
+        return Err(shape_mismatch("a struct", value));
+    };
+    let fields = value.field_values();
+    if fields.len() != def.fields.len() {
+        return Err(RtError::new(
+            RtErrorKind::MissingField,
+            format!(
+                "serde expected {} struct fields, got {}",
+                def.fields.len(),
+                fields.len()
+            ),
+        ));
+    }
+    Ok(fields)
+}
+
+pub fn to_json(value: &RtValue, schema: &str) -> RtResult<RtValue> {
+    let schema = Schema::parse(schema)?;
+    let mut out = String::new();
+    write_json(&schema, &schema.root, value, &mut out)?;
+    Ok(RtValue::String(RtString::from(out)))
+}
+
+fn write_json(schema: &Schema, shape: &Shape, value: &RtValue, out: &mut String) -> RtResult<()> {
+    match (shape, value) {
+        (Shape::Int, RtValue::Int(v)) => out.push_str(&v.to_string()),
+        (Shape::Float, RtValue::Float(v)) => {
+            if !v.is_finite() {
+                return Err(RtError::new(
+                    RtErrorKind::InvalidArgument,
+                    format!("serde.toJson cannot encode non-finite Float `{v}`"),
+                ));
+            }
+            out.push_str(&format!("{v:?}"));
+        }
+        (Shape::Bool, RtValue::Bool(v)) => out.push_str(if *v { "true" } else { "false" }),
+        (Shape::Char, RtValue::Char(v)) => write_json_string(&v.to_string(), out),
+        (Shape::String, RtValue::String(v)) => write_json_string(v.as_str(), out),
+        (Shape::Bytes, RtValue::Bytes(v)) => {
+            out.push('[');
+            for (index, byte) in v.as_slice().iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&byte.to_string());
+            }
+            out.push(']');
+        }
+        (Shape::Option(inner), RtValue::Option(v)) => match &v.0 {
+            Some(item) => write_json(schema, inner, item, out)?,
+            None => out.push_str("null"),
+        },
+        (Shape::Result(ok, err), RtValue::Result(v)) => match v {
+            RtResultValue::Ok(item) => {
+                out.push_str("{\"ok\":");
+                write_json(schema, ok, item, out)?;
+                out.push('}');
+            }
+            RtResultValue::Err(item) => {
+                out.push_str("{\"err\":");
+                write_json(schema, err, item, out)?;
+                out.push('}');
+            }
+        },
+        (Shape::Vec(inner), RtValue::Vec(v)) => write_json_list(schema, inner, &v.items(), out)?,
+        (Shape::Array(_, inner), RtValue::Array(v)) => {
+            write_json_list(schema, inner, &v.items(), out)?
+        }
+        (Shape::Map(inner), RtValue::Map(v)) => {
+            out.push('{');
+            for (index, key) in v.keys().iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_json_string(key, out);
+                out.push(':');
+                let item = v.get(key).unwrap_or(RtValue::Unit);
+                write_json(schema, inner, &item, out)?;
+            }
+            out.push('}');
+        }
+        (Shape::Struct(index), _) => {
+            let def = &schema.structs[*index];
+            let fields = struct_fields(value, def)?;
+            match &def.names {
+                Some(names) => {
+                    out.push('{');
+                    for (index, ((name, shape), field)) in
+                        names.iter().zip(&def.fields).zip(&fields).enumerate()
+                    {
+                        if index > 0 {
+                            out.push(',');
+                        }
+                        write_json_string(name, out);
+                        out.push(':');
+                        write_json(schema, shape, field, out)?;
+                    }
+                    out.push('}');
+                }
+                None => {
+                    out.push('[');
+                    for (index, (shape, field)) in def.fields.iter().zip(&fields).enumerate() {
+                        if index > 0 {
+                            out.push(',');
+                        }
+                        write_json(schema, shape, field, out)?;
+                    }
+                    out.push(']');
+                }
+            }
+        }
+        (shape, value) => return Err(shape_mismatch(shape_label(shape), value)),
+    }
+    Ok(())
+}
+
+fn write_json_list(
+    schema: &Schema,
+    shape: &Shape,
+    items: &[RtValue],
+    out: &mut String,
+) -> RtResult<()> {
+    out.push('[');
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        write_json(schema, shape, item, out)?;
+    }
+    out.push(']');
+    Ok(())
+}
+
+fn write_json_string(text: &str, out: &mut String) {
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if u32::from(ch) < 0x20 => out.push_str(&format!("\\u{:04x}", u32::from(ch))),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+fn shape_label(shape: &Shape) -> &'static str {
+    match shape {
+        Shape::Int => "Int",
+        Shape::Float => "Float",
+        Shape::Bool => "Bool",
+        Shape::Char => "Char",
+        Shape::String => "String",
+        Shape::Bytes => "Bytes",
+        Shape::Option(_) => "Option",
+        Shape::Result(_, _) => "Result",
+        Shape::Vec(_) => "Vec",
+        Shape::Map(_) => "Map",
+        Shape::Array(_, _) => "Array",
+        Shape::Struct(_) => "struct",
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Json {
+    Null,
+    Bool(bool),
+    /// The number's source text, so integers keep their full precision.
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool(_) => "a boolean",
+            Self::Number(_) => "a number",
+            Self::String(_) => "a string",
+            Self::Array(_) => "an array",
+            Self::Object(_) => "an object",
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    text: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+/// Nesting limit for JSON input, so hostile data cannot exhaust the stack.
+const MAX_JSON_DEPTH: usize = 512;
+
+impl JsonParser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("invalid JSON at byte {}: {message}", self.pos)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_ws();
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn document(&mut self) -> Result<Json, String> {
+        let value = self.value()?;
+        self.skip_ws();
+        if self.pos != self.text.len() {
+            return Err(self.error("unexpected trailing characters"));
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_ws();
+        match self.peek() {
+            Some(b'n') => self.keyword("null", Json::Null),
+            Some(b't') => self.keyword("true", Json::Bool(true)),
+            Some(b'f') => self.keyword("false", Json::Bool(false)),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b'[') => {
+                self.enter()?;
+                self.pos += 1;
+                let mut items = Vec::new();
+                if !self.eat(b']') {
+                    loop {
+                        items.push(self.value()?);
+                        if self.eat(b']') {
+                            break;
+                        }
+                        if !self.eat(b',') {
+                            return Err(self.error("expected `,` or `]`"));
+                        }
+                    }
+                }
+                self.depth -= 1;
+                Ok(Json::Array(items))
+            }
+            Some(b'{') => {
+                self.enter()?;
+                self.pos += 1;
+                let mut entries = Vec::new();
+                if !self.eat(b'}') {
+                    loop {
+                        self.skip_ws();
+                        if self.peek() != Some(b'"') {
+                            return Err(self.error("expected an object key"));
+                        }
+                        let key = self.string()?;
+                        if !self.eat(b':') {
+                            return Err(self.error("expected `:`"));
+                        }
+                        entries.push((key, self.value()?));
+                        if self.eat(b'}') {
+                            break;
+                        }
+                        if !self.eat(b',') {
+                            return Err(self.error("expected `,` or `}`"));
+                        }
+                    }
+                }
+                self.depth -= 1;
+                Ok(Json::Object(entries))
+            }
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("expected a value")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_JSON_DEPTH {
+            return Err(self.error("nesting is too deep"));
+        }
+        Ok(())
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        if self.text[self.pos..].starts_with(word) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a value"))
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        let digits = |parser: &mut Self| {
+            let from = parser.pos;
+            while parser.peek().is_some_and(|b| b.is_ascii_digit()) {
+                parser.pos += 1;
+            }
+            parser.pos > from
+        };
+        if self.peek() == Some(b'0') {
+            self.pos += 1;
+        } else if !digits(self) {
+            return Err(self.error("expected digits"));
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !digits(self) {
+                return Err(self.error("expected digits after `.`"));
+            }
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                return Err(self.error("expected exponent digits"));
+            }
+        }
+        Ok(Json::Number(self.text[start..self.pos].to_string()))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let Some(ch) = self.text[self.pos..].chars().next() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += ch.len_utf8();
+            match ch {
+                '"' => return Ok(out),
+                '\\' => {
+                    let Some(escape) = self.peek() else {
+                        return Err(self.error("unterminated string"));
+                    };
+                    self.pos += 1;
+                    match escape {
+                        b'"' => out.push('"'),
+                        b'\\' => out.push('\\'),
+                        b'/' => out.push('/'),
+                        b'b' => out.push('\u{8}'),
+                        b'f' => out.push('\u{c}'),
+                        b'n' => out.push('\n'),
+                        b'r' => out.push('\r'),
+                        b't' => out.push('\t'),
+                        b'u' => out.push(self.unicode_escape()?),
+                        _ => return Err(self.error("invalid escape")),
+                    }
+                }
+                ch if u32::from(ch) < 0x20 => {
+                    return Err(self.error("control character in string"));
+                }
+                ch => out.push(ch),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        let value =
+            u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(value)
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.text[self.pos..].starts_with("\\u") {
+                return Err(self.error("unpaired surrogate"));
+            }
+            self.pos += 2;
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("unpaired surrogate"))
+    }
+}
+
+pub fn from_json(ctx: &mut dyn BuiltinContext, text: &str, schema: &str) -> RtResult<RtValue> {
+    let schema = Schema::parse(schema)?;
+    let mut parser = JsonParser {
+        text,
+        pos: 0,
+        depth: 0,
+    };
+    let json = match parser.document() {
+        Ok(json) => json,
+        Err(message) => return Ok(serde_err(message)),
+    };
+    let mut path = String::from("$");
+    Ok(
+        match read_json(ctx, &schema, &schema.root, &json, &mut path)? {
+            Ok(value) => serde_ok(value),
+            Err(message) => serde_err(message),
+        },
+    )
+}
+
+/// Converts `json` to a value of `shape`. The outer `RtResult` is for runtime
+/// failures; the inner error is a mismatch reported to the program.
+fn read_json(
+    ctx: &mut dyn BuiltinContext,
+    schema: &Schema,
+    shape: &Shape,
+    json: &Json,
+    path: &mut String,
+) -> RtResult<Result<RtValue, String>> {
+    let mismatch = |path: &str, expected: &str| {
+        Ok(Err(format!(
+            "type mismatch at `{path}`: expected {expected}, got {}",
+            json.kind()
+        )))
+    };
+    let value = match (shape, json) {
+        (Shape::Int, Json::Number(text)) => match text.parse::<i64>() {
+            Ok(value) => RtValue::Int(value),
+            Err(_) => return mismatch(path, "Int"),
+        },
+        (Shape::Float, Json::Number(text)) => match text.parse::<f64>() {
+            Ok(value) if value.is_finite() => RtValue::Float(value),
+            _ => return mismatch(path, "Float"),
+        },
+        (Shape::Bool, Json::Bool(value)) => RtValue::Bool(*value),
+        (Shape::Char, Json::String(text)) => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) => RtValue::Char(ch),
+                _ => return mismatch(path, "Char"),
+            }
+        }
+        (Shape::String, Json::String(text)) => RtValue::String(RtString::from(text.as_str())),
+        (Shape::Bytes, Json::Array(items)) => {
+            let mut data = Vec::with_capacity(items.len());
+            for item in items {
+                match item {
+                    Json::Number(text) if text.parse::<u8>().is_ok() => {
+                        data.push(text.parse::<u8>().unwrap_or_default());
+                    }
+                    _ => return mismatch(path, "Bytes"),
+                }
+            }
+            RtValue::Bytes(RtBytes::from(data))
+        }
+        (Shape::Option(_), Json::Null) => RtValue::Option(RtOption::none()),
+        (Shape::Option(inner), _) => match read_json(ctx, schema, inner, json, path)? {
+            Ok(value) => RtValue::Option(RtOption::some(value)),
+            Err(message) => return Ok(Err(message)),
+        },
+        (Shape::Result(ok, err), Json::Object(entries)) if entries.len() == 1 => {
+            let (key, item) = &entries[0];
+            let (inner, wrap): (&Shape, fn(RtValue) -> RtResultValue) = match key.as_str() {
+                "ok" => (ok, RtResultValue::ok),
+                "err" => (err, RtResultValue::err),
+                _ => return mismatch(path, "Result"),
+            };
+            let len = path.len();
+            path.push('.');
+            path.push_str(key);
+            let value = read_json(ctx, schema, inner, item, path)?;
+            path.truncate(len);
+            match value {
+                Ok(value) => RtValue::Result(wrap(value)),
+                Err(message) => return Ok(Err(message)),
+            }
+        }
+        (Shape::Vec(inner), Json::Array(items)) => {
+            match read_json_items(ctx, schema, inner, items, path)? {
+                Ok(items) => {
+                    let vec = RtVec::new();
+                    vec.replace_items(items);
+                    RtValue::Vec(vec)
+                }
+                Err(message) => return Ok(Err(message)),
+            }
+        }
+        (Shape::Array(size, inner), Json::Array(items)) => {
+            if items.len() != *size {
+                return Ok(Err(format!(
+                    "type mismatch at `{path}`: expected {size} array elements, got {}",
+                    items.len()
+                )));
+            }
+            match read_json_items(ctx, schema, inner, items, path)? {
+                Ok(items) => RtValue::Array(RtArray::new(items)),
+                Err(message) => return Ok(Err(message)),
+            }
+        }
+        (Shape::Map(inner), Json::Object(entries)) => {
+            let map = RtMap::new();
+            for (key, item) in entries {
+                let len = path.len();
+                path.push_str(&format!("[{key:?}]"));
+                let value = read_json(ctx, schema, inner, item, path)?;
+                path.truncate(len);
+                match value {
+                    Ok(value) => map.insert(key.as_str(), value),
+                    Err(message) => return Ok(Err(message)),
+                }
+            }
+            RtValue::Map(map)
+        }
+        (Shape::Struct(index), _) => {
+            let def = &schema.structs[*index];
+            let fields = match (&def.names, json) {
+                (Some(names), Json::Object(entries)) => {
+                    if let Some((key, _)) = entries.iter().find(|(key, _)| !names.contains(key)) {
+                        return Ok(Err(format!("unknown field `{key}` at `{path}`")));
+                    }
+                    let mut fields = Vec::with_capacity(names.len());
+                    for (name, field) in names.iter().zip(&def.fields) {
+                        let Some((_, item)) = entries.iter().rev().find(|(key, _)| key == name)
+                        else {
+                            return Ok(Err(format!("missing field `{name}` at `{path}`")));
+                        };
+                        let len = path.len();
+                        path.push('.');
+                        path.push_str(name);
+                        let value = read_json(ctx, schema, field, item, path)?;
+                        path.truncate(len);
+                        match value {
+                            Ok(value) => fields.push(value),
+                            Err(message) => return Ok(Err(message)),
+                        }
+                    }
+                    fields
+                }
+                (None, Json::Array(items)) if items.len() == def.fields.len() => {
+                    let mut fields = Vec::with_capacity(items.len());
+                    for (index, (field, item)) in def.fields.iter().zip(items).enumerate() {
+                        let len = path.len();
+                        path.push_str(&format!("[{index}]"));
+                        let value = read_json(ctx, schema, field, item, path)?;
+                        path.truncate(len);
+                        match value {
+                            Ok(value) => fields.push(value),
+                            Err(message) => return Ok(Err(message)),
+                        }
+                    }
+                    fields
+                }
+                (Some(_), _) => return mismatch(path, "an object"),
+                (None, _) => {
+                    return mismatch(path, &format!("an array of {}", def.fields.len()));
+                }
+            };
+            schema.make_struct(ctx, def, fields)?
+        }
+        (shape, _) => return mismatch(path, shape_label(shape)),
+    };
+    Ok(Ok(value))
+}
+
+fn read_json_items(
+    ctx: &mut dyn BuiltinContext,
+    schema: &Schema,
+    shape: &Shape,
+    items: &[Json],
+    path: &mut String,
+) -> RtResult<Result<Vec<RtValue>, String>> {
+    let mut values = Vec::with_capacity(items.len());
+    for (index, item) in items.iter().enumerate() {
+        let len = path.len();
+        path.push_str(&format!("[{index}]"));
+        let value = read_json(ctx, schema, shape, item, path)?;
+        path.truncate(len);
+        match value {
+            Ok(value) => values.push(value),
+            Err(message) => return Ok(Err(message)),
+        }
+    }
+    Ok(Ok(values))
+}
+
+/// Binary form: `SKS1`, the schema fingerprint as a length-prefixed string, then
+/// the value. Lengths and Ints are LEB128 varints (Ints zigzag-encoded).
+pub fn encode(value: &RtValue, schema: &str) -> RtResult<RtValue> {
+    let schema = Schema::parse(schema)?;
+    let mut out = BINARY_MAGIC.to_vec();
+    write_bytes(schema.fingerprint().as_bytes(), &mut out);
+    write_binary(&schema, &schema.root, value, &mut out)?;
+    Ok(RtValue::Bytes(RtBytes::from(out)))
+}
+
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_bytes(data: &[u8], out: &mut Vec<u8>) {
+    write_varint(data.len() as u64, out);
+    out.extend_from_slice(data);
+}
+
+fn write_binary(
+    schema: &Schema,
+    shape: &Shape,
+    value: &RtValue,
+    out: &mut Vec<u8>,
+) -> RtResult<()> {
+    match (shape, value) {
+        (Shape::Int, RtValue::Int(v)) => write_varint(((v << 1) ^ (v >> 63)) as u64, out),
+        (Shape::Float, RtValue::Float(v)) => out.extend_from_slice(&v.to_le_bytes()),
+        (Shape::Bool, RtValue::Bool(v)) => out.push(u8::from(*v)),
+        (Shape::Char, RtValue::Char(v)) => write_varint(u64::from(u32::from(*v)), out),
+        (Shape::String, RtValue::String(v)) => write_bytes(v.as_str().as_bytes(), out),
+        (Shape::Bytes, RtValue::Bytes(v)) => write_bytes(v.as_slice(), out),
+        (Shape::Option(inner), RtValue::Option(v)) => match &v.0 {
+            Some(item) => {
+                out.push(1);
+                write_binary(schema, inner, item, out)?;
+            }
+            None => out.push(0),
+        },
+        (Shape::Result(ok, err), RtValue::Result(v)) => match v {
+            RtResultValue::Ok(item) => {
+                out.push(0);
+                write_binary(schema, ok, item, out)?;
+            }
+            RtResultValue::Err(item) => {
+                out.push(1);
+                write_binary(schema, err, item, out)?;
+            }
+        },
+        (Shape::Vec(inner), RtValue::Vec(v)) => {
+            let items = v.items();
+            if items.len() > MAX_EMPTY_ITEMS && schema.min_encoded_len(inner) == 0 {
+                return Err(RtError::new(
+                    RtErrorKind::InvalidArgument,
+                    format!(
+                        "serde.encode cannot write more than {MAX_EMPTY_ITEMS} empty values in one Vec"
+                    ),
+                ));
+            }
+            write_varint(items.len() as u64, out);
+            for item in &items {
+                write_binary(schema, inner, item, out)?;
+            }
+        }
+        (Shape::Array(_, inner), RtValue::Array(v)) => {
+            for item in v.iter() {
+                write_binary(schema, inner, &item, out)?;
+            }
+        }
+        (Shape::Map(inner), RtValue::Map(v)) => {
+            let keys = v.keys();
+            write_varint(keys.len() as u64, out);
+            for key in &keys {
+                write_bytes(key.as_bytes(), out);
+                let item = v.get(key).unwrap_or(RtValue::Unit);
+                write_binary(schema, inner, &item, out)?;
+            }
+        }
+        (Shape::Struct(index), _) => {
+            let def = &schema.structs[*index];
+            for (shape, field) in def.fields.iter().zip(struct_fields(value, def)?) {
+                write_binary(schema, shape, &field, out)?;
+            }
+        }
+        (shape, value) => return Err(shape_mismatch(shape_label(shape), value)),
+    }
+    Ok(())
+}
+
+struct BinaryReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl BinaryReader<'_> {
+    fn byte(&mut self) -> Result<u8, String> {
+        let byte = *self
+            .data
+            .get(self.pos)
+            .ok_or_else(|| "serde.decode found truncated data".to_string())?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&[u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| "serde.decode found truncated data".to_string())?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("serde.decode found an overlong integer".to_string())
+    }
+
+    /// Reads a length of items that each take at least `item_len` bytes, which
+    /// bounds preallocation.
+    fn len(&mut self, item_len: usize) -> Result<usize, String> {
+        let len = usize::try_from(self.varint()?).unwrap_or(usize::MAX);
+        let max = match item_len {
+            0 => MAX_EMPTY_ITEMS,
+            _ => (self.data.len() - self.pos) / item_len,
+        };
+        if len > max {
+            return Err("serde.decode found truncated data".to_string());
+        }
+        Ok(len)
+    }
+
+    fn text(&mut self) -> Result<String, String> {
+        let len = self.len(1)?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| "serde.decode found invalid UTF-8 text".to_string())
+    }
+
+    fn tag(&mut self) -> Result<bool, String> {
+        match self.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(format!("serde.decode found invalid tag {tag}")),
+        }
+    }
+}
+
+pub fn decode(ctx: &mut dyn BuiltinContext, data: &RtBytes, schema: &str) -> RtResult<RtValue> {
+    let schema = Schema::parse(schema)?;
+    let mut reader = BinaryReader {
+        data: data.as_slice(),
+        pos: 0,
+    };
+    let header = reader
+        .take(BINARY_MAGIC.len())
+        .ok()
+        .filter(|magic| *magic == BINARY_MAGIC);
+    if header.is_none() {
+        return Ok(serde_err(
+            "serde.decode expected data written by serde.encode".to_string(),
+        ));
+    }
+    match reader.text() {
+        Ok(fingerprint) if fingerprint == schema.fingerprint() => {}
+        Ok(_) => {
+            return Ok(serde_err(
+                "serde.decode found data encoded from a different type".to_string(),
+            ));
+        }
+        Err(message) => return Ok(serde_err(message)),
+    }
+    let value = read_binary(ctx, &schema, &schema.root, &mut reader)?;
+    Ok(match value {
+        Ok(_) if reader.pos != reader.data.len() => {
+            serde_err("serde.decode found trailing bytes".to_string())
+        }
+        Ok(value) => serde_ok(value),
+        Err(message) => serde_err(message),
+    })
+}
+
+fn read_binary(
+    ctx: &mut dyn BuiltinContext,
+    schema: &Schema,
+    shape: &Shape,
+    reader: &mut BinaryReader<'_>,
+) -> RtResult<Result<RtValue, String>> {
+    macro_rules! tri {
+        ($expr:expr) => {
+            match $expr {
+                Ok(value) => value,
+                Err(message) => return Ok(Err(message)),
+            }
+        };
+    }
+    let value = match shape {
+        Shape::Int => {
+            let raw = tri!(reader.varint());
+            RtValue::Int(((raw >> 1) as i64) ^ -((raw & 1) as i64))
+        }
+        Shape::Float => {
+            let bytes = tri!(reader.take(8));
+            let mut raw = [0u8; 8];
+            raw.copy_from_slice(bytes);
+            RtValue::Float(f64::from_le_bytes(raw))
+        }
+        Shape::Bool => RtValue::Bool(tri!(reader.tag())),
+        Shape::Char => {
+            let code = tri!(reader.varint());
+            let ch = u32::try_from(code).ok().and_then(char::from_u32);
+            RtValue::Char(tri!(
+                ch.ok_or_else(|| "serde.decode found an invalid Char".to_string())
+            ))
+        }
+        Shape::String => RtValue::String(RtString::from(tri!(reader.text()))),
+        Shape::Bytes => {
+            let len = tri!(reader.len(1));
+            RtValue::Bytes(RtBytes::from(tri!(reader.take(len))))
+        }
+        Shape::Option(inner) => {
+            if tri!(reader.tag()) {
+                RtValue::Option(RtOption::some(tri!(read_binary(
+                    ctx, schema, inner, reader
+                )?)))
+            } else {
+                RtValue::Option(RtOption::none())
+            }
+        }
+        Shape::Result(ok, err) => {
+            if tri!(reader.tag()) {
+                RtValue::Result(RtResultValue::err(tri!(read_binary(
+                    ctx, schema, err, reader
+                )?)))
+            } else {
+                RtValue::Result(RtResultValue::ok(tri!(read_binary(
+                    ctx, schema, ok, reader
+                )?)))
+            }
+        }
+        Shape::Vec(inner) => {
+            let len = tri!(reader.len(schema.min_encoded_len(inner)));
+            let mut items = Vec::with_capacity(len);
+            for _ in 0..len {
+                items.push(tri!(read_binary(ctx, schema, inner, reader)?));
+            }
+            let vec = RtVec::new();
+            vec.replace_items(items);
+            RtValue::Vec(vec)
+        }
+        Shape::Array(size, inner) => {
+            let mut items = Vec::with_capacity((*size).min(reader.data.len()));
+            for _ in 0..*size {
+                items.push(tri!(read_binary(ctx, schema, inner, reader)?));
+            }
+            RtValue::Array(RtArray::new(items))
+        }
+        Shape::Map(inner) => {
+            let len = tri!(reader.len(schema.min_encoded_len(inner).saturating_add(1)));
+            let map = RtMap::new();
+            for _ in 0..len {
+                let key = tri!(reader.text());
+                let item = tri!(read_binary(ctx, schema, inner, reader)?);
+                map.insert(key, item);
+            }
+            RtValue::Map(map)
+        }
+        Shape::Struct(index) => {
+            let def = &schema.structs[*index];
+            let mut fields = Vec::with_capacity(def.fields.len());
+            for field in &def.fields {
+                fields.push(tri!(read_binary(ctx, schema, field, reader)?));
+            }
+            schema.make_struct(ctx, def, fields)?
+        }
+    };
+    Ok(Ok(value))
+}
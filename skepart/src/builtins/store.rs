@@ -0,0 +1,51 @@
+use crate::{
+    RtErrorKind, RtHandle, RtHost, RtOption, RtResult, RtResultValue, RtString, RtValue, RtVec,
+};
+
+/// File failures are ordinary `Err` values: files can be locked, removed, or
+/// corrupted by other programs at any time. Handle misuse still raises.
+fn to_result(value: RtResult<RtValue>) -> RtResult<RtValue> {
+    Ok(RtValue::Result(match value {
+        Ok(value) => RtResultValue::ok(value),
+        Err(err) if err.kind == RtErrorKind::Io => {
+            RtResultValue::err(RtValue::String(RtString::from(err.to_string())))
+        }
+        Err(err) => return Err(err),
+    }))
+}
+
+pub fn open(host: &mut dyn RtHost, path: &str) -> RtResult<RtValue> {
+    to_result(host.store_open(path).map(RtValue::Handle))
+}
+
+pub fn get(host: &mut dyn RtHost, store: RtHandle, key: &str) -> RtResult<RtValue> {
+    to_result(host.store_get(store, key).map(|value| {
+        RtValue::Option(match value {
+            Some(value) => RtOption::some(RtValue::String(value)),
+            None => RtOption::none(),
+        })
+    }))
+}
+
+pub fn set(host: &mut dyn RtHost, store: RtHandle, key: &str, value: &str) -> RtResult<RtValue> {
+    to_result(host.store_set(store, key, value).map(|()| RtValue::Unit))
+}
+
+pub fn delete(host: &mut dyn RtHost, store: RtHandle, key: &str) -> RtResult<RtValue> {
+    to_result(host.store_delete(store, key).map(RtValue::Bool))
+}
+
+pub fn keys(host: &mut dyn RtHost, store: RtHandle) -> RtResult<RtValue> {
+    to_result(host.store_keys(store).map(|keys| {
+        let out = RtVec::new();
+        for key in keys {
+            out.push(RtValue::String(key));
+        }
+        RtValue::Vec(out)
+    }))
+}
+
+pub fn close(host: &mut dyn RtHost, store: RtHandle) -> RtResult<RtValue> {
+    host.store_close(store)?;
+    Ok(RtValue::Unit)
+}
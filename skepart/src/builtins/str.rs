@@ -1,4 +1,4 @@
-use crate::{RtResult, RtResultValue, RtString, RtValue};
+use crate::{RtError, RtErrorKind, RtResult, RtResultValue, RtString, RtValue};
 
 pub fn len(value: &RtString) -> i64 {
     value.len_chars() as i64
@@ -12,6 +12,30 @@ pub fn index_of(haystack: &RtString, needle: &RtString) -> i64 {
     haystack.index_of(needle)
 }
 
+/// `s[i]`: the character at `index` as a one-character string.
+pub fn char_at(value: &RtString, index: i64) -> RtResult<RtString> {
+    let len = value.len_chars();
+    match usize::try_from(index) {
+        Ok(index) if index < len => value.slice_chars(index..index + 1),
+        _ => Err(RtError::new(
+            RtErrorKind::IndexOutOfBounds,
+            format!("index {index} out of bounds for length {len}"),
+        )),
+    }
+}
+
+/// `s[start..end]`: unlike `str.slice`, bad bounds are a runtime error.
+pub fn slice_strict(value: &RtString, start: i64, end: i64) -> RtResult<RtString> {
+    let len = value.len_chars();
+    match (usize::try_from(start), usize::try_from(end)) {
+        (Ok(start), Ok(end)) if start <= end && end <= len => value.slice_chars(start..end),
+        _ => Err(RtError::new(
+            RtErrorKind::IndexOutOfBounds,
+            format!("slice {start}..{end} out of bounds for length {len}"),
+        )),
+    }
+}
+
 pub fn slice(value: &RtString, start: usize, end: usize) -> RtResult<RtValue> {
     match value.slice_chars(start..end) {
         Ok(sliced) => Ok(RtValue::Result(RtResultValue::ok(RtValue::String(sliced)))),
@@ -1,4 +1,5 @@
-use crate::{RtOption, RtResult, RtValue, RtVec};
+use super::BuiltinContext;
+use crate::{RtFunctionRef, RtOption, RtResult, RtValue, RtVec};
 
 pub fn new() -> RtVec {
     RtVec::new()
@@ -29,3 +30,51 @@ pub fn set(vec: &RtVec, index: usize, value: RtValue) -> RtResult<()> {
 pub fn delete(vec: &RtVec, index: usize) -> RtResult<RtValue> {
     vec.delete(index)
 }
+
+// The callbacks below iterate over a snapshot, so a callback that mutates
+// `vec` does not change which elements are visited.
+
+pub fn map(ctx: &mut dyn BuiltinContext, vec: &RtVec, f: RtFunctionRef) -> RtResult<RtValue> {
+    let out = RtVec::new();
+    for item in vec.items() {
+        out.push(ctx.call_function(f, &[item])?);
+    }
+    Ok(RtValue::Vec(out))
+}
+
+pub fn filter(
+    ctx: &mut dyn BuiltinContext,
+    vec: &RtVec,
+    predicate: RtFunctionRef,
+) -> RtResult<RtValue> {
+    let out = RtVec::new();
+    for item in vec.items() {
+        if ctx
+            .call_function(predicate, std::slice::from_ref(&item))?
+            .expect_bool()?
+        {
+            out.push(item);
+        }
+    }
+    Ok(RtValue::Vec(out))
+}
+
+pub fn reduce(
+    ctx: &mut dyn BuiltinContext,
+    vec: &RtVec,
+    init: RtValue,
+    f: RtFunctionRef,
+) -> RtResult<RtValue> {
+    let mut acc = init;
+    for item in vec.items() {
+        acc = ctx.call_function(f, &[acc, item])?;
+    }
+    Ok(acc)
+}
+
+/// Sorts `vec` in place with [`super::arr::stable_sort`].
+pub fn sort(ctx: &mut dyn BuiltinContext, vec: &RtVec, cmp: RtFunctionRef) -> RtResult<()> {
+    let sorted = super::arr::stable_sort(ctx, vec.items(), cmp)?;
+    vec.replace_items(sorted);
+    Ok(())
+}
@@ -0,0 +1,80 @@
+//! Shared text renderings of runtime values, used by the `io` printers and
+//! by anything else that needs to show a value to a person.
+
+use crate::{RtResultValue, RtStruct, RtValue};
+
+/// Renders a value for output: scalars and strings print as-is, everything
+/// else uses the debug rendering from [`debug_value`].
+pub fn display_value(value: &RtValue) -> String {
+    match value {
+        RtValue::Float(value) => value.to_string(),
+        RtValue::String(value) => value.as_str().to_owned(),
+        RtValue::Char(value) => value.to_string(),
+        RtValue::Unit => String::new(),
+        other => debug_value(other),
+    }
+}
+
+/// Renders a value the way it would be written in source where possible, e.g.
+/// `User { id: 1, name: "ada" }`, `[1, 2]`, or `Some("x")`.
+pub fn debug_value(value: &RtValue) -> String {
+    match value {
+        RtValue::Int(value) => value.to_string(),
+        RtValue::Float(value) => format!("{value:?}"),
+        RtValue::Bool(value) => value.to_string(),
+        RtValue::Char(value) => format!("{value:?}"),
+        RtValue::String(value) => format!("{:?}", value.as_str()),
+        RtValue::Bytes(value) => format!("[bytes len={}]", value.len()),
+        RtValue::Option(value) => match &value.0 {
+            Some(inner) => format!("Some({})", debug_value(inner)),
+            None => "None()".to_owned(),
+        },
+        RtValue::Result(value) => match value {
+            RtResultValue::Ok(inner) => format!("Ok({})", debug_value(inner)),
+            RtResultValue::Err(inner) => format!("Err({})", debug_value(inner)),
+        },
+        RtValue::Array(value) => debug_list(value.iter()),
+        RtValue::Vec(value) => debug_list(value.items().into_iter()),
+        RtValue::Map(value) => {
+            let entries = value
+                .keys()
+                .into_iter()
+                .map(|key| {
+                    let item = value.get(&key).unwrap_or(RtValue::Unit);
+                    format!("{key:?}: {}", debug_value(&item))
+                })
+                .collect::<Vec<_>>();
+            format!("{{{}}}", entries.join(", "))
+        }
+        RtValue::Function(_) => "[function]".to_owned(),
+        RtValue::Handle(value) => format!("[handle {:?}#{}]", value.kind, value.id),
+        RtValue::Struct(value) => debug_struct(value),
+        RtValue::Unit => "()".to_owned(),
+    }
+}
+
+fn debug_list(items: impl Iterator<Item = RtValue>) -> String {
+    let items = items.map(|item| debug_value(&item)).collect::<Vec<_>>();
+    format!("[{}]", items.join(", "))
+}
+
+fn debug_struct(value: &RtStruct) -> String {
+    let fields = value.field_values();
+    // Tuples lower to structs named after their element types, e.g. `(Int, String)`.
+    if value.layout.name.starts_with('(') {
+        let items = fields.iter().map(debug_value).collect::<Vec<_>>();
+        return format!("({})", items.join(", "));
+    }
+    if fields.is_empty() {
+        return format!("{} {{}}", value.layout.name);
+    }
+    let fields = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match value.layout.field_names.get(index) {
+            Some(name) => format!("{name}: {}", debug_value(field)),
+            None => format!("{index}: {}", debug_value(field)),
+        })
+        .collect::<Vec<_>>();
+    format!("{} {{ {} }}", value.layout.name, fields.join(", "))
+}
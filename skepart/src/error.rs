@@ -3,6 +3,7 @@ use std::fmt;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RtErrorKind {
     DivisionByZero,
+    IntegerOverflow,
     IndexOutOfBounds,
     TypeMismatch,
     MissingField,
@@ -10,6 +11,25 @@ pub enum RtErrorKind {
     Io,
     Process,
     UnsupportedBuiltin,
+    Panic,
+}
+
+impl RtErrorKind {
+    /// Stable diagnostic code, explained by `skepac explain <code>`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DivisionByZero => "E-RT-001",
+            Self::IntegerOverflow => "E-RT-002",
+            Self::IndexOutOfBounds => "E-RT-003",
+            Self::TypeMismatch => "E-RT-004",
+            Self::MissingField => "E-RT-005",
+            Self::InvalidArgument => "E-RT-006",
+            Self::Io => "E-RT-007",
+            Self::Process => "E-RT-008",
+            Self::UnsupportedBuiltin => "E-RT-009",
+            Self::Panic => "E-RT-010",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,6 +41,9 @@ pub struct RtError {
 pub type RtResult<T> = Result<T, RtError>;
 
 impl RtError {
+    /// Exit status of a native program that stops on an uncaught runtime error.
+    pub const EXIT_CODE: i32 = 101;
+
     pub fn new(kind: RtErrorKind, message: impl Into<String>) -> Self {
         Self {
             kind,
@@ -32,6 +55,10 @@ impl RtError {
         Self::new(RtErrorKind::TypeMismatch, message)
     }
 
+    pub fn integer_overflow() -> Self {
+        Self::new(RtErrorKind::IntegerOverflow, "integer overflow")
+    }
+
     pub fn index_out_of_bounds(index: usize, len: usize) -> Self {
         Self::new(
             RtErrorKind::IndexOutOfBounds,
@@ -46,6 +73,10 @@ impl RtError {
         )
     }
 
+    pub fn panic(message: impl Into<String>) -> Self {
+        Self::new(RtErrorKind::Panic, message)
+    }
+
     pub fn io(message: impl Into<String>) -> Self {
         Self::new(RtErrorKind::Io, message)
     }
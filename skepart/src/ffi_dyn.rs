@@ -286,6 +286,19 @@ impl RtForeignSymbol {
         unsafe { function(left, right) }
     }
 
+    pub fn call_1_float(&self, value: f64) -> f64 {
+        // SAFETY: caller guarantees the symbol uses the expected ABI/signature.
+        let function: unsafe extern "C" fn(f64) -> f64 = unsafe { std::mem::transmute(self.ptr) };
+        unsafe { function(value) }
+    }
+
+    pub fn call_2_float_float(&self, left: f64, right: f64) -> f64 {
+        // SAFETY: caller guarantees the symbol uses the expected ABI/signature.
+        let function: unsafe extern "C" fn(f64, f64) -> f64 =
+            unsafe { std::mem::transmute(self.ptr) };
+        unsafe { function(left, right) }
+    }
+
     pub fn call_1_bytes_usize(&self, value: &[u8]) -> i64 {
         #[cfg(windows)]
         {
@@ -174,8 +174,8 @@ unsafe fn free_boxed_struct(ptr: *mut RtStruct) {
 #[no_mangle]
 pub extern "C" fn skp_rt_abort_if_error() {
     if let Some(err) = take_last_error() {
-        eprintln!("{err}");
-        std::process::exit(101);
+        eprintln!("[{}] {err}", err.kind.code());
+        std::process::exit(crate::RtError::EXIT_CODE);
     }
 }
 
@@ -188,6 +188,20 @@ pub extern "C" fn skp_rt_raise_division_by_zero() {
     skp_rt_abort_if_error();
 }
 
+#[no_mangle]
+pub extern "C" fn skp_rt_raise_integer_overflow() {
+    set_last_error(crate::RtError::integer_overflow());
+    skp_rt_abort_if_error();
+}
+
+/// Reached only if a call the compiler treats as diverging (`panic`,
+/// `os.exit`) returned.
+#[no_mangle]
+pub extern "C" fn skp_rt_raise_unreachable() {
+    set_last_error(crate::RtError::panic("reached code after a diverging call"));
+    skp_rt_abort_if_error();
+}
+
 #[no_mangle]
 pub extern "C" fn skp_rt_raise_negative_shift_count() {
     set_last_error(crate::RtError::new(
@@ -208,6 +222,8 @@ pub extern "C" fn skp_rt_last_error_kind() -> i32 {
         Some(crate::RtErrorKind::UnsupportedBuiltin) => 6,
         Some(crate::RtErrorKind::Io) => 7,
         Some(crate::RtErrorKind::Process) => 8,
+        Some(crate::RtErrorKind::IntegerOverflow) => 9,
+        Some(crate::RtErrorKind::Panic) => 10,
         None => 0,
     })
 }
@@ -99,6 +99,26 @@ pub unsafe extern "C" fn skp_rt_result_eq(
     unsafe { *left == *right }
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn skp_rt_array_eq(left: *mut RtArray, right: *mut RtArray) -> bool {
+    clear_last_error();
+    if left.is_null() || right.is_null() {
+        set_last_error(invalid_argument("array pointers must not be null"));
+        return false;
+    }
+    unsafe { *left == *right }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn skp_rt_struct_eq(left: *mut RtStruct, right: *mut RtStruct) -> bool {
+    clear_last_error();
+    if left.is_null() || right.is_null() {
+        set_last_error(invalid_argument("struct pointers must not be null"));
+        return false;
+    }
+    unsafe { *left == *right }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn skp_rt_option_some(value: *mut RtValue) -> *mut RtOption {
     match ffi_try(|| clone_value(value).map(RtOption::some).map(boxed_option)) {
@@ -203,6 +223,14 @@ pub extern "C" fn skp_rt_value_from_bool(value: bool) -> *mut RtValue {
     boxed_value(RtValue::Bool(value))
 }
 
+/// Native code carries `Char` as an `i32` code point.
+#[no_mangle]
+pub extern "C" fn skp_rt_value_from_char(value: u32) -> *mut RtValue {
+    boxed_value(RtValue::Char(
+        char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER),
+    ))
+}
+
 #[no_mangle]
 pub extern "C" fn skp_rt_value_from_float(value: f64) -> *mut RtValue {
     boxed_value(RtValue::Float(value))
@@ -388,6 +416,17 @@ pub unsafe extern "C" fn skp_rt_value_to_int(value: *mut RtValue) -> i64 {
     }
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn skp_rt_value_to_char(value: *mut RtValue) -> u32 {
+    match ffi_try(|| clone_value(value)?.expect_char()) {
+        Ok(value) => value as u32,
+        Err(err) => {
+            set_last_error(err);
+            0
+        }
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn skp_rt_value_to_bool(value: *mut RtValue) -> bool {
     match ffi_try(|| clone_value(value)?.expect_bool()) {
@@ -3,9 +3,16 @@ use std::panic::{catch_unwind, AssertUnwindSafe};
 
 pub type RtNativeFn = fn(&mut dyn RtHost, &[RtValue]) -> RtResult<RtValue>;
 
+/// A registered host function that may capture state; see
+/// [`RtFunctionRegistry::register_closure`].
+pub type RtHostFn = Box<dyn Fn(&mut dyn RtHost, &[RtValue]) -> RtResult<RtValue>>;
+
+/// Host functions callable through [`RtFunctionRef`]s. Hand a registry to
+/// `IrInterpreter::set_functions` or [`crate::builtins::call_with_functions`] so
+/// Skepa code and builtins such as `vec.map` can call the refs it returns.
 #[derive(Default)]
 pub struct RtFunctionRegistry {
-    functions: Vec<RtNativeFn>,
+    functions: Vec<RtHostFn>,
 }
 
 impl RtFunctionRegistry {
@@ -14,9 +21,19 @@ impl RtFunctionRegistry {
     }
 
     pub fn register(&mut self, function: RtNativeFn) -> RtFunctionRef {
+        self.register_closure(function)
+    }
+
+    /// Registers a closure, e.g. one holding a database handle or a channel
+    /// sender. Share state with the application through `Rc`/`Arc` and use
+    /// interior mutability for state the closure updates.
+    pub fn register_closure(
+        &mut self,
+        function: impl Fn(&mut dyn RtHost, &[RtValue]) -> RtResult<RtValue> + 'static,
+    ) -> RtFunctionRef {
         let id = self.functions.len();
-        self.functions.push(function);
-        RtFunctionRef(id)
+        self.functions.push(Box::new(function));
+        RtFunctionRef::host(id)
     }
 
     pub fn call(
@@ -25,12 +42,15 @@ impl RtFunctionRegistry {
         function: RtFunctionRef,
         args: &[RtValue],
     ) -> RtResult<RtValue> {
-        let function = self.functions.get(function.0).ok_or_else(|| {
-            crate::RtError::new(
-                crate::RtErrorKind::InvalidArgument,
-                format!("unknown runtime function id {}", function.0),
-            )
-        })?;
+        let function = function
+            .host_index()
+            .and_then(|index| self.functions.get(index))
+            .ok_or_else(|| {
+                crate::RtError::new(
+                    crate::RtErrorKind::InvalidArgument,
+                    format!("unknown runtime function id {}", function.0),
+                )
+            })?;
         catch_unwind(AssertUnwindSafe(|| function(host, args))).map_err(|_| {
             crate::RtError::new(
                 crate::RtErrorKind::InvalidArgument,
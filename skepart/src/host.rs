@@ -3,9 +3,9 @@ use std::collections::VecDeque;
 use std::ffi::OsString;
 use std::fs;
 use std::io::{BufRead, Read, Write};
-use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::Weak;
@@ -18,6 +18,7 @@ use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
 
 use crate::{
     ffi_dyn::{RtForeignLibrary, RtForeignSymbol},
+    store::{RtStoreFile, RtStoreTable},
     RtBytes, RtError, RtErrorKind, RtHandle, RtHandleKind, RtMap, RtResult, RtString, RtValue,
 };
 
@@ -30,6 +31,7 @@ pub enum RtNetResource {
     TcpStream(TcpStream),
     TlsStream(Box<StreamOwned<ClientConnection, TcpStream>>),
     TcpListener(TcpListener),
+    UdpSocket(UdpSocket),
 }
 
 pub enum RtTaskState {
@@ -47,6 +49,7 @@ impl RtNetResource {
             Self::ForeignSymbol(_) => RtHandleKind::Symbol,
             Self::TcpStream(_) | Self::TlsStream(_) => RtHandleKind::Socket,
             Self::TcpListener(_) => RtHandleKind::Listener,
+            Self::UdpSocket(_) => RtHandleKind::UdpSocket,
         }
     }
 }
@@ -77,6 +80,10 @@ impl RtNetResourceTable {
         self.insert(RtNetResource::TcpListener(listener))
     }
 
+    pub fn insert_udp_socket(&mut self, socket: UdpSocket) -> RtHandle {
+        self.insert(RtNetResource::UdpSocket(socket))
+    }
+
     pub fn insert_channel(&mut self) -> RtHandle {
         self.insert(RtNetResource::Channel(Arc::new(
             Mutex::new(VecDeque::new()),
@@ -164,6 +171,21 @@ impl RtNetResourceTable {
         }
     }
 
+    pub fn udp_socket_mut(&mut self, handle: RtHandle) -> RtResult<&mut UdpSocket> {
+        self.kind_of(handle)?;
+        match self.resources.get_mut(&handle.id) {
+            Some(RtNetResource::UdpSocket(socket)) => Ok(socket),
+            Some(other) => Err(RtError::invalid_handle_kind(
+                RtHandleKind::UdpSocket.type_name(),
+                other.kind().type_name(),
+            )),
+            None => Err(RtError::invalid_handle(format!(
+                "unknown handle id {}",
+                handle.id
+            ))),
+        }
+    }
+
     pub fn remove(&mut self, handle: RtHandle) -> RtResult<RtNetResource> {
         self.kind_of(handle)?;
         self.resources
@@ -260,10 +282,41 @@ pub trait RtHost {
         self.io_print("\n")
     }
 
+    fn io_eprint(&mut self, _text: &str) -> RtResult<()> {
+        Err(RtError::unsupported_builtin("io.eprint"))
+    }
+
+    fn io_eprintln(&mut self, text: &str) -> RtResult<()> {
+        self.io_eprint(text)?;
+        self.io_eprint("\n")
+    }
+
     fn io_read_line(&mut self) -> RtResult<RtString> {
         Ok(RtString::from(""))
     }
 
+    /// Reads one line including its `\n` or `\r\n` terminator, or `None` once input
+    /// is exhausted. A last line without a terminator comes back as it is.
+    fn io_read_raw_line(&mut self) -> RtResult<Option<RtString>> {
+        Ok(None)
+    }
+
+    /// Reads one line without its terminator, or `None` once input is exhausted.
+    fn io_try_read_line(&mut self) -> RtResult<Option<RtString>> {
+        Ok(self
+            .io_read_raw_line()?
+            .map(|line| RtString::from(trim_line_terminator(line.as_str()))))
+    }
+
+    /// Reads the rest of the input exactly, terminators included.
+    fn io_read_all(&mut self) -> RtResult<RtString> {
+        let mut out = String::new();
+        while let Some(line) = self.io_read_raw_line()? {
+            out.push_str(line.as_str());
+        }
+        Ok(RtString::from(out))
+    }
+
     fn datetime_now_unix(&mut self) -> RtResult<i64> {
         Err(RtError::unsupported_builtin("datetime.nowUnix"))
     }
@@ -284,6 +337,19 @@ pub trait RtHost {
         Err(RtError::unsupported_builtin("datetime.parseUnix"))
     }
 
+    fn datetime_format(&mut self, _value: i64, _pattern: &str) -> RtResult<RtString> {
+        Err(RtError::unsupported_builtin("datetime.format"))
+    }
+
+    fn datetime_parse(&mut self, _pattern: &str, _value: &str) -> RtResult<i64> {
+        Err(RtError::unsupported_builtin("datetime.parse"))
+    }
+
+    /// Minutes east of UTC of the host's local time zone at Unix time `value`.
+    fn datetime_local_offset_minutes(&mut self, _value: i64) -> RtResult<i64> {
+        Err(RtError::unsupported_builtin("datetime.offsetMinutes"))
+    }
+
     fn datetime_component(&mut self, _name: &str, _value: i64) -> RtResult<i64> {
         Err(RtError::unsupported_builtin("datetime.component"))
     }
@@ -328,6 +394,22 @@ pub trait RtHost {
         Err(RtError::unsupported_builtin("fs.removeDirAll"))
     }
 
+    fn fs_copy_file(&mut self, _from: &str, _to: &str) -> RtResult<()> {
+        Err(RtError::unsupported_builtin("fs.copyFile"))
+    }
+
+    fn fs_move_file(&mut self, _from: &str, _to: &str) -> RtResult<()> {
+        Err(RtError::unsupported_builtin("fs.moveFile"))
+    }
+
+    fn fs_read_bytes(&mut self, _path: &str) -> RtResult<RtBytes> {
+        Err(RtError::unsupported_builtin("fs.readBytes"))
+    }
+
+    fn fs_write_bytes(&mut self, _path: &str, _data: &RtBytes) -> RtResult<()> {
+        Err(RtError::unsupported_builtin("fs.writeBytes"))
+    }
+
     fn fs_join(&mut self, _left: &str, _right: &str) -> RtResult<RtString> {
         Err(RtError::unsupported_builtin("fs.join"))
     }
@@ -427,6 +509,19 @@ pub trait RtHost {
         Err(RtError::unsupported_builtin("ffi.call2IntInt"))
     }
 
+    fn ffi_call_1_float(&mut self, _symbol: RtHandle, _value: f64) -> RtResult<f64> {
+        Err(RtError::unsupported_builtin("ffi.call1Float"))
+    }
+
+    fn ffi_call_2_float_float(
+        &mut self,
+        _symbol: RtHandle,
+        _left: f64,
+        _right: f64,
+    ) -> RtResult<f64> {
+        Err(RtError::unsupported_builtin("ffi.call2FloatFloat"))
+    }
+
     fn ffi_call_1_bytes_usize(&mut self, _symbol: RtHandle, _value: &RtBytes) -> RtResult<i64> {
         Err(RtError::unsupported_builtin("ffi.call1BytesInt"))
     }
@@ -484,6 +579,19 @@ pub trait RtHost {
         Err(RtError::unsupported_builtin("os.execOut"))
     }
 
+    /// Runs `program` without a shell and returns `code`, `stdout` and `stderr`.
+    ///
+    /// `options` may set `cwd`, `stdin`, `clearEnv` (`"true"`) and `env.NAME`
+    /// entries for the child environment.
+    fn os_exec_capture(
+        &mut self,
+        _program: &str,
+        _args: &[String],
+        _options: &RtMap,
+    ) -> RtResult<RtMap> {
+        Err(RtError::unsupported_builtin("os.execCapture"))
+    }
+
     fn net_make_socket_handle(&mut self, _id: usize) -> RtResult<RtHandle> {
         Err(RtError::unsupported_builtin("net.Socket"))
     }
@@ -622,6 +730,65 @@ pub trait RtHost {
     fn net_set_write_timeout(&mut self, _socket: RtHandle, _millis: i64) -> RtResult<()> {
         Err(RtError::unsupported_builtin("net.setWriteTimeout"))
     }
+
+    fn net_bind_udp(&mut self, _address: &str) -> RtResult<RtHandle> {
+        Err(RtError::unsupported_builtin("net.bindUdp"))
+    }
+
+    fn net_udp_connect(&mut self, _socket: RtHandle, _address: &str) -> RtResult<()> {
+        Err(RtError::unsupported_builtin("net.udpConnect"))
+    }
+
+    fn net_send(&mut self, _socket: RtHandle, _data: &RtBytes) -> RtResult<()> {
+        Err(RtError::unsupported_builtin("net.send"))
+    }
+
+    fn net_send_to(&mut self, _socket: RtHandle, _address: &str, _data: &RtBytes) -> RtResult<()> {
+        Err(RtError::unsupported_builtin("net.sendTo"))
+    }
+
+    fn net_recv(&mut self, _socket: RtHandle) -> RtResult<RtBytes> {
+        Err(RtError::unsupported_builtin("net.recv"))
+    }
+
+    fn net_recv_from(&mut self, _socket: RtHandle, _max: i64) -> RtResult<(RtBytes, RtString)> {
+        Err(RtError::unsupported_builtin("net.recvFrom"))
+    }
+
+    fn net_udp_local_addr(&mut self, _socket: RtHandle) -> RtResult<RtString> {
+        Err(RtError::unsupported_builtin("net.udpLocalAddr"))
+    }
+
+    fn store_open(&mut self, _path: &str) -> RtResult<RtHandle> {
+        Err(RtError::unsupported_builtin("store.open"))
+    }
+
+    fn store_get(&mut self, _store: RtHandle, _key: &str) -> RtResult<Option<RtString>> {
+        Err(RtError::unsupported_builtin("store.get"))
+    }
+
+    fn store_set(&mut self, _store: RtHandle, _key: &str, _value: &str) -> RtResult<()> {
+        Err(RtError::unsupported_builtin("store.set"))
+    }
+
+    fn store_delete(&mut self, _store: RtHandle, _key: &str) -> RtResult<bool> {
+        Err(RtError::unsupported_builtin("store.delete"))
+    }
+
+    fn store_keys(&mut self, _store: RtHandle) -> RtResult<Vec<RtString>> {
+        Err(RtError::unsupported_builtin("store.keys"))
+    }
+
+    fn store_close(&mut self, _store: RtHandle) -> RtResult<()> {
+        Err(RtError::unsupported_builtin("store.close"))
+    }
+
+    /// Runs `package.name` for a builtin the runtime does not implement, such as
+    /// a function of a host package the embedder declared in skeplib's
+    /// `CustomBuiltins`.
+    fn call_custom(&mut self, package: &str, name: &str, _args: &[RtValue]) -> RtResult<RtValue> {
+        Err(RtError::unsupported_builtin(format!("{package}.{name}")))
+    }
 }
 
 pub struct NoopHost {
@@ -629,6 +796,7 @@ pub struct NoopHost {
     args: Vec<String>,
     env_vars: HashMap<String, OsString>,
     net_resources: RtNetResourceTable,
+    stores: RtStoreTable,
     tls_root_certs: Vec<CertificateDer<'static>>,
     ffi_library_cache: HashMap<String, Weak<RtForeignLibrary>>,
     ffi_symbol_cache: HashMap<(String, String), Weak<RtForeignSymbol>>,
@@ -644,6 +812,7 @@ impl Default for NoopHost {
                 .filter_map(|(key, value)| key.into_string().ok().map(|key| (key, value)))
                 .collect(),
             net_resources: RtNetResourceTable::default(),
+            stores: RtStoreTable::default(),
             tls_root_certs: Vec::new(),
             ffi_library_cache: HashMap::new(),
             ffi_symbol_cache: HashMap::new(),
@@ -667,11 +836,33 @@ impl RtHost for NoopHost {
         Ok(())
     }
 
+    fn io_eprint(&mut self, text: &str) -> RtResult<()> {
+        eprint!("{text}");
+        std::io::stderr()
+            .flush()
+            .map_err(|err| RtError::io(err.to_string()))?;
+        Ok(())
+    }
+
     fn io_read_line(&mut self) -> RtResult<RtString> {
         let mut stdin = std::io::stdin().lock();
         read_line_trimmed(&mut stdin)
     }
 
+    fn io_read_raw_line(&mut self) -> RtResult<Option<RtString>> {
+        let mut stdin = std::io::stdin().lock();
+        Ok(read_raw_line(&mut stdin)?.map(RtString::from))
+    }
+
+    fn io_read_all(&mut self) -> RtResult<RtString> {
+        let mut out = String::new();
+        std::io::stdin()
+            .lock()
+            .read_to_string(&mut out)
+            .map_err(|err| RtError::io(err.to_string()))?;
+        Ok(RtString::from(out))
+    }
+
     fn datetime_now_unix(&mut self) -> RtResult<i64> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -698,6 +889,18 @@ impl RtHost for NoopHost {
         parse_iso8601_utc(value)
     }
 
+    fn datetime_format(&mut self, value: i64, pattern: &str) -> RtResult<RtString> {
+        format_with_pattern("datetime.format", value, 0, pattern)
+    }
+
+    fn datetime_parse(&mut self, pattern: &str, value: &str) -> RtResult<i64> {
+        parse_with_pattern(pattern, value)
+    }
+
+    fn datetime_local_offset_minutes(&mut self, value: i64) -> RtResult<i64> {
+        platform_local_offset_minutes(value)
+    }
+
     fn datetime_component(&mut self, name: &str, value: i64) -> RtResult<i64> {
         let (year, month, day, hour, minute, second) = unix_seconds_to_components(value);
         match name {
@@ -781,6 +984,39 @@ impl RtHost for NoopHost {
         fs::remove_dir_all(path).map_err(|err| RtError::io(err.to_string()))
     }
 
+    fn fs_copy_file(&mut self, from: &str, to: &str) -> RtResult<()> {
+        fs::copy(from, to)
+            .map(|_| ())
+            .map_err(|err| RtError::io(err.to_string()))
+    }
+
+    fn fs_move_file(&mut self, from: &str, to: &str) -> RtResult<()> {
+        let Err(rename_err) = fs::rename(from, to) else {
+            return Ok(());
+        };
+        // `rename` cannot cross filesystems; fall back to copy-then-remove.
+        // Unlike `rename` this is not atomic: `to` briefly exists alongside
+        // `from`, and a crash in between leaves both.
+        if fs::copy(from, to).is_err() {
+            return Err(RtError::io(rename_err.to_string()));
+        }
+        fs::remove_file(from).map_err(|err| {
+            // Keep the move all-or-nothing: drop the copy rather than leave a
+            // duplicate behind when the source cannot be removed.
+            let _ = fs::remove_file(to);
+            RtError::io(err.to_string())
+        })
+    }
+
+    fn fs_read_bytes(&mut self, path: &str) -> RtResult<RtBytes> {
+        let data = fs::read(path).map_err(|err| RtError::io(err.to_string()))?;
+        Ok(RtBytes::from(data))
+    }
+
+    fn fs_write_bytes(&mut self, path: &str, data: &RtBytes) -> RtResult<()> {
+        fs::write(path, data.as_slice()).map_err(|err| RtError::io(err.to_string()))
+    }
+
     fn fs_join(&mut self, left: &str, right: &str) -> RtResult<RtString> {
         Ok(RtString::from(
             PathBuf::from(left)
@@ -953,6 +1189,16 @@ impl RtHost for NoopHost {
         Ok(symbol.call_2_int_int(left, right))
     }
 
+    fn ffi_call_1_float(&mut self, symbol: RtHandle, value: f64) -> RtResult<f64> {
+        let symbol = self.net_resources.foreign_symbol(symbol)?;
+        Ok(symbol.call_1_float(value))
+    }
+
+    fn ffi_call_2_float_float(&mut self, symbol: RtHandle, left: f64, right: f64) -> RtResult<f64> {
+        let symbol = self.net_resources.foreign_symbol(symbol)?;
+        Ok(symbol.call_2_float_float(left, right))
+    }
+
     fn ffi_call_2_bytes_usize_usize(
         &mut self,
         symbol: RtHandle,
@@ -1048,6 +1294,79 @@ impl RtHost for NoopHost {
         ))
     }
 
+    fn os_exec_capture(
+        &mut self,
+        program: &str,
+        args: &[String],
+        options: &RtMap,
+    ) -> RtResult<RtMap> {
+        let option = |key: &str| -> RtResult<Option<RtString>> {
+            options
+                .get(key)
+                .map(|value| value.expect_string())
+                .transpose()
+        };
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(cwd) = option("cwd")? {
+            command.current_dir(cwd.as_str());
+        }
+        if option("clearEnv")?.is_some_and(|value| value.as_str() == "true") {
+            command.env_clear();
+        }
+        for key in options.keys() {
+            if let (Some(name), Some(value)) = (key.strip_prefix("env."), option(&key)?) {
+                command.env(name, value.as_str());
+            }
+        }
+        let stdin = option("stdin")?;
+        command.stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        });
+        let mut child = command
+            .spawn()
+            .map_err(|err| RtError::process(err.to_string()))?;
+        // Feed stdin from another thread so a child that fills its stdout pipe
+        // before draining stdin cannot deadlock us.
+        let writer = match (stdin, child.stdin.take()) {
+            (Some(text), Some(mut pipe)) => Some(std::thread::spawn(move || {
+                let _ = pipe.write_all(text.as_str().as_bytes());
+            })),
+            _ => None,
+        };
+        let output = child
+            .wait_with_output()
+            .map_err(|err| RtError::process(err.to_string()))?;
+        if let Some(writer) = writer {
+            let _ = writer.join();
+        }
+        let response = RtMap::new();
+        response.insert(
+            "code",
+            RtValue::String(RtString::from(
+                output.status.code().unwrap_or(-1).to_string(),
+            )),
+        );
+        response.insert(
+            "stdout",
+            RtValue::String(RtString::from(
+                String::from_utf8_lossy(&output.stdout).into_owned(),
+            )),
+        );
+        response.insert(
+            "stderr",
+            RtValue::String(RtString::from(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            )),
+        );
+        Ok(response)
+    }
+
     fn net_make_socket_handle(&mut self, id: usize) -> RtResult<RtHandle> {
         self.net_resources
             .insert_with_id(id, RtNetResource::Placeholder(RtHandleKind::Socket))
@@ -1346,6 +1665,100 @@ impl RtHost for NoopHost {
         let timeout = duration_from_timeout_millis("net.setWriteTimeout", millis)?;
         self.socket_set_write_timeout(socket, timeout)
     }
+
+    fn net_bind_udp(&mut self, address: &str) -> RtResult<RtHandle> {
+        let socket = UdpSocket::bind(address).map_err(|err| RtError::io(err.to_string()))?;
+        Ok(self.net_resources.insert_udp_socket(socket))
+    }
+
+    fn net_udp_connect(&mut self, socket: RtHandle, address: &str) -> RtResult<()> {
+        self.net_resources
+            .udp_socket_mut(socket)?
+            .connect(address)
+            .map_err(|err| RtError::io(err.to_string()))
+    }
+
+    fn net_send(&mut self, socket: RtHandle, data: &RtBytes) -> RtResult<()> {
+        let socket = self.net_resources.udp_socket_mut(socket)?;
+        let sent = socket
+            .send(data.as_slice())
+            .map_err(|err| RtError::io(err.to_string()))?;
+        check_datagram_sent("net.send", sent, data)
+    }
+
+    fn net_send_to(&mut self, socket: RtHandle, address: &str, data: &RtBytes) -> RtResult<()> {
+        let socket = self.net_resources.udp_socket_mut(socket)?;
+        let sent = socket
+            .send_to(data.as_slice(), address)
+            .map_err(|err| RtError::io(err.to_string()))?;
+        check_datagram_sent("net.sendTo", sent, data)
+    }
+
+    fn net_recv(&mut self, socket: RtHandle) -> RtResult<RtBytes> {
+        // Large enough for any UDP payload, so a datagram is never truncated.
+        let mut buf = vec![0_u8; 65536];
+        let bytes = self
+            .net_resources
+            .udp_socket_mut(socket)?
+            .recv(&mut buf)
+            .map_err(|err| RtError::io(err.to_string()))?;
+        buf.truncate(bytes);
+        Ok(RtBytes::from(buf))
+    }
+
+    fn net_recv_from(&mut self, socket: RtHandle, max: i64) -> RtResult<(RtBytes, RtString)> {
+        let max = usize::try_from(max).map_err(|_| {
+            RtError::new(
+                RtErrorKind::InvalidArgument,
+                "net.recvFrom max must be non-negative",
+            )
+        })?;
+        // A datagram longer than `max` is truncated; the rest of it is dropped.
+        let mut buf = vec![0_u8; max];
+        let (bytes, peer) = self
+            .net_resources
+            .udp_socket_mut(socket)?
+            .recv_from(&mut buf)
+            .map_err(|err| RtError::io(err.to_string()))?;
+        buf.truncate(bytes);
+        Ok((RtBytes::from(buf), RtString::from(peer.to_string())))
+    }
+
+    fn net_udp_local_addr(&mut self, socket: RtHandle) -> RtResult<RtString> {
+        let addr = self
+            .net_resources
+            .udp_socket_mut(socket)?
+            .local_addr()
+            .map_err(|err| RtError::io(err.to_string()))?;
+        Ok(RtString::from(addr.to_string()))
+    }
+
+    fn store_open(&mut self, path: &str) -> RtResult<RtHandle> {
+        let store = RtStoreFile::open(path)?;
+        Ok(self.stores.insert(store))
+    }
+
+    fn store_get(&mut self, store: RtHandle, key: &str) -> RtResult<Option<RtString>> {
+        let value = self.stores.get(store)?.get(key)?;
+        Ok(value.map(RtString::from))
+    }
+
+    fn store_set(&mut self, store: RtHandle, key: &str, value: &str) -> RtResult<()> {
+        self.stores.get(store)?.set(key, value)
+    }
+
+    fn store_delete(&mut self, store: RtHandle, key: &str) -> RtResult<bool> {
+        self.stores.get(store)?.delete(key)
+    }
+
+    fn store_keys(&mut self, store: RtHandle) -> RtResult<Vec<RtString>> {
+        let keys = self.stores.get(store)?.keys()?;
+        Ok(keys.into_iter().map(RtString::from).collect())
+    }
+
+    fn store_close(&mut self, store: RtHandle) -> RtResult<()> {
+        self.stores.remove(store).map(drop)
+    }
 }
 
 impl NoopHost {
@@ -1554,6 +1967,16 @@ impl NoopHost {
     }
 }
 
+fn check_datagram_sent(name: &str, sent: usize, data: &RtBytes) -> RtResult<()> {
+    if sent != data.len() {
+        return Err(RtError::io(format!(
+            "{name} sent {sent} of {} bytes",
+            data.len()
+        )));
+    }
+    Ok(())
+}
+
 fn duration_from_timeout_millis(name: &str, millis: i64) -> RtResult<Option<Duration>> {
     if millis < 0 {
         return Err(RtError::new(
@@ -1896,6 +2319,334 @@ fn parse_part<T: std::str::FromStr>(value: Option<&str>, name: &str) -> RtResult
         })
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DatetimeField {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Offset,
+}
+
+impl DatetimeField {
+    fn token(self) -> &'static str {
+        match self {
+            Self::Year => "YYYY",
+            Self::Month => "MM",
+            Self::Day => "DD",
+            Self::Hour => "hh",
+            Self::Minute => "mm",
+            Self::Second => "ss",
+            Self::Offset => "Z",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Year => "year",
+            Self::Month => "month",
+            Self::Day => "day",
+            Self::Hour => "hour",
+            Self::Minute => "minute",
+            Self::Second => "second",
+            Self::Offset => "offset",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DatetimePiece {
+    Field(DatetimeField),
+    Literal(String),
+}
+
+/// Splits a `datetime.format` / `datetime.parse` pattern into fields and
+/// literal text. Letters are reserved for tokens; quote literal letters
+/// (`'T'`), and write `''` for a single quote.
+fn parse_datetime_pattern(builtin: &str, pattern: &str) -> RtResult<Vec<DatetimePiece>> {
+    let invalid = |message: String| RtError::new(RtErrorKind::InvalidArgument, message);
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            if chars.peek() == Some(&'\'') {
+                chars.next();
+                literal.push('\'');
+                continue;
+            }
+            loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(quoted) => literal.push(quoted),
+                    None => {
+                        return Err(invalid(format!(
+                            "{builtin} pattern has an unterminated quote in `{pattern}`"
+                        )));
+                    }
+                }
+            }
+            continue;
+        }
+        if !c.is_ascii_alphabetic() {
+            literal.push(c);
+            continue;
+        }
+        let mut run = String::from(c);
+        while chars.peek() == Some(&c) {
+            run.push(c);
+            chars.next();
+        }
+        let field = match run.as_str() {
+            "YYYY" => DatetimeField::Year,
+            "MM" => DatetimeField::Month,
+            "DD" => DatetimeField::Day,
+            "hh" => DatetimeField::Hour,
+            "mm" => DatetimeField::Minute,
+            "ss" => DatetimeField::Second,
+            "Z" => DatetimeField::Offset,
+            _ => {
+                return Err(invalid(format!(
+                    "{builtin} pattern has unknown token `{run}`; supported tokens are YYYY, MM, DD, hh, mm, ss, Z and literal letters must be quoted like 'T'"
+                )));
+            }
+        };
+        if pieces.contains(&DatetimePiece::Field(field)) {
+            return Err(invalid(format!(
+                "{builtin} pattern repeats token `{}`",
+                field.token()
+            )));
+        }
+        if !literal.is_empty() {
+            pieces.push(DatetimePiece::Literal(std::mem::take(&mut literal)));
+        }
+        pieces.push(DatetimePiece::Field(field));
+    }
+    if !literal.is_empty() {
+        pieces.push(DatetimePiece::Literal(literal));
+    }
+    Ok(pieces)
+}
+
+/// Renders `seconds` as wall-clock time at `offset_minutes` east of UTC.
+pub(crate) fn format_with_pattern(
+    builtin: &str,
+    seconds: i64,
+    offset_minutes: i64,
+    pattern: &str,
+) -> RtResult<RtString> {
+    let pieces = parse_datetime_pattern(builtin, pattern)?;
+    let local = offset_minutes
+        .checked_mul(60)
+        .and_then(|offset| seconds.checked_add(offset))
+        .ok_or_else(|| {
+            RtError::new(
+                RtErrorKind::InvalidArgument,
+                format!("{builtin} timestamp is out of range"),
+            )
+        })?;
+    let (year, month, day, hour, minute, second) = unix_seconds_to_components(local);
+    let mut out = String::new();
+    for piece in pieces {
+        match piece {
+            DatetimePiece::Literal(text) => out.push_str(&text),
+            DatetimePiece::Field(field) => out.push_str(&match field {
+                DatetimeField::Year => format!("{year:04}"),
+                DatetimeField::Month => format!("{month:02}"),
+                DatetimeField::Day => format!("{day:02}"),
+                DatetimeField::Hour => format!("{hour:02}"),
+                DatetimeField::Minute => format!("{minute:02}"),
+                DatetimeField::Second => format!("{second:02}"),
+                DatetimeField::Offset => format_offset(offset_minutes),
+            }),
+        }
+    }
+    Ok(RtString::from(out))
+}
+
+/// Parses `value` against `pattern`. Every field must be written with exactly
+/// its token width; fields missing from the pattern default to
+/// `1970-01-01 00:00:00` UTC. A `Z` field shifts the result back to UTC.
+fn parse_with_pattern(pattern: &str, value: &str) -> RtResult<i64> {
+    let pieces = parse_datetime_pattern("datetime.parse", pattern)?;
+    let invalid = |message: String| RtError::new(RtErrorKind::InvalidArgument, message);
+    let (mut year, mut month, mut day) = (1970i32, 1u32, 1u32);
+    let (mut hour, mut minute, mut second) = (0u32, 0u32, 0u32);
+    let mut offset_minutes = 0i64;
+    let mut rest = value;
+    for piece in pieces {
+        match piece {
+            DatetimePiece::Literal(text) => {
+                rest = rest.strip_prefix(text.as_str()).ok_or_else(|| {
+                    invalid(format!(
+                        "datetime.parse expected `{text}` at byte {}",
+                        value.len() - rest.len()
+                    ))
+                })?;
+            }
+            DatetimePiece::Field(DatetimeField::Offset) => {
+                let (offset, len) = parse_offset(rest).ok_or_else(|| {
+                    invalid(format!(
+                        "datetime.parse invalid offset: expected `Z` or `+hh:mm`/`-hh:mm` at byte {}",
+                        value.len() - rest.len()
+                    ))
+                })?;
+                offset_minutes = offset;
+                rest = &rest[len..];
+            }
+            DatetimePiece::Field(field) => {
+                let width = field.token().len();
+                let digits = rest
+                    .get(..width)
+                    .filter(|digits| digits.bytes().all(|b| b.is_ascii_digit()))
+                    .ok_or_else(|| {
+                        invalid(format!(
+                            "datetime.parse invalid {}: expected {width} digits for `{}` at byte {}",
+                            field.name(),
+                            field.token(),
+                            value.len() - rest.len()
+                        ))
+                    })?;
+                rest = &rest[width..];
+                let parsed: u32 = digits.parse().unwrap_or_default();
+                match field {
+                    DatetimeField::Year => year = parsed as i32,
+                    DatetimeField::Month => month = parsed,
+                    DatetimeField::Day => day = parsed,
+                    DatetimeField::Hour => hour = parsed,
+                    DatetimeField::Minute => minute = parsed,
+                    DatetimeField::Second => second = parsed,
+                    DatetimeField::Offset => {}
+                }
+            }
+        }
+    }
+    if !rest.is_empty() {
+        return Err(invalid(format!(
+            "datetime.parse has trailing input `{rest}` after pattern `{pattern}`"
+        )));
+    }
+    if !(1..=12).contains(&month)
+        || day == 0
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(invalid(
+            "datetime.parse received out-of-range date/time component".to_string(),
+        ));
+    }
+    let days = days_from_civil(year, month, day);
+    Ok(
+        days * 86_400 + (hour as i64 * 3_600) + (minute as i64 * 60) + second as i64
+            - offset_minutes * 60,
+    )
+}
+
+/// `+hh:mm` / `-hh:mm`, as rendered by the `Z` pattern token.
+fn format_offset(offset_minutes: i64) -> String {
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    let abs = offset_minutes.unsigned_abs();
+    format!("{sign}{:02}:{:02}", abs / 60, abs % 60)
+}
+
+/// Reads a `Z` or `+hh:mm`/`-hh:mm` offset, returning minutes east of UTC and
+/// the consumed byte length.
+fn parse_offset(text: &str) -> Option<(i64, usize)> {
+    if text.starts_with('Z') {
+        return Some((0, 1));
+    }
+    let sign = match text.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits = text.get(1..6)?;
+    let (hours, minutes) = digits.split_once(':')?;
+    if hours.len() != 2
+        || !hours
+            .bytes()
+            .chain(minutes.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let (hours, minutes) = (hours.parse::<i64>().ok()?, minutes.parse::<i64>().ok()?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some((sign * (hours * 60 + minutes), 6))
+}
+
+/// Offset of the platform's local time zone from UTC at `seconds`, in
+/// minutes east of UTC, including any daylight-saving adjustment.
+fn platform_local_offset_minutes(seconds: i64) -> RtResult<i64> {
+    let unavailable = || {
+        RtError::new(
+            RtErrorKind::InvalidArgument,
+            format!("datetime could not determine the local time zone offset at {seconds}"),
+        )
+    };
+    let (year, month, day, hour, minute, second) =
+        platform_localtime(seconds).ok_or_else(unavailable)?;
+    let local = days_from_civil(year, month, day) * 86_400
+        + hour as i64 * 3_600
+        + minute as i64 * 60
+        + second as i64;
+    Ok((local - seconds).div_euclid(60))
+}
+
+#[cfg(unix)]
+fn platform_localtime(seconds: i64) -> Option<(i32, u32, u32, u32, u32, u32)> {
+    let time = libc::time_t::try_from(seconds).ok()?;
+    // SAFETY: `libc::tm` is plain integers and pointers, so the all-zero value
+    // is valid.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    // SAFETY: `time` and `tm` are valid for the duration of the call.
+    let out = unsafe { libc::localtime_r(&time, &mut tm) };
+    if out.is_null() {
+        return None;
+    }
+    platform_tm_components(&tm)
+}
+
+#[cfg(windows)]
+fn platform_localtime(seconds: i64) -> Option<(i32, u32, u32, u32, u32, u32)> {
+    let time = libc::time_t::try_from(seconds).ok()?;
+    // SAFETY: `libc::tm` is plain integers, so the all-zero value is valid.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    // SAFETY: `time` and `tm` are valid for the duration of the call.
+    let status = unsafe {
+        libc::tzset();
+        libc::localtime_s(&mut tm, &time)
+    };
+    if status != 0 {
+        return None;
+    }
+    platform_tm_components(&tm)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn platform_localtime(_seconds: i64) -> Option<(i32, u32, u32, u32, u32, u32)> {
+    None
+}
+
+#[cfg(any(unix, windows))]
+fn platform_tm_components(tm: &libc::tm) -> Option<(i32, u32, u32, u32, u32, u32)> {
+    Some((
+        tm.tm_year.checked_add(1900)?,
+        u32::try_from(tm.tm_mon + 1).ok()?,
+        u32::try_from(tm.tm_mday).ok()?,
+        u32::try_from(tm.tm_hour).ok()?,
+        u32::try_from(tm.tm_min).ok()?,
+        // Leap seconds are reported as 60; fold them into the minute.
+        u32::try_from(tm.tm_sec.min(59)).ok()?,
+    ))
+}
+
 fn unix_seconds_to_components(seconds: i64) -> (i32, u32, u32, u32, u32, u32) {
     let days = seconds.div_euclid(86_400);
     let secs_of_day = seconds.rem_euclid(86_400);
@@ -1931,7 +2682,7 @@ fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
     era * 146_097 + doe - 719_468
 }
 
-fn days_in_month(year: i32, month: u32) -> u32 {
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
     match month {
         1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
         4 | 6 | 9 | 11 => 30,
@@ -1951,22 +2702,33 @@ fn unit_interval_from_bits(state: u64) -> f64 {
 }
 
 fn read_line_trimmed(reader: &mut impl BufRead) -> RtResult<RtString> {
+    Ok(try_read_line_trimmed(reader)?.unwrap_or_else(|| RtString::from("")))
+}
+
+fn try_read_line_trimmed(reader: &mut impl BufRead) -> RtResult<Option<RtString>> {
+    Ok(read_raw_line(reader)?.map(|line| RtString::from(trim_line_terminator(&line))))
+}
+
+fn read_raw_line(reader: &mut impl BufRead) -> RtResult<Option<String>> {
     let mut line = String::new();
-    reader
+    let read = reader
         .read_line(&mut line)
         .map_err(|err| RtError::io(err.to_string()))?;
-    if line.ends_with('\n') {
-        line.pop();
-        if line.ends_with('\r') {
-            line.pop();
-        }
+    Ok((read > 0).then_some(line))
+}
+
+fn trim_line_terminator(line: &str) -> &str {
+    match line.strip_suffix('\n') {
+        Some(line) => line.strip_suffix('\r').unwrap_or(line),
+        None => line,
     }
-    Ok(RtString::from(line))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{read_line_trimmed, unit_interval_from_bits, NoopHost, RtHost};
+    use super::{
+        read_line_trimmed, try_read_line_trimmed, unit_interval_from_bits, NoopHost, RtHost,
+    };
     use crate::RtString;
     use std::io::Cursor;
 
@@ -2088,6 +2850,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_read_line_trimmed_distinguishes_blank_lines_from_eof() {
+        let mut input = Cursor::new("first\n\nlast");
+        assert_eq!(
+            try_read_line_trimmed(&mut input).expect("first"),
+            Some(RtString::from("first"))
+        );
+        assert_eq!(
+            try_read_line_trimmed(&mut input).expect("blank"),
+            Some(RtString::from(""))
+        );
+        assert_eq!(
+            try_read_line_trimmed(&mut input).expect("last"),
+            Some(RtString::from("last"))
+        );
+        assert_eq!(try_read_line_trimmed(&mut input).expect("eof"), None);
+    }
+
     #[test]
     fn random_int_handles_full_non_negative_i64_span() {
         let mut host = NoopHost::default();
@@ -1,6 +1,7 @@
 pub mod array;
 pub mod builtins;
 pub mod bytes;
+pub mod display;
 pub mod error;
 mod ffi_builtins;
 mod ffi_containers;
@@ -13,6 +14,7 @@ pub mod host;
 pub mod map;
 pub mod option;
 pub mod result;
+pub mod store;
 pub mod string;
 pub mod value;
 pub mod vec;
@@ -21,7 +23,7 @@ pub use array::RtArray;
 pub use builtins::str as str_builtin;
 pub use bytes::RtBytes;
 pub use error::{RtError, RtErrorKind, RtResult};
-pub use function::{RtFunctionRegistry, RtNativeFn};
+pub use function::{RtFunctionRegistry, RtHostFn, RtNativeFn};
 pub use host::{NoopHost, RtHost, RtNetResource, RtNetResourceTable};
 pub use map::RtMap;
 pub use option::RtOption;
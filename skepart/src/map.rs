@@ -35,6 +35,13 @@ impl RtMap {
         self.guard().remove(key)
     }
 
+    /// Keys in sorted order.
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys = self.guard().keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
     fn guard(&self) -> MutexGuard<'_, HashMap<String, RtValue>> {
         self.0
             .lock()
@@ -0,0 +1,205 @@
+//! File-backed string key-value store behind the `store` builtins.
+//!
+//! The file is text: a `skepa-store 1` header line, then one record per key
+//! in key order, written as `<key bytes> <value bytes>\n<key><value>\n`. Length
+//! prefixes keep keys and values free to contain newlines.
+//!
+//! Every operation takes a lock on a sibling `<path>.lock` file (shared for
+//! reads, exclusive for writes) and re-reads the data file, so concurrent
+//! programs using the same store see each other's writes. Writes go to
+//! `<path>.tmp` and are renamed over the data file, so a crash never leaves a
+//! half-written store.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{RtError, RtHandle, RtHandleKind, RtResult};
+
+const HEADER: &str = "skepa-store 1\n";
+
+#[derive(Debug)]
+pub struct RtStoreFile {
+    path: PathBuf,
+}
+
+impl RtStoreFile {
+    /// Opens the store at `path`, creating an empty one if the file is missing.
+    pub fn open(path: &str) -> RtResult<Self> {
+        let store = Self {
+            path: PathBuf::from(path),
+        };
+        let _lock = store.lock(true)?;
+        if store.path.exists() {
+            store.load()?;
+        } else {
+            store.save(&BTreeMap::new())?;
+        }
+        Ok(store)
+    }
+
+    pub fn get(&self, key: &str) -> RtResult<Option<String>> {
+        let _lock = self.lock(false)?;
+        Ok(self.load()?.remove(key))
+    }
+
+    pub fn keys(&self) -> RtResult<Vec<String>> {
+        let _lock = self.lock(false)?;
+        Ok(self.load()?.into_keys().collect())
+    }
+
+    pub fn set(&self, key: &str, value: &str) -> RtResult<()> {
+        let _lock = self.lock(true)?;
+        let mut entries = self.load()?;
+        entries.insert(key.to_string(), value.to_string());
+        self.save(&entries)
+    }
+
+    /// Removes `key`, returning whether it was present.
+    pub fn delete(&self, key: &str) -> RtResult<bool> {
+        let _lock = self.lock(true)?;
+        let mut entries = self.load()?;
+        if entries.remove(key).is_none() {
+            return Ok(false);
+        }
+        self.save(&entries)?;
+        Ok(true)
+    }
+
+    /// The lock is released when the returned file is dropped.
+    fn lock(&self, exclusive: bool) -> RtResult<File> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(sibling(&self.path, "lock"))
+            .map_err(|err| self.io_error(err))?;
+        let locked = if exclusive {
+            file.lock()
+        } else {
+            file.lock_shared()
+        };
+        locked.map_err(|err| self.io_error(err))?;
+        Ok(file)
+    }
+
+    fn load(&self) -> RtResult<BTreeMap<String, String>> {
+        let data = match fs::read(&self.path) {
+            Ok(data) => data,
+            // Removed behind our back: read it as empty, as `open` would create it.
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(err) => return Err(self.io_error(err)),
+        };
+        parse(&data).map_err(|detail| {
+            RtError::io(format!(
+                "store file `{}` is malformed: {detail}",
+                self.path.display()
+            ))
+        })
+    }
+
+    fn save(&self, entries: &BTreeMap<String, String>) -> RtResult<()> {
+        let mut out = HEADER.as_bytes().to_vec();
+        for (key, value) in entries {
+            out.extend_from_slice(format!("{} {}\n", key.len(), value.len()).as_bytes());
+            out.extend_from_slice(key.as_bytes());
+            out.extend_from_slice(value.as_bytes());
+            out.push(b'\n');
+        }
+        let tmp = sibling(&self.path, "tmp");
+        let mut file = File::create(&tmp).map_err(|err| self.io_error(err))?;
+        file.write_all(&out)
+            .and_then(|()| file.sync_all())
+            .map_err(|err| self.io_error(err))?;
+        fs::rename(&tmp, &self.path).map_err(|err| self.io_error(err))
+    }
+
+    fn io_error(&self, err: std::io::Error) -> RtError {
+        RtError::io(format!("store `{}`: {err}", self.path.display()))
+    }
+}
+
+/// Open stores, kept apart from the socket and task handles in
+/// `RtNetResourceTable` so closing one never goes through the network path.
+#[derive(Debug, Default)]
+pub struct RtStoreTable {
+    next_handle_id: usize,
+    stores: HashMap<usize, RtStoreFile>,
+}
+
+impl RtStoreTable {
+    pub fn insert(&mut self, store: RtStoreFile) -> RtHandle {
+        let id = self.next_handle_id;
+        self.next_handle_id += 1;
+        self.stores.insert(id, store);
+        RtHandle {
+            id,
+            kind: RtHandleKind::Store,
+        }
+    }
+
+    pub fn get(&self, handle: RtHandle) -> RtResult<&RtStoreFile> {
+        self.check_kind(handle)?;
+        self.stores
+            .get(&handle.id)
+            .ok_or_else(|| unknown_store(handle))
+    }
+
+    pub fn remove(&mut self, handle: RtHandle) -> RtResult<RtStoreFile> {
+        self.check_kind(handle)?;
+        self.stores
+            .remove(&handle.id)
+            .ok_or_else(|| unknown_store(handle))
+    }
+
+    fn check_kind(&self, handle: RtHandle) -> RtResult<()> {
+        if handle.kind != RtHandleKind::Store {
+            return Err(RtError::invalid_handle_kind(
+                RtHandleKind::Store.type_name(),
+                handle.kind.type_name(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn unknown_store(handle: RtHandle) -> RtError {
+    RtError::invalid_handle(format!("unknown store handle id {}", handle.id))
+}
+
+fn sibling(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+fn parse(data: &[u8]) -> Result<BTreeMap<String, String>, String> {
+    let mut rest = data
+        .strip_prefix(HEADER.as_bytes())
+        .ok_or("missing `skepa-store 1` header")?;
+    let mut entries = BTreeMap::new();
+    while !rest.is_empty() {
+        let line_end = rest
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .ok_or("unterminated record header")?;
+        let line = std::str::from_utf8(&rest[..line_end]).map_err(|_| "invalid record header")?;
+        let (key_len, value_len) = line
+            .split_once(' ')
+            .and_then(|(key, value)| Some((key.parse().ok()?, value.parse().ok()?)))
+            .ok_or_else(|| format!("invalid record header `{line}`"))?;
+        rest = &rest[line_end + 1..];
+        let record_len = usize::checked_add(key_len, value_len).ok_or("truncated record")?;
+        if rest.len() <= record_len || rest[record_len] != b'\n' {
+            return Err("truncated record".to_string());
+        }
+        let text = |bytes: &[u8]| {
+            String::from_utf8(bytes.to_vec()).map_err(|_| "record is not valid UTF-8".to_string())
+        };
+        entries.insert(text(&rest[..key_len])?, text(&rest[key_len..record_len])?);
+        rest = &rest[record_len + 1..];
+    }
+    Ok(entries)
+}
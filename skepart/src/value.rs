@@ -5,10 +5,29 @@ use crate::{RtArray, RtBytes, RtError, RtMap, RtOption, RtResult, RtResultValue,
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct RtFunctionRef(pub usize);
 
+impl RtFunctionRef {
+    /// Set on refs handed out by [`crate::RtFunctionRegistry`]. IR function ids and
+    /// native code addresses never have the top bit set, so callers can tell a
+    /// host function from a program function.
+    const HOST_BIT: usize = 1 << (usize::BITS - 1);
+
+    /// Ref to the host function at `index` in a [`crate::RtFunctionRegistry`].
+    pub fn host(index: usize) -> Self {
+        Self(index | Self::HOST_BIT)
+    }
+
+    /// Registry index of a host function ref, or `None` for program functions.
+    pub fn host_index(self) -> Option<usize> {
+        (self.0 & Self::HOST_BIT != 0).then_some(self.0 & !Self::HOST_BIT)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RtHandleKind {
     Socket,
     Listener,
+    UdpSocket,
+    Store,
     Task,
     Channel,
     Library,
@@ -37,17 +56,32 @@ enum RtStructFields {
     Strings(Vec<RtString>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct RtStruct {
     pub layout: Arc<RtStructLayout>,
     fields: RtStructFields,
 }
 
+/// Structs are equal when they are the same struct type and every field is
+/// equal, comparing nested structs and arrays by value.
+impl PartialEq for RtStruct {
+    fn eq(&self, other: &Self) -> bool {
+        if self.layout.name != other.layout.name {
+            return false;
+        }
+        if self.fields == other.fields {
+            return true;
+        }
+        Self::fields_to_values(&self.fields) == Self::fields_to_values(&other.fields)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum RtValue {
     Int(i64),
     Float(f64),
     Bool(bool),
+    Char(char),
     String(RtString),
     Bytes(RtBytes),
     Option(RtOption),
@@ -67,6 +101,7 @@ impl RtValue {
             Self::Int(_) => "Int",
             Self::Float(_) => "Float",
             Self::Bool(_) => "Bool",
+            Self::Char(_) => "Char",
             Self::String(_) => "String",
             Self::Bytes(_) => "Bytes",
             Self::Option(_) => "Option",
@@ -111,6 +146,16 @@ impl RtValue {
         }
     }
 
+    pub fn expect_char(&self) -> RtResult<char> {
+        match self {
+            Self::Char(value) => Ok(*value),
+            other => Err(RtError::type_mismatch(format!(
+                "expected Char, got {}",
+                other.type_name()
+            ))),
+        }
+    }
+
     pub fn expect_string(&self) -> RtResult<RtString> {
         match self {
             Self::String(value) => Ok(value.clone()),
@@ -240,6 +285,8 @@ impl RtHandleKind {
         match self {
             Self::Socket => "Socket",
             Self::Listener => "Listener",
+            Self::UdpSocket => "UdpSocket",
+            Self::Store => "Store",
             Self::Task => "Task",
             Self::Channel => "Channel",
             Self::Library => "Library",
@@ -381,6 +428,10 @@ impl RtStruct {
         }
     }
 
+    pub fn field_values(&self) -> Vec<RtValue> {
+        Self::fields_to_values(&self.fields)
+    }
+
     pub fn get_named_field(&self, name: &str) -> RtResult<RtValue> {
         let index = self.field_index(name).ok_or_else(|| {
             RtError::new(
@@ -187,6 +187,21 @@ impl RtVec {
         }
     }
 
+    /// Snapshot of the current elements; later mutations do not affect it.
+    pub fn items(&self) -> Vec<RtValue> {
+        Self::repr_to_values(&self.guard())
+    }
+
+    /// Replaces every element, keeping the same shared vector object.
+    pub fn replace_items(&self, items: Vec<RtValue>) {
+        let rebuilt = Self::new();
+        for item in items {
+            rebuilt.push(item);
+        }
+        let repr = rebuilt.guard().clone();
+        *self.guard() = repr;
+    }
+
     fn repr_to_values(repr: &RtVecRepr) -> Vec<RtValue> {
         match repr {
             RtVecRepr::Values(items) => items.clone(),
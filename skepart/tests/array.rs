@@ -77,3 +77,16 @@ fn arrays_support_deeper_nested_mixed_values() {
         other => panic!("expected nested array, got {other:?}"),
     }
 }
+
+#[test]
+fn arrays_compare_by_elements_across_representations() {
+    let ints = RtArray::new(vec![RtValue::Int(1), RtValue::Int(2)]);
+    let mut widened = RtArray::new(vec![RtValue::Int(1), RtValue::Int(0)]);
+    widened
+        .set(1, RtValue::String(RtString::from("x")))
+        .expect("set should work");
+    widened.set(1, RtValue::Int(2)).expect("set should work");
+    assert_eq!(ints, widened);
+    assert_ne!(ints, RtArray::new(vec![RtValue::Int(1)]));
+    assert_ne!(ints, RtArray::new(vec![RtValue::Int(1), RtValue::Int(3)]));
+}
@@ -1,7 +1,10 @@
 mod common;
 
 use common::RecordingHostBuilder;
-use skepart::{builtins, RtBytes, RtErrorKind, RtFunctionRef, RtHost, RtResult, RtString, RtValue};
+use skepart::{
+    builtins, NoopHost, RtArray, RtBytes, RtErrorKind, RtFunctionRef, RtHost, RtOption, RtResult,
+    RtResultValue, RtString, RtValue,
+};
 
 struct UnsupportedHost;
 
@@ -25,6 +28,25 @@ impl builtins::BuiltinRuntime for ImmediateRuntime {
     }
 }
 
+struct CallbackRuntime;
+
+impl builtins::BuiltinRuntime for CallbackRuntime {
+    fn call_function(&mut self, function: RtFunctionRef, args: &[RtValue]) -> RtResult<RtValue> {
+        match (function.0, args) {
+            (1, [RtValue::Int(x)]) => Ok(RtValue::Int(x * 2)),
+            (2, [RtValue::Int(x)]) => Ok(RtValue::Bool(x % 2 == 0)),
+            (3, [RtValue::Int(acc), RtValue::Int(x)]) => Ok(RtValue::Int(acc + x)),
+            // Compares by tens digit only, so stability is observable.
+            (4, [RtValue::Int(a), RtValue::Int(b)]) => Ok(RtValue::Int(a / 10 - b / 10)),
+            (5, [_, _]) => Ok(RtValue::Bool(true)),
+            _ => Err(skepart::RtError::new(
+                skepart::RtErrorKind::InvalidArgument,
+                format!("unknown runtime function id {}", function.0),
+            )),
+        }
+    }
+}
+
 struct ThreadedRuntime;
 
 impl builtins::BuiltinRuntime for ThreadedRuntime {
@@ -112,6 +134,217 @@ fn builtins_dispatch_valid_core_families() {
     );
 }
 
+#[test]
+fn builtins_cover_strict_string_index_and_slice_helpers() {
+    let text = |value: &str| RtValue::String(RtString::from(value));
+    assert_eq!(
+        builtins::call("str", "__index", &[text("héllo"), RtValue::Int(1)]).expect("str.__index"),
+        text("é")
+    );
+    assert_eq!(
+        builtins::call(
+            "str",
+            "__slice",
+            &[text("héllo"), RtValue::Int(1), RtValue::Int(4)]
+        )
+        .expect("str.__slice"),
+        text("éll")
+    );
+    for args in [
+        vec![text("abc"), RtValue::Int(3)],
+        vec![text("abc"), RtValue::Int(-1)],
+    ] {
+        assert_eq!(
+            builtins::call("str", "__index", &args)
+                .expect_err("index out of range")
+                .kind,
+            RtErrorKind::IndexOutOfBounds
+        );
+    }
+    let err = builtins::call(
+        "str",
+        "__slice",
+        &[text("abc"), RtValue::Int(2), RtValue::Int(1)],
+    )
+    .expect_err("reversed bounds");
+    assert_eq!(err.kind, RtErrorKind::IndexOutOfBounds);
+    assert!(err
+        .message
+        .contains("slice 2..1 out of bounds for length 3"));
+}
+
+#[test]
+fn builtins_cover_wrapping_math() {
+    let call = |name: &str, left: i64, right: i64| {
+        builtins::call("math", name, &[RtValue::Int(left), RtValue::Int(right)]).expect(name)
+    };
+    assert_eq!(call("wrappingAdd", i64::MAX, 1), RtValue::Int(i64::MIN));
+    assert_eq!(call("wrappingSub", i64::MIN, 1), RtValue::Int(i64::MAX));
+    assert_eq!(call("wrappingMul", i64::MAX, 2), RtValue::Int(-2));
+    assert_eq!(call("wrappingAdd", 2, 3), RtValue::Int(5));
+}
+
+#[test]
+fn builtins_cover_checked_division() {
+    let checked = |left: i64, right: i64| {
+        builtins::call(
+            "math",
+            "checkedDiv",
+            &[RtValue::Int(left), RtValue::Int(right)],
+        )
+        .expect("math.checkedDiv")
+    };
+    let div_or = |left: i64, right: i64, default: i64| {
+        builtins::call(
+            "math",
+            "divOr",
+            &[
+                RtValue::Int(left),
+                RtValue::Int(right),
+                RtValue::Int(default),
+            ],
+        )
+        .expect("math.divOr")
+    };
+    assert_eq!(
+        checked(7, 2),
+        RtValue::Option(RtOption::some(RtValue::Int(3)))
+    );
+    assert_eq!(checked(7, 0), RtValue::Option(RtOption::none()));
+    assert_eq!(checked(i64::MIN, -1), RtValue::Option(RtOption::none()));
+    assert_eq!(div_or(-7, 2, 99), RtValue::Int(-3));
+    assert_eq!(div_or(7, 0, 99), RtValue::Int(99));
+    assert_eq!(div_or(i64::MIN, -1, 0), RtValue::Int(0));
+}
+
+#[test]
+fn builtins_cover_char_conversions_and_classification() {
+    let some_char = |value: char| RtValue::Option(RtOption::some(RtValue::Char(value)));
+    let none = RtValue::Option(RtOption::none());
+    let text = |value: &str| RtValue::String(RtString::from(value));
+    assert_eq!(
+        builtins::call("char", "toInt", &[RtValue::Char('é')]).expect("char.toInt"),
+        RtValue::Int(233)
+    );
+    assert_eq!(
+        builtins::call("char", "fromInt", &[RtValue::Int(65)]).expect("char.fromInt"),
+        some_char('A')
+    );
+    for code in [-1, 0xD800, 0x110000] {
+        assert_eq!(
+            builtins::call("char", "fromInt", &[RtValue::Int(code)]).expect("char.fromInt"),
+            none
+        );
+    }
+    assert_eq!(
+        builtins::call("char", "toString", &[RtValue::Char('é')]).expect("char.toString"),
+        text("é")
+    );
+    assert_eq!(
+        builtins::call("char", "fromString", &[text("é")]).expect("char.fromString"),
+        some_char('é')
+    );
+    for value in ["", "ab"] {
+        assert_eq!(
+            builtins::call("char", "fromString", &[text(value)]).expect("char.fromString"),
+            none
+        );
+    }
+    assert_eq!(
+        builtins::call("char", "isDigit", &[RtValue::Char('7')]).expect("char.isDigit"),
+        RtValue::Bool(true)
+    );
+    assert_eq!(
+        builtins::call("char", "isAlpha", &[RtValue::Char('_')]).expect("char.isAlpha"),
+        RtValue::Bool(false)
+    );
+    assert_eq!(
+        builtins::call("char", "isWhitespace", &[RtValue::Char('\t')]).expect("char.isWhitespace"),
+        RtValue::Bool(true)
+    );
+    assert_eq!(
+        builtins::call("char", "toInt", &[RtValue::Int(1)])
+            .expect_err("non-char argument")
+            .kind,
+        RtErrorKind::TypeMismatch
+    );
+}
+
+#[test]
+fn builtins_cover_bytes_hex_and_text_encodings() {
+    let ok_bytes = |data: &[u8]| {
+        RtValue::Result(skepart::RtResultValue::ok(RtValue::Bytes(RtBytes::from(
+            data,
+        ))))
+    };
+    let err = |message: &str| {
+        RtValue::Result(skepart::RtResultValue::err(RtValue::String(
+            RtString::from(message),
+        )))
+    };
+    let text = |value: &str| RtValue::String(RtString::from(value));
+
+    assert_eq!(
+        builtins::call(
+            "bytes",
+            "toHex",
+            &[RtValue::Bytes(RtBytes::from(vec![0u8, 171, 255]))]
+        )
+        .expect("bytes.toHex"),
+        text("00abff")
+    );
+    assert_eq!(
+        builtins::call("bytes", "fromHex", &[text("00ABff")]).expect("bytes.fromHex"),
+        ok_bytes(&[0, 171, 255])
+    );
+    assert_eq!(
+        builtins::call("bytes", "fromHex", &[text("abc")]).expect("odd hex"),
+        err("bytes.fromHex expected an even number of hex digits")
+    );
+    assert_eq!(
+        builtins::call("bytes", "fromHex", &[text("+f")]).expect("signed hex"),
+        err("bytes.fromHex found invalid hex digits `+f`")
+    );
+
+    assert_eq!(
+        builtins::call("bytes", "encode", &[text("café"), text("latin1")]).expect("latin1"),
+        ok_bytes(&[99, 97, 102, 233])
+    );
+    assert_eq!(
+        builtins::call("bytes", "encode", &[text("café"), text("UTF-8")]).expect("utf-8"),
+        ok_bytes("café".as_bytes())
+    );
+    assert_eq!(
+        builtins::call("bytes", "encode", &[text("café"), text("ascii")]).expect("ascii"),
+        err("bytes.encode found a character outside ascii")
+    );
+    assert_eq!(
+        builtins::call("bytes", "encode", &[text("x"), text("ebcdic")]).expect("unknown"),
+        err("bytes.encode does not support encoding `ebcdic`")
+    );
+    assert_eq!(
+        builtins::call(
+            "bytes",
+            "decode",
+            &[
+                RtValue::Bytes(RtBytes::from(vec![99u8, 97, 102, 233])),
+                text("iso-8859-1")
+            ],
+        )
+        .expect("decode latin1"),
+        RtValue::Result(skepart::RtResultValue::ok(text("café")))
+    );
+    assert_eq!(
+        builtins::call(
+            "bytes",
+            "decode",
+            &[RtValue::Bytes(RtBytes::from(vec![233u8])), text("utf8")],
+        )
+        .expect("decode invalid utf8"),
+        err("bytes.decode expected valid utf-8 data")
+    );
+}
+
 #[test]
 fn builtins_cover_bytes_roundtrip_and_type_errors() {
     let bytes_value = builtins::call(
@@ -530,6 +763,229 @@ fn builtins_spawn_uses_runtime_spawn_hook_when_available() {
     );
 }
 
+#[test]
+fn builtins_cover_vec_map_filter_and_reduce() {
+    let mut host = NoopHost::default();
+    let mut runtime = CallbackRuntime;
+    let values = skepart::RtVec::new();
+    for value in 1..=4 {
+        values.push(RtValue::Int(value));
+    }
+    let values = RtValue::Vec(values);
+
+    let doubled = builtins::call_with_host_runtime(
+        &mut host,
+        &mut runtime,
+        "vec",
+        "map",
+        &[values.clone(), RtValue::Function(RtFunctionRef(1))],
+    )
+    .expect("vec.map");
+    let RtValue::Vec(doubled) = doubled else {
+        panic!("vec.map should return a vec");
+    };
+    assert_eq!(
+        doubled.items(),
+        vec![
+            RtValue::Int(2),
+            RtValue::Int(4),
+            RtValue::Int(6),
+            RtValue::Int(8)
+        ]
+    );
+
+    let evens = builtins::call_with_host_runtime(
+        &mut host,
+        &mut runtime,
+        "vec",
+        "filter",
+        &[values.clone(), RtValue::Function(RtFunctionRef(2))],
+    )
+    .expect("vec.filter");
+    let RtValue::Vec(evens) = evens else {
+        panic!("vec.filter should return a vec");
+    };
+    assert_eq!(evens.items(), vec![RtValue::Int(2), RtValue::Int(4)]);
+
+    assert_eq!(
+        builtins::call_with_host_runtime(
+            &mut host,
+            &mut runtime,
+            "vec",
+            "reduce",
+            &[
+                values,
+                RtValue::Int(10),
+                RtValue::Function(RtFunctionRef(3))
+            ],
+        )
+        .expect("vec.reduce"),
+        RtValue::Int(20)
+    );
+}
+
+#[test]
+fn builtins_cover_arr_map_and_filter() {
+    let mut host = NoopHost::default();
+    let mut runtime = CallbackRuntime;
+    let values = RtValue::Array(RtArray::new(vec![
+        RtValue::Int(1),
+        RtValue::Int(2),
+        RtValue::Int(3),
+    ]));
+
+    assert_eq!(
+        builtins::call_with_host_runtime(
+            &mut host,
+            &mut runtime,
+            "arr",
+            "map",
+            &[values.clone(), RtValue::Function(RtFunctionRef(1))],
+        )
+        .expect("arr.map"),
+        RtValue::Array(RtArray::new(vec![
+            RtValue::Int(2),
+            RtValue::Int(4),
+            RtValue::Int(6),
+        ]))
+    );
+
+    let evens = builtins::call_with_host_runtime(
+        &mut host,
+        &mut runtime,
+        "arr",
+        "filter",
+        &[values, RtValue::Function(RtFunctionRef(2))],
+    )
+    .expect("arr.filter");
+    let RtValue::Vec(evens) = evens else {
+        panic!("arr.filter should return a vec");
+    };
+    assert_eq!(evens.items(), vec![RtValue::Int(2)]);
+}
+
+#[test]
+fn builtins_cover_arr_lookups_by_value() {
+    let mut host = NoopHost::default();
+    let mut runtime = CallbackRuntime;
+    let pair =
+        |a: i64, b: i64| RtValue::Array(RtArray::new(vec![RtValue::Int(a), RtValue::Int(b)]));
+    let values = RtValue::Array(RtArray::new(vec![pair(1, 2), pair(3, 4), pair(1, 2)]));
+    let mut call = |name: &str, needle: RtValue| {
+        builtins::call_with_host_runtime(
+            &mut host,
+            &mut runtime,
+            "arr",
+            name,
+            &[values.clone(), needle],
+        )
+        .expect("arr lookup")
+    };
+
+    assert_eq!(call("contains", pair(3, 4)), RtValue::Bool(true));
+    assert_eq!(call("contains", pair(4, 3)), RtValue::Bool(false));
+    assert_eq!(call("indexOf", pair(3, 4)), RtValue::Int(1));
+    assert_eq!(call("indexOf", pair(9, 9)), RtValue::Int(-1));
+    assert_eq!(call("count", pair(1, 2)), RtValue::Int(2));
+}
+
+#[test]
+fn builtins_sort_by_comparator_is_stable() {
+    let mut host = NoopHost::default();
+    let mut runtime = CallbackRuntime;
+    let values = RtValue::Array(RtArray::new(vec![
+        RtValue::Int(21),
+        RtValue::Int(12),
+        RtValue::Int(20),
+        RtValue::Int(11),
+        RtValue::Int(5),
+    ]));
+
+    assert_eq!(
+        builtins::call_with_host_runtime(
+            &mut host,
+            &mut runtime,
+            "arr",
+            "sortBy",
+            &[values, RtValue::Function(RtFunctionRef(4))],
+        )
+        .expect("arr.sortBy"),
+        RtValue::Array(RtArray::new(vec![
+            RtValue::Int(5),
+            RtValue::Int(12),
+            RtValue::Int(11),
+            RtValue::Int(21),
+            RtValue::Int(20),
+        ]))
+    );
+
+    let vec = skepart::RtVec::new();
+    for value in [30, 3, 31, 2] {
+        vec.push(RtValue::Int(value));
+    }
+    assert_eq!(
+        builtins::call_with_host_runtime(
+            &mut host,
+            &mut runtime,
+            "vec",
+            "sort",
+            &[
+                RtValue::Vec(vec.clone()),
+                RtValue::Function(RtFunctionRef(4))
+            ],
+        )
+        .expect("vec.sort"),
+        RtValue::Unit
+    );
+    assert_eq!(
+        vec.items(),
+        vec![
+            RtValue::Int(3),
+            RtValue::Int(2),
+            RtValue::Int(30),
+            RtValue::Int(31)
+        ]
+    );
+}
+
+#[test]
+fn builtins_reject_non_int_sort_comparator() {
+    let mut host = NoopHost::default();
+    let mut runtime = CallbackRuntime;
+    let vec = skepart::RtVec::new();
+    vec.push(RtValue::Int(1));
+    vec.push(RtValue::Int(2));
+    let err = builtins::call_with_host_runtime(
+        &mut host,
+        &mut runtime,
+        "vec",
+        "sort",
+        &[
+            RtValue::Vec(vec.clone()),
+            RtValue::Function(RtFunctionRef(5)),
+        ],
+    )
+    .expect_err("comparator returning Bool");
+    assert_eq!(err.kind, RtErrorKind::TypeMismatch);
+    assert_eq!(vec.items(), vec![RtValue::Int(1), RtValue::Int(2)]);
+}
+
+#[test]
+fn builtins_reject_non_bool_filter_predicate() {
+    let mut host = NoopHost::default();
+    let mut runtime = CallbackRuntime;
+    let values = RtValue::Array(RtArray::new(vec![RtValue::Int(1)]));
+    let err = builtins::call_with_host_runtime(
+        &mut host,
+        &mut runtime,
+        "arr",
+        "filter",
+        &[values, RtValue::Function(RtFunctionRef(1))],
+    )
+    .expect_err("predicate returning Int");
+    assert_eq!(err.kind, RtErrorKind::TypeMismatch);
+}
+
 #[test]
 fn builtins_cover_net_flush() {
     let mut host = RecordingHostBuilder::seeded().build();
@@ -1336,6 +1792,21 @@ fn builtins_cover_ffi_integer_calls_and_errors() {
         .expect("ffi.call generic bytes-int"),
         RtValue::Int(7)
     );
+    assert_eq!(
+        builtins::call_with_host(
+            &mut host,
+            "ffi",
+            "call",
+            &[
+                symbol.clone(),
+                RtValue::String(RtString::from("f64,f64->f64")),
+                RtValue::Float(1.5),
+                RtValue::Float(4.0),
+            ],
+        )
+        .expect("ffi.call generic float-float"),
+        RtValue::Float(6.0)
+    );
     assert!(
         host.output.contains("[fficall0int 1]")
             && host.output.contains("[fficall0void 1]")
@@ -1354,7 +1825,8 @@ fn builtins_cover_ffi_integer_calls_and_errors() {
             && (host.output.contains("[fficall2stringint 1=alpha|beta]")
                 || host.output.contains("[fficall2systemcstri32 1=alpha|beta]"))
             && host.output.contains("[fficall2stringintint 1=abc|2]")
-            && host.output.contains("[fficall2intint 1=2|5]"),
+            && host.output.contains("[fficall2intint 1=2|5]")
+            && host.output.contains("[fficall2floatfloat 1=1.5|4]"),
         "unexpected host output: {}",
         host.output
     );
@@ -1822,6 +2294,66 @@ fn builtins_reject_wrong_or_closed_net_handles_for_accept() {
     );
 }
 
+#[test]
+fn builtins_render_any_value_for_display() {
+    let mut host = RecordingHostBuilder::seeded().build();
+    let user = skepart::RtStruct::new(
+        std::sync::Arc::new(skepart::RtStructLayout {
+            name: "User".into(),
+            field_names: vec!["id".into(), "tags".into()],
+            field_types: vec![None, None],
+        }),
+        vec![
+            RtValue::Int(1),
+            RtValue::Array(RtArray::new(vec![RtValue::String(RtString::from("a"))])),
+        ],
+    )
+    .expect("valid struct");
+    let pair = skepart::RtStruct::named("(Int, Char)", vec![RtValue::Int(2), RtValue::Char('x')])
+        .expect("valid tuple");
+    let map = skepart::RtMap::new();
+    map.insert("k", RtValue::Option(RtOption::some(RtValue::Bool(true))));
+
+    builtins::call_with_host(
+        &mut host,
+        "io",
+        "printAny",
+        &[RtValue::Struct(user.clone())],
+    )
+    .expect("printAny struct");
+    builtins::call_with_host(&mut host, "io", "debug", &[RtValue::Struct(user)])
+        .expect("debug struct");
+    builtins::call_with_host(
+        &mut host,
+        "io",
+        "debug",
+        &[RtValue::String(RtString::from("|"))],
+    )
+    .expect("debug string");
+    assert_eq!(host.error_output, "User { id: 1, tags: [\"a\"] }\n\"|\"\n");
+    builtins::call_with_host(
+        &mut host,
+        "io",
+        "printAny",
+        &[RtValue::String(RtString::from("|"))],
+    )
+    .expect("printAny string");
+    assert_eq!(host.output, "User { id: 1, tags: [\"a\"] }|");
+    assert_eq!(
+        builtins::call(
+            "io",
+            "format",
+            &[
+                RtValue::String(RtString::from("%v %v")),
+                RtValue::Struct(pair),
+                RtValue::Map(map),
+            ],
+        )
+        .expect("format %v"),
+        RtValue::String(RtString::from("(2, 'x') {\"k\": Some(true)}"))
+    );
+}
+
 #[test]
 fn builtins_cover_more_io_arr_and_vec_edge_shapes() {
     let mut host = RecordingHostBuilder::seeded().build();
@@ -1850,6 +2382,191 @@ fn builtins_cover_more_io_arr_and_vec_edge_shapes() {
     );
 }
 
+#[test]
+fn builtins_read_piped_input_until_eof() {
+    let mut host = RecordingHostBuilder::seeded()
+        .stdin_lines(["first", "", "third"])
+        .build();
+    assert_eq!(
+        builtins::call_with_host(&mut host, "io", "tryReadLine", &[]).expect("tryReadLine"),
+        RtValue::Option(RtOption::some(RtValue::String(RtString::from("first"))))
+    );
+    let RtValue::Vec(rest) =
+        builtins::call_with_host(&mut host, "io", "readLines", &[]).expect("readLines")
+    else {
+        panic!("io.readLines should return a vec");
+    };
+    assert_eq!(rest.len(), 2);
+    assert_eq!(
+        rest.get(0).expect("blank line"),
+        RtValue::String(RtString::from(""))
+    );
+    assert_eq!(
+        builtins::call_with_host(&mut host, "io", "tryReadLine", &[]).expect("eof"),
+        RtValue::Option(RtOption::none())
+    );
+
+    let mut host = RecordingHostBuilder::seeded()
+        .stdin_lines(["a", "b"])
+        .build();
+    assert_eq!(
+        builtins::call_with_host(&mut host, "io", "readAll", &[]).expect("readAll"),
+        RtValue::String(RtString::from("a\nb\n"))
+    );
+    assert_eq!(
+        builtins::call_with_host(&mut host, "io", "readAll", &[]).expect("readAll at eof"),
+        RtValue::String(RtString::from(""))
+    );
+
+    let mut host = RecordingHostBuilder::seeded().stdin("a\r\nb").build();
+    assert_eq!(
+        builtins::call_with_host(&mut host, "io", "readAll", &[]).expect("readAll exact"),
+        RtValue::String(RtString::from("a\r\nb"))
+    );
+}
+
+#[test]
+fn builtins_route_eprint_to_host_error_stream() {
+    let mut host = RecordingHostBuilder::seeded().build();
+    builtins::call_with_host(
+        &mut host,
+        "io",
+        "println",
+        &[RtValue::String(RtString::from("out"))],
+    )
+    .expect("io.println");
+    builtins::call_with_host(
+        &mut host,
+        "io",
+        "eprint",
+        &[RtValue::String(RtString::from("warn: "))],
+    )
+    .expect("io.eprint");
+    builtins::call_with_host(
+        &mut host,
+        "io",
+        "eprintln",
+        &[RtValue::String(RtString::from("bad input"))],
+    )
+    .expect("io.eprintln");
+    assert_eq!(host.output, "out\n");
+    assert_eq!(host.error_output, "warn: bad input\n");
+}
+
+#[test]
+fn builtins_copy_move_and_round_trip_bytes_through_fs() {
+    let mut host = RecordingHostBuilder::seeded().build();
+    let data = skepart::RtBytes::from(vec![0u8, 255, 10]);
+    let ok_unit = RtValue::Result(skepart::RtResultValue::ok(RtValue::Unit));
+
+    assert_eq!(
+        builtins::call_with_host(
+            &mut host,
+            "fs",
+            "writeBytes",
+            &[
+                RtValue::String(RtString::from("a.bin")),
+                RtValue::Bytes(data.clone()),
+            ],
+        )
+        .expect("fs writeBytes"),
+        ok_unit
+    );
+    assert_eq!(
+        builtins::call_with_host(
+            &mut host,
+            "fs",
+            "copyFile",
+            &[
+                RtValue::String(RtString::from("a.bin")),
+                RtValue::String(RtString::from("b.bin")),
+            ],
+        )
+        .expect("fs copyFile"),
+        ok_unit
+    );
+    assert_eq!(
+        builtins::call_with_host(
+            &mut host,
+            "fs",
+            "moveFile",
+            &[
+                RtValue::String(RtString::from("b.bin")),
+                RtValue::String(RtString::from("c.bin")),
+            ],
+        )
+        .expect("fs moveFile"),
+        ok_unit
+    );
+    assert_eq!(
+        builtins::call_with_host(
+            &mut host,
+            "fs",
+            "readBytes",
+            &[RtValue::String(RtString::from("c.bin"))],
+        )
+        .expect("fs readBytes"),
+        RtValue::Result(skepart::RtResultValue::ok(RtValue::Bytes(data)))
+    );
+    assert_eq!(
+        builtins::call_with_host(
+            &mut host,
+            "fs",
+            "moveFile",
+            &[
+                RtValue::String(RtString::from("b.bin")),
+                RtValue::String(RtString::from("d.bin")),
+            ],
+        )
+        .expect("fs moveFile missing"),
+        RtValue::Result(skepart::RtResultValue::err(RtValue::String(
+            RtString::from("Io: missing b.bin")
+        )))
+    );
+    assert_eq!(
+        host.output,
+        "[write a.bin=3 bytes][copy a.bin->b.bin][move b.bin->c.bin]"
+    );
+}
+
+#[test]
+fn builtins_route_exec_capture_argv_and_options_to_host() {
+    let mut host = RecordingHostBuilder::seeded().build();
+    let options = skepart::RtMap::new();
+    options.insert("cwd", RtValue::String(RtString::from("/work")));
+    options.insert("env.MODE", RtValue::String(RtString::from("ci")));
+
+    let value = builtins::call_with_host(
+        &mut host,
+        "os",
+        "execCapture",
+        &[
+            RtValue::String(RtString::from("git")),
+            string_vec(&["log", "--format=%H %s"]),
+            RtValue::Map(options),
+        ],
+    )
+    .expect("execCapture");
+
+    let RtValue::Result(skepart::RtResultValue::Ok(response)) = value else {
+        panic!("execCapture should return Ok");
+    };
+    let response = response.expect_map().expect("response map");
+    assert_eq!(
+        response.get("stdout"),
+        Some(RtValue::String(RtString::from("exec-out")))
+    );
+    assert_eq!(
+        response.get("code"),
+        Some(RtValue::String(RtString::from("9")))
+    );
+    assert_eq!(host.exec_argv, vec!["log", "--format=%H %s"]);
+    assert_eq!(
+        host.output,
+        "[execcapture git log --format=%H %s cwd=/work env.MODE=ci]"
+    );
+}
+
 #[test]
 fn builtins_cover_host_backed_fs_os_and_random_families_more_thoroughly() {
     let mut host = RecordingHostBuilder::seeded()
@@ -2060,6 +2777,29 @@ fn builtins_cover_datetime_component_and_parse_shapes() {
         .expect("parse unix"),
         RtValue::Result(skepart::RtResultValue::ok(RtValue::Int(10)))
     );
+    assert_eq!(
+        builtins::call_with_host(
+            &mut host,
+            "datetime",
+            "format",
+            &[RtValue::Int(5), RtValue::String(RtString::from("YYYY"))],
+        )
+        .expect("format"),
+        RtValue::String(RtString::from("format:5:YYYY"))
+    );
+    assert_eq!(
+        builtins::call_with_host(
+            &mut host,
+            "datetime",
+            "parse",
+            &[
+                RtValue::String(RtString::from("YYYY")),
+                RtValue::String(RtString::from("2025")),
+            ],
+        )
+        .expect("parse"),
+        RtValue::Result(skepart::RtResultValue::ok(RtValue::Int(404)))
+    );
     assert_eq!(
         builtins::call_with_host(&mut host, "datetime", "year", &[RtValue::Int(100)])
             .expect("year"),
@@ -2072,6 +2812,179 @@ fn builtins_cover_datetime_component_and_parse_shapes() {
     );
 }
 
+#[test]
+fn builtins_render_local_time_from_host_offset() {
+    let mut host = RecordingHostBuilder::seeded()
+        .unix_now(0)
+        .local_offset_minutes(90)
+        .build();
+    assert_eq!(
+        builtins::call_with_host(&mut host, "datetime", "nowLocal", &[]).expect("now local"),
+        RtValue::String(RtString::from("1970-01-01T01:30:00+01:30"))
+    );
+    assert_eq!(
+        builtins::call_with_host(&mut host, "datetime", "offsetMinutes", &[])
+            .expect("offset minutes"),
+        RtValue::Int(90)
+    );
+
+    let mut host = RecordingHostBuilder::seeded()
+        .local_offset_minutes(-300)
+        .build();
+    assert_eq!(
+        builtins::call_with_host(
+            &mut host,
+            "datetime",
+            "toLocal",
+            &[RtValue::Int(1_704_112_496)]
+        )
+        .expect("to local"),
+        RtValue::String(RtString::from("2024-01-01T07:34:56-05:00"))
+    );
+    assert_eq!(
+        builtins::call_with_host(
+            &mut host,
+            "datetime",
+            "formatLocal",
+            &[
+                RtValue::Int(1_704_112_496),
+                RtValue::String(RtString::from("hh:mm Z")),
+            ],
+        )
+        .expect("format local"),
+        RtValue::String(RtString::from("07:34 -05:00"))
+    );
+    let bad = builtins::call_with_host(
+        &mut host,
+        "datetime",
+        "formatLocal",
+        &[RtValue::Int(0), RtValue::String(RtString::from("ZZ"))],
+    )
+    .expect_err("unknown token");
+    assert!(bad
+        .message
+        .contains("datetime.formatLocal pattern has unknown token `ZZ`"));
+}
+
+#[test]
+fn builtins_cover_datetime_arithmetic_and_calendar_helpers() {
+    let mut host = RecordingHostBuilder::seeded().build();
+    let mut call = |name: &str, args: &[i64]| {
+        let args = args.iter().copied().map(RtValue::Int).collect::<Vec<_>>();
+        builtins::call_with_host(&mut host, "datetime", name, &args)
+    };
+
+    assert_eq!(
+        call("addDays", &[0, 2]).expect("add days"),
+        RtValue::Int(172_800)
+    );
+    assert_eq!(
+        call("addDays", &[0, -1]).expect("sub day"),
+        RtValue::Int(-86_400)
+    );
+    assert_eq!(
+        call("addHours", &[60, 3]).expect("add hours"),
+        RtValue::Int(10_860)
+    );
+    assert_eq!(
+        call("diffSeconds", &[100, 40]).expect("diff"),
+        RtValue::Int(60)
+    );
+    // 1970-01-01 was a Thursday; 2024-01-01 a Monday; 1969-12-28 a Sunday.
+    assert_eq!(call("weekday", &[0]).expect("thursday"), RtValue::Int(4));
+    assert_eq!(
+        call("weekday", &[1_704_067_200]).expect("monday"),
+        RtValue::Int(1)
+    );
+    assert_eq!(
+        call("weekday", &[-345_600]).expect("sunday"),
+        RtValue::Int(7)
+    );
+    assert_eq!(
+        call("daysInMonth", &[2024, 2]).expect("leap"),
+        RtValue::Int(29)
+    );
+    assert_eq!(
+        call("daysInMonth", &[1900, 2]).expect("century"),
+        RtValue::Int(28)
+    );
+    assert_eq!(
+        call("daysInMonth", &[2023, 4]).expect("april"),
+        RtValue::Int(30)
+    );
+
+    let bad_month = call("daysInMonth", &[2024, 13]).expect_err("bad month");
+    assert_eq!(bad_month.kind, RtErrorKind::InvalidArgument);
+    assert!(bad_month
+        .message
+        .contains("month must be in 1..=12, got 13"));
+    let overflow = call("addDays", &[i64::MAX, 1]).expect_err("overflow");
+    assert!(overflow.message.contains("datetime.addDays overflowed"));
+}
+
+#[test]
+fn builtins_cover_random_choice_shuffle_bool_and_gaussian() {
+    let ints = |values: &[i64]| {
+        RtValue::Array(RtArray::new(
+            values.iter().copied().map(RtValue::Int).collect(),
+        ))
+    };
+    let mut host = RecordingHostBuilder::seeded().random_int(2).build();
+    assert_eq!(
+        builtins::call_with_host(&mut host, "random", "choice", &[ints(&[10, 20, 30])])
+            .expect("choice"),
+        RtValue::Option(RtOption::some(RtValue::Int(30)))
+    );
+    assert_eq!(
+        builtins::call_with_host(&mut host, "random", "choice", &[ints(&[])])
+            .expect("empty choice"),
+        RtValue::Option(RtOption::none())
+    );
+
+    let shuffled_with_seed = |seed: i64| {
+        let mut host = NoopHost::default();
+        builtins::call_with_host(&mut host, "random", "seed", &[RtValue::Int(seed)]).expect("seed");
+        builtins::call_with_host(&mut host, "random", "shuffle", &[ints(&[1, 2, 3, 4, 5, 6])])
+            .expect("shuffle")
+            .expect_array()
+            .expect("array")
+            .items()
+    };
+    let first = shuffled_with_seed(42);
+    assert_eq!(first, shuffled_with_seed(42));
+    let mut sorted = first
+        .iter()
+        .map(|value| value.expect_int().expect("int"))
+        .collect::<Vec<_>>();
+    sorted.sort();
+    assert_eq!(sorted, vec![1, 2, 3, 4, 5, 6]);
+
+    let mut host = NoopHost::default();
+    let mut call = |name: &str, args: &[f64]| {
+        let args = args.iter().copied().map(RtValue::Float).collect::<Vec<_>>();
+        builtins::call_with_host(&mut host, "random", name, &args)
+    };
+    assert_eq!(call("bool", &[0.0]).expect("never"), RtValue::Bool(false));
+    assert_eq!(call("bool", &[1.0]).expect("always"), RtValue::Bool(true));
+    assert_eq!(
+        call("gaussian", &[3.5, 0.0]).expect("zero std"),
+        RtValue::Float(3.5)
+    );
+    let sample = call("gaussian", &[0.0, 1.0])
+        .expect("gaussian")
+        .expect_float()
+        .expect("float");
+    assert!(sample.is_finite());
+
+    let bad_p = call("bool", &[1.5]).expect_err("probability");
+    assert_eq!(bad_p.kind, RtErrorKind::InvalidArgument);
+    assert!(bad_p
+        .message
+        .contains("random.bool probability must be in 0.0..=1.0"));
+    let bad_std = call("gaussian", &[0.0, -1.0]).expect_err("negative std");
+    assert!(bad_std.message.contains("non-negative std"));
+}
+
 #[test]
 fn builtins_reject_typed_io_print_mismatches_and_format_extra_args() {
     let mut host = RecordingHostBuilder::seeded().build();
@@ -2192,3 +3105,322 @@ fn builtins_cover_option_and_result_inspection_helpers() {
         RtErrorKind::TypeMismatch
     );
 }
+
+#[test]
+fn builtins_cover_serde_json_and_binary_round_trips() {
+    let text = |value: &str| RtValue::String(RtString::from(value));
+    let err = |message: &str| {
+        RtValue::Result(skepart::RtResultValue::err(RtValue::String(
+            RtString::from(message),
+        )))
+    };
+
+    let counts = builtins::call("map", "new", &[]).expect("map.new");
+    for (key, value) in [("b", 2), ("a", -1)] {
+        builtins::call(
+            "map",
+            "insert",
+            &[counts.clone(), text(key), RtValue::Int(value)],
+        )
+        .expect("map.insert");
+    }
+    assert_eq!(
+        builtins::call("serde", "toJson", &[counts.clone(), text("mi")]).expect("serde.toJson"),
+        text(r#"{"a":-1,"b":2}"#)
+    );
+    let pair = RtValue::Array(RtArray::new(vec![text("x\"y\n"), text("é")]));
+    assert_eq!(
+        builtins::call("serde", "toJson", &[pair.clone(), text("a2.s")]).expect("array"),
+        text(r#"["x\"y\n","é"]"#)
+    );
+
+    let encoded =
+        builtins::call("serde", "encode", &[pair.clone(), text("a2.s")]).expect("serde.encode");
+    let RtValue::Bytes(data) = &encoded else {
+        panic!("serde.encode should return Bytes");
+    };
+    assert!(data.as_slice().starts_with(b"SKS1"));
+    assert_eq!(
+        builtins::call("serde", "decode", &[encoded.clone(), text("a2.s")]).expect("decode"),
+        RtValue::Result(skepart::RtResultValue::ok(pair))
+    );
+    let empties = builtins::call("vec", "new", &[]).expect("vec.new");
+    builtins::call(
+        "vec",
+        "push",
+        &[empties.clone(), RtValue::Array(RtArray::new(Vec::new()))],
+    )
+    .expect("vec.push");
+    let data = builtins::call("serde", "encode", &[empties.clone(), text("va0.i")])
+        .expect("encode empty arrays");
+    assert_eq!(
+        builtins::call("serde", "decode", &[data, text("va0.i")]).expect("decode empty arrays"),
+        RtValue::Result(skepart::RtResultValue::ok(empties))
+    );
+    assert_eq!(
+        builtins::call("serde", "decode", &[encoded, text("vs")]).expect("other type"),
+        err("serde.decode found data encoded from a different type")
+    );
+    assert_eq!(
+        builtins::call(
+            "serde",
+            "decode",
+            &[RtValue::Bytes(RtBytes::from(vec![1u8, 2])), text("i")]
+        )
+        .expect("foreign bytes"),
+        err("serde.decode expected data written by serde.encode")
+    );
+
+    assert_eq!(
+        builtins::call("serde", "fromJson", &[text("[1, 2.5]"), text("vi")]).expect("float"),
+        err("type mismatch at `$[1]`: expected Int, got a number")
+    );
+    assert_eq!(
+        builtins::call("serde", "fromJson", &[text("{\"a\":1"), text("mi")]).expect("eof"),
+        err("invalid JSON at byte 6: expected `,` or `}`")
+    );
+    assert_eq!(
+        builtins::call("serde", "toJson", &[RtValue::Float(f64::NAN), text("f")])
+            .expect_err("NaN")
+            .kind,
+        RtErrorKind::InvalidArgument
+    );
+}
+
+#[test]
+fn builtins_cover_encoding_round_trips_and_decode_errors() {
+    let text = |value: &str| RtValue::String(RtString::from(value));
+    let bytes = |value: &[u8]| RtValue::Bytes(RtBytes::from(value));
+    let ok = |value: RtValue| RtValue::Result(RtResultValue::ok(value));
+
+    for (raw, encoded) in [
+        (&b""[..], ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"\xff\xfe\x00", "//4A"),
+    ] {
+        assert_eq!(
+            builtins::call("encoding", "base64Encode", &[bytes(raw)]).expect("base64Encode"),
+            text(encoded)
+        );
+        assert_eq!(
+            builtins::call("encoding", "base64Decode", &[text(encoded)]).expect("base64Decode"),
+            ok(bytes(raw))
+        );
+    }
+    assert_eq!(
+        builtins::call("encoding", "base64Decode", &[text("Zm8")]).expect("unpadded"),
+        ok(bytes(b"fo"))
+    );
+    assert_eq!(
+        builtins::call("encoding", "hexEncode", &[bytes(b"\x00\xab")]).expect("hexEncode"),
+        text("00ab")
+    );
+    assert_eq!(
+        builtins::call("encoding", "hexDecode", &[text("00AB")]).expect("hexDecode"),
+        ok(bytes(b"\x00\xab"))
+    );
+    assert_eq!(
+        builtins::call("encoding", "urlEncode", &[text("a b/é+~")]).expect("urlEncode"),
+        text("a%20b%2F%C3%A9%2B~")
+    );
+    assert_eq!(
+        builtins::call("encoding", "urlDecode", &[text("a%20b%2f%C3%A9+")]).expect("urlDecode"),
+        ok(text("a b/é+"))
+    );
+    assert_eq!(
+        builtins::call("encoding", "isValidUtf8", &[bytes("é".as_bytes())]).expect("valid"),
+        RtValue::Bool(true)
+    );
+    assert_eq!(
+        builtins::call("encoding", "isValidUtf8", &[bytes(b"\xc3")]).expect("invalid"),
+        RtValue::Bool(false)
+    );
+
+    for (name, input, message) in [
+        (
+            "base64Decode",
+            "Zm9v!A==",
+            "encoding.base64Decode input has invalid character `!` at offset 4",
+        ),
+        (
+            "base64Decode",
+            "Zm8==",
+            "encoding.base64Decode input has misplaced `=` padding",
+        ),
+        (
+            "base64Decode",
+            "Zm9vZ",
+            "encoding.base64Decode input has a truncated final group",
+        ),
+        (
+            "hexDecode",
+            "0g",
+            "encoding.hexDecode found invalid hex digits `0g`",
+        ),
+        (
+            "hexDecode",
+            "abc",
+            "encoding.hexDecode expected an even number of hex digits",
+        ),
+        (
+            "urlDecode",
+            "100%",
+            "encoding.urlDecode input has a malformed `%` escape at offset 3",
+        ),
+        (
+            "urlDecode",
+            "%C3",
+            "encoding.urlDecode input escapes bytes that are not valid UTF-8",
+        ),
+    ] {
+        assert_eq!(
+            builtins::call("encoding", name, &[text(input)]).expect(name),
+            RtValue::Result(RtResultValue::err(text(message)))
+        );
+    }
+}
+
+#[test]
+fn builtins_cover_hash_digests_of_strings_and_bytes() {
+    let text = |value: &str| RtValue::String(RtString::from(value));
+    let long = "é".repeat(40);
+    for (input, sha256, md5, crc32, fnv64) in [
+        (
+            "",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            "d41d8cd98f00b204e9800998ecf8427e",
+            0,
+            -3750763034362895579,
+        ),
+        (
+            "abc",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            "900150983cd24fb0d6963f7d28e17f72",
+            891568578,
+            -1792535898324117685,
+        ),
+        (
+            long.as_str(),
+            "84fe2e03d50dd3a18b630669d7d5e361117ac6af9cbb487c284c8e6c91a9758a",
+            "639258863c18c505cc2183a5e4b63394",
+            1396315086,
+            -5452339248809687291,
+        ),
+    ] {
+        for value in [text(input), RtValue::Bytes(RtBytes::from(input.as_bytes()))] {
+            let call = |name: &str| {
+                builtins::call("hash", name, std::slice::from_ref(&value)).expect(name)
+            };
+            assert_eq!(call("sha256"), text(sha256));
+            assert_eq!(call("md5"), text(md5));
+            assert_eq!(call("crc32"), RtValue::Int(crc32));
+            assert_eq!(call("fnv64"), RtValue::Int(fnv64));
+        }
+    }
+    let err = builtins::call("hash", "sha256", &[RtValue::Int(1)]).expect_err("Int input");
+    assert_eq!(err.kind, RtErrorKind::TypeMismatch);
+}
+
+#[test]
+fn builtins_cover_csv_quoting_headers_and_malformed_input() {
+    let text = |value: &str| RtValue::String(RtString::from(value));
+    let rows = |value: RtValue| match value {
+        RtValue::Result(skepart::RtResultValue::Ok(rows)) => rows
+            .expect_vec()
+            .expect("rows")
+            .items()
+            .into_iter()
+            .map(|row| {
+                row.expect_vec()
+                    .expect("row")
+                    .items()
+                    .into_iter()
+                    .map(|field| field.expect_string().expect("field").as_str().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>(),
+        other => panic!("expected Ok rows, got {other:?}"),
+    };
+    let err = |value: RtValue| match value {
+        RtValue::Result(skepart::RtResultValue::Err(message)) => message
+            .expect_string()
+            .expect("message")
+            .as_str()
+            .to_string(),
+        other => panic!("expected Err, got {other:?}"),
+    };
+
+    let source = "name,note\r\n\"Lovelace, Ada\",\"said \"\"hi\"\"\nthen left\"\n,\n\"\"";
+    let parsed = builtins::call("csv", "parse", &[text(source)]).expect("csv.parse");
+    assert_eq!(
+        rows(parsed.clone()),
+        vec![
+            vec!["name", "note"],
+            vec!["Lovelace, Ada", "said \"hi\"\nthen left"],
+            vec!["", ""],
+            vec![""],
+        ]
+    );
+    assert_eq!(
+        rows(builtins::call("csv", "parse", &[text("")]).expect("empty")),
+        Vec::<Vec<String>>::new()
+    );
+    assert_eq!(
+        rows(builtins::call("csv", "parse", &[text("a\n\nb\n")]).expect("blank line")),
+        vec![vec!["a"], vec![""], vec!["b"]]
+    );
+
+    let RtValue::Result(skepart::RtResultValue::Ok(table)) = parsed else {
+        panic!("expected Ok rows");
+    };
+    assert_eq!(
+        builtins::call("csv", "stringify", &[*table]).expect("csv.stringify"),
+        text("name,note\n\"Lovelace, Ada\",\"said \"\"hi\"\"\nthen left\"\n,\n\n")
+    );
+
+    let people = builtins::call(
+        "csv",
+        "parseWithHeader",
+        &[text("id,name\n1,Ada\n2,\"Grace\"\n")],
+    )
+    .expect("csv.parseWithHeader");
+    let RtValue::Result(skepart::RtResultValue::Ok(people)) = people else {
+        panic!("expected Ok rows");
+    };
+    let people = people.expect_vec().expect("rows").items();
+    assert_eq!(people.len(), 2);
+    let grace = people[1].expect_map().expect("row");
+    assert_eq!(grace.keys(), vec!["id".to_string(), "name".to_string()]);
+    assert_eq!(grace.get("name"), Some(text("Grace")));
+
+    for (name, input, message) in [
+        ("parse", "a,\"b", "unterminated quoted field on line 1"),
+        (
+            "parse",
+            "a\n\"b\"c",
+            "unexpected text after closing quote on line 2",
+        ),
+        (
+            "parse",
+            "a,b\"c",
+            "unexpected quote inside unquoted field on line 1",
+        ),
+        (
+            "parseWithHeader",
+            "a,b\n1,2\n3\n",
+            "record 3 has 1 field(s), but the header has 2",
+        ),
+        (
+            "parseWithHeader",
+            "a,a\n1,2\n",
+            "duplicate column `a` in header",
+        ),
+    ] {
+        assert_eq!(
+            err(builtins::call("csv", name, &[text(input)]).expect(name)),
+            message
+        );
+    }
+}
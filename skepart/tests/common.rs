@@ -3,19 +3,24 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
 
-use skepart::{RtBytes, RtError, RtHandle, RtHandleKind, RtHost, RtResult, RtString, RtValue};
+use skepart::{
+    RtBytes, RtError, RtHandle, RtHandleKind, RtHost, RtMap, RtResult, RtString, RtValue,
+};
 
 #[derive(Default)]
 pub struct RecordingHost {
     pub output: String,
+    pub error_output: String,
     pub unix_now: i64,
     pub millis_now: i64,
+    pub local_offset_minutes: i64,
     pub random_int_value: i64,
     pub random_float_value: f64,
     pub platform: String,
     pub arch: String,
     pub args: Vec<String>,
     pub read_line: String,
+    pub stdin: String,
     pub exec_status: i64,
     pub exec_out: String,
     pub exec_argv: Vec<String>,
@@ -62,10 +67,25 @@ pub struct RecordingHost {
     pub task_channels: HashMap<usize, VecDeque<RtValue>>,
     pub env: HashMap<String, String>,
     pub files: HashMap<String, String>,
+    pub binary_files: HashMap<String, Vec<u8>>,
     pub existing_paths: HashMap<String, bool>,
 }
 
 impl RecordingHost {
+    fn duplicate_file(&mut self, from: &str, to: &str) -> RtResult<()> {
+        if !self.existing_paths.get(from).copied().unwrap_or(false) {
+            return Err(RtError::io(format!("missing {from}")));
+        }
+        if let Some(text) = self.files.get(from).cloned() {
+            self.files.insert(to.to_string(), text);
+        }
+        if let Some(data) = self.binary_files.get(from).cloned() {
+            self.binary_files.insert(to.to_string(), data);
+        }
+        self.existing_paths.insert(to.to_string(), true);
+        Ok(())
+    }
+
     pub fn seeded() -> Self {
         Self {
             unix_now: 100,
@@ -137,6 +157,11 @@ impl RecordingHostBuilder {
         self
     }
 
+    pub fn local_offset_minutes(mut self, value: i64) -> Self {
+        self.host.local_offset_minutes = value;
+        self
+    }
+
     pub fn random_int(mut self, value: i64) -> Self {
         self.host.random_int_value = value;
         self
@@ -167,6 +192,18 @@ impl RecordingHostBuilder {
         self
     }
 
+    /// Raw input served by `io_read_raw_line`; once drained the host reports EOF.
+    pub fn stdin(mut self, text: impl Into<String>) -> Self {
+        self.host.stdin = text.into();
+        self
+    }
+
+    /// Input made of `values`, each ended by a newline.
+    pub fn stdin_lines(mut self, values: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.host.stdin = values.into_iter().map(|line| line.into() + "\n").collect();
+        self
+    }
+
     pub fn exec_status(mut self, value: i64) -> Self {
         self.host.exec_status = value;
         self
@@ -365,10 +402,25 @@ impl RtHost for RecordingHost {
         Ok(())
     }
 
+    fn io_eprint(&mut self, text: &str) -> RtResult<()> {
+        self.error_output.push_str(text);
+        Ok(())
+    }
+
     fn io_read_line(&mut self) -> RtResult<RtString> {
         Ok(RtString::from(self.read_line.clone()))
     }
 
+    fn io_read_raw_line(&mut self) -> RtResult<Option<RtString>> {
+        if self.stdin.is_empty() {
+            return Ok(None);
+        }
+        let end = self.stdin.find('\n').map_or(self.stdin.len(), |at| at + 1);
+        Ok(Some(RtString::from(
+            self.stdin.drain(..end).collect::<String>(),
+        )))
+    }
+
     fn datetime_now_unix(&mut self) -> RtResult<i64> {
         Ok(self.unix_now)
     }
@@ -389,6 +441,18 @@ impl RtHost for RecordingHost {
         Ok(value.len() as i64)
     }
 
+    fn datetime_format(&mut self, value: i64, pattern: &str) -> RtResult<RtString> {
+        Ok(RtString::from(format!("format:{value}:{pattern}")))
+    }
+
+    fn datetime_parse(&mut self, pattern: &str, value: &str) -> RtResult<i64> {
+        Ok((pattern.len() * 100 + value.len()) as i64)
+    }
+
+    fn datetime_local_offset_minutes(&mut self, _value: i64) -> RtResult<i64> {
+        Ok(self.local_offset_minutes)
+    }
+
     fn datetime_component(&mut self, name: &str, value: i64) -> RtResult<i64> {
         Ok(value + name.len() as i64)
     }
@@ -454,6 +518,37 @@ impl RtHost for RecordingHost {
         Ok(())
     }
 
+    fn fs_copy_file(&mut self, from: &str, to: &str) -> RtResult<()> {
+        self.duplicate_file(from, to)?;
+        self.output.push_str(&format!("[copy {from}->{to}]"));
+        Ok(())
+    }
+
+    fn fs_move_file(&mut self, from: &str, to: &str) -> RtResult<()> {
+        self.duplicate_file(from, to)?;
+        self.files.remove(from);
+        self.binary_files.remove(from);
+        self.existing_paths.insert(from.to_string(), false);
+        self.output.push_str(&format!("[move {from}->{to}]"));
+        Ok(())
+    }
+
+    fn fs_read_bytes(&mut self, path: &str) -> RtResult<RtBytes> {
+        match self.binary_files.get(path) {
+            Some(data) => Ok(RtBytes::from(data.clone())),
+            None => Ok(RtBytes::from(self.fs_read_text(path)?.as_str().as_bytes())),
+        }
+    }
+
+    fn fs_write_bytes(&mut self, path: &str, data: &RtBytes) -> RtResult<()> {
+        self.binary_files
+            .insert(path.to_string(), data.as_slice().to_vec());
+        self.existing_paths.insert(path.to_string(), true);
+        self.output
+            .push_str(&format!("[write {path}={} bytes]", data.len()));
+        Ok(())
+    }
+
     fn fs_join(&mut self, left: &str, right: &str) -> RtResult<RtString> {
         Ok(RtString::from(format!("{left}/{right}")))
     }
@@ -832,6 +927,26 @@ impl RtHost for RecordingHost {
         Ok(left + right)
     }
 
+    fn ffi_call_2_float_float(&mut self, symbol: RtHandle, left: f64, right: f64) -> RtResult<f64> {
+        if let Some(message) = &self.ffi_call_error {
+            return Err(RtError::io(message.clone()));
+        }
+        match self.net_lookup_handle_kind(symbol)? {
+            RtHandleKind::Symbol => {}
+            other => {
+                return Err(RtError::invalid_handle_kind(
+                    RtHandleKind::Symbol.type_name(),
+                    other.type_name(),
+                ))
+            }
+        }
+        self.output.push_str(&format!(
+            "[fficall2floatfloat {}={left}|{right}]",
+            symbol.id
+        ));
+        Ok(left * right)
+    }
+
     fn ffi_call_1_bytes_usize(&mut self, symbol: RtHandle, value: &RtBytes) -> RtResult<i64> {
         if let Some(message) = &self.ffi_call_error {
             return Err(RtError::io(message.clone()));
@@ -953,6 +1068,35 @@ impl RtHost for RecordingHost {
         Ok(RtString::from(self.exec_out.clone()))
     }
 
+    fn os_exec_capture(
+        &mut self,
+        program: &str,
+        args: &[String],
+        options: &RtMap,
+    ) -> RtResult<RtMap> {
+        self.exec_argv = args.to_vec();
+        let mut entry = std::iter::once(program.to_string())
+            .chain(args.iter().cloned())
+            .collect::<Vec<_>>();
+        for key in options.keys() {
+            let value = options.get(&key).expect("listed key").expect_string()?;
+            entry.push(format!("{key}={}", value.as_str()));
+        }
+        self.output
+            .push_str(&format!("[execcapture {}]", entry.join(" ")));
+        let response = RtMap::new();
+        response.insert(
+            "code",
+            RtValue::String(RtString::from(self.exec_status.to_string())),
+        );
+        response.insert(
+            "stdout",
+            RtValue::String(RtString::from(self.exec_out.clone())),
+        );
+        response.insert("stderr", RtValue::String(RtString::from("")));
+        Ok(response)
+    }
+
     fn net_make_socket_handle(&mut self, id: usize) -> RtResult<RtHandle> {
         self.net_handles.insert(id, RtHandleKind::Socket);
         Ok(RtHandle {
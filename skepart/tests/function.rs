@@ -1,5 +1,9 @@
 mod common;
 
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
 use common::RecordingHostBuilder;
 use skepart::{RtErrorKind, RtFunctionRef, RtFunctionRegistry, RtValue};
 
@@ -56,8 +60,10 @@ fn function_registry_assigns_stable_incrementing_ids() {
     let mut registry = RtFunctionRegistry::new();
     let first = registry.register(add_one);
     let second = registry.register(sum_two);
-    assert_eq!(first, RtFunctionRef(0));
-    assert_eq!(second, RtFunctionRef(1));
+    assert_eq!(first, RtFunctionRef::host(0));
+    assert_eq!(second, RtFunctionRef::host(1));
+    assert_eq!(second.host_index(), Some(1));
+    assert_eq!(RtFunctionRef(1).host_index(), None);
 }
 
 #[test]
@@ -94,3 +100,65 @@ fn function_registry_reports_panic_inside_registered_function_as_invalid_argumen
         RtErrorKind::InvalidArgument
     );
 }
+
+#[test]
+fn function_registry_calls_closures_that_capture_host_state() {
+    let calls = Rc::new(Cell::new(0));
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let mut registry = RtFunctionRegistry::new();
+    let pointer = registry.register(add_one);
+    let closure = registry.register_closure({
+        let calls = calls.clone();
+        let sent = sent.clone();
+        move |_host, args| {
+            calls.set(calls.get() + 1);
+            let value = args[0].expect_int()?;
+            sent.lock().expect("lock sent").push(value);
+            Ok(RtValue::Int(value * 2))
+        }
+    });
+
+    let mut host = RecordingHostBuilder::seeded().build();
+    assert_eq!(
+        registry
+            .call(&mut host, closure, &[RtValue::Int(21)])
+            .expect("closure call"),
+        RtValue::Int(42)
+    );
+    assert_eq!(
+        registry
+            .call(&mut host, pointer, &[RtValue::Int(1)])
+            .expect("pointer call"),
+        RtValue::Int(2)
+    );
+    registry
+        .call(&mut host, closure, &[RtValue::Int(5)])
+        .expect("second closure call");
+    assert_eq!(calls.get(), 2);
+    assert_eq!(*sent.lock().expect("sent"), vec![21, 5]);
+}
+
+#[test]
+fn builtins_call_registered_functions_through_the_registry() {
+    let mut registry = RtFunctionRegistry::new();
+    let offset = 10;
+    let f = registry
+        .register_closure(move |_host, args| Ok(RtValue::Int(args[0].expect_int()? + offset)));
+    let mut host = RecordingHostBuilder::seeded().build();
+    let items = skepart::RtArray::new(vec![RtValue::Int(1), RtValue::Int(2)]);
+    let mapped = skepart::builtins::call_with_functions(
+        &mut host,
+        &registry,
+        "arr",
+        "map",
+        &[RtValue::Array(items), RtValue::Function(f)],
+    )
+    .expect("arr.map with host function");
+    assert_eq!(
+        mapped,
+        RtValue::Array(skepart::RtArray::new(vec![
+            RtValue::Int(11),
+            RtValue::Int(12)
+        ]))
+    );
+}
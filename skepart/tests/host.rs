@@ -348,6 +348,99 @@ fn noop_host_supports_print_and_time_defaults() {
     assert_eq!(host.datetime_component("year", 0).expect("year"), 1970);
 }
 
+#[test]
+fn noop_host_formats_and_parses_datetime_patterns() {
+    let mut host = NoopHost::default();
+    let ts = 1_704_112_496;
+    assert_eq!(
+        host.datetime_format(ts, "YYYY-MM-DD hh:mm:ss")
+            .expect("format"),
+        RtString::from("2024-01-01 12:34:56")
+    );
+    assert_eq!(
+        host.datetime_format(ts, "DD/MM/YYYY'T'hh'h' ''ss''")
+            .expect("quoted format"),
+        RtString::from("01/01/2024T12h '56'")
+    );
+    assert_eq!(
+        host.datetime_parse("YYYY-MM-DD hh:mm:ss", "2024-01-01 12:34:56")
+            .expect("parse"),
+        ts
+    );
+    assert_eq!(
+        host.datetime_parse("DD.MM.YYYY", "02.01.1970")
+            .expect("date only"),
+        86_400
+    );
+
+    let bad_token = host
+        .datetime_format(ts, "YYYY-MM-DDTHH")
+        .expect_err("unknown token");
+    assert!(bad_token.message.contains("unknown token `T`"));
+    let unterminated = host
+        .datetime_parse("YYYY'T", "2024T")
+        .expect_err("unterminated quote");
+    assert!(unterminated.message.contains("unterminated quote"));
+    let repeated = host
+        .datetime_parse("YYYY YYYY", "2024 2024")
+        .expect_err("repeated token");
+    assert!(repeated.message.contains("repeats token `YYYY`"));
+    let short = host
+        .datetime_parse("YYYY-MM", "2024-1")
+        .expect_err("short field");
+    assert!(short
+        .message
+        .contains("datetime.parse invalid month: expected 2 digits for `MM` at byte 5"));
+    let literal = host
+        .datetime_parse("YYYY-MM", "2024/01")
+        .expect_err("literal mismatch");
+    assert!(literal.message.contains("expected `-` at byte 4"));
+    let trailing = host
+        .datetime_parse("YYYY", "2024!")
+        .expect_err("trailing input");
+    assert!(trailing.message.contains("trailing input `!`"));
+    let range = host
+        .datetime_parse("YYYY-MM-DD", "2023-02-29")
+        .expect_err("out of range");
+    assert!(range.message.contains("out-of-range"));
+}
+
+#[test]
+fn noop_host_formats_and_parses_utc_offsets() {
+    let mut host = NoopHost::default();
+    let ts = 1_704_112_496;
+    assert_eq!(
+        host.datetime_format(ts, "hh:mmZ").expect("utc offset"),
+        RtString::from("12:34+00:00")
+    );
+    assert_eq!(
+        host.datetime_parse("YYYY-MM-DD hh:mm:ssZ", "2024-01-01 14:04:56+01:30")
+            .expect("east offset"),
+        ts
+    );
+    assert_eq!(
+        host.datetime_parse("YYYY-MM-DD hh:mm:ssZ", "2024-01-01 07:34:56-05:00")
+            .expect("west offset"),
+        ts
+    );
+    assert_eq!(
+        host.datetime_parse("YYYY-MM-DD hh:mm:ssZ", "2024-01-01 12:34:56Z")
+            .expect("zulu"),
+        ts
+    );
+    let bad = host
+        .datetime_parse("hh:mmZ", "12:34+0100")
+        .expect_err("compact offset");
+    assert!(bad
+        .message
+        .contains("datetime.parse invalid offset: expected `Z` or `+hh:mm`/`-hh:mm` at byte 5"));
+
+    let offset = host
+        .datetime_local_offset_minutes(ts)
+        .expect("local offset");
+    assert!(offset.abs() <= 18 * 60, "implausible offset {offset}");
+}
+
 #[test]
 fn datetime_from_millis_round_trips_through_parse_unix() {
     // Regression: fromMillis emits fractional seconds (e.g. "1970-01-01T00:00:01.234Z")
@@ -451,12 +544,15 @@ fn recording_host_tracks_fs_os_and_random_side_effects() {
     host.fs_write_text("f.txt", "x").expect("write");
     host.fs_append_text("f.txt", "y").expect("append");
     host.fs_mkdir_all("dir").expect("mkdir");
+    host.fs_copy_file("f.txt", "g.txt").expect("copy");
+    host.fs_move_file("g.txt", "h.txt").expect("move");
+    assert_eq!(host.files.get("h.txt").map(String::as_str), Some("xy"));
     host.fs_remove_file("f.txt").expect("rm file");
     host.fs_remove_dir_all("dir").expect("rm dir");
     host.os_sleep(12).expect("sleep");
     assert_eq!(
         host.output,
-        "[exec hostname --help][execout hostname --help][envset MODE=debug][envrm MODE][exit 7][write f.txt=x][append f.txt+=y][mkdir dir][rmfile f.txt][rmdir dir][sleep 12]"
+        "[exec hostname --help][execout hostname --help][envset MODE=debug][envrm MODE][exit 7][write f.txt=x][append f.txt+=y][mkdir dir][copy f.txt->g.txt][move g.txt->h.txt][rmfile f.txt][rmdir dir][sleep 12]"
     );
 }
 
@@ -490,6 +586,45 @@ fn noop_host_environment_mutation_is_host_local() {
     assert_eq!(host.os_env_get(&key).expect("host env removed"), None);
 }
 
+#[cfg(unix)]
+#[test]
+fn noop_host_exec_capture_applies_options_and_captures_both_streams() {
+    let dir = std::env::temp_dir().canonicalize().expect("temp dir");
+    let options = skepart::RtMap::new();
+    let text = |value: &str| skepart::RtValue::String(RtString::from(value));
+    options.insert("cwd", text(&dir.to_string_lossy()));
+    options.insert("env.SKEPA_GREETING", text("hi there"));
+    options.insert("stdin", text("piped"));
+    let mut host = NoopHost::default();
+
+    let response = host
+        .os_exec_capture(
+            "sh",
+            &[
+                "-c".into(),
+                r#"printf '%s|%s|' "$SKEPA_GREETING" "$(pwd)"; cat; echo oops >&2; exit 3"#.into(),
+            ],
+            &options,
+        )
+        .expect("exec capture");
+
+    assert_eq!(response.get("code"), Some(text("3")));
+    assert_eq!(
+        response.get("stdout"),
+        Some(text(&format!("hi there|{}|piped", dir.display())))
+    );
+    assert_eq!(response.get("stderr"), Some(text("oops\n")));
+
+    let err = host
+        .os_exec_capture(
+            "skepa-definitely-missing-binary",
+            &[],
+            &skepart::RtMap::new(),
+        )
+        .expect_err("missing program should fail to spawn");
+    assert_eq!(err.kind, skepart::RtErrorKind::Process);
+}
+
 #[test]
 fn noop_host_fs_exists_surfaces_real_filesystem_errors() {
     let mut host = NoopHost::default();
@@ -499,6 +634,101 @@ fn noop_host_fs_exists_surfaces_real_filesystem_errors() {
     assert_eq!(err.kind, skepart::RtErrorKind::Io);
 }
 
+#[test]
+fn noop_host_persists_store_entries_across_handles() {
+    let dir = std::env::temp_dir().join(format!(
+        "skepa_noop_host_store_{}_{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("unix epoch")
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let path = dir.join("state.db").to_string_lossy().into_owned();
+    let mut host = NoopHost::default();
+
+    let first = host.store_open(&path).expect("open new store");
+    let second = host.store_open(&path).expect("open same store again");
+    host.store_set(first, "b", "two\nlines").expect("set b");
+    host.store_set(first, "a", "").expect("set a");
+    host.store_set(first, "é", "x y").expect("set é");
+    assert_eq!(
+        host.store_get(second, "b").expect("get b"),
+        Some(RtString::from("two\nlines"))
+    );
+    assert!(host.store_delete(second, "é").expect("delete é"));
+    assert!(!host.store_delete(second, "é").expect("delete é again"));
+    assert_eq!(host.store_get(first, "missing").expect("get missing"), None);
+    assert_eq!(
+        host.store_keys(first).expect("keys"),
+        vec![RtString::from("a"), RtString::from("b")]
+    );
+    assert_eq!(
+        std::fs::read_to_string(&path).expect("read store file"),
+        "skepa-store 1\n1 0\na\n1 9\nbtwo\nlines\n"
+    );
+
+    host.store_close(first).expect("close first");
+    host.store_close(second).expect("close second");
+    let err = host
+        .store_get(first, "a")
+        .expect_err("closed store handle should be rejected");
+    assert!(err.message.contains("unknown store handle id"), "{err:?}");
+    assert!(host.net_close_handle(second).is_err());
+    let reopened = host.store_open(&path).expect("reopen store");
+    assert_eq!(
+        host.store_get(reopened, "a").expect("get a"),
+        Some(RtString::from(""))
+    );
+
+    std::fs::write(&path, "skepa-store 1\n5 1\nab\n").expect("corrupt store");
+    let err = host
+        .store_get(reopened, "a")
+        .expect_err("corrupt store should fail");
+    assert_eq!(err.kind, skepart::RtErrorKind::Io);
+    assert!(err.message.contains("is malformed: truncated record"));
+    assert!(host.store_open(&path).is_err());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn noop_host_copies_moves_and_round_trips_binary_files() {
+    let dir = std::env::temp_dir().join(format!(
+        "skepa_noop_host_fs_bytes_{}_{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("unix epoch")
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+    let mut host = NoopHost::default();
+
+    let data = skepart::RtBytes::from(vec![0u8, 159, 146, 150, 255]);
+    host.fs_write_bytes(&path("a.bin"), &data)
+        .expect("write bytes");
+    host.fs_copy_file(&path("a.bin"), &path("b.bin"))
+        .expect("copy");
+    host.fs_move_file(&path("b.bin"), &path("c.bin"))
+        .expect("move");
+
+    assert_eq!(
+        host.fs_read_bytes(&path("c.bin")).expect("read bytes"),
+        data
+    );
+    assert!(host.fs_exists(&path("a.bin")).expect("source kept"));
+    assert!(!host.fs_exists(&path("b.bin")).expect("moved away"));
+    let err = host
+        .fs_copy_file(&path("missing.bin"), &path("d.bin"))
+        .expect_err("missing source should fail");
+    assert_eq!(err.kind, skepart::RtErrorKind::Io);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 #[test]
 fn hosts_can_construct_typed_placeholder_net_handles() {
     let mut noop = NoopHost::default();
@@ -596,6 +826,30 @@ fn noop_host_calls_integer_ffi_symbols() {
     host.net_close_handle(library).expect("close library");
 }
 
+#[cfg(all(unix, not(target_os = "macos")))]
+#[test]
+fn noop_host_calls_float_ffi_symbols() {
+    let mut host = NoopHost::default();
+    let library = host
+        .ffi_open_library("libm.so.6")
+        .expect("open shared library");
+    let fabs = host
+        .ffi_bind_symbol(library, "fabs")
+        .expect("bind one-arg float symbol");
+    let pow = host
+        .ffi_bind_symbol(library, "pow")
+        .expect("bind two-arg float symbol");
+
+    assert_eq!(host.ffi_call_1_float(fabs, -2.5).expect("fabs"), 2.5);
+    assert_eq!(
+        host.ffi_call_2_float_float(pow, 2.0, 10.0).expect("pow"),
+        1024.0
+    );
+    host.net_close_handle(fabs).expect("close fabs");
+    host.net_close_handle(pow).expect("close pow");
+    host.net_close_handle(library).expect("close library");
+}
+
 #[test]
 fn noop_host_calls_zero_void_ffi_symbols() {
     let mut host = NoopHost::default();
@@ -1101,6 +1355,95 @@ fn noop_host_supports_loopback_connect_accept_write_and_read_bytes() {
     host.net_close_handle(listener).expect("close listener");
 }
 
+#[test]
+fn noop_host_exchanges_udp_datagrams_over_loopback() {
+    let mut host = NoopHost::default();
+    let a = host.net_bind_udp("127.0.0.1:0").expect("bind a");
+    let b = host.net_bind_udp("127.0.0.1:0").expect("bind b");
+    let a_addr = host.net_udp_local_addr(a).expect("a addr");
+    let b_addr = host.net_udp_local_addr(b).expect("b addr");
+
+    host.net_send_to(a, b_addr.as_str(), &RtBytes::from(vec![1_u8, 2, 3]))
+        .expect("send a->b");
+    assert_eq!(
+        host.net_recv(b).expect("recv b"),
+        RtBytes::from(vec![1_u8, 2, 3])
+    );
+
+    host.net_udp_connect(b, a_addr.as_str())
+        .expect("connect b->a");
+    host.net_send(b, &RtBytes::from(Vec::<u8>::new()))
+        .expect("send empty datagram");
+    assert_eq!(
+        host.net_recv(a).expect("recv a"),
+        RtBytes::from(Vec::<u8>::new())
+    );
+
+    assert_eq!(
+        host.net_send(a, &RtBytes::from(vec![0_u8]))
+            .expect_err("unconnected send")
+            .kind,
+        skepart::RtErrorKind::Io
+    );
+    let listener = host.net_listen("127.0.0.1:0").expect("listen");
+    assert_eq!(
+        host.net_recv(listener)
+            .expect_err("listener is not a udp socket")
+            .kind,
+        skepart::RtErrorKind::InvalidArgument
+    );
+
+    host.net_close_handle(a).expect("close a");
+    host.net_close_handle(b).expect("close b");
+    host.net_close_handle(listener).expect("close listener");
+}
+
+#[test]
+fn noop_host_echoes_udp_datagrams_to_the_sender_from_recv_from() {
+    let mut host = NoopHost::default();
+    let server = host.net_bind_udp("127.0.0.1:0").expect("bind server");
+    let client = host.net_bind_udp("127.0.0.1:0").expect("bind client");
+    let server_addr = host.net_udp_local_addr(server).expect("server addr");
+    let client_addr = host.net_udp_local_addr(client).expect("client addr");
+
+    host.net_send_to(
+        client,
+        server_addr.as_str(),
+        &RtBytes::from(b"ping".to_vec()),
+    )
+    .expect("send ping");
+    let (data, sender) = host.net_recv_from(server, 1024).expect("recv ping");
+    assert_eq!(data, RtBytes::from(b"ping".to_vec()));
+    assert_eq!(sender, client_addr);
+
+    host.net_send_to(server, sender.as_str(), &data)
+        .expect("echo ping");
+    assert_eq!(
+        host.net_recv(client).expect("recv echo"),
+        RtBytes::from(b"ping".to_vec())
+    );
+
+    host.net_send_to(
+        client,
+        server_addr.as_str(),
+        &RtBytes::from(b"long".to_vec()),
+    )
+    .expect("send long");
+    assert_eq!(
+        host.net_recv_from(server, 2).expect("recv truncated").0,
+        RtBytes::from(b"lo".to_vec())
+    );
+    assert_eq!(
+        host.net_recv_from(server, -1)
+            .expect_err("negative max")
+            .kind,
+        skepart::RtErrorKind::InvalidArgument
+    );
+
+    host.net_close_handle(server).expect("close server");
+    host.net_close_handle(client).expect("close client");
+}
+
 #[test]
 fn noop_host_net_read_and_read_bytes_are_single_chunk_apis() {
     let mut host = NoopHost::default();
@@ -1632,3 +1975,36 @@ fn noop_host_rejects_non_utf8_net_reads() {
     peer.join().expect("peer thread should finish");
     host.net_close_handle(client).expect("close client");
 }
+
+#[test]
+fn noop_host_reads_all_stdin_exactly() {
+    const CHILD_OUTPUT: &str = "SKEPART_READ_ALL_OUTPUT";
+    if let Some(path) = std::env::var_os(CHILD_OUTPUT) {
+        let text = NoopHost::default().io_read_all().expect("read stdin");
+        std::fs::write(path, text.as_str()).expect("write child output");
+        return;
+    }
+
+    let output = std::env::temp_dir().join(format!("skepart_read_all_{}", std::process::id()));
+    let mut child = std::process::Command::new(std::env::current_exe().expect("test binary"))
+        .args([
+            "noop_host_reads_all_stdin_exactly",
+            "--exact",
+            "--test-threads=1",
+        ])
+        .env(CHILD_OUTPUT, &output)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .expect("spawn child test");
+    child
+        .stdin
+        .take()
+        .expect("child stdin")
+        .write_all(b"first\r\nsecond\nno newline")
+        .expect("write child stdin");
+    assert!(child.wait().expect("wait child").success());
+    let read = std::fs::read_to_string(&output).expect("child output");
+    let _ = std::fs::remove_file(&output);
+    assert_eq!(read, "first\r\nsecond\nno newline");
+}
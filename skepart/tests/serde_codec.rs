@@ -0,0 +1,212 @@
+//! Property tests for the `serde.encode` / `serde.decode` binary codec: any value
+//! of any schema type must decode back to itself.
+
+use proptest::prelude::*;
+use skepart::{
+    builtins, RtArray, RtBytes, RtMap, RtOption, RtResultValue, RtString, RtStruct, RtValue, RtVec,
+};
+
+/// A Skepa type the codec can describe. Tuples carry the index of their struct
+/// definition in the schema.
+#[derive(Debug, Clone)]
+enum Ty {
+    Int,
+    Float,
+    Bool,
+    Char,
+    String,
+    Bytes,
+    Option(Box<Ty>),
+    Result(Box<Ty>, Box<Ty>),
+    Vec(Box<Ty>),
+    Map(Box<Ty>),
+    Array(usize, Box<Ty>),
+    Tuple(usize, Vec<Ty>),
+}
+
+fn leaf() -> impl Strategy<Value = Ty> {
+    prop_oneof![
+        Just(Ty::Int),
+        Just(Ty::Float),
+        Just(Ty::Bool),
+        Just(Ty::Char),
+        Just(Ty::String),
+        Just(Ty::Bytes),
+    ]
+}
+
+fn ty() -> impl Strategy<Value = Ty> {
+    leaf()
+        .prop_recursive(4, 24, 3, |inner| {
+            prop_oneof![
+                inner.clone().prop_map(|ty| Ty::Option(Box::new(ty))),
+                (inner.clone(), inner.clone())
+                    .prop_map(|(ok, err)| Ty::Result(Box::new(ok), Box::new(err))),
+                inner.clone().prop_map(|ty| Ty::Vec(Box::new(ty))),
+                inner.clone().prop_map(|ty| Ty::Map(Box::new(ty))),
+                (0..4usize, inner.clone()).prop_map(|(len, ty)| Ty::Array(len, Box::new(ty))),
+                prop::collection::vec(inner, 1..4).prop_map(|fields| Ty::Tuple(0, fields)),
+            ]
+        })
+        .prop_map(|mut ty| {
+            number_tuples(&mut ty, &mut 0);
+            ty
+        })
+}
+
+/// Numbers tuples in the order [`write_schema`] emits their definitions.
+fn number_tuples(ty: &mut Ty, next: &mut usize) {
+    match ty {
+        Ty::Option(inner) | Ty::Vec(inner) | Ty::Map(inner) | Ty::Array(_, inner) => {
+            number_tuples(inner, next)
+        }
+        Ty::Result(ok, err) => {
+            number_tuples(ok, next);
+            number_tuples(err, next);
+        }
+        Ty::Tuple(id, fields) => {
+            *id = *next;
+            *next += 1;
+            for field in fields {
+                number_tuples(field, next);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn schema(ty: &Ty) -> String {
+    let mut defs = Vec::new();
+    let mut out = String::new();
+    write_schema(ty, &mut out, &mut defs);
+    for def in defs {
+        out.push('|');
+        out.push_str(&def);
+    }
+    out
+}
+
+fn write_schema(ty: &Ty, out: &mut String, defs: &mut Vec<String>) {
+    match ty {
+        Ty::Int => out.push('i'),
+        Ty::Float => out.push('f'),
+        Ty::Bool => out.push('b'),
+        Ty::Char => out.push('c'),
+        Ty::String => out.push('s'),
+        Ty::Bytes => out.push('y'),
+        Ty::Option(inner) => {
+            out.push('o');
+            write_schema(inner, out, defs);
+        }
+        Ty::Result(ok, err) => {
+            out.push('r');
+            write_schema(ok, out, defs);
+            write_schema(err, out, defs);
+        }
+        Ty::Vec(inner) => {
+            out.push('v');
+            write_schema(inner, out, defs);
+        }
+        Ty::Map(inner) => {
+            out.push('m');
+            write_schema(inner, out, defs);
+        }
+        Ty::Array(len, inner) => {
+            out.push_str(&format!("a{len}."));
+            write_schema(inner, out, defs);
+        }
+        Ty::Tuple(id, fields) => {
+            defs.push(String::new());
+            let mut def = format!("{id}(");
+            for (index, field) in fields.iter().enumerate() {
+                if index > 0 {
+                    def.push(',');
+                }
+                write_schema(field, &mut def, defs);
+            }
+            def.push(')');
+            defs[*id] = def;
+            out.push_str(&format!("#{id}."));
+        }
+    }
+}
+
+fn value(ty: &Ty) -> BoxedStrategy<RtValue> {
+    match ty {
+        Ty::Int => any::<i64>().prop_map(RtValue::Int).boxed(),
+        Ty::Float => any::<f64>()
+            .prop_filter("NaN never equals itself", |value| !value.is_nan())
+            .prop_map(RtValue::Float)
+            .boxed(),
+        Ty::Bool => any::<bool>().prop_map(RtValue::Bool).boxed(),
+        Ty::Char => any::<char>().prop_map(RtValue::Char).boxed(),
+        Ty::String => any::<String>()
+            .prop_map(|text| RtValue::String(RtString::from(text)))
+            .boxed(),
+        Ty::Bytes => prop::collection::vec(any::<u8>(), 0..8)
+            .prop_map(|data| RtValue::Bytes(RtBytes::from(data)))
+            .boxed(),
+        Ty::Option(inner) => prop::option::of(value(inner))
+            .prop_map(|value| {
+                RtValue::Option(match value {
+                    Some(value) => RtOption::some(value),
+                    None => RtOption::none(),
+                })
+            })
+            .boxed(),
+        Ty::Result(ok, err) => prop_oneof![
+            value(ok).prop_map(|value| RtValue::Result(RtResultValue::ok(value))),
+            value(err).prop_map(|value| RtValue::Result(RtResultValue::err(value))),
+        ]
+        .boxed(),
+        Ty::Vec(inner) => prop::collection::vec(value(inner), 0..4)
+            .prop_map(|items| {
+                let vec = RtVec::new();
+                for item in items {
+                    vec.push(item);
+                }
+                RtValue::Vec(vec)
+            })
+            .boxed(),
+        Ty::Map(inner) => prop::collection::hash_map(any::<String>(), value(inner), 0..4)
+            .prop_map(|entries| {
+                let map = RtMap::new();
+                for (key, value) in entries {
+                    map.insert(key, value);
+                }
+                RtValue::Map(map)
+            })
+            .boxed(),
+        Ty::Array(len, inner) => prop::collection::vec(value(inner), *len)
+            .prop_map(|items| RtValue::Array(RtArray::new(items)))
+            .boxed(),
+        Ty::Tuple(id, fields) => {
+            let id = *id;
+            fields
+                .iter()
+                .map(value)
+                .collect::<Vec<_>>()
+                .prop_map(move |fields| {
+                    RtValue::Struct(
+                        RtStruct::named(format!("Struct{id}"), fields).expect("tuple value"),
+                    )
+                })
+                .boxed()
+        }
+    }
+}
+
+fn typed_value() -> impl Strategy<Value = (String, RtValue)> {
+    ty().prop_flat_map(|ty| (Just(schema(&ty)), value(&ty)))
+}
+
+proptest! {
+    #[test]
+    fn serde_binary_round_trips_any_value((schema, value) in typed_value()) {
+        let schema = RtValue::String(RtString::from(schema));
+        let encoded = builtins::call("serde", "encode", &[value.clone(), schema.clone()])
+            .expect("serde.encode");
+        let decoded = builtins::call("serde", "decode", &[encoded, schema]).expect("serde.decode");
+        prop_assert_eq!(decoded, RtValue::Result(RtResultValue::ok(value)));
+    }
+}
@@ -138,3 +138,26 @@ fn structs_keep_typed_storage_until_mixed_mutation() {
     assert_eq!(strukt.get_field(0), Ok(RtValue::String("mixed".into())));
     assert_eq!(strukt.get_field(1), Ok(RtValue::Int(9)));
 }
+
+#[test]
+fn structs_compare_by_type_and_field_values() {
+    let layout = Arc::new(RtStructLayout {
+        name: "Point".into(),
+        field_names: vec!["x".into(), "y".into()],
+        field_types: vec![None, None],
+    });
+    let point =
+        |x: RtValue| RtStruct::new(layout.clone(), vec![x, RtValue::Int(2)]).expect("valid struct");
+    let mut widened = point(RtValue::Int(0));
+    widened
+        .set_field(0, RtValue::Bool(true))
+        .expect("set field should work");
+    widened
+        .set_field(0, RtValue::Int(1))
+        .expect("set field should work");
+    assert_eq!(point(RtValue::Int(1)), widened);
+    assert_ne!(point(RtValue::Int(1)), point(RtValue::Int(3)));
+    let other =
+        RtStruct::named("Other", vec![RtValue::Int(1), RtValue::Int(2)]).expect("valid struct");
+    assert_ne!(point(RtValue::Int(1)), other);
+}
@@ -162,6 +162,14 @@ fn value_accessors_report_wrong_type() {
     );
 }
 
+#[test]
+fn runtime_error_kinds_have_stable_codes() {
+    let err = RtValue::Bool(true).expect_int().expect_err("wrong type");
+    assert_eq!(err.kind.code(), "E-RT-004");
+    assert_eq!(skepart::RtError::integer_overflow().kind.code(), "E-RT-002");
+    assert_eq!(skepart::RtError::panic("boom").kind.code(), "E-RT-010");
+}
+
 #[test]
 fn runtime_values_are_send_ready_for_future_task_runtime() {
     fn assert_send<T: Send>() {}
@@ -170,3 +178,39 @@ fn runtime_values_are_send_ready_for_future_task_runtime() {
     assert_send::<RtValue>();
     assert_sync::<RtValue>();
 }
+
+#[test]
+fn values_render_in_a_literal_like_debug_form() {
+    use skepart::display::{debug_value, display_value};
+
+    let vec = RtVec::new();
+    vec.push(RtValue::Float(1.0));
+    let nested = RtValue::Array(RtArray::new(vec![
+        RtValue::Vec(vec),
+        RtValue::Vec(RtVec::new()),
+    ]));
+    assert_eq!(debug_value(&nested), "[[1.0], []]");
+    assert_eq!(
+        debug_value(&RtValue::Result(RtResultValue::err(RtValue::String(
+            RtString::from("bad \"x\"")
+        )))),
+        "Err(\"bad \\\"x\\\"\")"
+    );
+    assert_eq!(debug_value(&RtValue::Option(RtOption::none())), "None()");
+    let empty = RtStruct::named("Empty", Vec::new()).expect("valid struct");
+    assert_eq!(debug_value(&RtValue::Struct(empty)), "Empty {}");
+    let unnamed =
+        RtStruct::named("Pair", vec![RtValue::Int(1), RtValue::Char('a')]).expect("valid struct");
+    assert_eq!(
+        debug_value(&RtValue::Struct(unnamed)),
+        "Pair { 0: 1, 1: 'a' }"
+    );
+
+    assert_eq!(display_value(&RtValue::Float(1.0)), "1");
+    assert_eq!(display_value(&RtValue::String(RtString::from("hi"))), "hi");
+    assert_eq!(display_value(&RtValue::Char('a')), "a");
+    assert_eq!(
+        debug_value(&RtValue::String(RtString::from("hi"))),
+        "\"hi\""
+    );
+}
@@ -0,0 +1,104 @@
+//! Representative programs run end to end, from source text to the value `main`
+//! returns, so changes to the frontend, optimizer, or interpreter show up as
+//! measured differences on realistic code rather than on micro-cases.
+
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use skeplib::ir::{IrInterpreter, IrProgram, IrValue, lowering};
+use std::fs;
+use std::path::PathBuf;
+
+/// A workload and the checksum its `main` returns; every run is checked against it
+/// so a benchmark can never get faster by computing the wrong answer.
+struct Workload {
+    name: &'static str,
+    source: Source,
+    checksum: i64,
+}
+
+enum Source {
+    File(&'static str),
+    Project(&'static str),
+}
+
+const WORKLOADS: &[Workload] = &[
+    Workload {
+        name: "loops",
+        source: Source::File("workloads/loops.sk"),
+        checksum: 1_317_167,
+    },
+    Workload {
+        name: "strings",
+        source: Source::File("workloads/strings.sk"),
+        checksum: 4_838,
+    },
+    Workload {
+        name: "structs",
+        source: Source::File("workloads/structs.sk"),
+        checksum: 302,
+    },
+    Workload {
+        name: "modules",
+        source: Source::Project("heavy_project/main.sk"),
+        checksum: 0,
+    },
+];
+
+fn fixture_path(relative: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("benches")
+        .join("fixtures")
+        .join(relative)
+}
+
+impl Workload {
+    fn compile(&self) -> IrProgram {
+        match self.source {
+            Source::File(path) => {
+                let source = fs::read_to_string(fixture_path(path)).expect("read workload");
+                lowering::compile_source(&source).expect("compile workload")
+            }
+            Source::Project(entry) => {
+                lowering::compile_project_entry(&fixture_path(entry)).expect("compile workload")
+            }
+        }
+    }
+
+    fn run(&self, program: &IrProgram) {
+        let value = IrInterpreter::new(program)
+            .run_main()
+            .expect("workload should run");
+        assert_eq!(
+            value,
+            IrValue::Int(self.checksum),
+            "workload `{}` returned the wrong checksum",
+            self.name
+        );
+    }
+}
+
+fn workload_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("workloads");
+    for workload in WORKLOADS {
+        let program = workload.compile();
+        workload.run(&program);
+
+        group.bench_with_input(
+            BenchmarkId::new("compile_and_run", workload.name),
+            workload,
+            |b, workload| {
+                b.iter(|| workload.run(&black_box(workload.compile())));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("run", workload.name),
+            &program,
+            |b, program| {
+                b.iter(|| workload.run(black_box(program)));
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(workloads, workload_benches);
+criterion_main!(workloads);
@@ -1,22 +1,32 @@
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+use serde::Serialize;
+
+use crate::diagnostic::Span;
+use crate::lint::LintDirective;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
 pub struct Program {
+    /// File-level `#allow(...)`, `#warn(...)`, and `#deny(...)` directives, in source order.
+    pub lints: Vec<LintDirective>,
     pub imports: Vec<ImportDecl>,
     pub exports: Vec<ExportDecl>,
     pub globals: Vec<GlobalLetDecl>,
+    pub type_aliases: Vec<TypeAliasDecl>,
     pub structs: Vec<StructDecl>,
     pub impls: Vec<ImplDecl>,
     pub operators: Vec<OperatorDecl>,
     pub functions: Vec<FnDecl>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct GlobalLetDecl {
     pub name: String,
     pub ty: Option<TypeName>,
     pub value: Expr,
+    /// Text of the `///` doc comment lines directly above the declaration.
+    pub doc: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum ImportDecl {
     ImportModule {
         path: Vec<String>,
@@ -29,13 +39,13 @@ pub enum ImportDecl {
     },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ImportItem {
     pub name: String,
     pub alias: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum ExportDecl {
     Local {
         items: Vec<ExportItem>,
@@ -49,23 +59,55 @@ pub enum ExportDecl {
     },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ExportItem {
     pub name: String,
     pub alias: Option<String>,
+    /// `export { User(opaque) }`: importers may name the struct and call its
+    /// methods, but cannot build it with a literal or touch its fields.
+    pub opaque: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct FnDecl {
     pub is_extern: bool,
     pub extern_library: Option<String>,
     pub name: String,
+    /// Span of the function name.
+    pub span: Span,
     pub params: Vec<Param>,
     pub return_type: Option<TypeName>,
     pub body: Vec<Stmt>,
+    /// Text of the `///` doc comment lines directly above the declaration.
+    pub doc: Option<String>,
+    pub attributes: Vec<Attribute>,
+}
+
+/// `@name` or `@name("arg", ...)` written before a `fn` or `struct`. The parser
+/// accepts any name; sema rejects the ones it does not know.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Attribute {
+    pub name: String,
+    /// String literal arguments, unescaped.
+    pub args: Vec<String>,
+}
+
+/// The note of an `@deprecated` or `@deprecated("note")` attribute in `attributes`:
+/// `Some(None)` without a note, `None` when the declaration is not deprecated.
+pub fn deprecation(attributes: &[Attribute]) -> Option<Option<&str>> {
+    attributes
+        .iter()
+        .find(|attr| attr.name == "deprecated")
+        .map(|attr| attr.args.first().map(String::as_str))
+}
+
+impl FnDecl {
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attributes.iter().any(|attr| attr.name == name)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct OperatorDecl {
     pub name: String,
     pub params: Vec<Param>,
@@ -74,39 +116,70 @@ pub struct OperatorDecl {
     pub body: Vec<Stmt>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct StructDecl {
     pub name: String,
+    /// Names in `struct Name[T, U]`; empty for ordinary structs.
+    pub type_params: Vec<String>,
     pub fields: Vec<FieldDecl>,
+    /// Text of the `///` doc comment lines directly above the declaration.
+    pub doc: Option<String>,
+    pub attributes: Vec<Attribute>,
+}
+
+/// `type Name = Target;`. Aliases are transparent: once a module is parsed every
+/// use of `Name` is replaced by `Target`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeAliasDecl {
+    pub name: String,
+    pub target: TypeName,
+    /// Text of the `///` doc comment lines directly above the declaration.
+    pub doc: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct FieldDecl {
     pub name: String,
     pub ty: TypeName,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ImplDecl {
     pub target: String,
     pub methods: Vec<MethodDecl>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct MethodDecl {
     pub name: String,
     pub params: Vec<Param>,
     pub return_type: Option<TypeName>,
     pub body: Vec<Stmt>,
+    /// Text of the `///` doc comment lines directly above the declaration.
+    pub doc: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Stmt {
+/// A statement and the source it spans, from its first token through its closing
+/// `;` or `}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum StmtKind {
     Let {
         name: String,
         ty: Option<TypeName>,
         value: Expr,
     },
+    /// `let User { id, name } = u;`, `let [first, rest..] = xs;` or `let (a, b) = t;`;
+    /// the pattern must be a struct, array or tuple pattern that always matches.
+    LetPattern {
+        pattern: MatchPattern,
+        value: Expr,
+    },
     Assign {
         target: AssignTarget,
         value: Expr,
@@ -127,6 +200,12 @@ pub enum Stmt {
         step: Option<Box<Stmt>>,
         body: Vec<Stmt>,
     },
+    /// `for (name in iterable) { ... }` over an array, a `Vec`, or an `iter` pipeline.
+    ForIn {
+        name: String,
+        iterable: Expr,
+        body: Vec<Stmt>,
+    },
     Break,
     Continue,
     Return(Option<Expr>),
@@ -134,21 +213,24 @@ pub enum Stmt {
         expr: Expr,
         arms: Vec<MatchArm>,
     },
+    Defer(Expr),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct MatchArm {
     pub pattern: MatchPattern,
+    pub guard: Option<Expr>,
     pub body: Vec<Stmt>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct MatchExprArm {
     pub pattern: MatchPattern,
+    pub guard: Option<Expr>,
     pub expr: Expr,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum MatchPattern {
     Wildcard,
     Literal(MatchLiteral),
@@ -157,29 +239,104 @@ pub enum MatchPattern {
         binding: Option<String>,
     },
     Or(Vec<MatchPattern>),
+    /// `name`: matches any value and binds it.
+    Binding(String),
+    /// `Name { field: pattern, field, .. }`. A bare `field` binds the field under
+    /// its own name; `rest` is set when `..` skips the fields not listed.
+    Struct {
+        name: String,
+        fields: Vec<(String, MatchPattern)>,
+        rest: bool,
+    },
+    /// `[first, second, tail..]` over a fixed-size array. `rest` is set when the
+    /// list ends in `..`, which binds the remaining elements as an array when named.
+    Array {
+        items: Vec<MatchPattern>,
+        rest: bool,
+        rest_binding: Option<String>,
+    },
+    /// `(first, second, ...)`: destructures a tuple element by element.
+    Tuple(Vec<MatchPattern>),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl MatchPattern {
+    /// Whether the pattern matches every value of a type it checks against.
+    pub fn is_irrefutable(&self) -> bool {
+        match self {
+            MatchPattern::Wildcard | MatchPattern::Binding(_) => true,
+            MatchPattern::Struct { fields, .. } => {
+                fields.iter().all(|(_, pattern)| pattern.is_irrefutable())
+            }
+            MatchPattern::Array { items, .. } | MatchPattern::Tuple(items) => {
+                items.iter().all(MatchPattern::is_irrefutable)
+            }
+            MatchPattern::Or(parts) => parts.iter().any(MatchPattern::is_irrefutable),
+            MatchPattern::Literal(_) | MatchPattern::Variant { .. } => false,
+        }
+    }
+
+    /// Names bound by the pattern, in source order.
+    pub fn bindings(&self) -> Vec<&str> {
+        let mut out = Vec::new();
+        self.collect_bindings(&mut out);
+        out
+    }
+
+    fn collect_bindings<'a>(&'a self, out: &mut Vec<&'a str>) {
+        match self {
+            MatchPattern::Variant {
+                binding: Some(name),
+                ..
+            }
+            | MatchPattern::Binding(name) => out.push(name),
+            MatchPattern::Struct { fields, .. } => {
+                for (_, pattern) in fields {
+                    pattern.collect_bindings(out);
+                }
+            }
+            MatchPattern::Array {
+                items,
+                rest_binding,
+                ..
+            } => {
+                for item in items {
+                    item.collect_bindings(out);
+                }
+                out.extend(rest_binding.as_deref());
+            }
+            MatchPattern::Tuple(parts) | MatchPattern::Or(parts) => {
+                for part in parts {
+                    part.collect_bindings(out);
+                }
+            }
+            MatchPattern::Wildcard | MatchPattern::Literal(_) | MatchPattern::Variant { .. } => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum MatchLiteral {
     Int(i64),
     Bool(bool),
+    Char(char),
     String(String),
     Float(String),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum AssignTarget {
     Ident(String),
     Index { base: Box<Expr>, index: Expr },
     Field { base: Box<Expr>, field: String },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum Expr {
     IntLit(i64),
     FloatLit(String),
     Ident(String),
     BoolLit(bool),
+    CharLit(char),
     StringLit(String),
     Path(Vec<String>),
     ArrayLit(Vec<Expr>),
@@ -187,16 +344,28 @@ pub enum Expr {
         value: Box<Expr>,
         size: usize,
     },
+    /// `vec![a, b, c]`; an empty literal needs a `Vec[T]` annotation like `vec.new()`.
+    VecLit(Vec<Expr>),
+    /// `(a, b, ...)` with at least two elements; read elements with `.0`, `.1`, ...
+    TupleLit(Vec<Expr>),
     Index {
         base: Box<Expr>,
         index: Box<Expr>,
     },
+    /// `s[start..end]`; either bound may be omitted.
+    Slice {
+        base: Box<Expr>,
+        start: Option<Box<Expr>>,
+        end: Option<Box<Expr>>,
+    },
     Field {
         base: Box<Expr>,
         field: String,
     },
     StructLit {
         name: String,
+        /// `Box[Int] { ... }` instantiates generic struct `Box` with `Int`.
+        type_args: Vec<TypeName>,
         fields: Vec<(String, Expr)>,
     },
     FnLit {
@@ -230,17 +399,20 @@ pub enum Expr {
     Group(Box<Expr>),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Param {
     pub name: String,
     pub ty: TypeName,
+    /// Span of the parameter name.
+    pub span: Span,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum TypeName {
     Int,
     Float,
     Bool,
+    Char,
     String,
     Bytes,
     Void,
@@ -266,9 +438,16 @@ pub enum TypeName {
         params: Vec<TypeName>,
         ret: Box<TypeName>,
     },
+    /// `(A, B, ...)` with at least two elements.
+    Tuple(Vec<TypeName>),
+    /// `Name[A, B]`: generic struct `Name` instantiated with type arguments.
+    Generic {
+        name: String,
+        args: Vec<TypeName>,
+    },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum UnaryOp {
     Neg,
     Pos,
@@ -276,7 +455,7 @@ pub enum UnaryOp {
     BitNot,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum BinaryOp {
     Add,
     Sub,
@@ -298,6 +477,105 @@ pub enum BinaryOp {
     OrOr,
 }
 
+impl BinaryOp {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Mod => "%",
+            BinaryOp::BitAnd => "&",
+            BinaryOp::BitOr => "|",
+            BinaryOp::BitXor => "^",
+            BinaryOp::Shl => "<<",
+            BinaryOp::Shr => ">>",
+            BinaryOp::EqEq => "==",
+            BinaryOp::Neq => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Lte => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Gte => ">=",
+            BinaryOp::AndAnd => "&&",
+            BinaryOp::OrOr => "||",
+        }
+    }
+
+    /// Method a struct implements to overload this operator, e.g. `fn add(self, other: T)`.
+    pub fn overload_method(self) -> Option<&'static str> {
+        match self {
+            BinaryOp::Add => Some("add"),
+            BinaryOp::Sub => Some("sub"),
+            BinaryOp::Mul => Some("mul"),
+            BinaryOp::Div => Some("div"),
+            BinaryOp::Mod => Some("mod"),
+            _ => None,
+        }
+    }
+}
+
+impl Expr {
+    /// Whether this is a call that never returns: `panic(...)` or `os.exit(...)`.
+    pub fn is_diverging_call(&self) -> bool {
+        let Expr::Call { callee, .. } = self else {
+            return false;
+        };
+        match callee.as_ref() {
+            Expr::Ident(name) => name == "panic",
+            Expr::Path(parts) => parts.len() == 2 && parts[0] == "os" && parts[1] == "exit",
+            Expr::Field { base, field } => {
+                field == "exit" && matches!(base.as_ref(), Expr::Ident(pkg) if pkg == "os")
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<StmtKind> for Stmt {
+    /// A statement without a source position, for code built outside the parser.
+    fn from(kind: StmtKind) -> Self {
+        Stmt {
+            kind,
+            span: Span::default(),
+        }
+    }
+}
+
+impl Stmt {
+    /// Whether this is a `while (true)` / `for (;;)` loop with no `break` of its own,
+    /// so control never reaches the statement after it.
+    pub fn is_infinite_loop(&self) -> bool {
+        let (cond, body) = match &self.kind {
+            StmtKind::While { cond, body } => (Some(cond), body),
+            StmtKind::For { cond, body, .. } => (cond.as_ref(), body),
+            _ => return false,
+        };
+        let always = cond.is_none_or(|cond| matches!(cond, Expr::BoolLit(true)));
+        always && !body.iter().any(Stmt::breaks_enclosing_loop)
+    }
+
+    /// Whether a `break` in this statement targets the loop that contains it.
+    /// Breaks inside nested loops belong to those loops and are not counted.
+    fn breaks_enclosing_loop(&self) -> bool {
+        match &self.kind {
+            StmtKind::Break => true,
+            StmtKind::If {
+                then_body,
+                else_body,
+                ..
+            } => then_body
+                .iter()
+                .chain(else_body)
+                .any(Stmt::breaks_enclosing_loop),
+            StmtKind::Match { arms, .. } => arms
+                .iter()
+                .flat_map(|arm| &arm.body)
+                .any(Stmt::breaks_enclosing_loop),
+            _ => false,
+        }
+    }
+}
+
 impl Program {
     pub fn pretty(&self) -> String {
         let mut out = String::new();
@@ -336,9 +614,16 @@ impl Program {
                 ExportDecl::Local { items } => {
                     let items = items
                         .iter()
-                        .map(|item| match &item.alias {
-                            Some(alias) => format!("{} as {}", item.name, alias),
-                            None => item.name.clone(),
+                        .map(|item| {
+                            let name = if item.opaque {
+                                format!("{}(opaque)", item.name)
+                            } else {
+                                item.name.clone()
+                            };
+                            match &item.alias {
+                                Some(alias) => format!("{name} as {alias}"),
+                                None => name,
+                            }
                         })
                         .collect::<Vec<_>>()
                         .join(", ");
@@ -347,9 +632,16 @@ impl Program {
                 ExportDecl::From { path, items } => {
                     let items = items
                         .iter()
-                        .map(|item| match &item.alias {
-                            Some(alias) => format!("{} as {}", item.name, alias),
-                            None => item.name.clone(),
+                        .map(|item| {
+                            let name = if item.opaque {
+                                format!("{}(opaque)", item.name)
+                            } else {
+                                item.name.clone()
+                            };
+                            match &item.alias {
+                                Some(alias) => format!("{name} as {alias}"),
+                                None => name,
+                            }
                         })
                         .collect::<Vec<_>>()
                         .join(", ");
@@ -372,6 +664,13 @@ impl Program {
                 out.push_str(&format!("let {} = {};\n", g.name, pretty_expr(&g.value)));
             }
         }
+        for alias in &self.type_aliases {
+            out.push_str(&format!(
+                "type {} = {};\n",
+                alias.name,
+                alias.target.as_str()
+            ));
+        }
         for s in &self.structs {
             pretty_struct(s, 0, &mut out);
         }
@@ -388,9 +687,211 @@ impl Program {
     }
 }
 
+impl Program {
+    /// Calls `f` on every type annotation in the program: signatures, struct
+    /// fields, alias targets, `let` annotations and function literals.
+    pub fn for_each_type_name_mut(&mut self, f: &mut impl FnMut(&mut TypeName)) {
+        for g in &mut self.globals {
+            if let Some(ty) = &mut g.ty {
+                f(ty);
+            }
+            expr_type_names_mut(&mut g.value, f);
+        }
+        for alias in &mut self.type_aliases {
+            f(&mut alias.target);
+        }
+        for s in &mut self.structs {
+            for field in &mut s.fields {
+                f(&mut field.ty);
+            }
+        }
+        for i in &mut self.impls {
+            for m in &mut i.methods {
+                signature_type_names_mut(&mut m.params, m.return_type.as_mut(), f);
+                stmts_type_names_mut(&mut m.body, f);
+            }
+        }
+        for operator in &mut self.operators {
+            signature_type_names_mut(&mut operator.params, Some(&mut operator.return_type), f);
+            stmts_type_names_mut(&mut operator.body, f);
+        }
+        for func in &mut self.functions {
+            signature_type_names_mut(&mut func.params, func.return_type.as_mut(), f);
+            stmts_type_names_mut(&mut func.body, f);
+        }
+    }
+}
+
+fn signature_type_names_mut(
+    params: &mut [Param],
+    ret: Option<&mut TypeName>,
+    f: &mut impl FnMut(&mut TypeName),
+) {
+    for p in params {
+        f(&mut p.ty);
+    }
+    if let Some(ret) = ret {
+        f(ret);
+    }
+}
+
+fn stmts_type_names_mut(stmts: &mut [Stmt], f: &mut impl FnMut(&mut TypeName)) {
+    for stmt in stmts {
+        stmt_type_names_mut(stmt, f);
+    }
+}
+
+fn stmt_type_names_mut(stmt: &mut Stmt, f: &mut impl FnMut(&mut TypeName)) {
+    match &mut stmt.kind {
+        StmtKind::Let { ty, value, .. } => {
+            if let Some(ty) = ty {
+                f(ty);
+            }
+            expr_type_names_mut(value, f);
+        }
+        StmtKind::LetPattern { value, .. } | StmtKind::Expr(value) | StmtKind::Defer(value) => {
+            expr_type_names_mut(value, f)
+        }
+        StmtKind::Assign { target, value } => {
+            match target {
+                AssignTarget::Ident(_) => {}
+                AssignTarget::Index { base, index } => {
+                    expr_type_names_mut(base, f);
+                    expr_type_names_mut(index, f);
+                }
+                AssignTarget::Field { base, .. } => expr_type_names_mut(base, f),
+            }
+            expr_type_names_mut(value, f);
+        }
+        StmtKind::If {
+            cond,
+            then_body,
+            else_body,
+        } => {
+            expr_type_names_mut(cond, f);
+            stmts_type_names_mut(then_body, f);
+            stmts_type_names_mut(else_body, f);
+        }
+        StmtKind::While { cond, body } => {
+            expr_type_names_mut(cond, f);
+            stmts_type_names_mut(body, f);
+        }
+        StmtKind::For {
+            init,
+            cond,
+            step,
+            body,
+        } => {
+            if let Some(init) = init {
+                stmt_type_names_mut(init, f);
+            }
+            if let Some(cond) = cond {
+                expr_type_names_mut(cond, f);
+            }
+            if let Some(step) = step {
+                stmt_type_names_mut(step, f);
+            }
+            stmts_type_names_mut(body, f);
+        }
+        StmtKind::ForIn { iterable, body, .. } => {
+            expr_type_names_mut(iterable, f);
+            stmts_type_names_mut(body, f);
+        }
+        StmtKind::Return(value) => {
+            if let Some(value) = value {
+                expr_type_names_mut(value, f);
+            }
+        }
+        StmtKind::Match { expr, arms } => {
+            expr_type_names_mut(expr, f);
+            for arm in arms {
+                if let Some(guard) = &mut arm.guard {
+                    expr_type_names_mut(guard, f);
+                }
+                stmts_type_names_mut(&mut arm.body, f);
+            }
+        }
+        StmtKind::Break | StmtKind::Continue => {}
+    }
+}
+
+fn expr_type_names_mut(expr: &mut Expr, f: &mut impl FnMut(&mut TypeName)) {
+    match expr {
+        Expr::FnLit {
+            params,
+            return_type,
+            body,
+        } => {
+            signature_type_names_mut(params, Some(return_type), f);
+            stmts_type_names_mut(body, f);
+        }
+        Expr::ArrayLit(items) | Expr::VecLit(items) | Expr::TupleLit(items) => {
+            for item in items {
+                expr_type_names_mut(item, f);
+            }
+        }
+        Expr::ArrayRepeat { value, .. }
+        | Expr::Field { base: value, .. }
+        | Expr::Unary { expr: value, .. }
+        | Expr::Try(value)
+        | Expr::Group(value) => expr_type_names_mut(value, f),
+        Expr::Index { base, index } => {
+            expr_type_names_mut(base, f);
+            expr_type_names_mut(index, f);
+        }
+        Expr::Slice { base, start, end } => {
+            expr_type_names_mut(base, f);
+            for bound in [start, end].into_iter().flatten() {
+                expr_type_names_mut(bound, f);
+            }
+        }
+        Expr::StructLit {
+            type_args, fields, ..
+        } => {
+            for ty in type_args {
+                f(ty);
+            }
+            for (_, value) in fields {
+                expr_type_names_mut(value, f);
+            }
+        }
+        Expr::Binary { left, right, .. } | Expr::CustomInfix { left, right, .. } => {
+            expr_type_names_mut(left, f);
+            expr_type_names_mut(right, f);
+        }
+        Expr::Call { callee, args } => {
+            expr_type_names_mut(callee, f);
+            for arg in args {
+                expr_type_names_mut(arg, f);
+            }
+        }
+        Expr::Match { expr, arms } => {
+            expr_type_names_mut(expr, f);
+            for arm in arms {
+                if let Some(guard) = &mut arm.guard {
+                    expr_type_names_mut(guard, f);
+                }
+                expr_type_names_mut(&mut arm.expr, f);
+            }
+        }
+        Expr::IntLit(_)
+        | Expr::FloatLit(_)
+        | Expr::Ident(_)
+        | Expr::BoolLit(_)
+        | Expr::CharLit(_)
+        | Expr::StringLit(_)
+        | Expr::Path(_) => {}
+    }
+}
+
 fn pretty_struct(s: &StructDecl, indent: usize, out: &mut String) {
     let pad = " ".repeat(indent);
-    out.push_str(&format!("{pad}struct {} {{\n", s.name));
+    let params = if s.type_params.is_empty() {
+        String::new()
+    } else {
+        format!("[{}]", s.type_params.join(", "))
+    };
+    out.push_str(&format!("{pad}struct {}{params} {{\n", s.name));
     for f in &s.fields {
         out.push_str(&format!("{pad}  {}: {}\n", f.name, f.ty.as_str()));
     }
@@ -483,8 +984,13 @@ fn pretty_operator(operator: &OperatorDecl, indent: usize, out: &mut String) {
 
 fn pretty_stmt(stmt: &Stmt, indent: usize, out: &mut String) {
     let pad = " ".repeat(indent);
-    match stmt {
-        Stmt::Let { name, ty, value } => {
+    match &stmt.kind {
+        StmtKind::LetPattern { pattern, value } => out.push_str(&format!(
+            "{pad}let {} = {}\n",
+            pretty_match_pattern(pattern),
+            pretty_expr(value)
+        )),
+        StmtKind::Let { name, ty, value } => {
             if let Some(ty) = ty {
                 out.push_str(&format!(
                     "{pad}let {}: {} = {}\n",
@@ -496,7 +1002,7 @@ fn pretty_stmt(stmt: &Stmt, indent: usize, out: &mut String) {
                 out.push_str(&format!("{pad}let {} = {}\n", name, pretty_expr(value)));
             }
         }
-        Stmt::Assign { target, value } => {
+        StmtKind::Assign { target, value } => {
             let target = match target {
                 AssignTarget::Ident(n) => n.clone(),
                 AssignTarget::Index { base, index } => {
@@ -510,10 +1016,10 @@ fn pretty_stmt(stmt: &Stmt, indent: usize, out: &mut String) {
                 pretty_expr(value)
             ));
         }
-        Stmt::Expr(expr) => {
+        StmtKind::Expr(expr) => {
             out.push_str(&format!("{pad}expr {}\n", pretty_expr(expr)));
         }
-        Stmt::If {
+        StmtKind::If {
             cond,
             then_body,
             else_body,
@@ -529,13 +1035,13 @@ fn pretty_stmt(stmt: &Stmt, indent: usize, out: &mut String) {
                 }
             }
         }
-        Stmt::While { cond, body } => {
+        StmtKind::While { cond, body } => {
             out.push_str(&format!("{pad}while {}\n", pretty_expr(cond)));
             for s in body {
                 pretty_stmt(s, indent + 2, out);
             }
         }
-        Stmt::For {
+        StmtKind::For {
             init,
             cond,
             step,
@@ -555,21 +1061,35 @@ fn pretty_stmt(stmt: &Stmt, indent: usize, out: &mut String) {
                 pretty_stmt(s, indent + 2, out);
             }
         }
-        Stmt::Return(expr) => {
+        StmtKind::ForIn {
+            name,
+            iterable,
+            body,
+        } => {
+            out.push_str(&format!("{pad}for ({name} in {})\n", pretty_expr(iterable)));
+            for s in body {
+                pretty_stmt(s, indent + 2, out);
+            }
+        }
+        StmtKind::Return(expr) => {
             if let Some(expr) = expr {
                 out.push_str(&format!("{pad}return {}\n", pretty_expr(expr)));
             } else {
                 out.push_str(&format!("{pad}return\n"));
             }
         }
-        Stmt::Break => out.push_str(&format!("{pad}break\n")),
-        Stmt::Continue => out.push_str(&format!("{pad}continue\n")),
-        Stmt::Match { expr, arms } => {
+        StmtKind::Break => out.push_str(&format!("{pad}break\n")),
+        StmtKind::Continue => out.push_str(&format!("{pad}continue\n")),
+        StmtKind::Defer(expr) => {
+            out.push_str(&format!("{pad}defer {}\n", pretty_expr(expr)));
+        }
+        StmtKind::Match { expr, arms } => {
             out.push_str(&format!("{pad}match {}\n", pretty_expr(expr)));
             for arm in arms {
                 out.push_str(&format!(
-                    "{pad}  arm {}\n",
-                    pretty_match_pattern(&arm.pattern)
+                    "{pad}  arm {}{}\n",
+                    pretty_match_pattern(&arm.pattern),
+                    pretty_match_guard(arm.guard.as_ref())
                 ));
                 for s in &arm.body {
                     pretty_stmt(s, indent + 4, out);
@@ -580,15 +1100,15 @@ fn pretty_stmt(stmt: &Stmt, indent: usize, out: &mut String) {
 }
 
 fn pretty_for_clause_stmt(stmt: &Stmt) -> String {
-    match stmt {
-        Stmt::Let { name, ty, value } => {
+    match &stmt.kind {
+        StmtKind::Let { name, ty, value } => {
             if let Some(ty) = ty {
                 format!("let {}: {} = {}", name, ty.as_str(), pretty_expr(value))
             } else {
                 format!("let {} = {}", name, pretty_expr(value))
             }
         }
-        Stmt::Assign { target, value } => {
+        StmtKind::Assign { target, value } => {
             let target = match target {
                 AssignTarget::Ident(n) => n.clone(),
                 AssignTarget::Index { base, index } => {
@@ -598,7 +1118,7 @@ fn pretty_for_clause_stmt(stmt: &Stmt) -> String {
             };
             format!("{target} = {}", pretty_expr(value))
         }
-        Stmt::Expr(expr) => pretty_expr(expr),
+        StmtKind::Expr(expr) => pretty_expr(expr),
         _ => "<invalid-for-clause>".to_string(),
     }
 }
@@ -609,6 +1129,7 @@ fn pretty_expr(expr: &Expr) -> String {
         Expr::FloatLit(v) => v.clone(),
         Expr::Ident(n) => n.clone(),
         Expr::BoolLit(v) => v.to_string(),
+        Expr::CharLit(c) => format!("'{}'", c.escape_default()),
         Expr::StringLit(s) => format!("\"{}\"", s.replace('"', "\\\"")),
         Expr::Path(parts) => parts.join("."),
         Expr::ArrayLit(items) => {
@@ -616,15 +1137,42 @@ fn pretty_expr(expr: &Expr) -> String {
             format!("[{items}]")
         }
         Expr::ArrayRepeat { value, size } => format!("[{}; {}]", pretty_expr(value), size),
+        Expr::VecLit(items) => {
+            let items = items.iter().map(pretty_expr).collect::<Vec<_>>().join(", ");
+            format!("vec![{items}]")
+        }
+        Expr::TupleLit(items) => {
+            let items = items.iter().map(pretty_expr).collect::<Vec<_>>().join(", ");
+            format!("({items})")
+        }
         Expr::Index { base, index } => format!("{}[{}]", pretty_expr(base), pretty_expr(index)),
+        Expr::Slice { base, start, end } => format!(
+            "{}[{}..{}]",
+            pretty_expr(base),
+            start.as_deref().map(pretty_expr).unwrap_or_default(),
+            end.as_deref().map(pretty_expr).unwrap_or_default()
+        ),
         Expr::Field { base, field } => format!("{}.{}", pretty_expr(base), field),
-        Expr::StructLit { name, fields } => {
+        Expr::StructLit {
+            name,
+            type_args,
+            fields,
+        } => {
             let fields = fields
                 .iter()
                 .map(|(n, v)| format!("{n}: {}", pretty_expr(v)))
                 .collect::<Vec<_>>()
                 .join(", ");
-            format!("{name} {{ {fields} }}")
+            if type_args.is_empty() {
+                format!("{name} {{ {fields} }}")
+            } else {
+                let args = type_args
+                    .iter()
+                    .map(TypeName::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{name}[{args}] {{ {fields} }}")
+            }
         }
         Expr::FnLit {
             params,
@@ -648,27 +1196,12 @@ fn pretty_expr(expr: &Expr) -> String {
             format!("({}{})", symbol, pretty_expr(expr))
         }
         Expr::Binary { left, op, right } => {
-            let symbol = match op {
-                BinaryOp::Add => "+",
-                BinaryOp::Sub => "-",
-                BinaryOp::Mul => "*",
-                BinaryOp::Div => "/",
-                BinaryOp::Mod => "%",
-                BinaryOp::BitAnd => "&",
-                BinaryOp::BitOr => "|",
-                BinaryOp::BitXor => "^",
-                BinaryOp::Shl => "<<",
-                BinaryOp::Shr => ">>",
-                BinaryOp::EqEq => "==",
-                BinaryOp::Neq => "!=",
-                BinaryOp::Lt => "<",
-                BinaryOp::Lte => "<=",
-                BinaryOp::Gt => ">",
-                BinaryOp::Gte => ">=",
-                BinaryOp::AndAnd => "&&",
-                BinaryOp::OrOr => "||",
-            };
-            format!("({} {} {})", pretty_expr(left), symbol, pretty_expr(right))
+            format!(
+                "({} {} {})",
+                pretty_expr(left),
+                op.symbol(),
+                pretty_expr(right)
+            )
         }
         Expr::CustomInfix {
             left,
@@ -689,8 +1222,9 @@ fn pretty_expr(expr: &Expr) -> String {
                 .iter()
                 .map(|arm| {
                     format!(
-                        "{} => {}",
+                        "{}{} => {}",
                         pretty_match_pattern(&arm.pattern),
+                        pretty_match_guard(arm.guard.as_ref()),
                         pretty_expr(&arm.expr)
                     )
                 })
@@ -703,11 +1237,18 @@ fn pretty_expr(expr: &Expr) -> String {
     }
 }
 
+fn pretty_match_guard(guard: Option<&Expr>) -> String {
+    guard
+        .map(|guard| format!(" if {}", pretty_expr(guard)))
+        .unwrap_or_default()
+}
+
 fn pretty_match_pattern(pat: &MatchPattern) -> String {
     match pat {
         MatchPattern::Wildcard => "_".to_string(),
         MatchPattern::Literal(MatchLiteral::Int(v)) => v.to_string(),
         MatchPattern::Literal(MatchLiteral::Bool(v)) => v.to_string(),
+        MatchPattern::Literal(MatchLiteral::Char(c)) => format!("'{}'", c.escape_default()),
         MatchPattern::Literal(MatchLiteral::String(s)) => format!("\"{}\"", s.replace('"', "\\\"")),
         MatchPattern::Literal(MatchLiteral::Float(v)) => v.clone(),
         MatchPattern::Variant { name, binding } => match binding {
@@ -719,15 +1260,90 @@ fn pretty_match_pattern(pat: &MatchPattern) -> String {
             .map(pretty_match_pattern)
             .collect::<Vec<_>>()
             .join(" | "),
+        MatchPattern::Binding(name) => name.clone(),
+        MatchPattern::Struct { name, fields, rest } => {
+            let mut parts = fields
+                .iter()
+                .map(|(field, pattern)| match pattern {
+                    MatchPattern::Binding(binding) if binding == field => field.clone(),
+                    _ => format!("{field}: {}", pretty_match_pattern(pattern)),
+                })
+                .collect::<Vec<_>>();
+            if *rest {
+                parts.push("..".to_string());
+            }
+            format!("{name} {{ {} }}", parts.join(", "))
+        }
+        MatchPattern::Array {
+            items,
+            rest,
+            rest_binding,
+        } => {
+            let mut parts = items.iter().map(pretty_match_pattern).collect::<Vec<_>>();
+            if *rest {
+                parts.push(format!("{}..", rest_binding.as_deref().unwrap_or("")));
+            }
+            format!("[{}]", parts.join(", "))
+        }
+        MatchPattern::Tuple(items) => format!(
+            "({})",
+            items
+                .iter()
+                .map(pretty_match_pattern)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
     }
 }
 
 impl TypeName {
+    /// Replaces each `Named` type for which `lookup` returns a type, without
+    /// looking inside the replacements. Returns whether anything was replaced.
+    pub fn replace_named(&mut self, lookup: &mut impl FnMut(&str) -> Option<TypeName>) -> bool {
+        match self {
+            TypeName::Named(name) => match lookup(name) {
+                Some(target) => {
+                    *self = target;
+                    true
+                }
+                None => false,
+            },
+            TypeName::Option { value } | TypeName::Map { value } => value.replace_named(lookup),
+            TypeName::Array { elem, .. } | TypeName::Vec { elem } => elem.replace_named(lookup),
+            TypeName::Result { ok, err } => {
+                let ok = ok.replace_named(lookup);
+                err.replace_named(lookup) || ok
+            }
+            TypeName::Fn { params, ret } => {
+                let mut replaced = ret.replace_named(lookup);
+                for param in params {
+                    replaced |= param.replace_named(lookup);
+                }
+                replaced
+            }
+            TypeName::Tuple(items) | TypeName::Generic { args: items, .. } => {
+                let mut replaced = false;
+                for item in items {
+                    replaced |= item.replace_named(lookup);
+                }
+                replaced
+            }
+            TypeName::Int
+            | TypeName::Float
+            | TypeName::Bool
+            | TypeName::Char
+            | TypeName::String
+            | TypeName::Bytes
+            | TypeName::Void => false,
+        }
+    }
+
     pub fn as_str(&self) -> String {
         match self {
             TypeName::Int => "Int".to_string(),
             TypeName::Float => "Float".to_string(),
             TypeName::Bool => "Bool".to_string(),
+            TypeName::Char => "Char".to_string(),
             TypeName::String => "String".to_string(),
             TypeName::Bytes => "Bytes".to_string(),
             TypeName::Void => "Void".to_string(),
@@ -747,6 +1363,21 @@ impl TypeName {
                     .join(", ");
                 format!("Fn({params}) -> {}", ret.as_str())
             }
+            TypeName::Tuple(items) => format!(
+                "({})",
+                items
+                    .iter()
+                    .map(TypeName::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            TypeName::Generic { name, args } => format!(
+                "{name}[{}]",
+                args.iter()
+                    .map(TypeName::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         }
     }
 }
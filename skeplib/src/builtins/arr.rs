@@ -59,4 +59,25 @@ pub(super) const SIGS: &[BuiltinSig] = &[
         ret: TypeInfo::Unknown,
         kind: BuiltinKind::ArrayOps,
     },
+    BuiltinSig {
+        package: "arr",
+        name: "map",
+        params: &[],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
+    BuiltinSig {
+        package: "arr",
+        name: "filter",
+        params: &[],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
+    BuiltinSig {
+        package: "arr",
+        name: "sortBy",
+        params: &[],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
 ];
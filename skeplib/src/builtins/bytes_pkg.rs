@@ -59,4 +59,32 @@ pub(super) const SIGS: &[BuiltinSig] = &[
         ret: TypeInfo::Bytes,
         kind: BuiltinKind::FixedArity,
     },
+    BuiltinSig {
+        package: "bytes",
+        name: "toHex",
+        params: &[TypeInfo::Bytes],
+        ret: TypeInfo::String,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "bytes",
+        name: "fromHex",
+        params: &[TypeInfo::String],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "bytes",
+        name: "encode",
+        params: &[TypeInfo::String, TypeInfo::String],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "bytes",
+        name: "decode",
+        params: &[TypeInfo::Bytes, TypeInfo::String],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::FixedArity,
+    },
 ];
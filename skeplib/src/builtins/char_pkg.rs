@@ -0,0 +1,69 @@
+use std::sync::LazyLock;
+
+use crate::types::TypeInfo;
+
+use super::{BuiltinKind, BuiltinSig};
+
+const CHAR1: &[TypeInfo] = &[TypeInfo::Char];
+const INT1: &[TypeInfo] = &[TypeInfo::Int];
+const STRING1: &[TypeInfo] = &[TypeInfo::String];
+
+fn option_char() -> TypeInfo {
+    TypeInfo::Option {
+        value: Box::new(TypeInfo::Char),
+    }
+}
+
+pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
+    vec![
+        BuiltinSig {
+            package: "char",
+            name: "toInt",
+            params: CHAR1,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "char",
+            name: "fromInt",
+            params: INT1,
+            ret: option_char(),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "char",
+            name: "toString",
+            params: CHAR1,
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "char",
+            name: "fromString",
+            params: STRING1,
+            ret: option_char(),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "char",
+            name: "isDigit",
+            params: CHAR1,
+            ret: TypeInfo::Bool,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "char",
+            name: "isAlpha",
+            params: CHAR1,
+            ret: TypeInfo::Bool,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "char",
+            name: "isWhitespace",
+            params: CHAR1,
+            ret: TypeInfo::Bool,
+            kind: BuiltinKind::FixedArity,
+        },
+    ]
+});
@@ -0,0 +1,12 @@
+use crate::types::TypeInfo;
+
+use super::{BuiltinKind, BuiltinSig};
+
+/// Runtime entry points for bare prelude calls such as `panic(msg)`.
+pub(super) const SIGS: &[BuiltinSig] = &[BuiltinSig {
+    package: "core",
+    name: "panic",
+    params: &[TypeInfo::String],
+    ret: TypeInfo::Void,
+    kind: BuiltinKind::FixedArity,
+}];
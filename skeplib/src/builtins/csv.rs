@@ -0,0 +1,50 @@
+use std::sync::LazyLock;
+
+use crate::types::TypeInfo;
+
+use super::{BuiltinKind, BuiltinSig};
+
+const STRING_PARAM: &[TypeInfo] = &[TypeInfo::String];
+
+fn vec_of(elem: TypeInfo) -> TypeInfo {
+    TypeInfo::Vec {
+        elem: Box::new(elem),
+    }
+}
+
+fn or_error(ok: TypeInfo) -> TypeInfo {
+    TypeInfo::Result {
+        ok: Box::new(ok),
+        err: Box::new(TypeInfo::String),
+    }
+}
+
+static ROWS_PARAM: LazyLock<[TypeInfo; 1]> = LazyLock::new(|| [vec_of(vec_of(TypeInfo::String))]);
+
+pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
+    vec![
+        BuiltinSig {
+            package: "csv",
+            name: "parse",
+            params: STRING_PARAM,
+            ret: or_error(vec_of(vec_of(TypeInfo::String))),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "csv",
+            name: "parseWithHeader",
+            params: STRING_PARAM,
+            ret: or_error(vec_of(TypeInfo::Map {
+                value: Box::new(TypeInfo::String),
+            })),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "csv",
+            name: "stringify",
+            params: &*ROWS_PARAM,
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+    ]
+});
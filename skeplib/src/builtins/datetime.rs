@@ -7,6 +7,9 @@ use super::{BuiltinKind, BuiltinSig};
 const DATETIME_NOW_PARAMS: &[TypeInfo] = &[];
 const DATETIME_UNIX_PARAMS: &[TypeInfo] = &[TypeInfo::Int];
 const DATETIME_PARSE_PARAMS: &[TypeInfo] = &[TypeInfo::String];
+const DATETIME_FORMAT_PARAMS: &[TypeInfo] = &[TypeInfo::Int, TypeInfo::String];
+const DATETIME_INT_PAIR_PARAMS: &[TypeInfo] = &[TypeInfo::Int, TypeInfo::Int];
+const DATETIME_PATTERN_PARSE_PARAMS: &[TypeInfo] = &[TypeInfo::String, TypeInfo::String];
 
 fn result_int_string() -> TypeInfo {
     TypeInfo::Result {
@@ -52,6 +55,20 @@ pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
             ret: result_int_string(),
             kind: BuiltinKind::FixedArity,
         },
+        BuiltinSig {
+            package: "datetime",
+            name: "format",
+            params: DATETIME_FORMAT_PARAMS,
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "datetime",
+            name: "parse",
+            params: DATETIME_PATTERN_PARSE_PARAMS,
+            ret: result_int_string(),
+            kind: BuiltinKind::FixedArity,
+        },
         BuiltinSig {
             package: "datetime",
             name: "year",
@@ -94,5 +111,68 @@ pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
             ret: TypeInfo::Int,
             kind: BuiltinKind::FixedArity,
         },
+        BuiltinSig {
+            package: "datetime",
+            name: "nowLocal",
+            params: DATETIME_NOW_PARAMS,
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "datetime",
+            name: "toLocal",
+            params: DATETIME_UNIX_PARAMS,
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "datetime",
+            name: "offsetMinutes",
+            params: DATETIME_NOW_PARAMS,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "datetime",
+            name: "formatLocal",
+            params: DATETIME_FORMAT_PARAMS,
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "datetime",
+            name: "addDays",
+            params: DATETIME_INT_PAIR_PARAMS,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "datetime",
+            name: "addHours",
+            params: DATETIME_INT_PAIR_PARAMS,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "datetime",
+            name: "diffSeconds",
+            params: DATETIME_INT_PAIR_PARAMS,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "datetime",
+            name: "weekday",
+            params: DATETIME_UNIX_PARAMS,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "datetime",
+            name: "daysInMonth",
+            params: DATETIME_INT_PAIR_PARAMS,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
     ]
 });
@@ -0,0 +1,67 @@
+use std::sync::LazyLock;
+
+use crate::types::TypeInfo;
+
+use super::{BuiltinKind, BuiltinSig};
+
+/// Decoders report malformed input as `Err(String)`.
+fn or_error(ok: TypeInfo) -> TypeInfo {
+    TypeInfo::Result {
+        ok: Box::new(ok),
+        err: Box::new(TypeInfo::String),
+    }
+}
+
+pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
+    vec![
+        BuiltinSig {
+            package: "encoding",
+            name: "base64Encode",
+            params: &[TypeInfo::Bytes],
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "encoding",
+            name: "base64Decode",
+            params: &[TypeInfo::String],
+            ret: or_error(TypeInfo::Bytes),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "encoding",
+            name: "hexEncode",
+            params: &[TypeInfo::Bytes],
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "encoding",
+            name: "hexDecode",
+            params: &[TypeInfo::String],
+            ret: or_error(TypeInfo::Bytes),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "encoding",
+            name: "urlEncode",
+            params: &[TypeInfo::String],
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "encoding",
+            name: "urlDecode",
+            params: &[TypeInfo::String],
+            ret: or_error(TypeInfo::String),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "encoding",
+            name: "isValidUtf8",
+            params: &[TypeInfo::Bytes],
+            ret: TypeInfo::Bool,
+            kind: BuiltinKind::FixedArity,
+        },
+    ]
+});
@@ -6,6 +6,7 @@ use super::{BuiltinKind, BuiltinSig};
 
 const STRING1: &[TypeInfo] = &[TypeInfo::String];
 const STRING2: &[TypeInfo] = &[TypeInfo::String, TypeInfo::String];
+const STRING_BYTES: &[TypeInfo] = &[TypeInfo::String, TypeInfo::Bytes];
 
 fn result_bool_string() -> TypeInfo {
     TypeInfo::Result {
@@ -21,6 +22,13 @@ fn result_string_string() -> TypeInfo {
     }
 }
 
+fn result_bytes_string() -> TypeInfo {
+    TypeInfo::Result {
+        ok: Box::new(TypeInfo::Bytes),
+        err: Box::new(TypeInfo::String),
+    }
+}
+
 fn result_void_string() -> TypeInfo {
     TypeInfo::Result {
         ok: Box::new(TypeInfo::Void),
@@ -79,6 +87,34 @@ pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
             ret: result_void_string(),
             kind: BuiltinKind::FixedArity,
         },
+        BuiltinSig {
+            package: "fs",
+            name: "copyFile",
+            params: STRING2,
+            ret: result_void_string(),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "fs",
+            name: "moveFile",
+            params: STRING2,
+            ret: result_void_string(),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "fs",
+            name: "readBytes",
+            params: STRING1,
+            ret: result_bytes_string(),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "fs",
+            name: "writeBytes",
+            params: STRING_BYTES,
+            ret: result_void_string(),
+            kind: BuiltinKind::FixedArity,
+        },
         BuiltinSig {
             package: "fs",
             name: "join",
@@ -0,0 +1,37 @@
+use crate::types::TypeInfo;
+
+use super::{BuiltinKind, BuiltinSig};
+
+/// Each digest takes a `String` or `Bytes`; sema checks that directly.
+const INPUT_PARAM_SENTINEL: &[TypeInfo] = &[TypeInfo::Unknown];
+
+pub(super) const SIGS: &[BuiltinSig] = &[
+    BuiltinSig {
+        package: "hash",
+        name: "sha256",
+        params: INPUT_PARAM_SENTINEL,
+        ret: TypeInfo::String,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "hash",
+        name: "md5",
+        params: INPUT_PARAM_SENTINEL,
+        ret: TypeInfo::String,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "hash",
+        name: "crc32",
+        params: INPUT_PARAM_SENTINEL,
+        ret: TypeInfo::Int,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "hash",
+        name: "fnv64",
+        params: INPUT_PARAM_SENTINEL,
+        ret: TypeInfo::Int,
+        kind: BuiltinKind::FixedArity,
+    },
+];
@@ -1,3 +1,5 @@
+use std::sync::LazyLock;
+
 use crate::types::TypeInfo;
 
 use super::{BuiltinKind, BuiltinSig};
@@ -9,68 +11,131 @@ const IO_PRINT_BOOL_PARAMS: &[TypeInfo] = &[TypeInfo::Bool];
 const IO_PRINT_STRING_PARAMS: &[TypeInfo] = &[TypeInfo::String];
 const IO_READLINE_PARAMS: &[TypeInfo] = &[];
 
-pub(super) const SIGS: &[BuiltinSig] = &[
-    BuiltinSig {
-        package: "io",
-        name: "print",
-        params: IO_PRINT_PARAMS,
-        ret: TypeInfo::Void,
-        kind: BuiltinKind::FixedArity,
-    },
-    BuiltinSig {
-        package: "io",
-        name: "println",
-        params: IO_PRINT_PARAMS,
-        ret: TypeInfo::Void,
-        kind: BuiltinKind::FixedArity,
-    },
-    BuiltinSig {
-        package: "io",
-        name: "printInt",
-        params: IO_PRINT_INT_PARAMS,
-        ret: TypeInfo::Void,
-        kind: BuiltinKind::FixedArity,
-    },
-    BuiltinSig {
-        package: "io",
-        name: "printFloat",
-        params: IO_PRINT_FLOAT_PARAMS,
-        ret: TypeInfo::Void,
-        kind: BuiltinKind::FixedArity,
-    },
-    BuiltinSig {
-        package: "io",
-        name: "printBool",
-        params: IO_PRINT_BOOL_PARAMS,
-        ret: TypeInfo::Void,
-        kind: BuiltinKind::FixedArity,
-    },
-    BuiltinSig {
-        package: "io",
-        name: "printString",
-        params: IO_PRINT_STRING_PARAMS,
-        ret: TypeInfo::Void,
-        kind: BuiltinKind::FixedArity,
-    },
-    BuiltinSig {
-        package: "io",
-        name: "format",
-        params: IO_PRINT_PARAMS,
-        ret: TypeInfo::String,
-        kind: BuiltinKind::FormatVariadic,
-    },
-    BuiltinSig {
-        package: "io",
-        name: "printf",
-        params: IO_PRINT_PARAMS,
-        ret: TypeInfo::Void,
-        kind: BuiltinKind::FormatVariadic,
-    },
-    BuiltinSig {
-        package: "io",
-        name: "readLine",
-        params: IO_READLINE_PARAMS,
-        ret: TypeInfo::String,
-        kind: BuiltinKind::FixedArity,
-    },
-];
+fn option_string() -> TypeInfo {
+    TypeInfo::Option {
+        value: Box::new(TypeInfo::String),
+    }
+}
+
+fn vec_string() -> TypeInfo {
+    TypeInfo::Vec {
+        elem: Box::new(TypeInfo::String),
+    }
+}
+
+pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
+    vec![
+        BuiltinSig {
+            package: "io",
+            name: "print",
+            params: IO_PRINT_PARAMS,
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "println",
+            params: IO_PRINT_PARAMS,
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "eprint",
+            params: IO_PRINT_PARAMS,
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "eprintln",
+            params: IO_PRINT_PARAMS,
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "printInt",
+            params: IO_PRINT_INT_PARAMS,
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "printFloat",
+            params: IO_PRINT_FLOAT_PARAMS,
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "printBool",
+            params: IO_PRINT_BOOL_PARAMS,
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "printString",
+            params: IO_PRINT_STRING_PARAMS,
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "printAny",
+            params: &[],
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::ArrayOps,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "debug",
+            params: &[],
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::ArrayOps,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "format",
+            params: IO_PRINT_PARAMS,
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FormatVariadic,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "printf",
+            params: IO_PRINT_PARAMS,
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::FormatVariadic,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "readLine",
+            params: IO_READLINE_PARAMS,
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "tryReadLine",
+            params: IO_READLINE_PARAMS,
+            ret: option_string(),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "readAll",
+            params: IO_READLINE_PARAMS,
+            ret: TypeInfo::String,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "io",
+            name: "readLines",
+            params: IO_READLINE_PARAMS,
+            ret: vec_string(),
+            kind: BuiltinKind::FixedArity,
+        },
+    ]
+});
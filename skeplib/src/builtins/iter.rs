@@ -0,0 +1,41 @@
+use crate::types::TypeInfo;
+
+use super::{BuiltinKind, BuiltinSig};
+
+pub(super) const SIGS: &[BuiltinSig] = &[
+    BuiltinSig {
+        package: "iter",
+        name: "range",
+        params: &[],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
+    BuiltinSig {
+        package: "iter",
+        name: "map",
+        params: &[],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
+    BuiltinSig {
+        package: "iter",
+        name: "filter",
+        params: &[],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
+    BuiltinSig {
+        package: "iter",
+        name: "take",
+        params: &[],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
+    BuiltinSig {
+        package: "iter",
+        name: "collect",
+        params: &[],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
+];
@@ -0,0 +1,54 @@
+use std::sync::LazyLock;
+
+use crate::types::TypeInfo;
+
+use super::{BuiltinKind, BuiltinSig};
+
+const INT2: &[TypeInfo] = &[TypeInfo::Int, TypeInfo::Int];
+const INT3: &[TypeInfo] = &[TypeInfo::Int, TypeInfo::Int, TypeInfo::Int];
+
+fn option_int() -> TypeInfo {
+    TypeInfo::Option {
+        value: Box::new(TypeInfo::Int),
+    }
+}
+
+pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
+    vec![
+        BuiltinSig {
+            package: "math",
+            name: "wrappingAdd",
+            params: INT2,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "math",
+            name: "wrappingSub",
+            params: INT2,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "math",
+            name: "wrappingMul",
+            params: INT2,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "math",
+            name: "checkedDiv",
+            params: INT2,
+            ret: option_int(),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "math",
+            name: "divOr",
+            params: INT3,
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        },
+    ]
+});
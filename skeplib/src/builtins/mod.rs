@@ -2,16 +2,25 @@ use crate::types::TypeInfo;
 
 mod arr;
 mod bytes_pkg;
+mod char_pkg;
+mod core_pkg;
+mod csv;
 mod datetime;
+mod encoding;
 mod ffi_pkg;
 mod fs;
+mod hash;
 mod io;
+mod iter;
 mod map_pkg;
+mod math;
 mod net;
 mod option_pkg;
 mod os;
 mod random;
 mod result_pkg;
+mod serde;
+mod store;
 mod str_pkg;
 mod task;
 mod vec_pkg;
@@ -67,6 +76,84 @@ pub struct BuiltinSpec {
     pub meta: BuiltinMeta,
 }
 
+/// Signature of one function in an embedder's host package. Unlike the native
+/// [`BuiltinSig`] tables it owns its strings, so packages can be described at runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomBuiltinSig {
+    pub package: String,
+    pub name: String,
+    pub params: Vec<TypeInfo>,
+    pub ret: TypeInfo,
+}
+
+impl CustomBuiltinSig {
+    pub fn new(
+        package: impl Into<String>,
+        name: impl Into<String>,
+        params: Vec<TypeInfo>,
+        ret: TypeInfo,
+    ) -> Self {
+        Self {
+            package: package.into(),
+            name: name.into(),
+            params,
+            ret,
+        }
+    }
+}
+
+impl From<BuiltinSig> for CustomBuiltinSig {
+    fn from(sig: BuiltinSig) -> Self {
+        Self::new(sig.package, sig.name, sig.params.to_vec(), sig.ret)
+    }
+}
+
+/// Signatures of host packages an embedder provides on top of the native ones, so
+/// `import pkg;` resolves and `pkg.fn(...)` calls type-check like fixed-arity builtins.
+/// At run time the calls go to `skepart::RtHost::call_custom`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CustomBuiltins {
+    sigs: Vec<CustomBuiltinSig>,
+}
+
+impl CustomBuiltins {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `sig`, rejecting native package names and duplicate functions.
+    pub fn register(&mut self, sig: impl Into<CustomBuiltinSig>) -> Result<(), String> {
+        let sig = sig.into();
+        if all_builtin_sigs_any()
+            .iter()
+            .any(|s| s.package == sig.package)
+        {
+            return Err(format!(
+                "Custom builtin `{}.{}` cannot extend native package `{}`",
+                sig.package, sig.name, sig.package
+            ));
+        }
+        if self.find(&sig.package, &sig.name).is_some() {
+            return Err(format!(
+                "Custom builtin `{}.{}` is already registered",
+                sig.package, sig.name
+            ));
+        }
+        self.sigs.push(sig);
+        Ok(())
+    }
+
+    pub fn find(&self, package: &str, name: &str) -> Option<&CustomBuiltinSig> {
+        self.sigs
+            .iter()
+            .find(|s| s.package == package && s.name == name)
+    }
+
+    pub fn has_package(&self, package: &str) -> bool {
+        self.sigs.iter().any(|s| s.package == package)
+    }
+}
+
 pub fn find_builtin_sig(package: &str, name: &str) -> Option<&'static BuiltinSig> {
     let spec = find_builtin_spec(package, name)?;
     Some(spec.sig)
@@ -76,19 +163,28 @@ pub fn find_builtin_sig_any(package: &str, name: &str) -> Option<&'static Builti
     io::SIGS
         .iter()
         .chain(bytes_pkg::SIGS.iter())
+        .chain(char_pkg::SIGS.iter())
+        .chain(csv::SIGS.iter())
         .chain(map_pkg::SIGS.iter())
+        .chain(math::SIGS.iter())
         .chain(str_pkg::SIGS.iter())
         .chain(arr::SIGS.iter())
         .chain(datetime::SIGS.iter())
+        .chain(encoding::SIGS.iter())
         .chain(ffi_pkg::SIGS.iter())
         .chain(fs::SIGS.iter())
+        .chain(hash::SIGS.iter())
         .chain(net::SIGS.iter())
         .chain(os::SIGS.iter())
         .chain(option_pkg::SIGS.iter())
         .chain(result_pkg::SIGS.iter())
+        .chain(serde::SIGS.iter())
+        .chain(store::SIGS.iter())
         .chain(random::SIGS.iter())
         .chain(task::SIGS.iter())
         .chain(vec_pkg::SIGS.iter())
+        .chain(iter::SIGS.iter())
+        .chain(core_pkg::SIGS.iter())
         .find(|s| s.package == package && s.name == name)
 }
 
@@ -120,19 +216,28 @@ fn all_builtin_sigs_any() -> Vec<&'static BuiltinSig> {
     io::SIGS
         .iter()
         .chain(bytes_pkg::SIGS.iter())
+        .chain(char_pkg::SIGS.iter())
+        .chain(csv::SIGS.iter())
         .chain(map_pkg::SIGS.iter())
+        .chain(math::SIGS.iter())
         .chain(str_pkg::SIGS.iter())
         .chain(arr::SIGS.iter())
         .chain(datetime::SIGS.iter())
+        .chain(encoding::SIGS.iter())
         .chain(ffi_pkg::SIGS.iter())
         .chain(fs::SIGS.iter())
+        .chain(hash::SIGS.iter())
         .chain(net::SIGS.iter())
         .chain(os::SIGS.iter())
         .chain(option_pkg::SIGS.iter())
         .chain(result_pkg::SIGS.iter())
+        .chain(serde::SIGS.iter())
+        .chain(store::SIGS.iter())
         .chain(random::SIGS.iter())
         .chain(task::SIGS.iter())
         .chain(vec_pkg::SIGS.iter())
+        .chain(iter::SIGS.iter())
+        .chain(core_pkg::SIGS.iter())
         .collect()
 }
 
@@ -166,6 +271,28 @@ fn builtin_meta(package: &str, name: &str) -> BuiltinMeta {
             runtime_helper: None,
             visibility: BuiltinVisibility::Public,
         },
+        ("str", "__index") | ("str", "__slice") => BuiltinMeta {
+            purity: BuiltinPurity::Pure,
+            lowering: BuiltinLowering::GenericDispatch,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Internal,
+        },
+        ("core", _) => BuiltinMeta {
+            purity: BuiltinPurity::HostEffectful,
+            lowering: BuiltinLowering::GenericDispatch,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Internal,
+        },
+        // Callbacks run arbitrary user code, so these are never pure.
+        ("arr", "map") | ("arr", "filter") | ("arr", "sortBy") => BuiltinMeta {
+            purity: BuiltinPurity::HostEffectful,
+            lowering: BuiltinLowering::GenericDispatch,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Public,
+        },
         ("arr", _) => BuiltinMeta {
             purity: BuiltinPurity::Pure,
             lowering: BuiltinLowering::TypeDirected,
@@ -187,6 +314,21 @@ fn builtin_meta(package: &str, name: &str) -> BuiltinMeta {
             runtime_helper: None,
             visibility: BuiltinVisibility::Public,
         },
+        // Pipelines are fused into loops at lowering time; the callbacks run user code.
+        ("iter", "range") | ("iter", "take") => BuiltinMeta {
+            purity: BuiltinPurity::Pure,
+            lowering: BuiltinLowering::TypeDirected,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Public,
+        },
+        ("iter", _) => BuiltinMeta {
+            purity: BuiltinPurity::HostEffectful,
+            lowering: BuiltinLowering::TypeDirected,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Public,
+        },
         ("io", "format") => BuiltinMeta {
             purity: BuiltinPurity::Pure,
             lowering: BuiltinLowering::GenericDispatch,
@@ -196,12 +338,19 @@ fn builtin_meta(package: &str, name: &str) -> BuiltinMeta {
         },
         ("io", "print")
         | ("io", "println")
+        | ("io", "eprint")
+        | ("io", "eprintln")
         | ("io", "printInt")
         | ("io", "printFloat")
         | ("io", "printBool")
         | ("io", "printString")
+        | ("io", "printAny")
+        | ("io", "debug")
         | ("io", "printf")
-        | ("io", "readLine") => BuiltinMeta {
+        | ("io", "readLine")
+        | ("io", "tryReadLine")
+        | ("io", "readAll")
+        | ("io", "readLines") => BuiltinMeta {
             purity: BuiltinPurity::HostEffectful,
             lowering: BuiltinLowering::GenericDispatch,
             can_const_fold: false,
@@ -215,6 +364,48 @@ fn builtin_meta(package: &str, name: &str) -> BuiltinMeta {
             runtime_helper: None,
             visibility: BuiltinVisibility::Public,
         },
+        ("math", _) => BuiltinMeta {
+            purity: BuiltinPurity::Pure,
+            lowering: BuiltinLowering::GenericDispatch,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Public,
+        },
+        ("serde", _) => BuiltinMeta {
+            purity: BuiltinPurity::Pure,
+            lowering: BuiltinLowering::GenericDispatch,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Public,
+        },
+        ("encoding", _) => BuiltinMeta {
+            purity: BuiltinPurity::Pure,
+            lowering: BuiltinLowering::GenericDispatch,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Public,
+        },
+        ("hash", _) => BuiltinMeta {
+            purity: BuiltinPurity::Pure,
+            lowering: BuiltinLowering::GenericDispatch,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Public,
+        },
+        ("csv", _) => BuiltinMeta {
+            purity: BuiltinPurity::Pure,
+            lowering: BuiltinLowering::GenericDispatch,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Public,
+        },
+        ("char", _) => BuiltinMeta {
+            purity: BuiltinPurity::Pure,
+            lowering: BuiltinLowering::GenericDispatch,
+            can_const_fold: false,
+            runtime_helper: None,
+            visibility: BuiltinVisibility::Public,
+        },
         ("option", _) => BuiltinMeta {
             purity: BuiltinPurity::Pure,
             lowering: BuiltinLowering::GenericDispatch,
@@ -235,6 +426,7 @@ fn builtin_meta(package: &str, name: &str) -> BuiltinMeta {
         | ("net", _)
         | ("os", _)
         | ("random", _)
+        | ("store", _)
         | ("task", _) => BuiltinMeta {
             purity: BuiltinPurity::HostEffectful,
             lowering: BuiltinLowering::GenericDispatch,
@@ -299,19 +491,28 @@ mod tests {
         let manual_count = [
             super::io::SIGS.len(),
             super::bytes_pkg::SIGS.len(),
+            super::char_pkg::SIGS.len(),
+            super::csv::SIGS.len(),
             super::map_pkg::SIGS.len(),
+            super::math::SIGS.len(),
             super::str_pkg::SIGS.len(),
             super::arr::SIGS.len(),
             super::datetime::SIGS.len(),
+            super::encoding::SIGS.len(),
             super::ffi_pkg::SIGS.len(),
             super::fs::SIGS.len(),
+            super::hash::SIGS.len(),
             super::net::SIGS.len(),
             super::os::SIGS.len(),
             super::option_pkg::SIGS.len(),
             super::result_pkg::SIGS.len(),
+            super::serde::SIGS.len(),
+            super::store::SIGS.len(),
             super::random::SIGS.len(),
             super::task::SIGS.len(),
             super::vec_pkg::SIGS.len(),
+            super::iter::SIGS.len(),
+            super::core_pkg::SIGS.len(),
         ]
         .into_iter()
         .sum::<usize>();
@@ -319,7 +520,15 @@ mod tests {
         assert!(find_builtin_sig("datetime", "nowUnix").is_some());
         assert!(find_builtin_spec("vec", "push").is_some());
         assert!(find_builtin_spec("bytes", "fromString").is_some());
+        assert!(find_builtin_spec("char", "fromInt").is_some());
         assert!(find_builtin_spec("map", "new").is_some());
+        assert!(find_builtin_spec("iter", "collect").is_some());
+        assert!(find_builtin_spec("math", "wrappingAdd").is_some());
+        assert!(find_builtin_spec("serde", "toJson").is_some());
+        assert!(find_builtin_spec("store", "open").is_some());
+        assert!(find_builtin_spec("encoding", "base64Decode").is_some());
+        assert!(find_builtin_spec("hash", "sha256").is_some());
+        assert!(find_builtin_spec("csv", "parseWithHeader").is_some());
         assert!(find_builtin_spec("task", "__testTask").is_none());
         assert!(find_builtin_sig("task", "__testTask").is_none());
         assert!(find_builtin_spec_any("task", "__testTask").is_some());
@@ -329,6 +538,44 @@ mod tests {
         assert!(find_builtin_spec("missing", "name").is_none());
     }
 
+    #[test]
+    fn every_runtime_dispatched_builtin_has_a_sema_signature() {
+        // The runtime's default builtins are the `("pkg", "name", [args])` arms
+        // of `skepart::builtins::call_with_context`.
+        let runtime = include_str!("../../../skepart/src/builtins/mod.rs");
+        let mut missing = Vec::new();
+        let mut checked = 0;
+        for arm in runtime.split("        (\"").skip(1) {
+            let mut parts = arm.splitn(3, '"');
+            let (Some(package), Some(sep), Some(rest)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            if sep != ", " {
+                continue;
+            }
+            let Some(name) = rest.split('"').next() else {
+                continue;
+            };
+            let arity = rest
+                .split_once('[')
+                .and_then(|(_, args)| args.split_once(']'))
+                .map(|(args, _)| args.split(',').filter(|a| !a.trim().is_empty()).count());
+            checked += 1;
+            match find_builtin_sig_any(package, name) {
+                Some(sig) if sig.kind != super::BuiltinKind::FixedArity => {}
+                Some(sig) if Some(sig.params.len()) == arity => {}
+                Some(sig) => missing.push(format!(
+                    "{package}.{name}: runtime takes {arity:?} args, sema signature takes {}",
+                    sig.params.len()
+                )),
+                None => missing.push(format!("{package}.{name}: no sema signature")),
+            }
+        }
+        assert!(checked > 100, "only found {checked} runtime builtins");
+        assert!(missing.is_empty(), "{}", missing.join("\n"));
+    }
+
     #[test]
     fn public_builtin_registry_hides_internal_helpers() {
         let public = all_builtin_specs()
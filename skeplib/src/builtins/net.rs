@@ -10,6 +10,8 @@ const OPAQUE_PARAM_SENTINEL: &[TypeInfo] = &[TypeInfo::Unknown];
 const OPAQUE_AND_STRING_PARAM: &[TypeInfo] = &[TypeInfo::Unknown, TypeInfo::String];
 const OPAQUE_AND_BYTES_PARAM: &[TypeInfo] = &[TypeInfo::Unknown, TypeInfo::Bytes];
 const OPAQUE_AND_INT_PARAM: &[TypeInfo] = &[TypeInfo::Unknown, TypeInfo::Int];
+const OPAQUE_STRING_AND_BYTES_PARAM: &[TypeInfo] =
+    &[TypeInfo::Unknown, TypeInfo::String, TypeInfo::Bytes];
 
 pub(super) const SIGS: &[BuiltinSig] = &[
     BuiltinSig {
@@ -152,4 +154,62 @@ pub(super) const SIGS: &[BuiltinSig] = &[
         ret: TypeInfo::Void,
         kind: BuiltinKind::FixedArity,
     },
+    BuiltinSig {
+        package: "net",
+        name: "bindUdp",
+        params: STRING_PARAM,
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "net",
+        name: "udpConnect",
+        params: OPAQUE_AND_STRING_PARAM,
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "net",
+        name: "send",
+        params: OPAQUE_AND_BYTES_PARAM,
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "net",
+        name: "sendTo",
+        params: OPAQUE_STRING_AND_BYTES_PARAM,
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "net",
+        name: "recv",
+        params: OPAQUE_PARAM_SENTINEL,
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::FixedArity,
+    },
+    // Lowering appends the id of the `(Bytes, String)` tuple struct, so the IR
+    // call takes one argument more than listed here.
+    BuiltinSig {
+        package: "net",
+        name: "recvFrom",
+        params: OPAQUE_AND_INT_PARAM,
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
+    BuiltinSig {
+        package: "net",
+        name: "udpLocalAddr",
+        params: OPAQUE_PARAM_SENTINEL,
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "net",
+        name: "closeUdp",
+        params: OPAQUE_PARAM_SENTINEL,
+        ret: TypeInfo::Void,
+        kind: BuiltinKind::FixedArity,
+    },
 ];
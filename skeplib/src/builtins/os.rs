@@ -18,6 +18,22 @@ fn string_and_vec_string_params() -> &'static [TypeInfo] {
     ]))
 }
 
+fn exec_capture_params() -> &'static [TypeInfo] {
+    Box::leak(Box::new([
+        TypeInfo::String,
+        TypeInfo::Vec {
+            elem: Box::new(TypeInfo::String),
+        },
+        map_string(),
+    ]))
+}
+
+fn map_string() -> TypeInfo {
+    TypeInfo::Map {
+        value: Box::new(TypeInfo::String),
+    }
+}
+
 fn result_int_string() -> TypeInfo {
     TypeInfo::Result {
         ok: Box::new(TypeInfo::Int),
@@ -32,8 +48,16 @@ fn result_string_string() -> TypeInfo {
     }
 }
 
+fn result_map_string() -> TypeInfo {
+    TypeInfo::Result {
+        ok: Box::new(map_string()),
+        err: Box::new(TypeInfo::String),
+    }
+}
+
 pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
     let string_and_vec_string = string_and_vec_string_params();
+    let exec_capture = exec_capture_params();
     vec![
         BuiltinSig {
             package: "os",
@@ -112,5 +136,12 @@ pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
             ret: result_string_string(),
             kind: BuiltinKind::FixedArity,
         },
+        BuiltinSig {
+            package: "os",
+            name: "execCapture",
+            params: exec_capture,
+            ret: result_map_string(),
+            kind: BuiltinKind::FixedArity,
+        },
     ]
 });
@@ -5,6 +5,8 @@ use super::{BuiltinKind, BuiltinSig};
 const RANDOM_SEED_PARAMS: &[TypeInfo] = &[TypeInfo::Int];
 const RANDOM_INT_PARAMS: &[TypeInfo] = &[TypeInfo::Int, TypeInfo::Int];
 const RANDOM_NO_PARAMS: &[TypeInfo] = &[];
+const RANDOM_BOOL_PARAMS: &[TypeInfo] = &[TypeInfo::Float];
+const RANDOM_GAUSSIAN_PARAMS: &[TypeInfo] = &[TypeInfo::Float, TypeInfo::Float];
 
 pub(super) const SIGS: &[BuiltinSig] = &[
     BuiltinSig {
@@ -28,4 +30,32 @@ pub(super) const SIGS: &[BuiltinSig] = &[
         ret: TypeInfo::Float,
         kind: BuiltinKind::FixedArity,
     },
+    BuiltinSig {
+        package: "random",
+        name: "bool",
+        params: RANDOM_BOOL_PARAMS,
+        ret: TypeInfo::Bool,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "random",
+        name: "gaussian",
+        params: RANDOM_GAUSSIAN_PARAMS,
+        ret: TypeInfo::Float,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "random",
+        name: "choice",
+        params: &[],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
+    BuiltinSig {
+        package: "random",
+        name: "shuffle",
+        params: &[],
+        ret: TypeInfo::Unknown,
+        kind: BuiltinKind::ArrayOps,
+    },
 ];
@@ -0,0 +1,48 @@
+use std::sync::LazyLock;
+
+use crate::types::TypeInfo;
+
+use super::{BuiltinKind, BuiltinSig};
+
+/// `Result[T, String]`; the decoded type `T` comes from the annotated `let`.
+fn decoded_result() -> TypeInfo {
+    TypeInfo::Result {
+        ok: Box::new(TypeInfo::Unknown),
+        err: Box::new(TypeInfo::String),
+    }
+}
+
+// Lowering appends a schema of the value's static type to every call, so the
+// IR calls take one argument more than listed here.
+pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
+    vec![
+        BuiltinSig {
+            package: "serde",
+            name: "toJson",
+            params: &[],
+            ret: TypeInfo::String,
+            kind: BuiltinKind::ArrayOps,
+        },
+        BuiltinSig {
+            package: "serde",
+            name: "fromJson",
+            params: &[TypeInfo::String],
+            ret: decoded_result(),
+            kind: BuiltinKind::ArrayOps,
+        },
+        BuiltinSig {
+            package: "serde",
+            name: "encode",
+            params: &[],
+            ret: TypeInfo::Bytes,
+            kind: BuiltinKind::ArrayOps,
+        },
+        BuiltinSig {
+            package: "serde",
+            name: "decode",
+            params: &[TypeInfo::Bytes],
+            ret: decoded_result(),
+            kind: BuiltinKind::ArrayOps,
+        },
+    ]
+});
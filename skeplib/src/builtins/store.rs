@@ -0,0 +1,73 @@
+use std::sync::LazyLock;
+
+use crate::types::TypeInfo;
+
+use super::{BuiltinKind, BuiltinSig};
+
+const STRING_PARAM: &[TypeInfo] = &[TypeInfo::String];
+const OPAQUE_PARAM_SENTINEL: &[TypeInfo] = &[TypeInfo::Unknown];
+const OPAQUE_AND_STRING_PARAM: &[TypeInfo] = &[TypeInfo::Unknown, TypeInfo::String];
+const OPAQUE_AND_TWO_STRINGS_PARAM: &[TypeInfo] =
+    &[TypeInfo::Unknown, TypeInfo::String, TypeInfo::String];
+
+fn store() -> TypeInfo {
+    TypeInfo::Opaque("store.Store".to_string())
+}
+
+fn or_error(ok: TypeInfo) -> TypeInfo {
+    TypeInfo::Result {
+        ok: Box::new(ok),
+        err: Box::new(TypeInfo::String),
+    }
+}
+
+pub(super) static SIGS: LazyLock<Vec<BuiltinSig>> = LazyLock::new(|| {
+    vec![
+        BuiltinSig {
+            package: "store",
+            name: "open",
+            params: STRING_PARAM,
+            ret: or_error(store()),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "store",
+            name: "get",
+            params: OPAQUE_AND_STRING_PARAM,
+            ret: or_error(TypeInfo::Option {
+                value: Box::new(TypeInfo::String),
+            }),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "store",
+            name: "set",
+            params: OPAQUE_AND_TWO_STRINGS_PARAM,
+            ret: or_error(TypeInfo::Void),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "store",
+            name: "delete",
+            params: OPAQUE_AND_STRING_PARAM,
+            ret: or_error(TypeInfo::Bool),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "store",
+            name: "keys",
+            params: OPAQUE_PARAM_SENTINEL,
+            ret: or_error(TypeInfo::Vec {
+                elem: Box::new(TypeInfo::String),
+            }),
+            kind: BuiltinKind::FixedArity,
+        },
+        BuiltinSig {
+            package: "store",
+            name: "close",
+            params: OPAQUE_PARAM_SENTINEL,
+            ret: TypeInfo::Void,
+            kind: BuiltinKind::FixedArity,
+        },
+    ]
+});
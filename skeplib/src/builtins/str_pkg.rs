@@ -98,4 +98,19 @@ pub(super) const SIGS: &[BuiltinSig] = &[
         ret: TypeInfo::String,
         kind: BuiltinKind::FixedArity,
     },
+    // Strict helpers behind `s[i]` and `s[a..b]`; not callable from source.
+    BuiltinSig {
+        package: "str",
+        name: "__index",
+        params: &[TypeInfo::String, TypeInfo::Int],
+        ret: TypeInfo::String,
+        kind: BuiltinKind::FixedArity,
+    },
+    BuiltinSig {
+        package: "str",
+        name: "__slice",
+        params: STR_SLICE_PARAMS,
+        ret: TypeInfo::String,
+        kind: BuiltinKind::FixedArity,
+    },
 ];
@@ -101,6 +101,30 @@ pub fn emit_compare(
                 }
             }
         }
+        crate::ir::IrType::Array { .. } | crate::ir::IrType::Named(_) => {
+            let (runtime_fn, ordering_error) =
+                if matches!(compare_ty, crate::ir::IrType::Array { .. }) {
+                    (
+                        "skp_rt_array_eq",
+                        "array ordering comparisons are not implemented in LLVM lowering",
+                    )
+                } else {
+                    (
+                        "skp_rt_struct_eq",
+                        "struct ordering comparisons are not implemented in LLVM lowering",
+                    )
+                };
+            let eq = format!("%v{counter}");
+            *counter += 1;
+            lines.push(format!(
+                "  {eq} = call i1 @{runtime_fn}(ptr {left}, ptr {right})"
+            ));
+            match op {
+                CmpOp::Eq => lines.push(format!("  {dest} = xor i1 {eq}, false")),
+                CmpOp::Ne => lines.push(format!("  {dest} = xor i1 {eq}, true")),
+                _ => return Err(CodegenError::Unsupported(ordering_error)),
+            }
+        }
         crate::ir::IrType::Float => {
             let pred = match op {
                 CmpOp::Eq => "oeq",
@@ -159,6 +183,7 @@ fn infer_operand_type(
         Operand::Const(ConstValue::Int(_)) => Some(crate::ir::IrType::Int),
         Operand::Const(ConstValue::Float(_)) => Some(crate::ir::IrType::Float),
         Operand::Const(ConstValue::Bool(_)) => Some(crate::ir::IrType::Bool),
+        Operand::Const(ConstValue::Char(_)) => Some(crate::ir::IrType::Char),
         Operand::Const(ConstValue::String(_)) => Some(crate::ir::IrType::String),
         Operand::Temp(id) => func
             .temps
@@ -29,6 +29,9 @@ pub fn emit_scalar_instr(
                     let int = if *v { 1 } else { 0 };
                     lines.push(format!("  {dest} = add {} 0, {int}", llvm_ty(ty)?));
                 }
+                ConstValue::Char(v) => {
+                    lines.push(format!("  {dest} = add {} 0, {}", llvm_ty(ty)?, *v as u32))
+                }
                 ConstValue::String(_) => {
                     let value = operand_load(
                         names,
@@ -81,7 +84,7 @@ pub fn emit_scalar_instr(
             let value = operand_load(names, operand, func, lines, counter, ty, string_literals)?;
             match (op, ty) {
                 (UnaryOp::Neg, crate::ir::IrType::Int) => {
-                    lines.push(format!("  {dest} = sub i64 0, {value}"));
+                    emit_checked_int_arith("ssub", dest, "0", &value, lines, counter);
                 }
                 (UnaryOp::Neg, crate::ir::IrType::Float) => {
                     lines.push(format!("  {dest} = fneg double {value}"));
@@ -110,6 +113,18 @@ pub fn emit_scalar_instr(
             let dest = names.temp(*dst)?;
             let left = operand_load(names, left, func, lines, counter, ty, string_literals)?;
             let right = operand_load(names, right, func, lines, counter, ty, string_literals)?;
+            if matches!(ty, crate::ir::IrType::Int)
+                && matches!(op, BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul)
+            {
+                let intrinsic = match op {
+                    BinaryOp::Add => "sadd",
+                    BinaryOp::Sub => "ssub",
+                    BinaryOp::Mul => "smul",
+                    _ => unreachable!(),
+                };
+                emit_checked_int_arith(intrinsic, dest, &left, &right, lines, counter);
+                return Ok(true);
+            }
             if matches!(ty, crate::ir::IrType::Int) && matches!(op, BinaryOp::Div | BinaryOp::Mod) {
                 let zero_check = format!("%v{counter}");
                 *counter += 1;
@@ -117,11 +132,6 @@ pub fn emit_scalar_instr(
                 *counter += 1;
                 let cont_label = format!("div_cont_{counter}");
                 *counter += 1;
-                let opname = match op {
-                    BinaryOp::Div => "sdiv",
-                    BinaryOp::Mod => "srem",
-                    _ => unreachable!(),
-                };
                 lines.push(format!("  {zero_check} = icmp eq i64 {right}, 0"));
                 lines.push(format!(
                     "  br i1 {zero_check}, label %{trap_label}, label %{cont_label}"
@@ -130,7 +140,39 @@ pub fn emit_scalar_instr(
                 lines.push("  call void @skp_rt_raise_division_by_zero()".into());
                 lines.push("  unreachable".into());
                 lines.push(format!("{cont_label}:"));
-                lines.push(format!("  {dest} = {opname} i64 {left}, {right}"));
+                let neg_one = format!("%v{counter}");
+                *counter += 1;
+                lines.push(format!("  {neg_one} = icmp eq i64 {right}, -1"));
+                if matches!(op, BinaryOp::Div) {
+                    // `i64::MIN / -1` is the only overflowing quotient.
+                    let is_min = format!("%v{counter}");
+                    *counter += 1;
+                    let overflow = format!("%v{counter}");
+                    *counter += 1;
+                    let overflow_label = format!("div_overflow_{counter}");
+                    *counter += 1;
+                    let ok_label = format!("div_ok_{counter}");
+                    *counter += 1;
+                    lines.push(format!("  {is_min} = icmp eq i64 {left}, {}", i64::MIN));
+                    lines.push(format!("  {overflow} = and i1 {is_min}, {neg_one}"));
+                    lines.push(format!(
+                        "  br i1 {overflow}, label %{overflow_label}, label %{ok_label}"
+                    ));
+                    lines.push(format!("{overflow_label}:"));
+                    lines.push("  call void @skp_rt_raise_integer_overflow()".into());
+                    lines.push("  unreachable".into());
+                    lines.push(format!("{ok_label}:"));
+                    lines.push(format!("  {dest} = sdiv i64 {left}, {right}"));
+                } else {
+                    // `x % -1` is always 0; dividing by 1 instead keeps `i64::MIN % -1`
+                    // from trapping in `srem`.
+                    let divisor = format!("%v{counter}");
+                    *counter += 1;
+                    lines.push(format!(
+                        "  {divisor} = select i1 {neg_one}, i64 1, i64 {right}"
+                    ));
+                    lines.push(format!("  {dest} = srem i64 {left}, {divisor}"));
+                }
                 return Ok(true);
             }
             if matches!(ty, crate::ir::IrType::Int) && matches!(op, BinaryOp::Shl | BinaryOp::Shr) {
@@ -211,3 +253,35 @@ pub fn emit_scalar_instr(
         _ => Ok(false),
     }
 }
+
+fn emit_checked_int_arith(
+    intrinsic: &str,
+    dest: &str,
+    left: &str,
+    right: &str,
+    lines: &mut Vec<String>,
+    counter: &mut usize,
+) {
+    let pair = format!("%v{counter}");
+    *counter += 1;
+    let overflow = format!("%v{counter}");
+    *counter += 1;
+    let trap_label = format!("int_overflow_{counter}");
+    *counter += 1;
+    let cont_label = format!("int_cont_{counter}");
+    *counter += 1;
+    lines.push(format!(
+        "  {pair} = call {{ i64, i1 }} @llvm.{intrinsic}.with.overflow.i64(i64 {left}, i64 {right})"
+    ));
+    lines.push(format!(
+        "  {overflow} = extractvalue {{ i64, i1 }} {pair}, 1"
+    ));
+    lines.push(format!(
+        "  br i1 {overflow}, label %{trap_label}, label %{cont_label}"
+    ));
+    lines.push(format!("{trap_label}:"));
+    lines.push("  call void @skp_rt_raise_integer_overflow()".into());
+    lines.push("  unreachable".into());
+    lines.push(format!("{cont_label}:"));
+    lines.push(format!("  {dest} = extractvalue {{ i64, i1 }} {pair}, 0"));
+}
@@ -52,13 +52,20 @@ pub fn emit_globals(
                     "0".into()
                 }
             }
+            Some(Operand::Const(ConstValue::Char(v)))
+                if matches!(global.ty, crate::ir::IrType::Char) =>
+            {
+                (*v as u32).to_string()
+            }
             Some(Operand::Const(ConstValue::Float(v)))
                 if matches!(global.ty, crate::ir::IrType::Float) =>
             {
                 llvm_float_literal(*v)
             }
             Some(_) | None => match global.ty {
-                crate::ir::IrType::Int | crate::ir::IrType::Bool => "0".into(),
+                crate::ir::IrType::Int | crate::ir::IrType::Bool | crate::ir::IrType::Char => {
+                    "0".into()
+                }
                 crate::ir::IrType::Float => "0.0".into(),
                 crate::ir::IrType::String
                 | crate::ir::IrType::Bytes
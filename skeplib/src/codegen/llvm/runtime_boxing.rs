@@ -52,6 +52,7 @@ pub fn emit_boxed_operand(
         IrType::Int => "skp_rt_value_from_int",
         IrType::Float => "skp_rt_value_from_float",
         IrType::Bool => "skp_rt_value_from_bool",
+        IrType::Char => "skp_rt_value_from_char",
         IrType::String => "skp_rt_value_from_string",
         IrType::Bytes => "skp_rt_value_from_bytes",
         IrType::Option { .. } => "skp_rt_value_from_option",
@@ -93,6 +94,9 @@ pub fn emit_unbox_value(
         IrType::Bool => lines.push(format!(
             "  {dest} = call i1 @skp_rt_value_to_bool(ptr {raw})"
         )),
+        IrType::Char => lines.push(format!(
+            "  {dest} = call i32 @skp_rt_value_to_char(ptr {raw})"
+        )),
         IrType::String => lines.push(format!(
             "  {dest} = call ptr @skp_rt_value_to_string(ptr {raw})"
         )),
@@ -152,6 +156,7 @@ pub fn infer_operand_type(func: &IrFunction, operand: &crate::ir::Operand) -> Ir
         crate::ir::Operand::Const(crate::ir::ConstValue::Int(_)) => IrType::Int,
         crate::ir::Operand::Const(crate::ir::ConstValue::Float(_)) => IrType::Float,
         crate::ir::Operand::Const(crate::ir::ConstValue::Bool(_)) => IrType::Bool,
+        crate::ir::Operand::Const(crate::ir::ConstValue::Char(_)) => IrType::Char,
         crate::ir::Operand::Const(crate::ir::ConstValue::String(_)) => IrType::String,
         crate::ir::Operand::Const(crate::ir::ConstValue::Unit) => IrType::Void,
         crate::ir::Operand::Temp(id) => func
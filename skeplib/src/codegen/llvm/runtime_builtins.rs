@@ -148,6 +148,7 @@ fn emit_const_builtin_result(
             let raw = if *v { 1 } else { 0 };
             lines.push(format!("  {dest} = add i1 0, {raw}"));
         }
+        ConstValue::Char(v) => lines.push(format!("  {dest} = add i32 0, {}", *v as u32)),
         ConstValue::String(value) => {
             let raw = string_literals.get(value).ok_or_else(|| {
                 CodegenError::InvalidIr("missing folded string literal declaration".into())
@@ -7,6 +7,8 @@ const RUNTIME_DECLS: &[(&str, &str)] = &[
     ("skp_rt_bytes_eq", "declare i1 @skp_rt_bytes_eq(ptr, ptr)"),
     ("skp_rt_option_eq", "declare i1 @skp_rt_option_eq(ptr, ptr)"),
     ("skp_rt_result_eq", "declare i1 @skp_rt_result_eq(ptr, ptr)"),
+    ("skp_rt_array_eq", "declare i1 @skp_rt_array_eq(ptr, ptr)"),
+    ("skp_rt_struct_eq", "declare i1 @skp_rt_struct_eq(ptr, ptr)"),
     (
         "skp_rt_builtin_str_len",
         "declare i64 @skp_rt_builtin_str_len(ptr)",
@@ -39,6 +41,26 @@ const RUNTIME_DECLS: &[(&str, &str)] = &[
         "skp_rt_raise_division_by_zero",
         "declare void @skp_rt_raise_division_by_zero()",
     ),
+    (
+        "skp_rt_raise_integer_overflow",
+        "declare void @skp_rt_raise_integer_overflow()",
+    ),
+    (
+        "skp_rt_raise_unreachable",
+        "declare void @skp_rt_raise_unreachable()",
+    ),
+    (
+        "llvm.sadd.with.overflow.i64",
+        "declare { i64, i1 } @llvm.sadd.with.overflow.i64(i64, i64)",
+    ),
+    (
+        "llvm.ssub.with.overflow.i64",
+        "declare { i64, i1 } @llvm.ssub.with.overflow.i64(i64, i64)",
+    ),
+    (
+        "llvm.smul.with.overflow.i64",
+        "declare { i64, i1 } @llvm.smul.with.overflow.i64(i64, i64)",
+    ),
     (
         "skp_rt_raise_negative_shift_count",
         "declare void @skp_rt_raise_negative_shift_count()",
@@ -51,6 +73,10 @@ const RUNTIME_DECLS: &[(&str, &str)] = &[
         "skp_rt_value_from_bool",
         "declare ptr @skp_rt_value_from_bool(i1)",
     ),
+    (
+        "skp_rt_value_from_char",
+        "declare ptr @skp_rt_value_from_char(i32)",
+    ),
     (
         "skp_rt_value_from_float",
         "declare ptr @skp_rt_value_from_float(double)",
@@ -108,6 +134,10 @@ const RUNTIME_DECLS: &[(&str, &str)] = &[
         "skp_rt_value_to_bool",
         "declare i1 @skp_rt_value_to_bool(ptr)",
     ),
+    (
+        "skp_rt_value_to_char",
+        "declare i32 @skp_rt_value_to_char(ptr)",
+    ),
     (
         "skp_rt_value_to_float",
         "declare double @skp_rt_value_to_float(ptr)",
@@ -215,6 +245,7 @@ mod tests {
         assert!(names.contains(&"skp_rt_value_free"));
         assert!(names.contains(&"skp_rt_abort_if_error"));
         assert!(names.contains(&"skp_rt_raise_division_by_zero"));
+        assert!(names.contains(&"skp_rt_raise_integer_overflow"));
         assert!(names.contains(&"skp_rt_raise_negative_shift_count"));
     }
 }
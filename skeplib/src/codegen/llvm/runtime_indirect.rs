@@ -157,6 +157,9 @@ fn emit_indirect_wrapper(func: &IrFunction) -> Result<Vec<String>, CodegenError>
             IrType::Bool => lines.push(format!(
                 "  %arg{index} = call i1 @skp_rt_value_to_bool(ptr %argraw{index})"
             )),
+            IrType::Char => lines.push(format!(
+                "  %arg{index} = call i32 @skp_rt_value_to_char(ptr %argraw{index})"
+            )),
             IrType::String => lines.push(format!(
                 "  %arg{index} = call ptr @skp_rt_value_to_string(ptr %argraw{index})"
             )),
@@ -216,6 +219,7 @@ fn emit_indirect_wrapper(func: &IrFunction) -> Result<Vec<String>, CodegenError>
             IrType::Int => "skp_rt_value_from_int",
             IrType::Float => "skp_rt_value_from_float",
             IrType::Bool => "skp_rt_value_from_bool",
+            IrType::Char => "skp_rt_value_from_char",
             IrType::String => "skp_rt_value_from_string",
             IrType::Bytes => "skp_rt_value_from_bytes",
             IrType::Option { .. } => "skp_rt_value_from_option",
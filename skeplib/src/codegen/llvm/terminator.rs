@@ -49,9 +49,10 @@ pub fn emit_terminator(
         }
         Terminator::Return(None) => lines.push("  ret void".into()),
         Terminator::Panic { .. } => {
-            return Err(CodegenError::InvalidIr(
-                "LLVM backend does not lower panic terminators".into(),
-            ));
+            // Lowering only emits panic terminators after calls that never
+            // return, so this path aborts without formatting the message.
+            lines.push("  call void @skp_rt_raise_unreachable()".into());
+            lines.push("  unreachable".into());
         }
         Terminator::Unreachable => {
             return Err(CodegenError::InvalidIr(
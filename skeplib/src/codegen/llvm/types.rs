@@ -6,6 +6,7 @@ pub fn llvm_ty(ty: &IrType) -> Result<&'static str, CodegenError> {
         IrType::Int => Ok("i64"),
         IrType::Float => Ok("double"),
         IrType::Bool => Ok("i1"),
+        IrType::Char => Ok("i32"),
         IrType::String => Ok("ptr"),
         IrType::Bytes => Ok("ptr"),
         IrType::Option { .. } => Ok("ptr"),
@@ -56,6 +56,7 @@ pub fn operand_value(
         Operand::Const(ConstValue::Int(v)) => Ok(v.to_string()),
         Operand::Const(ConstValue::Float(v)) => Ok(llvm_float_literal(*v)),
         Operand::Const(ConstValue::Bool(v)) => Ok(if *v { "1".into() } else { "0".into() }),
+        Operand::Const(ConstValue::Char(v)) => Ok((*v as u32).to_string()),
         Operand::Temp(id) => Ok(names.temp(*id)?.to_string()),
         Operand::Local(id) => Ok(format!("%local{}", id.0)),
         Operand::Global(id) => Ok(format!("@g{}", id.0)),
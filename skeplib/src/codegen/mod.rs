@@ -469,15 +469,18 @@ fn compile_llvm_ir_to_executable_with_tool(
 struct CodegenTimings {
     label: &'static str,
     enabled: bool,
+    json: bool,
     started: Instant,
     phases: Vec<(&'static str, Duration)>,
 }
 
 impl CodegenTimings {
     fn new(label: &'static str) -> Self {
+        let setting = std::env::var_os("SKEPA_CODEGEN_TIMINGS");
         Self {
             label,
-            enabled: std::env::var_os("SKEPA_CODEGEN_TIMINGS").is_some(),
+            enabled: setting.is_some(),
+            json: setting.is_some_and(|value| value == "json"),
             started: Instant::now(),
             phases: Vec::new(),
         }
@@ -493,16 +496,23 @@ impl CodegenTimings {
         if !self.enabled {
             return;
         }
+        let total = self.started.elapsed().as_micros();
+        if self.json {
+            let phases = self
+                .phases
+                .iter()
+                .map(|(phase, elapsed)| (*phase, elapsed.as_micros()))
+                .collect::<Vec<_>>();
+            println!("{}", format_timing_json(self.label, &phases, total));
+            return;
+        }
         for (phase, elapsed) in &self.phases {
             println!(
                 "{}",
                 format_timing_line(self.label, phase, elapsed.as_micros())
             );
         }
-        println!(
-            "{}",
-            format_timing_line(self.label, "total", self.started.elapsed().as_micros())
-        );
+        println!("{}", format_timing_line(self.label, "total", total));
     }
 }
 
@@ -510,6 +520,16 @@ fn format_timing_line(label: &str, phase: &str, micros: u128) -> String {
     format!("timing[codegen:{label}] {phase}={micros}us")
 }
 
+/// Labels and phase names are fixed identifiers, so they need no JSON escaping.
+fn format_timing_json(label: &str, phases: &[(&str, u128)], total_micros: u128) -> String {
+    let phases = phases
+        .iter()
+        .map(|(phase, micros)| format!(r#"{{"phase":"{phase}","us":{micros}}}"#))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(r#"{{"timings":"codegen:{label}","phases":[{phases}],"total_us":{total_micros}}}"#)
+}
+
 pub fn runtime_link_inputs() -> Result<RuntimeLinkInputs, CodegenError> {
     let artifacts = runtime_link_artifacts()?;
     let mut cache_inputs = vec![runtime_file_details(&artifacts.link_lib)?];
@@ -763,7 +783,8 @@ fn runtime_native_libraries() -> Vec<&'static str> {
     } else if cfg!(target_os = "macos") {
         vec!["-framework", "Security", "-framework", "CoreFoundation"]
     } else {
-        Vec::new()
+        // The runtime's float helpers need libm, which is not part of libc here.
+        vec!["-lm"]
     }
 }
 
@@ -772,8 +793,9 @@ mod tests {
     use super::{
         CodegenError, RuntimeLinkArtifacts, compile_program_llvm_ir_section,
         compile_program_to_bitcode_file_with_tool, compile_program_to_llvm_ir,
-        compile_program_to_object_file_with_tools, format_timing_line, link_args_for_executable,
-        link_command_for_executable, link_object_file_to_executable_with_tool, run_tool,
+        compile_program_to_object_file_with_tools, format_timing_json, format_timing_line,
+        link_args_for_executable, link_command_for_executable,
+        link_object_file_to_executable_with_tool, run_tool,
         runtime_link_artifacts_from_search_dirs, sync_runtime_sidecars,
     };
     use crate::codegen::llvm::LlvmEmitSection;
@@ -1127,6 +1149,18 @@ fn main() -> Int {
         );
     }
 
+    #[test]
+    fn codegen_timing_json_format_is_stable() {
+        assert_eq!(
+            format_timing_json(
+                "object",
+                &[("llvm_ir_emit", 10), ("clang_codegen", 1234)],
+                1300
+            ),
+            r#"{"timings":"codegen:object","phases":[{"phase":"llvm_ir_emit","us":10},{"phase":"clang_codegen","us":1234}],"total_us":1300}"#
+        );
+    }
+
     #[test]
     fn missing_opt_reports_clean_tool_error() {
         let program = simple_program();
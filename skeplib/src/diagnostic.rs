@@ -1,13 +1,15 @@
 use core::fmt;
 use std::path::PathBuf;
 
+use serde::Serialize;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DiagnosticLevel {
     Error,
     Warning,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
@@ -41,37 +43,102 @@ impl Span {
     }
 }
 
+/// A secondary span shown alongside a diagnostic, e.g. "first declared here".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Diagnostic {
     pub level: DiagnosticLevel,
+    /// Registered code from [`crate::error_codes::ERROR_CODES`], set where the
+    /// diagnostic is created; `None` for diagnostics reported under their phase's
+    /// generic code.
+    pub code: Option<&'static str>,
     pub message: String,
     pub span: Span,
+    /// The span only locates the enclosing declaration, not the offending
+    /// source, so renderers report the location without drawing a caret.
+    pub approximate: bool,
     pub path: Option<PathBuf>,
+    pub labels: Vec<Label>,
+    /// What the message's `did you mean` hint proposes, such as a corrected name.
+    pub suggestion: Option<String>,
 }
 
 impl Diagnostic {
     pub fn error(message: impl Into<String>, span: Span) -> Self {
         Self {
             level: DiagnosticLevel::Error,
+            code: None,
             message: message.into(),
             span,
+            approximate: false,
             path: None,
+            labels: Vec::new(),
+            suggestion: None,
         }
     }
 
     pub fn warning(message: impl Into<String>, span: Span) -> Self {
         Self {
             level: DiagnosticLevel::Warning,
+            code: None,
             message: message.into(),
             span,
+            approximate: false,
             path: None,
+            labels: Vec::new(),
+            suggestion: None,
         }
     }
 
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        debug_assert!(
+            crate::error_codes::lookup(code).is_some(),
+            "unregistered diagnostic code {code}"
+        );
+        self.code = Some(code);
+        self
+    }
+
+    /// Marks the span as standing in for an unknown location; see
+    /// [`Diagnostic::approximate`].
+    pub fn approximate(mut self) -> Self {
+        self.approximate = true;
+        self
+    }
+
     pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
         self.path = Some(path.into());
         self
     }
+
+    pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    /// Renders the source lines under this diagnostic's span and labels; see
+    /// [`render_snippet`]. An approximate span is left out, so only labels show.
+    pub fn render_snippet(&self, source: &str, color: bool) -> String {
+        let span = if self.approximate {
+            Span::default()
+        } else {
+            self.span
+        };
+        render_snippet(source, span, &self.labels, color)
+    }
 }
 
 impl fmt::Display for Diagnostic {
@@ -123,8 +190,17 @@ impl DiagnosticBag {
         self.push(Diagnostic::error(message, span));
     }
 
+    pub fn error_with_code(&mut self, code: &'static str, message: impl Into<String>, span: Span) {
+        self.push(Diagnostic::error(message, span).with_code(code));
+    }
+
+    /// Reports an unexpected token under `E-PARSE-004`.
     pub fn error_expected_found(&mut self, expected_message: &str, found_label: &str, span: Span) {
-        self.error(format_expected_found(expected_message, found_label), span);
+        self.error_with_code(
+            "E-PARSE-004",
+            format_expected_found(expected_message, found_label),
+            span,
+        );
     }
 
     pub fn warning(&mut self, message: impl Into<String>, span: Span) {
@@ -139,6 +215,11 @@ impl DiagnosticBag {
         self.items.is_empty()
     }
 
+    /// Whether any diagnostic is an error; warnings alone do not fail a phase.
+    pub fn has_errors(&self) -> bool {
+        self.items.iter().any(|d| d.level == DiagnosticLevel::Error)
+    }
+
     pub fn as_slice(&self) -> &[Diagnostic] {
         &self.items
     }
@@ -148,6 +229,123 @@ impl DiagnosticBag {
     }
 }
 
+/// Receives diagnostics as they are produced, e.g. to stream them to an editor
+/// instead of waiting for a whole phase. [`DiagnosticBag`] is the collecting
+/// implementation; closures taking a [`Diagnostic`] are sinks too.
+pub trait DiagnosticSink {
+    fn report(&mut self, diagnostic: Diagnostic);
+}
+
+impl DiagnosticSink for DiagnosticBag {
+    fn report(&mut self, diagnostic: Diagnostic) {
+        self.push(diagnostic);
+    }
+}
+
+impl<F: FnMut(Diagnostic)> DiagnosticSink for F {
+    fn report(&mut self, diagnostic: Diagnostic) {
+        self(diagnostic)
+    }
+}
+
 pub fn format_expected_found(expected_message: &str, found_label: &str) -> String {
     format!("{expected_message}; found {found_label}")
 }
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_PRIMARY: &str = "\x1b[1;31m";
+const ANSI_SECONDARY: &str = "\x1b[1;34m";
+
+/// Renders the source line under `span` with a `^^^` underline, followed by
+/// each label's line underlined with `---` and its message:
+///
+/// ```text
+///   |
+/// 3 | fn helper() -> Int { return 2; }
+///   | ^^^^^^^^^
+///   |
+/// 1 | fn helper() -> Int { return 1; }
+///   | --------- first declared here
+/// ```
+///
+/// Spans without a line are skipped, and an empty string is returned when
+/// nothing can be shown. Underlines cover `span.end - span.start` characters
+/// from `span.col`, clamped to the line and at least one wide.
+pub fn render_snippet(source: &str, span: Span, labels: &[Label], color: bool) -> String {
+    let lines = source.lines().collect::<Vec<_>>();
+    let mut parts = Vec::new();
+    if let Some(part) = snippet_part(&lines, span, '^', "", color) {
+        parts.push(part);
+    }
+    for label in labels {
+        if let Some(part) = snippet_part(&lines, label.span, '-', &label.message, color) {
+            parts.push(part);
+        }
+    }
+    if parts.is_empty() {
+        return String::new();
+    }
+    let width = parts
+        .iter()
+        .map(|(line_no, ..)| line_no.to_string().len())
+        .max()
+        .unwrap_or(1);
+    let (gutter_on, gutter_off) = if color {
+        (ANSI_SECONDARY, ANSI_RESET)
+    } else {
+        ("", "")
+    };
+    let mut out = String::new();
+    for (line_no, text, marker) in parts {
+        out.push_str(&format!("{:width$} {gutter_on}|{gutter_off}\n", ""));
+        out.push_str(&format!(
+            "{gutter_on}{line_no:>width$} |{gutter_off} {text}\n"
+        ));
+        out.push_str(&format!(
+            "{:width$} {gutter_on}|{gutter_off} {marker}\n",
+            ""
+        ));
+    }
+    out
+}
+
+fn snippet_part(
+    lines: &[&str],
+    span: Span,
+    mark: char,
+    message: &str,
+    color: bool,
+) -> Option<(usize, String, String)> {
+    if span.line == 0 {
+        return None;
+    }
+    let text = lines.get(span.line - 1)?.trim_end_matches('\r');
+    let len = text.chars().count();
+    let col = span.col.max(1).min(len + 1);
+    let width = span
+        .end
+        .saturating_sub(span.start)
+        .min(len.saturating_sub(col - 1))
+        .max(1);
+    let indent = text
+        .chars()
+        .take(col - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+    let mut underline = mark.to_string().repeat(width);
+    if !message.is_empty() {
+        underline.push(' ');
+        underline.push_str(message);
+    }
+    let marker = if color {
+        let style = if mark == '^' {
+            ANSI_PRIMARY
+        } else {
+            ANSI_SECONDARY
+        };
+        format!("{indent}{style}{underline}{ANSI_RESET}")
+    } else {
+        format!("{indent}{underline}")
+    };
+    Some((span.line, text.to_string(), marker))
+}
@@ -0,0 +1,299 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::ast::{ExportDecl, FnDecl, MethodDecl, Param, Program, TypeName};
+use crate::resolver::{ModuleGraph, ModuleId, ResolveError, SymbolKind, build_export_maps};
+
+/// Output flavour for generated documentation pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+impl DocFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// Public API of one module: everything it exports, with signatures and the
+/// `///` comments written above each declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleDoc {
+    pub id: ModuleId,
+    pub path: PathBuf,
+    pub items: Vec<DocItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocItem {
+    pub kind: DocItemKind,
+    /// Name the item is exported under.
+    pub name: String,
+    pub signature: String,
+    pub doc: Option<String>,
+    /// Methods from `impl` blocks in the defining module; structs only.
+    pub methods: Vec<DocItem>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocItemKind {
+    Struct,
+    TypeAlias,
+    Fn,
+    Global,
+    Method,
+}
+
+/// Collects the exported API of every module in `graph`, sorted by module id.
+///
+/// Re-exported symbols are documented under the exporting module using the
+/// declaration (and doc comment) from the module that defines them. Modules
+/// with no exports are still listed so every page in the output tree exists.
+pub fn collect_docs(graph: &ModuleGraph) -> Result<Vec<ModuleDoc>, Vec<ResolveError>> {
+    let export_maps = build_export_maps(graph)?;
+    let mut ids = graph.modules.keys().cloned().collect::<Vec<_>>();
+    ids.sort();
+    let mut out = Vec::new();
+    for id in ids {
+        let unit = &graph.modules[&id];
+        let mut items = Vec::new();
+        for (name, sym) in export_maps.get(&id).into_iter().flatten() {
+            let Some(origin) = graph.modules.get(&sym.module_id) else {
+                continue;
+            };
+            let program = &origin.program;
+            let item = match sym.kind {
+                SymbolKind::Struct => struct_item(program, name, &sym.local_name),
+                SymbolKind::Fn => program
+                    .functions
+                    .iter()
+                    .find(|f| f.name == sym.local_name)
+                    .map(|f| fn_item(name, f)),
+                SymbolKind::GlobalLet => program
+                    .globals
+                    .iter()
+                    .find(|g| g.name == sym.local_name)
+                    .map(|g| DocItem {
+                        kind: DocItemKind::Global,
+                        name: name.clone(),
+                        signature: match &g.ty {
+                            Some(ty) => format!("let {name}: {}", ty.as_str()),
+                            None => format!("let {name}"),
+                        },
+                        doc: g.doc.clone(),
+                        methods: Vec::new(),
+                    }),
+                SymbolKind::TypeAlias => program
+                    .type_aliases
+                    .iter()
+                    .find(|alias| alias.name == sym.local_name)
+                    .map(|alias| DocItem {
+                        kind: DocItemKind::TypeAlias,
+                        name: name.clone(),
+                        signature: format!("type {name} = {}", alias.target.as_str()),
+                        doc: alias.doc.clone(),
+                        methods: Vec::new(),
+                    }),
+                SymbolKind::Namespace => None,
+            };
+            items.extend(item);
+        }
+        items.sort_by(|a, b| {
+            kind_rank(a.kind)
+                .cmp(&kind_rank(b.kind))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        out.push(ModuleDoc {
+            id: id.clone(),
+            path: unit.path.clone(),
+            items,
+        });
+    }
+    Ok(out)
+}
+
+impl ModuleDoc {
+    pub fn render(&self, format: DocFormat) -> String {
+        match format {
+            DocFormat::Markdown => self.render_markdown(),
+            DocFormat::Html => self.render_html(),
+        }
+    }
+
+    pub fn render_markdown(&self) -> String {
+        let mut out = format!("# Module `{}`\n", self.id);
+        if self.items.is_empty() {
+            out.push_str("\nThis module exports nothing.\n");
+        }
+        for item in &self.items {
+            push_markdown_item(&mut out, item, "##");
+            for method in &item.methods {
+                push_markdown_item(&mut out, method, "###");
+            }
+        }
+        out
+    }
+
+    pub fn render_html(&self) -> String {
+        let title = escape_html(&self.id);
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<h1>Module <code>{title}</code></h1>\n"
+        );
+        if self.items.is_empty() {
+            out.push_str("<p>This module exports nothing.</p>\n");
+        }
+        for item in &self.items {
+            push_html_item(&mut out, item, "h2");
+            for method in &item.methods {
+                push_html_item(&mut out, method, "h3");
+            }
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+fn struct_item(program: &Program, name: &str, local_name: &str) -> Option<DocItem> {
+    let decl = program.structs.iter().find(|s| s.name == local_name)?;
+    let signature = if is_opaque_export(program, local_name) {
+        format!("struct {name}")
+    } else {
+        let fields = decl
+            .fields
+            .iter()
+            .map(|f| format!("{}: {}", f.name, f.ty.as_str()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("struct {name} {{ {fields} }}")
+    };
+    let methods = program
+        .impls
+        .iter()
+        .filter(|imp| imp.target == local_name)
+        .flat_map(|imp| &imp.methods)
+        .map(|m| method_item(name, m))
+        .collect();
+    Some(DocItem {
+        kind: DocItemKind::Struct,
+        name: name.to_string(),
+        signature,
+        doc: decl.doc.clone(),
+        methods,
+    })
+}
+
+fn fn_item(name: &str, f: &FnDecl) -> DocItem {
+    let prefix = if f.is_extern { "extern fn" } else { "fn" };
+    DocItem {
+        kind: DocItemKind::Fn,
+        name: name.to_string(),
+        signature: format!(
+            "{prefix} {name}{}",
+            render_params(&f.params, f.return_type.as_ref())
+        ),
+        doc: f.doc.clone(),
+        methods: Vec::new(),
+    }
+}
+
+fn method_item(owner: &str, m: &MethodDecl) -> DocItem {
+    DocItem {
+        kind: DocItemKind::Method,
+        name: format!("{owner}.{}", m.name),
+        signature: format!(
+            "fn {}{}",
+            m.name,
+            render_params(&m.params, m.return_type.as_ref())
+        ),
+        doc: m.doc.clone(),
+        methods: Vec::new(),
+    }
+}
+
+fn render_params(params: &[Param], ret: Option<&TypeName>) -> String {
+    let params = params
+        .iter()
+        .map(|p| {
+            if p.name == "self" {
+                "self".to_string()
+            } else {
+                format!("{}: {}", p.name, p.ty.as_str())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    match ret {
+        Some(ty) => format!("({params}) -> {}", ty.as_str()),
+        None => format!("({params})"),
+    }
+}
+
+/// Whether the defining module exports `name` as `Name(opaque)`, in which case
+/// its fields are not part of the public API.
+fn is_opaque_export(program: &Program, name: &str) -> bool {
+    let opaque = program
+        .exports
+        .iter()
+        .filter_map(|e| match e {
+            ExportDecl::Local { items } => Some(items),
+            _ => None,
+        })
+        .flatten()
+        .filter(|item| item.opaque)
+        .map(|item| item.name.as_str())
+        .collect::<HashSet<_>>();
+    opaque.contains(name)
+}
+
+fn kind_rank(kind: DocItemKind) -> u8 {
+    match kind {
+        DocItemKind::Struct => 0,
+        DocItemKind::TypeAlias => 1,
+        DocItemKind::Fn => 2,
+        DocItemKind::Global => 3,
+        DocItemKind::Method => 4,
+    }
+}
+
+fn push_markdown_item(out: &mut String, item: &DocItem, heading: &str) {
+    out.push_str(&format!(
+        "\n{heading} `{}`\n\n```skepa\n{}\n```\n",
+        item.name, item.signature
+    ));
+    if let Some(doc) = &item.doc {
+        out.push_str(&format!("\n{doc}\n"));
+    }
+}
+
+fn push_html_item(out: &mut String, item: &DocItem, heading: &str) {
+    out.push_str(&format!(
+        "<{heading} id=\"{id}\"><code>{name}</code></{heading}>\n<pre><code>{sig}</code></pre>\n",
+        id = escape_html(&item.name),
+        name = escape_html(&item.name),
+        sig = escape_html(&item.signature),
+    ));
+    if let Some(doc) = &item.doc {
+        for para in doc.split("\n\n") {
+            out.push_str(&format!("<p>{}</p>\n", escape_html(para.trim())));
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
@@ -0,0 +1,274 @@
+use std::path::PathBuf;
+
+use crate::builtins::CustomBuiltins;
+use crate::codegen;
+use crate::diagnostic::Diagnostic;
+use crate::ir::{self, IrProgram};
+use crate::lint::LintConfig;
+use crate::resolver::{
+    ModuleGraph, ResolveError, resolve_directory_with_builtins, resolve_project_with_builtins,
+};
+use crate::sema::{analyze_project_graph_with_lints, check_entry_main};
+
+/// Compiler phase a diagnostic came from, or the phase that stopped a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Resolve,
+    Sema,
+    Codegen,
+}
+
+/// Artifact a [`Compiler`] run should produce once the frontend succeeds. With no
+/// emit kinds a run only checks the project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emit {
+    Ir,
+    LlvmIr,
+}
+
+/// One diagnostic reported during a run, tagged with the phase that produced it.
+/// Parse errors are resolve errors: the resolver parses every module it loads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriverDiagnostic {
+    Resolve(ResolveError),
+    Sema(Diagnostic),
+    Codegen(String),
+}
+
+impl DriverDiagnostic {
+    pub fn phase(&self) -> Phase {
+        match self {
+            Self::Resolve(_) => Phase::Resolve,
+            Self::Sema(_) => Phase::Sema,
+            Self::Codegen(_) => Phase::Codegen,
+        }
+    }
+}
+
+/// Receives each diagnostic as soon as it is reported: resolve errors once the graph
+/// is built, sema diagnostics module by module. Closures taking `&DriverDiagnostic`
+/// are sinks.
+pub trait DriverSink {
+    fn report(&mut self, diagnostic: &DriverDiagnostic);
+}
+
+impl<F: FnMut(&DriverDiagnostic)> DriverSink for F {
+    fn report(&mut self, diagnostic: &DriverDiagnostic) {
+        self(diagnostic)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Input {
+    Entry(PathBuf),
+    Directory(PathBuf),
+}
+
+/// Runs resolve, sema and lowering over a project the way `skepac` does:
+///
+/// ```no_run
+/// use skeplib::driver::{Compiler, Emit};
+///
+/// let output = Compiler::entry("main.sk").emit(Emit::Ir).run();
+/// if let Some(program) = output.ir {
+///     println!("{} functions", program.functions.len());
+/// }
+/// ```
+pub struct Compiler {
+    input: Input,
+    emit: Vec<Emit>,
+    optimize: bool,
+    entry_fn: String,
+    builtins: CustomBuiltins,
+    lints: LintConfig,
+    sink: Option<Box<dyn DriverSink>>,
+}
+
+/// Structured result of a [`Compiler`] run. `failed` names the first phase that
+/// reported an error; later phases did not run. Artifacts are only set for a run
+/// that reached them.
+#[derive(Debug, Clone, Default)]
+pub struct CompileOutput {
+    pub graph: Option<ModuleGraph>,
+    pub ir: Option<IrProgram>,
+    pub llvm_ir: Option<String>,
+    pub diagnostics: Vec<DriverDiagnostic>,
+    pub failed: Option<Phase>,
+}
+
+impl CompileOutput {
+    pub fn succeeded(&self) -> bool {
+        self.failed.is_none()
+    }
+}
+
+impl Compiler {
+    /// Compiles the project reachable from the entry module at `path`.
+    pub fn entry(path: impl Into<PathBuf>) -> Self {
+        Self::new(Input::Entry(path.into()))
+    }
+
+    /// Checks every `.sk` file under `dir` as one project; see
+    /// [`crate::resolver::resolve_directory`]. Such a project has no entry, so it
+    /// cannot emit IR.
+    pub fn directory(dir: impl Into<PathBuf>) -> Self {
+        Self::new(Input::Directory(dir.into()))
+    }
+
+    fn new(input: Input) -> Self {
+        Self {
+            input,
+            emit: Vec::new(),
+            optimize: true,
+            entry_fn: "main".to_string(),
+            builtins: CustomBuiltins::default(),
+            lints: LintConfig::default(),
+            sink: None,
+        }
+    }
+
+    pub fn emit(mut self, kind: Emit) -> Self {
+        if !self.emit.contains(&kind) {
+            self.emit.push(kind);
+        }
+        self
+    }
+
+    /// Whether emitted IR runs through the optimizer; on by default.
+    pub fn optimize(mut self, optimize: bool) -> Self {
+        self.optimize = optimize;
+        self
+    }
+
+    /// Entry-module function that the emitted program's `main` runs instead of
+    /// `main`; it must take no parameters and return `Int` or `Void`.
+    pub fn entry_function(mut self, name: impl Into<String>) -> Self {
+        self.entry_fn = name.into();
+        self
+    }
+
+    /// Embedder packages that projects may import and call; see [`CustomBuiltins`].
+    pub fn builtins(mut self, builtins: CustomBuiltins) -> Self {
+        self.builtins = builtins;
+        self
+    }
+
+    /// Lint levels for every module; a module's own `#allow`/`#warn`/`#deny`
+    /// directives take precedence. Denied lints fail the sema phase.
+    pub fn lints(mut self, lints: LintConfig) -> Self {
+        self.lints = lints;
+        self
+    }
+
+    pub fn diagnostic_sink(mut self, sink: impl DriverSink + 'static) -> Self {
+        self.sink = Some(Box::new(sink));
+        self
+    }
+
+    pub fn run(mut self) -> CompileOutput {
+        let mut output = CompileOutput::default();
+        let resolved = match &self.input {
+            Input::Entry(path) => resolve_project_with_builtins(path, &self.builtins),
+            Input::Directory(dir) => resolve_directory_with_builtins(dir, &self.builtins),
+        };
+        let graph = match resolved {
+            Ok(graph) => graph,
+            Err(errs) => {
+                self.fail_resolve(&mut output, errs);
+                return output;
+            }
+        };
+
+        let sink = &mut self.sink;
+        let diagnostics = &mut output.diagnostics;
+        let analyzed =
+            analyze_project_graph_with_lints(&graph, &self.builtins, &self.lints, &mut |d| {
+                let diagnostic = DriverDiagnostic::Sema(d);
+                if let Some(sink) = sink {
+                    sink.report(&diagnostic);
+                }
+                diagnostics.push(diagnostic);
+            });
+        let mut entry_main_errors = false;
+        if let (Input::Entry(entry), Ok(_)) = (&self.input, &analyzed) {
+            for d in check_entry_main(&graph, entry).into_vec() {
+                entry_main_errors = true;
+                self.report(&mut output, DriverDiagnostic::Sema(d));
+            }
+        }
+        output.graph = Some(graph);
+        match analyzed {
+            Ok(result) if result.has_errors || entry_main_errors => {
+                output.failed = Some(Phase::Sema);
+                return output;
+            }
+            Ok(_) => {}
+            Err(errs) => {
+                self.fail_resolve(&mut output, errs);
+                return output;
+            }
+        }
+
+        if !self.emit.is_empty() {
+            self.lower(&mut output);
+        }
+        output
+    }
+
+    fn lower(&mut self, output: &mut CompileOutput) {
+        let Input::Entry(entry) = &self.input else {
+            self.fail_codegen(output, "emitting IR needs an entry module".to_string());
+            return;
+        };
+        let Some(graph) = &output.graph else {
+            return;
+        };
+        let lowered = if self.optimize {
+            ir::lowering::compile_project_graph_for_entry_fn(graph, entry, &self.entry_fn)
+        } else {
+            ir::lowering::compile_project_graph_for_entry_fn_unoptimized(
+                graph,
+                entry,
+                &self.entry_fn,
+            )
+        };
+        let program = match lowered {
+            Ok(program) => program,
+            Err(message) => {
+                self.fail_codegen(output, message);
+                return;
+            }
+        };
+        if self.emit.contains(&Emit::LlvmIr) {
+            match codegen::compile_program_to_llvm_ir(&program) {
+                Ok(llvm_ir) => output.llvm_ir = Some(llvm_ir),
+                Err(err) => {
+                    self.fail_codegen(output, err.to_string());
+                    return;
+                }
+            }
+        }
+        if self.emit.contains(&Emit::Ir) {
+            output.ir = Some(program);
+        }
+    }
+
+    fn fail_resolve(&mut self, output: &mut CompileOutput, errs: Vec<ResolveError>) {
+        for err in errs {
+            self.report(output, DriverDiagnostic::Resolve(err));
+        }
+        output.failed = Some(Phase::Resolve);
+    }
+
+    fn fail_codegen(&mut self, output: &mut CompileOutput, message: String) {
+        self.report(output, DriverDiagnostic::Codegen(message));
+        output.failed = Some(Phase::Codegen);
+    }
+
+    fn report(&mut self, output: &mut CompileOutput, diagnostic: DriverDiagnostic) {
+        if let Some(sink) = &mut self.sink {
+            sink.report(&diagnostic);
+        }
+        output.diagnostics.push(diagnostic);
+    }
+}
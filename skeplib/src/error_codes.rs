@@ -0,0 +1,549 @@
+/// A stable diagnostic code with the long-form text shown by `skepac explain`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCode {
+    pub code: &'static str,
+    /// Phase that reports the code: `parse`, `sema`, `resolve`, `codegen`, or `runtime`.
+    pub phase: &'static str,
+    pub title: &'static str,
+    pub explanation: &'static str,
+    pub example: &'static str,
+    pub fix: &'static str,
+}
+
+impl ErrorCode {
+    /// Renders the full explanation as printed by `skepac explain`.
+    pub fn render(&self) -> String {
+        format!(
+            "{} ({}): {}\n\n{}\n\nExample:\n\n{}\n\nFix:\n\n{}\n",
+            self.code,
+            self.phase,
+            self.title,
+            self.explanation,
+            indent(self.example),
+            self.fix
+        )
+    }
+}
+
+/// Every registered code. Parse and sema diagnostics carry theirs in
+/// [`crate::diagnostic::Diagnostic::code`], set where they are reported.
+pub const ERROR_CODES: &[ErrorCode] = &[
+    ErrorCode {
+        code: "E-PARSE-001",
+        phase: "parse",
+        title: "unexpected character",
+        explanation: "The lexer found a character that does not start any token in Skepa source.",
+        example: "fn main() -> Int {\n  return 1 $ 2;\n}",
+        fix: "Remove the character, or put it inside a string or character literal.",
+    },
+    ErrorCode {
+        code: "E-PARSE-002",
+        phase: "parse",
+        title: "unterminated literal or comment",
+        explanation: "A string literal, character literal, or block comment runs to the end of the file without its closing delimiter. Block comments nest, so every `/*` needs its own `*/`.",
+        example: "/* outer /* inner */\nfn main() -> Int { return 0; }",
+        fix: "Add the missing closing `\"`, `'`, or `*/`.",
+    },
+    ErrorCode {
+        code: "E-PARSE-003",
+        phase: "parse",
+        title: "malformed numeric literal",
+        explanation: "A numeric literal uses a digit its base does not allow, has no digits after a `0x`/`0o`/`0b` prefix, or does not fit in a 64-bit `Int`.",
+        example: "let mask = 0b102;",
+        fix: "Use only digits valid for the prefix and keep integer literals within the `Int` range.",
+    },
+    ErrorCode {
+        code: "E-PARSE-004",
+        phase: "parse",
+        title: "unexpected token",
+        explanation: "The parser expected a particular token or construct here, such as a `;` after a statement or a type after `:`. The message names what was expected and what was found.",
+        example: "fn main() -> Int {\n  return 0\n}",
+        fix: "Insert the expected token, or remove the stray one reported after `found`.",
+    },
+    ErrorCode {
+        code: "E-PARSE-005",
+        phase: "parse",
+        title: "invalid escape or character literal",
+        explanation: "String literals accept the escapes `\\n`, `\\t`, `\\r`, `\\\"`, and `\\\\`; character literals accept `\\n`, `\\t`, `\\r`, `\\0`, `\\\'`, and `\\\\`. Any other character after a backslash is rejected, and a character literal must hold exactly one character.",
+        example: "let path = \"C:\\data\";",
+        fix: "Write a literal backslash as `\\\\`, and use a string literal for text longer than one character.",
+    },
+    ErrorCode {
+        code: "E-PARSE-006",
+        phase: "parse",
+        title: "invalid import or export item",
+        explanation: "A `from ... import` clause lists the same symbol or the same alias twice, or an `export { ... }` item uses a modifier other than `(opaque)`.",
+        example: "from utils import add, add;",
+        fix: "Import each name and alias once, and write `Name(opaque)` as the only export modifier.",
+    },
+    ErrorCode {
+        code: "E-PARSE-007",
+        phase: "parse",
+        title: "invalid `self` type",
+        explanation: "A method's `self` parameter may leave out its type or annotate it with the struct the `impl` block is for. Any other annotation is rejected.",
+        example: "struct User { id: Int }\n\nimpl User {\n  fn id(self: Int) -> Int { return 0; }\n}",
+        fix: "Remove the annotation, or write the receiver struct, as in `self: User`.",
+    },
+    ErrorCode {
+        code: "E-PARSE-008",
+        phase: "parse",
+        title: "unknown infix operator",
+        explanation: "A backtick operator must be declared with `opr` in the module, or imported directly with `from ... import`, before it is used, because its precedence is needed while parsing.",
+        example: "fn main() -> Int {\n  return 4 `xoxo` 2;\n}",
+        fix: "Declare the operator, as in `opr xoxo(lhs: Int, rhs: Int) -> Int precedence 9 { ... }`, or import it with `from ops import xoxo;`.",
+    },
+    ErrorCode {
+        code: "E-PARSE-009",
+        phase: "parse",
+        title: "recursive type alias",
+        explanation: "A type alias expands to itself, directly or through other aliases, so it never names a concrete type.",
+        example: "type A = B;\ntype B = A;",
+        fix: "Point one of the aliases at a concrete type, or use a struct for recursive data.",
+    },
+    ErrorCode {
+        code: "E-PARSE-010",
+        phase: "parse",
+        title: "unknown lint directive",
+        explanation: "A `#allow(...)`, `#warn(...)`, or `#deny(...)` directive uses a level other than `allow`, `warn`, or `deny`, or names a lint that does not exist. The message lists the known lints.",
+        example: "#allow(unused_variable)\nfn main() -> Int { return 0; }",
+        fix: "Use one of the three levels with `warnings` or a lint named in the message, or remove the directive.",
+    },
+    ErrorCode {
+        code: "E-SEMA-001",
+        phase: "sema",
+        title: "unknown name",
+        explanation: "A variable, function, struct, type, or module path is used but not declared in scope. Imported names must be exported by their module and imported explicitly.",
+        example: "fn main() -> Int {\n  return count;\n}",
+        fix: "Declare the name, fix its spelling, or import it from the module that exports it.",
+    },
+    ErrorCode {
+        code: "E-SEMA-002",
+        phase: "sema",
+        title: "unknown or missing field or method",
+        explanation: "A field or method is accessed on a struct that does not declare it, or a struct literal omits a declared field. A tuple only has the elements `.0` up to one less than its length. An operator applied to structs needs the matching operator method in the struct's `impl` block.",
+        example: "struct User { id: Int }\n\nfn main() -> Int {\n  let u = User { id: 1 };\n  return u.age;\n}",
+        fix: "Use a field or method the struct declares, and give every field a value in struct literals.",
+    },
+    ErrorCode {
+        code: "E-SEMA-003",
+        phase: "sema",
+        title: "duplicate declaration",
+        explanation: "Two declarations in the same scope share a name. Functions, structs, globals, operators, fields, methods, parameters, and local bindings must each be unique where they are declared. A pattern may bind each name only once. The name `panic` is reserved for the builtin.",
+        example: "fn helper() -> Int { return 1; }\nfn helper() -> Int { return 2; }",
+        fix: "Rename or remove one of the declarations.",
+    },
+    ErrorCode {
+        code: "E-SEMA-004",
+        phase: "sema",
+        title: "wrong number of arguments",
+        explanation: "A function, method, function value, or builtin is called with a different number of arguments than it declares.",
+        example: "fn add(a: Int, b: Int) -> Int { return a + b; }\n\nfn main() -> Int {\n  return add(1);\n}",
+        fix: "Pass exactly the parameters the callee declares.",
+    },
+    ErrorCode {
+        code: "E-SEMA-005",
+        phase: "sema",
+        title: "return type mismatch or missing return",
+        explanation: "A `return` value does not match the function's declared return type, or some path through a non-`Void` function reaches the end without returning.",
+        example: "fn main() -> Int {\n  return true;\n}",
+        fix: "Return a value of the declared type on every path, or change the declared return type.",
+    },
+    ErrorCode {
+        code: "E-SEMA-006",
+        phase: "sema",
+        title: "non-exhaustive or redundant match",
+        explanation: "A `match` does not cover every possible value of its scrutinee, or lists the same pattern twice. `Bool`, `Option`, and `Result` matches must name every variant or use `_`.",
+        example: "match (flag) {\n  true => { return 1; }\n}",
+        fix: "Add arms for the missing cases or a final `_` arm, and remove repeated patterns.",
+    },
+    ErrorCode {
+        code: "E-SEMA-007",
+        phase: "sema",
+        title: "control flow outside its context",
+        explanation: "`break` and `continue` are only valid inside a loop, `defer` only at the top level of a function body, and `?` is only valid in a function returning a compatible `Option` or `Result`.",
+        example: "fn main() -> Int {\n  break;\n  return 0;\n}",
+        fix: "Move the statement into a loop, or handle the `Option`/`Result` with `match` instead of `?`.",
+    },
+    ErrorCode {
+        code: "E-SEMA-008",
+        phase: "sema",
+        title: "opaque struct used outside its module",
+        explanation: "The struct was exported as `Name(opaque)`. Importers may name it and call its methods, but cannot build it with a literal or read or write its fields.",
+        example: "// models.sk: export { Token(opaque) };\nlet t = Token { value: 1 };",
+        fix: "Use constructor functions and methods exported by the defining module.",
+    },
+    ErrorCode {
+        code: "E-SEMA-009",
+        phase: "sema",
+        title: "cannot infer type",
+        explanation: "The type of an empty container or other expression cannot be determined from its use.",
+        example: "let items = vec.new();",
+        fix: "Add a type annotation, for example `let items: Vec[Int] = vec.new();`.",
+    },
+    ErrorCode {
+        code: "E-SEMA-010",
+        phase: "sema",
+        title: "type mismatch",
+        explanation: "A value's type does not match what the context requires: a `let` annotation, an assignment target, a function parameter, an operator operand, or a condition that must be `Bool`.",
+        example: "fn main() -> Int {\n  let n: Int = \"three\";\n  return n;\n}",
+        fix: "Convert the value explicitly, or change the annotation to the value's actual type.",
+    },
+    ErrorCode {
+        code: "E-SEMA-011",
+        phase: "sema",
+        title: "not callable or not a value",
+        explanation: "A call targets an expression that cannot be called, such as a literal or a folder namespace without its file segment, or a builtin or type path is used as a value instead of being called.",
+        example: "import str;\n\nfn main() -> Int {\n  let f = str.len;\n  return 0;\n}",
+        fix: "Call a declared function or a function value, and call builtins directly, as in `str.len(s)`.",
+    },
+    ErrorCode {
+        code: "E-SEMA-012",
+        phase: "sema",
+        title: "operation not supported by the type",
+        explanation: "The value does not support the operation applied to it: only arrays, vectors, and strings can be indexed, only arrays can be assigned through an index, only structs have fields and methods, and range slicing applies to `String`.",
+        example: "fn main() -> Int {\n  let n = 5;\n  return n[0];\n}",
+        fix: "Apply the operation to a value of a type that supports it, or use the matching builtin such as `vec.set`.",
+    },
+    ErrorCode {
+        code: "E-SEMA-013",
+        phase: "sema",
+        title: "invalid operator or method declaration",
+        explanation: "An `opr` declaration must take exactly two parameters, a method must take `self` as its first parameter, and an operator method on a struct must take `self` and exactly one other parameter.",
+        example: "opr xoxo(lhs: Int) -> Int precedence 9 {\n  return lhs;\n}",
+        fix: "Give the declaration the parameters its form requires.",
+    },
+    ErrorCode {
+        code: "E-SEMA-014",
+        phase: "sema",
+        title: "pattern does not fit the matched value",
+        explanation: "A pattern does not fit the value it is matched against: it names a variant the type does not have, has a different shape than the value, or binds a value where binding is not allowed, such as with `None` or inside an OR-pattern of variants. `let` patterns must always match, so they cannot contain literals or variants.",
+        example: "fn main() -> Int {\n  let n = 3;\n  match (n) {\n    Some(v) => { return v; }\n    _ => { return 0; }\n  }\n}",
+        fix: "Match with patterns built for the value's type, and bind names only where the pattern carries a value.",
+    },
+    ErrorCode {
+        code: "E-SEMA-015",
+        phase: "sema",
+        title: "invalid export",
+        explanation: "An `export { ... }` names something the module does not declare, an exported global has no explicit type annotation, or `(opaque)` is applied to something other than a struct declared in the module. Generic structs cannot be exported.",
+        example: "let count = 0;\nexport { count };",
+        fix: "Export only names the module declares, annotate exported globals as in `let count: Int = 0;`, and use `(opaque)` only on local structs.",
+    },
+    ErrorCode {
+        code: "E-SEMA-016",
+        phase: "sema",
+        title: "function literal captures a local",
+        explanation: "Function literals are non-capturing: their body may use globals, its own parameters, and its own locals, but not the locals or parameters of the enclosing function.",
+        example: "fn main() -> Int {\n  let y = 1;\n  let add = fn(x: Int) -> Int { return x + y; };\n  return add(1);\n}",
+        fix: "Pass the value as a parameter, or move it into a global.",
+    },
+    ErrorCode {
+        code: "E-SEMA-017",
+        phase: "sema",
+        title: "unsupported `extern fn` signature",
+        explanation: "Linked `extern fn` declarations are called through a fixed set of native call shapes, so only the parameter and result type combinations listed in the message are accepted.",
+        example: "extern(\"libc.so.6\") fn getenv(name: String) -> String;",
+        fix: "Declare the function with one of the listed signatures, or wrap it in a native helper that has one.",
+    },
+    ErrorCode {
+        code: "E-SEMA-018",
+        phase: "sema",
+        title: "package used without import",
+        explanation: "Builtin packages such as `str`, `vec`, or `fs` are only in scope in modules that import them.",
+        example: "fn main() -> Int {\n  return str.len(\"abc\");\n}",
+        fix: "Add the import, for example `import str;`, at the top of the module.",
+    },
+    ErrorCode {
+        code: "E-SEMA-019",
+        phase: "sema",
+        title: "internal builtin used directly",
+        explanation: "The `option`, `result`, and `ffi` builtins back compiler-generated code and are not part of the user-facing language.",
+        example: "fn main() -> Int {\n  let x = option.some(1);\n  return 0;\n}",
+        fix: "Write `Some(...)`, `None()`, `Ok(...)`, or `Err(...)` directly, and declare native functions with `extern(\"...\") fn ...;`.",
+    },
+    ErrorCode {
+        code: "E-SEMA-020",
+        phase: "sema",
+        title: "invalid format string",
+        explanation: "A literal format string passed to `io.format` or `io.printf` uses a specifier other than `%d` (Int), `%f` (Float), `%s` (String), `%b` (Bool), `%v` (any value), and `%%`, or ends with a lone `%`.",
+        example: "io.printf(\"%x\\n\", 255);",
+        fix: "Use one of the supported specifiers, and write `%%` for a literal percent sign.",
+    },
+    ErrorCode {
+        code: "E-SEMA-021",
+        phase: "sema",
+        title: "unreachable statement",
+        explanation: "A statement follows a `return`, `break`, `continue`, `panic(...)`, `os.exit(...)`, an infinite loop, or an `if`/`match` whose every branch exits, so it can never run. This is the `unreachable_code` lint: a warning by default, silenced by `#allow(unreachable_code)` or `--allow unreachable_code`, and an error that stops compilation under `#deny(...)` or `--deny warnings`.",
+        example: "fn main() -> Int {\n  return 0;\n  let unused = 1;\n}",
+        fix: "Delete the dead statement, or move it before the statement that leaves the block.",
+    },
+    ErrorCode {
+        code: "E-SEMA-022",
+        phase: "sema",
+        title: "invalid `main` signature",
+        explanation: "The entry module's `main` starts the program, so it takes no parameters and returns `Int` or `Void`. An `Int` result becomes the process exit code; `Void` exits with code 0. Other result types are rejected instead of being discarded, so print a value explicitly if the program should show it.",
+        example: "fn main() -> String {\n  return \"done\";\n}",
+        fix: "Return an `Int` exit code or `Void`, and print any other result with `io.println` before returning.",
+    },
+    ErrorCode {
+        code: "E-SEMA-023",
+        phase: "sema",
+        title: "spawned task mutates a global",
+        explanation: "Spawned tasks run on their own thread in native builds, so `task.spawn` rejects a function that assigns a global or passes one to a mutating builtin such as `vec.push`, either itself or through functions it calls by name.",
+        example: "let hits: Int = 0;\nfn bump() -> Int { hits = hits + 1; return hits; }\nfn main() -> Int { return task.join(task.spawn(bump)); }",
+        fix: "Send results over a `task.Channel` or return them from the task, and update the global on the spawning side.",
+    },
+    ErrorCode {
+        code: "E-SEMA-024",
+        phase: "sema",
+        title: "invalid use of a generic struct",
+        explanation: "A generic struct needs type arguments wherever it is named or built, a struct without type parameters cannot be given any, and a generic struct cannot contain an instantiation of itself that keeps growing. Generic structs cannot have `impl` blocks yet.",
+        example: "struct Box[T] { value: T }\n\nfn main() -> Int {\n  let b: Box = Box[Int] { value: 1 };\n  return b.value;\n}",
+        fix: "Write the type arguments, as in `Box[Int]`, and keep methods on non-generic structs.",
+    },
+    ErrorCode {
+        code: "E-SEMA-025",
+        phase: "sema",
+        title: "struct contains itself",
+        explanation: "A struct holds itself by value, directly or through other structs, fixed arrays, or tuples, so its values would be infinitely large. Recursion behind `Option`, `Result`, `Vec`, or `Map` is allowed, because those store their payload out of line.",
+        example: "struct Node {\n  value: Int,\n  next: Node\n}",
+        fix: "Wrap the field in `Option[...]` or `Vec[...]`.",
+    },
+    ErrorCode {
+        code: "E-SEMA-026",
+        phase: "sema",
+        title: "use of a deprecated declaration",
+        explanation: "The called function or constructed struct is marked `@deprecated`, usually with a note naming its replacement. This is the `deprecated` lint: a warning by default, silenced by `#allow(deprecated)` or `--allow deprecated`, and an error under `#deny(...)` or `--deny warnings`.",
+        example: "@deprecated(\"use total\")\nfn sum(a: Int, b: Int) -> Int { return a + b; }\nfn main() -> Int { return sum(1, 2); }",
+        fix: "Switch to the replacement named in the note, or allow the lint while migrating.",
+    },
+    ErrorCode {
+        code: "E-SEMA-027",
+        phase: "sema",
+        title: "invalid attribute",
+        explanation: "Functions accept `@inline`, `@test`, and `@deprecated`; structs accept only `@deprecated`. Each may appear once, only `@deprecated` takes an argument (its note), neither `@inline` nor `@test` applies to an `extern fn`, and a `@test` function takes no parameters and returns `Int` or `Void`.",
+        example: "@test\nfn check(n: Int) -> Int { return n; }",
+        fix: "Remove the attribute, or adjust the declaration to meet its requirements.",
+    },
+    ErrorCode {
+        code: "E-SEMA-028",
+        phase: "sema",
+        title: "global read before initialization",
+        explanation: "Globals initialize once, before `main`: every imported module's globals first, then this module's in declaration order. An initializer that calls or names a function reading a global declared at or after it would observe that global before its value exists.",
+        example: "let total: Int = read();\nfn read() -> Int { return base; }\nlet base: Int = 1;",
+        fix: "Declare the global that is read before the one whose initializer reads it, or compute the value inside `main`.",
+    },
+    ErrorCode {
+        code: "E-SEMA-029",
+        phase: "sema",
+        title: "type has no serialized form",
+        explanation: "`serde` handles values built from scalars, `Option`, `Result`, `Vec`, `Map`, arrays, tuples, and structs. The value passed to a `serde` function, or the type a `let` decodes into, contains something else, such as a function value. JSON also cannot represent a nested `Option` such as `Option[Option[Int]]`, because `None` and `Some(None)` would both be `null`.",
+        example: "import serde;\n\nfn main() -> Int {\n  let f = fn(x: Int) -> Int { return x; };\n  let text = serde.toJson(f);\n  return 0;\n}",
+        fix: "Serialize only the data fields, and rebuild functions and handles after decoding.",
+    },
+    ErrorCode {
+        code: "E-PARSE",
+        phase: "parse",
+        title: "parse error",
+        explanation: "The source could not be tokenized or parsed. Each parse diagnostic is reported under one of the numbered `E-PARSE-NNN` codes, which name the specific problem.",
+        example: "fn main( -> Int { return 0; }",
+        fix: "Fix the syntax at the reported position; later errors are often caused by the first one.",
+    },
+    ErrorCode {
+        code: "E-SEMA",
+        phase: "sema",
+        title: "semantic error",
+        explanation: "The program parsed but is not well-typed or well-formed. Each semantic diagnostic is reported under one of the numbered `E-SEMA-NNN` codes, which name the specific problem.",
+        example: "fn main() -> Int {\n  return missing;\n}",
+        fix: "Read the message for the rule that was broken and adjust the code at the reported position.",
+    },
+    ErrorCode {
+        code: "E-MOD-NOT-FOUND",
+        phase: "resolve",
+        title: "module not found",
+        explanation: "An `import` or `export ... from` names a module path that maps to neither `<path>.sk` nor a folder under the entry's root.",
+        example: "import utils.mathz;",
+        fix: "Check the spelling and that `utils/math.sk` (or a `utils/math/` folder) exists relative to the entry file.",
+    },
+    ErrorCode {
+        code: "E-MOD-CYCLE",
+        phase: "resolve",
+        title: "circular import",
+        explanation: "Modules import each other in a cycle. Skepa requires the module graph to be acyclic.",
+        example: "// a.sk\nimport b;\n// b.sk\nimport a;",
+        fix: "Move the shared declarations into a third module imported by both.",
+    },
+    ErrorCode {
+        code: "E-MOD-AMBIG",
+        phase: "resolve",
+        title: "ambiguous module path",
+        explanation: "A module path matches both a file `<path>.sk` and a folder `<path>/`.",
+        example: "// utils.sk and utils/ both exist\nimport utils;",
+        fix: "Rename either the file or the folder.",
+    },
+    ErrorCode {
+        code: "E-MOD-DUPLICATE",
+        phase: "resolve",
+        title: "duplicate module id",
+        explanation: "Two different source files resolve to the same module id, for example through a symlinked folder.",
+        example: "// lib/ is a symlink to vendor/lib/; both paths reach lib/util.sk",
+        fix: "Import each module through a single path.",
+    },
+    ErrorCode {
+        code: "E-MOD-IO",
+        phase: "resolve",
+        title: "module could not be read",
+        explanation: "A module file exists but could not be read from disk.",
+        example: "skepac check missing.sk",
+        fix: "Check the path and file permissions.",
+    },
+    ErrorCode {
+        code: "E-MOD-PATH",
+        phase: "resolve",
+        title: "invalid module path",
+        explanation: "A source path cannot be mapped to a module id: it is not a `.sk` file, or it contains a non-UTF-8 component.",
+        example: "skepac check main.txt",
+        fix: "Pass an entry file with the `.sk` extension and use UTF-8 file and folder names.",
+    },
+    ErrorCode {
+        code: "E-EXPORT-UNKNOWN",
+        phase: "resolve",
+        title: "export of unknown name",
+        explanation: "An `export { ... }` lists a name the module does not declare, or a re-export names something the source module does not export.",
+        example: "fn add(a: Int, b: Int) -> Int { return a + b; }\nexport { sub };",
+        fix: "Export only names declared in, or exported by, the module.",
+    },
+    ErrorCode {
+        code: "E-IMPORT-NOT-EXPORTED",
+        phase: "resolve",
+        title: "import of unexported name",
+        explanation: "A `from ... import` names a symbol that exists in the target module but is not exported.",
+        example: "// utils.sk declares helper() without exporting it\nfrom utils import helper;",
+        fix: "Add the name to the target module's `export { ... }` or import a different name.",
+    },
+    ErrorCode {
+        code: "E-IMPORT-CONFLICT",
+        phase: "resolve",
+        title: "conflicting import",
+        explanation: "Two imports, or an import and a local declaration, bind the same name in one module.",
+        example: "from a import run;\nfrom b import run;",
+        fix: "Rename one binding with `as`, for example `from b import run as runB;`.",
+    },
+    ErrorCode {
+        code: "E-CAP-DENIED",
+        phase: "resolve",
+        title: "capability denied",
+        explanation: "The project imports a builtin package, or declares an `extern fn`, that was denied with a `--no-<package>` flag.",
+        example: "skepac run --no-fs app.sk   // app.sk imports fs",
+        fix: "Remove the flag if the access is intended, or stop using the denied package.",
+    },
+    ErrorCode {
+        code: "E-CODEGEN",
+        phase: "codegen",
+        title: "code generation failure",
+        explanation: "Lowering or native code generation failed after the frontend accepted the program, or the native toolchain (`clang`) could not be run.",
+        example: "skepac build-native app.sk app   // clang not on PATH",
+        fix: "Make sure the native toolchain and runtime library are installed; otherwise report the program as a compiler bug.",
+    },
+    ErrorCode {
+        code: "E-RUNTIME",
+        phase: "runtime",
+        title: "native executable failed",
+        explanation: "`skepac run` could not start the compiled executable, or it terminated without an exit code.",
+        example: "skepac run app.sk",
+        fix: "Check the process was not killed by a signal, and that the output directory is writable and executable.",
+    },
+    ErrorCode {
+        code: "E-RT-001",
+        phase: "runtime",
+        title: "division by zero",
+        explanation: "An integer `/` or `%` had a zero right-hand side.",
+        example: "let d = 0;\nreturn 10 / d;",
+        fix: "Check the divisor before dividing.",
+    },
+    ErrorCode {
+        code: "E-RT-002",
+        phase: "runtime",
+        title: "integer overflow",
+        explanation: "Checked `Int` arithmetic produced a result outside the 64-bit signed range.",
+        example: "let big = 9223372036854775807;\nreturn big + 1;",
+        fix: "Use a wider algorithm, or `math.wrappingAdd` and friends when wrapping is intended.",
+    },
+    ErrorCode {
+        code: "E-RT-003",
+        phase: "runtime",
+        title: "index out of bounds",
+        explanation: "An array, vector, string, or bytes index was negative or not less than the length.",
+        example: "let xs = [1, 2, 3];\nreturn xs[3];",
+        fix: "Check the index against the length first.",
+    },
+    ErrorCode {
+        code: "E-RT-004",
+        phase: "runtime",
+        title: "runtime type mismatch",
+        explanation: "A runtime value did not have the type an operation required. Well-typed programs normally cannot reach this; values crossing `ffi` or dynamic boundaries can.",
+        example: "// calling an ffi symbol bound with the wrong signature",
+        fix: "Check the types declared for foreign functions and dynamic values.",
+    },
+    ErrorCode {
+        code: "E-RT-005",
+        phase: "runtime",
+        title: "missing field",
+        explanation: "A struct value was accessed at a field slot it does not have.",
+        example: "// a struct value passed through ffi with fewer fields than declared",
+        fix: "Make sure values crossing foreign boundaries match their declared struct layout.",
+    },
+    ErrorCode {
+        code: "E-RT-006",
+        phase: "runtime",
+        title: "invalid argument",
+        explanation: "A builtin received an argument outside its accepted domain, such as a negative shift count or a handle of the wrong kind.",
+        example: "return 1 << -1;",
+        fix: "Validate the argument before calling the builtin.",
+    },
+    ErrorCode {
+        code: "E-RT-007",
+        phase: "runtime",
+        title: "I/O failure",
+        explanation: "A host file, console, or network operation failed in a builtin that does not report failure through `Result`.",
+        example: "// io.println when stdout has been closed",
+        fix: "Prefer `Result`-returning builtins where they exist and handle the error value.",
+    },
+    ErrorCode {
+        code: "E-RT-008",
+        phase: "runtime",
+        title: "process or foreign call failure",
+        explanation: "Spawning or waiting on a child process failed, or a foreign function bound with `ffi` could not be called.",
+        example: "// an ffi symbol whose library was unloaded before the call",
+        fix: "Check that commands exist and that foreign libraries stay open while their symbols are used.",
+    },
+    ErrorCode {
+        code: "E-RT-009",
+        phase: "runtime",
+        title: "unsupported builtin",
+        explanation: "The program called a builtin that this runtime build does not provide.",
+        example: "// a builtin compiled against a newer runtime",
+        fix: "Rebuild with a runtime library that matches the compiler version.",
+    },
+    ErrorCode {
+        code: "E-RT-010",
+        phase: "runtime",
+        title: "explicit panic",
+        explanation: "The program called `panic(message)`, which stops execution with that message.",
+        example: "fn main() -> Int {\n  panic(\"config missing\");\n}",
+        fix: "Handle the condition that led to the panic, or return an `Err(...)` the caller can recover from.",
+    },
+];
+
+/// Looks up a code case-insensitively, e.g. `e-sema-001`.
+pub fn lookup(code: &str) -> Option<&'static ErrorCode> {
+    ERROR_CODES
+        .iter()
+        .find(|entry| entry.code.eq_ignore_ascii_case(code))
+}
+
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("    {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
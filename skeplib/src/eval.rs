@@ -0,0 +1,244 @@
+//! Evaluates standalone Skepa expressions for embedders, such as rule engines or
+//! configuration logic that compute a value from host-provided variables.
+//!
+//! The expression is checked with its bindings as typed parameters, lowered to
+//! IR, and run by the IR interpreter. Only the pure builtin packages are in
+//! scope, and the interpreter's host refuses every I/O call, so a snippet can
+//! compute a value but never reach files, the network, the process, or FFI.
+
+use std::fmt;
+
+use skepart::{RtError, RtHost, RtResult, RtValue};
+
+use crate::ast::{FnDecl, ImportDecl, Param, Program, Stmt, StmtKind, TypeName};
+use crate::diagnostic::{DiagnosticBag, Span};
+use crate::ir::{IrInterpError, IrInterpreter, lowering, opt};
+use crate::parser::Parser;
+use crate::sema::analyze_local_type;
+use crate::types::{TypeInfo, display_type};
+
+/// Packages an expression may use without importing them. I/O, process, time,
+/// randomness, tasks, and FFI are left out.
+const PURE_PACKAGES: [&str; 10] = [
+    "str", "arr", "bytes", "char", "map", "math", "option", "result", "vec", "iter",
+];
+
+const EVAL_FN: &str = "__eval";
+const EVAL_VALUE: &str = "__value";
+
+/// A host variable visible to the expression under `name`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalBinding {
+    pub name: String,
+    pub ty: TypeInfo,
+    pub value: RtValue,
+}
+
+impl EvalBinding {
+    pub fn new(name: impl Into<String>, ty: TypeInfo, value: RtValue) -> Self {
+        Self {
+            name: name.into(),
+            ty,
+            value,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum EvalError {
+    /// The source is not a single expression.
+    Parse(DiagnosticBag),
+    /// The expression does not type-check against the bindings.
+    Type(DiagnosticBag),
+    /// A binding or the result has a type eval cannot pass across the host
+    /// boundary, or a binding's value does not match its declared type.
+    Unsupported(String),
+    Runtime(IrInterpError),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(diags) | Self::Type(diags) => {
+                let messages = diags
+                    .as_slice()
+                    .iter()
+                    .map(|d| d.message.as_str())
+                    .collect::<Vec<_>>();
+                write!(f, "{}", messages.join("; "))
+            }
+            Self::Unsupported(msg) => write!(f, "{msg}"),
+            Self::Runtime(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// Parses `source` as one expression, checks it with `bindings` in scope, and
+/// returns its value. A `Void` expression evaluates to [`RtValue::Unit`].
+pub fn eval_expr(source: &str, bindings: &[EvalBinding]) -> Result<RtValue, EvalError> {
+    let (expr, diags) = Parser::parse_expr_source(source);
+    let Some(expr) = expr else {
+        return Err(EvalError::Parse(diags));
+    };
+
+    let mut params = Vec::with_capacity(bindings.len());
+    for binding in bindings {
+        let ty = binding_type_name(&binding.ty).ok_or_else(|| {
+            EvalError::Unsupported(format!(
+                "Binding `{}` has type `{}`, which eval cannot pass to an expression",
+                binding.name,
+                display_type(&binding.ty)
+            ))
+        })?;
+        if !value_matches(&binding.value, &binding.ty) {
+            return Err(EvalError::Unsupported(format!(
+                "Binding `{}` is declared as `{}` but holds a `{}` value",
+                binding.name,
+                display_type(&binding.ty),
+                binding.value.type_name()
+            )));
+        }
+        params.push(Param {
+            name: binding.name.clone(),
+            ty,
+            span: Span::default(),
+        });
+    }
+
+    let probe = snippet_program(
+        params.clone(),
+        None,
+        vec![
+            StmtKind::Let {
+                name: EVAL_VALUE.to_string(),
+                ty: None,
+                value: expr.clone(),
+            }
+            .into(),
+        ],
+    );
+    let (ty, diags) = analyze_local_type(&probe, EVAL_FN, EVAL_VALUE);
+    let Some(ty) = ty else {
+        return Err(EvalError::Type(diags));
+    };
+
+    let (return_type, body) = if ty == TypeInfo::Void {
+        (None, vec![StmtKind::Expr(expr).into()])
+    } else {
+        let ret = result_type_name(&ty).ok_or_else(|| {
+            EvalError::Unsupported(format!(
+                "Expression has type `{}`, which eval cannot return to the host",
+                display_type(&ty)
+            ))
+        })?;
+        (Some(ret), vec![StmtKind::Return(Some(expr)).into()])
+    };
+    let program = snippet_program(params, return_type, body);
+    let mut ir = lowering::compile_checked_program(&program).map_err(EvalError::Type)?;
+    opt::optimize_program(&mut ir);
+
+    let args = bindings.iter().map(|b| b.value.clone()).collect();
+    IrInterpreter::with_host(&ir, Box::new(SandboxHost))
+        .call(EVAL_FN, args)
+        .map_err(EvalError::Runtime)
+}
+
+fn snippet_program(params: Vec<Param>, return_type: Option<TypeName>, body: Vec<Stmt>) -> Program {
+    Program {
+        imports: PURE_PACKAGES
+            .iter()
+            .map(|name| ImportDecl::ImportModule {
+                path: vec![name.to_string()],
+                alias: None,
+            })
+            .collect(),
+        functions: vec![FnDecl {
+            is_extern: false,
+            extern_library: None,
+            name: EVAL_FN.to_string(),
+            span: Span::default(),
+            params,
+            return_type,
+            body,
+            doc: None,
+            attributes: Vec::new(),
+        }],
+        ..Program::default()
+    }
+}
+
+/// Source type for a binding. Only data types with a direct runtime value are
+/// accepted; structs, functions, tuples, and handles are not.
+fn binding_type_name(ty: &TypeInfo) -> Option<TypeName> {
+    Some(match ty {
+        TypeInfo::Int => TypeName::Int,
+        TypeInfo::Float => TypeName::Float,
+        TypeInfo::Bool => TypeName::Bool,
+        TypeInfo::Char => TypeName::Char,
+        TypeInfo::String => TypeName::String,
+        TypeInfo::Bytes => TypeName::Bytes,
+        TypeInfo::Option { value } => TypeName::Option {
+            value: Box::new(binding_type_name(value)?),
+        },
+        TypeInfo::Result { ok, err } => TypeName::Result {
+            ok: Box::new(binding_type_name(ok)?),
+            err: Box::new(binding_type_name(err)?),
+        },
+        TypeInfo::Array { elem, size } => TypeName::Array {
+            elem: Box::new(binding_type_name(elem)?),
+            size: *size,
+        },
+        TypeInfo::Vec { elem } => TypeName::Vec {
+            elem: Box::new(binding_type_name(elem)?),
+        },
+        TypeInfo::Map { value } => TypeName::Map {
+            value: Box::new(binding_type_name(value)?),
+        },
+        TypeInfo::Void
+        | TypeInfo::Named(_)
+        | TypeInfo::Opaque(_)
+        | TypeInfo::Fn { .. }
+        | TypeInfo::Tuple(_)
+        | TypeInfo::Unknown => return None,
+    })
+}
+
+/// Source type for the result: binding types, plus tuples of them.
+fn result_type_name(ty: &TypeInfo) -> Option<TypeName> {
+    match ty {
+        TypeInfo::Tuple(items) => Some(TypeName::Tuple(
+            items.iter().map(result_type_name).collect::<Option<_>>()?,
+        )),
+        _ => binding_type_name(ty),
+    }
+}
+
+/// Whether `value` has the outer shape of `ty`; element types are trusted.
+fn value_matches(value: &RtValue, ty: &TypeInfo) -> bool {
+    matches!(
+        (value, ty),
+        (RtValue::Int(_), TypeInfo::Int)
+            | (RtValue::Float(_), TypeInfo::Float)
+            | (RtValue::Bool(_), TypeInfo::Bool)
+            | (RtValue::Char(_), TypeInfo::Char)
+            | (RtValue::String(_), TypeInfo::String)
+            | (RtValue::Bytes(_), TypeInfo::Bytes)
+            | (RtValue::Option(_), TypeInfo::Option { .. })
+            | (RtValue::Result(_), TypeInfo::Result { .. })
+            | (RtValue::Array(_), TypeInfo::Array { .. })
+            | (RtValue::Vec(_), TypeInfo::Vec { .. })
+            | (RtValue::Map(_), TypeInfo::Map { .. })
+    )
+}
+
+/// Host that refuses console output; every other host call already defaults to
+/// an unsupported-builtin error.
+struct SandboxHost;
+
+impl RtHost for SandboxHost {
+    fn io_print(&mut self, _text: &str) -> RtResult<()> {
+        Err(RtError::unsupported_builtin("io.print"))
+    }
+}
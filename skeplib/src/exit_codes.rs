@@ -0,0 +1,85 @@
+//! Process exit codes shared by `skepac` and anything that wraps it. The numeric
+//! values are a stable contract: scripts and CI setups match on them, so new
+//! reasons get new numbers and existing numbers never change meaning.
+
+use crate::driver::{CompileOutput, DriverDiagnostic, Phase};
+
+/// Why a compiler run or native program exited, see [`ExitReason::code`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExitReason {
+    Ok,
+    /// Bad command-line arguments.
+    Usage,
+    /// A source file or output path could not be read or written.
+    Io,
+    /// A file handed to `skepac parse` failed to parse. Parse errors found while
+    /// resolving a project are [`ExitReason::Resolve`].
+    Parse,
+    Sema,
+    Codegen,
+    Resolve,
+    /// A native program stopped on an uncaught runtime error.
+    Runtime,
+    /// `skepac test` ran and at least one `@test` function failed.
+    TestFailed,
+}
+
+impl ExitReason {
+    pub const ALL: [ExitReason; 9] = [
+        Self::Ok,
+        Self::Usage,
+        Self::Io,
+        Self::Parse,
+        Self::Sema,
+        Self::Codegen,
+        Self::Resolve,
+        Self::Runtime,
+        Self::TestFailed,
+    ];
+
+    pub fn code(self) -> i32 {
+        match self {
+            Self::Ok => 0,
+            Self::Usage => 2,
+            Self::Io => 3,
+            Self::Parse => 10,
+            Self::Sema => 11,
+            Self::Codegen => 12,
+            Self::Resolve => 15,
+            Self::TestFailed => 20,
+            Self::Runtime => skepart::RtError::EXIT_CODE,
+        }
+    }
+
+    pub fn from_code(code: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|reason| reason.code() == code)
+    }
+
+    /// How a [`Compiler`](crate::driver::Compiler) run should exit: the failing
+    /// phase, with resolve failures caused by unreadable files reported as
+    /// [`ExitReason::Io`].
+    pub fn of_output(output: &CompileOutput) -> Self {
+        let Some(phase) = output.failed else {
+            return Self::Ok;
+        };
+        let io = output
+            .diagnostics
+            .iter()
+            .any(|diag| matches!(diag, DriverDiagnostic::Resolve(err) if err.code == "E-MOD-IO"));
+        if phase == Phase::Resolve && io {
+            Self::Io
+        } else {
+            phase.into()
+        }
+    }
+}
+
+impl From<Phase> for ExitReason {
+    fn from(phase: Phase) -> Self {
+        match phase {
+            Phase::Resolve => Self::Resolve,
+            Phase::Sema => Self::Sema,
+            Phase::Codegen => Self::Codegen,
+        }
+    }
+}
@@ -0,0 +1,418 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::resolver::{ModuleGraph, ModuleId, ResolveError, SymbolKind, build_export_maps};
+
+const INDEX_HEADER: &str = "skepa-symbol-index v3";
+
+/// Compact, persisted view of a resolved project for editor tooling.
+///
+/// The index records each module's top-level declarations with their source
+/// positions, its imports and exports, and a hash of the source it was built
+/// from. Tools can answer definition and completion queries from it on a cold
+/// start and use [`SymbolIndex::stale_modules`] to decide what to re-analyze.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolIndex {
+    pub modules: Vec<IndexedModule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedModule {
+    pub id: ModuleId,
+    pub path: PathBuf,
+    pub source_hash: u64,
+    pub imports: Vec<ModuleId>,
+    pub exports: Vec<IndexedExport>,
+    pub symbols: Vec<IndexedSymbol>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedExport {
+    pub name: String,
+    pub module_id: ModuleId,
+    pub local_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IndexedSymbol {
+    pub kind: IndexedSymbolKind,
+    /// Declared name; methods are recorded as `Type.method`.
+    pub name: String,
+    pub line: usize,
+    pub col: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexedSymbolKind {
+    Fn,
+    Struct,
+    #[serde(rename = "type")]
+    TypeAlias,
+    Method,
+    Global,
+    Operator,
+}
+
+impl IndexedSymbolKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Fn => "fn",
+            Self::Struct => "struct",
+            Self::TypeAlias => "type",
+            Self::Method => "method",
+            Self::Global => "global",
+            Self::Operator => "operator",
+        }
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        Some(match text {
+            "fn" => Self::Fn,
+            "struct" => Self::Struct,
+            "type" => Self::TypeAlias,
+            "method" => Self::Method,
+            "global" => Self::Global,
+            "operator" => Self::Operator,
+            _ => return None,
+        })
+    }
+}
+
+/// Where a symbol is defined, as answered by [`SymbolIndex::definition`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolLocation {
+    pub module_id: ModuleId,
+    pub path: PathBuf,
+    pub line: usize,
+    pub col: usize,
+}
+
+impl SymbolIndex {
+    pub fn build(graph: &ModuleGraph) -> Result<Self, Vec<ResolveError>> {
+        let export_maps = build_export_maps(graph)?;
+        let mut ids = graph.modules.keys().cloned().collect::<Vec<_>>();
+        ids.sort();
+        let mut modules = Vec::new();
+        for id in ids {
+            let unit = &graph.modules[&id];
+            let mut imports = unit.imports.clone();
+            imports.sort();
+            imports.dedup();
+            let mut exports = export_maps
+                .get(&id)
+                .into_iter()
+                .flatten()
+                .filter(|(_, sym)| sym.kind != SymbolKind::Namespace)
+                .map(|(name, sym)| IndexedExport {
+                    name: name.clone(),
+                    module_id: sym.module_id.clone(),
+                    local_name: sym.local_name.clone(),
+                })
+                .collect::<Vec<_>>();
+            exports.sort_by(|a, b| a.name.cmp(&b.name));
+            modules.push(IndexedModule {
+                id: id.clone(),
+                path: unit.path.clone(),
+                source_hash: source_hash(&unit.source),
+                imports,
+                exports,
+                symbols: collect_symbols(&unit.program, &unit.source),
+            });
+        }
+        Ok(Self { modules })
+    }
+
+    pub fn module(&self, id: &str) -> Option<&IndexedModule> {
+        self.modules.iter().find(|m| m.id == id)
+    }
+
+    /// Resolves `name` as seen from `module_id`: a local declaration first, then
+    /// any export of a module it imports.
+    pub fn definition(&self, module_id: &str, name: &str) -> Option<SymbolLocation> {
+        let module = self.module(module_id)?;
+        if let Some(location) = self.local_definition(module, name) {
+            return Some(location);
+        }
+        module.imports.iter().find_map(|import| {
+            let export = self
+                .module(import)?
+                .exports
+                .iter()
+                .find(|e| e.name == name)?;
+            self.local_definition(self.module(&export.module_id)?, &export.local_name)
+        })
+    }
+
+    /// Names visible in `module_id` that start with `prefix`, sorted and deduplicated.
+    pub fn completions(&self, module_id: &str, prefix: &str) -> Vec<String> {
+        let Some(module) = self.module(module_id) else {
+            return Vec::new();
+        };
+        let mut out = module
+            .symbols
+            .iter()
+            .filter(|s| s.kind != IndexedSymbolKind::Method)
+            .map(|s| s.name.clone())
+            .chain(
+                module
+                    .imports
+                    .iter()
+                    .filter_map(|import| self.module(import))
+                    .flat_map(|m| m.exports.iter().map(|e| e.name.clone())),
+            )
+            .filter(|name| name.starts_with(prefix))
+            .collect::<Vec<_>>();
+        out.sort();
+        out.dedup();
+        out
+    }
+
+    /// Modules whose file on disk no longer matches the indexed source.
+    pub fn stale_modules(&self) -> Vec<ModuleId> {
+        self.modules
+            .iter()
+            .filter(|m| {
+                fs::read_to_string(&m.path)
+                    .map(|source| source_hash(&source) != m.source_hash)
+                    .unwrap_or(true)
+            })
+            .map(|m| m.id.clone())
+            .collect()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{INDEX_HEADER}\n");
+        for module in &self.modules {
+            out.push_str(&format!(
+                "module\t{}\t{}\t{:016x}\n",
+                escape_field(&module.id),
+                escape_field(&module.path.to_string_lossy()),
+                module.source_hash
+            ));
+            for import in &module.imports {
+                out.push_str(&format!("import\t{}\n", escape_field(import)));
+            }
+            for export in &module.exports {
+                out.push_str(&format!(
+                    "export\t{}\t{}\t{}\n",
+                    escape_field(&export.name),
+                    escape_field(&export.module_id),
+                    escape_field(&export.local_name)
+                ));
+            }
+            for symbol in &module.symbols {
+                out.push_str(&format!(
+                    "symbol\t{}\t{}\t{}\t{}\n",
+                    symbol.kind.as_str(),
+                    escape_field(&symbol.name),
+                    symbol.line,
+                    symbol.col
+                ));
+            }
+        }
+        out
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, INDEX_HEADER)) => {}
+            _ => return Err(format!("Missing `{INDEX_HEADER}` header")),
+        }
+        let mut modules: Vec<IndexedModule> = Vec::new();
+        for (idx, line) in lines {
+            let fields = line.split('\t').collect::<Vec<_>>();
+            let bad = || format!("Malformed symbol index line {}: `{line}`", idx + 1);
+            if fields[0] == "module" {
+                let [_, id, path, hash] = fields.as_slice() else {
+                    return Err(bad());
+                };
+                modules.push(IndexedModule {
+                    id: unescape_field(id).ok_or_else(bad)?,
+                    path: PathBuf::from(unescape_field(path).ok_or_else(bad)?),
+                    source_hash: u64::from_str_radix(hash, 16).map_err(|_| bad())?,
+                    imports: Vec::new(),
+                    exports: Vec::new(),
+                    symbols: Vec::new(),
+                });
+                continue;
+            }
+            let Some(module) = modules.last_mut() else {
+                return Err(bad());
+            };
+            match fields.as_slice() {
+                ["import", id] => module.imports.push(unescape_field(id).ok_or_else(bad)?),
+                ["export", name, module_id, local_name] => module.exports.push(IndexedExport {
+                    name: unescape_field(name).ok_or_else(bad)?,
+                    module_id: unescape_field(module_id).ok_or_else(bad)?,
+                    local_name: unescape_field(local_name).ok_or_else(bad)?,
+                }),
+                ["symbol", kind, name, line_no, col] => module.symbols.push(IndexedSymbol {
+                    kind: IndexedSymbolKind::parse(kind).ok_or_else(bad)?,
+                    name: unescape_field(name).ok_or_else(bad)?,
+                    line: line_no.parse().map_err(|_| bad())?,
+                    col: col.parse().map_err(|_| bad())?,
+                }),
+                _ => return Err(bad()),
+            }
+        }
+        Ok(Self { modules })
+    }
+
+    pub fn write_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    pub fn read_from(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::from_text(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    fn local_definition(&self, module: &IndexedModule, name: &str) -> Option<SymbolLocation> {
+        let symbol = module.symbols.iter().find(|s| s.name == name)?;
+        Some(SymbolLocation {
+            module_id: module.id.clone(),
+            path: module.path.clone(),
+            line: symbol.line,
+            col: symbol.col,
+        })
+    }
+}
+
+/// Escapes the index's field and line separators (and `\r`, which `lines`
+/// would strip) so any string survives a round trip through one field.
+fn escape_field(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Reverses [`escape_field`]; `None` for an unknown or dangling escape.
+fn unescape_field(field: &str) -> Option<String> {
+    let mut out = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        out.push(match chars.next()? {
+            '\\' => '\\',
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            _ => return None,
+        });
+    }
+    Some(out)
+}
+
+pub fn source_hash(source: &str) -> u64 {
+    // 64-bit FNV-1a: unlike `DefaultHasher`, its output is fixed across Rust
+    // releases, so a toolchain upgrade does not mark every module stale.
+    source.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Top-level declarations of one parsed module with their 1-based source positions;
+/// a declaration that cannot be found in `source` is recorded at `0:0`.
+pub fn collect_symbols(program: &crate::ast::Program, source: &str) -> Vec<IndexedSymbol> {
+    let mut out = Vec::new();
+    let mut push = |kind, keyword: &str, decl_name: &str, name: String, from_line: usize| {
+        let (line, col) = find_declaration(source, keyword, decl_name, from_line).unwrap_or((0, 0));
+        out.push(IndexedSymbol {
+            kind,
+            name,
+            line,
+            col,
+        });
+    };
+    for s in &program.structs {
+        push(
+            IndexedSymbolKind::Struct,
+            "struct",
+            &s.name,
+            s.name.clone(),
+            0,
+        );
+    }
+    for alias in &program.type_aliases {
+        push(
+            IndexedSymbolKind::TypeAlias,
+            "type",
+            &alias.name,
+            alias.name.clone(),
+            0,
+        );
+    }
+    for g in &program.globals {
+        push(IndexedSymbolKind::Global, "let", &g.name, g.name.clone(), 0);
+    }
+    for f in &program.functions {
+        push(IndexedSymbolKind::Fn, "fn", &f.name, f.name.clone(), 0);
+    }
+    for op in &program.operators {
+        push(
+            IndexedSymbolKind::Operator,
+            "opr",
+            &op.name,
+            op.name.clone(),
+            0,
+        );
+    }
+    for imp in &program.impls {
+        // Search for methods after the `impl` header so free functions with
+        // the same name do not shadow them.
+        let impl_line = find_declaration(source, "impl", &imp.target, 0).map_or(0, |(l, _)| l);
+        for m in &imp.methods {
+            let name = format!("{}.{}", imp.target, m.name);
+            push(IndexedSymbolKind::Method, "fn", &m.name, name, impl_line);
+        }
+    }
+    out
+}
+
+/// Finds the 1-based line/column of `keyword name` at word boundaries, skipping
+/// the first `skip_lines` lines.
+pub(crate) fn find_declaration(
+    source: &str,
+    keyword: &str,
+    name: &str,
+    skip_lines: usize,
+) -> Option<(usize, usize)> {
+    let needle = format!("{keyword} {name}");
+    source
+        .lines()
+        .enumerate()
+        .skip(skip_lines)
+        .find_map(|(idx, line)| {
+            let col = line.match_indices(&needle).find_map(|(col, _)| {
+                let before_ok = line[..col]
+                    .chars()
+                    .next_back()
+                    .is_none_or(|c| !c.is_alphanumeric() && c != '_');
+                let after_ok = line[col + needle.len()..]
+                    .chars()
+                    .next()
+                    .is_none_or(|c| !c.is_alphanumeric() && c != '_');
+                (before_ok && after_ok).then_some(col)
+            })?;
+            Some((idx + 1, col + 1))
+        })
+}
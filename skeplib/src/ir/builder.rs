@@ -40,6 +40,7 @@ impl IrBuilder {
             ret_ty,
             entry,
             blocks: Vec::new(),
+            inline_hint: false,
         };
         func.blocks.push(BasicBlock::new(entry, "entry"));
         func
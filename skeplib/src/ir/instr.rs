@@ -200,6 +200,41 @@ pub enum Instr {
     },
 }
 
+impl Instr {
+    /// The instruction's variant name, used to group executions in interpreter statistics.
+    pub fn opcode(&self) -> &'static str {
+        match self {
+            Self::Const { .. } => "Const",
+            Self::Copy { .. } => "Copy",
+            Self::Unary { .. } => "Unary",
+            Self::Binary { .. } => "Binary",
+            Self::Compare { .. } => "Compare",
+            Self::Logic { .. } => "Logic",
+            Self::LoadGlobal { .. } => "LoadGlobal",
+            Self::StoreGlobal { .. } => "StoreGlobal",
+            Self::LoadLocal { .. } => "LoadLocal",
+            Self::StoreLocal { .. } => "StoreLocal",
+            Self::MakeArray { .. } => "MakeArray",
+            Self::MakeArrayRepeat { .. } => "MakeArrayRepeat",
+            Self::VecNew { .. } => "VecNew",
+            Self::VecLen { .. } => "VecLen",
+            Self::ArrayGet { .. } => "ArrayGet",
+            Self::ArraySet { .. } => "ArraySet",
+            Self::VecPush { .. } => "VecPush",
+            Self::VecGet { .. } => "VecGet",
+            Self::VecSet { .. } => "VecSet",
+            Self::VecDelete { .. } => "VecDelete",
+            Self::MakeStruct { .. } => "MakeStruct",
+            Self::StructGet { .. } => "StructGet",
+            Self::StructSet { .. } => "StructSet",
+            Self::MakeClosure { .. } => "MakeClosure",
+            Self::CallDirect { .. } => "CallDirect",
+            Self::CallIndirect { .. } => "CallIndirect",
+            Self::CallBuiltin { .. } => "CallBuiltin",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct BranchTerminator {
     pub cond: Operand,
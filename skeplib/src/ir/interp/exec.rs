@@ -1,8 +1,11 @@
+use std::sync::Arc;
+
 use crate::ir::{
     BinaryOp, CmpOp, ConstValue, FunctionId, Instr, IrFunction, IrType, Operand, UnaryOp,
 };
 use skepart::{
-    RtArray, RtFunctionRef, RtResultValue, RtString, RtStruct, RtValue, RtVec, builtins,
+    RtArray, RtFunctionRef, RtResultValue, RtString, RtStruct, RtStructLayout, RtValue, RtVec,
+    builtins,
 };
 
 use super::{Frame, IrInterpError, IrInterpreter};
@@ -27,7 +30,9 @@ impl<'a> IrInterpreter<'a> {
             } => {
                 let value = frame.read_operand(operand, &self.globals)?;
                 let out = match (op, value) {
-                    (UnaryOp::Neg, RtValue::Int(v)) => RtValue::Int(-v),
+                    (UnaryOp::Neg, RtValue::Int(v)) => {
+                        RtValue::Int(v.checked_neg().ok_or(IrInterpError::IntegerOverflow)?)
+                    }
                     (UnaryOp::Neg, RtValue::Float(v)) => RtValue::Float(-v),
                     (UnaryOp::Not, RtValue::Bool(v)) => RtValue::Bool(!v),
                     (UnaryOp::BitNot, RtValue::Int(v)) => RtValue::Int(!v),
@@ -364,14 +369,14 @@ impl<'a> IrInterpreter<'a> {
             } => {
                 let callee = frame.read_operand(callee, &self.globals)?;
                 let function = match callee {
-                    RtValue::Function(function) => FunctionId(function.0),
+                    RtValue::Function(function) => function,
                     _ => return Err(IrInterpError::TypeMismatch("indirect call on non-closure")),
                 };
                 let args = args
                     .iter()
                     .map(|arg| frame.read_operand(arg, &self.globals))
                     .collect::<Result<Vec<_>, _>>()?;
-                let value = self.run_function(function, args)?;
+                let value = self.call_function_ref(function, args)?;
                 if let Some(dst) = dst {
                     frame.temps.insert(*dst, value);
                 }
@@ -389,6 +394,22 @@ impl<'a> IrInterpreter<'a> {
         Ok(())
     }
 
+    /// Calls a function value, dispatching refs from the host's
+    /// [`skepart::RtFunctionRegistry`] to the registered closure.
+    fn call_function_ref(
+        &mut self,
+        function: RtFunctionRef,
+        args: Vec<RtValue>,
+    ) -> Result<RtValue, IrInterpError> {
+        if function.host_index().is_some() {
+            return self
+                .host_functions
+                .call(self.host.as_mut(), function, &args)
+                .map_err(IrInterpError::from_runtime);
+        }
+        self.run_function(FunctionId(function.0), args)
+    }
+
     fn eval_builtin(
         &mut self,
         builtin: &crate::ir::BuiltinCall,
@@ -409,12 +430,13 @@ impl<'a> IrInterpreter<'a> {
                 args: &[RtValue],
             ) -> skepart::RtResult<RtValue> {
                 self.interp
-                    .run_function(FunctionId(function.0), args.to_vec())
+                    .call_function_ref(function, args.to_vec())
                     .map_err(|err| match err {
                         IrInterpError::DivisionByZero => skepart::RtError::new(
                             skepart::RtErrorKind::DivisionByZero,
                             "division by zero",
                         ),
+                        IrInterpError::IntegerOverflow => skepart::RtError::integer_overflow(),
                         IrInterpError::IndexOutOfBounds => skepart::RtError::new(
                             skepart::RtErrorKind::IndexOutOfBounds,
                             "index out of bounds",
@@ -428,6 +450,7 @@ impl<'a> IrInterpreter<'a> {
                         IrInterpError::UnsupportedBuiltin(msg) => {
                             skepart::RtError::unsupported_builtin(msg)
                         }
+                        IrInterpError::Panic(msg) => skepart::RtError::panic(msg),
                         IrInterpError::InvalidOperand(msg) => {
                             skepart::RtError::new(skepart::RtErrorKind::InvalidArgument, msg)
                         }
@@ -439,6 +462,10 @@ impl<'a> IrInterpreter<'a> {
                             skepart::RtErrorKind::InvalidArgument,
                             format!("IR program is missing function {:?}", id),
                         ),
+                        IrInterpError::UnknownFunction(name) => skepart::RtError::new(
+                            skepart::RtErrorKind::InvalidArgument,
+                            format!("IR program has no function named `{name}`"),
+                        ),
                         IrInterpError::MissingBlock(id) => skepart::RtError::new(
                             skepart::RtErrorKind::InvalidArgument,
                             format!("IR function is missing block {:?}", id),
@@ -454,6 +481,10 @@ impl<'a> IrInterpreter<'a> {
                 let value = self.call_function(function, args)?;
                 self.host().task_store_completed(value)
             }
+
+            fn struct_layout(&mut self, id: usize) -> Option<Arc<RtStructLayout>> {
+                self.interp.struct_layouts.get(id).cloned()
+            }
         }
 
         let mut ctx = InterpContext { interp: self };
@@ -484,6 +515,7 @@ impl<'a> IrInterpreter<'a> {
             IrType::Int => matches!(value, RtValue::Int(_)),
             IrType::Float => matches!(value, RtValue::Float(_)),
             IrType::Bool => matches!(value, RtValue::Bool(_)),
+            IrType::Char => matches!(value, RtValue::Char(_)),
             IrType::String => matches!(value, RtValue::String(_)),
             IrType::Bytes => matches!(value, RtValue::Bytes(_)),
             IrType::Void => matches!(value, RtValue::Unit),
@@ -517,15 +549,30 @@ impl<'a> IrInterpreter<'a> {
         right: RtValue,
     ) -> Result<RtValue, IrInterpError> {
         match (op, left, right) {
-            (BinaryOp::Add, RtValue::Int(a), RtValue::Int(b)) => Ok(RtValue::Int(a + b)),
-            (BinaryOp::Sub, RtValue::Int(a), RtValue::Int(b)) => Ok(RtValue::Int(a - b)),
-            (BinaryOp::Mul, RtValue::Int(a), RtValue::Int(b)) => Ok(RtValue::Int(a * b)),
+            (BinaryOp::Add, RtValue::Int(a), RtValue::Int(b)) => a
+                .checked_add(b)
+                .map(RtValue::Int)
+                .ok_or(IrInterpError::IntegerOverflow),
+            (BinaryOp::Sub, RtValue::Int(a), RtValue::Int(b)) => a
+                .checked_sub(b)
+                .map(RtValue::Int)
+                .ok_or(IrInterpError::IntegerOverflow),
+            (BinaryOp::Mul, RtValue::Int(a), RtValue::Int(b)) => a
+                .checked_mul(b)
+                .map(RtValue::Int)
+                .ok_or(IrInterpError::IntegerOverflow),
             (BinaryOp::Div, RtValue::Int(_), RtValue::Int(0))
             | (BinaryOp::Mod, RtValue::Int(_), RtValue::Int(0)) => {
                 Err(IrInterpError::DivisionByZero)
             }
-            (BinaryOp::Div, RtValue::Int(a), RtValue::Int(b)) => Ok(RtValue::Int(a / b)),
-            (BinaryOp::Mod, RtValue::Int(a), RtValue::Int(b)) => Ok(RtValue::Int(a % b)),
+            (BinaryOp::Div, RtValue::Int(a), RtValue::Int(b)) => a
+                .checked_div(b)
+                .map(RtValue::Int)
+                .ok_or(IrInterpError::IntegerOverflow),
+            // `i64::MIN % -1` is mathematically 0, so remainder never overflows.
+            (BinaryOp::Mod, RtValue::Int(a), RtValue::Int(b)) => {
+                Ok(RtValue::Int(a.wrapping_rem(b)))
+            }
             (BinaryOp::BitAnd, RtValue::Int(a), RtValue::Int(b)) => Ok(RtValue::Int(a & b)),
             (BinaryOp::BitOr, RtValue::Int(a), RtValue::Int(b)) => Ok(RtValue::Int(a | b)),
             (BinaryOp::BitXor, RtValue::Int(a), RtValue::Int(b)) => Ok(RtValue::Int(a ^ b)),
@@ -574,6 +621,14 @@ impl<'a> IrInterpreter<'a> {
                 CmpOp::Ne => a != b,
                 _ => return Err(IrInterpError::TypeMismatch("unsupported bool comparison")),
             }),
+            (RtValue::Char(a), RtValue::Char(b)) => Ok(match op {
+                CmpOp::Eq => a == b,
+                CmpOp::Ne => a != b,
+                CmpOp::Lt => a < b,
+                CmpOp::Le => a <= b,
+                CmpOp::Gt => a > b,
+                CmpOp::Ge => a >= b,
+            }),
             (RtValue::String(a), RtValue::String(b)) => Ok(match op {
                 CmpOp::Eq => a.as_str() == b.as_str(),
                 CmpOp::Ne => a.as_str() != b.as_str(),
@@ -594,6 +649,16 @@ impl<'a> IrInterpreter<'a> {
                 CmpOp::Ne => a != b,
                 _ => return Err(IrInterpError::TypeMismatch("unsupported result comparison")),
             }),
+            (RtValue::Array(a), RtValue::Array(b)) => Ok(match op {
+                CmpOp::Eq => a == b,
+                CmpOp::Ne => a != b,
+                _ => return Err(IrInterpError::TypeMismatch("unsupported array comparison")),
+            }),
+            (RtValue::Struct(a), RtValue::Struct(b)) => Ok(match op {
+                CmpOp::Eq => a == b,
+                CmpOp::Ne => a != b,
+                _ => return Err(IrInterpError::TypeMismatch("unsupported struct comparison")),
+            }),
             _ => Err(IrInterpError::TypeMismatch("bad compare operands")),
         }
     }
@@ -603,6 +668,7 @@ impl<'a> IrInterpreter<'a> {
             ConstValue::Int(v) => RtValue::Int(*v),
             ConstValue::Float(v) => RtValue::Float(*v),
             ConstValue::Bool(v) => RtValue::Bool(*v),
+            ConstValue::Char(v) => RtValue::Char(*v),
             ConstValue::String(v) => RtValue::String(RtString::from(v.clone())),
             ConstValue::Unit => RtValue::Unit,
         }
@@ -1,25 +1,33 @@
 use std::fmt;
 use std::sync::Arc;
 
-use crate::ir::{BranchTerminator, FunctionId, IrProgram, IrType, Terminator};
-use skepart::{NoopHost, RtError, RtErrorKind, RtHost, RtStructLayout, RtValue};
+use crate::ir::{BranchTerminator, FunctionId, IrFunction, IrProgram, IrType, Terminator};
+use skepart::{
+    NoopHost, RtError, RtErrorKind, RtFunctionRegistry, RtHost, RtStructLayout, RtValue,
+};
 
 mod exec;
 mod frame;
+mod stats;
 
 use frame::Frame;
+use stats::StatsRecorder;
+pub use stats::{IrExecStats, IrFunctionStats};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IrInterpError {
     MissingMain,
     MissingFunction(FunctionId),
+    UnknownFunction(String),
     MissingBlock(crate::ir::BlockId),
     UnsupportedBuiltin(String),
     TypeMismatch(&'static str),
     DivisionByZero,
+    IntegerOverflow,
     InvalidOperand(&'static str),
     InvalidField(String),
     IndexOutOfBounds,
+    Panic(String),
 }
 
 impl fmt::Display for IrInterpError {
@@ -27,15 +35,18 @@ impl fmt::Display for IrInterpError {
         match self {
             Self::MissingMain => write!(f, "IR program has no main function"),
             Self::MissingFunction(id) => write!(f, "IR program is missing function {:?}", id),
+            Self::UnknownFunction(name) => write!(f, "IR program has no function named `{name}`"),
             Self::MissingBlock(id) => write!(f, "IR function is missing block {:?}", id),
             Self::UnsupportedBuiltin(name) => {
                 write!(f, "IR interpreter does not support builtin `{name}`")
             }
             Self::TypeMismatch(msg) => write!(f, "IR type mismatch: {msg}"),
             Self::DivisionByZero => write!(f, "IR division by zero"),
+            Self::IntegerOverflow => write!(f, "IR integer overflow"),
             Self::InvalidOperand(msg) => write!(f, "IR invalid operand: {msg}"),
             Self::InvalidField(name) => write!(f, "IR invalid field `{name}`"),
             Self::IndexOutOfBounds => write!(f, "IR index out of bounds"),
+            Self::Panic(msg) => write!(f, "panic: {msg}"),
         }
     }
 }
@@ -44,6 +55,7 @@ impl IrInterpError {
     fn from_runtime(err: RtError) -> Self {
         match err.kind {
             RtErrorKind::DivisionByZero => Self::DivisionByZero,
+            RtErrorKind::IntegerOverflow => Self::IntegerOverflow,
             RtErrorKind::IndexOutOfBounds => Self::IndexOutOfBounds,
             RtErrorKind::TypeMismatch => {
                 Self::TypeMismatch(Box::leak(err.message.into_boxed_str()))
@@ -56,15 +68,22 @@ impl IrInterpError {
                 Self::InvalidOperand(Box::leak(err.message.into_boxed_str()))
             }
             RtErrorKind::UnsupportedBuiltin => Self::UnsupportedBuiltin(err.message),
+            RtErrorKind::Panic => Self::Panic(err.message),
         }
     }
 }
 
 pub struct IrInterpreter<'a> {
     program: &'a IrProgram,
+    /// Functions sorted by id when the interpreter is created, so calls binary-search
+    /// this instance's table instead of scanning the program.
+    functions: Vec<&'a IrFunction>,
     globals: Vec<RtValue>,
     struct_layouts: Vec<Arc<RtStructLayout>>,
     host: Box<dyn RtHost>,
+    host_functions: RtFunctionRegistry,
+    initialized: bool,
+    stats: Option<StatsRecorder<'a>>,
 }
 
 impl<'a> IrInterpreter<'a> {
@@ -73,8 +92,11 @@ impl<'a> IrInterpreter<'a> {
     }
 
     pub fn with_host(program: &'a IrProgram, host: Box<dyn RtHost>) -> Self {
+        let mut functions = program.functions.iter().collect::<Vec<_>>();
+        functions.sort_by_key(|func| func.id);
         Self {
             program,
+            functions,
             globals: vec![RtValue::Unit; program.globals.len()],
             struct_layouts: program
                 .structs
@@ -96,13 +118,35 @@ impl<'a> IrInterpreter<'a> {
                 })
                 .collect(),
             host,
+            host_functions: RtFunctionRegistry::new(),
+            initialized: false,
+            stats: None,
         }
     }
 
-    pub fn run_main(mut self) -> Result<RtValue, IrInterpError> {
-        if let Some(init) = &self.program.module_init {
-            let _ = self.run_function(init.function, Vec::new())?;
+    /// Makes the refs from `functions` callable: Skepa code can call them like
+    /// any function value, and builtins such as `vec.map` accept them.
+    pub fn set_functions(&mut self, functions: RtFunctionRegistry) {
+        self.host_functions = functions;
+    }
+
+    /// Starts counting executed instructions, calls, and call sites. Read the
+    /// counts with [`IrInterpreter::stats`] after driving the program through
+    /// [`IrInterpreter::call`].
+    pub fn enable_stats(&mut self) {
+        if self.stats.is_none() {
+            self.stats = Some(StatsRecorder::default());
         }
+    }
+
+    /// Counts gathered since [`IrInterpreter::enable_stats`], or `None` when
+    /// statistics are off.
+    pub fn stats(&self) -> Option<&IrExecStats> {
+        self.stats.as_ref().map(|recorder| &recorder.stats)
+    }
+
+    pub fn run_main(mut self) -> Result<RtValue, IrInterpError> {
+        self.init()?;
         let main = self
             .program
             .functions
@@ -112,20 +156,68 @@ impl<'a> IrInterpreter<'a> {
         self.run_function(main.id, Vec::new())
     }
 
+    /// Runs the module initializer, which evaluates global initializers, unless it
+    /// already ran. [`IrInterpreter::call`] does this on first use.
+    pub fn init(&mut self) -> Result<(), IrInterpError> {
+        if self.initialized {
+            return Ok(());
+        }
+        self.initialized = true;
+        if let Some(init) = &self.program.module_init {
+            let _ = self.run_function(init.function, Vec::new())?;
+        }
+        Ok(())
+    }
+
+    /// Calls the function with IR name `name` (`module::fn` for functions of
+    /// imported project modules). Globals keep their values between calls, so an
+    /// embedder can call into a program repeatedly and inspect its state.
+    pub fn call(&mut self, name: &str, args: Vec<RtValue>) -> Result<RtValue, IrInterpError> {
+        self.init()?;
+        let function = self
+            .program
+            .functions
+            .iter()
+            .find(|func| func.name == name)
+            .ok_or_else(|| IrInterpError::UnknownFunction(name.to_string()))?;
+        self.run_function(function.id, args)
+    }
+
+    /// Current value of the global with IR name `name`; globals read as `Unit`
+    /// until [`IrInterpreter::init`] has run.
+    pub fn global(&self, name: &str) -> Option<&RtValue> {
+        let global = self.program.globals.iter().find(|g| g.name == name)?;
+        self.globals.get(global.id.0)
+    }
+
     fn run_function(
         &mut self,
         function_id: FunctionId,
         args: Vec<RtValue>,
     ) -> Result<RtValue, IrInterpError> {
         let func = self
-            .program
             .functions
-            .iter()
-            .find(|func| func.id == function_id)
-            .ok_or(IrInterpError::MissingFunction(function_id))?;
+            .binary_search_by_key(&function_id, |func| func.id)
+            .map(|idx| self.functions[idx])
+            .map_err(|_| IrInterpError::MissingFunction(function_id))?;
         if func.params.len() != args.len() {
             return Err(IrInterpError::InvalidOperand("call arity mismatch"));
         }
+        if let Some(stats) = &mut self.stats {
+            stats.enter(&func.name);
+        }
+        let result = self.run_blocks(func, args);
+        if let Some(stats) = &mut self.stats {
+            stats.exit();
+        }
+        result
+    }
+
+    fn run_blocks(
+        &mut self,
+        func: &'a IrFunction,
+        args: Vec<RtValue>,
+    ) -> Result<RtValue, IrInterpError> {
         let mut frame = Frame::new(func, args);
         let mut current_block = func.entry;
 
@@ -137,6 +229,9 @@ impl<'a> IrInterpreter<'a> {
                 .ok_or(IrInterpError::MissingBlock(current_block))?;
 
             for instr in &block.instrs {
+                if let Some(stats) = &mut self.stats {
+                    stats.instr(&func.name, instr);
+                }
                 self.exec_instr(func, &mut frame, instr)?;
             }
 
@@ -150,9 +245,7 @@ impl<'a> IrInterpreter<'a> {
                     });
                 }
                 Terminator::Panic { message } => {
-                    return Err(IrInterpError::InvalidOperand(Box::leak(
-                        message.clone().into_boxed_str(),
-                    )));
+                    return Err(IrInterpError::Panic(message.clone()));
                 }
                 Terminator::Unreachable => return Ok(RtValue::Unit),
             }
@@ -177,6 +270,7 @@ fn runtime_type_name(ty: &IrType) -> &'static str {
         IrType::Int => "Int",
         IrType::Float => "Float",
         IrType::Bool => "Bool",
+        IrType::Char => "Char",
         IrType::String => "String",
         IrType::Bytes => "Bytes",
         IrType::Option { .. } => "Option",
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use crate::ir::Instr;
+
+/// Caller recorded for calls that do not come from interpreted code.
+const RUNTIME_CALLER: &str = "<runtime>";
+
+/// Execution counts collected by an [`IrInterpreter`](super::IrInterpreter) after
+/// [`enable_stats`](super::IrInterpreter::enable_stats).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IrExecStats {
+    /// Executed instructions per opcode, see [`Instr::opcode`].
+    pub opcodes: HashMap<&'static str, u64>,
+    /// Calls and executed instructions per function, keyed by IR function name.
+    pub functions: HashMap<String, IrFunctionStats>,
+    /// Calls per `(caller, callee)` pair of IR function names. Calls made from
+    /// outside interpreted code, such as the entry call, use the caller `<runtime>`.
+    pub call_sites: HashMap<(String, String), u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IrFunctionStats {
+    pub calls: u64,
+    /// Instructions executed in the function body, excluding its callees.
+    pub instructions: u64,
+}
+
+impl IrExecStats {
+    /// Human-readable summary of the `limit` hottest opcodes, functions, and call sites.
+    pub fn report(&self, limit: usize) -> String {
+        let mut out = String::new();
+
+        let mut opcodes = self.opcodes.iter().collect::<Vec<_>>();
+        opcodes.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        out.push_str("hottest opcodes:\n");
+        for (opcode, count) in opcodes.into_iter().take(limit) {
+            let _ = writeln!(out, "  {opcode} {count}");
+        }
+
+        let mut functions = self.functions.iter().collect::<Vec<_>>();
+        functions.sort_by(|a, b| {
+            b.1.instructions
+                .cmp(&a.1.instructions)
+                .then(b.1.calls.cmp(&a.1.calls))
+                .then(a.0.cmp(b.0))
+        });
+        out.push_str("hottest functions:\n");
+        for (name, stats) in functions.into_iter().take(limit) {
+            let _ = writeln!(
+                out,
+                "  {name} calls={} instructions={}",
+                stats.calls, stats.instructions
+            );
+        }
+
+        let mut call_sites = self.call_sites.iter().collect::<Vec<_>>();
+        call_sites.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        out.push_str("hottest call sites:\n");
+        for ((caller, callee), count) in call_sites.into_iter().take(limit) {
+            let _ = writeln!(out, "  {caller} -> {callee} {count}");
+        }
+        out
+    }
+}
+
+/// Collects [`IrExecStats`] while tracking the active call stack, so each call
+/// can be attributed to its caller.
+#[derive(Default)]
+pub(super) struct StatsRecorder<'a> {
+    pub(super) stats: IrExecStats,
+    stack: Vec<&'a str>,
+}
+
+impl<'a> StatsRecorder<'a> {
+    pub(super) fn enter(&mut self, callee: &'a str) {
+        let caller = self.stack.last().copied().unwrap_or(RUNTIME_CALLER);
+        *self
+            .stats
+            .call_sites
+            .entry((caller.to_string(), callee.to_string()))
+            .or_default() += 1;
+        match self.stats.functions.get_mut(callee) {
+            Some(function) => function.calls += 1,
+            None => {
+                self.stats.functions.insert(
+                    callee.to_string(),
+                    IrFunctionStats {
+                        calls: 1,
+                        instructions: 0,
+                    },
+                );
+            }
+        }
+        self.stack.push(callee);
+    }
+
+    pub(super) fn exit(&mut self) {
+        self.stack.pop();
+    }
+
+    pub(super) fn instr(&mut self, function: &str, instr: &Instr) {
+        *self.stats.opcodes.entry(instr.opcode()).or_default() += 1;
+        if let Some(function) = self.stats.functions.get_mut(function) {
+            function.instructions += 1;
+        }
+    }
+}
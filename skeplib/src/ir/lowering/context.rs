@@ -18,6 +18,11 @@ pub(super) struct IrLowerer {
     pub(super) project_mode: bool,
     pub(super) lifted_functions: Vec<crate::ir::IrFunction>,
     pub(super) fn_lit_counter: usize,
+    /// Struct shapes synthesized for tuple types and generic struct
+    /// instantiations, appended to the program at the end.
+    pub(super) synthesized_structs: Vec<crate::ir::IrStruct>,
+    /// Generic struct declarations by runtime name, instantiated on first use.
+    pub(super) generic_structs: HashMap<String, crate::ast::StructDecl>,
 }
 
 pub(super) struct FunctionLowering {
@@ -25,6 +30,9 @@ pub(super) struct FunctionLowering {
     pub(super) locals: HashMap<String, crate::ir::LocalId>,
     pub(super) scratch_counter: usize,
     pub(super) loops: Vec<LoopLowering>,
+    /// Registered `defer` expressions, each with the locals in scope at the
+    /// `defer` statement so later shadowing does not change what they name.
+    pub(super) defers: Vec<(crate::ast::Expr, HashMap<String, crate::ir::LocalId>)>,
 }
 
 pub(super) struct LoopLowering {
@@ -32,6 +40,14 @@ pub(super) struct LoopLowering {
     pub(super) break_block: BlockId,
 }
 
+/// Blocks a match arm is lowered between: the arm's test runs in `dispatch`,
+/// a match continues in `body`, and a failed pattern or guard goes to `next`.
+pub(super) struct MatchArmBlocks {
+    pub(super) dispatch: BlockId,
+    pub(super) body: BlockId,
+    pub(super) next: BlockId,
+}
+
 #[derive(Clone)]
 pub(super) struct FunctionSig {
     pub(super) id: crate::ir::FunctionId,
@@ -64,6 +80,8 @@ impl IrLowerer {
             project_mode: false,
             lifted_functions: Vec::new(),
             fn_lit_counter: 0,
+            synthesized_structs: Vec::new(),
+            generic_structs: HashMap::new(),
         }
     }
 
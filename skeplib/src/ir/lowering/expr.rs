@@ -1,7 +1,7 @@
 use crate::ast::{BinaryOp as AstBinaryOp, Expr, UnaryOp as AstUnaryOp};
 use crate::ir::{BranchTerminator, ConstValue, Instr, IrType, Operand, Terminator, UnaryOp};
 
-use super::context::{FunctionLowering, IrLowerer};
+use super::context::{FunctionLowering, IrLowerer, MatchArmBlocks};
 
 impl IrLowerer {
     fn expr_to_path_parts(expr: &Expr) -> Option<Vec<String>> {
@@ -33,6 +33,7 @@ impl IrLowerer {
                 }
             },
             Expr::BoolLit(value) => Some(Operand::Const(ConstValue::Bool(*value))),
+            Expr::CharLit(value) => Some(Operand::Const(ConstValue::Char(*value))),
             Expr::StringLit(value) => Some(Operand::Const(ConstValue::String(value.clone()))),
             Expr::Ident(name) => lowering
                 .locals
@@ -132,6 +133,60 @@ impl IrLowerer {
                 );
                 Some(Operand::Temp(dst))
             }
+            Expr::TupleLit(items) => {
+                let mut lowered_items = Vec::with_capacity(items.len());
+                for item in items {
+                    lowered_items.push(self.compile_expr(func, lowering, item)?);
+                }
+                let item_types = lowered_items
+                    .iter()
+                    .map(|item| self.infer_operand_type(func, item))
+                    .collect();
+                let (struct_id, ty) = self.tuple_struct(item_types);
+                let dst = self.builder.push_temp(func, ty);
+                self.builder.push_instr(
+                    func,
+                    lowering.current_block,
+                    Instr::MakeStruct {
+                        dst,
+                        struct_id,
+                        fields: lowered_items,
+                    },
+                );
+                Some(Operand::Temp(dst))
+            }
+            Expr::VecLit(items) => {
+                let mut lowered_items = Vec::with_capacity(items.len());
+                for item in items {
+                    lowered_items.push(self.compile_expr(func, lowering, item)?);
+                }
+                let elem_ty = lowered_items
+                    .first()
+                    .map(|item| self.infer_operand_type(func, item))
+                    .unwrap_or(IrType::Unknown);
+                let dst = self.builder.push_temp(
+                    func,
+                    IrType::Vec {
+                        elem: Box::new(elem_ty.clone()),
+                    },
+                );
+                self.builder.push_instr(
+                    func,
+                    lowering.current_block,
+                    Instr::VecNew { dst, elem_ty },
+                );
+                for value in lowered_items {
+                    self.builder.push_instr(
+                        func,
+                        lowering.current_block,
+                        Instr::VecPush {
+                            vec: Operand::Temp(dst),
+                            value,
+                        },
+                    );
+                }
+                Some(Operand::Temp(dst))
+            }
             Expr::ArrayRepeat { value, size } => {
                 let value = self.compile_expr(func, lowering, value)?;
                 let elem_ty = self.infer_operand_type(func, &value);
@@ -152,8 +207,16 @@ impl IrLowerer {
                 );
                 Some(Operand::Temp(dst))
             }
-            Expr::StructLit { name, fields } => {
-                let runtime_name = self.resolve_struct_runtime_name(name);
+            Expr::StructLit {
+                name,
+                type_args,
+                fields,
+            } => {
+                let runtime_name = if type_args.is_empty() {
+                    self.resolve_struct_runtime_name(name)
+                } else {
+                    self.generic_struct(name, type_args)?
+                };
                 let Some((struct_id, struct_fields)) = self.structs.get(&runtime_name).cloned()
                 else {
                     self.unsupported(format!("unknown struct `{name}` in IR lowering"));
@@ -196,6 +259,15 @@ impl IrLowerer {
             Expr::Index { base, index } => {
                 let array = self.compile_expr(func, lowering, base)?;
                 let index = self.compile_expr(func, lowering, index)?;
+                if self.infer_operand_type(func, &array) == IrType::String {
+                    return Some(self.emit_str_builtin(
+                        func,
+                        lowering,
+                        "__index",
+                        IrType::String,
+                        vec![array, index],
+                    ));
+                }
                 let elem_ty = self.array_element_type(func, &array);
                 let dst = self.builder.push_temp(func, elem_ty.clone());
                 // Array and Vec share trapping subscript semantics (element type T).
@@ -213,6 +285,30 @@ impl IrLowerer {
                 );
                 Some(Operand::Temp(dst))
             }
+            Expr::Slice { base, start, end } => {
+                let value = self.compile_expr(func, lowering, base)?;
+                let start = match start {
+                    Some(start) => self.compile_expr(func, lowering, start)?,
+                    None => Operand::Const(ConstValue::Int(0)),
+                };
+                let end = match end {
+                    Some(end) => self.compile_expr(func, lowering, end)?,
+                    None => self.emit_str_builtin(
+                        func,
+                        lowering,
+                        "len",
+                        IrType::Int,
+                        vec![value.clone()],
+                    ),
+                };
+                Some(self.emit_str_builtin(
+                    func,
+                    lowering,
+                    "__slice",
+                    IrType::String,
+                    vec![value, start, end],
+                ))
+            }
             Expr::Group(inner) => self.compile_expr(func, lowering, inner),
             Expr::Unary { op, expr } => {
                 let operand = self.compile_expr(func, lowering, expr)?;
@@ -245,6 +341,17 @@ impl IrLowerer {
                 }
                 let left = self.compile_expr(func, lowering, left)?;
                 let right = self.compile_expr(func, lowering, right)?;
+                if let IrType::Named(struct_name) = self.infer_operand_type(func, &left)
+                    && let Some(method) = op.overload_method()
+                {
+                    return self.emit_method_call(
+                        func,
+                        lowering,
+                        &struct_name,
+                        method,
+                        vec![left, right],
+                    );
+                }
                 let ty = self.infer_binary_type(func, &left, op, &right);
                 let dst = self.builder.push_temp(func, ty.clone());
                 if let Some(op) = self.lower_binary_op(op) {
@@ -412,6 +519,11 @@ impl IrLowerer {
                 self.builder
                     .set_terminator(func, some_block, Terminator::Jump(join_block));
 
+                lowering.current_block = none_block;
+                if !self.compile_deferred(func, lowering) {
+                    return None;
+                }
+                let none_exit = lowering.current_block;
                 let none_temp = self.builder.push_temp(
                     func,
                     IrType::Option {
@@ -420,7 +532,7 @@ impl IrLowerer {
                 );
                 self.builder.push_instr(
                     func,
-                    none_block,
+                    none_exit,
                     Instr::CallBuiltin {
                         dst: Some(none_temp),
                         ret_ty: IrType::Option {
@@ -435,7 +547,7 @@ impl IrLowerer {
                 );
                 self.builder.set_terminator(
                     func,
-                    none_block,
+                    none_exit,
                     Terminator::Return(Some(Operand::Temp(none_temp))),
                 );
 
@@ -513,11 +625,16 @@ impl IrLowerer {
                 self.builder
                     .set_terminator(func, ok_block, Terminator::Jump(join_block));
 
+                lowering.current_block = err_block;
+                if !self.compile_deferred(func, lowering) {
+                    return None;
+                }
+                let err_exit = lowering.current_block;
                 let propagated_err_ty = (**err_ty).clone();
                 let unwrap_err_temp = self.builder.push_temp(func, propagated_err_ty.clone());
                 self.builder.push_instr(
                     func,
-                    err_block,
+                    err_exit,
                     Instr::CallBuiltin {
                         dst: Some(unwrap_err_temp),
                         ret_ty: propagated_err_ty.clone(),
@@ -535,7 +652,7 @@ impl IrLowerer {
                 let err_result_temp = self.builder.push_temp(func, err_result_ty.clone());
                 self.builder.push_instr(
                     func,
-                    err_block,
+                    err_exit,
                     Instr::CallBuiltin {
                         dst: Some(err_result_temp),
                         ret_ty: err_result_ty,
@@ -548,7 +665,7 @@ impl IrLowerer {
                 );
                 self.builder.set_terminator(
                     func,
-                    err_block,
+                    err_exit,
                     Terminator::Return(Some(Operand::Temp(err_result_temp))),
                 );
 
@@ -614,31 +731,19 @@ impl IrLowerer {
                     .push_block(func, format!("match_expr_next_{index}"))
             };
 
-            if matches!(arm.pattern, crate::ast::MatchPattern::Wildcard) {
-                self.builder
-                    .set_terminator(func, dispatch_block, Terminator::Jump(body_block));
-            } else {
-                let cond = self.compile_match_condition(
-                    func,
-                    dispatch_block,
-                    match_local,
-                    &target_ty,
-                    &arm.pattern,
-                )?;
-                self.builder.set_terminator(
-                    func,
-                    dispatch_block,
-                    Terminator::Branch(BranchTerminator {
-                        cond,
-                        then_block: body_block,
-                        else_block: next_block,
-                    }),
-                );
-            }
-
-            lowering.current_block = body_block;
             let saved_locals = lowering.locals.clone();
-            if !self.bind_match_pattern(func, lowering, match_local, &target_ty, &arm.pattern) {
+            if !self.enter_match_arm(
+                func,
+                lowering,
+                MatchArmBlocks {
+                    dispatch: dispatch_block,
+                    body: body_block,
+                    next: next_block,
+                },
+                Operand::Local(match_local),
+                &arm.pattern,
+                arm.guard.as_ref(),
+            ) {
                 return None;
             }
             let arm_value = self.compile_expr(func, lowering, &arm.expr)?;
@@ -662,6 +767,31 @@ impl IrLowerer {
         Some(Operand::Local(result_local))
     }
 
+    fn emit_str_builtin(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &FunctionLowering,
+        name: &str,
+        ret_ty: IrType,
+        args: Vec<Operand>,
+    ) -> Operand {
+        let dst = self.builder.push_temp(func, ret_ty.clone());
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::CallBuiltin {
+                dst: Some(dst),
+                ret_ty,
+                builtin: crate::ir::BuiltinCall {
+                    package: "str".to_string(),
+                    name: name.to_string(),
+                },
+                args,
+            },
+        );
+        Operand::Temp(dst)
+    }
+
     fn expr_type(
         &self,
         func: &crate::ir::IrFunction,
@@ -18,7 +18,7 @@ impl IrLowerer {
         }
     }
 
-    fn is_builtin_member_expr(expr: &Expr, package: &str, member: &str) -> bool {
+    pub(super) fn is_builtin_member_expr(expr: &Expr, package: &str, member: &str) -> bool {
         match expr {
             Expr::Path(parts) => parts.len() == 2 && parts[0] == package && parts[1] == member,
             Expr::Field { base, field } => {
@@ -64,6 +64,23 @@ impl IrLowerer {
     ) -> Option<Operand> {
         if let Expr::Ident(name) = callee {
             match (name.as_str(), args) {
+                ("panic", [message_expr]) => {
+                    let message = self.compile_expr(func, lowering, message_expr)?;
+                    self.builder.push_instr(
+                        func,
+                        lowering.current_block,
+                        Instr::CallBuiltin {
+                            dst: None,
+                            ret_ty: IrType::Void,
+                            builtin: crate::ir::BuiltinCall {
+                                package: "core".to_string(),
+                                name: "panic".to_string(),
+                            },
+                            args: vec![message],
+                        },
+                    );
+                    return OkOperand::from_call_result(None);
+                }
                 ("Some", [value_expr]) => {
                     let value = self.compile_expr(func, lowering, value_expr)?;
                     let value_ty = self.infer_operand_type(func, &value);
@@ -156,6 +173,10 @@ impl IrLowerer {
             }
         }
 
+        if let Some(name) = Self::iter_member(lowering, callee) {
+            return self.compile_iter_call(func, lowering, name, args);
+        }
+
         let mut lowered_args = Vec::with_capacity(args.len());
         for arg in args {
             lowered_args.push(self.compile_expr(func, lowering, arg)?);
@@ -212,7 +233,11 @@ impl IrLowerer {
                         || self.globals.contains_key(package)
                         || self.globals.contains_key(&self.qualify_name(package))
                         || self.functions.contains_key(package);
-                    if package == "vec" {
+                    // Callback-taking vec builtins have no dedicated instruction and
+                    // lower as ordinary runtime builtin calls below.
+                    if package == "vec"
+                        && !matches!(field.as_str(), "map" | "filter" | "reduce" | "sort")
+                    {
                         return self.compile_vec_call(
                             func,
                             lowering.current_block,
@@ -243,7 +268,17 @@ impl IrLowerer {
                         );
                         return OkOperand::from_call_result(dst);
                     }
+                    if !is_value_receiver && package == "serde" {
+                        return self.compile_serde_call(func, lowering, field, lowered_args);
+                    }
+                    if !is_value_receiver && package == "net" && field == "recvFrom" {
+                        return self.compile_net_recv_from(func, lowering, lowered_args);
+                    }
                     if !is_value_receiver {
+                        if package == "io" {
+                            lowered_args =
+                                self.apply_to_string_methods(func, lowering, field, lowered_args)?;
+                        }
                         let ret_ty = self
                             .builtin_return_type(func, package, field, &lowered_args)
                             .unwrap_or(IrType::Unknown);
@@ -293,6 +328,37 @@ impl IrLowerer {
         }
     }
 
+    /// `net.recvFrom(socket, max)`: the `(Bytes, String)` tuple it returns is
+    /// a program struct, so its id is passed after the arguments.
+    fn compile_net_recv_from(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        mut args: Vec<Operand>,
+    ) -> Option<Operand> {
+        let (struct_id, tuple_ty) = self.tuple_struct(vec![IrType::Bytes, IrType::String]);
+        args.push(Operand::Const(ConstValue::Int(struct_id.0 as i64)));
+        let ret_ty = IrType::Result {
+            ok: Box::new(tuple_ty),
+            err: Box::new(IrType::String),
+        };
+        let dst = self.builder.push_temp(func, ret_ty.clone());
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::CallBuiltin {
+                dst: Some(dst),
+                ret_ty,
+                builtin: crate::ir::BuiltinCall {
+                    package: "net".to_string(),
+                    name: "recvFrom".to_string(),
+                },
+                args,
+            },
+        );
+        Some(Operand::Temp(dst))
+    }
+
     fn compile_extern_call(
         &mut self,
         func: &mut crate::ir::IrFunction,
@@ -518,16 +584,67 @@ impl IrLowerer {
             );
             return None;
         };
-        let method_name = Self::mangle_method_name(&struct_name, field);
+        let mut call_args = Vec::with_capacity(args.len() + 1);
+        call_args.push(receiver);
+        call_args.append(&mut args);
+        self.emit_method_call(func, lowering, &struct_name, field, call_args)
+    }
+
+    /// Replaces struct arguments of `io.printAny` and the `io.format` family with
+    /// the result of their `toString(self) -> String` method, when they have one.
+    fn apply_to_string_methods(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        name: &str,
+        args: Vec<Operand>,
+    ) -> Option<Vec<Operand>> {
+        let first_value = match name {
+            "printAny" => 0,
+            "format" | "printf" => 1,
+            _ => return Some(args),
+        };
+        let mut rendered = Vec::with_capacity(args.len());
+        for (idx, arg) in args.into_iter().enumerate() {
+            let IrType::Named(struct_name) = self.infer_operand_type(func, &arg) else {
+                rendered.push(arg);
+                continue;
+            };
+            let has_to_string = self
+                .functions
+                .get(&Self::mangle_method_name(&struct_name, "toString"))
+                .is_some_and(|sig| sig.params.len() == 1 && sig.ret == IrType::String);
+            if idx < first_value || !has_to_string {
+                rendered.push(arg);
+                continue;
+            }
+            rendered.push(self.emit_method_call(
+                func,
+                lowering,
+                &struct_name,
+                "toString",
+                vec![arg],
+            )?);
+        }
+        Some(rendered)
+    }
+
+    /// Emits a direct call to `struct_name::method`; `call_args` starts with the receiver.
+    pub(super) fn emit_method_call(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        struct_name: &str,
+        method: &str,
+        call_args: Vec<Operand>,
+    ) -> Option<Operand> {
+        let method_name = Self::mangle_method_name(struct_name, method);
         let Some(sig) = self.functions.get(&method_name).cloned() else {
             self.unsupported(format!(
-                "unknown method `{field}` for struct `{struct_name}` in IR lowering"
+                "unknown method `{method}` for struct `{struct_name}` in IR lowering"
             ));
             return None;
         };
-        let mut call_args = Vec::with_capacity(args.len() + 1);
-        call_args.push(receiver);
-        call_args.append(&mut args);
         let dst = if sig.ret.is_void() {
             None
         } else {
@@ -557,10 +674,14 @@ impl IrLowerer {
         let Some(crate::ast::TypeName::Vec { elem }) = ty else {
             return None;
         };
-        let Expr::Call { callee, args } = value else {
-            return None;
+        let is_empty_vec = match value {
+            Expr::Call { callee, args } => {
+                args.is_empty() && Self::is_builtin_member_expr(callee, "vec", "new")
+            }
+            Expr::VecLit(items) => items.is_empty(),
+            _ => false,
         };
-        if !args.is_empty() || !Self::is_builtin_member_expr(callee, "vec", "new") {
+        if !is_empty_vec {
             return None;
         }
 
@@ -871,6 +992,30 @@ impl IrLowerer {
                     err: Box::new(IrType::String),
                 });
             }
+            ("net", "bindUdp") => {
+                return Some(IrType::Result {
+                    ok: Box::new(IrType::Opaque("net.UdpSocket".to_string())),
+                    err: Box::new(IrType::String),
+                });
+            }
+            ("net", "recv") => {
+                return Some(IrType::Result {
+                    ok: Box::new(IrType::Bytes),
+                    err: Box::new(IrType::String),
+                });
+            }
+            ("net", "udpConnect") | ("net", "send") | ("net", "sendTo") => {
+                return Some(IrType::Result {
+                    ok: Box::new(IrType::Void),
+                    err: Box::new(IrType::String),
+                });
+            }
+            ("net", "udpLocalAddr") => {
+                return Some(IrType::Result {
+                    ok: Box::new(IrType::String),
+                    err: Box::new(IrType::String),
+                });
+            }
             ("net", "parseUrl") => {
                 return Some(IrType::Result {
                     ok: Box::new(IrType::Map {
@@ -921,7 +1066,7 @@ impl IrLowerer {
                     err: Box::new(IrType::String),
                 });
             }
-            ("datetime", "parseUnix") => {
+            ("datetime", "parseUnix") | ("datetime", "parse") => {
                 return Some(IrType::Result {
                     ok: Box::new(IrType::Int),
                     err: Box::new(IrType::String),
@@ -939,8 +1084,17 @@ impl IrLowerer {
                     err: Box::new(IrType::String),
                 });
             }
+            ("fs", "readBytes") => {
+                return Some(IrType::Result {
+                    ok: Box::new(IrType::Bytes),
+                    err: Box::new(IrType::String),
+                });
+            }
             ("fs", "writeText")
             | ("fs", "appendText")
+            | ("fs", "writeBytes")
+            | ("fs", "copyFile")
+            | ("fs", "moveFile")
             | ("fs", "mkdirAll")
             | ("fs", "removeFile")
             | ("fs", "removeDirAll") => {
@@ -971,11 +1125,19 @@ impl IrLowerer {
                     err: Box::new(IrType::String),
                 });
             }
+            ("os", "execCapture") => {
+                return Some(IrType::Result {
+                    ok: Box::new(IrType::Map {
+                        value: Box::new(IrType::String),
+                    }),
+                    err: Box::new(IrType::String),
+                });
+            }
             ("task", "__testTask") => {
                 let value = args.first()?;
                 return Some(IrType::Opaque(format!(
                     "task.Task[{}]",
-                    self.display_ir_type(&self.infer_operand_type(func, value))
+                    self.infer_operand_type(func, value).source_name()
                 )));
             }
             ("task", "__testChannel") => {
@@ -986,10 +1148,7 @@ impl IrLowerer {
                 if let IrType::Fn { params, ret } = self.infer_operand_type(func, function)
                     && params.is_empty()
                 {
-                    return Some(IrType::Opaque(format!(
-                        "task.Task[{}]",
-                        self.display_ir_type(&ret)
-                    )));
+                    return Some(IrType::Opaque(format!("task.Task[{}]", ret.source_name())));
                 }
             }
             ("task", "join") => {
@@ -1035,18 +1194,67 @@ impl IrLowerer {
                     value: Box::new(IrType::Int),
                 });
             }
-            ("bytes", "toString") => {
+            ("bytes", "toString") | ("bytes", "decode") => {
                 return Some(IrType::Result {
                     ok: Box::new(IrType::String),
                     err: Box::new(IrType::String),
                 });
             }
+            ("bytes", "fromHex") | ("bytes", "encode") => {
+                return Some(IrType::Result {
+                    ok: Box::new(IrType::Bytes),
+                    err: Box::new(IrType::String),
+                });
+            }
             ("vec", "get") => {
                 let vec = args.first()?;
                 if let IrType::Vec { elem } = self.infer_operand_type(func, vec) {
                     return Some(IrType::Option { value: elem });
                 }
             }
+            ("random", "choice") => {
+                let array = args.first()?;
+                if let IrType::Array { elem, .. } = self.infer_operand_type(func, array) {
+                    return Some(IrType::Option { value: elem });
+                }
+            }
+            ("random", "shuffle") | ("arr", "sortBy") => {
+                let array = args.first()?;
+                let ty = self.infer_operand_type(func, array);
+                if matches!(ty, IrType::Array { .. }) {
+                    return Some(ty);
+                }
+            }
+            ("vec", "map") | ("arr", "map") => {
+                let source = self.infer_operand_type(func, args.first()?);
+                let IrType::Fn { ret, .. } = self.infer_operand_type(func, args.get(1)?) else {
+                    return None;
+                };
+                return match source {
+                    IrType::Array { size, .. } => Some(IrType::Array { elem: ret, size }),
+                    _ => Some(IrType::Vec { elem: ret }),
+                };
+            }
+            ("vec", "filter") => {
+                let vec = self.infer_operand_type(func, args.first()?);
+                if matches!(vec, IrType::Vec { .. }) {
+                    return Some(vec);
+                }
+            }
+            ("arr", "filter") => {
+                if let IrType::Array { elem, .. } = self.infer_operand_type(func, args.first()?) {
+                    return Some(IrType::Vec { elem });
+                }
+            }
+            ("vec", "reduce") => {
+                if let IrType::Fn { ret, .. } = self.infer_operand_type(func, args.get(2)?) {
+                    return Some(*ret);
+                }
+            }
+            ("vec", "sort") => return Some(IrType::Void),
+            ("arr", "len") | ("arr", "indexOf") | ("arr", "count") => return Some(IrType::Int),
+            ("arr", "isEmpty") | ("arr", "contains") => return Some(IrType::Bool),
+            ("arr", "join") => return Some(IrType::String),
             ("arr", "first") | ("arr", "last") => {
                 let array = args.first()?;
                 if let IrType::Array { elem, .. } = self.infer_operand_type(func, array) {
@@ -1065,7 +1273,11 @@ impl IrLowerer {
         Some(IrType::from(&spec.sig.ret))
     }
 
-    fn indirect_call_return_type(&self, func: &crate::ir::IrFunction, callee: &Operand) -> IrType {
+    pub(super) fn indirect_call_return_type(
+        &self,
+        func: &crate::ir::IrFunction,
+        callee: &Operand,
+    ) -> IrType {
         match self.infer_operand_type(func, callee) {
             IrType::Fn { ret, .. } => (*ret).clone(),
             _ => IrType::Unknown,
@@ -1112,6 +1324,8 @@ impl IrLowerer {
             }
             ([IrType::String, IrType::Int], IrType::Int) => "cstr,usize->usize",
             ([IrType::Int, IrType::Int], IrType::Int) => "i64,i64->i64",
+            ([IrType::Float], IrType::Float) => "f64->f64",
+            ([IrType::Float, IrType::Float], IrType::Float) => "f64,f64->f64",
             ([IrType::Bytes], IrType::Int) => "bytes->usize",
             ([IrType::Bytes, IrType::Int], IrType::Int) => "bytes,usize->usize",
             _ => return None,
@@ -1131,39 +1345,7 @@ impl OkOperand {
     }
 }
 
-impl IrLowerer {
-    fn display_ir_type(&self, value: &IrType) -> String {
-        let _ = self;
-        match value {
-            IrType::Int => "Int".to_string(),
-            IrType::Float => "Float".to_string(),
-            IrType::Bool => "Bool".to_string(),
-            IrType::String => "String".to_string(),
-            IrType::Bytes => "Bytes".to_string(),
-            IrType::Void => "Void".to_string(),
-            IrType::Option { value } => format!("Option[{}]", self.display_ir_type(value)),
-            IrType::Result { ok, err } => format!(
-                "Result[{}, {}]",
-                self.display_ir_type(ok),
-                self.display_ir_type(err)
-            ),
-            IrType::Named(name) | IrType::Opaque(name) => name.clone(),
-            IrType::Array { elem, size } => format!("[{}; {}]", self.display_ir_type(elem), size),
-            IrType::Vec { elem } => format!("Vec[{}]", self.display_ir_type(elem)),
-            IrType::Map { value } => format!("Map[String, {}]", self.display_ir_type(value)),
-            IrType::Fn { params, ret } => format!(
-                "Fn({}) -> {}",
-                params
-                    .iter()
-                    .map(|param| self.display_ir_type(param))
-                    .collect::<Vec<_>>()
-                    .join(", "),
-                self.display_ir_type(ret)
-            ),
-            IrType::Unknown => "Unknown".to_string(),
-        }
-    }
-}
+impl IrLowerer {}
 
 #[cfg(test)]
 mod tests {
@@ -15,6 +15,7 @@ impl IrLowerer {
             Operand::Const(ConstValue::Int(_)) => IrType::Int,
             Operand::Const(ConstValue::Float(_)) => IrType::Float,
             Operand::Const(ConstValue::Bool(_)) => IrType::Bool,
+            Operand::Const(ConstValue::Char(_)) => IrType::Char,
             Operand::Const(ConstValue::String(_)) => IrType::String,
             Operand::Const(ConstValue::Unit) => IrType::Void,
             Operand::Temp(id) => func
@@ -180,15 +181,16 @@ impl IrLowerer {
         self.fn_lit_counter += 1;
         let name = format!("__fn_lit_{}", self.fn_lit_counter);
         let ret_ty = self.lower_type_name(return_type);
-        let function_id = crate::ir::FunctionId(self.functions.len() + self.lifted_functions.len());
+        let param_sig = params
+            .iter()
+            .map(|param| self.lower_type_name(&param.ty))
+            .collect();
+        let function_id = crate::ir::FunctionId(self.functions.len());
         self.functions.insert(
             name.clone(),
             super::context::FunctionSig {
                 id: function_id,
-                params: params
-                    .iter()
-                    .map(|param| self.lower_type_name(&param.ty))
-                    .collect(),
+                params: param_sig,
                 ret: ret_ty.clone(),
             },
         );
@@ -200,6 +202,7 @@ impl IrLowerer {
             locals: HashMap::new(),
             scratch_counter: 0,
             loops: Vec::new(),
+            defers: Vec::new(),
         };
         let mut param_types = Vec::with_capacity(params.len());
         for param in params {
@@ -232,6 +235,9 @@ impl IrLowerer {
                 );
                 return None;
             };
+            if !self.compile_deferred(&mut lifted, &mut lowering) {
+                return None;
+            }
             self.builder
                 .set_terminator(&mut lifted, lowering.current_block, terminator);
         }
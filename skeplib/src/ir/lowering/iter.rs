@@ -0,0 +1,507 @@
+use crate::ast::{Expr, Stmt};
+use crate::ir::{
+    BinaryOp, BlockId, BranchTerminator, CmpOp, ConstValue, Instr, IrType, LocalId, Operand,
+    Terminator,
+};
+
+use super::context::{FunctionLowering, IrLowerer, LoopLowering};
+
+/// Where a fused pipeline draws elements from. Every source walks an `Int`
+/// cursor up to an exclusive end.
+enum IterSource {
+    Range { end: LocalId },
+    Array { array: LocalId, size: usize },
+    Vec { vec: LocalId },
+}
+
+/// An `iter` adapter with its argument already evaluated into a local.
+enum IterStage {
+    Map { callback: LocalId, ret: IrType },
+    Filter { callback: LocalId },
+    Take { limit: LocalId, taken: LocalId },
+}
+
+/// A pipeline whose source and adapter arguments have been evaluated, ready to
+/// be opened as a single loop.
+struct IterPipeline {
+    source: IterSource,
+    cursor: LocalId,
+    stages: Vec<IterStage>,
+    elem_ty: IrType,
+}
+
+/// An opened pipeline loop. The current block holds `value`, the next element;
+/// jumping to `head` moves on to the element after it.
+struct IterLoop {
+    head: BlockId,
+    exit: BlockId,
+    value: Operand,
+}
+
+impl IrLowerer {
+    /// The `iter` function named by `callee`, unless a local shadows the package.
+    pub(super) fn iter_member<'e>(
+        lowering: &FunctionLowering,
+        callee: &'e Expr,
+    ) -> Option<&'e str> {
+        if lowering.locals.contains_key("iter") {
+            return None;
+        }
+        match callee {
+            Expr::Path(parts) if parts.len() == 2 && parts[0] == "iter" => Some(&parts[1]),
+            Expr::Field { base, field } if matches!(&**base, Expr::Ident(pkg) if pkg == "iter") => {
+                Some(field)
+            }
+            _ => None,
+        }
+    }
+
+    /// `iter.collect` is the only `iter` call that produces a value; the rest are
+    /// fused into the loop that consumes them and never exist at runtime.
+    pub(super) fn compile_iter_call(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        name: &str,
+        args: &[Expr],
+    ) -> Option<Operand> {
+        let [source] = args else {
+            self.unsupported(format!(
+                "iter.{name} must be consumed by a for-in loop or iter.collect"
+            ));
+            return None;
+        };
+        if name != "collect" {
+            self.unsupported(format!(
+                "iter.{name} must be consumed by a for-in loop or iter.collect"
+            ));
+            return None;
+        }
+
+        let pipeline = self.prepare_iter_pipeline(func, lowering, source)?;
+        let vec_ty = IrType::Vec {
+            elem: Box::new(pipeline.elem_ty.clone()),
+        };
+        let vec = self.iter_scratch_local(func, lowering, "collect", vec_ty.clone());
+        let dst = self.builder.push_temp(func, vec_ty.clone());
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::VecNew {
+                dst,
+                elem_ty: pipeline.elem_ty.clone(),
+            },
+        );
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::StoreLocal {
+                local: vec,
+                ty: vec_ty,
+                value: Operand::Temp(dst),
+            },
+        );
+
+        let iter_loop = self.open_iter_loop(func, lowering, pipeline);
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::VecPush {
+                vec: Operand::Local(vec),
+                value: iter_loop.value,
+            },
+        );
+        self.builder.set_terminator(
+            func,
+            lowering.current_block,
+            Terminator::Jump(iter_loop.head),
+        );
+        lowering.current_block = iter_loop.exit;
+        Some(Operand::Local(vec))
+    }
+
+    pub(super) fn compile_for_in(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        name: &str,
+        iterable: &Expr,
+        body: &[Stmt],
+    ) -> bool {
+        let Some(pipeline) = self.prepare_iter_pipeline(func, lowering, iterable) else {
+            return false;
+        };
+        let saved_locals = lowering.locals.clone();
+        let elem_ty = pipeline.elem_ty.clone();
+        let iter_loop = self.open_iter_loop(func, lowering, pipeline);
+        let local = self
+            .builder
+            .push_local(func, name.to_string(), elem_ty.clone());
+        lowering.locals.insert(name.to_string(), local);
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::StoreLocal {
+                local,
+                ty: elem_ty,
+                value: iter_loop.value,
+            },
+        );
+
+        lowering.loops.push(LoopLowering {
+            continue_block: iter_loop.head,
+            break_block: iter_loop.exit,
+        });
+        if !self.compile_stmt_list(func, lowering, body) {
+            lowering.loops.pop();
+            return false;
+        }
+        lowering.loops.pop();
+        self.ensure_fallthrough_jump(func, lowering.current_block, iter_loop.head);
+        lowering.locals = saved_locals;
+        lowering.current_block = iter_loop.exit;
+        true
+    }
+
+    /// Evaluates the source and every adapter argument of `expr` once, innermost
+    /// first, in the current block.
+    fn prepare_iter_pipeline(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        expr: &Expr,
+    ) -> Option<IterPipeline> {
+        let mut adapters = Vec::new();
+        let mut source = expr;
+        let mut range = None;
+        while let Expr::Call { callee, args } = source
+            && let Some(name) = Self::iter_member(lowering, callee)
+        {
+            match (name, args.as_slice()) {
+                ("map" | "filter" | "take", [inner, arg]) => {
+                    adapters.push((name, arg));
+                    source = inner;
+                }
+                ("range", [start, end]) => {
+                    range = Some((start, end));
+                    break;
+                }
+                _ => {
+                    self.unsupported(format!("iter.{name} cannot be used as an iteration source"));
+                    return None;
+                }
+            }
+        }
+
+        let cursor = self.iter_scratch_local(func, lowering, "cursor", IrType::Int);
+        let (source, mut elem_ty, start) = match range {
+            Some((start, end)) => {
+                let start = self.compile_expr(func, lowering, start)?;
+                let end_value = self.compile_expr(func, lowering, end)?;
+                let end = self.iter_store_scratch(func, lowering, "end", end_value);
+                (IterSource::Range { end }, IrType::Int, start)
+            }
+            None => {
+                let value = self.compile_expr(func, lowering, source)?;
+                let ty = self.infer_operand_type(func, &value);
+                let local = self.iter_store_scratch(func, lowering, "source", value);
+                let start = Operand::Const(ConstValue::Int(0));
+                match ty {
+                    IrType::Array { elem, size } => {
+                        (IterSource::Array { array: local, size }, *elem, start)
+                    }
+                    IrType::Vec { elem } => (IterSource::Vec { vec: local }, *elem, start),
+                    other => {
+                        self.unsupported(format!(
+                            "for-in over {} is not supported in IR lowering",
+                            other.source_name()
+                        ));
+                        return None;
+                    }
+                }
+            }
+        };
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::StoreLocal {
+                local: cursor,
+                ty: IrType::Int,
+                value: start,
+            },
+        );
+
+        let mut stages = Vec::with_capacity(adapters.len());
+        for (name, arg) in adapters.into_iter().rev() {
+            let value = self.compile_expr(func, lowering, arg)?;
+            stages.push(match name {
+                "map" => {
+                    let ret = self.indirect_call_return_type(func, &value);
+                    elem_ty = ret.clone();
+                    IterStage::Map {
+                        callback: self.iter_store_scratch(func, lowering, "map", value),
+                        ret,
+                    }
+                }
+                "filter" => IterStage::Filter {
+                    callback: self.iter_store_scratch(func, lowering, "filter", value),
+                },
+                _ => {
+                    let limit = self.iter_store_scratch(func, lowering, "limit", value);
+                    let taken = self.iter_store_scratch(
+                        func,
+                        lowering,
+                        "taken",
+                        Operand::Const(ConstValue::Int(0)),
+                    );
+                    IterStage::Take { limit, taken }
+                }
+            });
+        }
+
+        Some(IterPipeline {
+            source,
+            cursor,
+            stages,
+            elem_ty,
+        })
+    }
+
+    /// Emits the loop for `pipeline`. Every `take` limit is checked at the head,
+    /// before the source is advanced, so an exhausted `take` stops upstream work.
+    fn open_iter_loop(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        pipeline: IterPipeline,
+    ) -> IterLoop {
+        let head = self.builder.push_block(func, "for_in_head");
+        let exit = self.builder.push_block(func, "for_in_exit");
+        self.builder
+            .set_terminator(func, lowering.current_block, Terminator::Jump(head));
+        lowering.current_block = head;
+
+        for stage in &pipeline.stages {
+            if let IterStage::Take { limit, taken } = stage {
+                let open = self.iter_compare(
+                    func,
+                    lowering.current_block,
+                    CmpOp::Lt,
+                    Operand::Local(*taken),
+                    Operand::Local(*limit),
+                );
+                self.iter_branch(func, lowering, open, "for_in_take", exit);
+            }
+        }
+        let end = match &pipeline.source {
+            IterSource::Range { end } => Operand::Local(*end),
+            IterSource::Array { size, .. } => Operand::Const(ConstValue::Int(*size as i64)),
+            IterSource::Vec { vec } => {
+                let len = self.builder.push_temp(func, IrType::Int);
+                self.builder.push_instr(
+                    func,
+                    lowering.current_block,
+                    Instr::VecLen {
+                        dst: len,
+                        vec: Operand::Local(*vec),
+                    },
+                );
+                Operand::Temp(len)
+            }
+        };
+        let more = self.iter_compare(
+            func,
+            lowering.current_block,
+            CmpOp::Lt,
+            Operand::Local(pipeline.cursor),
+            end,
+        );
+        self.iter_branch(func, lowering, more, "for_in_next", exit);
+
+        let block = lowering.current_block;
+        let mut value = match &pipeline.source {
+            IterSource::Range { .. } => {
+                let dst = self.builder.push_temp(func, IrType::Int);
+                self.builder.push_instr(
+                    func,
+                    block,
+                    Instr::LoadLocal {
+                        dst,
+                        ty: IrType::Int,
+                        local: pipeline.cursor,
+                    },
+                );
+                Operand::Temp(dst)
+            }
+            IterSource::Array { array: source, .. } | IterSource::Vec { vec: source } => {
+                let elem_ty = self.array_element_type(func, &Operand::Local(*source));
+                let dst = self.builder.push_temp(func, elem_ty.clone());
+                self.builder.push_instr(
+                    func,
+                    block,
+                    Instr::ArrayGet {
+                        dst,
+                        elem_ty,
+                        array: Operand::Local(*source),
+                        index: Operand::Local(pipeline.cursor),
+                    },
+                );
+                Operand::Temp(dst)
+            }
+        };
+        self.iter_increment(func, block, pipeline.cursor);
+
+        for stage in &pipeline.stages {
+            match stage {
+                IterStage::Map { callback, ret } => {
+                    let dst = self.builder.push_temp(func, ret.clone());
+                    self.builder.push_instr(
+                        func,
+                        lowering.current_block,
+                        Instr::CallIndirect {
+                            dst: Some(dst),
+                            ret_ty: ret.clone(),
+                            callee: Operand::Local(*callback),
+                            args: vec![value],
+                        },
+                    );
+                    value = Operand::Temp(dst);
+                }
+                IterStage::Filter { callback } => {
+                    // Pin the element so it survives into the block after the test.
+                    let ty = self.infer_operand_type(func, &value);
+                    let kept = self.iter_store_scratch(func, lowering, "item", value);
+                    let keep = self.builder.push_temp(func, IrType::Bool);
+                    self.builder.push_instr(
+                        func,
+                        lowering.current_block,
+                        Instr::CallIndirect {
+                            dst: Some(keep),
+                            ret_ty: IrType::Bool,
+                            callee: Operand::Local(*callback),
+                            args: vec![Operand::Local(kept)],
+                        },
+                    );
+                    self.iter_branch(func, lowering, Operand::Temp(keep), "for_in_keep", head);
+                    let dst = self.builder.push_temp(func, ty.clone());
+                    self.builder.push_instr(
+                        func,
+                        lowering.current_block,
+                        Instr::LoadLocal {
+                            dst,
+                            ty,
+                            local: kept,
+                        },
+                    );
+                    value = Operand::Temp(dst);
+                }
+                IterStage::Take { taken, .. } => {
+                    self.iter_increment(func, lowering.current_block, *taken);
+                }
+            }
+        }
+
+        IterLoop { head, exit, value }
+    }
+
+    /// Continues in a fresh block when `cond` holds and jumps to `otherwise` if not.
+    fn iter_branch(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        cond: Operand,
+        label: &str,
+        otherwise: BlockId,
+    ) {
+        let next = self.builder.push_block(func, label);
+        self.builder.set_terminator(
+            func,
+            lowering.current_block,
+            Terminator::Branch(BranchTerminator {
+                cond,
+                then_block: next,
+                else_block: otherwise,
+            }),
+        );
+        lowering.current_block = next;
+    }
+
+    fn iter_compare(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        block: BlockId,
+        op: CmpOp,
+        left: Operand,
+        right: Operand,
+    ) -> Operand {
+        let dst = self.builder.push_temp(func, IrType::Bool);
+        self.builder.push_instr(
+            func,
+            block,
+            Instr::Compare {
+                dst,
+                op,
+                left,
+                right,
+            },
+        );
+        Operand::Temp(dst)
+    }
+
+    fn iter_increment(&mut self, func: &mut crate::ir::IrFunction, block: BlockId, local: LocalId) {
+        let dst = self.builder.push_temp(func, IrType::Int);
+        self.builder.push_instr(
+            func,
+            block,
+            Instr::Binary {
+                dst,
+                ty: IrType::Int,
+                op: BinaryOp::Add,
+                left: Operand::Local(local),
+                right: Operand::Const(ConstValue::Int(1)),
+            },
+        );
+        self.builder.push_instr(
+            func,
+            block,
+            Instr::StoreLocal {
+                local,
+                ty: IrType::Int,
+                value: Operand::Temp(dst),
+            },
+        );
+    }
+
+    fn iter_scratch_local(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        role: &str,
+        ty: IrType,
+    ) -> LocalId {
+        let local = self.builder.push_local(
+            func,
+            format!("__iter_{role}{}", lowering.scratch_counter),
+            ty,
+        );
+        lowering.scratch_counter += 1;
+        local
+    }
+
+    fn iter_store_scratch(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        role: &str,
+        value: Operand,
+    ) -> LocalId {
+        let ty = self.infer_operand_type(func, &value);
+        let local = self.iter_scratch_local(func, lowering, role, ty.clone());
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::StoreLocal { local, ty, value },
+        );
+        local
+    }
+}
@@ -11,7 +11,9 @@ mod context;
 mod expr;
 mod expr_calls;
 mod expr_helpers;
+mod iter;
 mod project;
+mod serde;
 mod stmt;
 
 use context::{ExternFunctionSig, FunctionLowering, FunctionSig, IrLowerer};
@@ -19,7 +21,8 @@ use context::{ExternFunctionSig, FunctionLowering, FunctionSig, IrLowerer};
 pub use project::{
     compile_project_entry, compile_project_entry_unoptimized, compile_project_graph,
     compile_project_graph_after_frontend, compile_project_graph_after_frontend_unoptimized,
-    compile_project_graph_unoptimized,
+    compile_project_graph_for_entry_fn, compile_project_graph_for_entry_fn_unoptimized,
+    compile_project_graph_unoptimized, compile_project_graph_without_entry,
 };
 
 pub fn compile_source(source: &str) -> Result<IrProgram, DiagnosticBag> {
@@ -34,13 +37,18 @@ pub fn compile_source_unoptimized(source: &str) -> Result<IrProgram, DiagnosticB
         return Err(diags);
     }
 
-    let (program, mut diags) = Parser::parse_source(source);
+    let (program, diags) = Parser::parse_source(source);
     if !diags.is_empty() {
         return Err(diags);
     }
+    compile_checked_program(&program)
+}
 
+/// Lowers a `program` that already passed sema, without optimizing it.
+pub(crate) fn compile_checked_program(program: &Program) -> Result<IrProgram, DiagnosticBag> {
+    let mut diags = DiagnosticBag::new();
     let mut lowerer = IrLowerer::new();
-    let ir = lowerer.compile_program(&program);
+    let ir = lowerer.compile_program(program);
     for diag in lowerer.diags.into_vec() {
         diags.push(diag);
     }
@@ -109,7 +117,7 @@ impl IrLowerer {
                                         format!("{}::{}", sym.module_id, sym.local_name),
                                     );
                                 }
-                                SymbolKind::Namespace => {}
+                                SymbolKind::TypeAlias | SymbolKind::Namespace => {}
                             }
                         }
                     } else {
@@ -137,7 +145,7 @@ impl IrLowerer {
                                         format!("{}::{}", sym.module_id, sym.local_name),
                                     );
                                 }
-                                SymbolKind::Namespace => {}
+                                SymbolKind::TypeAlias | SymbolKind::Namespace => {}
                             }
                         }
                     }
@@ -258,11 +266,12 @@ impl IrLowerer {
             .unwrap_or_else(|| self.qualify_name(name))
     }
 
-    fn lower_type_name(&self, ty: &crate::ast::TypeName) -> IrType {
+    fn lower_type_name(&mut self, ty: &crate::ast::TypeName) -> IrType {
         match ty {
             crate::ast::TypeName::Int => IrType::Int,
             crate::ast::TypeName::Float => IrType::Float,
             crate::ast::TypeName::Bool => IrType::Bool,
+            crate::ast::TypeName::Char => IrType::Char,
             crate::ast::TypeName::String => IrType::String,
             crate::ast::TypeName::Bytes => IrType::Bytes,
             crate::ast::TypeName::Void => IrType::Void,
@@ -294,13 +303,109 @@ impl IrLowerer {
                 params: params.iter().map(|p| self.lower_type_name(p)).collect(),
                 ret: Box::new(self.lower_type_name(ret)),
             },
+            crate::ast::TypeName::Tuple(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| self.lower_type_name(item))
+                    .collect::<Vec<_>>();
+                self.tuple_struct(items).1
+            }
+            crate::ast::TypeName::Generic { name, args } => self
+                .generic_struct(name, args)
+                .map(IrType::Named)
+                .unwrap_or(IrType::Unknown),
+        }
+    }
+
+    /// Returns the struct a tuple of `items` lowers to, registering its shape
+    /// the first time it is seen.
+    fn tuple_struct(&mut self, items: Vec<IrType>) -> (crate::ir::StructId, IrType) {
+        let name = crate::ir::tuple_struct_name(&items);
+        if let Some((id, _)) = self.structs.get(&name) {
+            return (*id, IrType::Named(name));
+        }
+        let id = crate::ir::StructId(self.structs.len());
+        let fields = items
+            .into_iter()
+            .enumerate()
+            .map(|(index, ty)| crate::ir::StructField {
+                name: index.to_string(),
+                ty,
+            })
+            .collect::<Vec<_>>();
+        self.structs.insert(name.clone(), (id, fields.clone()));
+        self.synthesized_structs.push(crate::ir::IrStruct {
+            id,
+            name: name.clone(),
+            fields,
+        });
+        (id, IrType::Named(name))
+    }
+
+    /// Returns the runtime name of `name[args]`, monomorphizing the generic
+    /// struct's fields the first time the instantiation is seen.
+    fn generic_struct(&mut self, name: &str, args: &[crate::ast::TypeName]) -> Option<String> {
+        let base = self.resolve_struct_runtime_name(name);
+        let lowered = args
+            .iter()
+            .map(|arg| self.lower_type_name(arg))
+            .collect::<Vec<_>>();
+        let instance = format!(
+            "{base}[{}]",
+            lowered
+                .iter()
+                .map(IrType::source_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        if self.structs.contains_key(&instance) {
+            return Some(instance);
         }
+        let Some(template) = self.generic_structs.get(&base).cloned() else {
+            self.unsupported(format!("unknown generic struct `{name}` in IR lowering"));
+            return None;
+        };
+        let id = crate::ir::StructId(self.structs.len());
+        // Register before lowering the fields so they can refer back to `instance`.
+        self.structs.insert(instance.clone(), (id, Vec::new()));
+        let fields = template
+            .fields
+            .iter()
+            .map(|field| {
+                let mut ty = field.ty.clone();
+                ty.replace_named(&mut |param| {
+                    template
+                        .type_params
+                        .iter()
+                        .position(|p| p == param)
+                        .map(|idx| args[idx].clone())
+                });
+                crate::ir::StructField {
+                    name: field.name.clone(),
+                    ty: self.lower_type_name(&ty),
+                }
+            })
+            .collect::<Vec<_>>();
+        self.structs.insert(instance.clone(), (id, fields.clone()));
+        self.synthesized_structs.push(crate::ir::IrStruct {
+            id,
+            name: instance.clone(),
+            fields,
+        });
+        Some(instance)
+    }
+
+    /// Moves synthesized struct shapes into `out`, keeping structs in id order.
+    fn append_synthesized_structs(&mut self, out: &mut IrProgram) {
+        out.structs.append(&mut self.synthesized_structs);
+        out.structs.sort_by_key(|strukt| strukt.id.0);
     }
 
     fn compile_program(&mut self, program: &Program) -> IrProgram {
         let mut out = self.builder.begin_program();
         self.compile_program_into(program, &mut out);
         out.functions.append(&mut self.lifted_functions);
+        self.append_synthesized_structs(&mut out);
         out
     }
 
@@ -311,8 +416,14 @@ impl IrLowerer {
 
     fn register_program_items(&mut self, program: &Program, out: &mut IrProgram) {
         for strukt in &program.structs {
-            let id = crate::ir::StructId(self.structs.len());
+            if !strukt.type_params.is_empty() {
+                self.generic_structs
+                    .insert(self.qualify_name(&strukt.name), strukt.clone());
+            }
+        }
+        for strukt in program.structs.iter().filter(|s| s.type_params.is_empty()) {
             let fields = self.lower_struct_fields(strukt);
+            let id = crate::ir::StructId(self.structs.len());
             let runtime_name = self.resolve_struct_runtime_name(&strukt.name);
             self.structs
                 .insert(runtime_name.clone(), (id, fields.clone()));
@@ -370,16 +481,8 @@ impl IrLowerer {
                     ExternFunctionSig {
                         library: func.extern_library.clone(),
                         symbol: func.name.clone(),
-                        params: func
-                            .params
-                            .iter()
-                            .map(|param| self.lower_type_name(&param.ty))
-                            .collect(),
-                        ret: func
-                            .return_type
-                            .as_ref()
-                            .map(|ty| self.lower_type_name(ty))
-                            .unwrap_or(IrType::Void),
+                        params: params.clone(),
+                        ret: ret_ty.clone(),
                     },
                 );
                 continue;
@@ -491,7 +594,7 @@ impl IrLowerer {
         }
     }
 
-    fn lower_struct_fields(&self, strukt: &StructDecl) -> Vec<crate::ir::StructField> {
+    fn lower_struct_fields(&mut self, strukt: &StructDecl) -> Vec<crate::ir::StructField> {
         strukt
             .fields
             .iter()
@@ -516,24 +619,20 @@ impl IrLowerer {
             .builder
             .begin_function(self.qualify_name(&func.name), sig.ret.clone());
         out.id = sig.id;
+        out.inline_hint = func.has_attribute("inline");
         let mut lowering = FunctionLowering {
             current_block: out.entry,
             locals: HashMap::new(),
             scratch_counter: 0,
             loops: Vec::new(),
+            defers: Vec::new(),
         };
 
         for param in &func.params {
-            self.builder.push_param(
-                &mut out,
-                param.name.clone(),
-                self.lower_type_name(&param.ty),
-            );
-            let local = self.builder.push_local(
-                &mut out,
-                param.name.clone(),
-                self.lower_type_name(&param.ty),
-            );
+            let ir_ty = self.lower_type_name(&param.ty);
+            self.builder
+                .push_param(&mut out, param.name.clone(), ir_ty.clone());
+            let local = self.builder.push_local(&mut out, param.name.clone(), ir_ty);
             lowering.locals.insert(param.name.clone(), local);
         }
 
@@ -560,6 +659,9 @@ impl IrLowerer {
                 );
                 return None;
             };
+            if !self.compile_deferred(&mut out, &mut lowering) {
+                return None;
+            }
             self.builder
                 .set_terminator(&mut out, lowering.current_block, terminator);
         }
@@ -586,19 +688,14 @@ impl IrLowerer {
             locals: HashMap::new(),
             scratch_counter: 0,
             loops: Vec::new(),
+            defers: Vec::new(),
         };
 
         for param in &operator.params {
-            self.builder.push_param(
-                &mut out,
-                param.name.clone(),
-                self.lower_type_name(&param.ty),
-            );
-            let local = self.builder.push_local(
-                &mut out,
-                param.name.clone(),
-                self.lower_type_name(&param.ty),
-            );
+            let ir_ty = self.lower_type_name(&param.ty);
+            self.builder
+                .push_param(&mut out, param.name.clone(), ir_ty.clone());
+            let local = self.builder.push_local(&mut out, param.name.clone(), ir_ty);
             lowering.locals.insert(param.name.clone(), local);
         }
 
@@ -649,6 +746,7 @@ impl IrLowerer {
             locals: HashMap::new(),
             scratch_counter: 0,
             loops: Vec::new(),
+            defers: Vec::new(),
         };
 
         for param in &method.params {
@@ -686,6 +784,9 @@ impl IrLowerer {
                 );
                 return None;
             };
+            if !self.compile_deferred(&mut out, &mut lowering) {
+                return None;
+            }
             self.builder
                 .set_terminator(&mut out, lowering.current_block, terminator);
         }
@@ -703,6 +804,7 @@ impl IrLowerer {
             locals: HashMap::new(),
             scratch_counter: 0,
             loops: Vec::new(),
+            defers: Vec::new(),
         };
 
         for global in &program.globals {
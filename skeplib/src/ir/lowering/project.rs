@@ -66,19 +66,118 @@ pub fn compile_project_graph_after_frontend_unoptimized(
     graph: &ModuleGraph,
     entry: &Path,
 ) -> Result<IrProgram, String> {
-    let export_maps = build_export_maps(graph).map_err(|errs| errs[0].message.clone())?;
-    let entry_path = entry.canonicalize().unwrap_or_else(|_| entry.to_path_buf());
-    let Some((entry_id, _)) = graph.modules.iter().find(|(_, m)| {
-        m.path == entry
-            || m.path == entry_path
-            || m.path
-                .canonicalize()
-                .map(|p| p == entry_path)
-                .unwrap_or(false)
-    }) else {
+    compile_project_graph_for_entry_fn_unoptimized(graph, entry, "main")
+}
+
+/// Like [`compile_project_graph_after_frontend`], but the program's `main` runs
+/// `entry_fn` from the entry module instead of its `main`.
+pub fn compile_project_graph_for_entry_fn(
+    graph: &ModuleGraph,
+    entry: &Path,
+    entry_fn: &str,
+) -> Result<IrProgram, String> {
+    let mut ir = compile_project_graph_for_entry_fn_unoptimized(graph, entry, entry_fn)?;
+    opt::optimize_program(&mut ir);
+    Ok(ir)
+}
+
+/// Lowers and optimizes an already checked project with no entry function, for
+/// callers that run functions by IR name through
+/// [`crate::ir::IrInterpreter::call`], such as a `@test` runner.
+pub fn compile_project_graph_without_entry(graph: &ModuleGraph) -> Result<IrProgram, String> {
+    let (mut lowerer, mut out) = lower_project_modules(graph)?;
+    out.functions.append(&mut lowerer.lifted_functions);
+    lowerer.append_synthesized_structs(&mut out);
+    IrVerifier::verify_program(&out).map_err(|err| format!("IR verification failed: {err:?}"))?;
+    opt::optimize_program(&mut out);
+    Ok(out)
+}
+
+/// Lowers an already checked project whose `main` calls `entry_fn` from the
+/// entry module. The entry function must take no parameters; one other than
+/// `main` must also return `Int` or `Void`, which becomes the exit code.
+pub fn compile_project_graph_for_entry_fn_unoptimized(
+    graph: &ModuleGraph,
+    entry: &Path,
+    entry_fn: &str,
+) -> Result<IrProgram, String> {
+    let Some(entry_id) = graph.entry_module(entry).map(|m| &m.id) else {
         return Err("Entry module missing from graph".to_string());
     };
+    let (mut lowerer, mut out) = lower_project_modules(graph)?;
 
+    let entry_main_name = format!("{entry_id}::{entry_fn}");
+    let Some(entry_main) = out
+        .functions
+        .iter()
+        .find(|func| func.name == entry_main_name)
+    else {
+        return Err(if entry_fn == "main" {
+            "Entry module does not define main".to_string()
+        } else {
+            format!("Entry module does not define function `{entry_fn}`")
+        });
+    };
+    if !entry_main.params.is_empty() {
+        return Err(format!(
+            "Entry function `{entry_fn}` must take no parameters, found {}",
+            entry_main.params.len()
+        ));
+    }
+    if entry_fn != "main" && !matches!(entry_main.ret_ty, IrType::Int | IrType::Void) {
+        return Err(format!(
+            "Entry function `{entry_fn}` must return Int or Void, found {:?}",
+            entry_main.ret_ty
+        ));
+    }
+    let (entry_main_id, entry_main_ty) = (entry_main.id, entry_main.ret_ty.clone());
+    let wrapper_main_id = crate::ir::FunctionId(lowerer.functions.len());
+    lowerer.functions.insert(
+        "main".to_string(),
+        FunctionSig {
+            id: wrapper_main_id,
+            params: Vec::new(),
+            ret: entry_main_ty.clone(),
+        },
+    );
+    let mut main = lowerer
+        .builder
+        .begin_function("main", entry_main_ty.clone());
+    main.id = wrapper_main_id;
+    let main_entry = main.entry;
+    let dst = if entry_main_ty.is_void() {
+        None
+    } else {
+        Some(lowerer.builder.push_temp(&mut main, entry_main_ty.clone()))
+    };
+    lowerer.builder.push_instr(
+        &mut main,
+        main_entry,
+        Instr::CallDirect {
+            dst,
+            ret_ty: entry_main_ty,
+            function: entry_main_id,
+            args: Vec::new(),
+        },
+    );
+    lowerer.builder.set_terminator(
+        &mut main,
+        main_entry,
+        Terminator::Return(dst.map(Operand::Temp)),
+    );
+    out.functions.push(main);
+    out.functions.append(&mut lowerer.lifted_functions);
+    lowerer.append_synthesized_structs(&mut out);
+
+    IrVerifier::verify_program(&out).map_err(|err| format!("IR verification failed: {err:?}"))?;
+    Ok(out)
+}
+
+/// Lowers every module of an already checked project, plus the `__globals_init`
+/// wrapper, without a `main` wrapper. Lifted functions and synthesized structs
+/// are left in the returned lowerer.
+fn lower_project_modules(graph: &ModuleGraph) -> Result<(IrLowerer, IrProgram), String> {
+    let export_maps = build_export_maps(graph).map_err(|errs| errs[0].message.clone())?;
     let mut lowerer = IrLowerer::new_project();
     let mut out = lowerer.builder.begin_program();
     let mut init_functions_by_module = std::collections::HashMap::new();
@@ -149,54 +248,7 @@ pub fn compile_project_graph_after_frontend_unoptimized(
         out.functions.push(init);
     }
 
-    let entry_main_name = format!("{entry_id}::main");
-    let Some((entry_main_id, entry_main_ty)) = out
-        .functions
-        .iter()
-        .find(|func| func.name == entry_main_name)
-        .map(|func| (func.id, func.ret_ty.clone()))
-    else {
-        return Err("Entry module does not define main".to_string());
-    };
-    let wrapper_main_id = crate::ir::FunctionId(lowerer.functions.len());
-    lowerer.functions.insert(
-        "main".to_string(),
-        FunctionSig {
-            id: wrapper_main_id,
-            params: Vec::new(),
-            ret: entry_main_ty.clone(),
-        },
-    );
-    let mut main = lowerer
-        .builder
-        .begin_function("main", entry_main_ty.clone());
-    main.id = wrapper_main_id;
-    let main_entry = main.entry;
-    let dst = if entry_main_ty.is_void() {
-        None
-    } else {
-        Some(lowerer.builder.push_temp(&mut main, entry_main_ty.clone()))
-    };
-    lowerer.builder.push_instr(
-        &mut main,
-        main_entry,
-        Instr::CallDirect {
-            dst,
-            ret_ty: entry_main_ty,
-            function: entry_main_id,
-            args: Vec::new(),
-        },
-    );
-    lowerer.builder.set_terminator(
-        &mut main,
-        main_entry,
-        Terminator::Return(dst.map(Operand::Temp)),
-    );
-    out.functions.push(main);
-    out.functions.append(&mut lowerer.lifted_functions);
-
-    IrVerifier::verify_program(&out).map_err(|err| format!("IR verification failed: {err:?}"))?;
-    Ok(out)
+    Ok((lowerer, out))
 }
 
 fn module_init_order(
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use crate::ast::Expr;
+use crate::ir::{ConstValue, Instr, IrType, Operand};
+
+use super::context::{FunctionLowering, IrLowerer};
+
+impl IrLowerer {
+    /// `serde.toJson(value)` / `serde.encode(value)`: passes the schema of the
+    /// value's static type after the value.
+    pub(super) fn compile_serde_call(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        method: &str,
+        mut args: Vec<Operand>,
+    ) -> Option<Operand> {
+        let ret_ty = match method {
+            "toJson" => IrType::String,
+            "encode" => IrType::Bytes,
+            _ => {
+                self.unsupported(format!(
+                    "serde.{method} must initialize a local `let` annotated as `Result[T, String]`"
+                ));
+                return None;
+            }
+        };
+        let [value] = args.as_slice() else {
+            self.unsupported(format!("serde.{method} expects 1 argument"));
+            return None;
+        };
+        let value_ty = self.infer_operand_type(func, value);
+        let schema = self.serde_schema(method, &value_ty)?;
+        args.push(schema);
+        let dst = self.builder.push_temp(func, ret_ty.clone());
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::CallBuiltin {
+                dst: Some(dst),
+                ret_ty,
+                builtin: crate::ir::BuiltinCall {
+                    package: "serde".to_string(),
+                    name: method.to_string(),
+                },
+                args,
+            },
+        );
+        Some(Operand::Temp(dst))
+    }
+
+    /// `let name: Result[T, String] = serde.fromJson(text)` (or `serde.decode`),
+    /// decoding to the declared `T`.
+    pub(super) fn try_compile_serde_decode_let(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        name: &str,
+        ty: &Option<crate::ast::TypeName>,
+        value: &Expr,
+    ) -> Option<bool> {
+        let declared = ty.as_ref()?;
+        let Expr::Call { callee, args } = value else {
+            return None;
+        };
+        let method = ["fromJson", "decode"]
+            .into_iter()
+            .find(|method| Self::is_builtin_member_expr(callee, "serde", method))?;
+        let [input] = args.as_slice() else {
+            return None;
+        };
+
+        let local_ty = self.lower_type_name(declared);
+        let IrType::Result { ok, .. } = &local_ty else {
+            return None;
+        };
+        let Some(schema) = self.serde_schema(method, ok) else {
+            return Some(false);
+        };
+        let Some(input) = self.compile_expr(func, lowering, input) else {
+            return Some(false);
+        };
+        let local = self
+            .builder
+            .push_local(func, name.to_string(), local_ty.clone());
+        lowering.locals.insert(name.to_string(), local);
+        let dst = self.builder.push_temp(func, local_ty.clone());
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::CallBuiltin {
+                dst: Some(dst),
+                ret_ty: local_ty.clone(),
+                builtin: crate::ir::BuiltinCall {
+                    package: "serde".to_string(),
+                    name: method.to_string(),
+                },
+                args: vec![input, schema],
+            },
+        );
+        self.builder.push_instr(
+            func,
+            lowering.current_block,
+            Instr::StoreLocal {
+                local,
+                ty: local_ty,
+                value: Operand::Temp(dst),
+            },
+        );
+        Some(true)
+    }
+
+    /// The schema `skepart`'s `serde` builtins read `ty` with; the grammar is
+    /// documented there.
+    fn serde_schema(&mut self, method: &str, ty: &IrType) -> Option<Operand> {
+        let mut defs = Vec::new();
+        let mut indices = HashMap::new();
+        let mut root = String::new();
+        if !self.write_serde_shape(ty, &mut root, &mut defs, &mut indices) {
+            self.unsupported(format!(
+                "serde.{method} cannot serialize `{}` in IR lowering",
+                ty.source_name()
+            ));
+            return None;
+        }
+        for def in defs {
+            root.push('|');
+            root.push_str(&def);
+        }
+        Some(Operand::Const(ConstValue::String(root)))
+    }
+
+    fn write_serde_shape(
+        &self,
+        ty: &IrType,
+        out: &mut String,
+        defs: &mut Vec<String>,
+        indices: &mut HashMap<String, usize>,
+    ) -> bool {
+        match ty {
+            IrType::Int => out.push('i'),
+            IrType::Float => out.push('f'),
+            IrType::Bool => out.push('b'),
+            IrType::Char => out.push('c'),
+            IrType::String => out.push('s'),
+            IrType::Bytes => out.push('y'),
+            IrType::Option { value } => {
+                out.push('o');
+                return self.write_serde_shape(value, out, defs, indices);
+            }
+            IrType::Result { ok, err } => {
+                out.push('r');
+                return self.write_serde_shape(ok, out, defs, indices)
+                    && self.write_serde_shape(err, out, defs, indices);
+            }
+            IrType::Vec { elem } => {
+                out.push('v');
+                return self.write_serde_shape(elem, out, defs, indices);
+            }
+            IrType::Map { value } => {
+                out.push('m');
+                return self.write_serde_shape(value, out, defs, indices);
+            }
+            IrType::Array { elem, size } => {
+                out.push_str(&format!("a{size}."));
+                return self.write_serde_shape(elem, out, defs, indices);
+            }
+            IrType::Named(name) => {
+                if let Some(index) = indices.get(name) {
+                    out.push_str(&format!("#{index}."));
+                    return true;
+                }
+                let Some((id, fields)) = self.structs.get(name) else {
+                    return false;
+                };
+                let index = defs.len();
+                indices.insert(name.clone(), index);
+                defs.push(String::new());
+                out.push_str(&format!("#{index}."));
+
+                let is_tuple = name.starts_with('(');
+                let mut def = format!("{}{}", id.0, if is_tuple { '(' } else { '{' });
+                for (position, field) in fields.iter().enumerate() {
+                    if position > 0 {
+                        def.push(',');
+                    }
+                    if !is_tuple {
+                        def.push_str(&field.name);
+                        def.push(':');
+                    }
+                    if !self.write_serde_shape(&field.ty, &mut def, defs, indices) {
+                        return false;
+                    }
+                }
+                def.push(if is_tuple { ')' } else { '}' });
+                defs[index] = def;
+            }
+            IrType::Void | IrType::Opaque(_) | IrType::Fn { .. } | IrType::Unknown => {
+                return false;
+            }
+        }
+        true
+    }
+}
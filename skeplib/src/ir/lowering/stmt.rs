@@ -1,7 +1,7 @@
-use crate::ast::{AssignTarget, Expr, MatchLiteral, MatchPattern, Stmt};
+use crate::ast::{AssignTarget, Expr, MatchLiteral, MatchPattern, Stmt, StmtKind};
 use crate::ir::{BlockId, BranchTerminator, Instr, IrType, Operand, Terminator};
 
-use super::context::{FunctionLowering, IrLowerer, LoopLowering};
+use super::context::{FunctionLowering, IrLowerer, LoopLowering, MatchArmBlocks};
 
 impl IrLowerer {
     pub(super) fn compile_stmt_list(
@@ -18,17 +18,50 @@ impl IrLowerer {
                 return false;
             }
         }
+        // Sema accepts a trailing `panic(...)` / `os.exit(...)` or infinite loop as
+        // the end of a non-void body, so close the block instead of falling off the end.
+        let never_completes = match body.last() {
+            Some(Stmt {
+                kind: StmtKind::Expr(expr),
+                ..
+            }) => expr.is_diverging_call(),
+            Some(stmt) => stmt.is_infinite_loop(),
+            None => false,
+        };
+        if never_completes && !self.is_block_terminated(func, lowering.current_block) {
+            self.builder.set_terminator(
+                func,
+                lowering.current_block,
+                Terminator::Panic {
+                    message: "reached code after a statement that never completes".to_string(),
+                },
+            );
+        }
         true
     }
 
+    /// Lowers the body of an `if` branch or loop. Its `let`s go out of scope at
+    /// the end, so an outer binding they shadow is visible again afterwards.
+    fn compile_scoped_stmt_list(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        body: &[Stmt],
+    ) -> bool {
+        let saved_locals = lowering.locals.clone();
+        let ok = self.compile_stmt_list(func, lowering, body);
+        lowering.locals = saved_locals;
+        ok
+    }
+
     pub(super) fn compile_stmt(
         &mut self,
         func: &mut crate::ir::IrFunction,
         lowering: &mut FunctionLowering,
         stmt: &Stmt,
     ) -> bool {
-        match stmt {
-            Stmt::Let { name, ty, value } => {
+        match &stmt.kind {
+            StmtKind::Let { name, ty, value } => {
                 if let Some(done) = self.try_compile_vec_new_let(func, lowering, name, ty, value) {
                     return done;
                 }
@@ -45,6 +78,11 @@ impl IrLowerer {
                 {
                     return done;
                 }
+                if let Some(done) =
+                    self.try_compile_serde_decode_let(func, lowering, name, ty, value)
+                {
+                    return done;
+                }
                 let rhs = match self.compile_expr(func, lowering, value) {
                     Some(value) => value,
                     None => return false,
@@ -66,7 +104,30 @@ impl IrLowerer {
                 );
                 true
             }
-            Stmt::Assign {
+            StmtKind::LetPattern { pattern, value } => {
+                let rhs = match self.compile_expr(func, lowering, value) {
+                    Some(value) => value,
+                    None => return false,
+                };
+                let ty = self.infer_operand_type(func, &rhs);
+                let subject = self.builder.push_local(
+                    func,
+                    format!("__let_pattern{}", lowering.scratch_counter),
+                    ty.clone(),
+                );
+                lowering.scratch_counter += 1;
+                self.builder.push_instr(
+                    func,
+                    lowering.current_block,
+                    Instr::StoreLocal {
+                        local: subject,
+                        ty: ty.clone(),
+                        value: rhs,
+                    },
+                );
+                self.bind_match_pattern(func, lowering, Operand::Local(subject), &ty, pattern)
+            }
+            StmtKind::Assign {
                 target: AssignTarget::Ident(name),
                 value,
             } => {
@@ -107,7 +168,7 @@ impl IrLowerer {
                 self.unsupported(format!("assignment to unknown local `{name}`"));
                 false
             }
-            Stmt::Assign {
+            StmtKind::Assign {
                 target: AssignTarget::Index { base, index },
                 value,
             } => {
@@ -136,7 +197,7 @@ impl IrLowerer {
                 );
                 true
             }
-            Stmt::Assign {
+            StmtKind::Assign {
                 target: AssignTarget::Field { base, field },
                 value,
             } => {
@@ -162,26 +223,48 @@ impl IrLowerer {
                 );
                 true
             }
-            Stmt::Expr(expr) => self.compile_expr(func, lowering, expr).is_some(),
-            Stmt::Return(value) => {
-                let ret = match value {
+            StmtKind::Expr(expr) => self.compile_expr(func, lowering, expr).is_some(),
+            StmtKind::Return(value) => {
+                let mut ret = match value {
                     Some(expr) => match self.compile_expr(func, lowering, expr) {
                         Some(value) => Some(value),
                         None => return false,
                     },
                     None => None,
                 };
+                if !lowering.defers.is_empty() {
+                    // Pin the return value before deferred code runs so it is
+                    // evaluated exactly once and still available afterwards.
+                    if let Some(value) = ret.take() {
+                        let ty = self.infer_operand_type(func, &value);
+                        let local = self.builder.push_local(
+                            func,
+                            format!("__defer_ret{}", lowering.scratch_counter),
+                            ty.clone(),
+                        );
+                        lowering.scratch_counter += 1;
+                        self.builder.push_instr(
+                            func,
+                            lowering.current_block,
+                            Instr::StoreLocal { local, ty, value },
+                        );
+                        ret = Some(Operand::Local(local));
+                    }
+                    if !self.compile_deferred(func, lowering) {
+                        return false;
+                    }
+                }
                 self.builder
                     .set_terminator(func, lowering.current_block, Terminator::Return(ret));
                 true
             }
-            Stmt::If {
+            StmtKind::If {
                 cond,
                 then_body,
                 else_body,
             } => self.compile_if(func, lowering, cond, then_body, else_body),
-            Stmt::While { cond, body } => self.compile_while(func, lowering, cond, body),
-            Stmt::For {
+            StmtKind::While { cond, body } => self.compile_while(func, lowering, cond, body),
+            StmtKind::For {
                 init,
                 cond,
                 step,
@@ -194,12 +277,42 @@ impl IrLowerer {
                 step.as_deref(),
                 body,
             ),
-            Stmt::Break => self.compile_break(func, lowering),
-            Stmt::Continue => self.compile_continue(func, lowering),
-            Stmt::Match { expr, arms } => self.compile_match(func, lowering, expr, arms),
+            StmtKind::ForIn {
+                name,
+                iterable,
+                body,
+            } => self.compile_for_in(func, lowering, name, iterable, body),
+            StmtKind::Break => self.compile_break(func, lowering),
+            StmtKind::Continue => self.compile_continue(func, lowering),
+            StmtKind::Match { expr, arms } => self.compile_match(func, lowering, expr, arms),
+            StmtKind::Defer(expr) => {
+                lowering
+                    .defers
+                    .push((expr.clone(), lowering.locals.clone()));
+                true
+            }
         }
     }
 
+    /// Lowers the registered `defer` expressions, most recent first, into the
+    /// current block, each against the locals captured at its `defer`. Called
+    /// on every path that leaves the function.
+    pub(super) fn compile_deferred(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+    ) -> bool {
+        let defers = std::mem::take(&mut lowering.defers);
+        let ok = defers.iter().rev().all(|(expr, locals)| {
+            let saved_locals = std::mem::replace(&mut lowering.locals, locals.clone());
+            let ok = self.compile_expr(func, lowering, expr).is_some();
+            lowering.locals = saved_locals;
+            ok
+        });
+        lowering.defers = defers;
+        ok
+    }
+
     fn compile_if(
         &mut self,
         func: &mut crate::ir::IrFunction,
@@ -226,13 +339,13 @@ impl IrLowerer {
         );
 
         lowering.current_block = then_block;
-        if !self.compile_stmt_list(func, lowering, then_body) {
+        if !self.compile_scoped_stmt_list(func, lowering, then_body) {
             return false;
         }
         self.ensure_fallthrough_jump(func, lowering.current_block, join_block);
 
         lowering.current_block = else_block;
-        if !self.compile_stmt_list(func, lowering, else_body) {
+        if !self.compile_scoped_stmt_list(func, lowering, else_body) {
             return false;
         }
         self.ensure_fallthrough_jump(func, lowering.current_block, join_block);
@@ -275,7 +388,7 @@ impl IrLowerer {
             break_block: exit_block,
         });
         lowering.current_block = body_block;
-        if !self.compile_stmt_list(func, lowering, body) {
+        if !self.compile_scoped_stmt_list(func, lowering, body) {
             lowering.loops.pop();
             return false;
         }
@@ -295,6 +408,8 @@ impl IrLowerer {
         step: Option<&Stmt>,
         body: &[Stmt],
     ) -> bool {
+        // A binding made by `init` is scoped to the loop.
+        let saved_locals = lowering.locals.clone();
         if let Some(init) = init
             && !self.compile_stmt(func, lowering, init)
         {
@@ -332,7 +447,7 @@ impl IrLowerer {
             break_block: exit_block,
         });
         lowering.current_block = body_block;
-        if !self.compile_stmt_list(func, lowering, body) {
+        if !self.compile_scoped_stmt_list(func, lowering, body) {
             lowering.loops.pop();
             return false;
         }
@@ -348,6 +463,7 @@ impl IrLowerer {
         lowering.loops.pop();
         self.ensure_fallthrough_jump(func, lowering.current_block, cond_block);
 
+        lowering.locals = saved_locals;
         lowering.current_block = exit_block;
         true
     }
@@ -416,6 +532,7 @@ impl IrLowerer {
 
         let join_block = self.builder.push_block(func, "match_join");
         let mut dispatch_block = lowering.current_block;
+        let mut falls_through = false;
 
         for (index, arm) in arms.iter().enumerate() {
             let body_block = self.builder.push_block(func, format!("match_arm_{index}"));
@@ -425,51 +542,41 @@ impl IrLowerer {
                 self.builder.push_block(func, format!("match_next_{index}"))
             };
 
-            if matches!(arm.pattern, MatchPattern::Wildcard) {
-                self.builder
-                    .set_terminator(func, dispatch_block, Terminator::Jump(body_block));
-            } else {
-                let cond = match self.compile_match_condition(
-                    func,
-                    dispatch_block,
-                    match_local,
-                    &target_ty,
-                    &arm.pattern,
-                ) {
-                    Some(cond) => cond,
-                    None => return false,
-                };
-                self.builder.set_terminator(
-                    func,
-                    dispatch_block,
-                    Terminator::Branch(BranchTerminator {
-                        cond,
-                        then_block: body_block,
-                        else_block: next_block,
-                    }),
-                );
-            }
-
-            lowering.current_block = body_block;
             let saved_locals = lowering.locals.clone();
-            if !self.bind_match_pattern(func, lowering, match_local, &target_ty, &arm.pattern) {
+            if !self.enter_match_arm(
+                func,
+                lowering,
+                MatchArmBlocks {
+                    dispatch: dispatch_block,
+                    body: body_block,
+                    next: next_block,
+                },
+                Operand::Local(match_local),
+                &arm.pattern,
+                arm.guard.as_ref(),
+            ) {
                 return false;
             }
             if !self.compile_stmt_list(func, lowering, &arm.body) {
                 return false;
             }
+            falls_through |= !self.is_block_terminated(func, lowering.current_block);
             self.ensure_fallthrough_jump(func, lowering.current_block, join_block);
             lowering.locals = saved_locals;
             dispatch_block = next_block;
         }
 
-        if matches!(
-            func.blocks
-                .iter()
-                .find(|block| block.id == dispatch_block)
-                .map(|block| &block.terminator),
-            Some(Terminator::Unreachable)
-        ) {
+        // Sema rejects non-exhaustive matches, so the join is only live when an
+        // arm falls through to it; otherwise close it like the arms themselves.
+        if (dispatch_block != join_block || !falls_through)
+            && matches!(
+                func.blocks
+                    .iter()
+                    .find(|block| block.id == dispatch_block)
+                    .map(|block| &block.terminator),
+                Some(Terminator::Unreachable)
+            )
+        {
             self.builder
                 .set_terminator(func, dispatch_block, Terminator::Jump(join_block));
         }
@@ -478,20 +585,87 @@ impl IrLowerer {
         true
     }
 
+    /// Branches from `blocks.dispatch` into an arm whose pattern matches, binds the
+    /// pattern's names, and evaluates the guard, falling through to `blocks.next`
+    /// when it is false. Leaves `lowering.current_block` at the start of the body.
+    pub(super) fn enter_match_arm(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        blocks: MatchArmBlocks,
+        subject: Operand,
+        pattern: &MatchPattern,
+        guard: Option<&Expr>,
+    ) -> bool {
+        let target_ty = &self.infer_operand_type(func, &subject);
+        let arm_block = if guard.is_some() {
+            self.builder.push_block(func, "match_guard")
+        } else {
+            blocks.body
+        };
+        if matches!(pattern, MatchPattern::Wildcard) {
+            self.builder
+                .set_terminator(func, blocks.dispatch, Terminator::Jump(arm_block));
+        } else {
+            let Some(cond) = self.compile_match_condition(
+                func,
+                blocks.dispatch,
+                subject.clone(),
+                target_ty,
+                pattern,
+            ) else {
+                return false;
+            };
+            self.builder.set_terminator(
+                func,
+                blocks.dispatch,
+                Terminator::Branch(BranchTerminator {
+                    cond,
+                    then_block: arm_block,
+                    else_block: blocks.next,
+                }),
+            );
+        }
+
+        lowering.current_block = arm_block;
+        if !self.bind_match_pattern(func, lowering, subject, target_ty, pattern) {
+            return false;
+        }
+        if let Some(guard) = guard {
+            let Some(cond) = self.compile_expr(func, lowering, guard) else {
+                return false;
+            };
+            self.builder.set_terminator(
+                func,
+                lowering.current_block,
+                Terminator::Branch(BranchTerminator {
+                    cond,
+                    then_block: blocks.body,
+                    else_block: blocks.next,
+                }),
+            );
+            lowering.current_block = blocks.body;
+        }
+        true
+    }
+
     pub(super) fn compile_match_condition(
         &mut self,
         func: &mut crate::ir::IrFunction,
         block: BlockId,
-        match_local: crate::ir::LocalId,
-        _target_ty: &IrType,
+        subject: Operand,
+        target_ty: &IrType,
         pattern: &MatchPattern,
     ) -> Option<Operand> {
         match pattern {
-            MatchPattern::Wildcard => Some(Operand::Const(crate::ir::ConstValue::Bool(true))),
+            MatchPattern::Wildcard | MatchPattern::Binding(_) => {
+                Some(Operand::Const(crate::ir::ConstValue::Bool(true)))
+            }
             MatchPattern::Literal(lit) => {
                 let rhs = match lit {
                     MatchLiteral::Int(v) => Operand::Const(crate::ir::ConstValue::Int(*v)),
                     MatchLiteral::Bool(v) => Operand::Const(crate::ir::ConstValue::Bool(*v)),
+                    MatchLiteral::Char(v) => Operand::Const(crate::ir::ConstValue::Char(*v)),
                     MatchLiteral::String(v) => {
                         Operand::Const(crate::ir::ConstValue::String(v.clone()))
                     }
@@ -507,7 +681,7 @@ impl IrLowerer {
                     Instr::Compare {
                         dst,
                         op: crate::ir::CmpOp::Eq,
-                        left: Operand::Local(match_local),
+                        left: subject,
                         right: rhs,
                     },
                 );
@@ -537,7 +711,7 @@ impl IrLowerer {
                             package: builtin.0.to_string(),
                             name: builtin.1.to_string(),
                         },
-                        args: vec![Operand::Local(match_local)],
+                        args: vec![subject],
                     },
                 );
                 Some(Operand::Temp(dst))
@@ -547,14 +721,19 @@ impl IrLowerer {
                 let first = self.compile_match_condition(
                     func,
                     block,
-                    match_local,
-                    _target_ty,
+                    subject.clone(),
+                    target_ty,
                     parts.next()?,
                 )?;
                 let mut acc = first;
                 for part in parts {
-                    let rhs =
-                        self.compile_match_condition(func, block, match_local, _target_ty, part)?;
+                    let rhs = self.compile_match_condition(
+                        func,
+                        block,
+                        subject.clone(),
+                        target_ty,
+                        part,
+                    )?;
                     let dst = self.builder.push_temp(func, IrType::Bool);
                     self.builder.push_instr(
                         func,
@@ -570,60 +749,268 @@ impl IrLowerer {
                 }
                 Some(acc)
             }
+            MatchPattern::Struct { fields, .. } => {
+                let mut acc = None;
+                for (field, part) in fields {
+                    if part.is_irrefutable() {
+                        continue;
+                    }
+                    let (value, value_ty) = self.match_struct_field(func, block, &subject, field);
+                    let cond = self.compile_match_condition(func, block, value, &value_ty, part)?;
+                    acc = Some(self.and_match_conditions(func, block, acc, cond));
+                }
+                Some(acc.unwrap_or(Operand::Const(crate::ir::ConstValue::Bool(true))))
+            }
+            MatchPattern::Array { items, .. } => {
+                let elem_ty = match target_ty {
+                    IrType::Array { elem, .. } => (**elem).clone(),
+                    _ => IrType::Unknown,
+                };
+                let mut acc = None;
+                for (index, part) in items.iter().enumerate() {
+                    if part.is_irrefutable() {
+                        continue;
+                    }
+                    let value = self.match_array_item(func, block, &subject, &elem_ty, index);
+                    let cond = self.compile_match_condition(func, block, value, &elem_ty, part)?;
+                    acc = Some(self.and_match_conditions(func, block, acc, cond));
+                }
+                Some(acc.unwrap_or(Operand::Const(crate::ir::ConstValue::Bool(true))))
+            }
+            MatchPattern::Tuple(items) => {
+                let mut acc = None;
+                for (index, part) in items.iter().enumerate() {
+                    if part.is_irrefutable() {
+                        continue;
+                    }
+                    let (value, value_ty) =
+                        self.match_struct_field(func, block, &subject, &index.to_string());
+                    let cond = self.compile_match_condition(func, block, value, &value_ty, part)?;
+                    acc = Some(self.and_match_conditions(func, block, acc, cond));
+                }
+                Some(acc.unwrap_or(Operand::Const(crate::ir::ConstValue::Bool(true))))
+            }
         }
     }
 
-    pub(super) fn bind_match_pattern(
+    fn and_match_conditions(
         &mut self,
         func: &mut crate::ir::IrFunction,
-        lowering: &mut FunctionLowering,
-        match_local: crate::ir::LocalId,
-        target_ty: &IrType,
-        pattern: &MatchPattern,
-    ) -> bool {
-        let MatchPattern::Variant {
-            name,
-            binding: Some(binding),
-        } = pattern
-        else {
-            return true;
+        block: BlockId,
+        acc: Option<Operand>,
+        cond: Operand,
+    ) -> Operand {
+        let Some(acc) = acc else {
+            return cond;
         };
+        let dst = self.builder.push_temp(func, IrType::Bool);
+        self.builder.push_instr(
+            func,
+            block,
+            Instr::Logic {
+                dst,
+                op: crate::ir::LogicOp::And,
+                left: acc,
+                right: cond,
+            },
+        );
+        Operand::Temp(dst)
+    }
 
-        let (builtin_pkg, builtin_name, value_ty) = match (name.as_str(), target_ty) {
-            ("Some", IrType::Option { value }) => ("option", "unwrapSome", (**value).clone()),
-            ("Ok", IrType::Result { ok, .. }) => ("result", "unwrapOk", (**ok).clone()),
-            ("Err", IrType::Result { err, .. }) => ("result", "unwrapErr", (**err).clone()),
-            _ => return true,
-        };
+    fn match_struct_field(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        block: BlockId,
+        subject: &Operand,
+        field: &str,
+    ) -> (Operand, IrType) {
+        let ty = self.field_type(func, subject, field);
+        let field = self.resolve_field_ref(func, subject, field);
+        let dst = self.builder.push_temp(func, ty.clone());
+        self.builder.push_instr(
+            func,
+            block,
+            Instr::StructGet {
+                dst,
+                ty: ty.clone(),
+                base: subject.clone(),
+                field,
+            },
+        );
+        (Operand::Temp(dst), ty)
+    }
 
-        let dst = self.builder.push_temp(func, value_ty.clone());
+    fn match_array_item(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        block: BlockId,
+        subject: &Operand,
+        elem_ty: &IrType,
+        index: usize,
+    ) -> Operand {
+        let dst = self.builder.push_temp(func, elem_ty.clone());
         self.builder.push_instr(
             func,
-            lowering.current_block,
-            Instr::CallBuiltin {
-                dst: Some(dst),
-                ret_ty: value_ty.clone(),
-                builtin: crate::ir::BuiltinCall {
-                    package: builtin_pkg.to_string(),
-                    name: builtin_name.to_string(),
-                },
-                args: vec![Operand::Local(match_local)],
+            block,
+            Instr::ArrayGet {
+                dst,
+                elem_ty: elem_ty.clone(),
+                array: subject.clone(),
+                index: Operand::Const(crate::ir::ConstValue::Int(index as i64)),
             },
         );
-        let local = self
-            .builder
-            .push_local(func, binding.clone(), value_ty.clone());
-        lowering.locals.insert(binding.clone(), local);
+        Operand::Temp(dst)
+    }
+
+    fn bind_match_local(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        name: &str,
+        ty: IrType,
+        value: Operand,
+    ) {
+        let local = self.builder.push_local(func, name.to_string(), ty.clone());
+        lowering.locals.insert(name.to_string(), local);
         self.builder.push_instr(
             func,
             lowering.current_block,
-            Instr::StoreLocal {
-                local,
-                ty: value_ty,
-                value: Operand::Temp(dst),
-            },
+            Instr::StoreLocal { local, ty, value },
         );
-        true
+    }
+
+    pub(super) fn bind_match_pattern(
+        &mut self,
+        func: &mut crate::ir::IrFunction,
+        lowering: &mut FunctionLowering,
+        subject: Operand,
+        target_ty: &IrType,
+        pattern: &MatchPattern,
+    ) -> bool {
+        match pattern {
+            MatchPattern::Binding(name) => {
+                self.bind_match_local(func, lowering, name, target_ty.clone(), subject);
+                true
+            }
+            MatchPattern::Variant {
+                name,
+                binding: Some(binding),
+            } => {
+                let (builtin_pkg, builtin_name, value_ty) = match (name.as_str(), target_ty) {
+                    ("Some", IrType::Option { value }) => {
+                        ("option", "unwrapSome", (**value).clone())
+                    }
+                    ("Ok", IrType::Result { ok, .. }) => ("result", "unwrapOk", (**ok).clone()),
+                    ("Err", IrType::Result { err, .. }) => ("result", "unwrapErr", (**err).clone()),
+                    _ => return true,
+                };
+
+                let dst = self.builder.push_temp(func, value_ty.clone());
+                self.builder.push_instr(
+                    func,
+                    lowering.current_block,
+                    Instr::CallBuiltin {
+                        dst: Some(dst),
+                        ret_ty: value_ty.clone(),
+                        builtin: crate::ir::BuiltinCall {
+                            package: builtin_pkg.to_string(),
+                            name: builtin_name.to_string(),
+                        },
+                        args: vec![subject],
+                    },
+                );
+                self.bind_match_local(func, lowering, binding, value_ty, Operand::Temp(dst));
+                true
+            }
+            MatchPattern::Struct { fields, .. } => {
+                for (field, part) in fields {
+                    if part.bindings().is_empty() {
+                        continue;
+                    }
+                    let (value, value_ty) =
+                        self.match_struct_field(func, lowering.current_block, &subject, field);
+                    if !self.bind_match_pattern(func, lowering, value, &value_ty, part) {
+                        return false;
+                    }
+                }
+                true
+            }
+            MatchPattern::Array {
+                items,
+                rest_binding,
+                ..
+            } => {
+                let (elem_ty, size) = match target_ty {
+                    IrType::Array { elem, size } => ((**elem).clone(), *size),
+                    _ => (IrType::Unknown, items.len()),
+                };
+                for (index, part) in items.iter().enumerate() {
+                    if part.bindings().is_empty() {
+                        continue;
+                    }
+                    let value = self.match_array_item(
+                        func,
+                        lowering.current_block,
+                        &subject,
+                        &elem_ty,
+                        index,
+                    );
+                    if !self.bind_match_pattern(func, lowering, value, &elem_ty, part) {
+                        return false;
+                    }
+                }
+                if let Some(rest) = rest_binding {
+                    let rest_items = (items.len()..size)
+                        .map(|index| {
+                            self.match_array_item(
+                                func,
+                                lowering.current_block,
+                                &subject,
+                                &elem_ty,
+                                index,
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    let rest_ty = IrType::Array {
+                        elem: Box::new(elem_ty.clone()),
+                        size: rest_items.len(),
+                    };
+                    let dst = self.builder.push_temp(func, rest_ty.clone());
+                    self.builder.push_instr(
+                        func,
+                        lowering.current_block,
+                        Instr::MakeArray {
+                            dst,
+                            elem_ty,
+                            items: rest_items,
+                        },
+                    );
+                    self.bind_match_local(func, lowering, rest, rest_ty, Operand::Temp(dst));
+                }
+                true
+            }
+            MatchPattern::Tuple(items) => {
+                for (index, part) in items.iter().enumerate() {
+                    if part.bindings().is_empty() {
+                        continue;
+                    }
+                    let (value, value_ty) = self.match_struct_field(
+                        func,
+                        lowering.current_block,
+                        &subject,
+                        &index.to_string(),
+                    );
+                    if !self.bind_match_pattern(func, lowering, value, &value_ty, part) {
+                        return false;
+                    }
+                }
+                true
+            }
+            MatchPattern::Wildcard
+            | MatchPattern::Literal(_)
+            | MatchPattern::Or(_)
+            | MatchPattern::Variant { binding: None, .. } => true,
+        }
     }
 
     pub(super) fn ensure_fallthrough_jump(
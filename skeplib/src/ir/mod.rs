@@ -20,7 +20,7 @@ pub use builder::IrBuilder;
 pub use instr::{
     BinaryOp, BranchTerminator, BuiltinCall, CmpOp, FieldRef, Instr, LogicOp, Terminator, UnaryOp,
 };
-pub use interp::{IrInterpError, IrInterpreter};
+pub use interp::{IrExecStats, IrFunctionStats, IrInterpError, IrInterpreter};
 pub use lowered::LoweredIrFunction;
 pub use native_aggregates::{NativeAggregatePlan, NativeArrayPlan, NativeStructPlan};
 pub use native_calls::{NativeCallLowering, NativeCallPlan};
@@ -34,6 +34,6 @@ pub use program::{
     IrFunction, IrGlobal, IrLocal, IrModuleInit, IrParam, IrProgram, IrStruct, IrTemp, StructField,
 };
 pub use skepart::RtValue as IrValue;
-pub use types::IrType;
+pub use types::{IrType, tuple_struct_name};
 pub use value::{ConstValue, Operand};
 pub use verify::{IrVerifier, IrVerifyError};
@@ -197,7 +197,7 @@ fn resolve_const(
 
 fn eval_unary(op: UnaryOp, value: &ConstValue) -> Option<ConstValue> {
     match (op, value) {
-        (UnaryOp::Neg, ConstValue::Int(v)) => Some(ConstValue::Int(v.wrapping_neg())),
+        (UnaryOp::Neg, ConstValue::Int(v)) => v.checked_neg().map(ConstValue::Int),
         (UnaryOp::Neg, ConstValue::Float(v)) => Some(ConstValue::Float(-v)),
         (UnaryOp::Not, ConstValue::Bool(v)) => Some(ConstValue::Bool(!v)),
         (UnaryOp::BitNot, ConstValue::Int(v)) => Some(ConstValue::Int(!v)),
@@ -207,14 +207,15 @@ fn eval_unary(op: UnaryOp, value: &ConstValue) -> Option<ConstValue> {
 
 fn eval_binary(op: BinaryOp, left: &ConstValue, right: &ConstValue) -> Option<ConstValue> {
     match (op, left, right) {
+        // Overflowing Int arithmetic is left unfolded so it still raises at runtime.
         (BinaryOp::Add, ConstValue::Int(a), ConstValue::Int(b)) => {
-            Some(ConstValue::Int(a.wrapping_add(*b)))
+            a.checked_add(*b).map(ConstValue::Int)
         }
         (BinaryOp::Sub, ConstValue::Int(a), ConstValue::Int(b)) => {
-            Some(ConstValue::Int(a.wrapping_sub(*b)))
+            a.checked_sub(*b).map(ConstValue::Int)
         }
         (BinaryOp::Mul, ConstValue::Int(a), ConstValue::Int(b)) => {
-            Some(ConstValue::Int(a.wrapping_mul(*b)))
+            a.checked_mul(*b).map(ConstValue::Int)
         }
         (BinaryOp::Div, ConstValue::Int(_), ConstValue::Int(0)) => None,
         (BinaryOp::Div, ConstValue::Int(a), ConstValue::Int(-1)) if *a == i64::MIN => None,
@@ -222,7 +223,6 @@ fn eval_binary(op: BinaryOp, left: &ConstValue, right: &ConstValue) -> Option<Co
             Some(ConstValue::Int(a.wrapping_div(*b)))
         }
         (BinaryOp::Mod, ConstValue::Int(_), ConstValue::Int(0)) => None,
-        (BinaryOp::Mod, ConstValue::Int(a), ConstValue::Int(-1)) if *a == i64::MIN => None,
         (BinaryOp::Mod, ConstValue::Int(a), ConstValue::Int(b)) => {
             Some(ConstValue::Int(a.wrapping_rem(*b)))
         }
@@ -277,6 +277,14 @@ fn eval_compare(op: CmpOp, left: &ConstValue, right: &ConstValue) -> Option<bool
             CmpOp::Ne => a != b,
             _ => return None,
         }),
+        (ConstValue::Char(a), ConstValue::Char(b)) => Some(match op {
+            CmpOp::Eq => a == b,
+            CmpOp::Ne => a != b,
+            CmpOp::Lt => a < b,
+            CmpOp::Le => a <= b,
+            CmpOp::Gt => a > b,
+            CmpOp::Ge => a >= b,
+        }),
         (ConstValue::String(a), ConstValue::String(b)) => Some(match op {
             CmpOp::Eq => a == b,
             CmpOp::Ne => a != b,
@@ -190,10 +190,13 @@ fn rewrite_operand(operand: &mut Operand, copies: &HashMap<crate::ir::TempId, Op
 fn is_copy_propagation_safe_const(value: &ConstValue) -> bool {
     matches!(
         value,
-        ConstValue::Int(_) | ConstValue::Float(_) | ConstValue::Bool(_)
+        ConstValue::Int(_) | ConstValue::Float(_) | ConstValue::Bool(_) | ConstValue::Char(_)
     )
 }
 
 fn is_copy_propagation_safe_type(ty: &IrType) -> bool {
-    matches!(ty, IrType::Int | IrType::Float | IrType::Bool)
+    matches!(
+        ty,
+        IrType::Int | IrType::Float | IrType::Bool | IrType::Char
+    )
 }
@@ -77,7 +77,7 @@ fn is_pure(instr: &Instr) -> bool {
         | Instr::MakeStruct { .. }
         | Instr::StructGet { .. }
         | Instr::MakeClosure { .. } => true,
-        // Negation can trap on i64::MIN in debug builds.
+        // Negation raises on i64::MIN.
         Instr::Unary {
             op: crate::ir::UnaryOp::Neg,
             ..
@@ -92,6 +92,12 @@ fn is_pure(instr: &Instr) -> bool {
                 | crate::ir::BinaryOp::Shr,
             ..
         } => false,
+        // Int add/sub/mul raise on overflow.
+        Instr::Binary {
+            ty: crate::ir::IrType::Int,
+            op: crate::ir::BinaryOp::Add | crate::ir::BinaryOp::Sub | crate::ir::BinaryOp::Mul,
+            ..
+        } => false,
         Instr::Binary { .. } => true,
         // Indexing may trap; VecDelete mutates the vector.
         Instr::ArrayGet { .. } | Instr::VecGet { .. } | Instr::VecDelete { .. } => false,
@@ -160,7 +160,10 @@ fn collect_operand_reads(
 }
 
 fn is_dead_store_safe_type(ty: &IrType) -> bool {
-    matches!(ty, IrType::Int | IrType::Float | IrType::Bool)
+    matches!(
+        ty,
+        IrType::Int | IrType::Float | IrType::Bool | IrType::Char
+    )
 }
 
 fn is_effect_barrier(instr: &Instr) -> bool {
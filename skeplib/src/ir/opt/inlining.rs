@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::ir::{
     BuiltinCall, ConstValue, FieldRef, FunctionId, Instr, IrFunction, IrLocal, IrProgram, IrTemp,
@@ -56,9 +56,15 @@ struct InlineCandidate {
 }
 
 fn collect_candidates(program: &IrProgram) -> HashMap<FunctionId, InlineCandidate> {
+    let hinted = program
+        .functions
+        .iter()
+        .filter(|func| func.inline_hint)
+        .map(|func| func.id)
+        .collect::<HashSet<_>>();
     let mut out = HashMap::new();
     for func in &program.functions {
-        let Some(candidate) = build_candidate(func) else {
+        let Some(candidate) = build_candidate(func, &hinted) else {
             continue;
         };
         out.insert(func.id, candidate);
@@ -66,7 +72,16 @@ fn collect_candidates(program: &IrProgram) -> HashMap<FunctionId, InlineCandidat
     out
 }
 
-fn build_candidate(func: &IrFunction) -> Option<InlineCandidate> {
+/// Largest body, in instructions, inlined without an `@inline` hint. Leaf
+/// getters and one-line arithmetic fit; larger bodies keep their call so every
+/// caller does not grow by a copy.
+const MAX_INLINE_INSTRS: usize = 16;
+
+/// A single-block function is inlined when its body makes no calls and has at
+/// most [`MAX_INLINE_INSTRS`] instructions. An `@inline` function has no size
+/// limit and may make calls, except direct calls to `@inline` functions, which
+/// keeps repeated inlining from expanding a recursive cycle forever.
+fn build_candidate(func: &IrFunction, hinted: &HashSet<FunctionId>) -> Option<InlineCandidate> {
     if func.blocks.len() != 1 {
         return None;
     }
@@ -74,24 +89,29 @@ fn build_candidate(func: &IrFunction) -> Option<InlineCandidate> {
     let Terminator::Return(ret) = &block.terminator else {
         return None;
     };
-    if block.instrs.iter().any(|instr| {
-        matches!(
-            instr,
-            Instr::CallDirect { .. } | Instr::CallIndirect { .. } | Instr::CallBuiltin { .. }
-        )
+    if !func.inline_hint && block.instrs.len() > MAX_INLINE_INSTRS {
+        return None;
+    }
+    if block.instrs.iter().any(|instr| match instr {
+        Instr::CallDirect { function, .. } => !func.inline_hint || hinted.contains(function),
+        Instr::CallIndirect { .. } | Instr::CallBuiltin { .. } => !func.inline_hint,
+        _ => false,
     }) {
         return None;
     }
 
+    // Parameter `i` arrives in local slot `i`, as in the interpreter and codegen;
+    // later locals may reuse a parameter's name in a nested scope.
+    if func.locals.len() < func.params.len() {
+        return None;
+    }
     Some(InlineCandidate {
         params: func
             .params
             .iter()
-            .map(|param| {
-                let local = func.locals.iter().find(|local| local.name == param.name)?;
-                Some((local.id, param.ty.clone()))
-            })
-            .collect::<Option<Vec<_>>>()?,
+            .zip(&func.locals)
+            .map(|(param, local)| (local.id, param.ty.clone()))
+            .collect(),
         locals: func.locals.clone(),
         temps: func.temps.clone(),
         instrs: block.instrs.clone(),
@@ -480,6 +500,7 @@ fn remap_operand(
         Operand::Const(ConstValue::Int(v)) => Operand::Const(ConstValue::Int(*v)),
         Operand::Const(ConstValue::Float(v)) => Operand::Const(ConstValue::Float(*v)),
         Operand::Const(ConstValue::Bool(v)) => Operand::Const(ConstValue::Bool(*v)),
+        Operand::Const(ConstValue::Char(v)) => Operand::Const(ConstValue::Char(*v)),
         Operand::Const(ConstValue::String(v)) => Operand::Const(ConstValue::String(v.clone())),
         Operand::Const(ConstValue::Unit) => Operand::Const(ConstValue::Unit),
         Operand::Temp(id) => Operand::Temp(remap_temp(*id, temp_map)),
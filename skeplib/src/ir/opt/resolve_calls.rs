@@ -64,6 +64,7 @@ mod tests {
                 instrs: vec![],
                 terminator: Terminator::Return(Some(Operand::Const(crate::ir::ConstValue::Int(1)))),
             }],
+            inline_hint: false,
         });
         program.functions.push(IrFunction {
             id: FunctionId(1),
@@ -117,6 +118,7 @@ mod tests {
                 ],
                 terminator: Terminator::Return(Some(Operand::Temp(TempId(2)))),
             }],
+            inline_hint: false,
         });
 
         let main_before = program
@@ -51,6 +51,9 @@ pub struct IrFunction {
     pub ret_ty: IrType,
     pub entry: BlockId,
     pub blocks: Vec<BasicBlock>,
+    /// Set for `@inline` functions, which the inliner expands even when their
+    /// body makes calls.
+    pub inline_hint: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
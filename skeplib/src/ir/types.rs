@@ -5,6 +5,7 @@ pub enum IrType {
     Int,
     Float,
     Bool,
+    Char,
     String,
     Bytes,
     Void,
@@ -40,6 +41,7 @@ impl From<&TypeInfo> for IrType {
             TypeInfo::Int => Self::Int,
             TypeInfo::Float => Self::Float,
             TypeInfo::Bool => Self::Bool,
+            TypeInfo::Char => Self::Char,
             TypeInfo::String => Self::String,
             TypeInfo::Bytes => Self::Bytes,
             TypeInfo::Void => Self::Void,
@@ -66,13 +68,60 @@ impl From<&TypeInfo> for IrType {
                 params: params.iter().map(Self::from).collect(),
                 ret: Box::new(Self::from(ret.as_ref())),
             },
+            TypeInfo::Tuple(items) => Self::Named(tuple_struct_name(
+                &items.iter().map(Self::from).collect::<Vec<_>>(),
+            )),
             TypeInfo::Unknown => Self::Unknown,
         }
     }
 }
 
+/// Tuples lower to anonymous structs with fields `0`, `1`, ... named after
+/// their element types, so `(Int, String)` shares one shape program-wide.
+pub fn tuple_struct_name(items: &[IrType]) -> String {
+    format!(
+        "({})",
+        items
+            .iter()
+            .map(IrType::source_name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
 impl IrType {
     pub fn is_void(&self) -> bool {
         matches!(self, Self::Void)
     }
+
+    /// The type as it is written in source.
+    pub fn source_name(&self) -> String {
+        match self {
+            IrType::Int => "Int".to_string(),
+            IrType::Float => "Float".to_string(),
+            IrType::Bool => "Bool".to_string(),
+            IrType::Char => "Char".to_string(),
+            IrType::String => "String".to_string(),
+            IrType::Bytes => "Bytes".to_string(),
+            IrType::Void => "Void".to_string(),
+            IrType::Option { value } => format!("Option[{}]", value.source_name()),
+            IrType::Result { ok, err } => {
+                format!("Result[{}, {}]", ok.source_name(), err.source_name())
+            }
+            IrType::Named(name) | IrType::Opaque(name) => name.clone(),
+            IrType::Array { elem, size } => format!("[{}; {}]", elem.source_name(), size),
+            IrType::Vec { elem } => format!("Vec[{}]", elem.source_name()),
+            IrType::Map { value } => format!("Map[String, {}]", value.source_name()),
+            IrType::Fn { params, ret } => format!(
+                "Fn({}) -> {}",
+                params
+                    .iter()
+                    .map(IrType::source_name)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                ret.source_name()
+            ),
+            IrType::Unknown => "Unknown".to_string(),
+        }
+    }
 }
@@ -5,6 +5,7 @@ pub enum ConstValue {
     Int(i64),
     Float(f64),
     Bool(bool),
+    Char(char),
     String(String),
     Unit,
 }
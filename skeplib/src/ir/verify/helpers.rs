@@ -251,6 +251,7 @@ impl IrVerifier {
                 crate::ir::ConstValue::Int(_) => crate::ir::IrType::Int,
                 crate::ir::ConstValue::Float(_) => crate::ir::IrType::Float,
                 crate::ir::ConstValue::Bool(_) => crate::ir::IrType::Bool,
+                crate::ir::ConstValue::Char(_) => crate::ir::IrType::Char,
                 crate::ir::ConstValue::String(_) => crate::ir::IrType::String,
                 crate::ir::ConstValue::Unit => crate::ir::IrType::Void,
             }),
@@ -545,6 +545,8 @@ impl IrVerifier {
             }
             "cstr,usize->usize" => (vec![IrType::String, IrType::Int], IrType::Int),
             "i64,i64->i64" => (vec![IrType::Int, IrType::Int], IrType::Int),
+            "f64->f64" => (vec![IrType::Float], IrType::Float),
+            "f64,f64->f64" => (vec![IrType::Float, IrType::Float], IrType::Float),
             "bytes->usize" => (vec![IrType::Bytes], IrType::Int),
             "bytes,usize->usize" => (vec![IrType::Bytes, IrType::Int], IrType::Int),
             _ => return None,
@@ -16,6 +16,42 @@ struct Lexer {
     diagnostics: DiagnosticBag,
 }
 
+/// Maps the character after a leading `0` to a radix and its display name.
+fn radix_prefix(ch: char) -> Option<(u32, &'static str)> {
+    match ch {
+        'x' => Some((16, "hexadecimal")),
+        'o' => Some((8, "octal")),
+        'b' => Some((2, "binary")),
+        _ => None,
+    }
+}
+
+/// Every `_` in a numeric literal must sit directly between two digits.
+fn separators_are_valid(text: &str) -> bool {
+    let chars: Vec<char> = text.chars().collect();
+    chars.iter().enumerate().all(|(i, ch)| {
+        *ch != '_'
+            || (i > 0
+                && chars[i - 1].is_ascii_alphanumeric()
+                && chars
+                    .get(i + 1)
+                    .is_some_and(|next| next.is_ascii_alphanumeric()))
+    })
+}
+
+/// Decodes the value of an `IntLit` lexeme, including `0x`/`0o`/`0b` prefixes
+/// and `_` separators. Returns `None` when the value does not fit in `Int`.
+pub(crate) fn int_literal_value(lexeme: &str) -> Option<i64> {
+    let (radix, digits) = match lexeme.get(..2) {
+        Some("0x") => (16, &lexeme[2..]),
+        Some("0o") => (8, &lexeme[2..]),
+        Some("0b") => (2, &lexeme[2..]),
+        _ => (10, lexeme),
+    };
+    let digits: String = digits.chars().filter(|ch| *ch != '_').collect();
+    i64::from_str_radix(&digits, radix).ok()
+}
+
 impl Lexer {
     fn new(source: &str) -> Self {
         Self {
@@ -57,6 +93,7 @@ impl Lexer {
 
         match c {
             '"' => self.lex_string(start, line, col),
+            '\'' => self.lex_char(start, line, col),
             '(' => self.single(TokenKind::LParen, start, line, col),
             ')' => self.single(TokenKind::RParen, start, line, col),
             '[' => self.single(TokenKind::LBracket, start, line, col),
@@ -64,8 +101,18 @@ impl Lexer {
             '{' => self.single(TokenKind::LBrace, start, line, col),
             '}' => self.single(TokenKind::RBrace, start, line, col),
             '`' => self.single(TokenKind::Backtick, start, line, col),
+            '#' => self.single(TokenKind::Hash, start, line, col),
+            '@' => self.single(TokenKind::At, start, line, col),
             ',' => self.single(TokenKind::Comma, start, line, col),
-            '.' => self.single(TokenKind::Dot, start, line, col),
+            '.' => {
+                self.bump();
+                if self.peek() == Some('.') {
+                    self.bump();
+                    self.push_token(TokenKind::DotDot, start, line, col);
+                } else {
+                    self.push_token(TokenKind::Dot, start, line, col);
+                }
+            }
             '?' => self.single(TokenKind::Question, start, line, col),
             ':' => self.single(TokenKind::Colon, start, line, col),
             ';' => self.single(TokenKind::Semi, start, line, col),
@@ -149,7 +196,8 @@ impl Lexer {
             }
             _ => {
                 self.bump();
-                self.diagnostics.error(
+                self.diagnostics.error_with_code(
+                    "E-PARSE-001",
                     format!("Unexpected character '{c}'"),
                     Span::new(start, self.idx, line, col),
                 );
@@ -173,11 +221,13 @@ impl Lexer {
             "opr" => TokenKind::KwOpr,
             "precedence" => TokenKind::KwPrecedence,
             "struct" => TokenKind::KwStruct,
+            "type" => TokenKind::KwType,
             "impl" => TokenKind::KwImpl,
             "let" => TokenKind::KwLet,
             "if" => TokenKind::KwIf,
             "else" => TokenKind::KwElse,
             "while" => TokenKind::KwWhile,
+            "defer" => TokenKind::KwDefer,
             "for" => TokenKind::KwFor,
             "break" => TokenKind::KwBreak,
             "continue" => TokenKind::KwContinue,
@@ -188,6 +238,7 @@ impl Lexer {
             "Int" => TokenKind::TyInt,
             "Float" => TokenKind::TyFloat,
             "Bool" => TokenKind::TyBool,
+            "Char" => TokenKind::TyChar,
             "String" => TokenKind::TyString,
             "Bytes" => TokenKind::TyBytes,
             "Void" => TokenKind::TyVoid,
@@ -201,33 +252,127 @@ impl Lexer {
     }
 
     fn lex_number(&mut self, start: usize, line: usize, col: usize) {
-        self.bump();
-        while matches!(self.peek(), Some(ch) if ch.is_ascii_digit()) {
-            self.bump();
+        if self.peek() == Some('0')
+            && let Some((radix, name)) = self.peek_next().and_then(radix_prefix)
+        {
+            self.lex_radix_int(start, line, col, radix, name);
+            return;
         }
 
+        self.bump_digits();
         let mut kind = TokenKind::IntLit;
         if self.peek() == Some('.') && matches!(self.peek_next(), Some(ch) if ch.is_ascii_digit()) {
             kind = TokenKind::FloatLit;
             self.bump();
-            while matches!(self.peek(), Some(ch) if ch.is_ascii_digit()) {
+            self.bump_digits();
+        }
+
+        let lexeme = self.slice(start, self.idx);
+        let span = Span::new(start, self.idx, line, col);
+        if !separators_are_valid(&lexeme) {
+            self.diagnostics.error_with_code(
+                "E-PARSE-003",
+                format!(
+                    "Digit separator `_` must appear between digits in numeric literal `{lexeme}`"
+                ),
+                span,
+            );
+            return;
+        }
+        self.tokens.push(Token::new(kind, lexeme, span));
+    }
+
+    fn lex_radix_int(&mut self, start: usize, line: usize, col: usize, radix: u32, name: &str) {
+        self.bump();
+        self.bump();
+        while matches!(self.peek(), Some(ch) if Self::is_ident_continue(ch)) {
+            self.bump();
+        }
+
+        let lexeme = self.slice(start, self.idx);
+        let span = Span::new(start, self.idx, line, col);
+        let prefix = &lexeme[..2];
+        let digits = &lexeme[2..];
+        if digits.chars().all(|ch| ch == '_') {
+            self.diagnostics.error_with_code(
+                "E-PARSE-003",
+                format!("Expected {name} digits after `{prefix}`"),
+                span,
+            );
+            return;
+        }
+        if let Some(bad) = digits.chars().find(|ch| *ch != '_' && !ch.is_digit(radix)) {
+            self.diagnostics.error_with_code(
+                "E-PARSE-003",
+                format!("Invalid digit `{bad}` in {name} literal `{lexeme}`"),
+                span,
+            );
+            return;
+        }
+        if !separators_are_valid(digits) {
+            self.diagnostics.error_with_code(
+                "E-PARSE-003",
+                format!(
+                    "Digit separator `_` must appear between digits in numeric literal `{lexeme}`"
+                ),
+                span,
+            );
+            return;
+        }
+        self.tokens
+            .push(Token::new(TokenKind::IntLit, lexeme, span));
+    }
+
+    fn bump_digits(&mut self) {
+        while matches!(self.peek(), Some(ch) if ch.is_ascii_digit() || ch == '_') {
+            self.bump();
+        }
+    }
+
+    fn lex_string(&mut self, start: usize, line: usize, col: usize) {
+        self.bump();
+        let mut terminated = false;
+        while let Some(ch) = self.peek() {
+            if ch == '"' {
                 self.bump();
+                terminated = true;
+                break;
             }
+            if ch == '\\' {
+                self.bump();
+                if self.peek().is_some() {
+                    self.bump();
+                }
+                continue;
+            }
+            if ch == '\n' {
+                break;
+            }
+            self.bump();
+        }
+
+        if !terminated {
+            self.diagnostics.error_with_code(
+                "E-PARSE-002",
+                "Unterminated string literal",
+                Span::new(start, self.idx, line, col),
+            );
+            return;
         }
 
         let lexeme = self.slice(start, self.idx);
         self.tokens.push(Token::new(
-            kind,
+            TokenKind::StringLit,
             lexeme,
             Span::new(start, self.idx, line, col),
         ));
     }
 
-    fn lex_string(&mut self, start: usize, line: usize, col: usize) {
+    fn lex_char(&mut self, start: usize, line: usize, col: usize) {
         self.bump();
         let mut terminated = false;
         while let Some(ch) = self.peek() {
-            if ch == '"' {
+            if ch == '\'' {
                 self.bump();
                 terminated = true;
                 break;
@@ -246,8 +391,9 @@ impl Lexer {
         }
 
         if !terminated {
-            self.diagnostics.error(
-                "Unterminated string literal",
+            self.diagnostics.error_with_code(
+                "E-PARSE-002",
+                "Unterminated character literal",
                 Span::new(start, self.idx, line, col),
             );
             return;
@@ -255,7 +401,7 @@ impl Lexer {
 
         let lexeme = self.slice(start, self.idx);
         self.tokens.push(Token::new(
-            TokenKind::StringLit,
+            TokenKind::CharLit,
             lexeme,
             Span::new(start, self.idx, line, col),
         ));
@@ -272,6 +418,12 @@ impl Lexer {
 
             if self.peek() == Some('/') && self.peek_next() == Some('/') {
                 progressed = true;
+                let start = self.idx;
+                let line = self.line;
+                let col = self.col;
+                // `///` starts a doc comment; `////` and longer stay plain comments.
+                let is_doc = self.chars.get(self.idx + 2) == Some(&'/')
+                    && self.chars.get(self.idx + 3) != Some(&'/');
                 self.bump();
                 self.bump();
                 while let Some(ch) = self.peek() {
@@ -280,6 +432,9 @@ impl Lexer {
                     }
                     self.bump();
                 }
+                if is_doc {
+                    self.push_token(TokenKind::DocComment, start, line, col);
+                }
                 continue;
             }
 
@@ -290,18 +445,30 @@ impl Lexer {
                 let col = self.col;
                 self.bump();
                 self.bump();
+                let mut depth = 1usize;
                 let mut terminated = false;
                 while !self.is_eof() {
+                    if self.peek() == Some('/') && self.peek_next() == Some('*') {
+                        self.bump();
+                        self.bump();
+                        depth += 1;
+                        continue;
+                    }
                     if self.peek() == Some('*') && self.peek_next() == Some('/') {
                         self.bump();
                         self.bump();
-                        terminated = true;
-                        break;
+                        depth -= 1;
+                        if depth == 0 {
+                            terminated = true;
+                            break;
+                        }
+                        continue;
                     }
                     self.bump();
                 }
                 if !terminated {
-                    self.diagnostics.error(
+                    self.diagnostics.error_with_code(
+                        "E-PARSE-002",
                         "Unterminated block comment",
                         Span::new(start, self.idx, line, col),
                     );
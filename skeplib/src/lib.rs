@@ -2,8 +2,15 @@ pub mod ast;
 pub mod builtins;
 pub mod codegen;
 pub mod diagnostic;
+pub mod docgen;
+pub mod driver;
+pub mod error_codes;
+pub mod eval;
+pub mod exit_codes;
+pub mod index;
 pub mod ir;
 pub mod lexer;
+pub mod lint;
 pub mod parser;
 pub mod resolver;
 pub mod sema;
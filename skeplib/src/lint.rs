@@ -0,0 +1,142 @@
+//! Lint levels for sema warnings. A [`LintConfig`] comes from the embedder or
+//! `skepac --allow/--warn/--deny`, and a module can override it with
+//! `#allow(name)`, `#warn(name)`, or `#deny(name)` directives, which apply to the
+//! whole file.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// A warning that can be allowed, kept as a warning, or turned into an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lint {
+    /// A statement after `return`, `break`, `continue`, or a diverging call.
+    UnreachableCode,
+    /// A use of a function or struct marked `@deprecated`.
+    Deprecated,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 2] = [Lint::UnreachableCode, Lint::Deprecated];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::UnreachableCode => "unreachable_code",
+            Self::Deprecated => "deprecated",
+        }
+    }
+
+    /// The diagnostic code the lint is reported under.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::UnreachableCode => "E-SEMA-021",
+            Self::Deprecated => "E-SEMA-026",
+        }
+    }
+
+    /// Looks a lint up by name; `unreachable-code` and `unreachable_code` are
+    /// the same lint.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.replace('-', "_");
+        Self::ALL.into_iter().find(|lint| lint.name() == name)
+    }
+
+    pub fn default_level(self) -> LintLevel {
+        match self {
+            Self::UnreachableCode | Self::Deprecated => LintLevel::Warn,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl LintLevel {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// What a level applies to: one lint, or `warnings`, every lint that would
+/// otherwise warn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintTarget {
+    Lint(Lint),
+    Warnings,
+}
+
+impl LintTarget {
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name == "warnings" {
+            return Some(Self::Warnings);
+        }
+        Lint::from_name(name).map(Self::Lint)
+    }
+}
+
+/// `#allow(...)`, `#warn(...)`, or `#deny(...)` at the top level of a module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintDirective {
+    pub level: LintLevel,
+    pub targets: Vec<LintTarget>,
+}
+
+/// Active level of every lint. Later settings win, so a module's directives
+/// applied on top of the command-line config take precedence. A level set for
+/// `warnings` applies to every lint whose own level is `Warn`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintConfig {
+    levels: HashMap<Lint, LintLevel>,
+    warnings: Option<LintLevel>,
+}
+
+impl LintConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, target: LintTarget, level: LintLevel) -> &mut Self {
+        match target {
+            LintTarget::Lint(lint) => {
+                self.levels.insert(lint, level);
+            }
+            LintTarget::Warnings => self.warnings = Some(level),
+        }
+        self
+    }
+
+    /// This config with `directives` applied in order.
+    pub fn with_directives(&self, directives: &[LintDirective]) -> Self {
+        let mut config = self.clone();
+        for directive in directives {
+            for target in &directive.targets {
+                config.set(*target, directive.level);
+            }
+        }
+        config
+    }
+
+    pub fn level(&self, lint: Lint) -> LintLevel {
+        let level = self
+            .levels
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_level());
+        match (level, self.warnings) {
+            (LintLevel::Warn, Some(warnings)) => warnings,
+            _ => level,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::ast::{BinaryOp, Expr, MatchExprArm, UnaryOp};
+use crate::lexer::int_literal_value;
 use crate::token::TokenKind;
 
 use super::Parser;
@@ -136,7 +137,7 @@ impl Parser {
                         InfixPeek::Known(InfixOp::Custom(operator.lexeme.clone()), *precedence)
                     }
                     None => {
-                        self.diagnostics.error(
+                        self.diagnostics.error_with_code("E-PARSE-008", 
                             format!(
                                 "Unknown operator `{}`; declare it locally or import it with `from ... import ...` so its precedence is known during parsing",
                                 operator.lexeme
@@ -252,7 +253,29 @@ impl Parser {
 
             if self.at(TokenKind::LBracket) {
                 self.bump();
-                let index = self.parse_expr()?;
+                let start = if self.at(TokenKind::DotDot) {
+                    None
+                } else {
+                    Some(self.parse_expr()?)
+                };
+                let index = match start {
+                    Some(index) if !self.at(TokenKind::DotDot) => index,
+                    start => {
+                        self.bump();
+                        let end = if self.at(TokenKind::RBracket) {
+                            None
+                        } else {
+                            Some(Box::new(self.parse_expr()?))
+                        };
+                        self.expect(TokenKind::RBracket, "Expected `]` after slice range")?;
+                        expr = Expr::Slice {
+                            base: Box::new(expr),
+                            start: start.map(Box::new),
+                            end,
+                        };
+                        continue;
+                    }
+                };
                 self.expect(TokenKind::RBracket, "Expected `]` after index expression")?;
                 expr = Expr::Index {
                     base: Box::new(expr),
@@ -263,6 +286,10 @@ impl Parser {
 
             if self.at(TokenKind::Dot) {
                 self.bump();
+                if self.at(TokenKind::IntLit) || self.at(TokenKind::FloatLit) {
+                    expr = self.parse_tuple_index(expr);
+                    continue;
+                }
                 let field = self.expect_ident("Expected identifier after `.`")?;
                 expr = Expr::Field {
                     base: Box::new(expr),
@@ -283,6 +310,7 @@ impl Parser {
                 let fields = self.parse_struct_literal_fields()?;
                 expr = Expr::StructLit {
                     name: parts.join("."),
+                    type_args: Vec::new(),
                     fields,
                 };
                 continue;
@@ -293,178 +321,288 @@ impl Parser {
         Some(expr)
     }
 
+    /// Every branch but the grouping one lives in its own out-of-line helper:
+    /// `(` recurses through here once per nesting level, so this frame has to
+    /// stay small for deeply nested expressions.
     fn parse_primary(&mut self) -> Option<Expr> {
-        if self.at(TokenKind::KwMatch) {
-            self.bump();
-            self.expect(TokenKind::LParen, "Expected `(` after `match`")?;
-            let expr = self.parse_expr()?;
-            self.expect(TokenKind::RParen, "Expected `)` after match target")?;
-            self.expect(TokenKind::LBrace, "Expected `{` before match arms")?;
-            let mut arms = Vec::new();
-            if self.at(TokenKind::RBrace) {
-                self.error_here_expected("Expected at least one match arm");
+        match self.current().kind {
+            TokenKind::KwMatch => self.parse_match_expr(),
+            TokenKind::KwFn => self.parse_fn_literal(),
+            TokenKind::IntLit => self.parse_int_literal(),
+            TokenKind::FloatLit => {
+                let tok = self.bump();
+                Some(Expr::FloatLit(tok.lexeme.replace('_', "")))
+            }
+            TokenKind::KwTrue => {
+                self.bump();
+                Some(Expr::BoolLit(true))
+            }
+            TokenKind::KwFalse => {
+                self.bump();
+                Some(Expr::BoolLit(false))
+            }
+            TokenKind::StringLit => self.parse_string_literal(),
+            TokenKind::CharLit => self.parse_char_literal(),
+            TokenKind::Ident => self.parse_ident_primary(),
+            TokenKind::LBracket => self.parse_array_literal(),
+            TokenKind::LParen => {
+                self.bump();
+                let expr = self.parse_expr()?;
+                if self.at(TokenKind::Comma) {
+                    return self.parse_tuple_literal_tail(expr);
+                }
+                self.expect(TokenKind::RParen, "Expected `)` after grouped expression")?;
+                Some(Expr::Group(Box::new(expr)))
+            }
+            _ => {
+                self.error_here_expected("Expected expression");
+                None
+            }
+        }
+    }
+
+    /// `match (target) { arms }`; the current token is `match`.
+    #[inline(never)]
+    fn parse_match_expr(&mut self) -> Option<Expr> {
+        self.bump();
+        self.expect(TokenKind::LParen, "Expected `(` after `match`")?;
+        let expr = self.parse_expr()?;
+        self.expect(TokenKind::RParen, "Expected `)` after match target")?;
+        self.expect(TokenKind::LBrace, "Expected `{` before match arms")?;
+        let mut arms = Vec::new();
+        if self.at(TokenKind::RBrace) {
+            self.error_here_expected("Expected at least one match arm");
+            return None;
+        }
+        while !self.at(TokenKind::RBrace) && !self.at(TokenKind::Eof) {
+            let pattern = self.parse_match_pattern()?;
+            let guard = self.parse_match_guard()?;
+            self.expect(TokenKind::FatArrow, "Expected `=>` after match pattern")?;
+            let arm_expr = self.parse_expr()?;
+            arms.push(MatchExprArm {
+                pattern,
+                guard,
+                expr: arm_expr,
+            });
+            if self.at(TokenKind::Comma) {
+                self.bump();
+                if self.at(TokenKind::RBrace) {
+                    break;
+                }
+            } else if !self.at(TokenKind::RBrace) {
+                self.error_here_expected("Expected `,` or `}` after match arm expression");
                 return None;
             }
-            while !self.at(TokenKind::RBrace) && !self.at(TokenKind::Eof) {
-                let pattern = self.parse_match_pattern()?;
-                self.expect(TokenKind::FatArrow, "Expected `=>` after match pattern")?;
-                let arm_expr = self.parse_expr()?;
-                arms.push(MatchExprArm {
-                    pattern,
-                    expr: arm_expr,
+        }
+        self.expect(TokenKind::RBrace, "Expected `}` after match expression")?;
+        Some(Expr::Match {
+            expr: Box::new(expr),
+            arms,
+        })
+    }
+
+    /// `fn(params) -> Type { body }`; the current token is `fn`.
+    #[inline(never)]
+    fn parse_fn_literal(&mut self) -> Option<Expr> {
+        self.bump();
+        self.expect(
+            TokenKind::LParen,
+            "Expected `(` after `fn` in function literal",
+        )?;
+        let mut params = Vec::new();
+        if !self.at(TokenKind::RParen) {
+            loop {
+                let name = self.expect_ident("Expected parameter name in function literal")?;
+                self.expect(TokenKind::Colon, "Expected `:` after parameter name")?;
+                let ty = self.expect_type_name(
+                    "Expected function literal parameter type after `:`, for example `fn(x: Int) -> Int { ... }`",
+                )?;
+                params.push(crate::ast::Param {
+                    name: name.lexeme,
+                    ty,
+                    span: name.span,
                 });
                 if self.at(TokenKind::Comma) {
                     self.bump();
-                    if self.at(TokenKind::RBrace) {
+                    if self.at(TokenKind::RParen) {
                         break;
                     }
-                } else if !self.at(TokenKind::RBrace) {
-                    self.error_here_expected("Expected `,` or `}` after match arm expression");
-                    return None;
+                    continue;
                 }
+                break;
             }
-            self.expect(TokenKind::RBrace, "Expected `}` after match expression")?;
-            return Some(Expr::Match {
-                expr: Box::new(expr),
-                arms,
-            });
         }
-        if self.at(TokenKind::KwFn) {
+        self.expect(
+            TokenKind::RParen,
+            "Expected `)` after function literal parameters",
+        )?;
+        self.expect(
+            TokenKind::Arrow,
+            "Expected `->` after function literal parameters",
+        )?;
+        let return_type = self.expect_type_name(
+            "Expected function literal return type after `->`, for example `fn(x: Int) -> Int { ... }`",
+        )?;
+        let body = self.parse_block("Expected `{` before function literal body")?;
+        Some(Expr::FnLit {
+            params,
+            return_type,
+            body,
+        })
+    }
+
+    #[inline(never)]
+    fn parse_int_literal(&mut self) -> Option<Expr> {
+        let tok = self.bump();
+        let value = match int_literal_value(&tok.lexeme) {
+            Some(v) => v,
+            None => {
+                self.diagnostics.error_with_code(
+                    "E-PARSE-003",
+                    format!("Integer literal `{}` is out of range for `Int`", tok.lexeme),
+                    tok.span,
+                );
+                return None;
+            }
+        };
+        Some(Expr::IntLit(value))
+    }
+
+    #[inline(never)]
+    fn parse_string_literal(&mut self) -> Option<Expr> {
+        let tok = self.bump();
+        let s = tok
+            .lexeme
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(&tok.lexeme)
+            .to_string();
+        let s = self.decode_string_escapes(&s, tok.span);
+        Some(Expr::StringLit(s))
+    }
+
+    #[inline(never)]
+    fn parse_char_literal(&mut self) -> Option<Expr> {
+        let tok = self.bump();
+        let raw = tok
+            .lexeme
+            .strip_prefix('\'')
+            .and_then(|v| v.strip_suffix('\''))
+            .unwrap_or(&tok.lexeme)
+            .to_string();
+        Some(Expr::CharLit(self.decode_char_literal(&raw, tok.span)))
+    }
+
+    /// A name, or a `vec![...]` or struct literal that starts with one.
+    #[inline(never)]
+    fn parse_ident_primary(&mut self) -> Option<Expr> {
+        let name = self.bump().lexeme;
+        if name == "vec" && self.at(TokenKind::Bang) {
             self.bump();
-            self.expect(
-                TokenKind::LParen,
-                "Expected `(` after `fn` in function literal",
-            )?;
-            let mut params = Vec::new();
-            if !self.at(TokenKind::RParen) {
-                loop {
-                    let name = self.expect_ident("Expected parameter name in function literal")?;
-                    self.expect(TokenKind::Colon, "Expected `:` after parameter name")?;
-                    let ty = self.expect_type_name(
-                        "Expected function literal parameter type after `:`, for example `fn(x: Int) -> Int { ... }`",
-                    )?;
-                    params.push(crate::ast::Param {
-                        name: name.lexeme,
-                        ty,
-                    });
-                    if self.at(TokenKind::Comma) {
-                        self.bump();
-                        if self.at(TokenKind::RParen) {
-                            break;
-                        }
-                        continue;
-                    }
+            self.expect(TokenKind::LBracket, "Expected `[` after `vec!`")?;
+            let mut items = Vec::new();
+            while !self.at(TokenKind::RBracket) {
+                items.push(self.parse_expr()?);
+                if !self.at(TokenKind::Comma) {
                     break;
                 }
+                self.bump();
             }
-            self.expect(
-                TokenKind::RParen,
-                "Expected `)` after function literal parameters",
-            )?;
-            self.expect(
-                TokenKind::Arrow,
-                "Expected `->` after function literal parameters",
-            )?;
-            let return_type = self.expect_type_name(
-                "Expected function literal return type after `->`, for example `fn(x: Int) -> Int { ... }`",
-            )?;
-            let body = self.parse_block("Expected `{` before function literal body")?;
-            return Some(Expr::FnLit {
-                params,
-                return_type,
-                body,
-            });
+            self.expect(TokenKind::RBracket, "Expected `]` after vec literal")?;
+            return Some(Expr::VecLit(items));
         }
-        if self.at(TokenKind::IntLit) {
-            let tok = self.bump();
-            let value = match tok.lexeme.parse::<i64>() {
-                Ok(v) => v,
-                Err(_) => {
-                    self.diagnostics.error(
-                        format!("Integer literal `{}` is out of range for `Int`", tok.lexeme),
-                        tok.span,
-                    );
-                    return None;
-                }
-            };
-            return Some(Expr::IntLit(value));
+        if self.at(TokenKind::LBrace) {
+            let fields = self.parse_struct_literal_fields()?;
+            return Some(Expr::StructLit {
+                name,
+                type_args: Vec::new(),
+                fields,
+            });
         }
-        if self.at(TokenKind::FloatLit) {
-            let tok = self.bump();
-            return Some(Expr::FloatLit(tok.lexeme));
+        if self.at_generic_struct_literal() {
+            let type_args = self.parse_type_args(&name)?;
+            let fields = self.parse_struct_literal_fields()?;
+            return Some(Expr::StructLit {
+                name,
+                type_args,
+                fields,
+            });
         }
-        if self.at(TokenKind::KwTrue) {
+        Some(Expr::Ident(name))
+    }
+
+    /// `[a, b]` or `[value; size]`; the current token is `[`.
+    #[inline(never)]
+    fn parse_array_literal(&mut self) -> Option<Expr> {
+        self.bump();
+        if self.at(TokenKind::RBracket) {
             self.bump();
-            return Some(Expr::BoolLit(true));
+            return Some(Expr::ArrayLit(Vec::new()));
         }
-        if self.at(TokenKind::KwFalse) {
+        let first = self.parse_expr()?;
+        if self.at(TokenKind::Semi) {
             self.bump();
-            return Some(Expr::BoolLit(false));
-        }
-        if self.at(TokenKind::StringLit) {
-            let tok = self.bump();
-            let s = tok
-                .lexeme
-                .strip_prefix('"')
-                .and_then(|v| v.strip_suffix('"'))
-                .unwrap_or(&tok.lexeme)
-                .to_string();
-            let s = self.decode_string_escapes(&s, tok.span);
-            return Some(Expr::StringLit(s));
-        }
-        if self.at(TokenKind::Ident) {
-            let name = self.bump().lexeme;
-            if self.at(TokenKind::LBrace) {
-                let fields = self.parse_struct_literal_fields()?;
-                return Some(Expr::StructLit { name, fields });
-            }
-            return Some(Expr::Ident(name));
+            let sz = self.expect(TokenKind::IntLit, "Expected integer size in array repeat")?;
+            let size = match int_literal_value(&sz.lexeme).and_then(|v| usize::try_from(v).ok()) {
+                Some(v) => v,
+                None => {
+                    self.error_here_expected("Expected valid array repeat size");
+                    return None;
+                }
+            };
+            self.expect(
+                TokenKind::RBracket,
+                "Expected `]` after array repeat literal",
+            )?;
+            return Some(Expr::ArrayRepeat {
+                value: Box::new(first),
+                size,
+            });
         }
-        if self.at(TokenKind::LBracket) {
+        let mut items = vec![first];
+        while self.at(TokenKind::Comma) {
             self.bump();
             if self.at(TokenKind::RBracket) {
-                self.bump();
-                return Some(Expr::ArrayLit(Vec::new()));
-            }
-            let first = self.parse_expr()?;
-            if self.at(TokenKind::Semi) {
-                self.bump();
-                let sz = self.expect(TokenKind::IntLit, "Expected integer size in array repeat")?;
-                let size = match sz.lexeme.parse::<usize>() {
-                    Ok(v) => v,
-                    Err(_) => {
-                        self.error_here_expected("Expected valid array repeat size");
-                        return None;
-                    }
-                };
-                self.expect(
-                    TokenKind::RBracket,
-                    "Expected `]` after array repeat literal",
-                )?;
-                return Some(Expr::ArrayRepeat {
-                    value: Box::new(first),
-                    size,
-                });
-            }
-            let mut items = vec![first];
-            while self.at(TokenKind::Comma) {
-                self.bump();
-                if self.at(TokenKind::RBracket) {
-                    break;
-                }
-                items.push(self.parse_expr()?);
+                break;
             }
-            self.expect(TokenKind::RBracket, "Expected `]` after array literal")?;
-            return Some(Expr::ArrayLit(items));
+            items.push(self.parse_expr()?);
         }
-        if self.at(TokenKind::LParen) {
-            self.bump();
-            let expr = self.parse_expr()?;
-            self.expect(TokenKind::RParen, "Expected `)` after grouped expression")?;
-            return Some(Expr::Group(Box::new(expr)));
+        self.expect(TokenKind::RBracket, "Expected `]` after array literal")?;
+        Some(Expr::ArrayLit(items))
+    }
+
+    /// `t.0`, and `t.0.1`, which lexes its indices as the float `0.1`.
+    ///
+    /// Kept out of line so the recursive postfix/primary frames stay small
+    /// enough for deeply nested expressions.
+    #[inline(never)]
+    fn parse_tuple_index(&mut self, mut expr: Expr) -> Expr {
+        for index in self.bump().lexeme.split('.') {
+            expr = Expr::Field {
+                base: Box::new(expr),
+                field: index.to_string(),
+            };
         }
+        expr
+    }
 
-        self.error_here_expected("Expected expression");
-        None
+    /// The rest of `(first, ...)` once the first `,` is seen. Out of line for
+    /// the same reason as [`Self::parse_tuple_index`].
+    #[inline(never)]
+    fn parse_tuple_literal_tail(&mut self, first: Expr) -> Option<Expr> {
+        let mut items = vec![first];
+        while self.at(TokenKind::Comma) {
+            self.bump();
+            if self.at(TokenKind::RParen) {
+                break;
+            }
+            items.push(self.parse_expr()?);
+        }
+        self.expect(TokenKind::RParen, "Expected `)` after tuple literal")?;
+        if items.len() < 2 {
+            self.error_here_expected("Tuple literals need at least two elements");
+            return None;
+        }
+        Some(Expr::TupleLit(items))
     }
 }
@@ -1,9 +1,11 @@
 use crate::ast::{
-    ExportDecl, ExportItem, FieldDecl, FnDecl, GlobalLetDecl, ImplDecl, ImportDecl, ImportItem,
-    MethodDecl, OperatorDecl, Param, Program, StructDecl, TypeName,
+    Attribute, ExportDecl, ExportItem, Expr, FieldDecl, FnDecl, GlobalLetDecl, ImplDecl,
+    ImportDecl, ImportItem, MethodDecl, OperatorDecl, Param, Program, StructDecl, TypeAliasDecl,
+    TypeName,
 };
-use crate::diagnostic::{DiagnosticBag, Span};
-use crate::lexer::lex;
+use crate::diagnostic::{DiagnosticBag, DiagnosticSink, Span};
+use crate::lexer::{int_literal_value, lex};
+use crate::lint::{Lint, LintDirective, LintLevel, LintTarget};
 use crate::token::{Token, TokenKind};
 use std::collections::{HashMap, HashSet};
 
@@ -41,6 +43,8 @@ pub struct Parser {
     idx: usize,
     diagnostics: DiagnosticBag,
     custom_operator_precedences: HashMap<String, i64>,
+    /// Doc comment text keyed by the index of the token that follows it.
+    doc_comments: HashMap<usize, String>,
 }
 
 impl Default for Parser {
@@ -50,6 +54,7 @@ impl Default for Parser {
             idx: 0,
             diagnostics: DiagnosticBag::new(),
             custom_operator_precedences: HashMap::new(),
+            doc_comments: HashMap::new(),
         }
     }
 }
@@ -59,11 +64,22 @@ impl Parser {
         Self::parse_source_with_operator_precedences(source, HashMap::new())
     }
 
+    /// Like [`Parser::parse_source`], but reports diagnostics to `sink` instead of
+    /// returning them.
+    pub fn parse_source_with_sink(source: &str, sink: &mut dyn DiagnosticSink) -> Program {
+        let (program, diagnostics) = Self::parse_source(source);
+        for d in diagnostics.into_vec() {
+            sink.report(d);
+        }
+        program
+    }
+
     pub fn parse_source_with_operator_precedences(
         source: &str,
         mut external_operator_precedences: HashMap<String, i64>,
     ) -> (Program, DiagnosticBag) {
         let (tokens, mut diagnostics) = lex(source);
+        let (tokens, doc_comments) = Self::split_doc_comments(tokens);
         let custom_operator_precedences = Self::collect_operator_precedences(&tokens);
         for (name, precedence) in custom_operator_precedences {
             external_operator_precedences.insert(name, precedence);
@@ -73,6 +89,7 @@ impl Parser {
             idx: 0,
             diagnostics: DiagnosticBag::new(),
             custom_operator_precedences: external_operator_precedences,
+            doc_comments,
         };
         let program = parser.parse_program();
         for d in parser.diagnostics.into_vec() {
@@ -81,11 +98,56 @@ impl Parser {
         (program, diagnostics)
     }
 
+    /// Parses `source` as a single expression with nothing after it.
+    pub fn parse_expr_source(source: &str) -> (Option<Expr>, DiagnosticBag) {
+        let (tokens, mut diagnostics) = lex(source);
+        let (tokens, doc_comments) = Self::split_doc_comments(tokens);
+        let mut parser = Parser {
+            tokens,
+            idx: 0,
+            diagnostics: DiagnosticBag::new(),
+            custom_operator_precedences: HashMap::new(),
+            doc_comments,
+        };
+        let expr = parser.parse_expr();
+        if expr.is_some() && !parser.at(TokenKind::Eof) {
+            parser.error_here_expected("Expected end of expression");
+        }
+        for d in parser.diagnostics.into_vec() {
+            diagnostics.push(d);
+        }
+        let expr = if diagnostics.is_empty() { expr } else { None };
+        (expr, diagnostics)
+    }
+
     pub fn scan_source_headers(source: &str) -> SourceHeaderInfo {
         let (tokens, _diagnostics) = lex(source);
+        let (tokens, _doc_comments) = Self::split_doc_comments(tokens);
         Self::scan_header_tokens(&tokens)
     }
 
+    /// Pulls `///` tokens out of the stream, joining consecutive lines and
+    /// keying each comment by the index of the token it documents.
+    fn split_doc_comments(tokens: Vec<Token>) -> (Vec<Token>, HashMap<usize, String>) {
+        let mut out = Vec::with_capacity(tokens.len());
+        let mut docs = HashMap::new();
+        let mut pending: Vec<String> = Vec::new();
+        for tok in tokens {
+            if tok.kind == TokenKind::DocComment {
+                let text = tok.lexeme.trim_end_matches('\r');
+                let text = text.strip_prefix("///").unwrap_or(text);
+                pending.push(text.strip_prefix(' ').unwrap_or(text).to_string());
+                continue;
+            }
+            if !pending.is_empty() {
+                docs.insert(out.len(), pending.join("\n"));
+                pending.clear();
+            }
+            out.push(tok);
+        }
+        (out, docs)
+    }
+
     fn collect_operator_precedences(tokens: &[Token]) -> HashMap<String, i64> {
         Self::scan_header_tokens(tokens).local_operator_precedences
     }
@@ -122,7 +184,7 @@ impl Parser {
                             TokenKind::KwPrecedence => {
                                 if let Some(value_tok) = tokens.get(scan + 1)
                                     && value_tok.kind == TokenKind::IntLit
-                                    && let Ok(precedence) = value_tok.lexeme.parse::<i64>()
+                                    && let Some(precedence) = int_literal_value(&value_tok.lexeme)
                                 {
                                     out.local_operator_precedences
                                         .insert(name_tok.lexeme.clone(), precedence);
@@ -373,12 +435,44 @@ impl Parser {
         let mut imports = Vec::new();
         let mut exports = Vec::new();
         let mut globals = Vec::new();
+        let mut type_aliases = Vec::new();
+        let mut alias_spans = Vec::new();
         let mut structs = Vec::new();
         let mut impls = Vec::new();
         let mut operators = Vec::new();
         let mut functions = Vec::new();
+        let mut lints = Vec::new();
 
         while !self.at(TokenKind::Eof) {
+            if self.at(TokenKind::Hash) {
+                if let Some(directive) = self.parse_lint_directive() {
+                    lints.push(directive);
+                }
+                continue;
+            }
+            if self.at(TokenKind::At) {
+                let attributes = self.parse_attributes();
+                if self.at(TokenKind::KwFn) {
+                    if let Some(mut f) = self.parse_function() {
+                        f.attributes = attributes;
+                        functions.push(f);
+                    }
+                } else if self.at(TokenKind::KwExtern) {
+                    if let Some(mut f) = self.parse_extern_function() {
+                        f.attributes = attributes;
+                        functions.push(f);
+                    }
+                } else if self.at(TokenKind::KwStruct) {
+                    if let Some(mut s) = self.parse_struct_decl() {
+                        s.attributes = attributes;
+                        structs.push(s);
+                    }
+                } else {
+                    self.error_here_expected("Expected `fn` or `struct` after attributes");
+                    self.synchronize_toplevel();
+                }
+                continue;
+            }
             if self.at(TokenKind::KwImport) {
                 if let Some(i) = self.parse_import() {
                     imports.push(i);
@@ -430,6 +524,13 @@ impl Parser {
                 }
                 continue;
             }
+            if self.at(TokenKind::KwType) {
+                if let Some((alias, span)) = self.parse_type_alias_decl() {
+                    type_aliases.push(alias);
+                    alias_spans.push(span);
+                }
+                continue;
+            }
             if self.at(TokenKind::KwImpl) {
                 if let Some(i) = self.parse_impl_decl() {
                     impls.push(i);
@@ -438,23 +539,121 @@ impl Parser {
             }
 
             self.error_here_expected(
-                "Expected top-level declaration (`import`, `from`, `export`, `let`, `struct`, `impl`, `opr`, `extern fn`, or `fn`)",
+                "Expected top-level declaration (`import`, `from`, `export`, `let`, `type`, `struct`, `impl`, `opr`, `extern fn`, `fn`, an `@attribute`, or a `#allow(...)` lint directive)",
             );
             self.synchronize_toplevel();
         }
 
-        Program {
+        let mut program = Program {
+            lints,
             imports,
             exports,
             globals,
+            type_aliases,
             structs,
             impls,
             operators,
             functions,
+        };
+        self.expand_type_aliases(&mut program, &alias_spans);
+        program
+    }
+
+    /// One or more `@name` / `@name("arg", ...)` attributes before a declaration.
+    /// A doc comment above the attributes moves to the declaration they annotate.
+    fn parse_attributes(&mut self) -> Vec<Attribute> {
+        let doc = self.take_doc_comment();
+        let mut attributes = Vec::new();
+        while self.at(TokenKind::At) {
+            self.bump();
+            let Some(name) = self.expect_ident("Expected attribute name after `@`") else {
+                break;
+            };
+            let mut args = Vec::new();
+            if self.at(TokenKind::LParen) {
+                self.bump();
+                while !self.at(TokenKind::RParen) {
+                    let Some(tok) =
+                        self.expect(TokenKind::StringLit, "Expected string attribute argument")
+                    else {
+                        break;
+                    };
+                    let raw = tok
+                        .lexeme
+                        .strip_prefix('"')
+                        .and_then(|v| v.strip_suffix('"'))
+                        .unwrap_or(&tok.lexeme)
+                        .to_string();
+                    args.push(self.decode_string_escapes(&raw, tok.span));
+                    if !self.at(TokenKind::Comma) {
+                        break;
+                    }
+                    self.bump();
+                }
+                self.expect(TokenKind::RParen, "Expected `)` after attribute arguments");
+            }
+            attributes.push(Attribute {
+                name: name.lexeme,
+                args,
+            });
+        }
+        if let Some(doc) = doc {
+            self.doc_comments.entry(self.idx).or_insert(doc);
+        }
+        attributes
+    }
+
+    /// `#allow(name, ...)`, `#warn(...)`, or `#deny(...)`, with an optional trailing `;`.
+    /// Names are lints such as `unreachable_code`, or `warnings` for all of them.
+    fn parse_lint_directive(&mut self) -> Option<LintDirective> {
+        self.expect(TokenKind::Hash, "Expected `#`")?;
+        let level_tok = self.expect_ident("Expected `allow`, `warn`, or `deny` after `#`")?;
+        let Some(level) = LintLevel::from_name(&level_tok.lexeme) else {
+            self.diagnostics.error_with_code(
+                "E-PARSE-010",
+                format!(
+                    "Unknown lint level `{}`; expected `allow`, `warn`, or `deny`",
+                    level_tok.lexeme
+                ),
+                level_tok.span,
+            );
+            self.synchronize_toplevel();
+            return None;
+        };
+        self.expect(TokenKind::LParen, "Expected `(` after lint level")?;
+        let mut targets = Vec::new();
+        loop {
+            let name = self.expect_ident("Expected lint name")?;
+            match LintTarget::from_name(&name.lexeme) {
+                Some(target) => targets.push(target),
+                None => {
+                    let known = Lint::ALL.iter().map(|lint| lint.name()).collect::<Vec<_>>();
+                    self.diagnostics.error_with_code(
+                        "E-PARSE-010",
+                        format!(
+                            "Unknown lint `{}`; known lints are `warnings`, `{}`",
+                            name.lexeme,
+                            known.join("`, `")
+                        ),
+                        name.span,
+                    );
+                }
+            }
+            if self.at(TokenKind::Comma) {
+                self.bump();
+                continue;
+            }
+            break;
+        }
+        self.expect(TokenKind::RParen, "Expected `)` after lint names")?;
+        if self.at(TokenKind::Semi) {
+            self.bump();
         }
+        Some(LintDirective { level, targets })
     }
 
     fn parse_global_let_decl(&mut self) -> Option<GlobalLetDecl> {
+        let doc = self.take_doc_comment();
         self.expect(TokenKind::KwLet, "Expected `let`")?;
         let name = self.expect_ident("Expected variable name after `let`")?;
         let ty = if self.at(TokenKind::Colon) {
@@ -463,6 +662,10 @@ impl Parser {
         } else {
             None
         };
+        if self.reject_missing_initializer(&name.lexeme, ty.as_ref()) {
+            self.bump();
+            return None;
+        }
         self.expect(TokenKind::Assign, "Expected `=` in global let declaration")?;
         let value = self.parse_expr()?;
         self.expect(TokenKind::Semi, "Expected `;` after global let declaration")?;
@@ -470,6 +673,7 @@ impl Parser {
             name: name.lexeme,
             ty,
             value,
+            doc,
         })
     }
 
@@ -536,7 +740,8 @@ impl Parser {
                 None
             };
             if !seen_names.insert(name.clone()) {
-                self.diagnostics.error(
+                self.diagnostics.error_with_code(
+                    "E-PARSE-006",
                     format!("Duplicate imported symbol `{name}` in from-import clause"),
                     self.current().span,
                 );
@@ -544,7 +749,8 @@ impl Parser {
             if let Some(a) = &alias
                 && !seen_aliases.insert(a.clone())
             {
-                self.diagnostics.error(
+                self.diagnostics.error_with_code(
+                    "E-PARSE-006",
                     format!("Duplicate import alias `{a}` in from-import clause"),
                     self.current().span,
                 );
@@ -576,12 +782,19 @@ impl Parser {
 
     fn parse_dotted_path(&mut self, first_err: &str) -> Option<Vec<String>> {
         let mut path = vec![self.expect_ident(first_err)?.lexeme];
-        while self.at(TokenKind::Dot) {
+        loop {
+            if self.at(TokenKind::DotDot) {
+                // `a..b` lexes as a range token; report it as an empty segment.
+                self.error_here_expected("Expected identifier after `.` in module path");
+                return None;
+            }
+            if !self.at(TokenKind::Dot) {
+                return Some(path);
+            }
             self.bump();
             let next = self.expect_ident("Expected identifier after `.` in module path")?;
             path.push(next.lexeme);
         }
-        Some(path)
     }
 
     fn parse_export_decl(&mut self) -> Option<ExportDecl> {
@@ -607,6 +820,26 @@ impl Parser {
         let mut items = Vec::new();
         loop {
             let name = self.expect_ident("Expected export symbol name")?.lexeme;
+            let opaque = if self.at(TokenKind::LParen) {
+                self.bump();
+                let modifier =
+                    self.expect_ident("Expected `opaque` inside export item parentheses")?;
+                if modifier.lexeme != "opaque" {
+                    self.diagnostics.error_with_code(
+                        "E-PARSE-006",
+                        format!(
+                            "Unknown export modifier `{}`; only `opaque` is supported",
+                            modifier.lexeme
+                        ),
+                        modifier.span,
+                    );
+                    return None;
+                }
+                self.expect(TokenKind::RParen, "Expected `)` after export modifier")?;
+                true
+            } else {
+                false
+            };
             let alias = if self.at(TokenKind::KwAs) {
                 self.bump();
                 Some(
@@ -618,7 +851,11 @@ impl Parser {
             } else {
                 None
             };
-            items.push(ExportItem { name, alias });
+            items.push(ExportItem {
+                name,
+                alias,
+                opaque,
+            });
             if self.at(TokenKind::Comma) {
                 self.bump();
                 if self.at(TokenKind::RBrace) {
@@ -647,6 +884,7 @@ impl Parser {
     }
 
     fn parse_function(&mut self) -> Option<FnDecl> {
+        let doc = self.take_doc_comment();
         self.expect(TokenKind::KwFn, "Expected `fn`")?;
         let name = self.expect_ident("Expected function name after `fn`")?;
         self.expect(TokenKind::LParen, "Expected `(` after function name")?;
@@ -659,6 +897,7 @@ impl Parser {
                 params.push(Param {
                     name: param_name.lexeme,
                     ty: param_ty,
+                    span: param_name.span,
                 });
 
                 if self.at(TokenKind::Comma) {
@@ -693,13 +932,17 @@ impl Parser {
             is_extern: false,
             extern_library: None,
             name: name.lexeme,
+            span: name.span,
             params,
             return_type,
             body,
+            doc,
+            attributes: Vec::new(),
         })
     }
 
     fn parse_extern_function(&mut self) -> Option<FnDecl> {
+        let doc = self.take_doc_comment();
         self.expect(TokenKind::KwExtern, "Expected `extern`")?;
         let extern_library = if self.at(TokenKind::LParen) {
             self.bump();
@@ -731,6 +974,7 @@ impl Parser {
                 params.push(Param {
                     name: param_name.lexeme,
                     ty: param_ty,
+                    span: param_name.span,
                 });
 
                 if self.at(TokenKind::Comma) {
@@ -759,9 +1003,12 @@ impl Parser {
             is_extern: true,
             extern_library,
             name: name.lexeme,
+            span: name.span,
             params,
             return_type,
             body: Vec::new(),
+            doc,
+            attributes: Vec::new(),
         })
     }
 
@@ -781,6 +1028,7 @@ impl Parser {
                 params.push(Param {
                     name: param_name.lexeme,
                     ty: param_ty,
+                    span: param_name.span,
                 });
                 if self.at(TokenKind::Comma) {
                     self.bump();
@@ -804,9 +1052,8 @@ impl Parser {
                 TokenKind::IntLit,
                 "Expected integer precedence after `precedence`",
             )?
-            .lexeme
-            .parse::<i64>()
-            .ok()?;
+            .lexeme;
+        let precedence = int_literal_value(&precedence)?;
         self.expect(TokenKind::LBrace, "Expected `{` before operator body")?;
         let mut body = Vec::new();
         while !self.at(TokenKind::RBrace) && !self.at(TokenKind::Eof) {
@@ -826,9 +1073,84 @@ impl Parser {
         })
     }
 
+    fn parse_type_alias_decl(&mut self) -> Option<(TypeAliasDecl, Span)> {
+        let doc = self.take_doc_comment();
+        self.expect(TokenKind::KwType, "Expected `type`")?;
+        let name = self.expect_ident("Expected alias name after `type`")?;
+        self.expect(TokenKind::Assign, "Expected `=` after type alias name")?;
+        let target = self.expect_type_name("Expected type after `=` in type alias")?;
+        self.expect(TokenKind::Semi, "Expected `;` after type alias")?;
+        Some((
+            TypeAliasDecl {
+                name: name.lexeme,
+                target,
+                doc,
+            },
+            name.span,
+        ))
+    }
+
+    /// Replaces every use of a type alias declared in this module with its fully
+    /// expanded target. Aliases that reach themselves are reported and left as is.
+    fn expand_type_aliases(&mut self, program: &mut Program, spans: &[Span]) {
+        if program.type_aliases.is_empty() {
+            return;
+        }
+        let mut targets = HashMap::new();
+        for alias in &program.type_aliases {
+            targets
+                .entry(alias.name.clone())
+                .or_insert_with(|| alias.target.clone());
+        }
+        // Each round expands one more level; a chain of N aliases settles in N rounds.
+        for _ in 0..targets.len() {
+            let snapshot = targets.clone();
+            let mut changed = false;
+            for target in targets.values_mut() {
+                changed |= target.replace_named(&mut |name| snapshot.get(name).cloned());
+            }
+            if !changed {
+                break;
+            }
+        }
+        let mut recursive = HashSet::new();
+        for (alias, span) in program.type_aliases.iter().zip(spans) {
+            let mut probe = targets[&alias.name].clone();
+            if probe.replace_named(&mut |name| targets.get(name).cloned()) {
+                self.diagnostics.error_with_code(
+                    "E-PARSE-009",
+                    format!("Type alias `{}` refers to itself", alias.name),
+                    *span,
+                );
+                recursive.insert(alias.name.clone());
+            }
+        }
+        targets.retain(|name, _| !recursive.contains(name));
+        program.for_each_type_name_mut(&mut |ty| {
+            ty.replace_named(&mut |name| targets.get(name).cloned());
+        });
+    }
+
     fn parse_struct_decl(&mut self) -> Option<StructDecl> {
+        let doc = self.take_doc_comment();
         self.expect(TokenKind::KwStruct, "Expected `struct`")?;
         let name = self.expect_ident("Expected struct name after `struct`")?;
+        let mut type_params = Vec::new();
+        if self.at(TokenKind::LBracket) {
+            self.bump();
+            loop {
+                let param = self.expect_ident("Expected type parameter name in struct")?;
+                type_params.push(param.lexeme);
+                if !self.at(TokenKind::Comma) {
+                    break;
+                }
+                self.bump();
+                if self.at(TokenKind::RBracket) {
+                    break;
+                }
+            }
+            self.expect(TokenKind::RBracket, "Expected `]` after type parameters")?;
+        }
         self.expect(TokenKind::LBrace, "Expected `{` after struct name")?;
         let mut fields = Vec::new();
         while !self.at(TokenKind::RBrace) && !self.at(TokenKind::Eof) {
@@ -852,7 +1174,10 @@ impl Parser {
         self.expect(TokenKind::RBrace, "Expected `}` after struct declaration")?;
         Some(StructDecl {
             name: name.lexeme,
+            type_params,
             fields,
+            doc,
+            attributes: Vec::new(),
         })
     }
 
@@ -872,6 +1197,7 @@ impl Parser {
     }
 
     fn parse_method_decl(&mut self, receiver_ty: &str) -> Option<MethodDecl> {
+        let doc = self.take_doc_comment();
         self.expect(TokenKind::KwFn, "Expected `fn` in impl block")?;
         let name = self.expect_ident("Expected method name after `fn`")?;
         self.expect(TokenKind::LParen, "Expected `(` after method name")?;
@@ -887,7 +1213,8 @@ impl Parser {
                         match &annotated {
                             TypeName::Named(name) if name == receiver_ty => annotated,
                             other => {
-                                self.diagnostics.error(
+                                self.diagnostics.error_with_code(
+                                    "E-PARSE-007",
                                     format!(
                                         "Method `self` type must be `{receiver_ty}`, got {}",
                                         other.as_str()
@@ -903,6 +1230,7 @@ impl Parser {
                     params.push(Param {
                         name: "self".to_string(),
                         ty,
+                        span: param_name.span,
                     });
                 } else {
                     self.expect(TokenKind::Colon, "Expected `:` after parameter name")?;
@@ -910,6 +1238,7 @@ impl Parser {
                     params.push(Param {
                         name: param_name.lexeme,
                         ty: param_ty,
+                        span: param_name.span,
                     });
                 }
 
@@ -945,9 +1274,15 @@ impl Parser {
             params,
             return_type,
             body,
+            doc,
         })
     }
 
+    /// Removes and returns the doc comment attached to the current token.
+    fn take_doc_comment(&mut self) -> Option<String> {
+        self.doc_comments.remove(&self.idx)
+    }
+
     fn expect_ident(&mut self, message: &str) -> Option<Token> {
         if self.at(TokenKind::Ident) {
             return Some(self.bump());
@@ -999,6 +1334,7 @@ impl Parser {
                 | TokenKind::KwBreak
                 | TokenKind::KwContinue
                 | TokenKind::KwReturn
+                | TokenKind::KwDefer
                 | TokenKind::Ident => return,
                 _ => {
                     self.bump();
@@ -1009,7 +1345,9 @@ impl Parser {
 
     fn synchronize_toplevel(&mut self) {
         while !self.at(TokenKind::Eof) {
-            if self.at(TokenKind::KwImport)
+            if self.at(TokenKind::Hash)
+                || self.at(TokenKind::At)
+                || self.at(TokenKind::KwImport)
                 || self.at(TokenKind::KwFrom)
                 || self.at(TokenKind::KwExport)
                 || self.at(TokenKind::KwLet)
@@ -1017,6 +1355,7 @@ impl Parser {
                 || self.at(TokenKind::KwFn)
                 || self.at(TokenKind::KwOpr)
                 || self.at(TokenKind::KwStruct)
+                || self.at(TokenKind::KwType)
                 || self.at(TokenKind::KwImpl)
             {
                 return;
@@ -1035,6 +1374,24 @@ impl Parser {
         format!("{:?}", token.kind)
     }
 
+    /// Skepa has no uninitialized locals or globals, so `let x: Int;` is rejected here
+    /// with a dedicated message instead of a bare "Expected `=`".
+    fn reject_missing_initializer(&mut self, name: &str, ty: Option<&TypeName>) -> bool {
+        if !self.at(TokenKind::Semi) {
+            return false;
+        }
+        let annotation = ty
+            .map(|ty| format!(": {}", ty.as_str()))
+            .unwrap_or_default();
+        self.diagnostics.error_with_code("E-PARSE-004", 
+            format!(
+                "Missing initializer for `{name}`; every `let` needs a value, for example `let {name}{annotation} = ...;`"
+            ),
+            self.current().span,
+        );
+        true
+    }
+
     fn error_here_expected(&mut self, message: &str) {
         let found = Self::token_label(self.current());
         self.diagnostics
@@ -1,10 +1,27 @@
-use crate::ast::{AssignTarget, Expr, MatchArm, MatchLiteral, MatchPattern, Stmt};
+use crate::ast::{AssignTarget, Expr, MatchArm, MatchLiteral, MatchPattern, Stmt, StmtKind};
+use crate::diagnostic::Span;
+use crate::lexer::int_literal_value;
 use crate::token::TokenKind;
 
 use super::Parser;
 
 impl Parser {
     pub(super) fn parse_stmt(&mut self) -> Option<Stmt> {
+        let start = self.current().span;
+        let kind = self.parse_stmt_kind()?;
+        Some(self.finish_stmt(kind, start))
+    }
+
+    /// Wraps a statement parsed from the token at `start` up to the last token consumed.
+    fn finish_stmt(&self, kind: StmtKind, start: Span) -> Stmt {
+        let end = self.tokens[self.idx - 1].span.end;
+        Stmt {
+            kind,
+            span: Span::new(start.start, end, start.line, start.col),
+        }
+    }
+
+    fn parse_stmt_kind(&mut self) -> Option<StmtKind> {
         if self.at(TokenKind::KwIf) {
             self.bump();
             self.expect(TokenKind::LParen, "Expected `(` after `if`")?;
@@ -22,7 +39,7 @@ impl Parser {
             } else {
                 Vec::new()
             };
-            return Some(Stmt::If {
+            return Some(StmtKind::If {
                 cond,
                 then_body,
                 else_body,
@@ -35,7 +52,7 @@ impl Parser {
             let cond = self.parse_expr()?;
             self.expect(TokenKind::RParen, "Expected `)` after while condition")?;
             let body = self.parse_block("Expected `{` before while body")?;
-            return Some(Stmt::While { cond, body });
+            return Some(StmtKind::While { cond, body });
         }
         if self.at(TokenKind::KwMatch) {
             self.bump();
@@ -50,17 +67,35 @@ impl Parser {
             }
             while !self.at(TokenKind::RBrace) && !self.at(TokenKind::Eof) {
                 let pattern = self.parse_match_pattern()?;
+                let guard = self.parse_match_guard()?;
                 self.expect(TokenKind::FatArrow, "Expected `=>` after match pattern")?;
                 let body = self.parse_block("Expected `{` before match arm body")?;
-                arms.push(MatchArm { pattern, body });
+                arms.push(MatchArm {
+                    pattern,
+                    guard,
+                    body,
+                });
             }
             self.expect(TokenKind::RBrace, "Expected `}` after match statement")?;
-            return Some(Stmt::Match { expr, arms });
+            return Some(StmtKind::Match { expr, arms });
         }
         if self.at(TokenKind::KwFor) {
             self.bump();
             self.expect(TokenKind::LParen, "Expected `(` after `for`")?;
 
+            if self.at_for_in_binding() {
+                let name = self.expect_ident("Expected loop variable name in `for`")?;
+                self.bump();
+                let iterable = self.parse_expr()?;
+                self.expect(TokenKind::RParen, "Expected `)` after for-in iterable")?;
+                let body = self.parse_block("Expected `{` before for body")?;
+                return Some(StmtKind::ForIn {
+                    name: name.lexeme,
+                    iterable,
+                    body,
+                });
+            }
+
             let init = if self.at(TokenKind::Semi) {
                 self.bump();
                 None
@@ -88,7 +123,7 @@ impl Parser {
 
             self.expect(TokenKind::RParen, "Expected `)` after for clauses")?;
             let body = self.parse_block("Expected `{` before for body")?;
-            return Some(Stmt::For {
+            return Some(StmtKind::For {
                 init,
                 cond,
                 step,
@@ -98,12 +133,18 @@ impl Parser {
         if self.at(TokenKind::KwBreak) {
             self.bump();
             self.expect(TokenKind::Semi, "Expected `;` after `break`")?;
-            return Some(Stmt::Break);
+            return Some(StmtKind::Break);
         }
         if self.at(TokenKind::KwContinue) {
             self.bump();
             self.expect(TokenKind::Semi, "Expected `;` after `continue`")?;
-            return Some(Stmt::Continue);
+            return Some(StmtKind::Continue);
+        }
+        if self.at(TokenKind::KwDefer) {
+            self.bump();
+            let expr = self.parse_expr()?;
+            self.expect(TokenKind::Semi, "Expected `;` after defer expression")?;
+            return Some(StmtKind::Defer(expr));
         }
         if self.at(TokenKind::KwExport) {
             self.bump();
@@ -122,16 +163,22 @@ impl Parser {
 
         if self.at(TokenKind::KwLet) {
             self.bump();
+            if self.at_let_pattern() {
+                return self.parse_let_pattern();
+            }
             let name = self.expect_ident("Expected variable name after `let`")?;
             let mut ty = None;
             if self.at(TokenKind::Colon) {
                 self.bump();
                 ty = Some(self.expect_type_name("Expected type after `:`")?);
             }
+            if self.reject_missing_initializer(&name.lexeme, ty.as_ref()) {
+                return None;
+            }
             self.expect(TokenKind::Assign, "Expected `=` in let declaration")?;
             let value = self.parse_expr()?;
             self.expect(TokenKind::Semi, "Expected `;` after let declaration")?;
-            return Some(Stmt::Let {
+            return Some(StmtKind::Let {
                 name: name.lexeme,
                 ty,
                 value,
@@ -143,24 +190,24 @@ impl Parser {
             self.expect(TokenKind::Assign, "Expected `=` after assignment target")?;
             let value = self.parse_expr()?;
             self.expect(TokenKind::Semi, "Expected `;` after assignment")?;
-            return Some(Stmt::Assign { target, value });
+            return Some(StmtKind::Assign { target, value });
         }
 
         if self.at(TokenKind::KwReturn) {
             self.bump();
             if self.at(TokenKind::Semi) {
                 self.bump();
-                return Some(Stmt::Return(None));
+                return Some(StmtKind::Return(None));
             }
 
             let expr = self.parse_expr()?;
             self.expect(TokenKind::Semi, "Expected `;` after return statement")?;
-            return Some(Stmt::Return(Some(expr)));
+            return Some(StmtKind::Return(Some(expr)));
         }
 
         let expr = self.parse_expr()?;
         self.expect(TokenKind::Semi, "Expected `;` after expression statement")?;
-        Some(Stmt::Expr(expr))
+        Some(StmtKind::Expr(expr))
     }
 
     pub(super) fn parse_block(&mut self, open_err: &str) -> Option<Vec<Stmt>> {
@@ -177,6 +224,12 @@ impl Parser {
     }
 
     fn parse_for_clause_stmt(&mut self) -> Option<Stmt> {
+        let start = self.current().span;
+        let kind = self.parse_for_clause_kind()?;
+        Some(self.finish_stmt(kind, start))
+    }
+
+    fn parse_for_clause_kind(&mut self) -> Option<StmtKind> {
         if self.at(TokenKind::KwLet) {
             self.bump();
             let name = self.expect_ident("Expected variable name after `let` in for clause")?;
@@ -187,7 +240,7 @@ impl Parser {
             }
             self.expect(TokenKind::Assign, "Expected `=` in for let clause")?;
             let value = self.parse_expr()?;
-            return Some(Stmt::Let {
+            return Some(StmtKind::Let {
                 name: name.lexeme,
                 ty,
                 value,
@@ -198,11 +251,46 @@ impl Parser {
             let target = self.parse_assignment_target()?;
             self.expect(TokenKind::Assign, "Expected `=` after assignment target")?;
             let value = self.parse_expr()?;
-            return Some(Stmt::Assign { target, value });
+            return Some(StmtKind::Assign { target, value });
         }
 
         let expr = self.parse_expr()?;
-        Some(Stmt::Expr(expr))
+        Some(StmtKind::Expr(expr))
+    }
+
+    fn at_let_pattern(&self) -> bool {
+        self.at(TokenKind::LBracket)
+            || self.at(TokenKind::LParen)
+            || (self.at(TokenKind::Ident)
+                && self
+                    .tokens
+                    .get(self.idx + 1)
+                    .is_some_and(|tok| tok.kind == TokenKind::LBrace))
+    }
+
+    fn parse_let_pattern(&mut self) -> Option<StmtKind> {
+        let pattern = if self.at(TokenKind::LBracket) {
+            self.parse_array_pattern()?
+        } else if self.at(TokenKind::LParen) {
+            self.parse_tuple_pattern()?
+        } else {
+            let name = self.bump().lexeme;
+            self.parse_struct_pattern(name)?
+        };
+        self.expect(TokenKind::Assign, "Expected `=` in let declaration")?;
+        let value = self.parse_expr()?;
+        self.expect(TokenKind::Semi, "Expected `;` after let declaration")?;
+        Some(StmtKind::LetPattern { pattern, value })
+    }
+
+    /// `in` is not a keyword, so `for (x in xs)` is recognized by an identifier
+    /// followed by the identifier `in`.
+    fn at_for_in_binding(&self) -> bool {
+        self.at(TokenKind::Ident)
+            && self
+                .tokens
+                .get(self.idx + 1)
+                .is_some_and(|tok| tok.kind == TokenKind::Ident && tok.lexeme == "in")
     }
 
     fn can_start_assignment_target(&self) -> bool {
@@ -282,6 +370,15 @@ impl Parser {
         }
     }
 
+    /// Parses an optional `if <expr>` guard between a match pattern and `=>`.
+    pub(super) fn parse_match_guard(&mut self) -> Option<Option<Expr>> {
+        if !self.at(TokenKind::KwIf) {
+            return Some(None);
+        }
+        self.bump();
+        Some(Some(self.parse_expr()?))
+    }
+
     pub(super) fn parse_match_pattern(&mut self) -> Option<MatchPattern> {
         let mut parts = vec![self.parse_match_simple_pattern()?];
         while self.at(TokenKind::Pipe) {
@@ -331,15 +428,24 @@ impl Parser {
                 }
                 return Some(MatchPattern::Variant { name, binding });
             }
-            self.error_here_expected("Expected match pattern (`_`, literal, or variant)");
-            return None;
+            if self.at(TokenKind::LBrace) {
+                return self.parse_struct_pattern(name);
+            }
+            return Some(MatchPattern::Binding(name));
+        }
+        if self.at(TokenKind::LBracket) {
+            return self.parse_array_pattern();
+        }
+        if self.at(TokenKind::LParen) {
+            return self.parse_tuple_pattern();
         }
         if self.at(TokenKind::IntLit) {
             let tok = self.bump();
-            let value = match tok.lexeme.parse::<i64>() {
-                Ok(v) => v,
-                Err(_) => {
-                    self.diagnostics.error(
+            let value = match int_literal_value(&tok.lexeme) {
+                Some(v) => v,
+                None => {
+                    self.diagnostics.error_with_code(
+                        "E-PARSE-003",
                         format!("Integer literal `{}` is out of range for `Int`", tok.lexeme),
                         tok.span,
                     );
@@ -350,7 +456,9 @@ impl Parser {
         }
         if self.at(TokenKind::FloatLit) {
             let tok = self.bump();
-            return Some(MatchPattern::Literal(MatchLiteral::Float(tok.lexeme)));
+            return Some(MatchPattern::Literal(MatchLiteral::Float(
+                tok.lexeme.replace('_', ""),
+            )));
         }
         if self.at(TokenKind::KwTrue) {
             self.bump();
@@ -360,6 +468,17 @@ impl Parser {
             self.bump();
             return Some(MatchPattern::Literal(MatchLiteral::Bool(false)));
         }
+        if self.at(TokenKind::CharLit) {
+            let tok = self.bump();
+            let raw = tok
+                .lexeme
+                .strip_prefix('\'')
+                .and_then(|v| v.strip_suffix('\''))
+                .unwrap_or(&tok.lexeme)
+                .to_string();
+            let c = self.decode_char_literal(&raw, tok.span);
+            return Some(MatchPattern::Literal(MatchLiteral::Char(c)));
+        }
         if self.at(TokenKind::StringLit) {
             let tok = self.bump();
             let raw = tok
@@ -372,7 +491,99 @@ impl Parser {
             return Some(MatchPattern::Literal(MatchLiteral::String(s)));
         }
 
-        self.error_here_expected("Expected match pattern (`_`, literal, or variant)");
+        self.error_here_expected(
+            "Expected match pattern (`_`, literal, variant, binding, struct, array, or tuple)",
+        );
         None
     }
+
+    /// `Name { field: pattern, field, .. }`, after the struct name.
+    fn parse_struct_pattern(&mut self, name: String) -> Option<MatchPattern> {
+        self.expect(TokenKind::LBrace, "Expected `{` in struct pattern")?;
+        let mut fields = Vec::new();
+        let mut rest = false;
+        while !self.at(TokenKind::RBrace) {
+            if self.at(TokenKind::DotDot) {
+                self.bump();
+                rest = true;
+                break;
+            }
+            let field = self
+                .expect_ident("Expected field name or `..` in struct pattern")?
+                .lexeme;
+            let pattern = if self.at(TokenKind::Colon) {
+                self.bump();
+                self.parse_match_pattern()?
+            } else {
+                MatchPattern::Binding(field.clone())
+            };
+            fields.push((field, pattern));
+            if !self.at(TokenKind::Comma) {
+                break;
+            }
+            self.bump();
+        }
+        self.expect(TokenKind::RBrace, "Expected `}` after struct pattern")?;
+        Some(MatchPattern::Struct { name, fields, rest })
+    }
+
+    /// `[pattern, ..., rest..]`, where the optional `..` entry must come last.
+    fn parse_array_pattern(&mut self) -> Option<MatchPattern> {
+        self.expect(TokenKind::LBracket, "Expected `[` in array pattern")?;
+        let mut items = Vec::new();
+        let mut rest = false;
+        let mut rest_binding = None;
+        while !self.at(TokenKind::RBracket) {
+            if self.at(TokenKind::DotDot) {
+                self.bump();
+                rest = true;
+                break;
+            }
+            if self.at(TokenKind::Ident)
+                && self
+                    .tokens
+                    .get(self.idx + 1)
+                    .is_some_and(|tok| tok.kind == TokenKind::DotDot)
+            {
+                let name = self.bump().lexeme;
+                self.bump();
+                rest = true;
+                rest_binding = (name != "_").then_some(name);
+                break;
+            }
+            items.push(self.parse_match_pattern()?);
+            if !self.at(TokenKind::Comma) {
+                break;
+            }
+            self.bump();
+        }
+        self.expect(
+            TokenKind::RBracket,
+            "Expected `]` after array pattern; `..` must be the last entry",
+        )?;
+        Some(MatchPattern::Array {
+            items,
+            rest,
+            rest_binding,
+        })
+    }
+
+    /// `(pattern, pattern, ...)` with at least two entries.
+    fn parse_tuple_pattern(&mut self) -> Option<MatchPattern> {
+        self.expect(TokenKind::LParen, "Expected `(` in tuple pattern")?;
+        let mut items = Vec::new();
+        while !self.at(TokenKind::RParen) {
+            items.push(self.parse_match_pattern()?);
+            if !self.at(TokenKind::Comma) {
+                break;
+            }
+            self.bump();
+        }
+        self.expect(TokenKind::RParen, "Expected `)` after tuple pattern")?;
+        if items.len() < 2 {
+            self.error_here_expected("Tuple patterns need at least two elements");
+            return None;
+        }
+        Some(MatchPattern::Tuple(items))
+    }
 }
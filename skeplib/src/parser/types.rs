@@ -1,10 +1,78 @@
 use crate::ast::TypeName;
 use crate::diagnostic::Span;
+use crate::lexer::int_literal_value;
 use crate::token::TokenKind;
 
 use super::Parser;
 
 impl Parser {
+    /// Parses the `[A, B]` type argument list after generic struct `name`.
+    pub(super) fn parse_type_args(&mut self, name: &str) -> Option<Vec<TypeName>> {
+        self.expect(TokenKind::LBracket, &format!("Expected `[` after `{name}`"))?;
+        let mut args = Vec::new();
+        loop {
+            args.push(self.expect_type_name(&format!("Expected type argument for `{name}`"))?);
+            if !self.at(TokenKind::Comma) {
+                break;
+            }
+            self.bump();
+            if self.at(TokenKind::RBracket) {
+                break;
+            }
+        }
+        self.expect(
+            TokenKind::RBracket,
+            &format!("Expected `]` after type arguments for `{name}`"),
+        )?;
+        Some(args)
+    }
+
+    /// Whether the `[` at the cursor opens type arguments followed by a struct
+    /// literal body, as in `Box[Int] { value: 1 }`, rather than an index.
+    pub(super) fn at_generic_struct_literal(&self) -> bool {
+        if !self.at(TokenKind::LBracket) {
+            return false;
+        }
+        let starts_type = self.tokens.get(self.idx + 1).is_some_and(|tok| {
+            matches!(
+                tok.kind,
+                TokenKind::Ident
+                    | TokenKind::TyInt
+                    | TokenKind::TyFloat
+                    | TokenKind::TyBool
+                    | TokenKind::TyChar
+                    | TokenKind::TyString
+                    | TokenKind::TyBytes
+                    | TokenKind::TyVoid
+                    | TokenKind::LParen
+                    | TokenKind::LBracket
+            )
+        });
+        if !starts_type {
+            return false;
+        }
+        let mut depth = 0usize;
+        for (offset, tok) in self.tokens[self.idx..].iter().enumerate() {
+            match tok.kind {
+                TokenKind::LBracket | TokenKind::LParen => depth += 1,
+                TokenKind::RBracket | TokenKind::RParen => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return self
+                            .tokens
+                            .get(self.idx + offset + 1)
+                            .is_some_and(|next| next.kind == TokenKind::LBrace);
+                    }
+                }
+                TokenKind::LBrace | TokenKind::RBrace | TokenKind::Semi | TokenKind::Eof => {
+                    return false;
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
     pub(super) fn expect_type_name(&mut self, message: &str) -> Option<TypeName> {
         if self.at(TokenKind::Ident) && self.current().lexeme == "Fn" {
             self.bump();
@@ -96,14 +164,32 @@ impl Parser {
             });
         }
 
+        if self.at(TokenKind::LParen) {
+            self.bump();
+            let mut items = vec![self.expect_type_name("Expected element type in tuple type")?];
+            while self.at(TokenKind::Comma) {
+                self.bump();
+                if self.at(TokenKind::RParen) {
+                    break;
+                }
+                items.push(self.expect_type_name("Expected element type in tuple type")?);
+            }
+            self.expect(TokenKind::RParen, "Expected `)` after tuple type")?;
+            if items.len() < 2 {
+                self.error_here_expected("Tuple types need at least two element types");
+                return None;
+            }
+            return Some(TypeName::Tuple(items));
+        }
+
         if self.at(TokenKind::LBracket) {
             self.bump();
             let elem = self.expect_type_name("Expected element type in array type")?;
             self.expect(TokenKind::Semi, "Expected `;` in array type")?;
             let sz = self.expect(TokenKind::IntLit, "Expected integer size in array type")?;
-            let size = match sz.lexeme.parse::<usize>() {
-                Ok(v) => v,
-                Err(_) => {
+            let size = match int_literal_value(&sz.lexeme).and_then(|v| usize::try_from(v).ok()) {
+                Some(v) => v,
+                None => {
                     self.error_here_expected("Expected valid integer size in array type");
                     return None;
                 }
@@ -118,6 +204,7 @@ impl Parser {
             TokenKind::TyInt => TypeName::Int,
             TokenKind::TyFloat => TypeName::Float,
             TokenKind::TyBool => TypeName::Bool,
+            TokenKind::TyChar => TypeName::Char,
             TokenKind::TyString => TypeName::String,
             TokenKind::TyBytes => TypeName::Bytes,
             TokenKind::TyVoid => TypeName::Void,
@@ -150,6 +237,10 @@ impl Parser {
                     )?;
                     return Some(TypeName::Named(format!("{name}[{}]", elem.as_str())));
                 }
+                if self.at(TokenKind::LBracket) {
+                    let args = self.parse_type_args(&name)?;
+                    return Some(TypeName::Generic { name, args });
+                }
                 return Some(TypeName::Named(name));
             }
             _ => {
@@ -176,18 +267,62 @@ impl Parser {
                 Some('"') => out.push('"'),
                 Some('\\') => out.push('\\'),
                 Some(other) => {
-                    self.diagnostics.error(
+                    self.diagnostics.error_with_code(
+                        "E-PARSE-005",
                         format!("Invalid escape sequence `\\{other}` in string literal"),
                         span,
                     );
                     out.push(other);
                 }
                 None => {
-                    self.diagnostics
-                        .error("String ends with trailing escape `\\`", span);
+                    self.diagnostics.error_with_code(
+                        "E-PARSE-005",
+                        "String ends with trailing escape `\\`",
+                        span,
+                    );
                 }
             }
         }
         out
     }
+
+    pub(super) fn decode_char_literal(&mut self, raw: &str, span: Span) -> char {
+        let mut chars = raw.chars();
+        let value = match chars.next() {
+            Some('\\') => match chars.next() {
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some('r') => '\r',
+                Some('0') => '\0',
+                Some('\'') => '\'',
+                Some('\\') => '\\',
+                Some(other) => {
+                    self.diagnostics.error_with_code(
+                        "E-PARSE-005",
+                        format!("Invalid escape sequence `\\{other}` in character literal"),
+                        span,
+                    );
+                    other
+                }
+                None => '\\',
+            },
+            Some(ch) => ch,
+            None => {
+                self.diagnostics.error_with_code(
+                    "E-PARSE-005",
+                    "Character literal must contain exactly one character",
+                    span,
+                );
+                return '\0';
+            }
+        };
+        if chars.next().is_some() {
+            self.diagnostics.error_with_code(
+                "E-PARSE-005",
+                "Character literal must contain exactly one character",
+                span,
+            );
+        }
+        value
+    }
 }
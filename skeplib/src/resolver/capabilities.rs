@@ -0,0 +1,74 @@
+use crate::ast::ImportDecl;
+
+use super::{ModuleGraph, ResolveError, ResolveErrorKind};
+
+/// Builtin packages that reach outside the process and can be denied per build.
+pub const CAPABILITY_PACKAGES: &[&str] = &["fs", "os", "net", "ffi", "store", "task"];
+
+/// The broader capability a builtin package also needs. `store` keeps its data
+/// in files, so denying `fs` denies it too.
+fn implied_capability(package: &str) -> Option<&'static str> {
+    match package {
+        "store" => Some("fs"),
+        _ => None,
+    }
+}
+
+/// Rejects every use of a denied capability package across the project graph.
+///
+/// A package counts as used when any module imports it or a package that needs
+/// it (see `implied_capability`). `extern fn` declarations
+/// are treated as uses of `ffi`, since they load foreign code just like `ffi.open`.
+pub fn check_capabilities(graph: &ModuleGraph, denied: &[String]) -> Vec<ResolveError> {
+    let mut errors = Vec::new();
+    if denied.is_empty() {
+        return errors;
+    }
+    let mut ids = graph.modules.keys().cloned().collect::<Vec<_>>();
+    ids.sort();
+    for id in ids {
+        let unit = &graph.modules[&id];
+        for import in &unit.program.imports {
+            let (ImportDecl::ImportModule { path, .. } | ImportDecl::ImportFrom { path, .. }) =
+                import;
+            if path.len() != 1 {
+                continue;
+            }
+            let package = path[0].as_str();
+            let Some(capability) = std::iter::once(package)
+                .chain(implied_capability(package))
+                .find(|capability| denied.iter().any(|pkg| pkg == capability))
+            else {
+                continue;
+            };
+            let mut err = ResolveError::new(
+                ResolveErrorKind::CapabilityDenied,
+                format!(
+                    "Capability `{capability}` is denied, but module `{id}` imports `{package}`"
+                ),
+                Some(unit.path.clone()),
+            );
+            if let Some((line, col)) = super::support::find_import_line_col(&unit.source, package) {
+                err = err.with_line_col(line, col);
+            }
+            errors.push(err);
+        }
+        if denied.iter().any(|pkg| pkg == "ffi") {
+            for func in unit.program.functions.iter().filter(|f| f.is_extern) {
+                let mut err = ResolveError::new(
+                    ResolveErrorKind::CapabilityDenied,
+                    format!(
+                        "Capability `ffi` is denied, but module `{id}` declares extern function `{}`",
+                        func.name
+                    ),
+                    Some(unit.path.clone()),
+                );
+                if func.span.line > 0 {
+                    err = err.with_line_col(func.span.line, func.span.col);
+                }
+                errors.push(err);
+            }
+        }
+    }
+    errors
+}
@@ -1,7 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
-use crate::ast::{ImportDecl, Program};
+use crate::ast::{ImportDecl, Program, TypeName, deprecation};
 
 use super::support::suggest_name;
 use super::{
@@ -88,7 +88,7 @@ pub fn build_export_maps(
                         let Some(sym) = dep_map.get(&item.name).cloned() else {
                             let suggestion =
                                 suggest_name(&item.name, dep_map.keys().map(|k| k.as_str()));
-                            let msg = if let Some(s) = suggestion {
+                            let msg = if let Some(s) = &suggestion {
                                 format!(
                                     "Cannot re-export `{}` from `{}` in module `{}` ({}): symbol is not exported; did you mean `{}`?",
                                     item.name,
@@ -106,11 +106,14 @@ pub fn build_export_maps(
                                     unit.path.display()
                                 )
                             };
-                            errors.push(ResolveError::new(
-                                ResolveErrorKind::NotExported,
-                                msg,
-                                Some(unit.path.clone()),
-                            ));
+                            errors.push(ResolveError {
+                                suggestion: suggestion.map(Into::into),
+                                ..ResolveError::new(
+                                    ResolveErrorKind::NotExported,
+                                    msg,
+                                    Some(unit.path.clone()),
+                                )
+                            });
                             continue;
                         };
                         if map.insert(export_name.clone(), sym).is_some() {
@@ -202,6 +205,138 @@ pub(crate) fn resolve_import_module_targets(
     matches
 }
 
+/// Replaces the imported type aliases each module uses with their targets. The
+/// parser already expanded every module's own aliases; modules are visited
+/// dependencies first, so an alias built on an imported alias is complete by the
+/// time its importers read it.
+pub(super) fn expand_imported_type_aliases(
+    graph: &mut ModuleGraph,
+    export_maps: &HashMap<ModuleId, ExportMap>,
+) {
+    fn visit(
+        id: &str,
+        graph: &ModuleGraph,
+        done: &mut HashSet<ModuleId>,
+        order: &mut Vec<ModuleId>,
+    ) {
+        if !done.insert(id.to_string()) {
+            return;
+        }
+        let Some(unit) = graph.modules.get(id) else {
+            return;
+        };
+        for dep in &unit.imports {
+            visit(dep, graph, done, order);
+        }
+        order.push(id.to_string());
+    }
+
+    let mut ids = graph.modules.keys().cloned().collect::<Vec<_>>();
+    ids.sort();
+    let mut done = HashSet::new();
+    let mut order = Vec::new();
+    for id in &ids {
+        visit(id, graph, &mut done, &mut order);
+    }
+
+    for id in order {
+        let Some(unit) = graph.modules.get(&id) else {
+            continue;
+        };
+        let aliases = imported_type_aliases(&unit.program, graph, export_maps);
+        if aliases.is_empty() {
+            continue;
+        }
+        if let Some(unit) = graph.modules.get_mut(&id) {
+            unit.program.for_each_type_name_mut(&mut |ty| {
+                ty.replace_named(&mut |name| aliases.get(name).cloned());
+            });
+        }
+    }
+}
+
+/// Type aliases visible in `program` through its imports, keyed by the name the
+/// module writes: `Id` for `from m import Id`, `m.Id` or `alias.Id` for module imports.
+fn imported_type_aliases(
+    program: &Program,
+    graph: &ModuleGraph,
+    export_maps: &HashMap<ModuleId, ExportMap>,
+) -> HashMap<String, TypeName> {
+    let target_of = |sym: &SymbolRef| {
+        if sym.kind != SymbolKind::TypeAlias {
+            return None;
+        }
+        graph
+            .modules
+            .get(&sym.module_id)?
+            .program
+            .type_aliases
+            .iter()
+            .find(|alias| alias.name == sym.local_name)
+            .map(|alias| alias.target.clone())
+    };
+    let mut out = HashMap::new();
+    for import in &program.imports {
+        match import {
+            ImportDecl::ImportFrom {
+                path,
+                wildcard,
+                items,
+            } => {
+                let targets = resolve_import_module_targets(graph, path);
+                let [target] = targets.as_slice() else {
+                    continue;
+                };
+                let Some(exports) = export_maps.get(target) else {
+                    continue;
+                };
+                if *wildcard {
+                    for (name, sym) in exports {
+                        if let Some(ty) = target_of(sym) {
+                            out.insert(name.clone(), ty);
+                        }
+                    }
+                } else {
+                    for item in items {
+                        let local = item.alias.as_ref().unwrap_or(&item.name);
+                        if let Some(ty) = exports.get(&item.name).and_then(target_of) {
+                            out.insert(local.clone(), ty);
+                        }
+                    }
+                }
+            }
+            ImportDecl::ImportModule { path, alias } => {
+                let target_prefix = path.join(".");
+                for mid in resolve_import_module_targets(graph, path) {
+                    let Some(exports) = export_maps.get(&mid) else {
+                        continue;
+                    };
+                    let visible_mid = match alias {
+                        Some(alias) => {
+                            let relative = mid
+                                .strip_prefix(&target_prefix)
+                                .unwrap_or("")
+                                .trim_start_matches('.');
+                            if relative.is_empty() {
+                                alias.clone()
+                            } else {
+                                format!("{alias}.{relative}")
+                            }
+                        }
+                        None => mid.clone(),
+                    };
+                    for (name, sym) in exports {
+                        if let Some(ty) = target_of(sym) {
+                            out.insert(format!("{visible_mid}.{name}"), ty);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
 pub(super) fn validate_import_bindings(
     graph: &ModuleGraph,
     export_maps: &HashMap<ModuleId, ExportMap>,
@@ -297,7 +432,7 @@ pub(super) fn validate_import_bindings(
                                     .get(target)
                                     .map(|u| u.path.display().to_string())
                                     .unwrap_or_else(|| "<unknown>".to_string());
-                                let msg = if let Some(s) = suggestion {
+                                let msg = if let Some(s) = &suggestion {
                                     format!(
                                         "Cannot import `{}` from `{}` in module `{}` ({}) -> target `{}` ({}): symbol is not exported; did you mean `{}`?",
                                         item.name,
@@ -319,11 +454,14 @@ pub(super) fn validate_import_bindings(
                                         target_path
                                     )
                                 };
-                                errors.push(ResolveError::new(
-                                    ResolveErrorKind::NotExported,
-                                    msg,
-                                    Some(unit.path.clone()),
-                                ));
+                                errors.push(ResolveError {
+                                    suggestion: suggestion.map(Into::into),
+                                    ..ResolveError::new(
+                                        ResolveErrorKind::NotExported,
+                                        msg,
+                                        Some(unit.path.clone()),
+                                    )
+                                });
                                 continue;
                             }
                             let local = item.alias.clone().unwrap_or_else(|| item.name.clone());
@@ -357,6 +495,7 @@ pub fn collect_module_symbols(program: &Program, module_id: &str) -> ModuleSymbo
                 module_id: module_id.to_string(),
                 local_name: f.name.clone(),
                 kind: SymbolKind::Fn,
+                deprecated: deprecation(&f.attributes).map(|note| note.map(str::to_string)),
             },
         );
     }
@@ -367,6 +506,7 @@ pub fn collect_module_symbols(program: &Program, module_id: &str) -> ModuleSymbo
                 module_id: module_id.to_string(),
                 local_name: operator.name.clone(),
                 kind: SymbolKind::Fn,
+                deprecated: None,
             },
         );
     }
@@ -377,6 +517,18 @@ pub fn collect_module_symbols(program: &Program, module_id: &str) -> ModuleSymbo
                 module_id: module_id.to_string(),
                 local_name: s.name.clone(),
                 kind: SymbolKind::Struct,
+                deprecated: deprecation(&s.attributes).map(|note| note.map(str::to_string)),
+            },
+        );
+    }
+    for alias in &program.type_aliases {
+        locals.insert(
+            alias.name.clone(),
+            SymbolRef {
+                module_id: module_id.to_string(),
+                local_name: alias.name.clone(),
+                kind: SymbolKind::TypeAlias,
+                deprecated: None,
             },
         );
     }
@@ -387,6 +539,7 @@ pub fn collect_module_symbols(program: &Program, module_id: &str) -> ModuleSymbo
                 module_id: module_id.to_string(),
                 local_name: g.name.clone(),
                 kind: SymbolKind::GlobalLet,
+                deprecated: None,
             },
         );
     }
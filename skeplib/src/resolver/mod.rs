@@ -1,25 +1,29 @@
+mod capabilities;
 mod exports;
 mod fs_scan;
 mod support;
 
-use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::ast::Program;
+use crate::builtins::CustomBuiltins;
 use crate::diagnostic::DiagnosticBag;
 use crate::parser::Parser;
 
-use self::exports::validate_import_bindings;
+use self::exports::{expand_imported_type_aliases, validate_import_bindings};
 use self::support::with_importer_context;
 
+pub use self::capabilities::{CAPABILITY_PACKAGES, check_capabilities};
 pub(crate) use self::exports::resolve_import_module_targets;
 pub use self::exports::{build_export_maps, collect_module_symbols, validate_and_build_export_map};
 pub use self::fs_scan::{
     collect_import_module_paths, module_id_from_relative_path, module_path_from_import,
     resolve_import_target, scan_folder_modules,
 };
+pub(crate) use self::support::{levenshtein, suggest_name};
 
 pub type ModuleId = String;
 
@@ -37,11 +41,28 @@ pub struct ModuleGraph {
     pub modules: HashMap<ModuleId, ModuleUnit>,
 }
 
+impl ModuleGraph {
+    /// The module loaded from `entry`, comparing canonical paths so a relative
+    /// entry path matches the graph's absolute ones.
+    pub fn entry_module(&self, entry: &Path) -> Option<&ModuleUnit> {
+        let entry_path = entry.canonicalize().unwrap_or_else(|_| entry.to_path_buf());
+        self.modules.values().find(|m| {
+            m.path == entry
+                || m.path == entry_path
+                || m.path
+                    .canonicalize()
+                    .map(|p| p == entry_path)
+                    .unwrap_or(false)
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SymbolKind {
     Fn,
     Struct,
     GlobalLet,
+    TypeAlias,
     Namespace,
 }
 
@@ -50,6 +71,9 @@ pub struct SymbolRef {
     pub module_id: ModuleId,
     pub local_name: String,
     pub kind: SymbolKind,
+    /// `Some` when the declaration is marked `@deprecated`, holding its note if
+    /// it has one. Re-exports keep it, so importers see it from any module.
+    pub deprecated: Option<Option<String>>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -73,6 +97,7 @@ pub enum ResolveErrorKind {
     NotExported,
     ExportUnknown,
     Cycle,
+    CapabilityDenied,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -83,6 +108,9 @@ pub struct ResolveError {
     pub path: Option<PathBuf>,
     pub line: Option<usize>,
     pub col: Option<usize>,
+    /// The name a `did you mean` hint in `message` proposes. Boxed to keep
+    /// `Result<_, ResolveError>` small.
+    pub suggestion: Option<Box<str>>,
 }
 
 impl ResolveError {
@@ -94,6 +122,7 @@ impl ResolveError {
             path,
             line: None,
             col: None,
+            suggestion: None,
         }
     }
 
@@ -123,6 +152,7 @@ fn code_for_kind(kind: ResolveErrorKind) -> &'static str {
         ResolveErrorKind::ImportConflict => "E-IMPORT-CONFLICT",
         ResolveErrorKind::NotExported => "E-IMPORT-NOT-EXPORTED",
         ResolveErrorKind::ExportUnknown => "E-EXPORT-UNKNOWN",
+        ResolveErrorKind::CapabilityDenied => "E-CAP-DENIED",
     }
 }
 
@@ -139,6 +169,7 @@ pub(crate) fn parse_diagnostics_to_resolve_errors(
                 diag.message.clone(),
                 Some(path.to_path_buf()),
             )
+            .with_code(diag.code.unwrap_or("E-PARSE"))
             .with_line_col(diag.span.line, diag.span.col)
         })
         .collect()
@@ -244,6 +275,15 @@ fn build_operator_precedence_export_maps(
 }
 
 pub fn resolve_project(entry: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
+    resolve_project_with_builtins(entry, &CustomBuiltins::default())
+}
+
+/// Like [`resolve_project`], but `import pkg;` of a package in `builtins` is
+/// treated like a native builtin import instead of a module on disk.
+pub fn resolve_project_with_builtins(
+    entry: &Path,
+    builtins: &CustomBuiltins,
+) -> Result<ModuleGraph, Vec<ResolveError>> {
     if !entry.exists() {
         return Err(vec![ResolveError::new(
             ResolveErrorKind::MissingModule,
@@ -255,11 +295,55 @@ pub fn resolve_project(entry: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
         .parent()
         .map(Path::to_path_buf)
         .unwrap_or_else(|| PathBuf::from("."));
+    resolve_from_roots(root, vec![entry.to_path_buf()], builtins)
+}
+
+/// Resolves every `.sk` file under `dir` as a root of one graph, so modules that no
+/// entry imports are still parsed and checked. Module ids are relative to `dir`.
+pub fn resolve_directory(dir: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
+    resolve_directory_with_builtins(dir, &CustomBuiltins::default())
+}
+
+/// [`resolve_directory`] with embedder packages; see [`resolve_project_with_builtins`].
+pub fn resolve_directory_with_builtins(
+    dir: &Path,
+    builtins: &CustomBuiltins,
+) -> Result<ModuleGraph, Vec<ResolveError>> {
+    if !dir.is_dir() {
+        return Err(vec![ResolveError::new(
+            ResolveErrorKind::MissingModule,
+            format!("Directory not found: {}", dir.display()),
+            Some(dir.to_path_buf()),
+        )]);
+    }
+    let mut files = scan_folder_modules(dir, &[])
+        .map_err(|e| vec![e])?
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect::<Vec<_>>();
+    if files.is_empty() {
+        return Err(vec![ResolveError::new(
+            ResolveErrorKind::MissingModule,
+            format!("No `.sk` files found under {}", dir.display()),
+            Some(dir.to_path_buf()),
+        )]);
+    }
+    files.sort();
+    resolve_from_roots(dir.to_path_buf(), files, builtins)
+}
+
+fn resolve_from_roots(
+    root: PathBuf,
+    roots: Vec<PathBuf>,
+    builtins: &CustomBuiltins,
+) -> Result<ModuleGraph, Vec<ResolveError>> {
     let mut graph = ModuleGraph::default();
     let mut headers = HashMap::<ModuleId, crate::parser::SourceHeaderInfo>::new();
     let mut errors = Vec::new();
-    let mut queue = VecDeque::new();
-    queue.push_back(entry.to_path_buf());
+    // Modules whose imports or source failed; import-binding checks skip them and
+    // everything that depends on them instead of reporting follow-on errors.
+    let mut broken = HashSet::<ModuleId>::new();
+    let mut queue = VecDeque::from(roots);
 
     while let Some(path) = queue.pop_front() {
         let rel = match path.strip_prefix(&root) {
@@ -310,7 +394,14 @@ pub fn resolve_project(entry: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
                 && matches!(
                     import_path[0].as_str(),
                     "io" | "bytes"
+                        | "char"
                         | "map"
+                        | "math"
+                        | "serde"
+                        | "store"
+                        | "encoding"
+                        | "hash"
+                        | "csv"
                         | "option"
                         | "result"
                         | "str"
@@ -323,7 +414,9 @@ pub fn resolve_project(entry: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
                         | "fs"
                         | "task"
                         | "vec"
+                        | "iter"
                 )
+                || (import_path.len() == 1 && builtins.has_package(&import_path[0]))
             {
                 continue;
             }
@@ -336,7 +429,10 @@ pub fn resolve_project(entry: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
                     };
                     match module_id_from_relative_path(&target_rel) {
                         Ok(dep_id) => imports.push(dep_id),
-                        Err(e) => errors.push(e),
+                        Err(e) => {
+                            broken.insert(id.clone());
+                            errors.push(e);
+                        }
                     }
                     queue.push_back(target_file);
                 }
@@ -349,11 +445,15 @@ pub fn resolve_project(entry: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
                             }
                         }
                         Err(e) => {
+                            broken.insert(id.clone());
                             errors.push(with_importer_context(e, &id, &path, &import_text, &source))
                         }
                     }
                 }
-                Err(e) => errors.push(with_importer_context(e, &id, &path, &import_text, &source)),
+                Err(e) => {
+                    broken.insert(id.clone());
+                    errors.push(with_importer_context(e, &id, &path, &import_text, &source));
+                }
             }
         }
 
@@ -370,21 +470,22 @@ pub fn resolve_project(entry: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
         );
     }
 
-    if errors.is_empty() {
-        let exported_operator_precedences =
-            match build_operator_precedence_export_maps(&graph, &headers) {
-                Ok(precedences) => precedences,
-                Err(mut e) => {
-                    errors.append(&mut e);
-                    HashMap::new()
-                }
-            };
-
-        let module_ids = graph.modules.keys().cloned().collect::<Vec<_>>();
-        for id in module_ids {
-            if !errors.is_empty() {
-                break;
+    // Every phase below runs even when an earlier one failed, so one invocation
+    // reports load, parse, cycle and import-binding errors together.
+    let exported_operator_precedences =
+        match build_operator_precedence_export_maps(&graph, &headers) {
+            Ok(precedences) => Some(precedences),
+            Err(mut e) => {
+                errors.append(&mut e);
+                // Without operator precedences no module can be parsed reliably.
+                broken.extend(graph.modules.keys().cloned());
+                None
             }
+        };
+    if let Some(exported_operator_precedences) = exported_operator_precedences {
+        let mut module_ids = graph.modules.keys().cloned().collect::<Vec<_>>();
+        module_ids.sort();
+        for id in module_ids {
             let Some(unit) = graph.modules.get(&id).cloned() else {
                 continue;
             };
@@ -392,6 +493,7 @@ pub fn resolve_project(entry: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
                 continue;
             };
             let mut external_precedences = HashMap::new();
+            let errors_before = errors.len();
             for from_import in &header.from_imports {
                 let targets = resolve_import_module_targets(&graph, &from_import.path);
                 if targets.len() != 1 {
@@ -453,12 +555,14 @@ pub fn resolve_project(entry: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
                     }
                 }
             }
-            if !errors.is_empty() {
-                break;
+            if errors.len() > errors_before {
+                broken.insert(id.clone());
+                continue;
             }
             let (program, parse_diags) =
                 Parser::parse_source_with_operator_precedences(&unit.source, external_precedences);
             if !parse_diags.is_empty() {
+                broken.insert(id.clone());
                 errors.extend(parse_diagnostics_to_resolve_errors(
                     &unit.path,
                     &parse_diags,
@@ -471,22 +575,64 @@ pub fn resolve_project(entry: &Path) -> Result<ModuleGraph, Vec<ResolveError>> {
         }
     }
 
+    let cycle_errors = detect_cycles(&graph);
+    let has_import_cycle = !cycle_errors.is_empty();
+    errors.extend(cycle_errors);
+
+    let checkable = sound_subgraph(&graph, &broken);
+    let export_maps = build_export_maps(&checkable);
+    let binding_errors = match &export_maps {
+        Ok(export_maps) => validate_import_bindings(&checkable, export_maps),
+        Err(e) => e.clone(),
+    };
+    // An import cycle through re-exports is already reported above.
+    errors.extend(
+        binding_errors
+            .into_iter()
+            .filter(|e| !(has_import_cycle && e.kind == ResolveErrorKind::Cycle)),
+    );
     if errors.is_empty() {
-        errors.extend(detect_cycles(&graph));
-    }
-    if errors.is_empty() {
-        match build_export_maps(&graph) {
-            Ok(export_maps) => errors.extend(validate_import_bindings(&graph, &export_maps)),
-            Err(mut e) => errors.append(&mut e),
+        if let Ok(export_maps) = export_maps {
+            expand_imported_type_aliases(&mut graph, &export_maps);
         }
-    }
-    if errors.is_empty() {
         Ok(graph)
     } else {
         Err(errors)
     }
 }
 
+/// The modules of `graph` that are not `broken` and import nothing broken, directly or
+/// transitively; export and import-binding checks on the rest would only repeat errors
+/// already reported for the broken modules.
+fn sound_subgraph(graph: &ModuleGraph, broken: &HashSet<ModuleId>) -> ModuleGraph {
+    let mut unsound = broken.clone();
+    loop {
+        let newly_unsound = graph
+            .modules
+            .iter()
+            .filter(|(id, unit)| {
+                !unsound.contains(*id)
+                    && unit
+                        .imports
+                        .iter()
+                        .any(|dep| unsound.contains(dep) || !graph.modules.contains_key(dep))
+            })
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        if newly_unsound.is_empty() {
+            break;
+        }
+        unsound.extend(newly_unsound);
+    }
+    let modules = graph
+        .modules
+        .iter()
+        .filter(|(id, _)| !unsound.contains(*id))
+        .map(|(id, unit)| (id.clone(), unit.clone()))
+        .collect();
+    ModuleGraph { modules }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ImportTarget {
     File(PathBuf),
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use super::ResolveError;
 
-pub(super) fn levenshtein(a: &str, b: &str) -> usize {
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
     if a == b {
         return 0;
     }
@@ -26,7 +26,7 @@ pub(super) fn levenshtein(a: &str, b: &str) -> usize {
     prev[b_chars.len()]
 }
 
-pub(super) fn suggest_name<'a>(
+pub(crate) fn suggest_name<'a>(
     needle: &str,
     haystack: impl Iterator<Item = &'a str>,
 ) -> Option<String> {
@@ -63,7 +63,7 @@ pub(super) fn with_importer_context(
     err
 }
 
-fn find_import_line_col(source: &str, import_text: &str) -> Option<(usize, usize)> {
+pub(super) fn find_import_line_col(source: &str, import_text: &str) -> Option<(usize, usize)> {
     let pat_import = format!("import {import_text}");
     let pat_from = format!("from {import_text} import");
     for (idx, line) in source.lines().enumerate() {
@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+
+use crate::ast::{Attribute, ImportDecl, Program, deprecation};
+use crate::lint::Lint;
+use crate::types::TypeInfo;
+
+use super::Checker;
+
+/// Every attribute sema knows; structs only accept `@deprecated`.
+const FUNCTION_ATTRIBUTES: [&str; 3] = ["inline", "test", "deprecated"];
+const STRUCT_ATTRIBUTES: [&str; 1] = ["deprecated"];
+
+impl Checker {
+    /// Validates `@inline`, `@test` and `@deprecated` on functions and structs,
+    /// and records deprecated declarations so their uses can be reported.
+    pub(super) fn check_attributes(&mut self, program: &Program) {
+        for f in &program.functions {
+            self.push_fallback_span(self.declaration_span("fn", &f.name));
+            self.check_attribute_list(&f.attributes, &FUNCTION_ATTRIBUTES, "function");
+            for name in ["inline", "test"] {
+                if f.has_attribute(name) && f.is_extern {
+                    self.error(
+                        "E-SEMA-027",
+                        format!("`@{name}` cannot be used on extern function `{}`", f.name),
+                    );
+                }
+            }
+            if f.has_attribute("test") && !f.is_extern {
+                let ret = f
+                    .return_type
+                    .as_ref()
+                    .map_or(TypeInfo::Void, TypeInfo::from_ast);
+                if !f.params.is_empty() || !matches!(ret, TypeInfo::Int | TypeInfo::Void) {
+                    self.error("E-SEMA-027", format!(
+                        "`@test` function `{}` must take no parameters and return `Int` or `Void`",
+                        f.name
+                    ));
+                }
+            }
+            if let Some(note) = deprecation(&f.attributes) {
+                self.deprecated_functions
+                    .insert(f.name.clone(), note.map(str::to_string));
+            }
+            self.pop_fallback_span();
+        }
+        for s in &program.structs {
+            self.push_fallback_span(self.declaration_span("struct", &s.name));
+            self.check_attribute_list(&s.attributes, &STRUCT_ATTRIBUTES, "struct");
+            if let Some(note) = deprecation(&s.attributes) {
+                self.deprecated_structs
+                    .insert(s.name.clone(), note.map(str::to_string));
+            }
+            self.pop_fallback_span();
+        }
+    }
+
+    /// Reports each `from m import name` item that names a deprecated export, at
+    /// the import. Wildcard and module imports are only reported where used.
+    pub(super) fn check_deprecated_imports(&mut self, program: &Program) {
+        for import in &program.imports {
+            let ImportDecl::ImportFrom {
+                path,
+                wildcard: false,
+                items,
+            } = import
+            else {
+                continue;
+            };
+            let span = self
+                .declaration_span("from", &path.join("."))
+                .unwrap_or_default();
+            for item in items {
+                let local = item.alias.as_ref().unwrap_or(&item.name);
+                let (kind, note) = if let Some(note) = self.deprecated_functions.get(local) {
+                    ("function", note.clone())
+                } else if let Some(note) = self.deprecated_structs.get(local) {
+                    ("struct", note.clone())
+                } else {
+                    continue;
+                };
+                let message = match note {
+                    Some(note) => format!(
+                        "Imported {kind} `{}` from `{}` is deprecated: {note}",
+                        item.name,
+                        path.join(".")
+                    ),
+                    None => format!(
+                        "Imported {kind} `{}` from `{}` is deprecated",
+                        item.name,
+                        path.join(".")
+                    ),
+                };
+                self.report_lint(Lint::Deprecated, message, Some(span));
+            }
+        }
+    }
+
+    fn check_attribute_list(&mut self, attributes: &[Attribute], allowed: &[&str], kind: &str) {
+        let mut seen = HashSet::new();
+        for attr in attributes {
+            let name = attr.name.as_str();
+            if !FUNCTION_ATTRIBUTES.contains(&name) {
+                self.error(
+                    "E-SEMA-027",
+                    format!(
+                        "Unknown attribute `@{name}`; expected `@inline`, `@test`, or `@deprecated`"
+                    ),
+                );
+                continue;
+            }
+            if !allowed.contains(&name) {
+                self.error(
+                    "E-SEMA-027",
+                    format!("Attribute `@{name}` cannot be used on a {kind}"),
+                );
+                continue;
+            }
+            if !seen.insert(name) {
+                self.error("E-SEMA-027", format!("Duplicate attribute `@{name}`"));
+            }
+            let max_args = usize::from(name == "deprecated");
+            if attr.args.len() > max_args {
+                self.error("E-SEMA-027", if max_args == 0 {
+                    format!("Attribute `@{name}` takes no arguments")
+                } else {
+                    format!(
+                        "Attribute `@{name}` takes at most one note, as in `@{name}(\"use other\")`"
+                    )
+                });
+            }
+        }
+    }
+
+    /// Reports a use of function `name` if it is marked `@deprecated`.
+    pub(super) fn note_deprecated_function_use(&mut self, name: &str) {
+        if let Some(note) = self.deprecated_functions.get(name).cloned() {
+            self.report_deprecated_use("Function", name, note);
+        }
+    }
+
+    /// Reports a use of struct `name` if it is marked `@deprecated`.
+    pub(super) fn note_deprecated_struct_use(&mut self, name: &str) {
+        if let Some(note) = self.deprecated_structs.get(name).cloned() {
+            self.report_deprecated_use("Struct", name, note);
+        }
+    }
+
+    fn report_deprecated_use(&mut self, kind: &str, name: &str, note: Option<String>) {
+        let message = match note {
+            Some(note) => format!("{kind} `{name}` is deprecated: {note}"),
+            None => format!("{kind} `{name}` is deprecated"),
+        };
+        self.report_lint(Lint::Deprecated, message, None);
+    }
+}
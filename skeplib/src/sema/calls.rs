@@ -10,11 +10,15 @@ mod bytes;
 mod datetime;
 mod ffi_pkg;
 mod fs;
+mod hash;
 mod io;
+mod iter;
 mod map;
 mod net;
 mod os;
 mod random;
+mod serde;
+mod store;
 mod str_pkg;
 mod task;
 mod vec;
@@ -74,6 +78,24 @@ impl Checker {
     ) -> TypeInfo {
         if let Expr::Ident(name) = callee {
             match (name.as_str(), args) {
+                ("panic", _) => {
+                    if args.len() != 1 {
+                        self.error(
+                            "E-SEMA-004",
+                            format!("panic expects 1 argument, got {}", args.len()),
+                        );
+                    }
+                    for (i, arg) in args.iter().enumerate() {
+                        let got = self.check_expr(arg, scopes);
+                        if i == 0 && !Self::types_compatible(&got, &TypeInfo::String) {
+                            self.error(
+                                "E-SEMA-010",
+                                format!("panic argument 1 expects String, got {:?}", got),
+                            );
+                        }
+                    }
+                    return TypeInfo::Void;
+                }
                 ("Some", [value]) => {
                     let value_ty = self.check_expr(value, scopes);
                     return TypeInfo::Option {
@@ -84,7 +106,10 @@ impl Checker {
                     for arg in args {
                         self.check_expr(arg, scopes);
                     }
-                    self.error(format!("{name} expects 1 argument, got {}", args.len()));
+                    self.error(
+                        "E-SEMA-004",
+                        format!("{name} expects 1 argument, got {}", args.len()),
+                    );
                     return TypeInfo::Unknown;
                 }
                 ("None", []) => {
@@ -96,7 +121,10 @@ impl Checker {
                     for arg in args {
                         self.check_expr(arg, scopes);
                     }
-                    self.error(format!("{name} expects 0 arguments, got {}", args.len()));
+                    self.error(
+                        "E-SEMA-004",
+                        format!("{name} expects 0 arguments, got {}", args.len()),
+                    );
                     return TypeInfo::Unknown;
                 }
                 ("Ok", [value]) => {
@@ -110,7 +138,10 @@ impl Checker {
                     for arg in args {
                         self.check_expr(arg, scopes);
                     }
-                    self.error(format!("{name} expects 1 argument, got {}", args.len()));
+                    self.error(
+                        "E-SEMA-004",
+                        format!("{name} expects 1 argument, got {}", args.len()),
+                    );
                     return TypeInfo::Unknown;
                 }
                 ("Err", [value]) => {
@@ -124,7 +155,10 @@ impl Checker {
                     for arg in args {
                         self.check_expr(arg, scopes);
                     }
-                    self.error(format!("{name} expects 1 argument, got {}", args.len()));
+                    self.error(
+                        "E-SEMA-004",
+                        format!("{name} expects 1 argument, got {}", args.len()),
+                    );
                     return TypeInfo::Unknown;
                 }
                 _ => {}
@@ -136,7 +170,14 @@ impl Checker {
             && (parts[0] == "io"
                 || parts[0] == "str"
                 || parts[0] == "bytes"
+                || parts[0] == "char"
                 || parts[0] == "map"
+                || parts[0] == "math"
+                || parts[0] == "serde"
+                || parts[0] == "store"
+                || parts[0] == "encoding"
+                || parts[0] == "hash"
+                || parts[0] == "csv"
                 || parts[0] == "option"
                 || parts[0] == "result"
                 || parts[0] == "arr"
@@ -147,7 +188,9 @@ impl Checker {
                 || parts[0] == "os"
                 || parts[0] == "fs"
                 || parts[0] == "task"
-                || parts[0] == "vec")
+                || parts[0] == "vec"
+                || parts[0] == "iter"
+                || self.custom_builtins.has_package(&parts[0]))
         {
             return self.check_builtin_call(&parts[0], &parts[1], args, scopes);
         }
@@ -156,13 +199,17 @@ impl Checker {
             && let Some(target) = self.direct_imports.get(name).cloned()
         {
             if let Some(sig) = self.functions.get(&target).cloned() {
+                self.note_deprecated_function_use(name);
                 return self.check_function_sig_call(&sig, args, scopes);
             }
             if self.has_external_context {
-                self.error(format!(
+                self.error("E-SEMA-001", format!(
                             "Imported function binding `{name}` resolved to missing target `{target}`; check that the target module exports it"
                         ));
             }
+            for arg in args {
+                self.check_expr(arg, scopes);
+            }
             return TypeInfo::Unknown;
         }
 
@@ -170,19 +217,23 @@ impl Checker {
             match self.resolve_qualified_import_call(&parts) {
                 Ok(Some(target)) => {
                     if let Some(sig) = self.functions.get(&target).cloned() {
+                        self.note_deprecated_function_use(&target);
                         return self.check_function_sig_call(&sig, args, scopes);
                     }
                     if self.has_external_context {
-                        self.error(format!(
+                        self.error("E-SEMA-001", format!(
                             "Qualified function call `{}` resolved to missing target `{target}`; check that the target module exports it",
                             parts.join(".")
                         ));
                     }
+                    for arg in args {
+                        self.check_expr(arg, scopes);
+                    }
                     return TypeInfo::Unknown;
                 }
                 Ok(None) => {}
                 Err(msg) => {
-                    self.error(msg);
+                    self.error("E-SEMA-011", msg);
                     for arg in args {
                         self.check_expr(arg, scopes);
                     }
@@ -191,6 +242,27 @@ impl Checker {
             }
         }
 
+        if let Expr::Field { base, field } = callee
+            && let Expr::Ident(package) = &**base
+            && !scopes.iter().any(|scope| scope.contains_key(package))
+            && !self.globals.contains_key(package)
+            && !self.module_namespaces.contains_key(package)
+        {
+            let packages = crate::builtins::all_builtin_specs()
+                .map(|spec| spec.sig.package)
+                .collect::<std::collections::BTreeSet<_>>();
+            let suggestion = Self::did_you_mean(package, packages.into_iter());
+            self.error_with_suggestion(
+                "E-SEMA-001",
+                format!("Unknown package `{package}` in call `{package}.{field}`"),
+                suggestion,
+            );
+            for arg in args {
+                self.check_expr(arg, scopes);
+            }
+            return TypeInfo::Unknown;
+        }
+
         if let Expr::Field { base, field } = callee {
             return self.check_method_call(base, field, args, scopes);
         }
@@ -204,43 +276,73 @@ impl Checker {
         if let Some(fn_name) = &callee_name
             && let Some(sig) = self.functions.get(fn_name).cloned()
         {
+            self.note_deprecated_function_use(fn_name);
             return self.check_function_sig_call(&sig, args, scopes);
         }
 
-        let callee_ty = self.check_expr(callee, scopes);
+        // A bare name bound nowhere is reported once, as an unknown function,
+        // rather than also as an unknown variable.
+        let unbound_name = matches!(callee, Expr::Ident(name)
+            if !scopes.iter().any(|scope| scope.contains_key(name))
+                && !self.globals.contains_key(name));
+        let callee_ty = if unbound_name {
+            TypeInfo::Void
+        } else {
+            self.check_expr(callee, scopes)
+        };
+        if callee_ty == TypeInfo::Unknown {
+            for arg in args {
+                self.check_expr(arg, scopes);
+            }
+            return TypeInfo::Unknown;
+        }
         if let TypeInfo::Fn { params, ret } = callee_ty {
             if params.len() != args.len() {
-                self.error(format!(
-                    "Arity mismatch for function value call: expected {}, got {}",
-                    params.len(),
-                    args.len()
-                ));
+                self.error(
+                    "E-SEMA-004",
+                    format!(
+                        "Arity mismatch for function value call: expected {}, got {}",
+                        params.len(),
+                        args.len()
+                    ),
+                );
+                for arg in args {
+                    self.check_expr(arg, scopes);
+                }
                 return TypeInfo::Unknown;
             }
             for (i, arg) in args.iter().enumerate() {
                 let got = self.check_expr(arg, scopes);
                 let expected = params[i].clone();
                 if !Self::types_compatible(&got, &expected) {
-                    self.error(format!(
-                        "Argument {} for function value call: expected {:?}, got {:?}",
-                        i + 1,
-                        expected,
-                        got
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!(
+                            "Argument {} for function value call: expected {:?}, got {:?}",
+                            i + 1,
+                            expected,
+                            got
+                        ),
+                    );
                 }
             }
             return *ret;
         }
 
         if let Some(fn_name) = callee_name {
-            self.error(format!("Unknown function `{fn_name}`"));
+            let suggestion = self.name_suggestion(&fn_name, scopes);
+            self.error_with_suggestion(
+                "E-SEMA-001",
+                format!("Unknown function `{fn_name}`"),
+                suggestion,
+            );
             for arg in args {
                 self.check_expr(arg, scopes);
             }
             return TypeInfo::Unknown;
         }
 
-        self.error("Invalid call target".to_string());
+        self.error("E-SEMA-011", "Invalid call target".to_string());
         for arg in args {
             self.check_expr(arg, scopes);
         }
@@ -254,12 +356,15 @@ impl Checker {
         scopes: &mut [HashMap<String, TypeInfo>],
     ) -> TypeInfo {
         if sig.params.len() != args.len() {
-            self.error(format!(
-                "Arity mismatch for `{}`: expected {}, got {}",
-                sig.name,
-                sig.params.len(),
-                args.len()
-            ));
+            self.error(
+                "E-SEMA-004",
+                format!(
+                    "Arity mismatch for `{}`: expected {}, got {}",
+                    sig.name,
+                    sig.params.len(),
+                    args.len()
+                ),
+            );
             for arg in args {
                 self.check_expr(arg, scopes);
             }
@@ -270,13 +375,16 @@ impl Checker {
             let got = self.check_expr(arg, scopes);
             let expected = sig.params[i].clone();
             if !Self::types_compatible(&got, &expected) {
-                self.error(format!(
-                    "Argument {} for `{}`: expected {:?}, got {:?}",
-                    i + 1,
-                    sig.name,
-                    expected,
-                    got
-                ));
+                self.error(
+                    "E-SEMA-010",
+                    format!(
+                        "Argument {} for `{}`: expected {:?}, got {:?}",
+                        i + 1,
+                        sig.name,
+                        expected,
+                        got
+                    ),
+                );
             }
         }
 
@@ -293,10 +401,10 @@ impl Checker {
         let recv_ty = self.check_expr(base, scopes);
         let TypeInfo::Named(struct_name) = recv_ty else {
             if recv_ty != TypeInfo::Unknown {
-                self.error(format!(
-                    "Method call requires struct receiver, got {:?}",
-                    recv_ty
-                ));
+                self.error(
+                    "E-SEMA-012",
+                    format!("Method call requires struct receiver, got {:?}", recv_ty),
+                );
             }
             for arg in args {
                 self.check_expr(arg, scopes);
@@ -305,10 +413,12 @@ impl Checker {
         };
 
         let Some(sig) = self.method_sig(&struct_name, method) else {
-            self.error(format!(
-                "Unknown method `{}` on struct `{}`",
-                method, struct_name
-            ));
+            let suggestion = self.method_suggestion(&struct_name, method);
+            self.error_with_suggestion(
+                "E-SEMA-002",
+                format!("Unknown method `{}` on struct `{}`", method, struct_name),
+                suggestion,
+            );
             for arg in args {
                 self.check_expr(arg, scopes);
             }
@@ -323,13 +433,16 @@ impl Checker {
         }
 
         if expected_params.len() != args.len() {
-            self.error(format!(
-                "Arity mismatch for method `{}.{}`: expected {}, got {}",
-                struct_name,
-                method,
-                expected_params.len(),
-                args.len()
-            ));
+            self.error(
+                "E-SEMA-004",
+                format!(
+                    "Arity mismatch for method `{}.{}`: expected {}, got {}",
+                    struct_name,
+                    method,
+                    expected_params.len(),
+                    args.len()
+                ),
+            );
             for arg in args {
                 self.check_expr(arg, scopes);
             }
@@ -340,14 +453,17 @@ impl Checker {
             let got = self.check_expr(arg, scopes);
             let expected = expected_params[i].clone();
             if !Self::types_compatible(&got, &expected) {
-                self.error(format!(
-                    "Argument {} for method `{}.{}`: expected {:?}, got {:?}",
-                    i + 1,
-                    struct_name,
-                    method,
-                    expected,
-                    got
-                ));
+                self.error(
+                    "E-SEMA-010",
+                    format!(
+                        "Argument {} for method `{}.{}`: expected {:?}, got {:?}",
+                        i + 1,
+                        struct_name,
+                        method,
+                        expected,
+                        got
+                    ),
+                );
             }
         }
 
@@ -365,16 +481,40 @@ impl Checker {
             for arg in args {
                 self.check_expr(arg, scopes);
             }
-            self.error(format!("`{package}.*` used without `import {package};`"));
+            self.error(
+                "E-SEMA-018",
+                format!("`{package}.*` used without `import {package};`"),
+            );
             return TypeInfo::Unknown;
         }
 
         if package == "vec" {
             return vec::check_vec_builtin(self, method, args, scopes);
         }
+        if package == "iter" {
+            return iter::check_iter_builtin(self, method, args, scopes);
+        }
 
         let Some(spec) = crate::builtins::find_builtin_spec_any(package, method) else {
-            self.error(format!("Unknown builtin `{package}.{method}`"));
+            if let Some(sig) = self.custom_builtins.find(package, method).cloned() {
+                return self.check_builtin_params(
+                    package,
+                    method,
+                    args,
+                    scopes,
+                    &sig.params,
+                    &sig.ret,
+                );
+            }
+            let suggestion = Self::builtin_suggestion(package, method);
+            self.error_with_suggestion(
+                "E-SEMA-001",
+                format!("Unknown builtin `{package}.{method}`"),
+                suggestion,
+            );
+            for arg in args {
+                self.check_expr(arg, scopes);
+            }
             return TypeInfo::Unknown;
         };
         let sig = spec.sig;
@@ -383,6 +523,9 @@ impl Checker {
             "io" => return io::check_io_builtin(self, method, args, scopes, sig),
             "str" => return str_pkg::check_str_builtin(self, method, args, scopes, sig),
             "bytes" => return bytes::check_bytes_builtin(self, method, args, scopes, sig),
+            "char" | "math" | "encoding" | "csv" => {
+                return self.check_fixed_arity_builtin(package, method, args, scopes, sig);
+            }
             "map" => return map::check_map_builtin(self, method, args, scopes),
             "option" => return self.check_option_builtin(method, args, scopes),
             "result" => return self.check_result_builtin(method, args, scopes),
@@ -398,6 +541,9 @@ impl Checker {
             "fs" => return fs::check_fs_builtin(self, method, args, scopes, sig),
             "os" => return os::check_os_builtin(self, method, args, scopes, sig),
             "task" => return task::check_task_builtin(self, method, args, scopes, sig),
+            "serde" => return serde::check_serde_builtin(self, method, args, scopes),
+            "store" => return store::check_store_builtin(self, method, args, scopes, sig),
+            "hash" => return hash::check_hash_builtin(self, method, args, scopes, sig),
             _ => {}
         }
 
@@ -415,35 +561,44 @@ impl Checker {
                 for arg in args {
                     self.check_expr(arg, scopes);
                 }
-                self.error(format!(
+                self.error("E-SEMA-019", format!(
                     "`option.{method}` is not part of the user-facing language surface; use `Some(...)` or `None()`"
                 ));
                 TypeInfo::Unknown
             }
             "isSome" | "isNone" => {
                 if args.len() != 1 {
-                    self.error(format!(
-                        "option.{method} expects 1 argument(s), got {}",
-                        args.len()
-                    ));
+                    self.error(
+                        "E-SEMA-004",
+                        format!("option.{method} expects 1 argument(s), got {}", args.len()),
+                    );
+                    for arg in args {
+                        self.check_expr(arg, scopes);
+                    }
                     return TypeInfo::Unknown;
                 }
                 let got = self.check_expr(&args[0], scopes);
                 match got {
                     TypeInfo::Option { .. } | TypeInfo::Unknown => {}
-                    other => self.error(format!(
-                        "option.{method} argument 1 expects Option, got {:?}",
-                        other
-                    )),
+                    other => self.error(
+                        "E-SEMA-010",
+                        format!("option.{method} argument 1 expects Option, got {:?}", other),
+                    ),
                 }
                 TypeInfo::Bool
             }
             "unwrapSome" => {
                 if args.len() != 1 {
-                    self.error(format!(
-                        "option.unwrapSome expects 1 argument(s), got {}",
-                        args.len()
-                    ));
+                    self.error(
+                        "E-SEMA-004",
+                        format!(
+                            "option.unwrapSome expects 1 argument(s), got {}",
+                            args.len()
+                        ),
+                    );
+                    for arg in args {
+                        self.check_expr(arg, scopes);
+                    }
                     return TypeInfo::Unknown;
                 }
                 let got = self.check_expr(&args[0], scopes);
@@ -451,10 +606,13 @@ impl Checker {
                     TypeInfo::Option { value } => *value,
                     TypeInfo::Unknown => TypeInfo::Unknown,
                     other => {
-                        self.error(format!(
-                            "option.unwrapSome argument 1 expects Option, got {:?}",
-                            other
-                        ));
+                        self.error(
+                            "E-SEMA-010",
+                            format!(
+                                "option.unwrapSome argument 1 expects Option, got {:?}",
+                                other
+                            ),
+                        );
                         TypeInfo::Unknown
                     }
                 }
@@ -482,35 +640,41 @@ impl Checker {
                 for arg in args {
                     self.check_expr(arg, scopes);
                 }
-                self.error(format!(
+                self.error("E-SEMA-019", format!(
                     "`result.{method}` is not part of the user-facing language surface; use `Ok(...)` or `Err(...)`"
                 ));
                 TypeInfo::Unknown
             }
             "isOk" | "isErr" => {
                 if args.len() != 1 {
-                    self.error(format!(
-                        "result.{method} expects 1 argument(s), got {}",
-                        args.len()
-                    ));
+                    self.error(
+                        "E-SEMA-004",
+                        format!("result.{method} expects 1 argument(s), got {}", args.len()),
+                    );
+                    for arg in args {
+                        self.check_expr(arg, scopes);
+                    }
                     return TypeInfo::Unknown;
                 }
                 let got = self.check_expr(&args[0], scopes);
                 match got {
                     TypeInfo::Result { .. } | TypeInfo::Unknown => {}
-                    other => self.error(format!(
-                        "result.{method} argument 1 expects Result, got {:?}",
-                        other
-                    )),
+                    other => self.error(
+                        "E-SEMA-010",
+                        format!("result.{method} argument 1 expects Result, got {:?}", other),
+                    ),
                 }
                 TypeInfo::Bool
             }
             "unwrapOk" => {
                 if args.len() != 1 {
-                    self.error(format!(
-                        "result.unwrapOk expects 1 argument(s), got {}",
-                        args.len()
-                    ));
+                    self.error(
+                        "E-SEMA-004",
+                        format!("result.unwrapOk expects 1 argument(s), got {}", args.len()),
+                    );
+                    for arg in args {
+                        self.check_expr(arg, scopes);
+                    }
                     return TypeInfo::Unknown;
                 }
                 let got = self.check_expr(&args[0], scopes);
@@ -518,20 +682,23 @@ impl Checker {
                     TypeInfo::Result { ok, .. } => *ok,
                     TypeInfo::Unknown => TypeInfo::Unknown,
                     other => {
-                        self.error(format!(
-                            "result.unwrapOk argument 1 expects Result, got {:?}",
-                            other
-                        ));
+                        self.error(
+                            "E-SEMA-010",
+                            format!("result.unwrapOk argument 1 expects Result, got {:?}", other),
+                        );
                         TypeInfo::Unknown
                     }
                 }
             }
             "unwrapErr" => {
                 if args.len() != 1 {
-                    self.error(format!(
-                        "result.unwrapErr expects 1 argument(s), got {}",
-                        args.len()
-                    ));
+                    self.error(
+                        "E-SEMA-004",
+                        format!("result.unwrapErr expects 1 argument(s), got {}", args.len()),
+                    );
+                    for arg in args {
+                        self.check_expr(arg, scopes);
+                    }
                     return TypeInfo::Unknown;
                 }
                 let got = self.check_expr(&args[0], scopes);
@@ -539,10 +706,13 @@ impl Checker {
                     TypeInfo::Result { err, .. } => *err,
                     TypeInfo::Unknown => TypeInfo::Unknown,
                     other => {
-                        self.error(format!(
-                            "result.unwrapErr argument 1 expects Result, got {:?}",
-                            other
-                        ));
+                        self.error(
+                            "E-SEMA-010",
+                            format!(
+                                "result.unwrapErr argument 1 expects Result, got {:?}",
+                                other
+                            ),
+                        );
                         TypeInfo::Unknown
                     }
                 }
@@ -567,28 +737,49 @@ impl Checker {
         scopes: &mut [HashMap<String, TypeInfo>],
         sig: &BuiltinSig,
     ) -> TypeInfo {
-        if sig.params.len() != args.len() {
-            self.error(format!(
-                "{package}.{method} expects {} argument(s), got {}",
-                sig.params.len(),
-                args.len()
-            ));
+        self.check_builtin_params(package, method, args, scopes, sig.params, &sig.ret)
+    }
+
+    fn check_builtin_params(
+        &mut self,
+        package: &str,
+        method: &str,
+        args: &[Expr],
+        scopes: &mut [HashMap<String, TypeInfo>],
+        params: &[TypeInfo],
+        ret: &TypeInfo,
+    ) -> TypeInfo {
+        if params.len() != args.len() {
+            self.error(
+                "E-SEMA-004",
+                format!(
+                    "{package}.{method} expects {} argument(s), got {}",
+                    params.len(),
+                    args.len()
+                ),
+            );
+            for arg in args {
+                self.check_expr(arg, scopes);
+            }
             return TypeInfo::Unknown;
         }
 
         for (idx, arg) in args.iter().enumerate() {
             let got = self.check_expr(arg, scopes);
-            let expected = sig.params[idx].clone();
+            let expected = params[idx].clone();
             if !Self::types_compatible(&got, &expected) {
-                self.error(format!(
-                    "{package}.{method} argument {} expects {:?}, got {:?}",
-                    idx + 1,
-                    expected,
-                    got
-                ));
+                self.error(
+                    "E-SEMA-010",
+                    format!(
+                        "{package}.{method} argument {} expects {:?}, got {:?}",
+                        idx + 1,
+                        expected,
+                        got
+                    ),
+                );
             }
         }
-        sig.ret.clone()
+        ret.clone()
     }
 
     pub(super) fn check_format_variadic_builtin(
@@ -600,16 +791,22 @@ impl Checker {
         sig: &BuiltinSig,
     ) -> TypeInfo {
         if args.is_empty() {
-            self.error(format!("{package}.{method} expects at least 1 argument"));
+            self.error(
+                "E-SEMA-004",
+                format!("{package}.{method} expects at least 1 argument"),
+            );
             return TypeInfo::Unknown;
         }
         let fmt_ty = self.check_expr(&args[0], scopes);
         if fmt_ty != TypeInfo::String && fmt_ty != TypeInfo::Unknown {
-            self.error(format!(
-                "{package}.{method} argument 1 expects {:?}, got {:?}",
-                TypeInfo::String,
-                fmt_ty
-            ));
+            self.error(
+                "E-SEMA-010",
+                format!(
+                    "{package}.{method} argument 1 expects {:?}, got {:?}",
+                    TypeInfo::String,
+                    fmt_ty
+                ),
+            );
         }
 
         if let Expr::StringLit(fmt) = &args[0] {
@@ -618,16 +815,28 @@ impl Checker {
                     let expected_args = specs.len();
                     let got_args = args.len().saturating_sub(1);
                     if expected_args != got_args {
-                        self.error(format!(
-                            "{package}.{method} format expects {} value argument(s), got {}",
-                            expected_args, got_args
-                        ));
+                        self.error(
+                            "E-SEMA-004",
+                            format!(
+                                "{package}.{method} format expects {} value argument(s), got {}",
+                                expected_args, got_args
+                            ),
+                        );
                     }
                     for (idx, arg) in args.iter().skip(1).enumerate() {
                         let got = self.check_expr(arg, scopes);
                         if idx >= specs.len() {
                             continue;
                         }
+                        if specs[idx] == 'v' {
+                            if got == TypeInfo::Void {
+                                self.error("E-SEMA-010", format!(
+                                    "{package}.{method} argument {} expects a value for `%v`, got Void",
+                                    idx + 2
+                                ));
+                            }
+                            continue;
+                        }
                         let expected = match specs[idx] {
                             'd' => TypeInfo::Int,
                             'f' => TypeInfo::Float,
@@ -636,7 +845,7 @@ impl Checker {
                             _ => TypeInfo::Unknown,
                         };
                         if got != TypeInfo::Unknown && got != expected {
-                            self.error(format!(
+                            self.error("E-SEMA-010", format!(
                                 "{package}.{method} argument {} expects {:?} for `%{}`, got {:?}",
                                 idx + 2,
                                 expected,
@@ -646,7 +855,10 @@ impl Checker {
                         }
                     }
                 }
-                Err(msg) => self.error(format!("{package}.{method} format error: {msg}")),
+                Err(msg) => self.error(
+                    "E-SEMA-020",
+                    format!("{package}.{method} format error: {msg}"),
+                ),
             }
         } else {
             for arg in args.iter().skip(1) {
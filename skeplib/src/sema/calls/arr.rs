@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use crate::ast::Expr;
-use crate::types::TypeInfo;
+use crate::types::{TypeInfo, display_type};
 
 use super::Checker;
+use super::vec::check_callback_arg;
 
 pub(super) fn check_arr_builtin(
     checker: &mut Checker,
@@ -14,19 +15,19 @@ pub(super) fn check_arr_builtin(
     match method {
         "len" | "isEmpty" | "first" | "last" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "arr.{method} expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("arr.{method} expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let arr_ty = checker.check_expr(&args[0], scopes);
             let TypeInfo::Array { elem, .. } = arr_ty else {
                 if arr_ty != TypeInfo::Unknown {
-                    checker.error(format!(
-                        "arr.{method} argument 1 expects Array, got {:?}",
-                        arr_ty
-                    ));
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("arr.{method} argument 1 expects Array, got {:?}", arr_ty),
+                    );
                 }
                 return TypeInfo::Unknown;
             };
@@ -39,32 +40,40 @@ pub(super) fn check_arr_builtin(
         }
         "contains" | "indexOf" | "count" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "arr.{method} expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("arr.{method} expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let arr_ty = checker.check_expr(&args[0], scopes);
             let needle_ty = checker.check_expr(&args[1], scopes);
             let TypeInfo::Array { elem, .. } = arr_ty else {
                 if arr_ty != TypeInfo::Unknown {
-                    checker.error(format!(
-                        "arr.{method} argument 1 expects Array, got {:?}",
-                        arr_ty
-                    ));
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("arr.{method} argument 1 expects Array, got {:?}", arr_ty),
+                    );
                 }
                 return TypeInfo::Unknown;
             };
             let elem_ty = *elem;
-            if needle_ty != TypeInfo::Unknown
-                && elem_ty != TypeInfo::Unknown
-                && needle_ty != elem_ty
-            {
-                checker.error(format!(
-                    "arr.{method} argument 2 expects {:?}, got {:?}",
-                    elem_ty, needle_ty
-                ));
+            if !Checker::types_compatible(&needle_ty, &elem_ty) {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "arr.{method} argument 2 expects {:?}, got {:?}",
+                        elem_ty, needle_ty
+                    ),
+                );
+            } else if let Some(blocker) = checker.equality_blocker(&elem_ty) {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "arr.{method} compares elements with `==`, but {} has no equality",
+                        display_type(&blocker)
+                    ),
+                );
             }
             match method {
                 "contains" => TypeInfo::Bool,
@@ -74,40 +83,112 @@ pub(super) fn check_arr_builtin(
         }
         "join" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "arr.{method} expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("arr.{method} expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let arr_ty = checker.check_expr(&args[0], scopes);
             let sep_ty = checker.check_expr(&args[1], scopes);
             if sep_ty != TypeInfo::String && sep_ty != TypeInfo::Unknown {
-                checker.error(format!(
-                    "arr.{method} argument 2 expects String, got {:?}",
-                    sep_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!("arr.{method} argument 2 expects String, got {:?}", sep_ty),
+                );
             }
             let TypeInfo::Array { elem, .. } = arr_ty else {
                 if arr_ty != TypeInfo::Unknown {
-                    checker.error(format!(
-                        "arr.{method} argument 1 expects Array, got {:?}",
-                        arr_ty
-                    ));
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("arr.{method} argument 1 expects Array, got {:?}", arr_ty),
+                    );
                 }
                 return TypeInfo::Unknown;
             };
             if *elem != TypeInfo::String && *elem != TypeInfo::Unknown {
-                checker.error(format!(
-                    "arr.{method} argument 1 expects Array[String], got {:?}",
-                    TypeInfo::Array { elem, size: 0 }
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "arr.{method} argument 1 expects Array[String], got {:?}",
+                        TypeInfo::Array { elem, size: 0 }
+                    ),
+                );
                 return TypeInfo::Unknown;
             }
             TypeInfo::String
         }
+        "map" | "filter" => {
+            if args.len() != 2 {
+                checker.error(
+                    "E-SEMA-004",
+                    format!("arr.{method} expects 2 argument(s), got {}", args.len()),
+                );
+                return TypeInfo::Unknown;
+            }
+            let arr_ty = checker.check_expr(&args[0], scopes);
+            let fn_ty = checker.check_expr(&args[1], scopes);
+            let TypeInfo::Array { elem, size } = arr_ty else {
+                if arr_ty != TypeInfo::Unknown {
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("arr.{method} argument 1 expects Array, got {:?}", arr_ty),
+                    );
+                }
+                return TypeInfo::Unknown;
+            };
+            let label = format!("arr.{method} argument 2");
+            if method == "filter" {
+                check_callback_arg(
+                    checker,
+                    &label,
+                    fn_ty,
+                    std::slice::from_ref(&*elem),
+                    Some(&TypeInfo::Bool),
+                );
+                return TypeInfo::Vec { elem };
+            }
+            match check_callback_arg(checker, &label, fn_ty, &[*elem], None) {
+                TypeInfo::Unknown => TypeInfo::Unknown,
+                ret => TypeInfo::Array {
+                    elem: Box::new(ret),
+                    size,
+                },
+            }
+        }
+        "sortBy" => {
+            if args.len() != 2 {
+                checker.error(
+                    "E-SEMA-004",
+                    format!("arr.sortBy expects 2 argument(s), got {}", args.len()),
+                );
+                return TypeInfo::Unknown;
+            }
+            let arr_ty = checker.check_expr(&args[0], scopes);
+            let cmp_ty = checker.check_expr(&args[1], scopes);
+            let TypeInfo::Array { elem, .. } = &arr_ty else {
+                if arr_ty != TypeInfo::Unknown {
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("arr.sortBy argument 1 expects Array, got {:?}", arr_ty),
+                    );
+                }
+                return TypeInfo::Unknown;
+            };
+            check_callback_arg(
+                checker,
+                "arr.sortBy argument 2",
+                cmp_ty,
+                &[(**elem).clone(), (**elem).clone()],
+                Some(&TypeInfo::Int),
+            );
+            arr_ty
+        }
         _ => {
-            checker.error(format!("Unsupported array builtin `arr.{method}`"));
+            checker.error(
+                "E-SEMA-001",
+                format!("Unsupported array builtin `arr.{method}`"),
+            );
             TypeInfo::Unknown
         }
     }
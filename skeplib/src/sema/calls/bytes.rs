@@ -18,10 +18,14 @@ pub(super) fn check_bytes_builtin(
         "get" => TypeInfo::Option {
             value: Box::new(TypeInfo::Int),
         },
-        "toString" => TypeInfo::Result {
+        "toString" | "decode" => TypeInfo::Result {
             ok: Box::new(TypeInfo::String),
             err: Box::new(TypeInfo::String),
         },
+        "fromHex" | "encode" => TypeInfo::Result {
+            ok: Box::new(TypeInfo::Bytes),
+            err: Box::new(TypeInfo::String),
+        },
         _ => ty,
     }
 }
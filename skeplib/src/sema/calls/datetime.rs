@@ -16,7 +16,7 @@ pub(super) fn check_datetime_builtin(
     match sig.kind {
         BuiltinKind::FixedArity => {
             checker.check_fixed_arity_builtin("datetime", method, args, scopes, sig);
-            if method == "parseUnix" {
+            if method == "parseUnix" || method == "parse" {
                 TypeInfo::Result {
                     ok: Box::new(TypeInfo::Int),
                     err: Box::new(TypeInfo::String),
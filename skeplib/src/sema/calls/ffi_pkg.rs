@@ -30,7 +30,7 @@ pub(super) fn check_ffi_builtin(
             | "call2IntInt"
             | "call2BytesIntInt"
     ) {
-        checker.error(format!(
+        checker.error("E-SEMA-019", format!(
             "`ffi.{method}` is a low-level internal helper; use `extern(\"...\") fn ...;` declarations instead"
         ));
         return TypeInfo::Unknown;
@@ -38,19 +38,22 @@ pub(super) fn check_ffi_builtin(
     match method {
         "open" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "ffi.open expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("ffi.open expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let path_ty = checker.check_expr(&args[0], scopes);
             if path_ty != TypeInfo::Unknown && path_ty != TypeInfo::String {
-                checker.error(format!(
-                    "ffi.open argument 1 expects {:?}, got {:?}",
-                    TypeInfo::String,
-                    path_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "ffi.open argument 1 expects {:?}, got {:?}",
+                        TypeInfo::String,
+                        path_ty
+                    ),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Opaque("ffi.Library".to_string())),
@@ -59,27 +62,33 @@ pub(super) fn check_ffi_builtin(
         }
         "bind" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "ffi.bind expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("ffi.bind expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let library_ty = checker.check_expr(&args[0], scopes);
             let expected_library = TypeInfo::Opaque("ffi.Library".to_string());
             if library_ty != TypeInfo::Unknown && library_ty != expected_library {
-                checker.error(format!(
-                    "ffi.bind argument 1 expects {:?}, got {:?}",
-                    expected_library, library_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "ffi.bind argument 1 expects {:?}, got {:?}",
+                        expected_library, library_ty
+                    ),
+                );
             }
             let name_ty = checker.check_expr(&args[1], scopes);
             if name_ty != TypeInfo::Unknown && name_ty != TypeInfo::String {
-                checker.error(format!(
-                    "ffi.bind argument 2 expects {:?}, got {:?}",
-                    TypeInfo::String,
-                    name_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "ffi.bind argument 2 expects {:?}, got {:?}",
+                        TypeInfo::String,
+                        name_ty
+                    ),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Opaque("ffi.Symbol".to_string())),
@@ -88,37 +97,43 @@ pub(super) fn check_ffi_builtin(
         }
         "closeLibrary" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "ffi.closeLibrary expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("ffi.closeLibrary expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let got = checker.check_expr(&args[0], scopes);
             let expected = TypeInfo::Opaque("ffi.Library".to_string());
             if got != TypeInfo::Unknown && got != expected {
-                checker.error(format!(
-                    "ffi.closeLibrary argument 1 expects {:?}, got {:?}",
-                    expected, got
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "ffi.closeLibrary argument 1 expects {:?}, got {:?}",
+                        expected, got
+                    ),
+                );
             }
             TypeInfo::Void
         }
         "closeSymbol" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "ffi.closeSymbol expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("ffi.closeSymbol expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let got = checker.check_expr(&args[0], scopes);
             let expected = TypeInfo::Opaque("ffi.Symbol".to_string());
             if got != TypeInfo::Unknown && got != expected {
-                checker.error(format!(
-                    "ffi.closeSymbol argument 1 expects {:?}, got {:?}",
-                    expected, got
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "ffi.closeSymbol argument 1 expects {:?}, got {:?}",
+                        expected, got
+                    ),
+                );
             }
             TypeInfo::Void
         }
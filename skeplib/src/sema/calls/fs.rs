@@ -28,7 +28,15 @@ pub(super) fn check_fs_builtin(
                 err: Box::new(TypeInfo::String),
             }
         }
-        "writeText" | "appendText" | "mkdirAll" | "removeFile" | "removeDirAll" => {
+        "readBytes" => {
+            checker.check_fixed_arity_builtin("fs", method, args, scopes, sig);
+            TypeInfo::Result {
+                ok: Box::new(TypeInfo::Bytes),
+                err: Box::new(TypeInfo::String),
+            }
+        }
+        "writeText" | "appendText" | "writeBytes" | "copyFile" | "moveFile" | "mkdirAll"
+        | "removeFile" | "removeDirAll" => {
             checker.check_fixed_arity_builtin("fs", method, args, scopes, sig);
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Void),
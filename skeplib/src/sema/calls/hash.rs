@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use crate::ast::Expr;
+use crate::builtins::BuiltinSig;
+use crate::types::TypeInfo;
+
+use super::Checker;
+
+/// `hash` builtins digest either a `String` or `Bytes`, which the signature
+/// table can only spell as `Unknown`.
+pub(super) fn check_hash_builtin(
+    checker: &mut Checker,
+    method: &str,
+    args: &[Expr],
+    scopes: &mut [HashMap<String, TypeInfo>],
+    sig: &BuiltinSig,
+) -> TypeInfo {
+    if args.len() != 1 {
+        checker.error(
+            "E-SEMA-004",
+            format!("hash.{method} expects 1 argument(s), got {}", args.len()),
+        );
+        for arg in args {
+            checker.check_expr(arg, scopes);
+        }
+        return TypeInfo::Unknown;
+    }
+    let got = checker.check_expr(&args[0], scopes);
+    if !matches!(got, TypeInfo::String | TypeInfo::Bytes | TypeInfo::Unknown) {
+        checker.error(
+            "E-SEMA-010",
+            format!("hash.{method} argument 1 expects String or Bytes, got {got:?}"),
+        );
+    }
+    sig.ret.clone()
+}
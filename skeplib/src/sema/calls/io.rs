@@ -20,6 +20,32 @@ pub(super) fn check_io_builtin(
         BuiltinKind::FormatVariadic => {
             checker.check_format_variadic_builtin("io", method, args, scopes, sig)
         }
-        BuiltinKind::ArrayOps => sig.ret.clone(),
+        BuiltinKind::ArrayOps => check_io_value_builtin(checker, method, args, scopes, sig),
     }
 }
+
+fn check_io_value_builtin(
+    checker: &mut Checker,
+    method: &str,
+    args: &[Expr],
+    scopes: &mut [HashMap<String, TypeInfo>],
+    sig: &BuiltinSig,
+) -> TypeInfo {
+    if args.len() != 1 {
+        checker.error(
+            "E-SEMA-004",
+            format!("io.{method} expects 1 argument(s), got {}", args.len()),
+        );
+        for arg in args {
+            checker.check_expr(arg, scopes);
+        }
+        return TypeInfo::Unknown;
+    }
+    if checker.check_expr(&args[0], scopes) == TypeInfo::Void {
+        checker.error(
+            "E-SEMA-010",
+            format!("io.{method} argument 1 expects a value, got Void"),
+        );
+    }
+    sig.ret.clone()
+}
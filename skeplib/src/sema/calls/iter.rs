@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use crate::ast::Expr;
+use crate::types::{TypeInfo, iter_type};
+
+use super::Checker;
+use super::vec::check_callback_arg;
+
+pub(super) fn check_iter_builtin(
+    checker: &mut Checker,
+    method: &str,
+    args: &[Expr],
+    scopes: &mut [HashMap<String, TypeInfo>],
+) -> TypeInfo {
+    let consumed = std::mem::take(&mut checker.iter_source_allowed);
+    let expected_args = match method {
+        "range" | "map" | "filter" | "take" => 2,
+        "collect" => 1,
+        _ => {
+            let suggestion = Checker::builtin_suggestion("iter", method);
+            checker.error_with_suggestion(
+                "E-SEMA-001",
+                format!("Unknown builtin `iter.{method}`"),
+                suggestion,
+            );
+            for arg in args {
+                checker.check_expr(arg, scopes);
+            }
+            return TypeInfo::Unknown;
+        }
+    };
+    if args.len() != expected_args {
+        checker.error(
+            "E-SEMA-004",
+            format!(
+                "iter.{method} expects {expected_args} argument(s), got {}",
+                args.len()
+            ),
+        );
+        for arg in args {
+            checker.check_expr(arg, scopes);
+        }
+        return TypeInfo::Unknown;
+    }
+    if method != "collect" && !consumed {
+        checker.error("E-SEMA-010", format!(
+            "iter.{method} must be consumed directly by a `for-in` loop, another `iter` call, or `iter.collect`"
+        ));
+    }
+
+    if method == "range" {
+        for (i, arg) in args.iter().enumerate() {
+            let got = checker.check_expr(arg, scopes);
+            if got != TypeInfo::Int && got != TypeInfo::Unknown {
+                checker.error(
+                    "E-SEMA-010",
+                    format!("iter.range argument {} expects Int, got {:?}", i + 1, got),
+                );
+            }
+        }
+        return iter_type(&TypeInfo::Int);
+    }
+
+    let elem = checker.check_iter_source(&args[0], scopes, &format!("iter.{method} argument 1"));
+    match method {
+        "map" => {
+            let fn_ty = checker.check_expr(&args[1], scopes);
+            let ret = check_callback_arg(checker, "iter.map argument 2", fn_ty, &[elem], None);
+            iter_type(&ret)
+        }
+        "filter" => {
+            let fn_ty = checker.check_expr(&args[1], scopes);
+            check_callback_arg(
+                checker,
+                "iter.filter argument 2",
+                fn_ty,
+                std::slice::from_ref(&elem),
+                Some(&TypeInfo::Bool),
+            );
+            iter_type(&elem)
+        }
+        "take" => {
+            let count = checker.check_expr(&args[1], scopes);
+            if count != TypeInfo::Int && count != TypeInfo::Unknown {
+                checker.error(
+                    "E-SEMA-010",
+                    format!("iter.take argument 2 expects Int, got {:?}", count),
+                );
+            }
+            iter_type(&elem)
+        }
+        _ => TypeInfo::Vec {
+            elem: Box::new(elem),
+        },
+    }
+}
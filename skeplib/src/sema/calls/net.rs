@@ -16,19 +16,22 @@ pub(super) fn check_net_builtin(
     match method {
         "accept" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "net.accept expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.accept expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let got = checker.check_expr(&args[0], scopes);
             let expected = TypeInfo::Opaque("net.Listener".to_string());
             if got != TypeInfo::Unknown && got != expected {
-                checker.error(format!(
-                    "net.accept argument 1 expects {:?}, got {:?}",
-                    expected, got
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.accept argument 1 expects {:?}, got {:?}",
+                        expected, got
+                    ),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Opaque("net.Socket".to_string())),
@@ -37,19 +40,19 @@ pub(super) fn check_net_builtin(
         }
         "read" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "net.read expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.read expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let got = checker.check_expr(&args[0], scopes);
             let expected = TypeInfo::Opaque("net.Socket".to_string());
             if got != TypeInfo::Unknown && got != expected {
-                checker.error(format!(
-                    "net.read argument 1 expects {:?}, got {:?}",
-                    expected, got
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!("net.read argument 1 expects {:?}, got {:?}", expected, got),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::String),
@@ -58,19 +61,22 @@ pub(super) fn check_net_builtin(
         }
         "readBytes" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "net.readBytes expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.readBytes expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let got = checker.check_expr(&args[0], scopes);
             let expected = TypeInfo::Opaque("net.Socket".to_string());
             if got != TypeInfo::Unknown && got != expected {
-                checker.error(format!(
-                    "net.readBytes argument 1 expects {:?}, got {:?}",
-                    expected, got
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.readBytes argument 1 expects {:?}, got {:?}",
+                        expected, got
+                    ),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Bytes),
@@ -79,27 +85,33 @@ pub(super) fn check_net_builtin(
         }
         "write" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "net.write expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.write expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let socket_ty = checker.check_expr(&args[0], scopes);
             let socket_expected = TypeInfo::Opaque("net.Socket".to_string());
             if socket_ty != TypeInfo::Unknown && socket_ty != socket_expected {
-                checker.error(format!(
-                    "net.write argument 1 expects {:?}, got {:?}",
-                    socket_expected, socket_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.write argument 1 expects {:?}, got {:?}",
+                        socket_expected, socket_ty
+                    ),
+                );
             }
             let data_ty = checker.check_expr(&args[1], scopes);
             if data_ty != TypeInfo::Unknown && data_ty != TypeInfo::String {
-                checker.error(format!(
-                    "net.write argument 2 expects {:?}, got {:?}",
-                    TypeInfo::String,
-                    data_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.write argument 2 expects {:?}, got {:?}",
+                        TypeInfo::String,
+                        data_ty
+                    ),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Void),
@@ -108,27 +120,33 @@ pub(super) fn check_net_builtin(
         }
         "writeBytes" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "net.writeBytes expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.writeBytes expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let socket_ty = checker.check_expr(&args[0], scopes);
             let socket_expected = TypeInfo::Opaque("net.Socket".to_string());
             if socket_ty != TypeInfo::Unknown && socket_ty != socket_expected {
-                checker.error(format!(
-                    "net.writeBytes argument 1 expects {:?}, got {:?}",
-                    socket_expected, socket_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.writeBytes argument 1 expects {:?}, got {:?}",
+                        socket_expected, socket_ty
+                    ),
+                );
             }
             let data_ty = checker.check_expr(&args[1], scopes);
             if data_ty != TypeInfo::Unknown && data_ty != TypeInfo::Bytes {
-                checker.error(format!(
-                    "net.writeBytes argument 2 expects {:?}, got {:?}",
-                    TypeInfo::Bytes,
-                    data_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.writeBytes argument 2 expects {:?}, got {:?}",
+                        TypeInfo::Bytes,
+                        data_ty
+                    ),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Void),
@@ -137,27 +155,33 @@ pub(super) fn check_net_builtin(
         }
         "readN" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "net.readN expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.readN expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let socket_ty = checker.check_expr(&args[0], scopes);
             let socket_expected = TypeInfo::Opaque("net.Socket".to_string());
             if socket_ty != TypeInfo::Unknown && socket_ty != socket_expected {
-                checker.error(format!(
-                    "net.readN argument 1 expects {:?}, got {:?}",
-                    socket_expected, socket_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.readN argument 1 expects {:?}, got {:?}",
+                        socket_expected, socket_ty
+                    ),
+                );
             }
             let count_ty = checker.check_expr(&args[1], scopes);
             if count_ty != TypeInfo::Unknown && count_ty != TypeInfo::Int {
-                checker.error(format!(
-                    "net.readN argument 2 expects {:?}, got {:?}",
-                    TypeInfo::Int,
-                    count_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.readN argument 2 expects {:?}, got {:?}",
+                        TypeInfo::Int,
+                        count_ty
+                    ),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Bytes),
@@ -166,19 +190,22 @@ pub(super) fn check_net_builtin(
         }
         "localAddr" | "peerAddr" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "net.{method} expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.{method} expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let got = checker.check_expr(&args[0], scopes);
             let expected = TypeInfo::Opaque("net.Socket".to_string());
             if got != TypeInfo::Unknown && got != expected {
-                checker.error(format!(
-                    "net.{method} argument 1 expects {:?}, got {:?}",
-                    expected, got
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.{method} argument 1 expects {:?}, got {:?}",
+                        expected, got
+                    ),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::String),
@@ -187,19 +214,19 @@ pub(super) fn check_net_builtin(
         }
         "flush" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "net.flush expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.flush expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let got = checker.check_expr(&args[0], scopes);
             let expected = TypeInfo::Opaque("net.Socket".to_string());
             if got != TypeInfo::Unknown && got != expected {
-                checker.error(format!(
-                    "net.flush argument 1 expects {:?}, got {:?}",
-                    expected, got
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!("net.flush argument 1 expects {:?}, got {:?}", expected, got),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Void),
@@ -208,27 +235,33 @@ pub(super) fn check_net_builtin(
         }
         "setReadTimeout" | "setWriteTimeout" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "net.{method} expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.{method} expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let socket_ty = checker.check_expr(&args[0], scopes);
             let socket_expected = TypeInfo::Opaque("net.Socket".to_string());
             if socket_ty != TypeInfo::Unknown && socket_ty != socket_expected {
-                checker.error(format!(
-                    "net.{method} argument 1 expects {:?}, got {:?}",
-                    socket_expected, socket_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.{method} argument 1 expects {:?}, got {:?}",
+                        socket_expected, socket_ty
+                    ),
+                );
             }
             let millis_ty = checker.check_expr(&args[1], scopes);
             if millis_ty != TypeInfo::Unknown && millis_ty != TypeInfo::Int {
-                checker.error(format!(
-                    "net.{method} argument 2 expects {:?}, got {:?}",
-                    TypeInfo::Int,
-                    millis_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.{method} argument 2 expects {:?}, got {:?}",
+                        TypeInfo::Int,
+                        millis_ty
+                    ),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Void),
@@ -237,40 +270,197 @@ pub(super) fn check_net_builtin(
         }
         "close" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "net.close expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.close expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let got = checker.check_expr(&args[0], scopes);
             let expected = TypeInfo::Opaque("net.Socket".to_string());
             if got != TypeInfo::Unknown && got != expected {
-                checker.error(format!(
-                    "net.close argument 1 expects {:?}, got {:?}",
-                    expected, got
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!("net.close argument 1 expects {:?}, got {:?}", expected, got),
+                );
             }
             TypeInfo::Void
         }
         "closeListener" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "net.closeListener expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!(
+                        "net.closeListener expects 1 argument(s), got {}",
+                        args.len()
+                    ),
+                );
                 return TypeInfo::Unknown;
             }
             let got = checker.check_expr(&args[0], scopes);
             let expected = TypeInfo::Opaque("net.Listener".to_string());
             if got != TypeInfo::Unknown && got != expected {
-                checker.error(format!(
-                    "net.closeListener argument 1 expects {:?}, got {:?}",
-                    expected, got
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.closeListener argument 1 expects {:?}, got {:?}",
+                        expected, got
+                    ),
+                );
             }
             TypeInfo::Void
         }
+        "udpConnect" | "send" => {
+            if args.len() != 2 {
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.{method} expects 2 argument(s), got {}", args.len()),
+                );
+                return TypeInfo::Unknown;
+            }
+            let socket_ty = checker.check_expr(&args[0], scopes);
+            let socket_expected = TypeInfo::Opaque("net.UdpSocket".to_string());
+            if socket_ty != TypeInfo::Unknown && socket_ty != socket_expected {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.{method} argument 1 expects {:?}, got {:?}",
+                        socket_expected, socket_ty
+                    ),
+                );
+            }
+            let expected = if method == "send" {
+                TypeInfo::Bytes
+            } else {
+                TypeInfo::String
+            };
+            let got = checker.check_expr(&args[1], scopes);
+            if got != TypeInfo::Unknown && got != expected {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.{method} argument 2 expects {:?}, got {:?}",
+                        expected, got
+                    ),
+                );
+            }
+            TypeInfo::Result {
+                ok: Box::new(TypeInfo::Void),
+                err: Box::new(TypeInfo::String),
+            }
+        }
+        "sendTo" => {
+            if args.len() != 3 {
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.sendTo expects 3 argument(s), got {}", args.len()),
+                );
+                return TypeInfo::Unknown;
+            }
+            let socket_ty = checker.check_expr(&args[0], scopes);
+            let socket_expected = TypeInfo::Opaque("net.UdpSocket".to_string());
+            if socket_ty != TypeInfo::Unknown && socket_ty != socket_expected {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.sendTo argument 1 expects {:?}, got {:?}",
+                        socket_expected, socket_ty
+                    ),
+                );
+            }
+            let address_ty = checker.check_expr(&args[1], scopes);
+            if address_ty != TypeInfo::Unknown && address_ty != TypeInfo::String {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.sendTo argument 2 expects {:?}, got {:?}",
+                        TypeInfo::String,
+                        address_ty
+                    ),
+                );
+            }
+            let data_ty = checker.check_expr(&args[2], scopes);
+            if data_ty != TypeInfo::Unknown && data_ty != TypeInfo::Bytes {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.sendTo argument 3 expects {:?}, got {:?}",
+                        TypeInfo::Bytes,
+                        data_ty
+                    ),
+                );
+            }
+            TypeInfo::Result {
+                ok: Box::new(TypeInfo::Void),
+                err: Box::new(TypeInfo::String),
+            }
+        }
+        "recvFrom" => {
+            if args.len() != 2 {
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.recvFrom expects 2 argument(s), got {}", args.len()),
+                );
+                return TypeInfo::Unknown;
+            }
+            let socket_ty = checker.check_expr(&args[0], scopes);
+            let socket_expected = TypeInfo::Opaque("net.UdpSocket".to_string());
+            if socket_ty != TypeInfo::Unknown && socket_ty != socket_expected {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.recvFrom argument 1 expects {:?}, got {:?}",
+                        socket_expected, socket_ty
+                    ),
+                );
+            }
+            let max_ty = checker.check_expr(&args[1], scopes);
+            if max_ty != TypeInfo::Unknown && max_ty != TypeInfo::Int {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.recvFrom argument 2 expects {:?}, got {:?}",
+                        TypeInfo::Int,
+                        max_ty
+                    ),
+                );
+            }
+            TypeInfo::Result {
+                ok: Box::new(TypeInfo::Tuple(vec![TypeInfo::Bytes, TypeInfo::String])),
+                err: Box::new(TypeInfo::String),
+            }
+        }
+        "recv" | "udpLocalAddr" | "closeUdp" => {
+            if args.len() != 1 {
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.{method} expects 1 argument(s), got {}", args.len()),
+                );
+                return TypeInfo::Unknown;
+            }
+            let got = checker.check_expr(&args[0], scopes);
+            let expected = TypeInfo::Opaque("net.UdpSocket".to_string());
+            if got != TypeInfo::Unknown && got != expected {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.{method} argument 1 expects {:?}, got {:?}",
+                        expected, got
+                    ),
+                );
+            }
+            match method {
+                "recv" => TypeInfo::Result {
+                    ok: Box::new(TypeInfo::Bytes),
+                    err: Box::new(TypeInfo::String),
+                },
+                "udpLocalAddr" => TypeInfo::Result {
+                    ok: Box::new(TypeInfo::String),
+                    err: Box::new(TypeInfo::String),
+                },
+                _ => TypeInfo::Void,
+            }
+        }
         "tlsConnect" => {
             checker.check_fixed_arity_builtin("net", method, args, scopes, sig);
             TypeInfo::Result {
@@ -296,29 +486,35 @@ pub(super) fn check_net_builtin(
         }
         "fetch" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "net.fetch expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("net.fetch expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let url_ty = checker.check_expr(&args[0], scopes);
             if url_ty != TypeInfo::Unknown && url_ty != TypeInfo::String {
-                checker.error(format!(
-                    "net.fetch argument 1 expects {:?}, got {:?}",
-                    TypeInfo::String,
-                    url_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.fetch argument 1 expects {:?}, got {:?}",
+                        TypeInfo::String,
+                        url_ty
+                    ),
+                );
             }
             let options_ty = checker.check_expr(&args[1], scopes);
             let expected = TypeInfo::Map {
                 value: Box::new(TypeInfo::String),
             };
             if options_ty != TypeInfo::Unknown && options_ty != expected {
-                checker.error(format!(
-                    "net.fetch argument 2 expects {:?}, got {:?}",
-                    expected, options_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "net.fetch argument 2 expects {:?}, got {:?}",
+                        expected, options_ty
+                    ),
+                );
             }
             TypeInfo::Result {
                 ok: Box::new(TypeInfo::Map {
@@ -327,7 +523,7 @@ pub(super) fn check_net_builtin(
                 err: Box::new(TypeInfo::String),
             }
         }
-        "__testSocket" | "listen" | "connect" => {
+        "__testSocket" | "listen" | "connect" | "bindUdp" => {
             checker.check_fixed_arity_builtin("net", method, args, scopes, sig);
             match method {
                 "__testSocket" => TypeInfo::Opaque("net.Socket".to_string()),
@@ -339,6 +535,10 @@ pub(super) fn check_net_builtin(
                     ok: Box::new(TypeInfo::Opaque("net.Socket".to_string())),
                     err: Box::new(TypeInfo::String),
                 },
+                "bindUdp" => TypeInfo::Result {
+                    ok: Box::new(TypeInfo::Opaque("net.UdpSocket".to_string())),
+                    err: Box::new(TypeInfo::String),
+                },
                 _ => unreachable!(),
             }
         }
@@ -15,15 +15,18 @@ pub(super) fn check_os_builtin(
 ) -> TypeInfo {
     if method == "envGet" {
         if args.len() != 1 {
-            checker.error(format!(
-                "os.envGet expects 1 argument(s), got {}",
-                args.len()
-            ));
+            checker.error(
+                "E-SEMA-004",
+                format!("os.envGet expects 1 argument(s), got {}", args.len()),
+            );
             return TypeInfo::Unknown;
         }
         let got = checker.check_expr(&args[0], scopes);
         if got != TypeInfo::String && got != TypeInfo::Unknown {
-            checker.error("os.envGet argument 1 expects String".to_string());
+            checker.error(
+                "E-SEMA-010",
+                "os.envGet argument 1 expects String".to_string(),
+            );
         }
         return TypeInfo::Option {
             value: Box::new(TypeInfo::String),
@@ -32,30 +35,40 @@ pub(super) fn check_os_builtin(
 
     if method == "arg" {
         if args.len() != 1 {
-            checker.error(format!("os.arg expects 1 argument(s), got {}", args.len()));
+            checker.error(
+                "E-SEMA-004",
+                format!("os.arg expects 1 argument(s), got {}", args.len()),
+            );
             return TypeInfo::Unknown;
         }
         let got = checker.check_expr(&args[0], scopes);
         if got != TypeInfo::Int && got != TypeInfo::Unknown {
-            checker.error("os.arg argument 1 expects Int".to_string());
+            checker.error("E-SEMA-010", "os.arg argument 1 expects Int".to_string());
         }
         return TypeInfo::Option {
             value: Box::new(TypeInfo::String),
         };
     }
 
-    if matches!(method, "exec" | "execOut") {
-        if args.len() != 2 {
-            checker.error(format!(
-                "os.{method} expects 2 argument(s), got {}",
-                args.len()
-            ));
+    if matches!(method, "exec" | "execOut" | "execCapture") {
+        let expected_args = if method == "execCapture" { 3 } else { 2 };
+        if args.len() != expected_args {
+            checker.error(
+                "E-SEMA-004",
+                format!(
+                    "os.{method} expects {expected_args} argument(s), got {}",
+                    args.len()
+                ),
+            );
             return TypeInfo::Unknown;
         }
 
         let program_ty = checker.check_expr(&args[0], scopes);
         if program_ty != TypeInfo::String {
-            checker.error(format!("os.{method} argument 1 expects String"));
+            checker.error(
+                "E-SEMA-010",
+                format!("os.{method} argument 1 expects String"),
+            );
         }
 
         let argv_ty = checker.check_expr(&args[1], scopes);
@@ -63,7 +76,23 @@ pub(super) fn check_os_builtin(
             elem: Box::new(TypeInfo::String),
         };
         if argv_ty != expected_argv_ty {
-            checker.error(format!("os.{method} argument 2 expects Vec[String]"));
+            checker.error(
+                "E-SEMA-010",
+                format!("os.{method} argument 2 expects Vec[String]"),
+            );
+        }
+
+        let string_map_ty = TypeInfo::Map {
+            value: Box::new(TypeInfo::String),
+        };
+        if let Some(options) = args.get(2) {
+            let options_ty = checker.check_expr(options, scopes);
+            if options_ty != TypeInfo::Unknown && options_ty != string_map_ty {
+                checker.error(
+                    "E-SEMA-010",
+                    format!("os.{method} argument 3 expects Map[String, String]"),
+                );
+            }
         }
 
         return match method {
@@ -75,6 +104,10 @@ pub(super) fn check_os_builtin(
                 ok: Box::new(TypeInfo::String),
                 err: Box::new(TypeInfo::String),
             },
+            "execCapture" => TypeInfo::Result {
+                ok: Box::new(string_map_ty),
+                err: Box::new(TypeInfo::String),
+            },
             _ => sig.ret.clone(),
         };
     }
@@ -17,6 +17,37 @@ pub(super) fn check_random_builtin(
         BuiltinKind::FixedArity => {
             checker.check_fixed_arity_builtin("random", method, args, scopes, sig)
         }
-        BuiltinKind::FormatVariadic | BuiltinKind::ArrayOps => sig.ret.clone(),
+        BuiltinKind::ArrayOps => check_random_array_builtin(checker, method, args, scopes),
+        BuiltinKind::FormatVariadic => sig.ret.clone(),
+    }
+}
+
+/// `random.choice` / `random.shuffle`, generic over the array element type.
+fn check_random_array_builtin(
+    checker: &mut Checker,
+    method: &str,
+    args: &[Expr],
+    scopes: &mut [HashMap<String, TypeInfo>],
+) -> TypeInfo {
+    if args.len() != 1 {
+        checker.error(
+            "E-SEMA-004",
+            format!("random.{method} expects 1 argument(s), got {}", args.len()),
+        );
+        return TypeInfo::Unknown;
+    }
+    let arr_ty = checker.check_expr(&args[0], scopes);
+    let TypeInfo::Array { elem, size } = arr_ty else {
+        if arr_ty != TypeInfo::Unknown {
+            checker.error(
+                "E-SEMA-010",
+                format!("random.{method} argument 1 expects Array, got {:?}", arr_ty),
+            );
+        }
+        return TypeInfo::Unknown;
+    };
+    match method {
+        "choice" => TypeInfo::Option { value: elem },
+        _ => TypeInfo::Array { elem, size },
     }
 }
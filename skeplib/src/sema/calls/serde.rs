@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use crate::ast::Expr;
+use crate::types::{TypeInfo, display_type};
+
+use super::Checker;
+
+pub(super) fn check_serde_builtin(
+    checker: &mut Checker,
+    method: &str,
+    args: &[Expr],
+    scopes: &mut [HashMap<String, TypeInfo>],
+) -> TypeInfo {
+    let decode_allowed = std::mem::take(&mut checker.serde_decode_allowed);
+    if args.len() != 1 {
+        checker.error(
+            "E-SEMA-004",
+            format!("serde.{method} expects 1 argument(s), got {}", args.len()),
+        );
+        for arg in args {
+            checker.check_expr(arg, scopes);
+        }
+        return TypeInfo::Unknown;
+    }
+    let got = checker.check_expr(&args[0], scopes);
+    match method {
+        "toJson" | "encode" => {
+            if let Some(part) = checker.serde_unsupported_type(&got) {
+                checker.error(
+                    "E-SEMA-029",
+                    format!(
+                        "serde.{method} cannot serialize `{}`: `{}` has no serialized form",
+                        display_type(&got),
+                        display_type(&part)
+                    ),
+                );
+            }
+            if method == "toJson"
+                && let Some(part) = checker.serde_json_nested_option(&got)
+            {
+                checker.error(
+                    "E-SEMA-029",
+                    format!(
+                        "serde.toJson cannot serialize `{}`: `{}` is ambiguous because `None` and `Some(None)` are both `null`; use `serde.encode` or wrap the inner Option in a struct",
+                        display_type(&got),
+                        display_type(&part)
+                    ),
+                );
+            }
+            if method == "toJson" {
+                TypeInfo::String
+            } else {
+                TypeInfo::Bytes
+            }
+        }
+        _ => {
+            let expected = if method == "fromJson" {
+                TypeInfo::String
+            } else {
+                TypeInfo::Bytes
+            };
+            if got != TypeInfo::Unknown && got != expected {
+                checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "serde.{method} argument 1 expects {:?}, got {:?}",
+                        expected, got
+                    ),
+                );
+            }
+            if !decode_allowed {
+                checker.error("E-SEMA-009", format!(
+                    "serde.{method} must initialize a local `let` annotated as `Result[T, String]`"
+                ));
+            }
+            TypeInfo::Result {
+                ok: Box::new(TypeInfo::Unknown),
+                err: Box::new(TypeInfo::String),
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use crate::ast::Expr;
+use crate::builtins::BuiltinSig;
+use crate::types::TypeInfo;
+
+use super::Checker;
+
+/// Every `store` builtin but `open` takes the `store.Store` handle first; the
+/// signature table can only spell that parameter as `Unknown`.
+pub(super) fn check_store_builtin(
+    checker: &mut Checker,
+    method: &str,
+    args: &[Expr],
+    scopes: &mut [HashMap<String, TypeInfo>],
+    sig: &BuiltinSig,
+) -> TypeInfo {
+    if method == "open" {
+        return checker.check_fixed_arity_builtin("store", method, args, scopes, sig);
+    }
+    if args.len() != sig.params.len() {
+        checker.error(
+            "E-SEMA-004",
+            format!(
+                "store.{method} expects {} argument(s), got {}",
+                sig.params.len(),
+                args.len()
+            ),
+        );
+        for arg in args {
+            checker.check_expr(arg, scopes);
+        }
+        return TypeInfo::Unknown;
+    }
+    for (idx, arg) in args.iter().enumerate() {
+        let got = checker.check_expr(arg, scopes);
+        let expected = if idx == 0 {
+            TypeInfo::Opaque("store.Store".to_string())
+        } else {
+            sig.params[idx].clone()
+        };
+        if !Checker::types_compatible(&got, &expected) {
+            checker.error(
+                "E-SEMA-010",
+                format!(
+                    "store.{method} argument {} expects {:?}, got {:?}",
+                    idx + 1,
+                    expected,
+                    got
+                ),
+            );
+        }
+    }
+    sig.ret.clone()
+}
@@ -22,10 +22,10 @@ pub(super) fn check_task_builtin(
         }
         "send" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "task.send expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("task.send expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let channel_ty = checker.check_expr(&args[0], scopes);
@@ -34,85 +34,102 @@ pub(super) fn check_task_builtin(
                 TypeInfo::Opaque(name) => match task_channel_value_type(&name) {
                     Some(expected) => {
                         if value_ty != TypeInfo::Unknown && value_ty != expected {
-                            checker.error(format!(
-                                "task.send argument 2 expects {:?}, got {:?}",
-                                expected, value_ty
-                            ));
+                            checker.error(
+                                "E-SEMA-010",
+                                format!(
+                                    "task.send argument 2 expects {:?}, got {:?}",
+                                    expected, value_ty
+                                ),
+                            );
                         }
                     }
-                    None => checker.error(format!(
-                        "task.send argument 1 expects {:?}, got {:?}",
-                        task_channel_type(&TypeInfo::Unknown),
-                        TypeInfo::Opaque(name)
-                    )),
+                    None => checker.error(
+                        "E-SEMA-010",
+                        format!(
+                            "task.send argument 1 expects {:?}, got {:?}",
+                            task_channel_type(&TypeInfo::Unknown),
+                            TypeInfo::Opaque(name)
+                        ),
+                    ),
                 },
                 TypeInfo::Unknown => {}
-                got => checker.error(format!(
-                    "task.send argument 1 expects Channel, got {:?}",
-                    got
-                )),
+                got => checker.error(
+                    "E-SEMA-010",
+                    format!("task.send argument 1 expects Channel, got {:?}", got),
+                ),
             }
             TypeInfo::Void
         }
         "spawn" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "task.spawn expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("task.spawn expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             match checker.check_expr(&args[0], scopes) {
                 TypeInfo::Fn { params, ret } => {
                     if !params.is_empty() {
-                        checker.error("task.spawn argument 1 expects Fn() -> T".to_string());
+                        checker.error(
+                            "E-SEMA-010",
+                            "task.spawn argument 1 expects Fn() -> T".to_string(),
+                        );
                         TypeInfo::Unknown
                     } else {
+                        if let Some(global) = checker.spawned_global_write(&args[0], scopes) {
+                            checker.error("E-SEMA-023", format!(
+                                "task.spawn argument 1 assigns global `{global}`; spawned tasks must not mutate globals, send results over a task.Channel or return them from the task"
+                            ));
+                        }
                         task_task_type(&ret)
                     }
                 }
                 TypeInfo::Unknown => TypeInfo::Unknown,
                 got => {
-                    checker.error(format!(
-                        "task.spawn argument 1 expects Fn() -> T, got {:?}",
-                        got
-                    ));
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("task.spawn argument 1 expects Fn() -> T, got {:?}", got),
+                    );
                     TypeInfo::Unknown
                 }
             }
         }
         "recv" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "task.recv expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("task.recv expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             match checker.check_expr(&args[0], scopes) {
                 TypeInfo::Opaque(name) => task_channel_value_type(&name).unwrap_or_else(|| {
-                    checker.error(format!(
-                        "task.recv argument 1 expects Channel, got {:?}",
-                        TypeInfo::Opaque(name)
-                    ));
+                    checker.error(
+                        "E-SEMA-010",
+                        format!(
+                            "task.recv argument 1 expects Channel, got {:?}",
+                            TypeInfo::Opaque(name)
+                        ),
+                    );
                     TypeInfo::Unknown
                 }),
                 TypeInfo::Unknown => TypeInfo::Unknown,
                 got => {
-                    checker.error(format!(
-                        "task.recv argument 1 expects Channel, got {:?}",
-                        got
-                    ));
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("task.recv argument 1 expects Channel, got {:?}", got),
+                    );
                     TypeInfo::Unknown
                 }
             }
         }
         "__testTask" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "task.__testTask expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("task.__testTask expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Opaque("task.Task".to_string());
             }
             let value_ty = checker.check_expr(&args[0], scopes);
@@ -125,48 +142,60 @@ pub(super) fn check_task_builtin(
         "__testChannel" => TypeInfo::Opaque("task.Channel".to_string()),
         "join" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "task.join expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("task.join expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             match checker.check_expr(&args[0], scopes) {
                 TypeInfo::Opaque(name) => task_task_value_type(&name).unwrap_or_else(|| {
-                    checker.error(format!(
-                        "task.join argument 1 expects Task, got {:?}",
-                        TypeInfo::Opaque(name)
-                    ));
+                    checker.error(
+                        "E-SEMA-010",
+                        format!(
+                            "task.join argument 1 expects Task, got {:?}",
+                            TypeInfo::Opaque(name)
+                        ),
+                    );
                     TypeInfo::Unknown
                 }),
                 TypeInfo::Unknown => TypeInfo::Unknown,
                 got => {
-                    checker.error(format!("task.join argument 1 expects Task, got {:?}", got));
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("task.join argument 1 expects Task, got {:?}", got),
+                    );
                     TypeInfo::Unknown
                 }
             }
         }
         "close" => {
             if args.len() != 1 {
-                checker.error(format!(
-                    "task.close expects 1 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("task.close expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Void;
             }
             match checker.check_expr(&args[0], scopes) {
                 TypeInfo::Opaque(name)
                     if task_task_value_type(&name).is_some()
                         || task_channel_value_type(&name).is_some() => {}
-                TypeInfo::Opaque(name) => checker.error(format!(
-                    "task.close argument 1 expects Task or Channel, got {:?}",
-                    TypeInfo::Opaque(name)
-                )),
+                TypeInfo::Opaque(name) => checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "task.close argument 1 expects Task or Channel, got {:?}",
+                        TypeInfo::Opaque(name)
+                    ),
+                ),
                 TypeInfo::Unknown => {}
-                got => checker.error(format!(
-                    "task.close argument 1 expects Task or Channel, got {:?}",
-                    got
-                )),
+                got => checker.error(
+                    "E-SEMA-010",
+                    format!(
+                        "task.close argument 1 expects Task or Channel, got {:?}",
+                        got
+                    ),
+                ),
             }
             TypeInfo::Void
         }
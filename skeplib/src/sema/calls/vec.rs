@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::ast::Expr;
-use crate::types::TypeInfo;
+use crate::types::{TypeInfo, display_type};
 
 use super::Checker;
 
@@ -14,27 +14,36 @@ pub(super) fn check_vec_builtin(
     match method {
         "new" => {
             if !args.is_empty() {
-                checker.error(format!("vec.new expects 0 argument(s), got {}", args.len()));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("vec.new expects 0 argument(s), got {}", args.len()),
+                );
             }
             TypeInfo::Unknown
         }
         "len" => {
             if args.len() != 1 {
-                checker.error(format!("vec.len expects 1 argument(s), got {}", args.len()));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("vec.len expects 1 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             match checker.check_expr(&args[0], scopes) {
                 TypeInfo::Vec { .. } | TypeInfo::Unknown => {}
-                got => checker.error(format!("vec.len argument 1 expects Vec, got {:?}", got)),
+                got => checker.error(
+                    "E-SEMA-010",
+                    format!("vec.len argument 1 expects Vec, got {:?}", got),
+                ),
             }
             TypeInfo::Int
         }
         "push" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "vec.push expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("vec.push expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let vec_ty = checker.check_expr(&args[0], scopes);
@@ -43,89 +52,273 @@ pub(super) fn check_vec_builtin(
                 TypeInfo::Vec { elem } => {
                     let expected = *elem;
                     if val_ty != TypeInfo::Unknown && val_ty != expected {
-                        checker.error(format!(
-                            "vec.push argument 2 expects {:?}, got {:?}",
-                            expected, val_ty
-                        ));
+                        checker.error(
+                            "E-SEMA-010",
+                            format!(
+                                "vec.push argument 2 expects {:?}, got {:?}",
+                                expected, val_ty
+                            ),
+                        );
                     }
                 }
                 TypeInfo::Unknown => {}
-                got => checker.error(format!("vec.push argument 1 expects Vec, got {:?}", got)),
+                got => checker.error(
+                    "E-SEMA-010",
+                    format!("vec.push argument 1 expects Vec, got {:?}", got),
+                ),
             }
             TypeInfo::Void
         }
         "get" => {
             if args.len() != 2 {
-                checker.error(format!("vec.get expects 2 argument(s), got {}", args.len()));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("vec.get expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let vec_ty = checker.check_expr(&args[0], scopes);
             let idx_ty = checker.check_expr(&args[1], scopes);
             if idx_ty != TypeInfo::Int && idx_ty != TypeInfo::Unknown {
-                checker.error(format!("vec.get argument 2 expects Int, got {:?}", idx_ty));
+                checker.error(
+                    "E-SEMA-010",
+                    format!("vec.get argument 2 expects Int, got {:?}", idx_ty),
+                );
             }
             match vec_ty {
                 TypeInfo::Vec { elem } => TypeInfo::Option { value: elem },
                 TypeInfo::Unknown => TypeInfo::Unknown,
                 got => {
-                    checker.error(format!("vec.get argument 1 expects Vec, got {:?}", got));
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("vec.get argument 1 expects Vec, got {:?}", got),
+                    );
                     TypeInfo::Unknown
                 }
             }
         }
         "set" => {
             if args.len() != 3 {
-                checker.error(format!("vec.set expects 3 argument(s), got {}", args.len()));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("vec.set expects 3 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let vec_ty = checker.check_expr(&args[0], scopes);
             let idx_ty = checker.check_expr(&args[1], scopes);
             let val_ty = checker.check_expr(&args[2], scopes);
             if idx_ty != TypeInfo::Int && idx_ty != TypeInfo::Unknown {
-                checker.error(format!("vec.set argument 2 expects Int, got {:?}", idx_ty));
+                checker.error(
+                    "E-SEMA-010",
+                    format!("vec.set argument 2 expects Int, got {:?}", idx_ty),
+                );
             }
             match vec_ty {
                 TypeInfo::Vec { elem } => {
                     let expected = *elem;
                     if val_ty != TypeInfo::Unknown && val_ty != expected {
-                        checker.error(format!(
-                            "vec.set argument 3 expects {:?}, got {:?}",
-                            expected, val_ty
-                        ));
+                        checker.error(
+                            "E-SEMA-010",
+                            format!(
+                                "vec.set argument 3 expects {:?}, got {:?}",
+                                expected, val_ty
+                            ),
+                        );
                     }
                 }
                 TypeInfo::Unknown => {}
-                got => checker.error(format!("vec.set argument 1 expects Vec, got {:?}", got)),
+                got => checker.error(
+                    "E-SEMA-010",
+                    format!("vec.set argument 1 expects Vec, got {:?}", got),
+                ),
             }
             TypeInfo::Void
         }
         "delete" => {
             if args.len() != 2 {
-                checker.error(format!(
-                    "vec.delete expects 2 argument(s), got {}",
-                    args.len()
-                ));
+                checker.error(
+                    "E-SEMA-004",
+                    format!("vec.delete expects 2 argument(s), got {}", args.len()),
+                );
                 return TypeInfo::Unknown;
             }
             let vec_ty = checker.check_expr(&args[0], scopes);
             let idx_ty = checker.check_expr(&args[1], scopes);
             if idx_ty != TypeInfo::Int && idx_ty != TypeInfo::Unknown {
-                checker.error(format!(
-                    "vec.delete argument 2 expects Int, got {:?}",
-                    idx_ty
-                ));
+                checker.error(
+                    "E-SEMA-010",
+                    format!("vec.delete argument 2 expects Int, got {:?}", idx_ty),
+                );
             }
             match vec_ty {
                 TypeInfo::Vec { elem } => *elem,
                 TypeInfo::Unknown => TypeInfo::Unknown,
                 got => {
-                    checker.error(format!("vec.delete argument 1 expects Vec, got {:?}", got));
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("vec.delete argument 1 expects Vec, got {:?}", got),
+                    );
                     TypeInfo::Unknown
                 }
             }
         }
+        "map" | "filter" => {
+            if args.len() != 2 {
+                checker.error(
+                    "E-SEMA-004",
+                    format!("vec.{method} expects 2 argument(s), got {}", args.len()),
+                );
+                return TypeInfo::Unknown;
+            }
+            let vec_ty = checker.check_expr(&args[0], scopes);
+            let fn_ty = checker.check_expr(&args[1], scopes);
+            let elem = match vec_ty {
+                TypeInfo::Vec { elem } => *elem,
+                TypeInfo::Unknown => TypeInfo::Unknown,
+                got => {
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("vec.{method} argument 1 expects Vec, got {:?}", got),
+                    );
+                    return TypeInfo::Unknown;
+                }
+            };
+            let label = format!("vec.{method} argument 2");
+            if method == "filter" {
+                check_callback_arg(
+                    checker,
+                    &label,
+                    fn_ty,
+                    std::slice::from_ref(&elem),
+                    Some(&TypeInfo::Bool),
+                );
+                return TypeInfo::Vec {
+                    elem: Box::new(elem),
+                };
+            }
+            match check_callback_arg(checker, &label, fn_ty, &[elem], None) {
+                TypeInfo::Unknown => TypeInfo::Unknown,
+                ret => TypeInfo::Vec {
+                    elem: Box::new(ret),
+                },
+            }
+        }
+        "reduce" => {
+            if args.len() != 3 {
+                checker.error(
+                    "E-SEMA-004",
+                    format!("vec.reduce expects 3 argument(s), got {}", args.len()),
+                );
+                return TypeInfo::Unknown;
+            }
+            let vec_ty = checker.check_expr(&args[0], scopes);
+            let init_ty = checker.check_expr(&args[1], scopes);
+            let fn_ty = checker.check_expr(&args[2], scopes);
+            let elem = match vec_ty {
+                TypeInfo::Vec { elem } => *elem,
+                TypeInfo::Unknown => TypeInfo::Unknown,
+                got => {
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("vec.reduce argument 1 expects Vec, got {:?}", got),
+                    );
+                    return TypeInfo::Unknown;
+                }
+            };
+            check_callback_arg(
+                checker,
+                "vec.reduce argument 3",
+                fn_ty,
+                &[init_ty.clone(), elem],
+                Some(&init_ty),
+            );
+            init_ty
+        }
+        "sort" => {
+            if args.len() != 2 {
+                checker.error(
+                    "E-SEMA-004",
+                    format!("vec.sort expects 2 argument(s), got {}", args.len()),
+                );
+                return TypeInfo::Unknown;
+            }
+            let vec_ty = checker.check_expr(&args[0], scopes);
+            let cmp_ty = checker.check_expr(&args[1], scopes);
+            let elem = match vec_ty {
+                TypeInfo::Vec { elem } => *elem,
+                TypeInfo::Unknown => TypeInfo::Unknown,
+                got => {
+                    checker.error(
+                        "E-SEMA-010",
+                        format!("vec.sort argument 1 expects Vec, got {:?}", got),
+                    );
+                    return TypeInfo::Unknown;
+                }
+            };
+            check_callback_arg(
+                checker,
+                "vec.sort argument 2",
+                cmp_ty,
+                &[elem.clone(), elem],
+                Some(&TypeInfo::Int),
+            );
+            TypeInfo::Void
+        }
         _ => {
-            checker.error(format!("Unknown builtin `vec.{method}`"));
+            let suggestion = Checker::builtin_suggestion("vec", method);
+            checker.error_with_suggestion(
+                "E-SEMA-001",
+                format!("Unknown builtin `vec.{method}`"),
+                suggestion,
+            );
+            TypeInfo::Unknown
+        }
+    }
+}
+
+/// Checks that a callback argument is a function value shaped like
+/// `Fn(params) -> ret`, where `ret` of `None` accepts any return type.
+/// `Unknown` on either side is accepted so earlier errors do not cascade.
+/// Returns the callback's return type.
+pub(super) fn check_callback_arg(
+    checker: &mut Checker,
+    label: &str,
+    got: TypeInfo,
+    params: &[TypeInfo],
+    ret: Option<&TypeInfo>,
+) -> TypeInfo {
+    let compatible = |expected: &TypeInfo, got: &TypeInfo| {
+        expected == got || *expected == TypeInfo::Unknown || *got == TypeInfo::Unknown
+    };
+    match got {
+        TypeInfo::Fn {
+            params: got_params,
+            ret: got_ret,
+        } if got_params.len() == params.len()
+            && params
+                .iter()
+                .zip(&got_params)
+                .all(|(e, g)| compatible(e, g))
+            && ret.is_none_or(|ret| compatible(ret, &got_ret)) =>
+        {
+            *got_ret
+        }
+        TypeInfo::Unknown => TypeInfo::Unknown,
+        got => {
+            let expected = format!(
+                "Fn({}) -> {}",
+                params
+                    .iter()
+                    .map(display_type)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                ret.map_or_else(|| "T".to_string(), display_type)
+            );
+            checker.error(
+                "E-SEMA-010",
+                format!("{label} expects {expected}, got {}", display_type(&got)),
+            );
             TypeInfo::Unknown
         }
     }
@@ -0,0 +1,370 @@
+use std::collections::HashSet;
+
+use crate::ast::Program;
+use crate::builtins::all_builtin_specs;
+use crate::index::find_declaration;
+use crate::resolver::ModuleGraph;
+use crate::types::{FunctionSig, TypeInfo, display_type};
+
+use super::Checker;
+use super::project::check_module_recording_bindings;
+
+/// Keywords offered where a statement or expression can start.
+const KEYWORDS: &[&str] = &[
+    "break", "continue", "defer", "else", "false", "fn", "for", "if", "let", "match", "return",
+    "true", "while",
+];
+
+/// 1-based line and column (in characters) of the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub col: usize,
+}
+
+/// Completion categories; declaration order is ranking order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CompletionKind {
+    Local,
+    Field,
+    Method,
+    Function,
+    Global,
+    Struct,
+    Module,
+    Keyword,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionItem {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// Type label, e.g. `Int` for a local or `fn(String) -> Int` for a function.
+    pub detail: String,
+}
+
+impl CompletionItem {
+    fn new(label: impl Into<String>, kind: CompletionKind, detail: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            kind,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Completes the identifier under `position` in module `module_id`.
+///
+/// After `receiver.` this offers struct fields and methods, builtin package
+/// functions or module namespace members; elsewhere it offers locals of the
+/// enclosing declaration, module-level names, imports and keywords. Items are
+/// filtered by the partial identifier before the cursor and ranked by kind,
+/// then by label.
+pub fn complete_at(
+    graph: &ModuleGraph,
+    module_id: &str,
+    position: Position,
+) -> Vec<CompletionItem> {
+    let Some(unit) = graph.modules.get(module_id) else {
+        return Vec::new();
+    };
+    let Some(offset) = byte_offset(&unit.source, position) else {
+        return Vec::new();
+    };
+    let Some(checker) = check_module_recording_bindings(graph, module_id) else {
+        return Vec::new();
+    };
+    let before = &unit.source[..offset];
+    let head = before.trim_end_matches(is_ident_char);
+    let prefix = &before[head.len()..];
+    let locals = visible_locals(&checker, &unit.program, &unit.source, head.len(), position);
+
+    let mut items = match head.strip_suffix('.') {
+        Some(receiver) => member_items(&checker, &locals, &receiver_path(receiver)),
+        None => scope_items(&checker, &locals),
+    };
+    items.retain(|item| item.label.starts_with(prefix));
+    items.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.label.cmp(&b.label)));
+    let mut seen = HashSet::new();
+    items.retain(|item| seen.insert(item.label.clone()));
+    items
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn byte_offset(source: &str, position: Position) -> Option<usize> {
+    let mut line_start = 0usize;
+    for _ in 1..position.line {
+        line_start += source[line_start..].find('\n')? + 1;
+    }
+    let line = source[line_start..].split('\n').next().unwrap_or_default();
+    let col = match line.char_indices().nth(position.col.checked_sub(1)?) {
+        Some((idx, _)) => idx,
+        None if position.col - 1 == line.chars().count() => line.len(),
+        None => return None,
+    };
+    Some(line_start + col)
+}
+
+/// `a.b.` before the cursor as `["a", "b"]`; empty when the receiver is not a
+/// plain dotted path (a call result, an index expression, ...).
+fn receiver_path(receiver: &str) -> Vec<String> {
+    let start = receiver
+        .trim_end_matches(|c: char| is_ident_char(c) || c == '.')
+        .len();
+    let parts = receiver[start..]
+        .split('.')
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if parts.iter().any(String::is_empty) {
+        return Vec::new();
+    }
+    parts
+}
+
+/// Parameters and locals of the declaration enclosing the cursor whose names
+/// already appear between the declaration header and the cursor.
+fn visible_locals(
+    checker: &Checker,
+    program: &Program,
+    source: &str,
+    cursor: usize,
+    position: Position,
+) -> Vec<(String, TypeInfo)> {
+    let Some((decl, decl_line)) = enclosing_decl(program, source, position.line) else {
+        return Vec::new();
+    };
+    let decl_start = source
+        .split_inclusive('\n')
+        .take(decl_line - 1)
+        .map(str::len)
+        .sum::<usize>();
+    let seen_text = source.get(decl_start..cursor).unwrap_or_default();
+    let mut out: Vec<(String, TypeInfo)> = Vec::new();
+    for binding in checker.recorded_bindings.iter().flatten() {
+        if binding.decl != decl || !contains_word(seen_text, &binding.name) {
+            continue;
+        }
+        // Later bindings shadow earlier ones with the same name.
+        out.retain(|(name, _)| name != &binding.name);
+        out.push((binding.name.clone(), binding.ty.clone()));
+    }
+    out
+}
+
+/// The `fn`/`method`/`opr` key of the last declaration starting at or before `line`.
+fn enclosing_decl(program: &Program, source: &str, line: usize) -> Option<(String, usize)> {
+    let mut decls = Vec::new();
+    for f in program.functions.iter().filter(|f| !f.is_extern) {
+        if let Some((l, _)) = find_declaration(source, "fn", &f.name, 0) {
+            decls.push((format!("fn {}", f.name), l));
+        }
+    }
+    for imp in &program.impls {
+        let impl_line = find_declaration(source, "impl", &imp.target, 0).map_or(0, |(l, _)| l);
+        for m in &imp.methods {
+            if let Some((l, _)) = find_declaration(source, "fn", &m.name, impl_line) {
+                decls.push((format!("method {}.{}", imp.target, m.name), l));
+            }
+        }
+    }
+    for op in &program.operators {
+        if let Some((l, _)) = find_declaration(source, "opr", &op.name, 0) {
+            decls.push((format!("opr {}", op.name), l));
+        }
+    }
+    decls
+        .into_iter()
+        .filter(|(_, l)| *l <= line)
+        .max_by_key(|(_, l)| *l)
+}
+
+fn contains_word(text: &str, word: &str) -> bool {
+    text.match_indices(word).any(|(idx, _)| {
+        let before_ok = text[..idx]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !is_ident_char(c));
+        let after_ok = text[idx + word.len()..]
+            .chars()
+            .next()
+            .is_none_or(|c| !is_ident_char(c));
+        before_ok && after_ok
+    })
+}
+
+fn scope_items(checker: &Checker, locals: &[(String, TypeInfo)]) -> Vec<CompletionItem> {
+    let mut items = locals
+        .iter()
+        .map(|(name, ty)| CompletionItem::new(name, CompletionKind::Local, display_type(ty)))
+        .collect::<Vec<_>>();
+    for (name, sig) in &checker.functions {
+        if !name.contains('.') {
+            items.push(CompletionItem::new(
+                name,
+                CompletionKind::Function,
+                fn_label(&sig.params, &sig.ret),
+            ));
+        }
+    }
+    for (name, ty) in &checker.globals {
+        if !name.contains('.') {
+            items.push(CompletionItem::new(
+                name,
+                CompletionKind::Global,
+                display_type(ty),
+            ));
+        }
+    }
+    for name in &checker.struct_names {
+        if !name.contains('.') {
+            items.push(CompletionItem::new(name, CompletionKind::Struct, "struct"));
+        }
+    }
+    for (name, path) in &checker.module_namespaces {
+        let detail = if checker.imported_modules.contains(name) {
+            "builtin package".to_string()
+        } else {
+            format!("module {}", path.join("."))
+        };
+        items.push(CompletionItem::new(name, CompletionKind::Module, detail));
+    }
+    for keyword in KEYWORDS {
+        items.push(CompletionItem::new(
+            *keyword,
+            CompletionKind::Keyword,
+            "keyword",
+        ));
+    }
+    items
+}
+
+fn member_items(
+    checker: &Checker,
+    locals: &[(String, TypeInfo)],
+    path: &[String],
+) -> Vec<CompletionItem> {
+    let Some((root, rest)) = path.split_first() else {
+        return Vec::new();
+    };
+    let root_ty = locals
+        .iter()
+        .rev()
+        .find(|(name, _)| name == root)
+        .map(|(_, ty)| ty)
+        .or_else(|| checker.globals.get(root));
+    if let Some(ty) = root_ty {
+        let mut ty = ty.clone();
+        for field in rest {
+            let TypeInfo::Named(struct_name) = &ty else {
+                return Vec::new();
+            };
+            let Some(next) = checker
+                .struct_fields
+                .get(struct_name)
+                .and_then(|fields| fields.get(field))
+            else {
+                return Vec::new();
+            };
+            ty = next.clone();
+        }
+        return match &ty {
+            TypeInfo::Named(struct_name) => struct_member_items(checker, struct_name),
+            _ => Vec::new(),
+        };
+    }
+    if rest.is_empty() && checker.imported_modules.contains(root) {
+        return all_builtin_specs()
+            .filter(|spec| spec.sig.package == root)
+            .map(|spec| {
+                CompletionItem::new(
+                    spec.sig.name,
+                    CompletionKind::Function,
+                    fn_label(spec.sig.params, &spec.sig.ret),
+                )
+            })
+            .collect();
+    }
+    let Some(ns_path) = checker.module_namespaces.get(root) else {
+        return Vec::new();
+    };
+    let mut qualified = ns_path.clone();
+    qualified.extend(rest.iter().cloned());
+    namespace_items(checker, &format!("{}.", qualified.join(".")))
+}
+
+fn struct_member_items(checker: &Checker, struct_name: &str) -> Vec<CompletionItem> {
+    // Fields of a struct exported as opaque are not accessible here.
+    let mut items = Vec::new();
+    if !checker.opaque_structs.contains_key(struct_name) {
+        for (name, ty) in checker.struct_fields.get(struct_name).into_iter().flatten() {
+            items.push(CompletionItem::new(
+                name,
+                CompletionKind::Field,
+                display_type(ty),
+            ));
+        }
+    }
+    for (name, sig) in checker.methods.get(struct_name).into_iter().flatten() {
+        items.push(CompletionItem::new(
+            name,
+            CompletionKind::Method,
+            method_label(sig, struct_name),
+        ));
+    }
+    items
+}
+
+/// Members of an imported module namespace, keyed in sema by their qualified
+/// name (`utils.math.add`); deeper namespaces are offered as modules.
+fn namespace_items(checker: &Checker, qualified_prefix: &str) -> Vec<CompletionItem> {
+    let mut items = Vec::new();
+    let mut push_member = |qualified: &str, kind, detail: String| {
+        let Some(rest) = qualified.strip_prefix(qualified_prefix) else {
+            return;
+        };
+        match rest.split_once('.') {
+            Some((module, _)) => {
+                let detail = format!("module {qualified_prefix}{module}");
+                items.push(CompletionItem::new(module, CompletionKind::Module, detail));
+            }
+            None => items.push(CompletionItem::new(rest, kind, detail)),
+        }
+    };
+    for (name, sig) in &checker.functions {
+        push_member(
+            name,
+            CompletionKind::Function,
+            fn_label(&sig.params, &sig.ret),
+        );
+    }
+    for (name, ty) in &checker.globals {
+        push_member(name, CompletionKind::Global, display_type(ty));
+    }
+    for name in &checker.struct_names {
+        push_member(name, CompletionKind::Struct, "struct".to_string());
+    }
+    items
+}
+
+fn fn_label(params: &[TypeInfo], ret: &TypeInfo) -> String {
+    format!(
+        "fn({}) -> {}",
+        params
+            .iter()
+            .map(display_type)
+            .collect::<Vec<_>>()
+            .join(", "),
+        display_type(ret)
+    )
+}
+
+fn method_label(sig: &FunctionSig, struct_name: &str) -> String {
+    let params = match sig.params.split_first() {
+        Some((TypeInfo::Named(self_ty), rest)) if self_ty == struct_name => rest,
+        _ => sig.params.as_slice(),
+    };
+    fn_label(params, &sig.ret)
+}
@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::ast::{BinaryOp, Expr, UnaryOp};
+use crate::ast::{BinaryOp, Expr, TypeName, UnaryOp};
 use crate::types::{TypeInfo, display_type};
 
 use super::Checker;
@@ -29,7 +29,14 @@ impl Checker {
                 parts[0].as_str(),
                 "io" | "str"
                     | "bytes"
+                    | "char"
                     | "map"
+                    | "math"
+                    | "serde"
+                    | "store"
+                    | "encoding"
+                    | "hash"
+                    | "csv"
                     | "option"
                     | "result"
                     | "arr"
@@ -41,12 +48,16 @@ impl Checker {
                     | "ffi"
                     | "task"
                     | "vec"
+                    | "iter"
             )
         {
-            self.error(format!(
-                "Builtin path `{}` is not a value; call it as a function",
-                parts.join(".")
-            ));
+            self.error(
+                "E-SEMA-011",
+                format!(
+                    "Builtin path `{}` is not a value; call it as a function",
+                    parts.join(".")
+                ),
+            );
             return TypeInfo::Unknown;
         }
 
@@ -80,12 +91,15 @@ impl Checker {
             };
         }
         if self.resolve_named_type_name(&joined).is_some() {
-            self.error(format!("Type path `{joined}` is not a value expression"));
+            self.error(
+                "E-SEMA-011",
+                format!("Type path `{joined}` is not a value expression"),
+            );
             return TypeInfo::Unknown;
         }
 
         let _ = scopes;
-        self.error(format!("Unknown path `{joined}`"));
+        self.error("E-SEMA-001", format!("Unknown path `{joined}`"));
         TypeInfo::Unknown
     }
 
@@ -98,6 +112,7 @@ impl Checker {
             Expr::IntLit(_) => TypeInfo::Int,
             Expr::FloatLit(_) => TypeInfo::Float,
             Expr::BoolLit(_) => TypeInfo::Bool,
+            Expr::CharLit(_) => TypeInfo::Char,
             Expr::StringLit(_) => TypeInfo::String,
             Expr::Ident(name) => self.lookup_var(name, scopes),
             Expr::Path(parts) => self.check_path_expr(parts, scopes),
@@ -109,7 +124,7 @@ impl Checker {
                         if ty == TypeInfo::Int || ty == TypeInfo::Float || ty == TypeInfo::Unknown {
                             ty
                         } else {
-                            self.error("Unary `-` expects Int or Float".to_string());
+                            self.error("E-SEMA-010", "Unary `-` expects Int or Float".to_string());
                             TypeInfo::Unknown
                         }
                     }
@@ -117,7 +132,7 @@ impl Checker {
                         if ty == TypeInfo::Int || ty == TypeInfo::Float || ty == TypeInfo::Unknown {
                             ty
                         } else {
-                            self.error("Unary `+` expects Int or Float".to_string());
+                            self.error("E-SEMA-010", "Unary `+` expects Int or Float".to_string());
                             TypeInfo::Unknown
                         }
                     }
@@ -125,7 +140,7 @@ impl Checker {
                         if ty == TypeInfo::Bool || ty == TypeInfo::Unknown {
                             TypeInfo::Bool
                         } else {
-                            self.error("Unary `!` expects Bool".to_string());
+                            self.error("E-SEMA-010", "Unary `!` expects Bool".to_string());
                             TypeInfo::Unknown
                         }
                     }
@@ -133,7 +148,7 @@ impl Checker {
                         if ty == TypeInfo::Int || ty == TypeInfo::Unknown {
                             TypeInfo::Int
                         } else {
-                            self.error("Unary `~` expects Int".to_string());
+                            self.error("E-SEMA-010", "Unary `~` expects Int".to_string());
                             TypeInfo::Unknown
                         }
                     }
@@ -142,6 +157,11 @@ impl Checker {
             Expr::Binary { left, op, right } => {
                 let lt = self.check_expr(left, scopes);
                 let rt = self.check_expr(right, scopes);
+                if let TypeInfo::Named(struct_name) = &lt
+                    && let Some(method) = op.overload_method()
+                {
+                    return self.check_operator_method(*op, struct_name, method, rt);
+                }
                 self.check_binary(*op, lt, rt)
             }
             Expr::CustomInfix {
@@ -152,34 +172,43 @@ impl Checker {
                 let lt = self.check_expr(left, scopes);
                 let rt = self.check_expr(right, scopes);
                 let Some(sig) = self.operators.get(operator).cloned() else {
-                    self.error(format!("Unknown operator `{}`", operator));
+                    self.error("E-SEMA-001", format!("Unknown operator `{}`", operator));
                     return TypeInfo::Unknown;
                 };
                 if sig.params.len() != 2 {
-                    self.error(format!(
-                        "Operator `{}` must have exactly 2 parameters",
-                        operator
-                    ));
+                    self.error(
+                        "E-SEMA-013",
+                        format!("Operator `{}` must have exactly 2 parameters", operator),
+                    );
                     return TypeInfo::Unknown;
                 }
                 if lt != TypeInfo::Unknown && lt != sig.params[0] {
-                    self.error(format!(
-                        "Left operand for operator `{}`: expected {:?}, got {:?}",
-                        operator, sig.params[0], lt
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!(
+                            "Left operand for operator `{}`: expected {:?}, got {:?}",
+                            operator, sig.params[0], lt
+                        ),
+                    );
                 }
                 if rt != TypeInfo::Unknown && rt != sig.params[1] {
-                    self.error(format!(
-                        "Right operand for operator `{}`: expected {:?}, got {:?}",
-                        operator, sig.params[1], rt
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!(
+                            "Right operand for operator `{}`: expected {:?}, got {:?}",
+                            operator, sig.params[1], rt
+                        ),
+                    );
                 }
                 sig.ret
             }
             Expr::Call { callee, args } => self.check_call(callee, args, scopes),
             Expr::ArrayLit(items) => {
                 if items.is_empty() {
-                    self.error("Cannot infer type of empty array literal".to_string());
+                    self.error(
+                        "E-SEMA-009",
+                        "Cannot infer type of empty array literal".to_string(),
+                    );
                     return TypeInfo::Unknown;
                 }
                 let mut elem_ty = self.check_expr(&items[0], scopes);
@@ -190,10 +219,13 @@ impl Checker {
                         continue;
                     }
                     if t != TypeInfo::Unknown && t != elem_ty {
-                        self.error(format!(
-                            "Array literal element type mismatch: expected {:?}, got {:?}",
-                            elem_ty, t
-                        ));
+                        self.error(
+                            "E-SEMA-010",
+                            format!(
+                                "Array literal element type mismatch: expected {:?}, got {:?}",
+                                elem_ty, t
+                            ),
+                        );
                         return TypeInfo::Unknown;
                     }
                 }
@@ -209,18 +241,81 @@ impl Checker {
                     size: *size,
                 }
             }
+            Expr::TupleLit(items) => TypeInfo::Tuple(
+                items
+                    .iter()
+                    .map(|item| self.check_expr(item, scopes))
+                    .collect(),
+            ),
+            Expr::VecLit(items) => {
+                // Like `vec.new()`, an empty literal takes its type from the `let` annotation.
+                let Some((first, rest)) = items.split_first() else {
+                    return TypeInfo::Unknown;
+                };
+                let mut elem_ty = self.check_expr(first, scopes);
+                for item in rest {
+                    let t = self.check_expr(item, scopes);
+                    if elem_ty == TypeInfo::Unknown {
+                        elem_ty = t;
+                        continue;
+                    }
+                    if t != TypeInfo::Unknown && t != elem_ty {
+                        self.error(
+                            "E-SEMA-010",
+                            format!(
+                                "Vec literal element type mismatch: expected {}, got {}",
+                                display_type(&elem_ty),
+                                display_type(&t)
+                            ),
+                        );
+                        return TypeInfo::Unknown;
+                    }
+                }
+                TypeInfo::Vec {
+                    elem: Box::new(elem_ty),
+                }
+            }
             Expr::Index { base, index } => {
                 let base_ty = self.check_expr(base, scopes);
                 let idx_ty = self.check_expr(index, scopes);
                 if idx_ty != TypeInfo::Int && idx_ty != TypeInfo::Unknown {
-                    self.error("Array index must be Int".to_string());
+                    self.error("E-SEMA-010", "Array index must be Int".to_string());
                 }
                 match base_ty {
                     TypeInfo::Array { elem, .. } => *elem,
                     TypeInfo::Vec { elem } => *elem,
+                    TypeInfo::String => TypeInfo::String,
                     TypeInfo::Unknown => TypeInfo::Unknown,
                     other => {
-                        self.error(format!("Cannot index into non-indexable type {:?}", other));
+                        self.error(
+                            "E-SEMA-012",
+                            format!("Cannot index into non-indexable type {:?}", other),
+                        );
+                        TypeInfo::Unknown
+                    }
+                }
+            }
+            Expr::Slice { base, start, end } => {
+                let base_ty = self.check_expr(base, scopes);
+                for bound in [start, end].into_iter().flatten() {
+                    let bound_ty = self.check_expr(bound, scopes);
+                    if bound_ty != TypeInfo::Int && bound_ty != TypeInfo::Unknown {
+                        self.error(
+                            "E-SEMA-010",
+                            format!("Slice bound must be Int, got {}", display_type(&bound_ty)),
+                        );
+                    }
+                }
+                match base_ty {
+                    TypeInfo::String | TypeInfo::Unknown => base_ty,
+                    other => {
+                        self.error(
+                            "E-SEMA-012",
+                            format!(
+                                "Range slicing is only supported on String, got {}",
+                                display_type(&other)
+                            ),
+                        );
                         TypeInfo::Unknown
                     }
                 }
@@ -233,7 +328,14 @@ impl Checker {
                             parts[0].as_str(),
                             "io" | "str"
                                 | "bytes"
+                                | "char"
                                 | "map"
+                                | "math"
+                                | "serde"
+                                | "store"
+                                | "encoding"
+                                | "hash"
+                                | "csv"
                                 | "option"
                                 | "result"
                                 | "arr"
@@ -245,6 +347,7 @@ impl Checker {
                                 | "ffi"
                                 | "task"
                                 | "vec"
+                                | "iter"
                         ))
                 {
                     return self.check_path_expr(&parts, scopes);
@@ -252,58 +355,142 @@ impl Checker {
                 let base_ty = self.check_expr(base, scopes);
                 match base_ty {
                     TypeInfo::Named(struct_name) => {
-                        if let Some(field_ty) = self.field_type(&struct_name, field) {
+                        if !self.check_opaque_struct_access(
+                            &struct_name,
+                            &format!("read field `{field}` of"),
+                        ) {
+                            TypeInfo::Unknown
+                        } else if let Some(field_ty) = self.field_type(&struct_name, field) {
                             field_ty
                         } else {
-                            self.error(format!(
-                                "Unknown field `{}` on struct `{}`",
-                                field, struct_name
-                            ));
+                            let suggestion = self.field_suggestion(&struct_name, field);
+                            self.error_with_suggestion(
+                                "E-SEMA-002",
+                                format!("Unknown field `{}` on struct `{}`", field, struct_name),
+                                suggestion,
+                            );
                             TypeInfo::Unknown
                         }
                     }
+                    TypeInfo::Tuple(items) => {
+                        match field
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|index| items.get(index))
+                        {
+                            Some(item_ty) => item_ty.clone(),
+                            None => {
+                                self.error(
+                                    "E-SEMA-002",
+                                    format!(
+                                        "Tuple {} has no element `.{field}`",
+                                        display_type(&TypeInfo::Tuple(items.clone()))
+                                    ),
+                                );
+                                TypeInfo::Unknown
+                            }
+                        }
+                    }
                     TypeInfo::Unknown => TypeInfo::Unknown,
                     other => {
-                        self.error(format!(
-                            "Field access requires struct value, got {:?}",
-                            other
-                        ));
+                        self.error(
+                            "E-SEMA-012",
+                            format!("Field access requires struct value, got {:?}", other),
+                        );
                         TypeInfo::Unknown
                     }
                 }
             }
-            Expr::StructLit { name, fields } => {
-                let Some(resolved_name) = self.resolve_named_type_name(name) else {
-                    self.error(format!("Unknown struct `{name}`"));
+            Expr::StructLit {
+                name,
+                type_args,
+                fields,
+            } => {
+                self.note_deprecated_struct_use(name);
+                let resolved = if type_args.is_empty() {
+                    self.resolve_named_type_name(name)
+                } else {
+                    let ty = TypeName::Generic {
+                        name: name.clone(),
+                        args: type_args.clone(),
+                    };
+                    self.check_decl_type_exists(&ty, "Unknown struct".to_string());
+                    match TypeInfo::from_ast(&ty) {
+                        TypeInfo::Named(instance) if self.struct_fields.contains_key(&instance) => {
+                            Some(instance)
+                        }
+                        _ => {
+                            for (_, expr) in fields {
+                                self.check_expr(expr, scopes);
+                            }
+                            return TypeInfo::Unknown;
+                        }
+                    }
+                };
+                if let Some(generic) = resolved
+                    .as_ref()
+                    .filter(|resolved| self.generic_structs.contains_key(*resolved))
+                {
+                    self.error("E-SEMA-024", format!(
+                        "Generic struct `{generic}` literal needs type arguments, as in `{generic}[...] {{ ... }}`"
+                    ));
+                    for (_, expr) in fields {
+                        self.check_expr(expr, scopes);
+                    }
+                    return TypeInfo::Unknown;
+                }
+                let name = match &resolved {
+                    Some(instance) if !type_args.is_empty() => instance,
+                    _ => name,
+                };
+                let Some(resolved_name) = resolved.clone() else {
+                    let suggestion =
+                        Self::did_you_mean(name, self.struct_names.iter().map(String::as_str));
+                    self.error_with_suggestion(
+                        "E-SEMA-001",
+                        format!("Unknown struct `{name}`"),
+                        suggestion,
+                    );
                     for (_, expr) in fields {
                         self.check_expr(expr, scopes);
                     }
                     return TypeInfo::Unknown;
                 };
                 let Some(expected_fields) = self.struct_fields.get(&resolved_name).cloned() else {
-                    self.error(format!("Unknown struct `{name}`"));
+                    self.error("E-SEMA-001", format!("Unknown struct `{name}`"));
                     for (_, expr) in fields {
                         self.check_expr(expr, scopes);
                     }
                     return TypeInfo::Unknown;
                 };
 
+                if !self.check_opaque_struct_access(&resolved_name, "construct") {
+                    for (_, expr) in fields {
+                        self.check_expr(expr, scopes);
+                    }
+                    return TypeInfo::Named(resolved_name);
+                }
+
                 let mut seen = HashMap::new();
                 for (field_name, expr) in fields {
                     let value_ty = self.check_expr(expr, scopes);
                     let Some(expected_ty) = expected_fields.get(field_name).cloned() else {
-                        self.error(format!(
-                            "Unknown field `{field_name}` in struct `{name}` literal"
-                        ));
+                        let suggestion = self.field_suggestion(&resolved_name, field_name);
+                        self.error_with_suggestion(
+                            "E-SEMA-002",
+                            format!("Unknown field `{field_name}` in struct `{name}` literal"),
+                            suggestion,
+                        );
                         continue;
                     };
                     if seen.insert(field_name.clone(), ()).is_some() {
-                        self.error(format!(
-                            "Duplicate field `{field_name}` in struct `{name}` literal"
-                        ));
+                        self.error(
+                            "E-SEMA-003",
+                            format!("Duplicate field `{field_name}` in struct `{name}` literal"),
+                        );
                     }
                     if !Self::types_compatible(&value_ty, &expected_ty) {
-                        self.error(format!(
+                        self.error("E-SEMA-010", format!(
                             "Type mismatch for field `{field_name}` in struct `{name}` literal: expected {:?}, got {:?}",
                             expected_ty, value_ty
                         ));
@@ -312,9 +499,10 @@ impl Checker {
 
                 for expected_name in expected_fields.keys() {
                     if !seen.contains_key(expected_name) {
-                        self.error(format!(
-                            "Missing field `{expected_name}` in struct `{name}` literal"
-                        ));
+                        self.error(
+                            "E-SEMA-002",
+                            format!("Missing field `{expected_name}` in struct `{name}` literal"),
+                        );
                     }
                 }
 
@@ -336,36 +524,45 @@ impl Checker {
                     "Unknown function literal return type".to_string(),
                 );
 
-                let expected_ret = TypeInfo::from_ast(return_type);
+                let expected_ret = self.declared_type(return_type);
                 let mut inner_scopes = scopes.to_vec();
                 let outer_scope_len = inner_scopes.len();
-                inner_scopes.push(HashMap::<String, TypeInfo>::new());
+                self.push_scope(&mut inner_scopes, HashMap::new());
                 for p in params {
                     if inner_scopes[outer_scope_len].contains_key(&p.name) {
-                        self.error(format!(
-                            "Duplicate parameter `{}` in function literal",
-                            p.name
-                        ));
+                        self.error(
+                            "E-SEMA-003",
+                            format!("Duplicate parameter `{}` in function literal", p.name),
+                        );
                     } else {
-                        inner_scopes[outer_scope_len]
-                            .insert(p.name.clone(), TypeInfo::from_ast(&p.ty));
+                        let ty = self.declared_type(&p.ty);
+                        self.note_binding(&p.name, &ty);
+                        inner_scopes[outer_scope_len].insert(p.name.clone(), ty);
+                        self.note_binding_span(outer_scope_len, &p.name, p.span);
                     }
                 }
                 self.fn_lit_scope_floors.push(outer_scope_len);
                 self.return_types.push(expected_ret.clone());
                 let saved_loop_depth = self.loop_depth;
+                let saved_defer_depth = self.defer_depth;
                 self.loop_depth = 0;
+                self.defer_depth = 0;
                 for stmt in body {
                     self.check_stmt(stmt, &mut inner_scopes, &expected_ret);
                 }
                 self.loop_depth = saved_loop_depth;
+                self.defer_depth = saved_defer_depth;
                 self.return_types.pop();
                 self.fn_lit_scope_floors.pop();
+                self.check_reachability(body);
                 if expected_ret != TypeInfo::Void && !Self::block_must_return(body) {
-                    self.error(format!(
-                        "Function literal may exit without returning {}",
-                        display_type(&expected_ret)
-                    ));
+                    self.error(
+                        "E-SEMA-005",
+                        format!(
+                            "Function literal may exit without returning {}",
+                            display_type(&expected_ret)
+                        ),
+                    );
                 }
 
                 TypeInfo::Fn {
@@ -390,50 +587,56 @@ impl Checker {
         let mut result_ty = TypeInfo::Unknown;
 
         if arms.is_empty() {
-            self.error("Match expression must have at least one arm".to_string());
+            self.error(
+                "E-SEMA-006",
+                "Match expression must have at least one arm".to_string(),
+            );
             return TypeInfo::Unknown;
         }
 
         for (idx, arm) in arms.iter().enumerate() {
-            if matches!(arm.pattern, crate::ast::MatchPattern::Wildcard)
-                || matches!(
-                    &arm.pattern,
-                    crate::ast::MatchPattern::Or(parts)
-                    if parts.iter().any(|part| matches!(part, crate::ast::MatchPattern::Wildcard))
-                )
-            {
+            if arm.guard.is_none() && arm.pattern.is_irrefutable() {
                 if seen_wildcard {
-                    self.error("Match expression can contain only one wildcard arm".to_string());
+                    self.error(
+                        "E-SEMA-006",
+                        "Match expression can contain only one wildcard arm".to_string(),
+                    );
                 }
                 if idx + 1 != arms.len() {
-                    self.error("Wildcard match arm `_` must be last".to_string());
+                    self.error("E-SEMA-006", Self::catch_all_not_last_message(&arm.pattern));
                 }
                 seen_wildcard = true;
             }
 
-            self.check_match_pattern(&arm.pattern, &target_ty, &mut seen_literals);
+            let bindings = self.check_match_arm_pattern(
+                &arm.pattern,
+                arm.guard.is_some(),
+                &target_ty,
+                &mut seen_literals,
+            );
 
             let mut arm_scopes = scopes.to_vec();
-            arm_scopes.push(HashMap::new());
-            if let crate::ast::MatchPattern::Variant {
-                binding: Some(binding),
-                ..
-            } = &arm.pattern
-                && let Some(binding_ty) = Self::match_variant_binding_type(&arm.pattern, &target_ty)
-                && let Some(scope) = arm_scopes.last_mut()
-            {
-                scope.insert(binding.clone(), binding_ty);
+            self.push_scope(&mut arm_scopes, HashMap::new());
+            for (binding, binding_ty) in bindings {
+                self.note_binding(&binding, &binding_ty);
+                if let Some(scope) = arm_scopes.last_mut() {
+                    scope.insert(binding, binding_ty);
+                }
             }
+            self.check_match_guard(arm.guard.as_ref(), &mut arm_scopes);
 
             let arm_ty = self.check_expr(&arm.expr, &mut arm_scopes);
             if matches!(result_ty, TypeInfo::Unknown) {
                 result_ty = arm_ty;
             } else if !Self::types_compatible(&arm_ty, &result_ty) {
-                self.error(format!(
-                    "Match expression arm type mismatch: expected {}, got {}",
-                    display_type(&result_ty),
-                    display_type(&arm_ty)
-                ));
+                self.error(
+                    "E-SEMA-010",
+                    format!(
+                        "Match expression arm type mismatch: expected {}, got {}",
+                        display_type(&result_ty),
+                        display_type(&arm_ty)
+                    ),
+                );
             }
         }
 
@@ -448,9 +651,19 @@ impl Checker {
     ) -> TypeInfo {
         let inner_ty = self.check_expr(inner, scopes);
         let Some(expected_ret) = self.return_types.last().cloned() else {
-            self.error("`?` is only allowed inside a function-like body".to_string());
+            self.error(
+                "E-SEMA-007",
+                "`?` is only allowed inside a function-like body".to_string(),
+            );
             return TypeInfo::Unknown;
         };
+        if self.defer_depth > 0 {
+            self.error(
+                "E-SEMA-007",
+                "`?` is not allowed inside a `defer` expression".to_string(),
+            );
+            return TypeInfo::Unknown;
+        }
 
         match (inner_ty, expected_ret) {
             (
@@ -460,7 +673,7 @@ impl Checker {
                 },
             ) => {
                 if !Self::types_compatible(&value, &expected_value) {
-                    self.error(format!(
+                    self.error("E-SEMA-010", format!(
                         "`?` option value type mismatch: expression has {}, but the enclosing function returns {}",
                         display_type(&value),
                         display_type(&expected_value)
@@ -477,7 +690,7 @@ impl Checker {
                 },
             ) => {
                 if !Self::types_compatible(&ok, &expected_ok) {
-                    self.error(format!(
+                    self.error("E-SEMA-010", format!(
                         "`?` result ok type mismatch: expression has {}, but the enclosing function returns {}",
                         display_type(&ok),
                         display_type(&expected_ok)
@@ -485,7 +698,7 @@ impl Checker {
                     return TypeInfo::Unknown;
                 }
                 if !Self::types_compatible(&err, &expected_err) {
-                    self.error(format!(
+                    self.error("E-SEMA-010", format!(
                         "`?` result error type mismatch: expression has {}, but the enclosing function returns {}",
                         display_type(&err),
                         display_type(&expected_err)
@@ -495,29 +708,101 @@ impl Checker {
                 *ok
             }
             (TypeInfo::Option { .. }, other) => {
-                self.error(format!(
+                self.error("E-SEMA-007", format!(
                     "`?` on Option is only allowed inside a function returning Option[...], but this function returns {}",
                     display_type(&other)
                 ));
                 TypeInfo::Unknown
             }
             (TypeInfo::Result { .. }, other) => {
-                self.error(format!(
+                self.error("E-SEMA-007", format!(
                     "`?` on Result is only allowed inside a function returning Result[..., ...], but this function returns {}",
                     display_type(&other)
                 ));
                 TypeInfo::Unknown
             }
             (other, _) => {
-                self.error(format!(
-                    "`?` expects an Option[...] or Result[..., ...] expression, got {}",
-                    display_type(&other)
-                ));
+                self.error(
+                    "E-SEMA-010",
+                    format!(
+                        "`?` expects an Option[...] or Result[..., ...] expression, got {}",
+                        display_type(&other)
+                    ),
+                );
                 TypeInfo::Unknown
             }
         }
     }
 
+    /// Resolves `a + b` on a struct to its `add(self, other)` method, and
+    /// likewise for the other arithmetic operators.
+    fn check_operator_method(
+        &mut self,
+        op: BinaryOp,
+        struct_name: &str,
+        method: &str,
+        rt: TypeInfo,
+    ) -> TypeInfo {
+        let Some(sig) = self.method_sig(struct_name, method) else {
+            self.error("E-SEMA-002", format!(
+                "Operator `{}` is not defined for struct `{struct_name}`; add `fn {method}(self, other: ...)` to `impl {struct_name}`",
+                op.symbol()
+            ));
+            return TypeInfo::Unknown;
+        };
+        if sig.params.len() != 2 {
+            self.error("E-SEMA-013", format!(
+                "Operator method `{struct_name}.{method}` must take `self` and exactly one other parameter"
+            ));
+            return TypeInfo::Unknown;
+        }
+        if !Self::types_compatible(&rt, &sig.params[1]) {
+            self.error(
+                "E-SEMA-010",
+                format!(
+                    "Right operand for `{}` on `{struct_name}`: expected {}, got {}",
+                    op.symbol(),
+                    display_type(&sig.params[1]),
+                    display_type(&rt)
+                ),
+            );
+        }
+        sig.ret
+    }
+
+    /// The first part of `ty` without `==`: a function, vector or map, found
+    /// through struct fields, array and tuple elements, and option or result
+    /// payloads. Everything else compares by value, all the way down.
+    pub(super) fn equality_blocker(&self, ty: &TypeInfo) -> Option<TypeInfo> {
+        self.equality_blocker_in(ty, &mut HashSet::new())
+    }
+
+    fn equality_blocker_in(&self, ty: &TypeInfo, seen: &mut HashSet<String>) -> Option<TypeInfo> {
+        match ty {
+            TypeInfo::Fn { .. } | TypeInfo::Vec { .. } | TypeInfo::Map { .. } => Some(ty.clone()),
+            TypeInfo::Array { elem: inner, .. } | TypeInfo::Option { value: inner } => {
+                self.equality_blocker_in(inner, seen)
+            }
+            TypeInfo::Result { ok, err } => self
+                .equality_blocker_in(ok, seen)
+                .or_else(|| self.equality_blocker_in(err, seen)),
+            TypeInfo::Tuple(items) => items
+                .iter()
+                .find_map(|item| self.equality_blocker_in(item, seen)),
+            TypeInfo::Named(name) => {
+                if !seen.insert(name.clone()) {
+                    return None;
+                }
+                let mut fields = self.struct_fields.get(name)?.iter().collect::<Vec<_>>();
+                fields.sort_by(|a, b| a.0.cmp(b.0));
+                fields
+                    .into_iter()
+                    .find_map(|(_, field_ty)| self.equality_blocker_in(field_ty, seen))
+            }
+            _ => None,
+        }
+    }
+
     fn check_binary(&mut self, op: BinaryOp, lt: TypeInfo, rt: TypeInfo) -> TypeInfo {
         use BinaryOp::*;
         match op {
@@ -547,10 +832,13 @@ impl Checker {
                             if lt == TypeInfo::Unknown || rt == TypeInfo::Unknown {
                                 TypeInfo::Unknown
                             } else {
-                                self.error(format!(
-                                    "Invalid operands for {:?}: left {:?}, right {:?}",
-                                    op, lt, rt
-                                ));
+                                self.error(
+                                    "E-SEMA-010",
+                                    format!(
+                                        "Invalid operands for {:?}: left {:?}, right {:?}",
+                                        op, lt, rt
+                                    ),
+                                );
                                 TypeInfo::Unknown
                             }
                         }
@@ -558,10 +846,13 @@ impl Checker {
                 } else if lt == TypeInfo::Unknown || rt == TypeInfo::Unknown {
                     TypeInfo::Unknown
                 } else {
-                    self.error(format!(
-                        "Invalid operands for {:?}: left {:?}, right {:?}",
-                        op, lt, rt
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!(
+                            "Invalid operands for {:?}: left {:?}, right {:?}",
+                            op, lt, rt
+                        ),
+                    );
                     TypeInfo::Unknown
                 }
             }
@@ -571,10 +862,13 @@ impl Checker {
                 } else if lt == TypeInfo::Unknown || rt == TypeInfo::Unknown {
                     TypeInfo::Unknown
                 } else {
-                    self.error(format!(
-                        "Invalid operands for {:?}: left {:?}, right {:?}",
-                        op, lt, rt
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!(
+                            "Invalid operands for {:?}: left {:?}, right {:?}",
+                            op, lt, rt
+                        ),
+                    );
                     TypeInfo::Unknown
                 }
             }
@@ -584,49 +878,73 @@ impl Checker {
                 } else if lt == TypeInfo::Unknown || rt == TypeInfo::Unknown {
                     TypeInfo::Unknown
                 } else {
-                    self.error(format!(
-                        "Invalid operands for {:?}: left {:?}, right {:?}",
-                        op, lt, rt
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!(
+                            "Invalid operands for {:?}: left {:?}, right {:?}",
+                            op, lt, rt
+                        ),
+                    );
                     TypeInfo::Unknown
                 }
             }
             EqEq | Neq => {
                 if matches!(lt, TypeInfo::Fn { .. }) || matches!(rt, TypeInfo::Fn { .. }) {
-                    self.error("Function values cannot be compared with `==` or `!=`".to_string());
+                    self.error(
+                        "E-SEMA-010",
+                        "Function values cannot be compared with `==` or `!=`".to_string(),
+                    );
                     return TypeInfo::Unknown;
                 }
                 if matches!(lt, TypeInfo::Vec { .. }) || matches!(rt, TypeInfo::Vec { .. }) {
-                    self.error("Vector values cannot be compared with `==` or `!=`".to_string());
+                    self.error(
+                        "E-SEMA-010",
+                        "Vector values cannot be compared with `==` or `!=`".to_string(),
+                    );
                     return TypeInfo::Unknown;
                 }
                 if matches!(lt, TypeInfo::Map { .. }) || matches!(rt, TypeInfo::Map { .. }) {
-                    self.error("Map values cannot be compared with `==` or `!=`".to_string());
+                    self.error(
+                        "E-SEMA-010",
+                        "Map values cannot be compared with `==` or `!=`".to_string(),
+                    );
                     return TypeInfo::Unknown;
                 }
                 if Self::types_compatible(&lt, &rt) {
+                    if let Some(blocker) = self.equality_blocker(&lt) {
+                        self.error("E-SEMA-010", format!(
+                            "{} values cannot be compared with `==` or `!=`: they contain {}, which has no equality",
+                            display_type(&lt),
+                            display_type(&blocker)
+                        ));
+                        return TypeInfo::Unknown;
+                    }
                     TypeInfo::Bool
                 } else {
-                    self.error(format!(
-                        "Cannot compare {} and {} with `==` or `!=`",
-                        display_type(&lt),
-                        display_type(&rt)
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!(
+                            "Cannot compare {} and {} with `==` or `!=`",
+                            display_type(&lt),
+                            display_type(&rt)
+                        ),
+                    );
                     TypeInfo::Unknown
                 }
             }
             Lt | Lte | Gt | Gte => {
                 if (lt == TypeInfo::Int && rt == TypeInfo::Int)
                     || (lt == TypeInfo::Float && rt == TypeInfo::Float)
+                    || (lt == TypeInfo::Char && rt == TypeInfo::Char)
                 {
                     TypeInfo::Bool
                 } else if lt == TypeInfo::Unknown || rt == TypeInfo::Unknown {
                     TypeInfo::Unknown
                 } else {
-                    self.error(format!(
-                        "Invalid comparison operands: left {:?}, right {:?}",
-                        lt, rt
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!("Invalid comparison operands: left {:?}, right {:?}", lt, rt),
+                    );
                     TypeInfo::Unknown
                 }
             }
@@ -636,10 +954,13 @@ impl Checker {
                 } else if lt == TypeInfo::Unknown || rt == TypeInfo::Unknown {
                     TypeInfo::Unknown
                 } else {
-                    self.error(format!(
-                        "Logical operators require Bool operands, got {:?} and {:?}",
-                        lt, rt
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!(
+                            "Logical operators require Bool operands, got {:?} and {:?}",
+                            lt, rt
+                        ),
+                    );
                     TypeInfo::Unknown
                 }
             }
@@ -0,0 +1,99 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::Program;
+
+use super::Checker;
+use super::task_isolation::{BodyEffects, collect_body, collect_expr};
+
+impl Checker {
+    /// Rejects global initializers that read a global of this module which is not
+    /// initialized yet through functions they call or name. Globals initialize in
+    /// declaration order after every imported module, so only later globals of the
+    /// same module (or the global itself) can be read too early; direct reads are
+    /// already rejected as unknown names.
+    pub(super) fn check_global_init_order(&mut self, program: &Program) {
+        let mut order = HashMap::new();
+        for (idx, g) in program.globals.iter().enumerate() {
+            order.entry(g.name.as_str()).or_insert(idx);
+        }
+        if order.is_empty() {
+            return;
+        }
+        let globals = order.keys().map(|name| name.to_string()).collect();
+        let effects = program
+            .functions
+            .iter()
+            .filter(|f| !f.is_extern)
+            .map(|f| {
+                let mut locals = f.params.iter().map(|p| p.name.clone()).collect();
+                let mut effects = BodyEffects::default();
+                collect_body(&f.body, &globals, &mut locals, &mut effects);
+                (f.name.as_str(), effects)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut reads = effects
+            .iter()
+            .map(|(name, e)| {
+                let direct = e
+                    .reads
+                    .iter()
+                    .filter(|r| order.contains_key(r.as_str()))
+                    .cloned()
+                    .collect::<HashSet<_>>();
+                (*name, direct)
+            })
+            .collect::<HashMap<_, _>>();
+        loop {
+            let mut changed = false;
+            for (name, e) in &effects {
+                let inherited = referenced_functions(e)
+                    .filter_map(|callee| reads.get(callee))
+                    .flatten()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let own = reads.get_mut(name).expect("every function has a read set");
+                for global in inherited {
+                    changed |= own.insert(global);
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        for (idx, g) in program.globals.iter().enumerate() {
+            let mut init = BodyEffects::default();
+            collect_expr(&g.value, &globals, &mut HashSet::new(), &mut init);
+            let mut reported = HashSet::new();
+            for callee in referenced_functions(&init) {
+                let Some(read) = reads.get(callee) else {
+                    continue;
+                };
+                let mut late = read
+                    .iter()
+                    .filter(|r| order[r.as_str()] >= idx && !reported.contains(*r))
+                    .collect::<Vec<_>>();
+                late.sort_by_key(|r| order[r.as_str()]);
+                for global in late {
+                    self.push_fallback_span(self.declaration_span("let", &g.name));
+                    self.error("E-SEMA-028", format!(
+                        "Initializer of global `{}` reads global `{global}` through `{callee}` before it is initialized",
+                        g.name
+                    ));
+                    self.pop_fallback_span();
+                    reported.insert(global.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Functions a body calls by name or refers to as a value.
+fn referenced_functions(effects: &BodyEffects) -> impl Iterator<Item = &str> {
+    effects
+        .callees
+        .iter()
+        .chain(&effects.reads)
+        .map(String::as_str)
+}
@@ -1,19 +1,30 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::ast::{OperatorDecl, Program, Stmt, TypeName};
-use crate::diagnostic::{DiagnosticBag, Span};
+use crate::ast::{OperatorDecl, Program, Stmt, StmtKind, StructDecl, TypeName};
+use crate::builtins::CustomBuiltins;
+use crate::diagnostic::{Diagnostic, DiagnosticBag, DiagnosticSink, Span};
+use crate::lint::{Lint, LintConfig, LintLevel};
 use crate::parser::Parser;
-use crate::types::{FunctionSig, TypeInfo, is_builtin_opaque_type};
+use crate::types::{
+    FunctionSig, TypeInfo, display_type, generic_struct_name, is_builtin_opaque_type,
+};
 
+mod attributes;
 mod calls;
+mod completion;
 mod expr;
+mod init_order;
 mod project;
+mod reachability;
 mod stmt;
+mod task_isolation;
 
+pub use self::completion::{CompletionItem, CompletionKind, Position, complete_at};
 use self::project::ModuleExternalContext;
 pub use self::project::{
     analyze_project_entry, analyze_project_entry_phased, analyze_project_graph,
-    analyze_project_graph_phased,
+    analyze_project_graph_phased, analyze_project_graph_with_builtins,
+    analyze_project_graph_with_lints, analyze_project_graph_with_sink, check_entry_main,
 };
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -22,21 +33,70 @@ pub struct SemaResult {
 }
 
 pub fn analyze_source(source: &str) -> (SemaResult, DiagnosticBag) {
-    let (program, mut diags) = Parser::parse_source(source);
-    if !diags.is_empty() {
-        return (SemaResult { has_errors: true }, diags);
+    let mut diags = DiagnosticBag::new();
+    let result = analyze_source_with_sink(source, &mut diags);
+    (result, diags)
+}
+
+/// Like [`analyze_source`], but reports parse and sema diagnostics to `sink`,
+/// each phase's as soon as it finishes.
+pub fn analyze_source_with_sink(source: &str, sink: &mut dyn DiagnosticSink) -> SemaResult {
+    analyze_source_impl(source, &CustomBuiltins::default(), sink)
+}
+
+/// Like [`analyze_source`], with calls into the embedder packages in `builtins`
+/// checked against their signatures.
+pub fn analyze_source_with_builtins(
+    source: &str,
+    builtins: &CustomBuiltins,
+) -> (SemaResult, DiagnosticBag) {
+    let mut diags = DiagnosticBag::new();
+    let result = analyze_source_impl(source, builtins, &mut diags);
+    (result, diags)
+}
+
+fn analyze_source_impl(
+    source: &str,
+    builtins: &CustomBuiltins,
+    sink: &mut dyn DiagnosticSink,
+) -> SemaResult {
+    let (program, parse_diags) = Parser::parse_source(source);
+    if !parse_diags.is_empty() {
+        for d in parse_diags.into_vec() {
+            sink.report(d);
+        }
+        return SemaResult { has_errors: true };
     }
     let mut checker = Checker::new(&program, Some(source));
+    checker.use_custom_builtins(&program, builtins);
     checker.check_program(&program);
+    let has_errors = checker.diagnostics.has_errors();
     for d in checker.diagnostics.into_vec() {
-        diags.push(d);
-    }
-    (
-        SemaResult {
-            has_errors: !diags.is_empty(),
-        },
-        diags,
-    )
+        sink.report(d);
+    }
+    SemaResult { has_errors }
+}
+
+/// Checks `program` and returns the type sema inferred for the local `name` of
+/// function `function`, or `None` if the program has errors. [`crate::eval`]
+/// uses this to type a snippet before lowering it.
+pub(crate) fn analyze_local_type(
+    program: &Program,
+    function: &str,
+    name: &str,
+) -> (Option<TypeInfo>, DiagnosticBag) {
+    let mut checker = Checker::new(program, None);
+    checker.recorded_bindings = Some(Vec::new());
+    checker.check_program(program);
+    let decl = format!("fn {function}");
+    let ty = checker
+        .recorded_bindings
+        .iter()
+        .flatten()
+        .find(|b| b.decl == decl && b.name == name)
+        .map(|b| b.ty.clone())
+        .filter(|_| !checker.diagnostics.has_errors());
+    (ty, checker.diagnostics)
 }
 
 struct Checker {
@@ -49,13 +109,58 @@ struct Checker {
     module_namespaces: HashMap<String, Vec<String>>,
     struct_names: HashSet<String>,
     struct_fields: HashMap<String, HashMap<String, TypeInfo>>,
+    /// Generic struct templates by name. Each `Name[A, B]` used in the module
+    /// becomes its own entry in `struct_names` and `struct_fields`.
+    generic_structs: HashMap<String, StructDecl>,
+    /// Type parameters in scope while checking a generic struct's fields.
+    generic_params: Vec<String>,
+    /// Nesting of generic instantiations currently being registered.
+    generic_depth: usize,
+    opaque_structs: HashMap<String, String>,
     globals: HashMap<String, TypeInfo>,
     loop_depth: usize,
+    defer_depth: usize,
     fn_lit_scope_floors: Vec<usize>,
     return_types: Vec<TypeInfo>,
     has_external_context: bool,
     fallback_spans: Vec<Span>,
+    /// Match statements already reported as non-exhaustive; they never count as
+    /// diverging for the unreachable-code lint.
+    non_exhaustive_matches: HashSet<Span>,
     source_text: Option<String>,
+    /// Local bindings seen while checking, kept only when completion asks for them.
+    recorded_bindings: Option<Vec<RecordedBinding>>,
+    current_decl: String,
+    /// Where each local of the current declaration was bound, by scope depth and
+    /// name, for labelling the first binding when a later one repeats it.
+    binding_spans: HashMap<(usize, String), Span>,
+    custom_builtins: CustomBuiltins,
+    /// Top-level function -> a global it assigns, directly or through calls.
+    global_writers: HashMap<String, String>,
+    /// Module and imported globals, for checking function literals passed to `task.spawn`.
+    task_globals: HashSet<String>,
+    /// Set while checking the source of a `for-in` loop or `iter` call, the only
+    /// places an `iter` pipeline may appear.
+    iter_source_allowed: bool,
+    /// Set while checking the initializer of a local `let`, the only place
+    /// `serde.fromJson` and `serde.decode` learn the type to decode.
+    serde_decode_allowed: bool,
+    /// Lint levels for this module: the caller's config with the module's
+    /// `#allow`/`#warn`/`#deny` directives applied.
+    lints: LintConfig,
+    /// Functions and structs marked `@deprecated`, with their notes: this module's
+    /// own, and imported ones under the names this module uses for them.
+    deprecated_functions: HashMap<String, Option<String>>,
+    deprecated_structs: HashMap<String, Option<String>>,
+}
+
+/// A parameter or local binding, tagged with its enclosing declaration
+/// (`fn name`, `method Type.name` or `opr name`).
+#[derive(Debug, Clone)]
+struct RecordedBinding {
+    decl: String,
+    name: String,
+    ty: TypeInfo,
 }
 
 impl Checker {
@@ -111,12 +216,18 @@ impl Checker {
                         .all(|(a, b)| Self::types_compatible(a, b))
                     && Self::types_compatible(a_ret, b_ret)
             }
+            (TypeInfo::Tuple(a), TypeInfo::Tuple(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(a, b)| Self::types_compatible(a, b))
+            }
             _ => false,
         }
     }
 
     fn resolve_named_type_name(&self, name: &str) -> Option<String> {
-        if is_builtin_opaque_type(name) {
+        if is_builtin_opaque_type(name) || self.generic_params.iter().any(|p| p == name) {
             return Some(name.to_string());
         }
         if self.struct_names.contains(name) {
@@ -156,6 +267,7 @@ impl Checker {
             self.struct_names.insert(name.clone());
             self.struct_fields.entry(name).or_insert(fields);
         }
+        self.opaque_structs.extend(ctx.opaque_structs);
         for (name, methods) in ctx.imported_methods {
             let slot = self.methods.entry(name).or_default();
             for (m, sig) in methods {
@@ -165,6 +277,8 @@ impl Checker {
         for (name, ty) in ctx.imported_globals {
             self.globals.entry(name).or_insert(ty);
         }
+        self.deprecated_functions.extend(ctx.deprecated_functions);
+        self.deprecated_structs.extend(ctx.deprecated_structs);
         for (local, target) in ctx.direct_import_targets {
             if let Some(sig) = self.functions.get(&local).cloned() {
                 self.functions.entry(target.clone()).or_insert(sig);
@@ -188,7 +302,7 @@ impl Checker {
             let spec = chars[i + 1];
             match spec {
                 '%' => {}
-                'd' | 'f' | 's' | 'b' => specs.push(spec),
+                'd' | 'f' | 's' | 'b' | 'v' => specs.push(spec),
                 other => return Err(format!("Unsupported format specifier `%{other}`")),
             }
             i += 2;
@@ -212,7 +326,14 @@ impl Checker {
                                 | "ffi"
                                 | "random"
                                 | "bytes"
+                                | "char"
                                 | "map"
+                                | "math"
+                                | "serde"
+                                | "store"
+                                | "encoding"
+                                | "hash"
+                                | "csv"
                                 | "option"
                                 | "result"
                                 | "net"
@@ -220,6 +341,7 @@ impl Checker {
                                 | "fs"
                                 | "task"
                                 | "vec"
+                                | "iter"
                         )
                     {
                         imported_modules.insert(path[0].clone());
@@ -259,13 +381,74 @@ impl Checker {
             module_namespaces,
             struct_names: HashSet::new(),
             struct_fields: HashMap::new(),
+            generic_structs: HashMap::new(),
+            generic_params: Vec::new(),
+            generic_depth: 0,
+            opaque_structs: HashMap::new(),
             globals: HashMap::new(),
             loop_depth: 0,
+            defer_depth: 0,
             fn_lit_scope_floors: Vec::new(),
             return_types: Vec::new(),
             has_external_context: false,
             fallback_spans: Vec::new(),
+            non_exhaustive_matches: HashSet::new(),
             source_text: source.map(ToString::to_string),
+            recorded_bindings: None,
+            current_decl: String::new(),
+            binding_spans: HashMap::new(),
+            custom_builtins: CustomBuiltins::default(),
+            global_writers: HashMap::new(),
+            task_globals: HashSet::new(),
+            iter_source_allowed: false,
+            serde_decode_allowed: false,
+            lints: LintConfig::default().with_directives(&program.lints),
+            deprecated_functions: HashMap::new(),
+            deprecated_structs: HashMap::new(),
+        }
+    }
+
+    /// Applies `config` under the module's own lint directives.
+    fn use_lints(&mut self, program: &Program, config: &LintConfig) {
+        self.lints = config.with_directives(&program.lints);
+    }
+
+    /// Reports a lint at its configured level: dropped when allowed, a warning,
+    /// or an error when denied. Without a `span` it is reported, approximately,
+    /// at the current declaration.
+    fn report_lint(&mut self, lint: Lint, message: impl Into<String>, span: Option<Span>) {
+        let located = span.unwrap_or_else(|| self.current_fallback_span());
+        let mut diag = match self.lints.level(lint) {
+            LintLevel::Allow => return,
+            LintLevel::Warn => Diagnostic::warning(message, located),
+            LintLevel::Deny => Diagnostic::error(message, located),
+        };
+        if span.is_none() {
+            diag = diag.approximate();
+        }
+        self.diagnostics.push(diag.with_code(lint.code()));
+    }
+
+    /// Makes embedder packages callable, for the modules `program` imports.
+    fn use_custom_builtins(&mut self, program: &Program, builtins: &CustomBuiltins) {
+        for imp in &program.imports {
+            if let crate::ast::ImportDecl::ImportModule { path, .. } = imp
+                && path.len() == 1
+                && builtins.has_package(&path[0])
+            {
+                self.imported_modules.insert(path[0].clone());
+            }
+        }
+        self.custom_builtins = builtins.clone();
+    }
+
+    fn note_binding(&mut self, name: &str, ty: &TypeInfo) {
+        if let Some(bindings) = &mut self.recorded_bindings {
+            bindings.push(RecordedBinding {
+                decl: self.current_decl.clone(),
+                name: name.to_string(),
+                ty: ty.clone(),
+            });
         }
     }
 
@@ -284,6 +467,79 @@ impl Checker {
         None
     }
 
+    /// Span of the `occurrence`-th (0-based) `keyword name` declaration in the
+    /// source, matched at word boundaries.
+    fn nth_declaration_span(&self, keyword: &str, name: &str, occurrence: usize) -> Option<Span> {
+        let source = self.source_text.as_deref()?;
+        let mut skip_lines = 0;
+        let mut found = None;
+        for _ in 0..=occurrence {
+            found = crate::index::find_declaration(source, keyword, name, skip_lines);
+            skip_lines = found?.0;
+        }
+        let (line, col) = found?;
+        let line_start = source
+            .split_inclusive('\n')
+            .take(line - 1)
+            .map(str::len)
+            .sum::<usize>();
+        let start = line_start + col - 1;
+        let end = start + keyword.len() + 1 + name.len();
+        Some(Span::new(start, end, line, col))
+    }
+
+    /// Reports a repeated declaration at the repeat, labelling the first one.
+    fn duplicate_declaration_error(
+        &mut self,
+        message: String,
+        keyword: &str,
+        name: &str,
+        occurrence: usize,
+    ) {
+        let span = self
+            .nth_declaration_span(keyword, name, occurrence)
+            .unwrap_or_else(|| self.current_fallback_span());
+        let mut diag = Diagnostic::error(message, span).with_code("E-SEMA-003");
+        if occurrence > 0
+            && let Some(first) = self.nth_declaration_span(keyword, name, 0)
+        {
+            diag = diag.with_label(first, "first declared here");
+        }
+        self.diagnostics.push(diag);
+    }
+
+    /// Reports a local that repeats a binding of its own scope, the one at `depth`,
+    /// at the repeated `let` and labels the earlier `let` or parameter.
+    pub(super) fn duplicate_local_error(&mut self, name: &str, depth: usize, span: Span) {
+        let message = format!("Duplicate local binding `{name}` in the same scope");
+        if span.line == 0 {
+            self.error("E-SEMA-003", message);
+            return;
+        }
+        let mut diag = Diagnostic::error(message, span).with_code("E-SEMA-003");
+        if let Some(&first) = self.binding_spans.get(&(depth, name.to_string())) {
+            diag = diag.with_label(first, "first declared here");
+        }
+        self.diagnostics.push(diag);
+    }
+
+    /// Records where a local of the scope at `depth` was bound.
+    pub(super) fn note_binding_span(&mut self, depth: usize, name: &str, span: Span) {
+        self.binding_spans.insert((depth, name.to_string()), span);
+    }
+
+    /// Opens a block scope below the innermost one. Binding positions recorded at
+    /// its depth belong to an earlier sibling block, so they are dropped.
+    pub(super) fn push_scope(
+        &mut self,
+        scopes: &mut Vec<HashMap<String, TypeInfo>>,
+        scope: HashMap<String, TypeInfo>,
+    ) {
+        let depth = scopes.len();
+        self.binding_spans.retain(|(at, _), _| *at < depth);
+        scopes.push(scope);
+    }
+
     fn push_fallback_span(&mut self, span: Option<Span>) {
         self.fallback_spans.push(span.unwrap_or_default());
     }
@@ -296,12 +552,48 @@ impl Checker {
         self.fallback_spans.last().copied().unwrap_or_default()
     }
 
+    /// `main` of an entry module becomes the process entry point: it takes no
+    /// parameters, and its `Int` result is the exit code (`Void` exits with 0).
+    fn check_entry_main(&mut self, program: &Program) {
+        let Some(main) = program.functions.iter().find(|f| f.name == "main") else {
+            return;
+        };
+        self.push_fallback_span(self.declaration_span("fn", "main"));
+        if !main.params.is_empty() {
+            self.error(
+                "E-SEMA-022",
+                format!(
+                    "`main` must take no parameters, found {}",
+                    main.params.len()
+                ),
+            );
+        }
+        let ret = main
+            .return_type
+            .as_ref()
+            .map_or(TypeInfo::Void, TypeInfo::from_ast);
+        if !matches!(ret, TypeInfo::Int | TypeInfo::Void) {
+            self.error(
+                "E-SEMA-022",
+                format!(
+                    "`main` must return `Int` or `Void`, found `{}`",
+                    display_type(&ret)
+                ),
+            );
+        }
+        self.pop_fallback_span();
+    }
+
     fn check_program(&mut self, program: &Program) {
+        self.check_attributes(program);
+        self.check_deprecated_imports(program);
+        self.collect_global_writers(program);
         self.check_struct_declarations(program);
+        self.check_type_alias_declarations(program);
         self.check_impl_declarations(program);
         self.collect_method_signatures(program);
 
-        for f in &program.functions {
+        for (idx, f) in program.functions.iter().enumerate() {
             self.push_fallback_span(self.declaration_span("fn", &f.name));
             for p in &f.params {
                 self.check_decl_type_exists(
@@ -321,20 +613,36 @@ impl Checker {
             if f.is_extern {
                 self.check_extern_function_signature(f);
             }
+            if f.name == "panic" {
+                self.error(
+                    "E-SEMA-003",
+                    "Function name `panic` is reserved for the builtin `panic(message)`"
+                        .to_string(),
+                );
+            }
             if self.functions.contains_key(&f.name) {
-                self.error(format!("Duplicate function declaration `{}`", f.name));
+                let occurrence = program.functions[..idx]
+                    .iter()
+                    .filter(|prev| prev.name == f.name)
+                    .count();
+                self.duplicate_declaration_error(
+                    format!("Duplicate function declaration `{}`", f.name),
+                    "fn",
+                    &f.name,
+                    occurrence,
+                );
                 self.pop_fallback_span();
                 continue;
             }
             let params = f
                 .params
                 .iter()
-                .map(|p| TypeInfo::from_ast(&p.ty))
+                .map(|p| self.declared_type(&p.ty))
                 .collect::<Vec<_>>();
             let ret = f
                 .return_type
                 .as_ref()
-                .map(TypeInfo::from_ast)
+                .map(|t| self.declared_type(t))
                 .unwrap_or(TypeInfo::Void);
             self.functions.insert(
                 f.name.clone(),
@@ -354,6 +662,7 @@ impl Checker {
         }
 
         self.check_global_declarations(program);
+        self.check_global_init_order(program);
         self.check_export_declarations(program);
 
         for f in &program.functions {
@@ -370,18 +679,111 @@ impl Checker {
         for operator in &program.operators {
             self.check_operator(operator);
         }
+        self.check_recursive_structs(program);
+    }
+
+    /// Reports structs that contain themselves by value, which would make them
+    /// infinitely large. Recursion has to pass through `Option`, `Result`, `Vec`
+    /// or `Map`, whose payloads are not stored inline. Runs last so that generic
+    /// instantiations made while checking bodies are covered too.
+    fn check_recursive_structs(&mut self, program: &Program) {
+        let mut roots = program
+            .structs
+            .iter()
+            .filter(|s| s.type_params.is_empty())
+            .map(|s| s.name.clone())
+            .collect::<Vec<_>>();
+        let mut instances = self
+            .struct_fields
+            .keys()
+            .filter(|name| {
+                name.split_once('[')
+                    .is_some_and(|(base, _)| self.generic_structs.contains_key(base))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        instances.sort();
+        roots.extend(instances);
+
+        let mut reported = HashSet::new();
+        for root in roots {
+            if reported.contains(&root) {
+                continue;
+            }
+            let mut path = Vec::new();
+            if !self.find_inline_struct_path(&root, &root, &mut HashSet::new(), &mut path) {
+                continue;
+            }
+            reported.extend(path.iter().map(|(strukt, _)| strukt.clone()));
+            let chain = path
+                .iter()
+                .map(|(strukt, field)| format!("{strukt}.{field}"))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            let base = root.split('[').next().unwrap_or(&root);
+            self.push_fallback_span(self.declaration_span("struct", base));
+            self.error("E-SEMA-025", format!(
+                "Struct `{root}` contains itself without indirection through `{chain}`; wrap the field in `Option[...]` or `Vec[...]`"
+            ));
+            self.pop_fallback_span();
+        }
+    }
+
+    /// Finds fields leading from `current` back to `root` through values stored
+    /// inline, recording each `(struct, field)` step in `path`.
+    fn find_inline_struct_path(
+        &self,
+        current: &str,
+        root: &str,
+        visited: &mut HashSet<String>,
+        path: &mut Vec<(String, String)>,
+    ) -> bool {
+        let Some(fields) = self.struct_fields.get(current) else {
+            return false;
+        };
+        let mut fields = fields.iter().collect::<Vec<_>>();
+        fields.sort_by(|a, b| a.0.cmp(b.0));
+        for (field, ty) in fields {
+            let mut inline = Vec::new();
+            Self::inline_struct_names(ty, &mut inline);
+            for next in inline {
+                path.push((current.to_string(), field.clone()));
+                if next == root
+                    || (visited.insert(next.clone())
+                        && self.find_inline_struct_path(&next, root, visited, path))
+                {
+                    return true;
+                }
+                path.pop();
+            }
+        }
+        false
+    }
+
+    /// Struct names whose values `ty` stores inline rather than behind a pointer.
+    fn inline_struct_names(ty: &TypeInfo, out: &mut Vec<String>) {
+        match ty {
+            TypeInfo::Named(name) => out.push(name.clone()),
+            TypeInfo::Array { elem, .. } => Self::inline_struct_names(elem, out),
+            TypeInfo::Tuple(items) => {
+                for item in items {
+                    Self::inline_struct_names(item, out);
+                }
+            }
+            _ => {}
+        }
     }
 
     fn check_extern_function_signature(&mut self, f: &crate::ast::FnDecl) {
         let params = f
             .params
             .iter()
-            .map(|p| TypeInfo::from_ast(&p.ty))
+            .map(|p| self.declared_type(&p.ty))
             .collect::<Vec<_>>();
         let ret = f
             .return_type
             .as_ref()
-            .map(TypeInfo::from_ast)
+            .map(|t| self.declared_type(t))
             .unwrap_or(TypeInfo::Void);
 
         let supported = (matches!(ret, TypeInfo::Int)
@@ -395,13 +797,18 @@ impl Checker {
                     | [TypeInfo::String, TypeInfo::String]
                     | [TypeInfo::String, TypeInfo::Int]
             ))
+            || (matches!(ret, TypeInfo::Float)
+                && matches!(
+                    params.as_slice(),
+                    [TypeInfo::Float] | [TypeInfo::Float, TypeInfo::Float]
+                ))
             || (matches!(ret, TypeInfo::Bool) && matches!(params.as_slice(), [] | [TypeInfo::Int]))
             || (matches!(ret, TypeInfo::Void)
                 && matches!(params.as_slice(), [] | [TypeInfo::String] | [TypeInfo::Int]));
 
         if !supported {
-            self.error(format!(
-                "Extern function `{}` uses unsupported signature; supported forms are `extern fn() -> Int`, `extern fn() -> Bool`, `extern fn() -> Void`, `extern fn(Int) -> Int`, `extern fn(Int) -> Bool`, `extern fn(Int) -> Void`, `extern fn(String) -> Int`, `extern fn(Bytes) -> Int`, `extern fn(Int, Int) -> Int`, `extern fn(Bytes, Int) -> Int`, `extern fn(String, String) -> Int`, `extern fn(String, Int) -> Int`, and `extern fn(String) -> Void`",
+            self.error("E-SEMA-017", format!(
+                "Extern function `{}` uses unsupported signature; supported forms are `extern fn() -> Int`, `extern fn() -> Bool`, `extern fn() -> Void`, `extern fn(Int) -> Int`, `extern fn(Int) -> Bool`, `extern fn(Int) -> Void`, `extern fn(String) -> Int`, `extern fn(Bytes) -> Int`, `extern fn(Int, Int) -> Int`, `extern fn(Bytes, Int) -> Int`, `extern fn(String, String) -> Int`, `extern fn(String, Int) -> Int`, `extern fn(Float) -> Float`, `extern fn(Float, Float) -> Float`, and `extern fn(String) -> Void`",
                 f.name
             ));
         }
@@ -411,24 +818,30 @@ impl Checker {
         if self.functions.contains_key(&operator.name)
             || self.operators.contains_key(&operator.name)
         {
-            self.error(format!(
-                "Duplicate operator declaration `{}`",
-                operator.name
-            ));
+            self.error(
+                "E-SEMA-003",
+                format!("Duplicate operator declaration `{}`", operator.name),
+            );
             return;
         }
         if operator.params.len() != 2 {
-            self.error(format!(
-                "Operator `{}` must declare exactly 2 parameters",
-                operator.name
-            ));
+            self.error(
+                "E-SEMA-013",
+                format!(
+                    "Operator `{}` must declare exactly 2 parameters",
+                    operator.name
+                ),
+            );
             return;
         }
         if operator.precedence < 0 {
-            self.error(format!(
-                "Operator `{}` precedence must be non-negative",
-                operator.name
-            ));
+            self.error(
+                "E-SEMA-010",
+                format!(
+                    "Operator `{}` precedence must be non-negative",
+                    operator.name
+                ),
+            );
         }
         for param in &operator.params {
             self.check_decl_type_exists(
@@ -450,9 +863,9 @@ impl Checker {
                 params: operator
                     .params
                     .iter()
-                    .map(|p| TypeInfo::from_ast(&p.ty))
+                    .map(|p| self.declared_type(&p.ty))
                     .collect(),
-                ret: TypeInfo::from_ast(&operator.return_type),
+                ret: self.declared_type(&operator.return_type),
             },
         );
     }
@@ -463,10 +876,10 @@ impl Checker {
         for g in &program.globals {
             self.push_fallback_span(self.declaration_span("let", &g.name));
             if scope.contains_key(&g.name) {
-                self.error(format!(
-                    "Duplicate global variable declaration `{}`",
-                    g.name
-                ));
+                self.error(
+                    "E-SEMA-003",
+                    format!("Duplicate global variable declaration `{}`", g.name),
+                );
                 self.pop_fallback_span();
                 continue;
             }
@@ -477,19 +890,19 @@ impl Checker {
                 );
             }
             let expr_ty = self.check_expr(&g.value, &mut scopes);
-            let declared_ty = g.ty.as_ref().map(TypeInfo::from_ast);
+            let declared_ty = g.ty.as_ref().map(|t| self.declared_type(t));
             let final_ty = match declared_ty {
                 Some(declared) => {
                     if Checker::is_vec_new_call(&g.value) {
-                        if !matches!(declared, TypeInfo::Vec { .. }) {
-                            self.error(format!(
+                        if !matches!(declared, TypeInfo::Vec { .. } | TypeInfo::Unknown) {
+                            self.error("E-SEMA-010", format!(
                                 "Type mismatch in global let `{}`: declared {:?}, got vec.new()",
                                 g.name, declared
                             ));
                         }
                     } else if Checker::is_map_new_call(&g.value) {
-                        if !matches!(declared, TypeInfo::Map { .. }) {
-                            self.error(format!(
+                        if !matches!(declared, TypeInfo::Map { .. } | TypeInfo::Unknown) {
+                            self.error("E-SEMA-010", format!(
                                 "Type mismatch in global let `{}`: declared {:?}, got map.new()",
                                 g.name, declared
                             ));
@@ -499,35 +912,39 @@ impl Checker {
                             &declared,
                             TypeInfo::Opaque(name)
                                 if crate::types::task_channel_value_type(name).is_some()
-                        ) {
-                            self.error(format!(
+                        ) && declared != TypeInfo::Unknown
+                        {
+                            self.error("E-SEMA-010", format!(
                                 "Type mismatch in global let `{}`: declared {:?}, got task.channel()",
                                 g.name, declared
                             ));
                         }
                     } else if !Self::types_compatible(&expr_ty, &declared) {
-                        self.error(format!(
-                            "Type mismatch in global let `{}`: declared {:?}, got {:?}",
-                            g.name, declared, expr_ty
-                        ));
+                        self.error(
+                            "E-SEMA-010",
+                            format!(
+                                "Type mismatch in global let `{}`: declared {:?}, got {:?}",
+                                g.name, declared, expr_ty
+                            ),
+                        );
                     }
                     declared
                 }
                 None => {
                     if Checker::is_vec_new_call(&g.value) {
-                        self.error(format!(
+                        self.error("E-SEMA-009", format!(
                             "Cannot infer vector element type for global let `{}`; annotate as `Vec[T]`",
                             g.name
                         ));
                         TypeInfo::Unknown
                     } else if Checker::is_map_new_call(&g.value) {
-                        self.error(format!(
+                        self.error("E-SEMA-009", format!(
                             "Cannot infer map value type for global let `{}`; annotate as `Map[String, T]`",
                             g.name
                         ));
                         TypeInfo::Unknown
                     } else if Checker::is_task_channel_call(&g.value) {
-                        self.error(format!(
+                        self.error("E-SEMA-009", format!(
                             "Cannot infer channel value type for global let `{}`; annotate as `task.Channel[T]`",
                             g.name
                         ));
@@ -553,6 +970,9 @@ impl Checker {
         for s in &program.structs {
             local_exportables.insert(s.name.as_str());
         }
+        for alias in &program.type_aliases {
+            local_exportables.insert(alias.name.as_str());
+        }
         for g in &program.globals {
             local_exportables.insert(g.name.as_str());
             global_annotations.insert(g.name.as_str(), g.ty.is_some());
@@ -570,8 +990,22 @@ impl Checker {
                         if matches!(export_decl, crate::ast::ExportDecl::Local { .. })
                             && !local_exportables.contains(item.name.as_str())
                         {
-                            self.error(format!(
-                                "Exported name `{}` does not exist in this module",
+                            self.error(
+                                "E-SEMA-015",
+                                format!(
+                                    "Exported name `{}` does not exist in this module",
+                                    item.name
+                                ),
+                            );
+                        }
+                        if matches!(export_decl, crate::ast::ExportDecl::Local { .. })
+                            && program
+                                .structs
+                                .iter()
+                                .any(|s| s.name == item.name && !s.type_params.is_empty())
+                        {
+                            self.error("E-SEMA-015", format!(
+                                "Generic struct `{}` cannot be exported; use it inside its module",
                                 item.name
                             ));
                         }
@@ -580,14 +1014,29 @@ impl Checker {
                                 .get(item.name.as_str())
                                 .is_some_and(|has_annotation| !has_annotation)
                         {
-                            self.error(format!(
-                                "Exported global `{}` must declare an explicit type annotation",
+                            self.error(
+                                "E-SEMA-015",
+                                format!(
+                                    "Exported global `{}` must declare an explicit type annotation",
+                                    item.name
+                                ),
+                            );
+                        }
+                        if item.opaque
+                            && (!matches!(export_decl, crate::ast::ExportDecl::Local { .. })
+                                || !program.structs.iter().any(|s| s.name == item.name))
+                        {
+                            self.error("E-SEMA-015", format!(
+                                "`(opaque)` on `{}` is only allowed when exporting a struct declared in this module",
                                 item.name
                             ));
                         }
                         let target = item.alias.as_deref().unwrap_or(item.name.as_str());
                         if !seen_targets.insert(target.to_string()) {
-                            self.error(format!("Duplicate exported target name `{target}`"));
+                            self.error(
+                                "E-SEMA-003",
+                                format!("Duplicate exported target name `{target}`"),
+                            );
                         }
                     }
                 }
@@ -597,32 +1046,167 @@ impl Checker {
     }
 
     fn check_struct_declarations(&mut self, program: &Program) {
-        for s in &program.structs {
+        for (idx, s) in program.structs.iter().enumerate() {
             self.push_fallback_span(self.declaration_span("struct", &s.name));
             if !self.struct_names.insert(s.name.clone()) {
-                self.error(format!("Duplicate struct declaration `{}`", s.name));
+                let occurrence = program.structs[..idx]
+                    .iter()
+                    .filter(|prev| prev.name == s.name)
+                    .count();
+                self.duplicate_declaration_error(
+                    format!("Duplicate struct declaration `{}`", s.name),
+                    "struct",
+                    &s.name,
+                    occurrence,
+                );
             }
             self.pop_fallback_span();
         }
 
+        for s in &program.structs {
+            if !s.type_params.is_empty() {
+                self.generic_structs
+                    .entry(s.name.clone())
+                    .or_insert_with(|| s.clone());
+            }
+        }
+
         for s in &program.structs {
             self.push_fallback_span(self.declaration_span("struct", &s.name));
+            let mut seen_params = HashSet::new();
+            for param in &s.type_params {
+                if !seen_params.insert(param.as_str()) {
+                    self.error(
+                        "E-SEMA-003",
+                        format!("Duplicate type parameter `{param}` in struct `{}`", s.name),
+                    );
+                }
+            }
+            self.generic_params = s.type_params.clone();
             let mut seen_fields = HashSet::new();
             let mut field_types = HashMap::new();
             for field in &s.fields {
                 if !seen_fields.insert(field.name.clone()) {
-                    self.error(format!(
-                        "Duplicate field `{}` in struct `{}`",
-                        field.name, s.name
-                    ));
+                    self.error(
+                        "E-SEMA-003",
+                        format!("Duplicate field `{}` in struct `{}`", field.name, s.name),
+                    );
                 }
                 self.check_decl_type_exists(
                     &field.ty,
                     format!("Unknown type in struct `{}` field `{}`", s.name, field.name),
                 );
-                field_types.insert(field.name.clone(), TypeInfo::from_ast(&field.ty));
+                field_types.insert(field.name.clone(), self.declared_type(&field.ty));
+            }
+            self.generic_params.clear();
+            if s.type_params.is_empty() {
+                self.struct_fields.insert(s.name.clone(), field_types);
             }
-            self.struct_fields.insert(s.name.clone(), field_types);
+            self.pop_fallback_span();
+        }
+    }
+
+    /// Registers the shape of `name[args]`, substituting `args` into the generic
+    /// struct's fields the first time the instantiation is seen. Returns the
+    /// instantiation's struct name, or `None` after reporting why it is invalid.
+    fn instantiate_generic_struct(
+        &mut self,
+        name: &str,
+        args: &[TypeName],
+        err_prefix: &str,
+    ) -> Option<String> {
+        const MAX_GENERIC_DEPTH: usize = 32;
+        let Some(template) = self.generic_structs.get(name).cloned() else {
+            if self.resolve_named_type_name(name).is_some() {
+                self.error("E-SEMA-024", format!("Struct `{name}` is not generic"));
+            } else {
+                self.error("E-SEMA-001", format!("{err_prefix}: `{name}`"));
+            }
+            return None;
+        };
+        if template.type_params.len() != args.len() {
+            self.error(
+                "E-SEMA-004",
+                format!(
+                    "Generic struct `{name}` expects {} type argument(s), got {}",
+                    template.type_params.len(),
+                    args.len()
+                ),
+            );
+            return None;
+        }
+        // Inside a generic struct the arguments may be its own type parameters;
+        // only concrete instantiations get a shape.
+        if !self.generic_params.is_empty() || !args.iter().all(|a| self.type_name_resolves(a)) {
+            return None;
+        }
+        let arg_types = args.iter().map(TypeInfo::from_ast).collect::<Vec<_>>();
+        let instance = generic_struct_name(name, &arg_types);
+        if self.struct_fields.contains_key(&instance) {
+            return Some(instance);
+        }
+        if self.generic_depth >= MAX_GENERIC_DEPTH {
+            self.error(
+                "E-SEMA-024",
+                format!("Generic struct `{name}` instantiates itself without end"),
+            );
+            return None;
+        }
+        // Register the name first so fields that mention `instance` again resolve.
+        self.struct_names.insert(instance.clone());
+        self.struct_fields.insert(instance.clone(), HashMap::new());
+        self.generic_depth += 1;
+        let mut field_types = HashMap::new();
+        for field in &template.fields {
+            let mut ty = field.ty.clone();
+            ty.replace_named(&mut |param| {
+                template
+                    .type_params
+                    .iter()
+                    .position(|p| p == param)
+                    .map(|idx| args[idx].clone())
+            });
+            self.check_decl_type_exists(
+                &ty,
+                format!("Unknown type in struct `{instance}` field `{}`", field.name),
+            );
+            field_types.insert(field.name.clone(), self.declared_type(&ty));
+        }
+        self.generic_depth -= 1;
+        self.struct_fields.insert(instance.clone(), field_types);
+        Some(instance)
+    }
+
+    /// Alias uses are already expanded by the parser; this checks the declarations.
+    fn check_type_alias_declarations(&mut self, program: &Program) {
+        let mut seen = HashSet::new();
+        for (idx, alias) in program.type_aliases.iter().enumerate() {
+            self.push_fallback_span(self.declaration_span("type", &alias.name));
+            if !seen.insert(alias.name.as_str()) {
+                let occurrence = program.type_aliases[..idx]
+                    .iter()
+                    .filter(|prev| prev.name == alias.name)
+                    .count();
+                self.duplicate_declaration_error(
+                    format!("Duplicate type alias `{}`", alias.name),
+                    "type",
+                    &alias.name,
+                    occurrence,
+                );
+            }
+            if program.structs.iter().any(|s| s.name == alias.name) {
+                self.error(
+                    "E-SEMA-003",
+                    format!(
+                        "Type alias `{}` conflicts with struct `{}`",
+                        alias.name, alias.name
+                    ),
+                );
+            }
+            self.check_decl_type_exists(
+                &alias.target,
+                format!("Unknown type in type alias `{}`", alias.name),
+            );
             self.pop_fallback_span();
         }
     }
@@ -632,33 +1216,53 @@ impl Checker {
         for imp in &program.impls {
             self.push_fallback_span(self.declaration_span("impl", &imp.target));
             if !self.struct_names.contains(&imp.target) {
-                self.error(format!("Unknown impl target struct `{}`", imp.target));
+                self.error(
+                    "E-SEMA-001",
+                    format!("Unknown impl target struct `{}`", imp.target),
+                );
+            } else if self.generic_structs.contains_key(&imp.target) {
+                self.error(
+                    "E-SEMA-024",
+                    format!(
+                        "Cannot implement methods on generic struct `{}`",
+                        imp.target
+                    ),
+                );
             }
 
             let seen_methods = global_seen_methods.entry(imp.target.clone()).or_default();
             for method in &imp.methods {
                 self.push_fallback_span(self.declaration_span("fn", &method.name));
                 if !seen_methods.insert(method.name.clone()) {
-                    self.error(format!(
-                        "Duplicate method `{}` in impl `{}`",
-                        method.name, imp.target
-                    ));
+                    self.error(
+                        "E-SEMA-003",
+                        format!(
+                            "Duplicate method `{}` in impl `{}`",
+                            method.name, imp.target
+                        ),
+                    );
                 }
 
                 if method.params.is_empty() {
-                    self.error(format!(
-                        "Method `{}.{}` must declare `self` as first parameter",
-                        imp.target, method.name
-                    ));
+                    self.error(
+                        "E-SEMA-013",
+                        format!(
+                            "Method `{}.{}` must declare `self` as first parameter",
+                            imp.target, method.name
+                        ),
+                    );
                 } else {
                     let first = &method.params[0];
                     let expected_self_ty = TypeInfo::Named(imp.target.clone());
                     let actual_self_ty = TypeInfo::from_ast(&first.ty);
                     if first.name != "self" || actual_self_ty != expected_self_ty {
-                        self.error(format!(
-                            "Method `{}.{}` must declare `self: {}` as first parameter",
-                            imp.target, method.name, imp.target
-                        ));
+                        self.error(
+                            "E-SEMA-013",
+                            format!(
+                                "Method `{}.{}` must declare `self: {}` as first parameter",
+                                imp.target, method.name, imp.target
+                            ),
+                        );
                     }
                 }
 
@@ -677,6 +1281,18 @@ impl Checker {
                         format!("Unknown return type in method `{}`", method.name),
                     );
                 }
+                if method.name == "toString" {
+                    let returns_string = method
+                        .return_type
+                        .as_ref()
+                        .is_some_and(|ret| self.declared_type(ret) == TypeInfo::String);
+                    if method.params.len() != 1 || !returns_string {
+                        self.error("E-SEMA-010", format!(
+                            "Method `{}.toString` is used to display values and must be declared as `fn toString(self) -> String`",
+                            imp.target
+                        ));
+                    }
+                }
                 self.pop_fallback_span();
             }
             self.pop_fallback_span();
@@ -685,18 +1301,18 @@ impl Checker {
 
     fn collect_method_signatures(&mut self, program: &Program) {
         for imp in &program.impls {
-            let methods = self.methods.entry(imp.target.clone()).or_default();
             for method in &imp.methods {
                 let params = method
                     .params
                     .iter()
-                    .map(|p| TypeInfo::from_ast(&p.ty))
+                    .map(|p| self.declared_type(&p.ty))
                     .collect::<Vec<_>>();
                 let ret = method
                     .return_type
                     .as_ref()
-                    .map(TypeInfo::from_ast)
+                    .map(|t| self.declared_type(t))
                     .unwrap_or(TypeInfo::Void);
+                let methods = self.methods.entry(imp.target.clone()).or_default();
                 methods.entry(method.name.clone()).or_insert(FunctionSig {
                     name: method.name.clone(),
                     params,
@@ -711,6 +1327,7 @@ impl Checker {
             TypeName::Int
             | TypeName::Float
             | TypeName::Bool
+            | TypeName::Char
             | TypeName::String
             | TypeName::Bytes
             | TypeName::Void => {}
@@ -728,11 +1345,69 @@ impl Checker {
                 }
                 self.check_decl_type_exists(ret, err_prefix);
             }
-            TypeName::Named(name) => {
-                if self.resolve_named_type_name(name).is_none() {
-                    self.error(format!("{err_prefix}: `{name}`"));
+            TypeName::Tuple(items) => {
+                for item in items {
+                    self.check_decl_type_exists(item, err_prefix.clone());
+                }
+            }
+            TypeName::Named(name) => match self.resolve_named_type_name(name) {
+                Some(resolved) if self.generic_structs.contains_key(&resolved) => {
+                    self.error(
+                        "E-SEMA-024",
+                        format!(
+                            "Generic struct `{name}` needs type arguments, as in `{name}[...]`"
+                        ),
+                    );
                 }
+                Some(_) => {}
+                None => self.error("E-SEMA-001", format!("{err_prefix}: `{name}`")),
+            },
+            TypeName::Generic { name, args } => {
+                for arg in args {
+                    self.check_decl_type_exists(arg, err_prefix.clone());
+                }
+                self.instantiate_generic_struct(name, args, &err_prefix);
+            }
+        }
+    }
+
+    /// The type a binding declared as `ty` gets in scope. Annotations naming an
+    /// unknown type have already been reported, so they become `Unknown` instead
+    /// of producing follow-up mismatches at every use.
+    pub(super) fn declared_type(&self, ty: &TypeName) -> TypeInfo {
+        if self.type_name_resolves(ty) {
+            TypeInfo::from_ast(ty)
+        } else {
+            TypeInfo::Unknown
+        }
+    }
+
+    fn type_name_resolves(&self, ty: &TypeName) -> bool {
+        match ty {
+            TypeName::Int
+            | TypeName::Float
+            | TypeName::Bool
+            | TypeName::Char
+            | TypeName::String
+            | TypeName::Bytes
+            | TypeName::Void => true,
+            TypeName::Option { value } => self.type_name_resolves(value),
+            TypeName::Result { ok, err } => {
+                self.type_name_resolves(ok) && self.type_name_resolves(err)
             }
+            TypeName::Array { elem, .. } | TypeName::Vec { elem } => self.type_name_resolves(elem),
+            TypeName::Map { value } => self.type_name_resolves(value),
+            TypeName::Fn { params, ret } => {
+                params.iter().all(|p| self.type_name_resolves(p)) && self.type_name_resolves(ret)
+            }
+            TypeName::Tuple(items) => items.iter().all(|item| self.type_name_resolves(item)),
+            TypeName::Named(name) => self
+                .resolve_named_type_name(name)
+                .is_some_and(|resolved| !self.generic_structs.contains_key(&resolved)),
+            TypeName::Generic { .. } => match TypeInfo::from_ast(ty) {
+                TypeInfo::Named(instance) => self.struct_fields.contains_key(&instance),
+                _ => false,
+            },
         }
     }
 
@@ -743,6 +1418,76 @@ impl Checker {
             .cloned()
     }
 
+    /// Reports an error when `struct_name` is an imported opaque struct, whose
+    /// literal construction and field access are private to its defining module.
+    pub(super) fn check_opaque_struct_access(&mut self, struct_name: &str, action: &str) -> bool {
+        let Some(module) = self.opaque_structs.get(struct_name) else {
+            return true;
+        };
+        let msg = format!(
+            "Cannot {action} struct `{struct_name}` outside module `{module}`: it is exported as opaque"
+        );
+        self.error("E-SEMA-008", msg);
+        false
+    }
+
+    /// The candidate `name` most likely misspells, for a `did you mean` hint.
+    /// One- and two-letter names are never "corrected" into unrelated names.
+    pub(super) fn did_you_mean<'a>(
+        name: &str,
+        candidates: impl Iterator<Item = &'a str>,
+    ) -> Option<String> {
+        let candidates = candidates.filter(|c| *c != name);
+        crate::resolver::suggest_name(name, candidates)
+            .filter(|s| crate::resolver::levenshtein(name, s) < name.chars().count())
+    }
+
+    pub(super) fn field_suggestion(&self, struct_name: &str, field: &str) -> Option<String> {
+        let fields = self.struct_fields.get(struct_name);
+        Self::did_you_mean(
+            field,
+            fields
+                .into_iter()
+                .flat_map(|f| f.keys())
+                .map(String::as_str),
+        )
+    }
+
+    pub(super) fn method_suggestion(&self, struct_name: &str, method: &str) -> Option<String> {
+        let methods = self.methods.get(struct_name);
+        Self::did_you_mean(
+            method,
+            methods
+                .into_iter()
+                .flat_map(|m| m.keys())
+                .map(String::as_str),
+        )
+    }
+
+    pub(super) fn builtin_suggestion(package: &str, method: &str) -> Option<String> {
+        let names = crate::builtins::all_builtin_specs()
+            .filter(|spec| spec.sig.package == package)
+            .map(|spec| spec.sig.name);
+        Self::did_you_mean(method, names).map(|s| format!("{package}.{s}"))
+    }
+
+    /// Names a bare identifier could have meant: locals visible from here,
+    /// functions, and globals.
+    pub(super) fn name_suggestion(
+        &self,
+        name: &str,
+        scopes: &[HashMap<String, TypeInfo>],
+    ) -> Option<String> {
+        let floor = self.fn_lit_scope_floors.last().copied().unwrap_or(0);
+        let locals = scopes.iter().skip(floor).flat_map(|scope| scope.keys());
+        let candidates = locals
+            .chain(self.functions.keys())
+            .chain(self.globals.keys())
+            .chain(self.direct_imports.keys())
+            .map(String::as_str);
+        Self::did_you_mean(name, candidates)
+    }
+
     pub(super) fn method_sig(&self, struct_name: &str, method: &str) -> Option<FunctionSig> {
         self.methods
             .get(struct_name)
@@ -752,20 +1497,25 @@ impl Checker {
 
     fn check_function(&mut self, f: &crate::ast::FnDecl) {
         self.push_fallback_span(self.declaration_span("fn", &f.name));
+        self.current_decl = format!("fn {}", f.name);
+        self.binding_spans.clear();
         let expected_ret = f
             .return_type
             .as_ref()
-            .map(TypeInfo::from_ast)
+            .map(|t| self.declared_type(t))
             .unwrap_or(TypeInfo::Void);
         let mut scopes = vec![HashMap::<String, TypeInfo>::new()];
         for p in &f.params {
             if scopes[0].contains_key(&p.name) {
-                self.error(format!(
-                    "Duplicate parameter `{}` in function `{}`",
-                    p.name, f.name
-                ));
+                self.error(
+                    "E-SEMA-003",
+                    format!("Duplicate parameter `{}` in function `{}`", p.name, f.name),
+                );
             } else {
-                scopes[0].insert(p.name.clone(), TypeInfo::from_ast(&p.ty));
+                let ty = self.declared_type(&p.ty);
+                self.note_binding(&p.name, &ty);
+                scopes[0].insert(p.name.clone(), ty);
+                self.note_binding_span(0, &p.name, p.span);
             }
         }
 
@@ -774,35 +1524,49 @@ impl Checker {
             self.check_stmt(stmt, &mut scopes, &expected_ret);
         }
         self.return_types.pop();
+        self.check_reachability(&f.body);
         if expected_ret != TypeInfo::Void && !Self::block_must_return(&f.body) {
-            self.error(format!(
-                "Function `{}` may exit without returning {:?}",
-                f.name, expected_ret
-            ));
+            self.error(
+                "E-SEMA-005",
+                format!(
+                    "Function `{}` may exit without returning {:?}",
+                    f.name, expected_ret
+                ),
+            );
         }
         self.pop_fallback_span();
     }
 
     fn check_method(&mut self, target: &str, m: &crate::ast::MethodDecl) {
         self.push_fallback_span(self.declaration_span("fn", &m.name));
+        self.current_decl = format!("method {target}.{}", m.name);
+        self.binding_spans.clear();
         let expected_ret = m
             .return_type
             .as_ref()
-            .map(TypeInfo::from_ast)
+            .map(|t| self.declared_type(t))
             .unwrap_or(TypeInfo::Void);
         let mut scopes = vec![HashMap::<String, TypeInfo>::new()];
         for p in &m.params {
             if scopes[0].contains_key(&p.name) {
-                self.error(format!(
-                    "Duplicate parameter `{}` in method `{}.{}`",
-                    p.name, target, m.name
-                ));
+                self.error(
+                    "E-SEMA-003",
+                    format!(
+                        "Duplicate parameter `{}` in method `{}.{}`",
+                        p.name, target, m.name
+                    ),
+                );
             } else {
-                scopes[0].insert(p.name.clone(), TypeInfo::from_ast(&p.ty));
+                let ty = self.declared_type(&p.ty);
+                self.note_binding(&p.name, &ty);
+                scopes[0].insert(p.name.clone(), ty);
+                self.note_binding_span(0, &p.name, p.span);
             }
         }
         if !scopes[0].contains_key("self") {
-            scopes[0].insert("self".to_string(), TypeInfo::Named(target.to_string()));
+            let ty = TypeInfo::Named(target.to_string());
+            self.note_binding("self", &ty);
+            scopes[0].insert("self".to_string(), ty);
         }
 
         self.return_types.push(expected_ret.clone());
@@ -810,27 +1574,39 @@ impl Checker {
             self.check_stmt(stmt, &mut scopes, &expected_ret);
         }
         self.return_types.pop();
+        self.check_reachability(&m.body);
         if expected_ret != TypeInfo::Void && !Self::block_must_return(&m.body) {
-            self.error(format!(
-                "Method `{}.{}` may exit without returning {:?}",
-                target, m.name, expected_ret
-            ));
+            self.error(
+                "E-SEMA-005",
+                format!(
+                    "Method `{}.{}` may exit without returning {:?}",
+                    target, m.name, expected_ret
+                ),
+            );
         }
         self.pop_fallback_span();
     }
 
     fn check_operator(&mut self, operator: &OperatorDecl) {
         self.push_fallback_span(self.declaration_span("opr", &operator.name));
-        let expected_ret = TypeInfo::from_ast(&operator.return_type);
+        self.current_decl = format!("opr {}", operator.name);
+        self.binding_spans.clear();
+        let expected_ret = self.declared_type(&operator.return_type);
         let mut scopes = vec![HashMap::<String, TypeInfo>::new()];
         for p in &operator.params {
             if scopes[0].contains_key(&p.name) {
-                self.error(format!(
-                    "Duplicate parameter `{}` in operator `{}`",
-                    p.name, operator.name
-                ));
+                self.error(
+                    "E-SEMA-003",
+                    format!(
+                        "Duplicate parameter `{}` in operator `{}`",
+                        p.name, operator.name
+                    ),
+                );
             } else {
-                scopes[0].insert(p.name.clone(), TypeInfo::from_ast(&p.ty));
+                let ty = self.declared_type(&p.ty);
+                self.note_binding(&p.name, &ty);
+                scopes[0].insert(p.name.clone(), ty);
+                self.note_binding_span(0, &p.name, p.span);
             }
         }
         self.return_types.push(expected_ret.clone());
@@ -838,17 +1614,25 @@ impl Checker {
             self.check_stmt(stmt, &mut scopes, &expected_ret);
         }
         self.return_types.pop();
+        self.check_reachability(&operator.body);
         if !Self::block_must_return(&operator.body) {
-            self.error(format!(
-                "Operator `{}` may exit without returning {:?}",
-                operator.name, expected_ret
-            ));
+            self.error(
+                "E-SEMA-005",
+                format!(
+                    "Operator `{}` may exit without returning {:?}",
+                    operator.name, expected_ret
+                ),
+            );
         }
         self.pop_fallback_span();
     }
 
     fn block_must_return(stmts: &[Stmt]) -> bool {
         for stmt in stmts {
+            if matches!(&stmt.kind, StmtKind::Break | StmtKind::Continue) {
+                // Control leaves the block here, so later statements never run.
+                return false;
+            }
             if Self::stmt_must_return(stmt) {
                 return true;
             }
@@ -857,9 +1641,10 @@ impl Checker {
     }
 
     fn stmt_must_return(stmt: &Stmt) -> bool {
-        match stmt {
-            Stmt::Return(_) => true,
-            Stmt::If {
+        match &stmt.kind {
+            StmtKind::Return(_) => true,
+            StmtKind::Expr(expr) => expr.is_diverging_call(),
+            StmtKind::If {
                 then_body,
                 else_body,
                 ..
@@ -868,9 +1653,10 @@ impl Checker {
                     && Self::block_must_return(then_body)
                     && Self::block_must_return(else_body)
             }
-            Stmt::Match { arms, .. } => {
+            StmtKind::Match { arms, .. } => {
                 !arms.is_empty() && arms.iter().all(|arm| Self::block_must_return(&arm.body))
             }
+            StmtKind::While { .. } | StmtKind::For { .. } => stmt.is_infinite_loop(),
             _ => false,
         }
     }
@@ -895,17 +1681,51 @@ impl Checker {
             return ty.clone();
         }
         if !self.fn_lit_scope_floors.is_empty() {
-            self.error(format!(
-                "Function literals cannot capture outer variable `{name}`"
-            ));
+            self.error(
+                "E-SEMA-016",
+                format!("Function literals cannot capture outer variable `{name}`"),
+            );
             return TypeInfo::Unknown;
         }
-        self.error(format!("Unknown variable `{name}`"));
+        let suggestion = self.name_suggestion(name, scopes);
+        self.error_with_suggestion(
+            "E-SEMA-001",
+            format!("Unknown variable `{name}`"),
+            suggestion,
+        );
         TypeInfo::Unknown
     }
 
-    fn error(&mut self, message: String) {
-        self.diagnostics
-            .error(message, self.current_fallback_span());
+    /// Reports `message` under `code`, one of the sema entries in
+    /// [`crate::error_codes::ERROR_CODES`].
+    fn error(&mut self, code: &'static str, message: String) {
+        self.diagnostics.push(
+            Diagnostic::error(message, self.current_fallback_span())
+                .with_code(code)
+                .approximate(),
+        );
+    }
+
+    /// Like [`Checker::error`], ending the message with a `did you mean` hint
+    /// when there is a `suggestion`, which tools also get on the diagnostic.
+    fn error_with_suggestion(
+        &mut self,
+        code: &'static str,
+        message: String,
+        suggestion: Option<String>,
+    ) {
+        let Some(suggestion) = suggestion else {
+            self.error(code, message);
+            return;
+        };
+        self.diagnostics.push(
+            Diagnostic::error(
+                format!("{message}; did you mean `{suggestion}`?"),
+                self.current_fallback_span(),
+            )
+            .with_code(code)
+            .with_suggestion(suggestion)
+            .approximate(),
+        );
     }
 }
@@ -1,8 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use crate::ast::{ImportDecl, Program};
-use crate::diagnostic::DiagnosticBag;
+use crate::builtins::CustomBuiltins;
+use crate::diagnostic::{DiagnosticBag, DiagnosticSink};
+use crate::lint::LintConfig;
 use crate::resolver::{
     ModuleGraph, ModuleId, ResolveError, build_export_maps, resolve_import_module_targets,
     resolve_project,
@@ -16,6 +18,7 @@ pub(super) struct ModuleApi {
     pub functions: HashMap<String, FunctionSig>,
     pub operators: HashMap<String, FunctionSig>,
     pub structs: HashMap<String, HashMap<String, TypeInfo>>,
+    pub opaque_structs: HashSet<String>,
     pub methods: HashMap<String, HashMap<String, FunctionSig>>,
     pub globals: HashMap<String, TypeInfo>,
 }
@@ -25,9 +28,15 @@ pub(super) struct ModuleExternalContext {
     pub imported_functions: HashMap<String, FunctionSig>,
     pub imported_operators: HashMap<String, FunctionSig>,
     pub imported_structs: HashMap<String, HashMap<String, TypeInfo>>,
+    /// Imported struct name -> id of the module that exported it as opaque.
+    pub opaque_structs: HashMap<String, ModuleId>,
     pub imported_methods: HashMap<String, HashMap<String, FunctionSig>>,
     pub imported_globals: HashMap<String, TypeInfo>,
     pub direct_import_targets: HashMap<String, String>,
+    /// Imported `@deprecated` functions and structs, under every name this
+    /// module can call or build them by, with their notes.
+    pub deprecated_functions: HashMap<String, Option<String>>,
+    pub deprecated_structs: HashMap<String, Option<String>>,
 }
 
 pub fn analyze_project_entry(
@@ -44,6 +53,22 @@ pub fn analyze_project_entry_phased(
     analyze_project_graph_phased(&graph)
 }
 
+/// Checks that `main` in the module loaded from `entry` can start the program:
+/// no parameters and an `Int` or `Void` result. An entry without `main` passes;
+/// lowering reports that when it needs one.
+pub fn check_entry_main(graph: &ModuleGraph, entry: &Path) -> DiagnosticBag {
+    let Some(unit) = graph.entry_module(entry) else {
+        return DiagnosticBag::new();
+    };
+    let mut checker = Checker::new(&unit.program, Some(unit.source.as_str()));
+    checker.check_entry_main(&unit.program);
+    let mut diags = DiagnosticBag::new();
+    for d in checker.diagnostics.into_vec() {
+        diags.push(d.with_path(unit.path.clone()));
+    }
+    diags
+}
+
 pub fn analyze_project_graph(
     graph: &ModuleGraph,
 ) -> Result<(SemaResult, DiagnosticBag), Vec<ResolveError>> {
@@ -56,50 +81,105 @@ pub fn analyze_project_graph_phased(
     analyze_project_graph_phased_impl(graph)
 }
 
-fn analyze_project_graph_impl(
+/// Checks every module of `graph`, reporting each module's sema diagnostics to
+/// `sink` as soon as that module is checked. Parse errors never reach this point:
+/// the resolver reports them while building the graph.
+pub fn analyze_project_graph_with_sink(
     graph: &ModuleGraph,
-) -> Result<(SemaResult, DiagnosticBag), Vec<ResolveError>> {
-    let (result, parse_diags, sema_diags) = analyze_project_graph_phased_impl(graph)?;
-    let mut all = DiagnosticBag::new();
-    for d in parse_diags.into_vec() {
-        all.push(d);
-    }
-    for d in sema_diags.into_vec() {
-        all.push(d);
-    }
-    Ok((result, all))
+    sink: &mut dyn DiagnosticSink,
+) -> Result<SemaResult, Vec<ResolveError>> {
+    analyze_project_graph_with_builtins(graph, &CustomBuiltins::default(), sink)
 }
 
-fn analyze_project_graph_phased_impl(
+/// [`analyze_project_graph_with_sink`] with calls into the embedder packages in
+/// `builtins` checked against their signatures. Resolve the graph with
+/// [`crate::resolver::resolve_project_with_builtins`] so those imports are accepted.
+pub fn analyze_project_graph_with_builtins(
     graph: &ModuleGraph,
-) -> Result<(SemaResult, DiagnosticBag, DiagnosticBag), Vec<ResolveError>> {
-    let parse_diags_all = DiagnosticBag::new();
-    let mut sema_diags_all = DiagnosticBag::new();
+    builtins: &CustomBuiltins,
+    sink: &mut dyn DiagnosticSink,
+) -> Result<SemaResult, Vec<ResolveError>> {
+    analyze_project_graph_with_lints(graph, builtins, &LintConfig::default(), sink)
+}
 
+/// [`analyze_project_graph_with_builtins`] with lint levels from `lints`. Each
+/// module's `#allow`/`#warn`/`#deny` directives override `lints` for that module,
+/// and a denied lint counts as an error.
+pub fn analyze_project_graph_with_lints(
+    graph: &ModuleGraph,
+    builtins: &CustomBuiltins,
+    lints: &LintConfig,
+    sink: &mut dyn DiagnosticSink,
+) -> Result<SemaResult, Vec<ResolveError>> {
     let mut module_apis = HashMap::<ModuleId, ModuleApi>::new();
     for (id, unit) in &graph.modules {
         module_apis.insert(id.clone(), build_module_api(&unit.program));
     }
     let export_maps = build_export_maps(graph)?;
 
+    let mut has_errors = false;
     for (id, unit) in &graph.modules {
         let ctx = build_external_context(id, &unit.program, graph, &module_apis, &export_maps);
         let mut checker = Checker::new(&unit.program, Some(unit.source.as_str()));
         checker.apply_external_context(ctx);
+        checker.use_custom_builtins(&unit.program, builtins);
+        checker.use_lints(&unit.program, lints);
         checker.check_program(&unit.program);
-        let path = unit.path.clone();
+        has_errors |= checker.diagnostics.has_errors();
         for d in checker.diagnostics.into_vec() {
-            sema_diags_all.push(d.with_path(path.clone()));
+            sink.report(d.with_path(unit.path.clone()));
         }
     }
+    Ok(SemaResult { has_errors })
+}
+
+fn analyze_project_graph_impl(
+    graph: &ModuleGraph,
+) -> Result<(SemaResult, DiagnosticBag), Vec<ResolveError>> {
+    let (result, parse_diags, sema_diags) = analyze_project_graph_phased_impl(graph)?;
+    let mut all = DiagnosticBag::new();
+    for d in parse_diags.into_vec() {
+        all.push(d);
+    }
+    for d in sema_diags.into_vec() {
+        all.push(d);
+    }
+    Ok((result, all))
+}
 
-    Ok((
-        SemaResult {
-            has_errors: !parse_diags_all.is_empty() || !sema_diags_all.is_empty(),
-        },
-        parse_diags_all,
-        sema_diags_all,
-    ))
+fn analyze_project_graph_phased_impl(
+    graph: &ModuleGraph,
+) -> Result<(SemaResult, DiagnosticBag, DiagnosticBag), Vec<ResolveError>> {
+    let mut sema_diags = DiagnosticBag::new();
+    let result = analyze_project_graph_with_sink(graph, &mut sema_diags)?;
+    Ok((result, DiagnosticBag::new(), sema_diags))
+}
+
+/// Checks one module of `graph` with binding recording enabled, so editor
+/// queries can see the checker's scopes, imports and signatures afterwards.
+pub(super) fn check_module_recording_bindings(
+    graph: &ModuleGraph,
+    module_id: &str,
+) -> Option<Checker> {
+    let unit = graph.modules.get(module_id)?;
+    let mut checker = Checker::new(&unit.program, Some(unit.source.as_str()));
+    if let Ok(export_maps) = build_export_maps(graph) {
+        let module_apis = graph
+            .modules
+            .iter()
+            .map(|(id, unit)| (id.clone(), build_module_api(&unit.program)))
+            .collect::<HashMap<_, _>>();
+        checker.apply_external_context(build_external_context(
+            module_id,
+            &unit.program,
+            graph,
+            &module_apis,
+            &export_maps,
+        ));
+    }
+    checker.recorded_bindings = Some(Vec::new());
+    checker.check_program(&unit.program);
+    Some(checker)
 }
 
 fn build_module_api(program: &Program) -> ModuleApi {
@@ -131,13 +211,20 @@ fn build_module_api(program: &Program) -> ModuleApi {
         api.functions.insert(operator.name.clone(), sig.clone());
         api.operators.insert(operator.name.clone(), sig);
     }
-    for s in &program.structs {
+    for s in program.structs.iter().filter(|s| s.type_params.is_empty()) {
         let mut fields = HashMap::new();
         for fld in &s.fields {
             fields.insert(fld.name.clone(), TypeInfo::from_ast(&fld.ty));
         }
         api.structs.insert(s.name.clone(), fields);
     }
+    for export in &program.exports {
+        if let crate::ast::ExportDecl::Local { items } = export {
+            for item in items.iter().filter(|item| item.opaque) {
+                api.opaque_structs.insert(item.name.clone());
+            }
+        }
+    }
     for i in &program.impls {
         let methods = api.methods.entry(i.target.clone()).or_default();
         for m in &i.methods {
@@ -203,6 +290,9 @@ fn build_external_context(
                                     ctx.imported_functions.insert(name.clone(), sig);
                                     ctx.direct_import_targets
                                         .insert(name.clone(), format!("{}.{}", target, name));
+                                    if let Some(note) = &sym.deprecated {
+                                        ctx.deprecated_functions.insert(name.clone(), note.clone());
+                                    }
                                 }
                                 if let Some(sig) = api.operators.get(&sym.local_name).cloned() {
                                     ctx.imported_operators.insert(name.clone(), sig);
@@ -212,6 +302,13 @@ fn build_external_context(
                                 if let Some(fields) = api.structs.get(&sym.local_name).cloned() {
                                     ctx.imported_structs.insert(name.clone(), fields);
                                 }
+                                if let Some(note) = &sym.deprecated {
+                                    ctx.deprecated_structs.insert(name.clone(), note.clone());
+                                }
+                                if api.opaque_structs.contains(&sym.local_name) {
+                                    ctx.opaque_structs
+                                        .insert(name.clone(), sym.module_id.clone());
+                                }
                                 if let Some(methods) = api.methods.get(&sym.local_name).cloned() {
                                     ctx.imported_methods.insert(
                                         name.clone(),
@@ -224,7 +321,8 @@ fn build_external_context(
                                     ctx.imported_globals.insert(name.clone(), ty);
                                 }
                             }
-                            crate::resolver::SymbolKind::Namespace => {}
+                            crate::resolver::SymbolKind::TypeAlias
+                            | crate::resolver::SymbolKind::Namespace => {}
                         }
                     }
                 } else {
@@ -242,6 +340,10 @@ fn build_external_context(
                                     ctx.imported_functions.insert(local.clone(), sig);
                                     ctx.direct_import_targets
                                         .insert(local.clone(), format!("{}.{}", target, item.name));
+                                    if let Some(note) = &sym.deprecated {
+                                        ctx.deprecated_functions
+                                            .insert(local.clone(), note.clone());
+                                    }
                                 }
                                 if let Some(sig) = api.operators.get(&sym.local_name).cloned() {
                                     ctx.imported_operators.insert(local.clone(), sig);
@@ -251,6 +353,13 @@ fn build_external_context(
                                 if let Some(fields) = api.structs.get(&sym.local_name).cloned() {
                                     ctx.imported_structs.insert(local.clone(), fields);
                                 }
+                                if let Some(note) = &sym.deprecated {
+                                    ctx.deprecated_structs.insert(local.clone(), note.clone());
+                                }
+                                if api.opaque_structs.contains(&sym.local_name) {
+                                    ctx.opaque_structs
+                                        .insert(local.clone(), sym.module_id.clone());
+                                }
                                 if let Some(methods) = api.methods.get(&sym.local_name).cloned() {
                                     ctx.imported_methods.insert(
                                         local.clone(),
@@ -263,7 +372,8 @@ fn build_external_context(
                                     ctx.imported_globals.insert(local, ty);
                                 }
                             }
-                            crate::resolver::SymbolKind::Namespace => {}
+                            crate::resolver::SymbolKind::TypeAlias
+                            | crate::resolver::SymbolKind::Namespace => {}
                         }
                     }
                 }
@@ -283,6 +393,9 @@ fn build_external_context(
                         match sym.kind {
                             crate::resolver::SymbolKind::Fn => {
                                 if let Some(sig) = api.functions.get(&sym.local_name).cloned() {
+                                    if let Some(note) = &sym.deprecated {
+                                        ctx.deprecated_functions.insert(q.clone(), note.clone());
+                                    }
                                     ctx.imported_functions.insert(q.clone(), sig.clone());
                                     if target_id
                                         .rsplit('.')
@@ -307,6 +420,12 @@ fn build_external_context(
                                 if let Some(fields) = api.structs.get(&sym.local_name).cloned() {
                                     ctx.imported_structs.insert(q.clone(), fields);
                                 }
+                                if let Some(note) = &sym.deprecated {
+                                    ctx.deprecated_structs.insert(q.clone(), note.clone());
+                                }
+                                if api.opaque_structs.contains(&sym.local_name) {
+                                    ctx.opaque_structs.insert(q.clone(), sym.module_id.clone());
+                                }
                                 if let Some(methods) = api.methods.get(&sym.local_name).cloned() {
                                     ctx.imported_methods.insert(
                                         q.clone(),
@@ -319,7 +438,8 @@ fn build_external_context(
                                     ctx.imported_globals.insert(q, ty);
                                 }
                             }
-                            crate::resolver::SymbolKind::Namespace => {}
+                            crate::resolver::SymbolKind::TypeAlias
+                            | crate::resolver::SymbolKind::Namespace => {}
                         }
                     }
                 }
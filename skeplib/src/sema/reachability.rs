@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use crate::ast::{Expr, Stmt, StmtKind};
+use crate::diagnostic::Span;
+use crate::lint::Lint;
+
+use super::Checker;
+
+/// A statement that can never run, and what ends control flow before it.
+struct UnreachableStmt {
+    span: Span,
+    after: String,
+}
+
+impl Checker {
+    /// Warns about the first statement after a `return`, `break`, `continue`, diverging call,
+    /// or other statement that never completes, once per block, at the dead statement.
+    pub(super) fn check_reachability(&mut self, body: &[Stmt]) {
+        let mut dead = Vec::new();
+        collect_unreachable(body, false, &self.non_exhaustive_matches, &mut dead);
+        for stmt in dead {
+            // Statements built outside the parser have no position of their own.
+            let span = (stmt.span.line > 0).then_some(stmt.span);
+            self.report_lint(
+                Lint::UnreachableCode,
+                format!("Unreachable statement after {}", stmt.after),
+                span,
+            );
+        }
+    }
+}
+
+fn collect_unreachable(
+    body: &[Stmt],
+    in_loop: bool,
+    non_exhaustive: &HashSet<Span>,
+    out: &mut Vec<UnreachableStmt>,
+) {
+    for (idx, stmt) in body.iter().enumerate() {
+        match &stmt.kind {
+            StmtKind::If {
+                then_body,
+                else_body,
+                ..
+            } => {
+                collect_unreachable(then_body, in_loop, non_exhaustive, out);
+                collect_unreachable(else_body, in_loop, non_exhaustive, out);
+            }
+            StmtKind::While { body, .. }
+            | StmtKind::For { body, .. }
+            | StmtKind::ForIn { body, .. } => collect_unreachable(body, true, non_exhaustive, out),
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    collect_unreachable(&arm.body, in_loop, non_exhaustive, out);
+                }
+            }
+            _ => {}
+        }
+        if let Some(after) = never_completes(stmt, in_loop, non_exhaustive) {
+            if let Some(next) = body.get(idx + 1) {
+                out.push(UnreachableStmt {
+                    span: next.span,
+                    after,
+                });
+            }
+            return;
+        }
+    }
+}
+
+/// Describes why control never reaches the statement after `stmt`, if it doesn't.
+/// `break` and `continue` only count inside a loop; outside one they are already errors.
+/// A `match` only counts when it is exhaustive and has no guards.
+fn never_completes(stmt: &Stmt, in_loop: bool, non_exhaustive: &HashSet<Span>) -> Option<String> {
+    let block_exits = |body: &[Stmt]| {
+        body.iter()
+            .any(|s| never_completes(s, in_loop, non_exhaustive).is_some())
+    };
+    match &stmt.kind {
+        StmtKind::Return(_) => Some("`return`".to_string()),
+        StmtKind::Break if in_loop => Some("`break`".to_string()),
+        StmtKind::Continue if in_loop => Some("`continue`".to_string()),
+        StmtKind::Expr(expr) if expr.is_diverging_call() => match expr {
+            Expr::Call { callee, .. } if matches!(callee.as_ref(), Expr::Ident(_)) => {
+                Some("`panic(...)`".to_string())
+            }
+            _ => Some("`os.exit(...)`".to_string()),
+        },
+        StmtKind::If {
+            then_body,
+            else_body,
+            ..
+        } if !else_body.is_empty() && block_exits(then_body) && block_exits(else_body) => {
+            Some("`if` whose every branch exits".to_string())
+        }
+        StmtKind::Match { arms, .. }
+            if !arms.is_empty()
+                && !non_exhaustive.contains(&stmt.span)
+                && arms
+                    .iter()
+                    .all(|arm| arm.guard.is_none() && block_exits(&arm.body)) =>
+        {
+            Some("`match` whose every arm exits".to_string())
+        }
+        StmtKind::While { .. } if stmt.is_infinite_loop() => {
+            Some("infinite `while` loop".to_string())
+        }
+        StmtKind::For { .. } if stmt.is_infinite_loop() => Some("infinite `for` loop".to_string()),
+        _ => None,
+    }
+}
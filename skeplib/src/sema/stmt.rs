@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::ast::{AssignTarget, Expr, MatchLiteral, MatchPattern, Stmt};
+use crate::ast::{AssignTarget, Expr, MatchLiteral, MatchPattern, Stmt, StmtKind};
 use crate::types::{TypeInfo, display_type};
 
 use super::Checker;
@@ -30,7 +30,11 @@ impl Checker {
         }
     }
 
+    /// `vec.new()` or an empty `vec![]`: both need a `Vec[T]` annotation.
     pub(super) fn is_vec_new_call(expr: &Expr) -> bool {
+        if matches!(expr, Expr::VecLit(items) if items.is_empty()) {
+            return true;
+        }
         matches!(
             expr,
             Expr::Call { callee, args }
@@ -84,6 +88,97 @@ impl Checker {
         )
     }
 
+    /// `serde.fromJson(...)` or `serde.decode(...)`, which decode to the type of
+    /// the `let` they initialize.
+    pub(super) fn is_serde_decode_call(expr: &Expr) -> bool {
+        matches!(Self::serde_method(expr), Some("fromJson" | "decode"))
+    }
+
+    /// The `serde` function `expr` calls, e.g. `fromJson` for `serde.fromJson(text)`.
+    fn serde_method(expr: &Expr) -> Option<&str> {
+        let Expr::Call { callee, .. } = expr else {
+            return None;
+        };
+        let (package, method) = match &**callee {
+            Expr::Path(parts) if parts.len() == 2 => (parts[0].as_str(), parts[1].as_str()),
+            Expr::Field { base, field } => match &**base {
+                Expr::Ident(package) => (package.as_str(), field.as_str()),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        (package == "serde").then_some(method)
+    }
+
+    /// The part of `ty` that `serde` cannot serialize (functions, handles,
+    /// `Void`), or `None` when every part of it can be.
+    pub(super) fn serde_unsupported_type(&self, ty: &TypeInfo) -> Option<TypeInfo> {
+        self.serde_type_part(ty, &mut HashSet::new(), &|part| {
+            matches!(
+                part,
+                TypeInfo::Void | TypeInfo::Opaque(_) | TypeInfo::Fn { .. }
+            )
+        })
+    }
+
+    /// The first `Option[Option[T]]` inside `ty`. JSON writes both `None` and
+    /// `Some(None)` as `null`, so `serde.toJson` and `serde.fromJson` reject these
+    /// types rather than lose the difference; the binary form keeps it.
+    pub(super) fn serde_json_nested_option(&self, ty: &TypeInfo) -> Option<TypeInfo> {
+        self.serde_type_part(ty, &mut HashSet::new(), &|part| {
+            matches!(part, TypeInfo::Option { value } if matches!(**value, TypeInfo::Option { .. }))
+        })
+    }
+
+    /// The first part of `ty`, outermost first, that `pick` selects.
+    fn serde_type_part(
+        &self,
+        ty: &TypeInfo,
+        seen: &mut HashSet<String>,
+        pick: &dyn Fn(&TypeInfo) -> bool,
+    ) -> Option<TypeInfo> {
+        if pick(ty) {
+            return Some(ty.clone());
+        }
+        match ty {
+            TypeInfo::Option { value } | TypeInfo::Map { value } => {
+                self.serde_type_part(value, seen, pick)
+            }
+            TypeInfo::Array { elem, .. } | TypeInfo::Vec { elem } => {
+                self.serde_type_part(elem, seen, pick)
+            }
+            TypeInfo::Result { ok, err } => self
+                .serde_type_part(ok, seen, pick)
+                .or_else(|| self.serde_type_part(err, seen, pick)),
+            TypeInfo::Tuple(items) => items
+                .iter()
+                .find_map(|item| self.serde_type_part(item, seen, pick)),
+            TypeInfo::Named(name) => {
+                if !seen.insert(name.clone()) {
+                    return None;
+                }
+                let mut fields = self.struct_fields.get(name)?.iter().collect::<Vec<_>>();
+                fields.sort_by(|a, b| a.0.cmp(b.0));
+                fields
+                    .into_iter()
+                    .find_map(|(_, field)| self.serde_type_part(field, seen, pick))
+            }
+            _ => None,
+        }
+    }
+
+    /// A call to one of the `iter` package's functions, e.g. `iter.range(0, n)`.
+    pub(super) fn is_iter_call(expr: &Expr) -> bool {
+        let Expr::Call { callee, .. } = expr else {
+            return false;
+        };
+        match &**callee {
+            Expr::Path(parts) => parts.len() == 2 && parts[0] == "iter",
+            Expr::Field { base, .. } => matches!(&**base, Expr::Ident(pkg) if pkg == "iter"),
+            _ => false,
+        }
+    }
+
     fn match_pattern_literal_key_and_label(pat: &MatchPattern) -> Option<(String, String)> {
         match pat {
             MatchPattern::Literal(MatchLiteral::Int(v)) => {
@@ -92,6 +187,10 @@ impl Checker {
             MatchPattern::Literal(MatchLiteral::Bool(v)) => {
                 Some((format!("bool:{v}"), format!("Bool literal `{v}`")))
             }
+            MatchPattern::Literal(MatchLiteral::Char(v)) => Some((
+                format!("char:{}", *v as u32),
+                format!("Char literal '{}'", v.escape_default()),
+            )),
             MatchPattern::Literal(MatchLiteral::String(v)) => {
                 Some((format!("string:{v}"), format!("String literal \"{v}\"")))
             }
@@ -101,7 +200,12 @@ impl Checker {
             MatchPattern::Variant { name, .. } => {
                 Some((format!("variant:{name}"), format!("variant `{name}`")))
             }
-            MatchPattern::Wildcard | MatchPattern::Or(_) => None,
+            MatchPattern::Wildcard
+            | MatchPattern::Or(_)
+            | MatchPattern::Binding(_)
+            | MatchPattern::Struct { .. }
+            | MatchPattern::Array { .. }
+            | MatchPattern::Tuple(_) => None,
         }
     }
 
@@ -140,111 +244,193 @@ impl Checker {
         }
     }
 
+    /// Message for a catch-all arm that is followed by more arms.
+    pub(super) fn catch_all_not_last_message(pat: &MatchPattern) -> String {
+        if Self::pattern_has_wildcard(pat) {
+            "Wildcard match arm `_` must be last".to_string()
+        } else {
+            "Catch-all match arm must be last".to_string()
+        }
+    }
+
+    /// Reports a match whose unguarded arms do not cover every value of the target,
+    /// and returns whether they do.
     pub(super) fn check_match_exhaustiveness(
         &mut self,
         target_ty: &TypeInfo,
         seen_wildcard: bool,
         seen_literals: &std::collections::HashSet<String>,
-    ) {
+    ) -> bool {
         if seen_wildcard {
-            return;
+            return true;
         }
-        match target_ty {
+        let has = |key: &str| seen_literals.contains(key);
+        let message = match target_ty {
+            TypeInfo::Option { .. } if has("variant:Some") && has("variant:None") => return true,
             TypeInfo::Option { .. } => {
-                let has_some = seen_literals.contains("variant:Some");
-                let has_none = seen_literals.contains("variant:None");
-                if !(has_some && has_none) {
-                    self.error(
-                        "Non-exhaustive match on Option: add both `Some(...)` and `None` arms, or add a wildcard arm `_`"
-                            .to_string(),
-                    );
-                }
+                "Non-exhaustive match on Option: add both `Some(...)` and `None` arms, or add a wildcard arm `_`"
+                    .to_string()
             }
+            TypeInfo::Result { .. } if has("variant:Ok") && has("variant:Err") => return true,
             TypeInfo::Result { .. } => {
-                let has_ok = seen_literals.contains("variant:Ok");
-                let has_err = seen_literals.contains("variant:Err");
-                if !(has_ok && has_err) {
-                    self.error(
-                        "Non-exhaustive match on Result: add both `Ok(...)` and `Err(...)` arms, or add a wildcard arm `_`"
-                            .to_string(),
-                    );
-                }
+                "Non-exhaustive match on Result: add both `Ok(...)` and `Err(...)` arms, or add a wildcard arm `_`"
+                    .to_string()
             }
+            TypeInfo::Bool if has("bool:true") && has("bool:false") => return true,
             TypeInfo::Bool => {
-                let has_true = seen_literals.contains("bool:true");
-                let has_false = seen_literals.contains("bool:false");
-                if !(has_true && has_false) {
-                    self.error(
-                        "Non-exhaustive match on Bool: add both `true` and `false` arms, or add a wildcard arm `_`"
-                            .to_string(),
-                    );
-                }
+                "Non-exhaustive match on Bool: add both `true` and `false` arms, or add a wildcard arm `_`"
+                    .to_string()
             }
-            _ => {
-                self.error(format!(
-                    "Non-exhaustive match on {}: add a wildcard arm `_`",
-                    display_type(target_ty)
-                ));
+            _ => format!(
+                "Non-exhaustive match on {}: add a wildcard arm `_`",
+                display_type(target_ty)
+            ),
+        };
+        self.error("E-SEMA-006", message);
+        false
+    }
+
+    /// Checks one arm's pattern against the match target and returns the names it
+    /// binds with their types. Reports a name bound twice in the same pattern.
+    /// Literals of a guarded arm do not count as covered, since the guard may fail.
+    pub(super) fn check_match_arm_pattern(
+        &mut self,
+        pat: &MatchPattern,
+        guarded: bool,
+        target_ty: &TypeInfo,
+        seen_literals: &mut std::collections::HashSet<String>,
+    ) -> Vec<(String, TypeInfo)> {
+        let mut bindings = Vec::new();
+        let mut guarded_literals = std::collections::HashSet::new();
+        let seen_literals = if guarded {
+            &mut guarded_literals
+        } else {
+            seen_literals
+        };
+        self.check_match_pattern(pat, target_ty, seen_literals, &mut bindings);
+        let mut seen = std::collections::HashSet::new();
+        for (name, _) in &bindings {
+            if !seen.insert(name.as_str()) {
+                self.error(
+                    "E-SEMA-003",
+                    format!("Match pattern binds `{name}` more than once"),
+                );
             }
         }
+        bindings
+    }
+
+    pub(super) fn check_match_guard(
+        &mut self,
+        guard: Option<&Expr>,
+        scopes: &mut [HashMap<String, TypeInfo>],
+    ) {
+        let Some(guard) = guard else {
+            return;
+        };
+        let guard_ty = self.check_expr(guard, scopes);
+        if guard_ty != TypeInfo::Bool && guard_ty != TypeInfo::Unknown {
+            self.error(
+                "E-SEMA-010",
+                format!("Match guard must be Bool, got {}", display_type(&guard_ty)),
+            );
+        }
     }
 
-    pub(super) fn check_match_pattern(
+    fn check_match_pattern(
         &mut self,
         pat: &MatchPattern,
         target_ty: &TypeInfo,
         seen_literals: &mut std::collections::HashSet<String>,
+        bindings: &mut Vec<(String, TypeInfo)>,
     ) {
         match pat {
             MatchPattern::Wildcard => {}
+            MatchPattern::Binding(name) => bindings.push((name.clone(), target_ty.clone())),
+            MatchPattern::Struct { name, fields, rest } => {
+                self.check_struct_pattern(name, fields, *rest, target_ty, bindings)
+            }
+            MatchPattern::Array {
+                items,
+                rest,
+                rest_binding,
+            } => {
+                self.check_array_pattern(items, *rest, rest_binding.as_deref(), target_ty, bindings)
+            }
+            MatchPattern::Tuple(items) => self.check_tuple_pattern(items, target_ty, bindings),
             MatchPattern::Literal(lit) => {
                 let lit_ty = match lit {
                     MatchLiteral::Int(_) => TypeInfo::Int,
                     MatchLiteral::Bool(_) => TypeInfo::Bool,
+                    MatchLiteral::Char(_) => TypeInfo::Char,
                     MatchLiteral::String(_) => TypeInfo::String,
                     MatchLiteral::Float(_) => TypeInfo::Float,
                 };
                 if *target_ty != TypeInfo::Unknown && *target_ty != lit_ty {
-                    self.error(format!(
-                        "Match pattern type mismatch: target {:?}, pattern {:?}",
-                        target_ty, lit_ty
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!(
+                            "Match pattern type mismatch: target {:?}, pattern {:?}",
+                            target_ty, lit_ty
+                        ),
+                    );
                 }
                 if let Some((key, label)) = Self::match_pattern_literal_key_and_label(pat)
                     && !seen_literals.insert(key)
                 {
-                    self.error(format!("Duplicate match pattern {label}"));
+                    self.error("E-SEMA-006", format!("Duplicate match pattern {label}"));
                 }
             }
             MatchPattern::Variant { name, binding } => {
                 if !Self::match_variant_allowed(name, target_ty) {
-                    self.error(format!(
-                        "Match variant `{name}` is not valid for target type {}",
-                        display_type(target_ty)
-                    ));
+                    self.error(
+                        "E-SEMA-014",
+                        format!(
+                            "Match variant `{name}` is not valid for target type {}",
+                            display_type(target_ty)
+                        ),
+                    );
                 }
                 if matches!(name.as_str(), "None" | "Some" | "Ok" | "Err")
                     && matches!(name.as_str(), "None")
                     && binding.is_some()
                 {
-                    self.error("Match variant `None` cannot bind a value".to_string());
+                    self.error(
+                        "E-SEMA-014",
+                        "Match variant `None` cannot bind a value".to_string(),
+                    );
                 }
                 if let Some((key, label)) = Self::match_pattern_literal_key_and_label(pat)
                     && !seen_literals.insert(key)
                 {
-                    self.error(format!("Duplicate match pattern {label}"));
+                    self.error("E-SEMA-006", format!("Duplicate match pattern {label}"));
+                }
+                if let Some(binding) = binding
+                    && let Some(binding_ty) = Self::match_variant_binding_type(pat, target_ty)
+                {
+                    bindings.push((binding.clone(), binding_ty));
                 }
             }
             MatchPattern::Or(parts) => {
                 if parts.is_empty() {
                     self.error(
+                        "E-SEMA-014",
                         "Match OR-pattern must contain at least one alternative".to_string(),
                     );
                     return;
                 }
                 for part in parts {
-                    if matches!(part, MatchPattern::Wildcard | MatchPattern::Or(_)) {
+                    if matches!(
+                        part,
+                        MatchPattern::Wildcard
+                            | MatchPattern::Or(_)
+                            | MatchPattern::Binding(_)
+                            | MatchPattern::Struct { .. }
+                            | MatchPattern::Array { .. }
+                            | MatchPattern::Tuple(_)
+                    ) {
                         self.error(
+                            "E-SEMA-010",
                             "Match OR-pattern alternatives must be literals or variants"
                                 .to_string(),
                         );
@@ -258,16 +444,229 @@ impl Checker {
                         }
                     ) {
                         self.error(
+                            "E-SEMA-014",
                             "Match OR-pattern variant alternatives cannot bind values".to_string(),
                         );
                         continue;
                     }
-                    self.check_match_pattern(part, target_ty, seen_literals);
+                    self.check_match_pattern(part, target_ty, seen_literals, bindings);
                 }
             }
         }
     }
 
+    fn check_struct_pattern(
+        &mut self,
+        name: &str,
+        fields: &[(String, MatchPattern)],
+        rest: bool,
+        target_ty: &TypeInfo,
+        bindings: &mut Vec<(String, TypeInfo)>,
+    ) {
+        let Some(resolved) = self.resolve_named_type_name(name) else {
+            let suggestion = Self::did_you_mean(name, self.struct_names.iter().map(String::as_str));
+            self.error_with_suggestion(
+                "E-SEMA-001",
+                format!("Unknown struct `{name}` in match pattern"),
+                suggestion,
+            );
+            self.poison_pattern_bindings(fields.iter().map(|(_, p)| p), bindings);
+            return;
+        };
+        // `Box { value }` matches any instantiation `Box[...]` of a generic struct.
+        let resolved = match target_ty {
+            TypeInfo::Named(target)
+                if self.generic_structs.contains_key(&resolved)
+                    && target.starts_with(&format!("{resolved}[")) =>
+            {
+                target.clone()
+            }
+            _ => resolved,
+        };
+        match target_ty {
+            TypeInfo::Named(target) if *target == resolved => {}
+            TypeInfo::Unknown => {}
+            other => {
+                self.error(
+                    "E-SEMA-014",
+                    format!(
+                        "Struct pattern `{name}` cannot match a value of type {}",
+                        display_type(other)
+                    ),
+                );
+                self.poison_pattern_bindings(fields.iter().map(|(_, p)| p), bindings);
+                return;
+            }
+        }
+        if !self.check_opaque_struct_access(&resolved, "destructure") {
+            self.poison_pattern_bindings(fields.iter().map(|(_, p)| p), bindings);
+            return;
+        }
+        let Some(expected_fields) = self.struct_fields.get(&resolved).cloned() else {
+            self.poison_pattern_bindings(fields.iter().map(|(_, p)| p), bindings);
+            return;
+        };
+        let mut seen = std::collections::HashSet::new();
+        for (field, pattern) in fields {
+            if !seen.insert(field.as_str()) {
+                self.error(
+                    "E-SEMA-003",
+                    format!("Duplicate field `{field}` in struct `{name}` pattern"),
+                );
+                continue;
+            }
+            let Some(field_ty) = expected_fields.get(field) else {
+                let suggestion = self.field_suggestion(&resolved, field);
+                self.error_with_suggestion(
+                    "E-SEMA-002",
+                    format!("Unknown field `{field}` in struct `{name}` pattern"),
+                    suggestion,
+                );
+                self.poison_pattern_bindings([pattern], bindings);
+                continue;
+            };
+            // Literals nested in a destructuring pattern only narrow this arm, so
+            // they stay out of the target's duplicate/exhaustiveness tracking.
+            let mut nested_literals = std::collections::HashSet::new();
+            self.check_match_pattern(pattern, field_ty, &mut nested_literals, bindings);
+        }
+        if !rest {
+            let mut missing = expected_fields
+                .keys()
+                .filter(|field| !seen.contains(field.as_str()))
+                .cloned()
+                .collect::<Vec<_>>();
+            missing.sort();
+            if !missing.is_empty() {
+                self.error("E-SEMA-014", format!(
+                    "Struct pattern `{name}` is missing field(s) {}; list them or end the pattern with `..`",
+                    missing
+                        .iter()
+                        .map(|field| format!("`{field}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+    }
+
+    /// Binds the names in patterns that were rejected as `Unknown`, so their
+    /// uses in the arm do not cascade into "Unknown variable" errors.
+    fn poison_pattern_bindings<'a>(
+        &mut self,
+        patterns: impl IntoIterator<Item = &'a MatchPattern>,
+        bindings: &mut Vec<(String, TypeInfo)>,
+    ) {
+        for pattern in patterns {
+            let mut nested_literals = std::collections::HashSet::new();
+            self.check_match_pattern(pattern, &TypeInfo::Unknown, &mut nested_literals, bindings);
+        }
+    }
+
+    fn check_array_pattern(
+        &mut self,
+        items: &[MatchPattern],
+        rest: bool,
+        rest_binding: Option<&str>,
+        target_ty: &TypeInfo,
+        bindings: &mut Vec<(String, TypeInfo)>,
+    ) {
+        let (elem, size) = match target_ty {
+            TypeInfo::Array { elem, size } => ((**elem).clone(), *size),
+            TypeInfo::Unknown => {
+                self.poison_array_pattern(items, rest_binding, bindings);
+                return;
+            }
+            other => {
+                self.error(
+                    "E-SEMA-014",
+                    format!(
+                        "Array pattern requires a fixed-size array, got {}",
+                        display_type(other)
+                    ),
+                );
+                self.poison_array_pattern(items, rest_binding, bindings);
+                return;
+            }
+        };
+        if items.len() > size || (!rest && items.len() != size) {
+            self.error(
+                "E-SEMA-014",
+                format!(
+                    "Array pattern lists {} element(s) but the target is {}",
+                    items.len(),
+                    display_type(target_ty)
+                ),
+            );
+            self.poison_array_pattern(items, rest_binding, bindings);
+            return;
+        }
+        for item in items {
+            let mut nested_literals = std::collections::HashSet::new();
+            self.check_match_pattern(item, &elem, &mut nested_literals, bindings);
+        }
+        if let Some(name) = rest_binding {
+            bindings.push((
+                name.to_string(),
+                TypeInfo::Array {
+                    elem: Box::new(elem),
+                    size: size - items.len(),
+                },
+            ));
+        }
+    }
+
+    fn poison_array_pattern(
+        &mut self,
+        items: &[MatchPattern],
+        rest_binding: Option<&str>,
+        bindings: &mut Vec<(String, TypeInfo)>,
+    ) {
+        self.poison_pattern_bindings(items, bindings);
+        if let Some(name) = rest_binding {
+            bindings.push((name.to_string(), TypeInfo::Unknown));
+        }
+    }
+
+    fn check_tuple_pattern(
+        &mut self,
+        items: &[MatchPattern],
+        target_ty: &TypeInfo,
+        bindings: &mut Vec<(String, TypeInfo)>,
+    ) {
+        let elems = match target_ty {
+            TypeInfo::Tuple(elems) => elems.clone(),
+            TypeInfo::Unknown => vec![TypeInfo::Unknown; items.len()],
+            other => {
+                self.error(
+                    "E-SEMA-014",
+                    format!(
+                        "Tuple pattern requires a tuple, got {}",
+                        display_type(other)
+                    ),
+                );
+                self.poison_pattern_bindings(items, bindings);
+                return;
+            }
+        };
+        if items.len() != elems.len() {
+            self.error(
+                "E-SEMA-014",
+                format!(
+                    "Tuple pattern lists {} element(s) but the target is {}",
+                    items.len(),
+                    display_type(target_ty)
+                ),
+            );
+            self.poison_pattern_bindings(items, bindings);
+            return;
+        }
+        for (item, elem) in items.iter().zip(&elems) {
+            let mut nested_literals = std::collections::HashSet::new();
+            self.check_match_pattern(item, elem, &mut nested_literals, bindings);
+        }
+    }
+
     fn lookup_assignment_target(
         &mut self,
         target: &AssignTarget,
@@ -280,16 +679,16 @@ impl Checker {
                     let base_ty = self.check_expr(base, scopes);
                     let idx_ty = self.check_expr(index, scopes);
                     if idx_ty != TypeInfo::Int && idx_ty != TypeInfo::Unknown {
-                        self.error("Array index must be Int".to_string());
+                        self.error("E-SEMA-010", "Array index must be Int".to_string());
                     }
                     match base_ty {
                         TypeInfo::Array { elem, .. } => *elem,
                         TypeInfo::Unknown => TypeInfo::Unknown,
                         other => {
-                            self.error(format!(
-                                "Cannot index-assign into non-array type {:?}",
-                                other
-                            ));
+                            self.error(
+                                "E-SEMA-012",
+                                format!("Cannot index-assign into non-array type {:?}", other),
+                            );
                             TypeInfo::Unknown
                         }
                     }
@@ -301,22 +700,29 @@ impl Checker {
                 let base_ty = self.check_expr(base, scopes);
                 match base_ty {
                     TypeInfo::Named(struct_name) => {
-                        if let Some(field_ty) = self.field_type(&struct_name, field) {
+                        if !self.check_opaque_struct_access(
+                            &struct_name,
+                            &format!("assign field `{field}` of"),
+                        ) {
+                            TypeInfo::Unknown
+                        } else if let Some(field_ty) = self.field_type(&struct_name, field) {
                             field_ty
                         } else {
-                            self.error(format!(
-                                "Unknown field `{}` on struct `{}`",
-                                field, struct_name
-                            ));
+                            let suggestion = self.field_suggestion(&struct_name, field);
+                            self.error_with_suggestion(
+                                "E-SEMA-002",
+                                format!("Unknown field `{}` on struct `{}`", field, struct_name),
+                                suggestion,
+                            );
                             TypeInfo::Unknown
                         }
                     }
                     TypeInfo::Unknown => TypeInfo::Unknown,
                     other => {
-                        self.error(format!(
-                            "Field assignment requires struct value, got {:?}",
-                            other
-                        ));
+                        self.error(
+                            "E-SEMA-012",
+                            format!("Field assignment requires struct value, got {:?}", other),
+                        );
                         TypeInfo::Unknown
                     }
                 }
@@ -324,24 +730,58 @@ impl Checker {
         }
     }
 
+    /// Checks something a `for-in` loop or `iter` call can draw elements from: an
+    /// array, a `Vec`, or an `iter` pipeline written in place. Returns the element type.
+    pub(super) fn check_iter_source(
+        &mut self,
+        source: &Expr,
+        scopes: &mut [HashMap<String, TypeInfo>],
+        label: &str,
+    ) -> TypeInfo {
+        self.iter_source_allowed = Self::is_iter_call(source);
+        let ty = self.check_expr(source, scopes);
+        self.iter_source_allowed = false;
+        match ty {
+            TypeInfo::Array { elem, .. } | TypeInfo::Vec { elem } => *elem,
+            TypeInfo::Unknown => TypeInfo::Unknown,
+            got => {
+                if let TypeInfo::Opaque(name) = &got
+                    && let Some(elem) = crate::types::iter_elem_type(name)
+                {
+                    return elem;
+                }
+                self.error(
+                    "E-SEMA-010",
+                    format!(
+                        "{label} expects an Array, Vec, or iter pipeline, got {}",
+                        crate::types::display_type(&got)
+                    ),
+                );
+                TypeInfo::Unknown
+            }
+        }
+    }
+
     pub(super) fn check_stmt(
         &mut self,
         stmt: &Stmt,
         scopes: &mut Vec<HashMap<String, TypeInfo>>,
         expected_ret: &TypeInfo,
     ) {
-        match stmt {
-            Stmt::Let { name, ty, value } => {
+        match &stmt.kind {
+            StmtKind::Let { name, ty, value } => {
+                self.serde_decode_allowed = Self::is_serde_decode_call(value);
                 let expr_ty = self.check_expr(value, scopes);
+                self.serde_decode_allowed = false;
                 let var_ty = match ty {
                     Some(t) => {
                         self.check_decl_type_exists(t, format!("Unknown type in let `{name}`"));
-                        let declared = TypeInfo::from_ast(t);
+                        let declared = self.declared_type(t);
                         if Self::is_vec_new_call(value) {
                             match &declared {
-                                TypeInfo::Vec { .. } => {}
+                                TypeInfo::Vec { .. } | TypeInfo::Unknown => {}
                                 _ => {
-                                    self.error(format!(
+                                    self.error("E-SEMA-010", format!(
                                         "Type mismatch in let `{name}`: declared {:?}, got vec.new()",
                                         declared
                                     ));
@@ -350,9 +790,9 @@ impl Checker {
                             declared
                         } else if Self::is_map_new_call(value) {
                             match &declared {
-                                TypeInfo::Map { .. } => {}
+                                TypeInfo::Map { .. } | TypeInfo::Unknown => {}
                                 _ => {
-                                    self.error(format!(
+                                    self.error("E-SEMA-010", format!(
                                         "Type mismatch in let `{name}`: declared {:?}, got map.new()",
                                         declared
                                     ));
@@ -363,113 +803,180 @@ impl Checker {
                             match &declared {
                                 TypeInfo::Opaque(name)
                                     if crate::types::task_channel_value_type(name).is_some() => {}
+                                TypeInfo::Unknown => {}
                                 _ => {
-                                    self.error(format!(
+                                    self.error("E-SEMA-010", format!(
                                         "Type mismatch in let `{name}`: declared {:?}, got task.channel()",
                                         declared
                                     ));
                                 }
                             }
                             declared
+                        } else if Self::is_serde_decode_call(value) {
+                            match &declared {
+                                TypeInfo::Result { ok, err } if **err == TypeInfo::String => {
+                                    if let Some(part) = self.serde_unsupported_type(ok) {
+                                        self.error("E-SEMA-029", format!(
+                                            "Cannot decode `{}` in let `{name}`: `{}` has no serialized form",
+                                            display_type(ok),
+                                            display_type(&part)
+                                        ));
+                                    }
+                                    if Self::serde_method(value) == Some("fromJson")
+                                        && let Some(part) = self.serde_json_nested_option(ok)
+                                    {
+                                        self.error("E-SEMA-029", format!(
+                                            "Cannot decode `{}` in let `{name}` from JSON: `{}` is ambiguous because `None` and `Some(None)` are both `null`; use `serde.decode` or wrap the inner Option in a struct",
+                                            display_type(ok),
+                                            display_type(&part)
+                                        ));
+                                    }
+                                }
+                                TypeInfo::Unknown => {}
+                                _ => {
+                                    self.error("E-SEMA-010", format!(
+                                        "Type mismatch in let `{name}`: declared {:?}, serde decoding gives `Result[T, String]`",
+                                        declared
+                                    ));
+                                }
+                            }
+                            declared
                         } else {
                             let expr_ty =
                                 Self::refine_result_type_from_expected(expr_ty, &declared);
                             if !Self::types_compatible(&expr_ty, &declared) {
-                                self.error(format!(
-                                    "Type mismatch in let `{name}`: declared {:?}, got {:?}",
-                                    declared, expr_ty
-                                ));
+                                self.error(
+                                    "E-SEMA-010",
+                                    format!(
+                                        "Type mismatch in let `{name}`: declared {:?}, got {:?}",
+                                        declared, expr_ty
+                                    ),
+                                );
                             }
                             declared
                         }
                     }
                     None => {
                         if Self::is_vec_new_call(value) {
-                            self.error(format!(
+                            self.error("E-SEMA-009", format!(
                                 "Cannot infer vector element type for let `{name}`; annotate as `Vec[T]`"
                             ));
                             TypeInfo::Unknown
                         } else if Self::is_map_new_call(value) {
-                            self.error(format!(
+                            self.error("E-SEMA-009", format!(
                                 "Cannot infer map value type for let `{name}`; annotate as `Map[String, T]`"
                             ));
                             TypeInfo::Unknown
                         } else if Self::is_task_channel_call(value) {
-                            self.error(format!(
+                            self.error("E-SEMA-009", format!(
                                 "Cannot infer channel value type for let `{name}`; annotate as `task.Channel[T]`"
                             ));
                             TypeInfo::Unknown
+                        } else if Self::is_serde_decode_call(value) {
+                            self.error("E-SEMA-009", format!(
+                                "Cannot infer decoded type for let `{name}`; annotate as `Result[T, String]`"
+                            ));
+                            TypeInfo::Unknown
                         } else {
                             expr_ty
                         }
                     }
                 };
+                let depth = scopes.len().saturating_sub(1);
                 if let Some(scope) = scopes.last_mut() {
                     if scope.contains_key(name) {
-                        self.error(format!(
-                            "Duplicate local binding `{name}` in the same scope"
-                        ));
+                        self.duplicate_local_error(name, depth, stmt.span);
                     } else {
+                        self.note_binding(name, &var_ty);
                         scope.insert(name.clone(), var_ty);
+                        self.note_binding_span(depth, name, stmt.span);
                     }
                 }
             }
-            Stmt::Assign { target, value } => {
+            StmtKind::LetPattern { pattern, value } => {
+                let value_ty = self.check_expr(value, scopes);
+                let mut seen_literals = std::collections::HashSet::new();
+                let bindings =
+                    self.check_match_arm_pattern(pattern, false, &value_ty, &mut seen_literals);
+                if !pattern.is_irrefutable() {
+                    self.error("E-SEMA-014", 
+                        "`let` patterns must always match; use `match` for patterns with literals or variants"
+                            .to_string(),
+                    );
+                }
+                let depth = scopes.len().saturating_sub(1);
+                for (name, ty) in bindings {
+                    let Some(scope) = scopes.last_mut() else {
+                        break;
+                    };
+                    if scope.contains_key(&name) {
+                        self.duplicate_local_error(&name, depth, stmt.span);
+                    } else {
+                        self.note_binding(&name, &ty);
+                        scope.insert(name.clone(), ty);
+                        self.note_binding_span(depth, &name, stmt.span);
+                    }
+                }
+            }
+            StmtKind::Assign { target, value } => {
                 let target_ty = self.lookup_assignment_target(target, scopes);
                 let value_ty = self.check_expr(value, scopes);
                 if !Self::types_compatible(&value_ty, &target_ty) {
-                    self.error(format!(
-                        "Assignment type mismatch: target {:?}, value {:?}",
-                        target_ty, value_ty
-                    ));
+                    self.error(
+                        "E-SEMA-010",
+                        format!(
+                            "Assignment type mismatch: target {:?}, value {:?}",
+                            target_ty, value_ty
+                        ),
+                    );
                 }
             }
-            Stmt::Expr(expr) => {
+            StmtKind::Expr(expr) => {
                 self.check_expr(expr, scopes);
             }
-            Stmt::If {
+            StmtKind::If {
                 cond,
                 then_body,
                 else_body,
             } => {
                 let cond_ty = self.check_expr(cond, scopes);
                 if cond_ty != TypeInfo::Bool && cond_ty != TypeInfo::Unknown {
-                    self.error("if condition must be Bool".to_string());
+                    self.error("E-SEMA-010", "if condition must be Bool".to_string());
                 }
 
-                scopes.push(HashMap::new());
+                self.push_scope(scopes, HashMap::new());
                 for s in then_body {
                     self.check_stmt(s, scopes, expected_ret);
                 }
                 scopes.pop();
 
-                scopes.push(HashMap::new());
+                self.push_scope(scopes, HashMap::new());
                 for s in else_body {
                     self.check_stmt(s, scopes, expected_ret);
                 }
                 scopes.pop();
             }
-            Stmt::While { cond, body } => {
+            StmtKind::While { cond, body } => {
                 let cond_ty = self.check_expr(cond, scopes);
                 if cond_ty != TypeInfo::Bool && cond_ty != TypeInfo::Unknown {
-                    self.error("while condition must be Bool".to_string());
+                    self.error("E-SEMA-010", "while condition must be Bool".to_string());
                 }
 
                 self.loop_depth += 1;
-                scopes.push(HashMap::new());
+                self.push_scope(scopes, HashMap::new());
                 for s in body {
                     self.check_stmt(s, scopes, expected_ret);
                 }
                 scopes.pop();
                 self.loop_depth = self.loop_depth.saturating_sub(1);
             }
-            Stmt::For {
+            StmtKind::For {
                 init,
                 cond,
                 step,
                 body,
             } => {
-                scopes.push(HashMap::new());
+                self.push_scope(scopes, HashMap::new());
                 if let Some(init) = init {
                     self.check_stmt(init, scopes, expected_ret);
                 }
@@ -477,7 +984,7 @@ impl Checker {
                 if let Some(cond) = cond {
                     let cond_ty = self.check_expr(cond, scopes);
                     if cond_ty != TypeInfo::Bool && cond_ty != TypeInfo::Unknown {
-                        self.error("for condition must be Bool".to_string());
+                        self.error("E-SEMA-010", "for condition must be Bool".to_string());
                     }
                 }
 
@@ -491,17 +998,50 @@ impl Checker {
                 self.loop_depth = self.loop_depth.saturating_sub(1);
                 scopes.pop();
             }
-            Stmt::Break => {
+            StmtKind::ForIn {
+                name,
+                iterable,
+                body,
+            } => {
+                let elem = self.check_iter_source(iterable, scopes, "for-in iterable");
+                self.loop_depth += 1;
+                self.note_binding(name, &elem);
+                self.push_scope(scopes, HashMap::from([(name.clone(), elem)]));
+                for s in body {
+                    self.check_stmt(s, scopes, expected_ret);
+                }
+                scopes.pop();
+                self.loop_depth = self.loop_depth.saturating_sub(1);
+            }
+            StmtKind::Break => {
                 if self.loop_depth == 0 {
-                    self.error("`break` is only allowed inside a loop".to_string());
+                    self.error(
+                        "E-SEMA-007",
+                        "`break` is only allowed inside a loop".to_string(),
+                    );
                 }
             }
-            Stmt::Continue => {
+            StmtKind::Continue => {
                 if self.loop_depth == 0 {
-                    self.error("`continue` is only allowed inside a loop".to_string());
+                    self.error(
+                        "E-SEMA-007",
+                        "`continue` is only allowed inside a loop".to_string(),
+                    );
                 }
             }
-            Stmt::Return(expr_opt) => {
+            StmtKind::Defer(expr) => {
+                let floor = self.fn_lit_scope_floors.last().copied().unwrap_or(0);
+                if scopes.len() != floor + 1 {
+                    self.error(
+                        "E-SEMA-007",
+                        "`defer` is only allowed at the top level of a function body".to_string(),
+                    );
+                }
+                self.defer_depth += 1;
+                self.check_expr(expr, scopes);
+                self.defer_depth -= 1;
+            }
+            StmtKind::Return(expr_opt) => {
                 let ret_ty = match expr_opt {
                     Some(expr) => self.check_expr(expr, scopes),
                     None => TypeInfo::Void,
@@ -511,55 +1051,74 @@ impl Checker {
                     && &ret_ty != expected_ret
                     && !Self::types_compatible(&ret_ty, expected_ret)
                 {
-                    self.error(format!(
-                        "Return type mismatch: expected {:?}, got {:?}",
-                        expected_ret, ret_ty
-                    ));
+                    self.error(
+                        "E-SEMA-005",
+                        format!(
+                            "Return type mismatch: expected {:?}, got {:?}",
+                            expected_ret, ret_ty
+                        ),
+                    );
                 }
             }
-            Stmt::Match { expr, arms } => {
+            StmtKind::Match { expr, arms } => {
                 let target_ty = self.check_expr(expr, scopes);
                 let mut seen_wildcard = false;
                 let mut seen_literals = std::collections::HashSet::<String>::new();
 
                 if arms.is_empty() {
-                    self.error("Match statement must have at least one arm".to_string());
+                    self.error(
+                        "E-SEMA-006",
+                        "Match statement must have at least one arm".to_string(),
+                    );
                     return;
                 }
 
                 for (idx, arm) in arms.iter().enumerate() {
-                    if Self::pattern_has_wildcard(&arm.pattern) {
+                    if arm.guard.is_none() && arm.pattern.is_irrefutable() {
                         if seen_wildcard {
                             self.error(
+                                "E-SEMA-006",
                                 "Match statement can contain only one wildcard arm".to_string(),
                             );
                         }
                         if idx + 1 != arms.len() {
-                            self.error("Wildcard match arm `_` must be last".to_string());
+                            self.error(
+                                "E-SEMA-006",
+                                Self::catch_all_not_last_message(&arm.pattern),
+                            );
                         }
                         seen_wildcard = true;
                     }
 
-                    self.check_match_pattern(&arm.pattern, &target_ty, &mut seen_literals);
-
-                    scopes.push(HashMap::new());
-                    if let MatchPattern::Variant {
-                        binding: Some(binding),
-                        ..
-                    } = &arm.pattern
-                        && let Some(binding_ty) =
-                            Self::match_variant_binding_type(&arm.pattern, &target_ty)
-                        && let Some(scope) = scopes.last_mut()
-                    {
-                        scope.insert(binding.clone(), binding_ty);
+                    let bindings = self.check_match_arm_pattern(
+                        &arm.pattern,
+                        arm.guard.is_some(),
+                        &target_ty,
+                        &mut seen_literals,
+                    );
+
+                    self.push_scope(scopes, HashMap::new());
+                    for (binding, binding_ty) in bindings {
+                        self.note_binding(&binding, &binding_ty);
+                        if let Some(scope) = scopes.last_mut() {
+                            scope.insert(binding, binding_ty);
+                        }
                     }
+                    self.check_match_guard(arm.guard.as_ref(), scopes);
                     for s in &arm.body {
                         self.check_stmt(s, scopes, expected_ret);
                     }
                     scopes.pop();
                 }
 
-                Self::check_match_exhaustiveness(self, &target_ty, seen_wildcard, &seen_literals);
+                if !Self::check_match_exhaustiveness(
+                    self,
+                    &target_ty,
+                    seen_wildcard,
+                    &seen_literals,
+                ) {
+                    self.non_exhaustive_matches.insert(stmt.span);
+                }
             }
         }
     }
@@ -0,0 +1,306 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{AssignTarget, Expr, MatchPattern, Program, Stmt, StmtKind};
+
+use super::Checker;
+
+/// What a function body does that matters to `task.spawn` and global
+/// initialization: the first global it assigns directly or hands to a mutating
+/// builtin such as `vec.push`, the top-level functions
+/// it calls by name, and the other unshadowed names it reads.
+#[derive(Default)]
+pub(super) struct BodyEffects {
+    pub(super) assigned_global: Option<String>,
+    pub(super) callees: Vec<String>,
+    pub(super) reads: Vec<String>,
+}
+
+impl Checker {
+    /// Records, for every top-level function, a global it assigns either itself or
+    /// through functions it calls by name. Spawned tasks run on their own thread in
+    /// native builds, so `task.spawn` rejects such functions instead of letting tasks
+    /// race on shared globals.
+    pub(super) fn collect_global_writers(&mut self, program: &Program) {
+        let globals = program
+            .globals
+            .iter()
+            .map(|g| g.name.clone())
+            .chain(self.globals.keys().cloned())
+            .collect::<HashSet<_>>();
+        let effects = program
+            .functions
+            .iter()
+            .map(|f| {
+                let mut locals = f.params.iter().map(|p| p.name.clone()).collect();
+                let mut effects = BodyEffects::default();
+                collect_body(&f.body, &globals, &mut locals, &mut effects);
+                (f.name.clone(), effects)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut writers = effects
+            .iter()
+            .filter_map(|(name, e)| Some((name.clone(), e.assigned_global.clone()?)))
+            .collect::<HashMap<_, _>>();
+        loop {
+            let mut changed = false;
+            for (name, e) in &effects {
+                if writers.contains_key(name) {
+                    continue;
+                }
+                if let Some(global) = e.callees.iter().find_map(|c| writers.get(c)) {
+                    writers.insert(name.clone(), global.clone());
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        self.global_writers = writers;
+        self.task_globals = globals;
+    }
+
+    /// Global assigned when `task` runs, for a function named directly or written
+    /// inline as a function literal; other function values are not tracked.
+    pub(super) fn spawned_global_write(
+        &self,
+        task: &Expr,
+        scopes: &[HashMap<String, crate::types::TypeInfo>],
+    ) -> Option<String> {
+        match task {
+            Expr::Ident(name) if !scopes.iter().any(|s| s.contains_key(name)) => {
+                self.global_writers.get(name).cloned()
+            }
+            Expr::FnLit { params, body, .. } => {
+                let mut locals = params.iter().map(|p| p.name.clone()).collect();
+                let mut effects = BodyEffects::default();
+                collect_body(body, &self.task_globals, &mut locals, &mut effects);
+                effects.assigned_global.or_else(|| {
+                    effects
+                        .callees
+                        .iter()
+                        .find_map(|c| self.global_writers.get(c).cloned())
+                })
+            }
+            Expr::Group(inner) => self.spawned_global_write(inner, scopes),
+            _ => None,
+        }
+    }
+}
+
+/// Walks `body` recording assignments rooted at a global and calls by name.
+/// `locals` holds the parameters and `let` names in scope; nested blocks get a
+/// copy, so a `let` inside one shadows a global only until the block ends.
+pub(super) fn collect_body(
+    body: &[Stmt],
+    globals: &HashSet<String>,
+    locals: &mut HashSet<String>,
+    out: &mut BodyEffects,
+) {
+    for stmt in body {
+        match &stmt.kind {
+            StmtKind::Let { name, value, .. } => {
+                collect_expr(value, globals, locals, out);
+                locals.insert(name.clone());
+            }
+            StmtKind::LetPattern { pattern, value } => {
+                collect_expr(value, globals, locals, out);
+                bind_pattern(pattern, locals);
+            }
+            StmtKind::Assign { target, value } => {
+                let root = match target {
+                    AssignTarget::Ident(name) => Some(name.as_str()),
+                    AssignTarget::Index { base, index } => {
+                        collect_expr(index, globals, locals, out);
+                        root_ident(base)
+                    }
+                    AssignTarget::Field { base, .. } => root_ident(base),
+                };
+                if let Some(root) = root {
+                    note_assignment(root, globals, locals, out);
+                }
+                collect_expr(value, globals, locals, out);
+            }
+            StmtKind::Expr(expr) | StmtKind::Defer(expr) | StmtKind::Return(Some(expr)) => {
+                collect_expr(expr, globals, locals, out)
+            }
+            StmtKind::If {
+                cond,
+                then_body,
+                else_body,
+            } => {
+                collect_expr(cond, globals, locals, out);
+                collect_body(then_body, globals, &mut locals.clone(), out);
+                collect_body(else_body, globals, &mut locals.clone(), out);
+            }
+            StmtKind::While { cond, body } => {
+                collect_expr(cond, globals, locals, out);
+                collect_body(body, globals, &mut locals.clone(), out);
+            }
+            StmtKind::For {
+                init,
+                cond,
+                step,
+                body,
+            } => {
+                let locals = &mut locals.clone();
+                if let Some(init) = init {
+                    collect_body(std::slice::from_ref(init), globals, locals, out);
+                }
+                if let Some(cond) = cond {
+                    collect_expr(cond, globals, locals, out);
+                }
+                if let Some(step) = step {
+                    collect_body(std::slice::from_ref(step), globals, locals, out);
+                }
+                collect_body(body, globals, &mut locals.clone(), out);
+            }
+            StmtKind::ForIn {
+                name,
+                iterable,
+                body,
+            } => {
+                collect_expr(iterable, globals, locals, out);
+                let mut locals = locals.clone();
+                locals.insert(name.clone());
+                collect_body(body, globals, &mut locals, out);
+            }
+            StmtKind::Match { expr, arms } => {
+                collect_expr(expr, globals, locals, out);
+                for arm in arms {
+                    let mut locals = locals.clone();
+                    bind_pattern(&arm.pattern, &mut locals);
+                    if let Some(guard) = &arm.guard {
+                        collect_expr(guard, globals, &mut locals, out);
+                    }
+                    collect_body(&arm.body, globals, &mut locals, out);
+                }
+            }
+            StmtKind::Break | StmtKind::Continue | StmtKind::Return(None) => {}
+        }
+    }
+}
+
+pub(super) fn collect_expr(
+    expr: &Expr,
+    globals: &HashSet<String>,
+    locals: &mut HashSet<String>,
+    out: &mut BodyEffects,
+) {
+    match expr {
+        Expr::Call { callee, args } => {
+            if let Expr::Ident(name) = callee.as_ref()
+                && !locals.contains(name)
+            {
+                out.callees.push(name.clone());
+            } else {
+                collect_expr(callee, globals, locals, out);
+            }
+            if mutates_first_arg(callee, locals)
+                && let Some(root) = args.first().and_then(root_ident)
+            {
+                note_assignment(root, globals, locals, out);
+            }
+            for arg in args {
+                collect_expr(arg, globals, locals, out);
+            }
+        }
+        Expr::FnLit { params, body, .. } => {
+            let mut locals = locals.clone();
+            locals.extend(params.iter().map(|p| p.name.clone()));
+            collect_body(body, globals, &mut locals, out);
+        }
+        Expr::ArrayLit(items) | Expr::VecLit(items) | Expr::TupleLit(items) => {
+            for item in items {
+                collect_expr(item, globals, locals, out);
+            }
+        }
+        Expr::ArrayRepeat { value, .. } => collect_expr(value, globals, locals, out),
+        Expr::Index { base, index } => {
+            collect_expr(base, globals, locals, out);
+            collect_expr(index, globals, locals, out);
+        }
+        Expr::Slice { base, start, end } => {
+            collect_expr(base, globals, locals, out);
+            for bound in [start, end].into_iter().flatten() {
+                collect_expr(bound, globals, locals, out);
+            }
+        }
+        Expr::Field { base, .. } => collect_expr(base, globals, locals, out),
+        Expr::StructLit { fields, .. } => {
+            for (_, value) in fields {
+                collect_expr(value, globals, locals, out);
+            }
+        }
+        Expr::Unary { expr, .. } | Expr::Try(expr) | Expr::Group(expr) => {
+            collect_expr(expr, globals, locals, out)
+        }
+        Expr::Binary { left, right, .. } | Expr::CustomInfix { left, right, .. } => {
+            collect_expr(left, globals, locals, out);
+            collect_expr(right, globals, locals, out);
+        }
+        Expr::Match { expr, arms } => {
+            collect_expr(expr, globals, locals, out);
+            for arm in arms {
+                let mut locals = locals.clone();
+                bind_pattern(&arm.pattern, &mut locals);
+                if let Some(guard) = &arm.guard {
+                    collect_expr(guard, globals, &mut locals, out);
+                }
+                collect_expr(&arm.expr, globals, &mut locals, out);
+            }
+        }
+        Expr::Ident(name) => {
+            if !locals.contains(name) {
+                out.reads.push(name.clone());
+            }
+        }
+        Expr::IntLit(_)
+        | Expr::FloatLit(_)
+        | Expr::BoolLit(_)
+        | Expr::CharLit(_)
+        | Expr::StringLit(_)
+        | Expr::Path(_) => {}
+    }
+}
+
+fn note_assignment(
+    root: &str,
+    globals: &HashSet<String>,
+    locals: &HashSet<String>,
+    out: &mut BodyEffects,
+) {
+    if out.assigned_global.is_none() && globals.contains(root) && !locals.contains(root) {
+        out.assigned_global = Some(root.to_string());
+    }
+}
+
+/// Builtins that change the vector or map passed as their first argument.
+fn mutates_first_arg(callee: &Expr, locals: &HashSet<String>) -> bool {
+    let (package, name) = match callee {
+        Expr::Path(parts) if parts.len() == 2 => (parts[0].as_str(), parts[1].as_str()),
+        Expr::Field { base, field } => match base.as_ref() {
+            Expr::Ident(package) => (package.as_str(), field.as_str()),
+            _ => return false,
+        },
+        _ => return false,
+    };
+    !locals.contains(package)
+        && matches!(
+            (package, name),
+            ("vec", "push" | "set" | "delete" | "sort") | ("map", "insert" | "remove")
+        )
+}
+
+fn bind_pattern(pattern: &MatchPattern, locals: &mut HashSet<String>) {
+    locals.extend(pattern.bindings().into_iter().map(str::to_string));
+}
+
+fn root_ident(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Ident(name) => Some(name),
+        Expr::Index { base, .. } | Expr::Field { base, .. } | Expr::Group(base) => root_ident(base),
+        _ => None,
+    }
+}
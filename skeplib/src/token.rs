@@ -7,6 +7,8 @@ pub enum TokenKind {
     IntLit,
     FloatLit,
     StringLit,
+    CharLit,
+    DocComment,
     KwImport,
     KwFrom,
     KwAs,
@@ -16,6 +18,7 @@ pub enum TokenKind {
     KwOpr,
     KwPrecedence,
     KwStruct,
+    KwType,
     KwImpl,
     KwLet,
     KwIf,
@@ -25,12 +28,14 @@ pub enum TokenKind {
     KwBreak,
     KwContinue,
     KwReturn,
+    KwDefer,
     KwMatch,
     KwTrue,
     KwFalse,
     TyInt,
     TyFloat,
     TyBool,
+    TyChar,
     TyString,
     TyBytes,
     TyVoid,
@@ -41,8 +46,11 @@ pub enum TokenKind {
     LBrace,
     RBrace,
     Backtick,
+    Hash,
+    At,
     Comma,
     Dot,
+    DotDot,
     Question,
     Colon,
     Semi,
@@ -5,6 +5,7 @@ pub enum TypeInfo {
     Int,
     Float,
     Bool,
+    Char,
     String,
     Bytes,
     Void,
@@ -31,6 +32,7 @@ pub enum TypeInfo {
         params: Vec<TypeInfo>,
         ret: Box<TypeInfo>,
     },
+    Tuple(Vec<TypeInfo>),
     Unknown,
 }
 
@@ -40,6 +42,7 @@ impl TypeInfo {
             TypeName::Int => TypeInfo::Int,
             TypeName::Float => TypeInfo::Float,
             TypeName::Bool => TypeInfo::Bool,
+            TypeName::Char => TypeInfo::Char,
             TypeName::String => TypeInfo::String,
             TypeName::Bytes => TypeInfo::Bytes,
             TypeName::Void => TypeInfo::Void,
@@ -71,10 +74,26 @@ impl TypeInfo {
                 params: params.iter().map(TypeInfo::from_ast).collect(),
                 ret: Box::new(TypeInfo::from_ast(ret)),
             },
+            TypeName::Tuple(items) => {
+                TypeInfo::Tuple(items.iter().map(TypeInfo::from_ast).collect())
+            }
+            TypeName::Generic { name, args } => {
+                let args = args.iter().map(TypeInfo::from_ast).collect::<Vec<_>>();
+                TypeInfo::Named(generic_struct_name(name, &args))
+            }
         }
     }
 }
 
+/// An instantiation of a generic struct is an ordinary named struct called
+/// `Name[A, B]`, so each distinct argument list gets its own shape.
+pub fn generic_struct_name(name: &str, args: &[TypeInfo]) -> String {
+    format!(
+        "{name}[{}]",
+        args.iter().map(display_type).collect::<Vec<_>>().join(", ")
+    )
+}
+
 pub fn task_channel_type_name(value: &TypeInfo) -> String {
     format!("task.Channel[{}]", display_type(value))
 }
@@ -101,11 +120,25 @@ pub fn task_task_value_type(name: &str) -> Option<TypeInfo> {
     parse_display_type(inner)
 }
 
+/// Element type `T` of a lazy `iter.Iter[T]` pipeline. Pipelines only exist at
+/// compile time: lowering fuses them into the loop that consumes them.
+pub fn iter_type(elem: &TypeInfo) -> TypeInfo {
+    TypeInfo::Opaque(format!("iter.Iter[{}]", display_type(elem)))
+}
+
+pub fn iter_elem_type(name: &str) -> Option<TypeInfo> {
+    match name.strip_prefix("iter.Iter[")?.strip_suffix(']')? {
+        "Unknown" => Some(TypeInfo::Unknown),
+        inner => parse_display_type(inner),
+    }
+}
+
 pub fn display_type(value: &TypeInfo) -> String {
     match value {
         TypeInfo::Int => "Int".to_string(),
         TypeInfo::Float => "Float".to_string(),
         TypeInfo::Bool => "Bool".to_string(),
+        TypeInfo::Char => "Char".to_string(),
         TypeInfo::String => "String".to_string(),
         TypeInfo::Bytes => "Bytes".to_string(),
         TypeInfo::Void => "Void".to_string(),
@@ -126,6 +159,14 @@ pub fn display_type(value: &TypeInfo) -> String {
                 .join(", "),
             display_type(ret)
         ),
+        TypeInfo::Tuple(items) => format!(
+            "({})",
+            items
+                .iter()
+                .map(display_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
         TypeInfo::Unknown => "Unknown".to_string(),
     }
 }
@@ -135,6 +176,7 @@ fn parse_display_type(value: &str) -> Option<TypeInfo> {
         "Int" => Some(TypeInfo::Int),
         "Float" => Some(TypeInfo::Float),
         "Bool" => Some(TypeInfo::Bool),
+        "Char" => Some(TypeInfo::Char),
         "String" => Some(TypeInfo::String),
         "Bytes" => Some(TypeInfo::Bytes),
         "Void" => Some(TypeInfo::Void),
@@ -170,6 +212,13 @@ fn parse_display_type(value: &str) -> Option<TypeInfo> {
                     value: Box::new(parse_display_type(inner)?),
                 });
             }
+            if let Some(inner) = value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+                return split_top_level_types(inner)
+                    .into_iter()
+                    .map(parse_display_type)
+                    .collect::<Option<Vec<_>>>()
+                    .map(TypeInfo::Tuple);
+            }
             if (value.starts_with("task.Channel[") || value.starts_with("task.Task["))
                 && value.ends_with(']')
             {
@@ -180,10 +229,37 @@ fn parse_display_type(value: &str) -> Option<TypeInfo> {
     }
 }
 
+/// Splits `A, B[C, D], (E, F)` at the commas that are not nested in brackets.
+fn split_top_level_types(value: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (idx, ch) in value.char_indices() {
+        match ch {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(value[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(value[start..].trim());
+    parts
+}
+
 pub fn is_builtin_opaque_type(name: &str) -> bool {
     matches!(
         name,
-        "net.Socket" | "net.Listener" | "task.Task" | "task.Channel" | "ffi.Library" | "ffi.Symbol"
+        "net.Socket"
+            | "net.Listener"
+            | "net.UdpSocket"
+            | "store.Store"
+            | "task.Task"
+            | "task.Channel"
+            | "ffi.Library"
+            | "ffi.Symbol"
     ) || ((name.starts_with("task.Channel[") || name.starts_with("task.Task["))
         && name.ends_with(']'))
 }
@@ -23,12 +23,17 @@ pub struct TempProject {
 #[derive(Default)]
 pub struct DeterministicHost {
     out: Arc<Mutex<String>>,
+    err: Arc<Mutex<String>>,
 }
 
 impl DeterministicHost {
     pub fn captured_output(&self) -> Arc<Mutex<String>> {
         Arc::clone(&self.out)
     }
+
+    pub fn captured_errors(&self) -> Arc<Mutex<String>> {
+        Arc::clone(&self.err)
+    }
 }
 
 impl RtHost for DeterministicHost {
@@ -40,6 +45,14 @@ impl RtHost for DeterministicHost {
         Ok(())
     }
 
+    fn io_eprint(&mut self, text: &str) -> RtResult<()> {
+        self.err
+            .lock()
+            .expect("lock deterministic host")
+            .push_str(text);
+        Ok(())
+    }
+
     fn datetime_now_unix(&mut self) -> RtResult<i64> {
         Ok(123)
     }
@@ -287,11 +300,13 @@ pub fn require_llvm_tool(tool: &str) {
 pub fn assert_runtime_error_kind(err: &IrInterpError, expected: RtErrorKind) {
     let actual = match err {
         IrInterpError::DivisionByZero => RtErrorKind::DivisionByZero,
+        IrInterpError::IntegerOverflow => RtErrorKind::IntegerOverflow,
         IrInterpError::IndexOutOfBounds => RtErrorKind::IndexOutOfBounds,
         IrInterpError::TypeMismatch(_) => RtErrorKind::TypeMismatch,
         IrInterpError::InvalidField(_) => RtErrorKind::MissingField,
         IrInterpError::InvalidOperand(_) => RtErrorKind::InvalidArgument,
         IrInterpError::UnsupportedBuiltin(_) => RtErrorKind::UnsupportedBuiltin,
+        IrInterpError::Panic(_) => RtErrorKind::Panic,
         other => panic!("unsupported interpreter error for kind assertion: {other:?}"),
     };
     assert_eq!(actual, expected);
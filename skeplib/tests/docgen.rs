@@ -0,0 +1,159 @@
+mod common;
+
+use skeplib::docgen::{DocFormat, DocItemKind, collect_docs};
+use skeplib::resolver::resolve_project;
+
+fn sample_project(prefix: &str) -> (common::TempProject, std::path::PathBuf) {
+    let project = common::TempProject::new(prefix);
+    project.file(
+        "models/user.sk",
+        r#"
+/// A registered user.
+struct User { id: Int, name: String }
+
+/// Internal bookkeeping.
+struct Secret { key: String }
+
+impl User {
+  /// Returns the user's id.
+  fn getId(self) -> Int {
+    return self.id;
+  }
+}
+
+/// Builds a user with an empty name.
+fn makeUser(id: Int) -> User {
+  return User { id: id, name: "" };
+}
+
+fn helper() -> Int {
+  return 1;
+}
+
+/// Upper bound on ids.
+let maxId: Int = 100;
+
+/// Users keyed by name.
+type Directory = Map[String, User];
+
+export { User, Secret(opaque), makeUser as make, maxId, Directory };
+"#,
+    );
+    let entry = project.file(
+        "main.sk",
+        r#"
+from models.user import make;
+export { make } from models.user;
+
+fn main() -> Int {
+  return make(1).getId();
+}
+"#,
+    );
+    (project, entry)
+}
+
+#[test]
+fn docgen_collects_exported_symbols_with_signatures_and_docs() {
+    let (_project, entry) = sample_project("docgen_collect");
+    let graph = resolve_project(&entry).expect("resolve");
+    let docs = collect_docs(&graph).expect("docs");
+    assert_eq!(
+        docs.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+        vec!["main", "models.user"]
+    );
+
+    let user = &docs[1];
+    let items = user
+        .items
+        .iter()
+        .map(|i| {
+            (
+                i.kind,
+                i.name.as_str(),
+                i.signature.as_str(),
+                i.doc.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        items,
+        vec![
+            (
+                DocItemKind::Struct,
+                "Secret",
+                "struct Secret",
+                Some("Internal bookkeeping.")
+            ),
+            (
+                DocItemKind::Struct,
+                "User",
+                "struct User { id: Int, name: String }",
+                Some("A registered user.")
+            ),
+            (
+                DocItemKind::TypeAlias,
+                "Directory",
+                "type Directory = Map[String, User]",
+                Some("Users keyed by name.")
+            ),
+            (
+                DocItemKind::Fn,
+                "make",
+                "fn make(id: Int) -> User",
+                Some("Builds a user with an empty name.")
+            ),
+            (
+                DocItemKind::Global,
+                "maxId",
+                "let maxId: Int",
+                Some("Upper bound on ids.")
+            ),
+        ]
+    );
+    let methods = &user.items[1].methods;
+    assert_eq!(methods.len(), 1);
+    assert_eq!(methods[0].name, "User.getId");
+    assert_eq!(methods[0].signature, "fn getId(self) -> Int");
+    assert_eq!(methods[0].doc.as_deref(), Some("Returns the user's id."));
+
+    let main = &docs[0];
+    assert_eq!(main.items.len(), 1);
+    assert_eq!(main.items[0].signature, "fn make(id: Int) -> User");
+    assert_eq!(
+        main.items[0].doc.as_deref(),
+        Some("Builds a user with an empty name.")
+    );
+}
+
+#[test]
+fn docgen_renders_markdown_and_escaped_html() {
+    let project = common::TempProject::new("docgen_render");
+    let entry = project.file(
+        "main.sk",
+        r#"
+/// Returns `a` when a < b.
+fn pick(a: Int, b: Int) -> Int {
+  return a;
+}
+
+fn main() -> Int {
+  return pick(1, 2);
+}
+
+export { pick };
+"#,
+    );
+    let graph = resolve_project(&entry).expect("resolve");
+    let docs = collect_docs(&graph).expect("docs");
+
+    let markdown = docs[0].render(DocFormat::Markdown);
+    assert!(markdown.starts_with("# Module `main`\n"));
+    assert!(markdown.contains("## `pick`\n\n```skepa\nfn pick(a: Int, b: Int) -> Int\n```\n"));
+    assert!(markdown.contains("\nReturns `a` when a < b.\n"));
+
+    let html = docs[0].render(DocFormat::Html);
+    assert!(html.contains("<h2 id=\"pick\"><code>pick</code></h2>"));
+    assert!(html.contains("<pre><code>fn pick(a: Int, b: Int) -&gt; Int</code></pre>"));
+    assert!(html.contains("<p>Returns `a` when a &lt; b.</p>"));
+}
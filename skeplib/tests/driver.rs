@@ -0,0 +1,154 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use skeplib::driver::{Compiler, DriverDiagnostic, Emit, Phase};
+use skeplib::exit_codes::ExitReason;
+use skeplib::ir::{IrInterpreter, IrValue};
+
+#[test]
+fn compiler_emits_ir_and_llvm_ir_for_a_valid_project() {
+    let project = common::TempProject::new("skepa_driver_valid");
+    project.file("util.sk", "fn one() -> Int { return 1; }\nexport { one };");
+    let entry = project.file(
+        "main.sk",
+        "from util import one;\nfn main() -> Int { return one() + 1; }",
+    );
+
+    let output = Compiler::entry(&entry)
+        .emit(Emit::Ir)
+        .emit(Emit::LlvmIr)
+        .run();
+    assert!(output.succeeded(), "{:?}", output.diagnostics);
+    assert_eq!(output.graph.expect("graph").modules.len(), 2);
+    let program = output.ir.expect("ir");
+    assert!(program.functions.iter().any(|f| f.name == "main"));
+    assert!(output.llvm_ir.expect("llvm ir").contains("define"));
+
+    let checked = Compiler::entry(&entry).run();
+    assert!(checked.succeeded());
+    assert!(checked.ir.is_none() && checked.llvm_ir.is_none());
+
+    let directory = Compiler::directory(project.root()).emit(Emit::Ir).run();
+    assert_eq!(directory.failed, Some(Phase::Codegen));
+}
+
+#[test]
+fn compiler_stops_at_the_failing_phase_and_feeds_the_sink() {
+    let project = common::TempProject::new("skepa_driver_failures");
+    let bad_sema = project.file("main.sk", "fn main() -> Int {\n  return;\n  let x = 1;\n}");
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let sink_seen = Rc::clone(&seen);
+    let output = Compiler::entry(&bad_sema)
+        .emit(Emit::Ir)
+        .diagnostic_sink(move |diag: &DriverDiagnostic| sink_seen.borrow_mut().push(diag.phase()))
+        .run();
+    assert_eq!(output.failed, Some(Phase::Sema));
+    assert!(output.ir.is_none());
+    assert_eq!(*seen.borrow(), vec![Phase::Sema; output.diagnostics.len()]);
+
+    let missing = Compiler::entry(project.root().join("absent.sk")).run();
+    assert_eq!(missing.failed, Some(Phase::Resolve));
+    assert!(missing.graph.is_none());
+
+    let directory = Compiler::directory(project.root()).emit(Emit::Ir).run();
+    assert_eq!(directory.failed, Some(Phase::Sema));
+}
+
+#[test]
+fn compiler_runs_a_selected_entry_function_and_validates_its_signature() {
+    let project = common::TempProject::new("skepa_driver_entry_fn");
+    let entry = project.file(
+        "main.sk",
+        r#"
+fn seed() -> Int { return 40; }
+fn migrate() -> Int { return seed() + 2; }
+fn greet(name: String) -> Int { return 0; }
+fn label() -> String { return "x"; }
+fn main() -> Int { return 0; }
+"#,
+    );
+
+    let output = Compiler::entry(&entry)
+        .entry_function("migrate")
+        .emit(Emit::Ir)
+        .run();
+    assert!(output.succeeded(), "{:?}", output.diagnostics);
+    let program = output.ir.expect("ir");
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(42))
+    );
+
+    for (name, expected) in [
+        ("missing", "does not define function `missing`"),
+        ("greet", "must take no parameters, found 1"),
+        ("label", "must return Int or Void"),
+    ] {
+        let output = Compiler::entry(&entry)
+            .entry_function(name)
+            .emit(Emit::Ir)
+            .run();
+        assert_eq!(output.failed, Some(Phase::Codegen));
+        let message = match &output.diagnostics[..] {
+            [DriverDiagnostic::Codegen(message)] => message,
+            other => panic!("expected one codegen diagnostic, got {other:?}"),
+        };
+        assert!(message.contains(expected), "{message}");
+    }
+}
+
+#[test]
+fn compiler_checks_the_entry_main_signature_only_in_the_entry_module() {
+    let project = common::TempProject::new("skepa_driver_main_sig");
+    project.file(
+        "util.sk",
+        "fn main() -> String { return \"lib\"; }\nexport { main };",
+    );
+    let valid = project.file("ok.sk", "import util;\nfn main() -> Void { return; }");
+    let output = Compiler::entry(&valid).run();
+    assert!(output.succeeded(), "{:?}", output.diagnostics);
+
+    let invalid = project.file("bad.sk", "fn main() -> Float { return 1.0; }");
+    let output = Compiler::entry(&invalid).emit(Emit::Ir).run();
+    assert_eq!(output.failed, Some(Phase::Sema));
+    assert!(output.ir.is_none());
+    let messages = output
+        .diagnostics
+        .iter()
+        .map(|diag| match diag {
+            DriverDiagnostic::Sema(d) => d.message.clone(),
+            other => panic!("expected sema diagnostics, got {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec!["`main` must return `Int` or `Void`, found `Float`".to_string()]
+    );
+}
+
+#[test]
+fn exit_reason_maps_compile_outputs_to_stable_codes() {
+    let project = common::TempProject::new("skepa_driver_exit_reason");
+    let ok = project.file("ok.sk", "fn main() -> Int { return 0; }");
+    let sema = project.file("sema.sk", "fn main() -> Int { return true; }");
+    let folder = project.root().join("folder.sk");
+    std::fs::create_dir_all(&folder).expect("create directory named like a module");
+
+    let reason = |path: &std::path::Path| ExitReason::of_output(&Compiler::entry(path).run());
+    assert_eq!(reason(&ok), ExitReason::Ok);
+    assert_eq!(reason(&sema), ExitReason::Sema);
+    assert_eq!(reason(&folder), ExitReason::Io);
+    assert_eq!(
+        reason(&project.root().join("absent.sk")),
+        ExitReason::Resolve
+    );
+
+    let codes = ExitReason::ALL.map(ExitReason::code);
+    assert_eq!(codes, [0, 2, 3, 10, 11, 12, 15, 101, 20]);
+    for reason in ExitReason::ALL {
+        assert_eq!(ExitReason::from_code(reason.code()), Some(reason));
+    }
+    assert_eq!(ExitReason::from_code(1), None);
+}
@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+
+use skepart::RtErrorKind;
+use skeplib::error_codes::{ERROR_CODES, lookup};
+use skeplib::parser::Parser;
+use skeplib::sema::analyze_source;
+
+#[test]
+fn error_codes_are_unique_and_fully_documented() {
+    let mut seen = HashSet::new();
+    for entry in ERROR_CODES {
+        assert!(seen.insert(entry.code), "duplicate code {}", entry.code);
+        assert!(entry.code.starts_with("E-"), "{}", entry.code);
+        assert!(!entry.title.is_empty(), "{} has no title", entry.code);
+        assert!(
+            !entry.explanation.is_empty(),
+            "{} has no explanation",
+            entry.code
+        );
+        assert!(!entry.example.is_empty(), "{} has no example", entry.code);
+        assert!(!entry.fix.is_empty(), "{} has no fix", entry.code);
+    }
+}
+
+#[test]
+fn error_code_lookup_is_case_insensitive_and_renders_all_sections() {
+    let entry = lookup("e-mod-cycle").expect("E-MOD-CYCLE registered");
+    assert_eq!(entry.code, "E-MOD-CYCLE");
+    let text = entry.render();
+    assert!(text.starts_with("E-MOD-CYCLE (resolve): circular import\n"));
+    assert!(text.contains("\nExample:\n\n    // a.sk\n"));
+    assert!(text.contains("\nFix:\n\n"));
+    assert!(lookup("E-SEMA-999").is_none());
+}
+
+#[test]
+fn every_resolver_and_runtime_code_is_registered() {
+    for code in [
+        "E-MOD-NOT-FOUND",
+        "E-MOD-CYCLE",
+        "E-MOD-AMBIG",
+        "E-MOD-DUPLICATE",
+        "E-MOD-IO",
+        "E-MOD-PATH",
+        "E-EXPORT-UNKNOWN",
+        "E-IMPORT-NOT-EXPORTED",
+        "E-IMPORT-CONFLICT",
+        "E-CAP-DENIED",
+        "E-PARSE",
+        "E-SEMA",
+        "E-CODEGEN",
+        "E-RUNTIME",
+    ] {
+        assert!(lookup(code).is_some(), "{code} missing from registry");
+    }
+    for kind in [
+        RtErrorKind::DivisionByZero,
+        RtErrorKind::IntegerOverflow,
+        RtErrorKind::IndexOutOfBounds,
+        RtErrorKind::TypeMismatch,
+        RtErrorKind::MissingField,
+        RtErrorKind::InvalidArgument,
+        RtErrorKind::Io,
+        RtErrorKind::Process,
+        RtErrorKind::UnsupportedBuiltin,
+        RtErrorKind::Panic,
+    ] {
+        let entry = lookup(kind.code()).expect("runtime code registered");
+        assert_eq!(entry.phase, "runtime");
+    }
+}
+
+#[test]
+fn diagnostics_carry_numbered_codes_from_where_they_are_reported() {
+    let cases = [
+        ("fn main() -> Int { return 1 $ 2; }", "E-PARSE-001"),
+        ("/* never closed", "E-PARSE-002"),
+        (
+            "fn main() -> Int { let x = 0b12; return x; }",
+            "E-PARSE-003",
+        ),
+        ("fn main() -> Int { return 0 }", "E-PARSE-004"),
+        (
+            "fn main() -> Int { let s = \"\\q\"; return 0; }",
+            "E-PARSE-005",
+        ),
+        (
+            "from utils import add, add;\nfn main() -> Int { return 0; }",
+            "E-PARSE-006",
+        ),
+        (
+            "struct U { id: Int }\nimpl U {\n  fn id(self: Int) -> Int { return 0; }\n}",
+            "E-PARSE-007",
+        ),
+        ("fn main() -> Int { return 4 `xoxo` 2; }", "E-PARSE-008"),
+        (
+            "type A = B;\ntype B = A;\nfn main() -> Int { return 0; }",
+            "E-PARSE-009",
+        ),
+        (
+            "#allow(unused_variable)\nfn main() -> Int { return 0; }",
+            "E-PARSE-010",
+        ),
+    ];
+    for (src, code) in cases {
+        let (_program, diags) = Parser::parse_source(src);
+        let first = &diags.as_slice()[0];
+        assert_eq!(first.code, Some(code), "{first:?}");
+    }
+
+    let cases = [
+        ("fn main() -> Int { return count; }", "E-SEMA-001"),
+        (
+            "struct U { id: Int }\nfn main() -> Int { let u = U { id: 1 }; return u.age; }",
+            "E-SEMA-002",
+        ),
+        (
+            "fn h() -> Int { return 1; }\nfn h() -> Int { return 2; }\nfn main() -> Int { return 0; }",
+            "E-SEMA-003",
+        ),
+        (
+            "fn add(a: Int, b: Int) -> Int { return a + b; }\nfn main() -> Int { return add(1); }",
+            "E-SEMA-004",
+        ),
+        ("fn main() -> Int { return true; }", "E-SEMA-005"),
+        ("fn main() -> Int { break; return 0; }", "E-SEMA-007"),
+        (
+            "fn main() -> Int { let n: Int = \"three\"; return n; }",
+            "E-SEMA-010",
+        ),
+        ("fn main() -> Int { return (1)(2); }", "E-SEMA-011"),
+        (
+            "fn main() -> Int {\n  let n = 3;\n  match (n) {\n    Some(v) => { return v; }\n    _ => { return 0; }\n  }\n}",
+            "E-SEMA-014",
+        ),
+        (
+            "fn main() -> Int {\n  let y = 1;\n  let f = fn(x: Int) -> Int { return x + y; };\n  return f(1);\n}",
+            "E-SEMA-016",
+        ),
+        (
+            "fn main() -> Int { return str.len(\"abc\"); }",
+            "E-SEMA-018",
+        ),
+        ("fn main() -> Int { return 0; let dead = 1; }", "E-SEMA-021"),
+        (
+            "import task;\nlet hits: Int = 0;\nfn bump() -> Int { hits = hits + 1; return hits; }\nfn main() -> Int { return task.join(task.spawn(bump)); }",
+            "E-SEMA-023",
+        ),
+        (
+            "struct Box[T] { value: T }\nfn main() -> Int { let b: Box = Box[Int] { value: 1 }; return 0; }",
+            "E-SEMA-024",
+        ),
+        (
+            "struct Node { value: Int, next: Node }\nfn main() -> Int { return 0; }",
+            "E-SEMA-025",
+        ),
+        (
+            "@deprecated(\"use total\")\nfn sum(a: Int, b: Int) -> Int { return a + b; }\nfn main() -> Int { return sum(1, 2); }",
+            "E-SEMA-026",
+        ),
+        (
+            "@test\nfn check(n: Int) -> Int { return n; }\nfn main() -> Int { return 0; }",
+            "E-SEMA-027",
+        ),
+        (
+            "let total: Int = read();\nfn read() -> Int { return base; }\nlet base: Int = 1;\nfn main() -> Int { return total; }",
+            "E-SEMA-028",
+        ),
+        (
+            "import serde;\nfn main() -> Int {\n  let f = fn(x: Int) -> Int { return x; };\n  let text = serde.toJson(f);\n  return 0;\n}",
+            "E-SEMA-029",
+        ),
+    ];
+    for (src, code) in cases {
+        let (_result, diags) = analyze_source(src);
+        let first = &diags.as_slice()[0];
+        assert_eq!(first.code, Some(code), "{first:?}");
+    }
+}
+
+#[test]
+fn every_reported_diagnostic_carries_a_code_of_its_phase() {
+    let (_program, diags) =
+        Parser::parse_source("fn main() -> Int { let s = \"\\q\"; return 1 $ 2 }");
+    assert!(!diags.as_slice().is_empty());
+    for diag in diags.as_slice() {
+        let code = diag.code.unwrap_or_else(|| panic!("no code: {diag:?}"));
+        assert_eq!(lookup(code).expect("registered").phase, "parse");
+    }
+
+    let (_result, diags) =
+        analyze_source("fn main() -> Int { let x = 1; x(); let y: Int = x[0]; return missing; }");
+    assert!(!diags.as_slice().is_empty());
+    for diag in diags.as_slice() {
+        let code = diag.code.unwrap_or_else(|| panic!("no code: {diag:?}"));
+        assert_eq!(lookup(code).expect("registered").phase, "sema");
+    }
+}
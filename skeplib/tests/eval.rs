@@ -0,0 +1,81 @@
+use skepart::{RtString, RtValue};
+use skeplib::eval::{EvalBinding, EvalError, eval_expr};
+use skeplib::types::TypeInfo;
+
+#[test]
+fn eval_expr_computes_value_from_bindings() {
+    let bindings = [
+        EvalBinding::new("price", TypeInfo::Int, RtValue::Int(40)),
+        EvalBinding::new("tax", TypeInfo::Float, RtValue::Float(0.5)),
+        EvalBinding::new(
+            "name",
+            TypeInfo::String,
+            RtValue::String(RtString::from("vip")),
+        ),
+    ];
+    let value =
+        eval_expr("price + 2 + str.len(name)", &bindings).expect("expression should evaluate");
+    assert_eq!(value, RtValue::Int(45));
+    let value = eval_expr("tax * 4.0 > 1.5 && name == \"vip\"", &bindings)
+        .expect("expression should evaluate");
+    assert_eq!(value, RtValue::Bool(true));
+}
+
+#[test]
+fn eval_expr_reports_parse_and_type_errors() {
+    let err = eval_expr("1 + ; fn evil() {}", &[]).expect_err("should not parse");
+    assert!(matches!(err, EvalError::Parse(_)), "{err:?}");
+    let err = eval_expr("1 + 2; 3", &[]).expect_err("trailing tokens should be rejected");
+    assert!(matches!(err, EvalError::Parse(_)), "{err:?}");
+
+    let bindings = [EvalBinding::new(
+        "flag",
+        TypeInfo::Bool,
+        RtValue::Bool(true),
+    )];
+    let err = eval_expr("flag + 1", &bindings).expect_err("should not type-check");
+    assert!(matches!(err, EvalError::Type(_)), "{err:?}");
+    let err = eval_expr("missing", &bindings).expect_err("unknown names are errors");
+    assert!(
+        err.to_string().contains("Unknown variable `missing`"),
+        "{err}"
+    );
+}
+
+#[test]
+fn eval_expr_cannot_reach_host_io() {
+    let err = eval_expr("io.println(\"hi\")", &[]).expect_err("io is not in scope");
+    assert!(matches!(err, EvalError::Type(_)), "{err:?}");
+    let err = eval_expr("fs.exists(\"/\")", &[]).expect_err("fs is not in scope");
+    assert!(matches!(err, EvalError::Type(_)), "{err:?}");
+}
+
+#[test]
+fn eval_expr_rejects_mismatched_or_unsupported_bindings() {
+    let err = eval_expr(
+        "x",
+        &[EvalBinding::new("x", TypeInfo::Int, RtValue::Bool(true))],
+    )
+    .expect_err("value must match declared type");
+    assert!(matches!(err, EvalError::Unsupported(_)), "{err:?}");
+    let err = eval_expr(
+        "0",
+        &[EvalBinding::new(
+            "user",
+            TypeInfo::Named("User".to_string()),
+            RtValue::Unit,
+        )],
+    )
+    .expect_err("struct bindings are not supported");
+    assert!(
+        err.to_string().contains("Binding `user` has type `User`"),
+        "{err}"
+    );
+}
+
+#[test]
+fn eval_expr_reports_runtime_errors() {
+    let bindings = [EvalBinding::new("d", TypeInfo::Int, RtValue::Int(0))];
+    let err = eval_expr("10 / d", &bindings).expect_err("division by zero");
+    assert!(matches!(err, EvalError::Runtime(_)), "{err:?}");
+}
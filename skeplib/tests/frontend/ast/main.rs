@@ -1,7 +1,7 @@
 #[path = "../../common.rs"]
 mod common;
 
-use skeplib::ast::{Expr, FnDecl, ImportDecl, Param, Program, Stmt, TypeName};
+use skeplib::ast::{Expr, FnDecl, ImportDecl, Param, Program, StmtKind, TypeName};
 use skeplib::parser::Parser;
 
 #[test]
@@ -27,9 +27,12 @@ fn create_program_with_one_import_and_one_function() {
             is_extern: false,
             extern_library: None,
             name: "main".to_string(),
+            span: Default::default(),
             params: Vec::new(),
             return_type: Some(TypeName::Int),
             body: Vec::new(),
+            doc: None,
+            attributes: Vec::new(),
         }],
     };
 
@@ -60,15 +63,20 @@ fn struct_and_impl_ast_nodes_roundtrip_data() {
                 ty: TypeName::String,
             },
         ],
+        doc: None,
+        attributes: Vec::new(),
     };
     let m = skeplib::ast::MethodDecl {
         name: "label".to_string(),
         params: vec![Param {
             name: "prefix".to_string(),
             ty: TypeName::String,
+            span: Default::default(),
         }],
         return_type: Some(TypeName::String),
-        body: vec![Stmt::Return(Some(Expr::StringLit("x".to_string())))],
+        body: vec![StmtKind::Return(Some(Expr::StringLit("x".to_string()))).into()],
+        doc: None,
+        attributes: Vec::new(),
     };
     let i = skeplib::ast::ImplDecl {
         target: "User".to_string(),
@@ -117,13 +125,16 @@ fn function_can_store_return_zero_stmt() {
         is_extern: false,
         extern_library: None,
         name: "main".to_string(),
+        span: Default::default(),
         params: Vec::new(),
         return_type: Some(TypeName::Int),
-        body: vec![Stmt::Return(Some(Expr::IntLit(0)))],
+        body: vec![StmtKind::Return(Some(Expr::IntLit(0))).into()],
+        doc: None,
+        attributes: Vec::new(),
     };
 
     assert_eq!(function.body.len(), 1);
-    assert_eq!(function.body[0], Stmt::Return(Some(Expr::IntLit(0))));
+    assert_eq!(function.body[0].kind, StmtKind::Return(Some(Expr::IntLit(0))));
 }
 
 #[test]
@@ -141,18 +152,23 @@ fn function_can_store_params_and_return_type() {
         is_extern: false,
         extern_library: None,
         name: "add".to_string(),
+        span: Default::default(),
         params: vec![
             Param {
                 name: "a".to_string(),
                 ty: TypeName::Int,
+                span: Default::default(),
             },
             Param {
                 name: "b".to_string(),
                 ty: TypeName::Int,
+                span: Default::default(),
             },
         ],
         return_type: Some(TypeName::Int),
-        body: vec![Stmt::Return(Some(Expr::IntLit(0)))],
+        body: vec![StmtKind::Return(Some(Expr::IntLit(0))).into()],
+        doc: None,
+        attributes: Vec::new(),
     };
     assert_eq!(function.params.len(), 2);
     assert_eq!(function.params[0].name, "a");
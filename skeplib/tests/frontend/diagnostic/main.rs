@@ -1,4 +1,4 @@
-use skeplib::diagnostic::{Diagnostic, DiagnosticBag, DiagnosticLevel, Span};
+use skeplib::diagnostic::{Diagnostic, DiagnosticBag, DiagnosticLevel, Span, render_snippet};
 
 #[test]
 fn span_creation_and_ordering() {
@@ -67,3 +67,54 @@ fn diagnostic_bag_into_vec_keeps_order() {
     assert_eq!(out[1].message, "b");
     assert_eq!(out[2].message, "c");
 }
+
+#[test]
+fn snippet_underlines_primary_span_and_labels() {
+    let source = "fn a() -> Int { return 1; }\nfn a() -> Int { return 2; }\n";
+    let d = Diagnostic::error("dup", Span::new(28, 32, 2, 1))
+        .with_label(Span::new(0, 4, 1, 1), "first declared here");
+    assert_eq!(
+        d.render_snippet(source, false),
+        "  |\n\
+         2 | fn a() -> Int { return 2; }\n\
+         \x20 | ^^^^\n\
+         \x20 |\n\
+         1 | fn a() -> Int { return 1; }\n\
+         \x20 | ---- first declared here\n"
+    );
+}
+
+#[test]
+fn snippet_skips_approximate_primary_span_but_keeps_labels() {
+    let source = "fn main() -> Int {\n  return x;\n}\n";
+    let d = Diagnostic::error("Unknown variable `x`", Span::new(0, 7, 1, 1)).approximate();
+    assert_eq!(d.render_snippet(source, false), "");
+    let labelled = d.with_label(Span::new(28, 29, 2, 10), "used here");
+    assert_eq!(
+        labelled.render_snippet(source, false),
+        "  |\n\
+         2 |   return x;\n\
+         \x20 |          - used here\n"
+    );
+}
+
+#[test]
+fn snippet_clamps_width_and_skips_spans_without_lines() {
+    let source = "let x = 1;\n";
+    let rendered = render_snippet(source, Span::new(0, 99, 1, 9), &[], false);
+    assert!(rendered.ends_with("  | \x20       ^^\n"), "{rendered:?}");
+    assert_eq!(render_snippet(source, Span::default(), &[], false), "");
+    assert_eq!(
+        render_snippet(source, Span::new(0, 1, 5, 1), &[], false),
+        ""
+    );
+}
+
+#[test]
+fn snippet_colors_markers_only_when_requested() {
+    let source = "return y;\n";
+    let plain = render_snippet(source, Span::new(7, 8, 1, 8), &[], false);
+    assert!(!plain.contains('\x1b'));
+    let colored = render_snippet(source, Span::new(7, 8, 1, 8), &[], true);
+    assert!(colored.contains("\x1b[1;31m^\x1b[0m"));
+}
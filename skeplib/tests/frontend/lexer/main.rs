@@ -93,6 +93,63 @@ fn lexes_operators_and_punctuation() {
     assert_eq!(got, want);
 }
 
+#[test]
+fn lexes_range_dots_without_eating_float_literals() {
+    let got = kinds("s[1..4] x.y 1.5");
+    let want = vec![
+        TokenKind::Ident,
+        TokenKind::LBracket,
+        TokenKind::IntLit,
+        TokenKind::DotDot,
+        TokenKind::IntLit,
+        TokenKind::RBracket,
+        TokenKind::Ident,
+        TokenKind::Dot,
+        TokenKind::Ident,
+        TokenKind::FloatLit,
+        TokenKind::Eof,
+    ];
+    assert_eq!(got, want);
+}
+
+#[test]
+fn lexes_char_literals_and_char_type() {
+    let (tokens, diags) = lex("let c: Char = '\\'';");
+    common::assert_no_diags(&diags);
+    assert_eq!(tokens[3].kind, TokenKind::TyChar);
+    assert_eq!(tokens[5].kind, TokenKind::CharLit);
+    assert_eq!(tokens[5].lexeme, "'\\''");
+}
+
+#[test]
+fn lexes_prefixed_and_separated_numeric_literals() {
+    let (tokens, diags) = lex("0xFF 0o77 0b1010 1_000_000 1_000.5");
+    common::assert_no_diags(&diags);
+    let lexemes: Vec<&str> = tokens.iter().map(|t| t.lexeme.as_str()).collect();
+    assert_eq!(
+        lexemes,
+        vec!["0xFF", "0o77", "0b1010", "1_000_000", "1_000.5", ""]
+    );
+    assert_eq!(tokens[3].kind, TokenKind::IntLit);
+    assert_eq!(tokens[4].kind, TokenKind::FloatLit);
+}
+
+#[test]
+fn reports_malformed_numeric_literals() {
+    for (src, msg) in [
+        ("0x", "Expected hexadecimal digits after `0x`"),
+        ("0b102", "Invalid digit `2` in binary literal `0b102`"),
+        ("0o8", "Invalid digit `8` in octal literal `0o8`"),
+        ("1__0", "must appear between digits"),
+        ("10_", "must appear between digits"),
+        ("0x_FF", "must appear between digits"),
+    ] {
+        let (_tokens, diags) = lex(src);
+        assert_eq!(diags.len(), 1, "{src}: {:?}", diags.as_slice());
+        assert!(diags.as_slice()[0].message.contains(msg), "{src}");
+    }
+}
+
 #[test]
 fn lexes_literals() {
     let (tokens, diags) = lex("123 3.14 \"hello\" true false");
@@ -164,14 +221,14 @@ fn reports_unterminated_string() {
 
 #[test]
 fn reports_unknown_character() {
-    let (_tokens, diags) = lex("@");
+    let (_tokens, diags) = lex("$");
     assert_eq!(diags.len(), 1);
     assert!(diags.as_slice()[0].message.contains("Unexpected character"));
 }
 
 #[test]
 fn unknown_character_reports_line_and_column() {
-    let (_tokens, diags) = lex("let x = 1;\n@");
+    let (_tokens, diags) = lex("let x = 1;\n$");
     assert_eq!(diags.len(), 1);
     assert_eq!(diags.as_slice()[0].span.line, 2);
     assert_eq!(diags.as_slice()[0].span.col, 1);
@@ -201,6 +258,30 @@ fn reports_unterminated_block_comment() {
     );
 }
 
+#[test]
+fn skips_nested_block_comments() {
+    let (tokens, diags) = lex("/* outer /* inner */ still comment */ return;");
+    common::assert_no_diags(&diags);
+    assert_eq!(tokens[0].kind, TokenKind::KwReturn);
+
+    let (_tokens, diags) = lex("/* outer /* inner */ never closed");
+    assert_eq!(diags.len(), 1);
+    assert!(
+        diags.as_slice()[0]
+            .message
+            .contains("Unterminated block comment")
+    );
+}
+
+#[test]
+fn lexes_triple_slash_as_doc_comment_token() {
+    let (tokens, diags) = lex("/// Adds.\n//// plain\n// plain\nfn");
+    common::assert_no_diags(&diags);
+    assert_eq!(tokens[0].kind, TokenKind::DocComment);
+    assert_eq!(tokens[0].lexeme, "/// Adds.");
+    assert_eq!(tokens[1].kind, TokenKind::KwFn);
+}
+
 #[test]
 fn reports_single_ampersand_and_pipe() {
     let (_tokens, diags) = lex("&");
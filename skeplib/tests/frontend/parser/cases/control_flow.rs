@@ -13,8 +13,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::If {
+    match &program.functions[0].body[0].kind {
+        StmtKind::If {
             cond,
             then_body,
             else_body,
@@ -39,14 +39,14 @@ fn main() -> Int {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[0].body[0] {
-        Stmt::Match { expr, arms } => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Match { expr, arms } => {
             assert_eq!(*expr, Expr::IntLit(1));
             assert_eq!(arms.len(), 3);
             assert_eq!(arms[0].pattern, MatchPattern::Literal(MatchLiteral::Int(0)));
             assert_eq!(arms[1].pattern, MatchPattern::Literal(MatchLiteral::Int(1)));
             assert_eq!(arms[2].pattern, MatchPattern::Wildcard);
-            assert!(matches!(arms[0].body[0], Stmt::Return(_)));
+            assert!(matches!(arms[0].body[0].kind, StmtKind::Return(_)));
         }
         _ => panic!("expected match statement"),
     }
@@ -63,8 +63,8 @@ fn main() -> Int {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[0].body[0] {
-        Stmt::Match { arms, .. } => match &arms[0].pattern {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Match { arms, .. } => match &arms[0].pattern {
             MatchPattern::Or(parts) => {
                 assert_eq!(parts.len(), 2);
                 assert_eq!(
@@ -110,17 +110,117 @@ fn main() -> Int {
 }
 
 #[test]
-fn reports_invalid_match_pattern_identifier() {
+fn parses_match_arm_guards() {
+    let src = r#"
+fn main() -> Int {
+  match (n) {
+    x if x > 10 => { return x; }
+    _ => { return 0; }
+  }
+  return match (n) { 0 | 1 if flag => 1, _ => 2 };
+}
+"#;
+    let program = parse_ok(src);
+    let StmtKind::Match { arms, .. } = &program.functions[0].body[0].kind else {
+        panic!("expected match");
+    };
+    assert_eq!(arms[0].pattern, MatchPattern::Binding("x".to_string()));
+    assert!(matches!(
+        &arms[0].guard,
+        Some(Expr::Binary {
+            op: BinaryOp::Gt,
+            ..
+        })
+    ));
+    assert!(arms[1].guard.is_none());
+    let StmtKind::Return(Some(Expr::Match { arms, .. })) = &program.functions[0].body[1].kind
+    else {
+        panic!("expected match expression");
+    };
+    assert!(matches!(arms[0].pattern, MatchPattern::Or(_)));
+    assert_eq!(arms[0].guard, Some(Expr::Ident("flag".to_string())));
+}
+
+#[test]
+fn reports_invalid_match_pattern_token() {
     let src = r#"
 fn main() -> Int {
   match (1) {
-    x => { return 1; }
+    + => { return 1; }
     _ => { return 0; }
   }
 }
 "#;
     let diags = parse_err(src);
-    assert_has_diag(&diags, "Expected match pattern (`_`, literal, or variant)");
+    assert_has_diag(
+        &diags,
+        "Expected match pattern (`_`, literal, variant, binding, struct, array, or tuple)",
+    );
+
+    let single = src.replace("+ =>", "(x) =>");
+    let diags = parse_err(&single);
+    assert_has_diag(&diags, "Tuple patterns need at least two elements");
+}
+
+#[test]
+fn parses_struct_array_and_binding_match_patterns() {
+    let src = r#"
+fn main() -> Int {
+  match (u) {
+    User { id: 1, name, .. } => { return 1; }
+    other => { return 0; }
+  }
+  match (xs) {
+    [0, second, rest..] => { return second; }
+    [_, _..] => { return 2; }
+  }
+  return 0;
+}
+"#;
+    let program = parse_ok(src);
+    let StmtKind::Match { arms, .. } = &program.functions[0].body[0].kind else {
+        panic!("expected match");
+    };
+    assert_eq!(
+        arms[0].pattern,
+        MatchPattern::Struct {
+            name: "User".to_string(),
+            fields: vec![
+                (
+                    "id".to_string(),
+                    MatchPattern::Literal(MatchLiteral::Int(1))
+                ),
+                (
+                    "name".to_string(),
+                    MatchPattern::Binding("name".to_string())
+                ),
+            ],
+            rest: true,
+        }
+    );
+    assert_eq!(arms[1].pattern, MatchPattern::Binding("other".to_string()));
+    let StmtKind::Match { arms, .. } = &program.functions[0].body[1].kind else {
+        panic!("expected match");
+    };
+    assert_eq!(
+        arms[0].pattern,
+        MatchPattern::Array {
+            items: vec![
+                MatchPattern::Literal(MatchLiteral::Int(0)),
+                MatchPattern::Binding("second".to_string()),
+            ],
+            rest: true,
+            rest_binding: Some("rest".to_string()),
+        }
+    );
+    assert_eq!(
+        arms[1].pattern,
+        MatchPattern::Array {
+            items: vec![MatchPattern::Wildcard],
+            rest: true,
+            rest_binding: None,
+        }
+    );
 }
 
 #[test]
@@ -148,8 +248,8 @@ fn main() -> Int {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[0].body[0] {
-        Stmt::Match { arms, .. } => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Match { arms, .. } => {
             assert_eq!(
                 arms[0].pattern,
                 MatchPattern::Variant {
@@ -181,8 +281,8 @@ fn main() -> Int {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => match value {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => match value {
             Expr::Match { arms, .. } => {
                 assert_eq!(arms.len(), 2);
                 assert_eq!(
@@ -219,8 +319,8 @@ fn main() -> Int {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[0].body[0] {
-        Stmt::Match { arms, .. } => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Match { arms, .. } => {
             assert_eq!(
                 arms[0].pattern,
                 MatchPattern::Variant {
@@ -279,8 +379,8 @@ fn main() -> Int {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => match value {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => match value {
             Expr::Match { arms, .. } => {
                 assert_eq!(arms.len(), 2);
                 assert_eq!(
@@ -315,8 +415,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::While { cond, body } => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::While { cond, body } => {
             assert_eq!(*cond, Expr::BoolLit(true));
             assert_eq!(body.len(), 1);
         }
@@ -337,15 +437,34 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::While { body, .. } => {
-            assert!(matches!(body[0], Stmt::Continue));
-            assert!(matches!(body[1], Stmt::Break));
+    match &program.functions[0].body[0].kind {
+        StmtKind::While { body, .. } => {
+            assert!(matches!(body[0].kind, StmtKind::Continue));
+            assert!(matches!(body[1].kind, StmtKind::Break));
         }
         _ => panic!("expected while"),
     }
 }
 
+#[test]
+fn parses_defer_statement() {
+    let src = r#"
+fn main() -> Int {
+  defer cleanup(1);
+  return 0;
+}
+"#;
+    let (program, diags) = Parser::parse_source(src);
+    assert_no_diags(&diags);
+    match &program.functions[0].body[0].kind {
+        StmtKind::Defer(Expr::Call { callee, args }) => {
+            assert_eq!(**callee, Expr::Ident("cleanup".to_string()));
+            assert_eq!(args.len(), 1);
+        }
+        other => panic!("expected defer, got {other:?}"),
+    }
+}
+
 #[test]
 fn parses_for_statement_with_all_clauses() {
     let src = r#"
@@ -358,8 +477,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::For {
+    match &program.functions[0].body[0].kind {
+        StmtKind::For {
             init,
             cond,
             step,
@@ -374,6 +493,32 @@ fn main() -> Int {
     }
 }
 
+#[test]
+fn parses_for_in_statement() {
+    let src = r#"
+fn main() -> Int {
+  for (x in iter.range(0, 3)) {
+    ping(x);
+  }
+  return 0;
+}
+"#;
+    let (program, diags) = Parser::parse_source(src);
+    assert_no_diags(&diags);
+    match &program.functions[0].body[0].kind {
+        StmtKind::ForIn {
+            name,
+            iterable,
+            body,
+        } => {
+            assert_eq!(name, "x");
+            assert!(matches!(iterable, Expr::Call { .. }));
+            assert_eq!(body.len(), 1);
+        }
+        _ => panic!("expected for-in"),
+    }
+}
+
 #[test]
 fn parses_for_with_no_clauses() {
     let src = r#"
@@ -386,8 +531,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::For {
+    match &program.functions[0].body[0].kind {
+        StmtKind::For {
             init,
             cond,
             step,
@@ -414,8 +559,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::For {
+    match &program.functions[0].body[0].kind {
+        StmtKind::For {
             init,
             cond,
             step,
@@ -443,8 +588,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[1] {
-        Stmt::For {
+    match &program.functions[0].body[1].kind {
+        StmtKind::For {
             init,
             cond,
             step,
@@ -472,8 +617,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[1] {
-        Stmt::For {
+    match &program.functions[0].body[1].kind {
+        StmtKind::For {
             init,
             cond,
             step,
@@ -503,10 +648,10 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::If { then_body, .. } => match &then_body[0] {
-            Stmt::While { body, .. } => {
-                assert!(matches!(body[0], Stmt::Expr(_)));
+    match &program.functions[0].body[0].kind {
+        StmtKind::If { then_body, .. } => match &then_body[0].kind {
+            StmtKind::While { body, .. } => {
+                assert!(matches!(body[0].kind, StmtKind::Expr(_)));
             }
             _ => panic!("expected nested while"),
         },
@@ -677,7 +822,7 @@ fn main() -> Int {
         program.functions[0]
             .body
             .iter()
-            .any(|s| matches!(s, Stmt::Return(Some(Expr::IntLit(0)))))
+            .any(|s| matches!(&s.kind, StmtKind::Return(Some(Expr::IntLit(0)))))
     );
 }
 
@@ -713,10 +858,10 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::If { else_body, .. } => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::If { else_body, .. } => {
             assert_eq!(else_body.len(), 1);
-            assert!(matches!(else_body[0], Stmt::If { .. }));
+            assert!(matches!(else_body[0].kind, StmtKind::If { .. }));
         }
         _ => panic!("expected if"),
     }
@@ -732,8 +877,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::Expr(Expr::Call { args, .. }) => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Expr(Expr::Call { args, .. }) => {
             assert_eq!(args.len(), 1);
             match &args[0] {
                 Expr::StringLit(s) => {
@@ -877,8 +1022,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::Expr(Expr::Call { callee, args }) => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Expr(Expr::Call { callee, args }) => {
             assert_eq!(args.len(), 1);
             assert!(matches!(&**callee, Expr::Call { .. }));
         }
@@ -896,8 +1041,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => match value {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => match value {
             Expr::Unary {
                 op: UnaryOp::Not,
                 expr,
@@ -957,11 +1102,11 @@ fn main() -> Int {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[0].body[1] {
-        Stmt::Match { arms, .. } => {
+    match &program.functions[0].body[1].kind {
+        StmtKind::Match { arms, .. } => {
             assert_eq!(arms.len(), 2);
-            assert!(matches!(arms[0].body[0], Stmt::For { .. }));
-            assert!(matches!(arms[0].body[1], Stmt::Return(_)));
+            assert!(matches!(arms[0].body[0].kind, StmtKind::For { .. }));
+            assert!(matches!(arms[0].body[1].kind, StmtKind::Return(_)));
         }
         _ => panic!("expected match statement"),
     }
@@ -11,20 +11,129 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => {
             assert!(matches!(value, Expr::ArrayLit(items) if items.len() == 3))
         }
         _ => panic!("expected let"),
     }
-    match &program.functions[0].body[1] {
-        Stmt::Let { value, .. } => {
+    match &program.functions[0].body[1].kind {
+        StmtKind::Let { value, .. } => {
             assert!(matches!(value, Expr::ArrayRepeat { size, .. } if *size == 8))
         }
         _ => panic!("expected let"),
     }
 }
 
+#[test]
+fn parses_vec_literals_with_optional_trailing_comma() {
+    let src = r#"
+fn main() -> Int {
+  let a = vec![1, 2, 3,];
+  let b: Vec[Int] = vec![];
+  return 0;
+}
+"#;
+    let (program, diags) = Parser::parse_source(src);
+    assert_no_diags(&diags);
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => {
+            assert!(matches!(value, Expr::VecLit(items) if items.len() == 3))
+        }
+        _ => panic!("expected let"),
+    }
+    match &program.functions[0].body[1].kind {
+        StmtKind::Let { value, .. } => {
+            assert!(matches!(value, Expr::VecLit(items) if items.is_empty()))
+        }
+        _ => panic!("expected let"),
+    }
+}
+
+#[test]
+fn parses_tuple_literals_and_element_access() {
+    let src = r#"
+fn main() -> Int {
+  let t = (1, ("a", true),);
+  let g = (1);
+  return t.1.0 + t.0;
+}
+"#;
+    let program = parse_ok(src);
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => {
+            assert!(matches!(value, Expr::TupleLit(items) if items.len() == 2))
+        }
+        _ => panic!("expected let"),
+    }
+    match &program.functions[0].body[1].kind {
+        StmtKind::Let { value, .. } => assert!(matches!(value, Expr::Group(_))),
+        _ => panic!("expected let"),
+    }
+    let StmtKind::Return(Some(Expr::Binary { left, .. })) = &program.functions[0].body[2].kind
+    else {
+        panic!("expected return of a sum");
+    };
+    assert_eq!(
+        **left,
+        Expr::Field {
+            base: Box::new(Expr::Field {
+                base: Box::new(Expr::Ident("t".to_string())),
+                field: "1".to_string(),
+            }),
+            field: "0".to_string(),
+        }
+    );
+}
+
+#[test]
+fn parser_reports_missing_bracket_after_vec_bang() {
+    let src = r#"
+fn main() -> Int {
+  let a = vec!(1, 2);
+  return 0;
+}
+"#;
+    let (_program, diags) = Parser::parse_source(src);
+    assert!(
+        diags
+            .as_slice()
+            .iter()
+            .any(|d| d.message.contains("Expected `[` after `vec!`"))
+    );
+}
+
+#[test]
+fn parses_slice_ranges_with_optional_bounds() {
+    let src = r#"
+fn main() -> Int {
+  let a = s[1..4];
+  let b = s[2..];
+  let c = s[..n + 1];
+  let d = s[..];
+  return 0;
+}
+"#;
+    let (program, diags) = Parser::parse_source(src);
+    assert_no_diags(&diags);
+    let bounds = program.functions[0]
+        .body
+        .iter()
+        .take(4)
+        .map(|stmt| match &stmt.kind {
+            StmtKind::Let {
+                value: Expr::Slice { start, end, .. },
+                ..
+            } => (start.is_some(), end.is_some()),
+            other => panic!("expected slice let, got {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bounds,
+        vec![(true, true), (true, false), (false, true), (false, false)]
+    );
+}
+
 #[test]
 fn parses_field_assignment_target() {
     let src = r#"
@@ -35,8 +144,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::Assign { target, value } => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Assign { target, value } => {
             assert!(matches!(target, AssignTarget::Field { .. }));
             assert_eq!(*value, Expr::IntLit(2));
         }
@@ -56,12 +165,12 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[1] {
-        Stmt::Let { value, .. } => assert!(matches!(value, Expr::Index { .. })),
+    match &program.functions[0].body[1].kind {
+        StmtKind::Let { value, .. } => assert!(matches!(value, Expr::Index { .. })),
         _ => panic!("expected index let"),
     }
-    match &program.functions[0].body[2] {
-        Stmt::Assign { target, .. } => assert!(matches!(target, AssignTarget::Index { .. })),
+    match &program.functions[0].body[2].kind {
+        StmtKind::Assign { target, .. } => assert!(matches!(target, AssignTarget::Index { .. })),
         _ => panic!("expected index assignment"),
     }
 }
@@ -77,8 +186,8 @@ fn main() -> Int {
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
     assert!(matches!(
-        program.functions[0].body[0],
-        Stmt::Expr(Expr::Ident(_))
+        program.functions[0].body[0].kind,
+        StmtKind::Expr(Expr::Ident(_))
     ));
 }
 
@@ -93,15 +202,15 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::Expr(Expr::Call { callee, args }) => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Expr(Expr::Call { callee, args }) => {
             assert!(matches!(&**callee, Expr::Ident(name) if name == "hello"));
             assert_eq!(args.len(), 2);
         }
         _ => panic!("expected call"),
     }
-    match &program.functions[0].body[1] {
-        Stmt::Expr(Expr::Call { callee, args }) => {
+    match &program.functions[0].body[1].kind {
+        StmtKind::Expr(Expr::Call { callee, args }) => {
             assert!(matches!(&**callee, Expr::Field { .. }));
             assert_eq!(args.len(), 1);
         }
@@ -137,8 +246,8 @@ fn main() -> Int {
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
 
-    let expr = match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => value,
+    let expr = match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => value,
         _ => panic!("expected let"),
     };
 
@@ -170,12 +279,61 @@ fn main() -> Float {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::Return(Some(Expr::FloatLit(v))) => assert_eq!(v, "3.14"),
+    match &program.functions[0].body[0].kind {
+        StmtKind::Return(Some(Expr::FloatLit(v))) => assert_eq!(v, "3.14"),
         other => panic!("expected float return, got {other:?}"),
     }
 }
 
+#[test]
+fn parses_char_literals_with_escapes() {
+    let src = r#"
+fn main() -> Int {
+  let a = 'a';
+  let b = '\n';
+  let c = '\'';
+  let d = 'é';
+  return 0;
+}
+"#;
+    let (program, diags) = Parser::parse_source(src);
+    assert_no_diags(&diags);
+    let chars = program.functions[0]
+        .body
+        .iter()
+        .take(4)
+        .map(|stmt| match &stmt.kind {
+            StmtKind::Let {
+                value: Expr::CharLit(c),
+                ..
+            } => *c,
+            other => panic!("expected char let, got {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(chars, vec!['a', '\n', '\'', 'é']);
+}
+
+#[test]
+fn reports_empty_and_multi_char_literals() {
+    let src = r#"
+fn main() -> Int {
+  let a = '';
+  let b = 'ab';
+  return 0;
+}
+"#;
+    let (_program, diags) = Parser::parse_source(src);
+    let count = diags
+        .as_slice()
+        .iter()
+        .filter(|d| {
+            d.message
+                .contains("Character literal must contain exactly one character")
+        })
+        .count();
+    assert_eq!(count, 2);
+}
+
 #[test]
 fn parses_grouped_expression_shape() {
     let src = r#"
@@ -186,8 +344,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    let expr = match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => value,
+    let expr = match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => value,
         _ => panic!("expected let"),
     };
     match expr {
@@ -222,8 +380,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::Let {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let {
             value: Expr::Binary {
                 op: BinaryOp::Mod, ..
             },
@@ -246,8 +404,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => assert!(matches!(
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => assert!(matches!(
             value,
             Expr::Unary {
                 op: UnaryOp::Neg,
@@ -256,8 +414,8 @@ fn main() -> Int {
         )),
         _ => panic!("expected let"),
     }
-    match &program.functions[0].body[1] {
-        Stmt::Let { value, .. } => assert!(matches!(
+    match &program.functions[0].body[1].kind {
+        StmtKind::Let { value, .. } => assert!(matches!(
             value,
             Expr::Unary {
                 op: UnaryOp::Pos,
@@ -266,8 +424,8 @@ fn main() -> Int {
         )),
         _ => panic!("expected let"),
     }
-    match &program.functions[0].body[2] {
-        Stmt::Let { value, .. } => assert!(matches!(
+    match &program.functions[0].body[2].kind {
+        StmtKind::Let { value, .. } => assert!(matches!(
             value,
             Expr::Unary {
                 op: UnaryOp::Not,
@@ -276,8 +434,8 @@ fn main() -> Int {
         )),
         _ => panic!("expected let"),
     }
-    match &program.functions[0].body[3] {
-        Stmt::Let { value, .. } => assert!(matches!(
+    match &program.functions[0].body[3].kind {
+        StmtKind::Let { value, .. } => assert!(matches!(
             value,
             Expr::Unary {
                 op: UnaryOp::BitNot,
@@ -301,8 +459,8 @@ opr xoxo(lhs: Int, rhs: Int) -> Int precedence 9 {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    match &program.functions[0].body[0] {
-        Stmt::Return(Some(Expr::CustomInfix {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Return(Some(Expr::CustomInfix {
             left,
             operator,
             right,
@@ -325,8 +483,8 @@ fn main() -> Int {
 "#;
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
-    let expr = match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => value,
+    let expr = match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => value,
         _ => panic!("expected let"),
     };
     match expr {
@@ -376,8 +534,8 @@ fn main() -> Int {
     assert_eq!(program.operators[0].name, "xoxo");
     assert_eq!(program.operators[0].precedence, 2);
     assert_eq!(program.operators[0].params.len(), 2);
-    match &program.functions[0].body[0] {
-        Stmt::Let {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let {
             value:
                 Expr::CustomInfix {
                     left,
@@ -420,8 +578,8 @@ fn main() -> Int {
     let (program, diags) = Parser::parse_source(src);
     assert_no_diags(&diags);
 
-    let a = match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => value,
+    let a = match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => value,
         _ => panic!("expected first let"),
     };
     match a {
@@ -449,8 +607,8 @@ fn main() -> Int {
         other => panic!("expected low-precedence custom infix, got {other:?}"),
     }
 
-    let b = match &program.functions[0].body[1] {
-        Stmt::Let { value, .. } => value,
+    let b = match &program.functions[0].body[1].kind {
+        StmtKind::Let { value, .. } => value,
         _ => panic!("expected second let"),
     };
     match b {
@@ -496,8 +654,8 @@ fn main() -> Int {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[0].body[0] {
-        Stmt::Let {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let {
             value: Expr::Index { base, index },
             ..
         } => {
@@ -535,8 +693,8 @@ fn main() -> Int {
         !diags.as_slice().is_empty(),
         "unknown operator should produce a diagnostic"
     );
-    match &program.functions[0].body[0] {
-        Stmt::Return(Some(expr)) => {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Return(Some(expr)) => {
             assert!(
                 !matches!(expr, Expr::CustomInfix { .. }),
                 "unknown operator must not invent a CustomInfix node; got {expr:?}"
@@ -549,3 +707,50 @@ fn main() -> Int {
         other => panic!("expected return statement, got {other:?}"),
     }
 }
+
+#[test]
+fn parses_prefixed_and_separated_integer_literals() {
+    let src = r#"
+fn main() -> Int {
+  let a = 0xFF;
+  let b = 0o77;
+  let c = 0b1010;
+  let d = 1_000_000;
+  let e = 0x7FFF_FFFF_FFFF_FFFF;
+  let f = 2_500.0_1;
+  return 0;
+}
+"#;
+    let (program, diags) = Parser::parse_source(src);
+    assert_no_diags(&diags);
+    let values: Vec<&Expr> = program.functions[0]
+        .body
+        .iter()
+        .filter_map(|stmt| match &stmt.kind {
+            StmtKind::Let { value, .. } => Some(value),
+            _ => None,
+        })
+        .collect();
+    assert!(matches!(values[0], Expr::IntLit(255)));
+    assert!(matches!(values[1], Expr::IntLit(63)));
+    assert!(matches!(values[2], Expr::IntLit(10)));
+    assert!(matches!(values[3], Expr::IntLit(1_000_000)));
+    assert!(matches!(values[4], Expr::IntLit(i64::MAX)));
+    assert!(matches!(values[5], Expr::FloatLit(v) if v == "2500.01"));
+}
+
+#[test]
+fn reports_out_of_range_hex_literal_and_malformed_literal() {
+    let src = r#"
+fn main() -> Int {
+  let a = 0x8000_0000_0000_0000;
+  return 0b12;
+}
+"#;
+    let diags = parse_err(src);
+    assert_has_diag(
+        &diags,
+        "Integer literal `0x8000_0000_0000_0000` is out of range for `Int`",
+    );
+    assert_has_diag(&diags, "Invalid digit `2` in binary literal `0b12`");
+}
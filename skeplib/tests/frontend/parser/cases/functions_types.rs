@@ -164,6 +164,23 @@ fn parse(data: String) -> Result[Int, String] {
     );
 }
 
+#[test]
+fn parses_tuple_type_annotations() {
+    let src = r#"
+fn split(data: String) -> (Int, (String, Bool)) {
+  return 0;
+}
+"#;
+    let program = parse_ok(src);
+    assert_eq!(
+        program.functions[0].return_type,
+        Some(TypeName::Tuple(vec![
+            TypeName::Int,
+            TypeName::Tuple(vec![TypeName::String, TypeName::Bool]),
+        ]))
+    );
+}
+
 #[test]
 fn parses_static_array_type_annotations() {
     let src = r#"
@@ -256,8 +273,8 @@ fn main() -> Int {
 "#;
     let program = parse_ok(src);
     let body = &program.functions[0].body;
-    match &body[0] {
-        Stmt::Let { value, .. } => match value {
+    match &body[0].kind {
+        StmtKind::Let { value, .. } => match value {
             Expr::FnLit {
                 params,
                 return_type,
@@ -267,7 +284,7 @@ fn main() -> Int {
                 assert_eq!(params[0].name, "x");
                 assert_eq!(params[0].ty, TypeName::Int);
                 assert_eq!(*return_type, TypeName::Int);
-                assert!(matches!(body[0], Stmt::Return(_)));
+                assert!(matches!(body[0].kind, StmtKind::Return(_)));
             }
             _ => panic!("expected fn literal in let value"),
         },
@@ -284,8 +301,8 @@ fn main() -> Int {
 "#;
     let program = parse_ok(src);
     let body = &program.functions[0].body;
-    match &body[0] {
-        Stmt::Return(Some(Expr::Call { callee, args })) => {
+    match &body[0].kind {
+        StmtKind::Return(Some(Expr::Call { callee, args })) => {
             assert_eq!(args.len(), 1);
             assert!(matches!(args[0], Expr::IntLit(2)));
             match callee.as_ref() {
@@ -310,12 +327,12 @@ fn main() -> Int {
 "#;
     let program = parse_ok(src);
     assert_eq!(program.functions.len(), 2);
-    match &program.functions[0].body[0] {
-        Stmt::Return(Some(Expr::FnLit { .. })) => {}
+    match &program.functions[0].body[0].kind {
+        StmtKind::Return(Some(Expr::FnLit { .. })) => {}
         _ => panic!("expected function literal return in makeInc"),
     }
-    match &program.functions[1].body[0] {
-        Stmt::Return(Some(Expr::Call { callee, args })) => {
+    match &program.functions[1].body[0].kind {
+        StmtKind::Return(Some(Expr::Call { callee, args })) => {
             assert_eq!(args.len(), 1);
             assert!(matches!(args[0], Expr::IntLit(2)));
             assert!(matches!(callee.as_ref(), Expr::Call { .. }));
@@ -377,8 +394,8 @@ fn main() -> Int {
     let body = &program.functions[0].body;
     assert_eq!(body.len(), 4);
 
-    match &body[0] {
-        Stmt::Let { name, ty, value } => {
+    match &body[0].kind {
+        StmtKind::Let { name, ty, value } => {
             assert_eq!(name, "x");
             assert_eq!(*ty, Some(TypeName::Int));
             assert_eq!(*value, Expr::IntLit(1));
@@ -386,8 +403,8 @@ fn main() -> Int {
         _ => panic!("expected let"),
     }
 
-    match &body[1] {
-        Stmt::Let { name, ty, value } => {
+    match &body[1].kind {
+        StmtKind::Let { name, ty, value } => {
             assert_eq!(name, "y");
             assert_eq!(*ty, None);
             assert_eq!(*value, Expr::Ident("x".to_string()));
@@ -395,8 +412,8 @@ fn main() -> Int {
         _ => panic!("expected let"),
     }
 
-    match &body[2] {
-        Stmt::Assign { target, value } => {
+    match &body[2].kind {
+        StmtKind::Assign { target, value } => {
             assert_eq!(*target, AssignTarget::Ident("y".to_string()));
             assert_eq!(*value, Expr::IntLit(2));
         }
@@ -404,6 +421,79 @@ fn main() -> Int {
     }
 }
 
+#[test]
+fn parses_destructuring_let_statements() {
+    let src = r#"
+fn main() -> Int {
+  let User { id, name: n } = u;
+  let [first, rest..] = xs;
+  return id;
+}
+"#;
+    let program = parse_ok(src);
+    assert_eq!(
+        program.functions[0].body[0].kind,
+        StmtKind::LetPattern {
+            pattern: MatchPattern::Struct {
+                name: "User".to_string(),
+                fields: vec![
+                    ("id".to_string(), MatchPattern::Binding("id".to_string())),
+                    ("name".to_string(), MatchPattern::Binding("n".to_string())),
+                ],
+                rest: false,
+            },
+            value: Expr::Ident("u".to_string()),
+        }
+    );
+    assert_eq!(
+        program.functions[0].body[1].kind,
+        StmtKind::LetPattern {
+            pattern: MatchPattern::Array {
+                items: vec![MatchPattern::Binding("first".to_string())],
+                rest: true,
+                rest_binding: Some("rest".to_string()),
+            },
+            value: Expr::Ident("xs".to_string()),
+        }
+    );
+}
+
+#[test]
+fn parses_tuple_destructuring_let() {
+    let src = r#"
+fn main() -> Int {
+  let (q, _) = divmod(7, 2);
+  return q;
+}
+"#;
+    let program = parse_ok(src);
+    assert_eq!(
+        program.functions[0].body[0].kind,
+        StmtKind::LetPattern {
+            pattern: MatchPattern::Tuple(vec![
+                MatchPattern::Binding("q".to_string()),
+                MatchPattern::Wildcard,
+            ]),
+            value: Expr::Call {
+                callee: Box::new(Expr::Ident("divmod".to_string())),
+                args: vec![Expr::IntLit(7), Expr::IntLit(2)],
+            },
+        }
+    );
+}
+
+#[test]
+fn reports_single_element_tuple_pattern() {
+    let src = r#"
+fn main() -> Int {
+  let (a) = pair;
+  return 0;
+}
+"#;
+    let diags = parse_err(src);
+    assert_has_diag(&diags, "Tuple patterns need at least two elements");
+}
+
 #[test]
 fn reports_missing_equals_in_let_declaration() {
     let src = r#"
@@ -416,6 +506,35 @@ fn main() -> Int {
     assert_has_diag(&diags, "Expected `=` in let declaration");
 }
 
+#[test]
+fn rejects_let_without_initializer_with_a_dedicated_diagnostic() {
+    let src = r#"
+let total: Int;
+
+fn main() -> Int {
+  let x: Int;
+  let y;
+  x = 1;
+  return x;
+}
+"#;
+    let diags = parse_err(src);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Missing initializer for `total`; every `let` needs a value, for example `let total: Int = ...;`",
+            "Missing initializer for `x`; every `let` needs a value, for example `let x: Int = ...;`",
+            "Missing initializer for `y`; every `let` needs a value, for example `let y = ...;`",
+        ]
+    );
+    assert_eq!(diags.as_slice()[1].span.line, 5);
+}
+
 #[test]
 fn parses_void_return_statement() {
     let src = r#"
@@ -425,7 +544,10 @@ fn log() -> Void {
 "#;
     let program = parse_ok(src);
     assert_eq!(program.functions.len(), 1);
-    assert!(matches!(program.functions[0].body[0], Stmt::Return(None)));
+    assert!(matches!(
+        program.functions[0].body[0].kind,
+        StmtKind::Return(None)
+    ));
 }
 
 #[test]
@@ -472,8 +594,8 @@ fn main() -> Int {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[1].body[0] {
-        Stmt::Let {
+    match &program.functions[1].body[0].kind {
+        StmtKind::Let {
             value: Expr::Call { callee, args },
             ..
         } => {
@@ -482,8 +604,8 @@ fn main() -> Int {
         }
         other => panic!("expected grouped function literal call, got {other:?}"),
     }
-    match &program.functions[1].body[1] {
-        Stmt::Let {
+    match &program.functions[1].body[1].kind {
+        StmtKind::Let {
             value: Expr::Call { callee, args },
             ..
         } => {
@@ -503,8 +625,8 @@ fn main() -> Option[Int] {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => match value {
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => match value {
             Expr::Try(inner) => {
                 assert!(matches!(inner.as_ref(), Expr::Call { .. }));
             }
@@ -513,3 +635,96 @@ fn main() -> Option[Int] {
         other => panic!("expected let statement, got {other:?}"),
     }
 }
+
+#[test]
+fn attaches_doc_comments_to_functions_structs_and_methods() {
+    let src = r#"
+/// A point.
+/// Second line.
+struct Point { x: Int }
+
+impl Point {
+  /// Returns x.
+  fn getX(self) -> Int { return self.x; }
+}
+
+/// Entry point.
+// plain comment between doc and fn
+fn main() -> Int {
+  /// ignored on statements
+  return 0;
+}
+
+fn undocumented() -> Int { return 1; }
+"#;
+    let program = parse_ok(src);
+    assert_eq!(
+        program.structs[0].doc.as_deref(),
+        Some("A point.\nSecond line.")
+    );
+    assert_eq!(
+        program.impls[0].methods[0].doc.as_deref(),
+        Some("Returns x.")
+    );
+    assert_eq!(program.functions[0].doc.as_deref(), Some("Entry point."));
+    assert_eq!(program.functions[1].doc, None);
+}
+
+#[test]
+fn skips_nested_block_comments() {
+    let src = r#"
+/* outer /* inner */ fn hidden() -> Int { return 1; } */
+fn main() -> Int {
+  return 0;
+}
+"#;
+    let program = parse_ok(src);
+    assert_eq!(program.functions.len(), 1);
+    assert_eq!(program.functions[0].name, "main");
+}
+
+#[test]
+fn parses_and_expands_type_alias_declarations() {
+    let src = r#"
+/// Identifier of a user.
+type UserId = Int;
+type Handler = Fn(UserId) -> UserId;
+
+fn apply(h: Handler, id: UserId) -> UserId {
+  return h(id);
+}
+"#;
+    let program = parse_ok(src);
+    assert_eq!(program.type_aliases.len(), 2);
+    assert_eq!(program.type_aliases[0].name, "UserId");
+    assert_eq!(
+        program.type_aliases[0].doc.as_deref(),
+        Some("Identifier of a user.")
+    );
+    let handler = TypeName::Fn {
+        params: vec![TypeName::Int],
+        ret: Box::new(TypeName::Int),
+    };
+    assert_eq!(program.type_aliases[1].target, handler);
+    assert_eq!(program.functions[0].params[0].ty, handler);
+    assert_eq!(program.functions[0].params[1].ty, TypeName::Int);
+    assert_eq!(program.functions[0].return_type, Some(TypeName::Int));
+}
+
+#[test]
+fn reports_recursive_type_aliases() {
+    let src = r#"
+type A = Vec[B];
+type B = Option[A];
+type C = Int;
+"#;
+    let diags = parse_err(src);
+    assert_has_diag(&diags, "Type alias `A` refers to itself");
+    assert_has_diag(&diags, "Type alias `B` refers to itself");
+    assert!(
+        !diags
+            .as_slice()
+            .iter()
+            .any(|diag| diag.message.contains("`C`"))
+    );
+}
@@ -22,7 +22,10 @@ fn main() -> Int {
     assert_eq!(program.functions[0].name, "main");
     assert_eq!(program.functions[0].params.len(), 0);
     assert_eq!(program.functions[0].body.len(), 1);
-    assert!(matches!(program.functions[0].body[0], Stmt::Return(_)));
+    assert!(matches!(
+        program.functions[0].body[0].kind,
+        StmtKind::Return(_)
+    ));
 }
 
 #[test]
@@ -160,19 +163,62 @@ fn main() -> Int { return 0; }
             skeplib::ast::ExportItem {
                 name: "add".to_string(),
                 alias: None,
+                opaque: false,
             },
             skeplib::ast::ExportItem {
                 name: "User".to_string(),
                 alias: None,
+                opaque: false,
             },
             skeplib::ast::ExportItem {
                 name: "version".to_string(),
                 alias: None,
+                opaque: false,
             },
         ]
     );
 }
 
+#[test]
+fn parses_opaque_export_items() {
+    let src = r#"
+export { User(opaque) as Account, make };
+fn main() -> Int { return 0; }
+"#;
+    let program = parse_ok(src);
+    assert_eq!(
+        match &program.exports[0] {
+            skeplib::ast::ExportDecl::Local { items } => items.clone(),
+            _ => panic!("expected local export"),
+        },
+        vec![
+            skeplib::ast::ExportItem {
+                name: "User".to_string(),
+                alias: Some("Account".to_string()),
+                opaque: true,
+            },
+            skeplib::ast::ExportItem {
+                name: "make".to_string(),
+                alias: None,
+                opaque: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn rejects_unknown_export_modifier() {
+    let src = r#"
+export { User(hidden) };
+fn main() -> Int { return 0; }
+"#;
+    let (_, diags) = Parser::parse_source(src);
+    assert!(diags.as_slice().iter().any(|d| {
+        d.message
+            .contains("Unknown export modifier `hidden`; only `opaque` is supported")
+    }));
+}
+
 #[test]
 fn parses_export_clause_with_aliases() {
     let src = r#"
@@ -190,10 +236,12 @@ fn main() -> Int { return 0; }
             skeplib::ast::ExportItem {
                 name: "add".to_string(),
                 alias: Some("plus".to_string()),
+                opaque: false,
             },
             skeplib::ast::ExportItem {
                 name: "sub".to_string(),
                 alias: None,
+                opaque: false,
             },
         ]
     );
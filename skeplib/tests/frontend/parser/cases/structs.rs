@@ -119,20 +119,77 @@ fn main() -> Int {
 }
 "#;
     let program = parse_ok(src);
-    match &program.functions[0].body[0] {
-        Stmt::Let { value, .. } => assert!(matches!(value, Expr::StructLit { .. })),
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let { value, .. } => assert!(matches!(value, Expr::StructLit { .. })),
         _ => panic!("expected struct literal"),
     }
-    match &program.functions[0].body[1] {
-        Stmt::Let { value, .. } => assert!(matches!(value, Expr::Field { .. })),
+    match &program.functions[0].body[1].kind {
+        StmtKind::Let { value, .. } => assert!(matches!(value, Expr::Field { .. })),
         _ => panic!("expected field access"),
     }
-    match &program.functions[0].body[2] {
-        Stmt::Assign { target, .. } => assert!(matches!(target, AssignTarget::Field { .. })),
+    match &program.functions[0].body[2].kind {
+        StmtKind::Assign { target, .. } => assert!(matches!(target, AssignTarget::Field { .. })),
         _ => panic!("expected field assignment target"),
     }
 }
 
+#[test]
+fn parses_generic_struct_declarations_types_and_literals() {
+    let src = r#"
+struct Pair[A, B] {
+  first: A,
+  second: B,
+}
+
+fn main() -> Int {
+  let p: Pair[Int, Vec[String]] = Pair[Int, Vec[String]] { first: 1, second: vec.new() };
+  let x = xs[i];
+  return p.first;
+}
+"#;
+    let program = parse_ok(src);
+    assert_eq!(program.structs[0].type_params, vec!["A", "B"]);
+    assert_eq!(
+        program.structs[0].fields[0].ty,
+        TypeName::Named("A".to_string())
+    );
+    let pair = TypeName::Generic {
+        name: "Pair".to_string(),
+        args: vec![
+            TypeName::Int,
+            TypeName::Vec {
+                elem: Box::new(TypeName::String),
+            },
+        ],
+    };
+    match &program.functions[0].body[0].kind {
+        StmtKind::Let {
+            ty: Some(ty),
+            value: Expr::StructLit {
+                name, type_args, ..
+            },
+            ..
+        } => {
+            assert_eq!(ty, &pair);
+            assert_eq!(name, "Pair");
+            assert_eq!(
+                type_args,
+                &[
+                    TypeName::Int,
+                    TypeName::Vec {
+                        elem: Box::new(TypeName::String),
+                    }
+                ]
+            );
+        }
+        other => panic!("expected generic struct literal, got {other:?}"),
+    }
+    match &program.functions[0].body[1].kind {
+        StmtKind::Let { value, .. } => assert!(matches!(value, Expr::Index { .. })),
+        other => panic!("expected index expression, got {other:?}"),
+    }
+}
+
 #[test]
 fn parses_vec_type_annotations() {
     let src = r#"
@@ -145,8 +202,8 @@ fn take(xs: Vec[Int]) -> Vec[String] {
     let f = &program.functions[0];
     assert_eq!(f.params[0].ty.as_str(), "Vec[Int]");
     assert_eq!(f.return_type.as_ref().expect("ret").as_str(), "Vec[String]");
-    match &f.body[0] {
-        Stmt::Let { ty: Some(ty), .. } => assert_eq!(ty.as_str(), "Vec[String]"),
+    match &f.body[0].kind {
+        StmtKind::Let { ty: Some(ty), .. } => assert_eq!(ty.as_str(), "Vec[String]"),
         _ => panic!("expected typed let"),
     }
 }
@@ -4,7 +4,7 @@ mod common;
 mod cases {
     use super::common::{assert_has_diag, assert_no_diags, parse_err, parse_ok};
     use skeplib::ast::{
-        AssignTarget, BinaryOp, Expr, MatchLiteral, MatchPattern, Stmt, TypeName, UnaryOp,
+        AssignTarget, BinaryOp, Expr, MatchLiteral, MatchPattern, StmtKind, TypeName, UnaryOp,
     };
     use skeplib::parser::Parser;
 
@@ -111,6 +111,279 @@ fn main() -> Int {
     assert_sema_success(&result, &diags);
 }
 
+#[test]
+fn sema_types_tuple_literals_and_element_access() {
+    let src = r#"
+fn divmod(a: Int, b: Int) -> (Int, Int) {
+  return (a / b, a % b);
+}
+
+fn main() -> Int {
+  let qr = divmod(7, 2);
+  let tagged: (String, (Int, Int)) = ("qr", qr);
+  let name: String = tagged.0;
+  return tagged.1.0 + qr.1;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_tuple_arity_and_element_mistakes() {
+    let src = r#"
+fn main() -> Int {
+  let t: (Int, String) = (1, 2);
+  let u: (Int, Int) = (1, 2, 3);
+  let v = (1, "a");
+  if (v == (1, "a")) {
+    return 1;
+  }
+  return v.2;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Tuple (Int, String) has no element `.2`");
+    assert!(
+        diags
+            .as_slice()
+            .iter()
+            .filter(|diag| diag.message.starts_with("Type mismatch in let"))
+            .count()
+            == 2
+    );
+}
+
+#[test]
+fn sema_checks_display_helpers_and_to_string_signatures() {
+    let src = r#"
+import io;
+
+struct User { id: Int }
+struct Label { text: String }
+
+impl User {
+  fn toString(self, verbose: Bool) -> String { return "user"; }
+}
+
+impl Label {
+  fn toString(self) -> String { return self.text; }
+}
+
+fn nothing() -> Void { return; }
+
+fn main() -> Int {
+  io.printAny(User { id: 1 });
+  io.printAny(Label { text: "x" }, 2);
+  io.printAny(nothing());
+  io.println(io.format("%v/%v/%d", [1, 2], Some(Label { text: "y" }), 3));
+  io.printf("%v", 1, 2);
+  io.debug(User { id: 1 });
+  io.debug();
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "Method `User.toString` is used to display values and must be declared as `fn toString(self) -> String`",
+    );
+    assert_has_diag(&diags, "io.printAny expects 1 argument(s), got 2");
+    assert_has_diag(&diags, "io.printAny argument 1 expects a value, got Void");
+    assert_has_diag(
+        &diags,
+        "io.printf format expects 1 value argument(s), got 2",
+    );
+    assert_has_diag(&diags, "io.debug expects 1 argument(s), got 0");
+    assert_eq!(diags.len(), 5, "{diags:?}");
+}
+
+#[test]
+fn sema_rejects_equality_on_values_containing_functions() {
+    let src = r#"
+import arr;
+
+struct Handler { run: Fn(Int) -> Int, id: Int }
+struct Registry { handlers: [Handler; 1] }
+struct Point { x: Int, label: String, tags: [Int; 2] }
+
+fn twice(x: Int) -> Int { return x * 2; }
+
+fn main() -> Int {
+  let h = Handler { run: twice, id: 1 };
+  let r = Registry { handlers: [h] };
+  let p = Point { x: 1, label: "a", tags: [1, 2] };
+  let points = [p, p];
+  let hs = [h];
+  if (r == r || h != h || Some(r) == None() || arr.contains(hs, h)) {
+    return 1;
+  }
+  if (p == p && (1, p) != (2, p) && arr.indexOf(points, p) == 0 && [[1], [2]] == [[1], [2]]) {
+    return 2;
+  }
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "Registry values cannot be compared with `==` or `!=`: they contain Fn(Int) -> Int, which has no equality",
+    );
+    assert_has_diag(
+        &diags,
+        "Handler values cannot be compared with `==` or `!=`: they contain Fn(Int) -> Int",
+    );
+    assert_has_diag(&diags, "Option[Registry] values cannot be compared");
+    assert_has_diag(
+        &diags,
+        "arr.contains compares elements with `==`, but Fn(Int) -> Int has no equality",
+    );
+    assert_eq!(diags.len(), 4, "{diags:?}");
+}
+
+#[test]
+fn sema_checks_tuple_destructuring_arity() {
+    let src = r#"
+fn divmod(a: Int, b: Int) -> (Int, Int) {
+  return (a / b, a % b);
+}
+
+fn main() -> Int {
+  let (q, r) = divmod(7, 2);
+  let (x, y, z) = divmod(7, 2);
+  let (n, m) = 5;
+  let label: String = q;
+  return r + z + m;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "Tuple pattern lists 3 element(s) but the target is (Int, Int)",
+    );
+    assert_has_diag(&diags, "Tuple pattern requires a tuple, got Int");
+    assert_has_diag(&diags, "Type mismatch in let `label`");
+    assert_eq!(diags.len(), 3, "{diags:?}");
+}
+
+#[test]
+fn sema_checks_type_alias_declarations() {
+    let src = r#"
+struct User { id: Int }
+type UserId = Int;
+type UserId = String;
+type User = Int;
+type Lookup = Fn(Missing) -> Int;
+
+fn main() -> Int {
+  let id: UserId = "x";
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Duplicate type alias `UserId`");
+    assert_has_diag(&diags, "Type alias `User` conflicts with struct `User`");
+    assert_has_diag(&diags, "Unknown type in type alias `Lookup`");
+    assert_has_diag(
+        &diags,
+        "Type mismatch in let `id`: declared Int, got String",
+    );
+}
+
+#[test]
+fn sema_checks_generic_struct_instantiations() {
+    let src = r#"
+struct Box[T] { value: T }
+struct Twice[T, T] { value: T }
+struct Plain { x: Int }
+
+impl Box {
+  fn get(self: Box) -> Int { return 0; }
+}
+
+fn main() -> Int {
+  let a: Box[Int] = Box[Int] { value: 1 };
+  let b = Box { value: 1 };
+  let c = Box[Int, Int] { value: 1 };
+  let d: Plain[Int] = Plain { x: 1 };
+  let e = Box[String] { value: 1 };
+  let f: Box[Int] = Box[String] { value: "s" };
+  let n: Int = a.value;
+  return n;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Duplicate type parameter `T` in struct `Twice`");
+    assert_has_diag(&diags, "Cannot implement methods on generic struct `Box`");
+    assert_has_diag(&diags, "Generic struct `Box` needs type arguments");
+    assert_has_diag(&diags, "Generic struct `Box` literal needs type arguments");
+    assert_has_diag(
+        &diags,
+        "Generic struct `Box` expects 1 type argument(s), got 2",
+    );
+    assert_has_diag(&diags, "Struct `Plain` is not generic");
+    assert_has_diag(
+        &diags,
+        "Type mismatch for field `value` in struct `Box[String]` literal: expected String, got Int",
+    );
+    assert_has_diag(
+        &diags,
+        "Type mismatch in let `f`: declared Named(\"Box[Int]\"), got Named(\"Box[String]\")",
+    );
+    assert!(
+        !diags
+            .as_slice()
+            .iter()
+            .any(|diag| diag.message.contains("`n`"))
+    );
+}
+
+#[test]
+fn sema_rejects_structs_that_contain_themselves_inline() {
+    let src = r#"
+struct Node { value: Int, next: Node }
+struct A { b: B }
+struct B { items: [A; 2] }
+struct Pair { inner: (Int, Pair) }
+struct Chain[T] { value: T, next: Chain[T] }
+struct List { next: Option[List], kids: Vec[List], byName: Map[String, List] }
+
+fn first(c: Chain[Int]) -> Int { return c.value; }
+
+fn main() -> Int { return 0; }
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "Struct `Node` contains itself without indirection through `Node.next`; wrap the field in `Option[...]` or `Vec[...]`",
+    );
+    assert_has_diag(
+        &diags,
+        "Struct `A` contains itself without indirection through `A.b -> B.items`",
+    );
+    assert_has_diag(
+        &diags,
+        "Struct `Pair` contains itself without indirection through `Pair.inner`",
+    );
+    assert_has_diag(
+        &diags,
+        "Struct `Chain[Int]` contains itself without indirection through `Chain[Int].next`",
+    );
+    assert!(
+        !diags
+            .as_slice()
+            .iter()
+            .any(|diag| diag.message.contains("Struct `B`") || diag.message.contains("`List`"))
+    );
+}
+
 #[test]
 fn sema_accepts_ok_err_values_and_result_equality() {
     let src = r#"
@@ -708,6 +981,49 @@ fn main(x: Int) -> Int {
     assert_has_diag(&diags, "Duplicate local binding `x` in the same scope");
 }
 
+#[test]
+fn sema_points_duplicate_locals_at_both_declarations() {
+    let src = r#"
+fn main(limit: Int) -> Int {
+  let f = fn(n: Int) -> Int {
+    let x = n;
+    return x;
+  };
+  let x = 1;
+  if (x > 0) {
+    let x = 2;
+    let y = x;
+    let x = 3;
+  }
+  let x = 4;
+  let limit = 5;
+  return x;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let found = diags
+        .as_slice()
+        .iter()
+        .map(|d| {
+            let name = d.message.split('`').nth(1).unwrap();
+            assert!(src[d.span.start..d.span.end].starts_with(&format!("let {name} = ")));
+            let first = d.labels.first().map(|l| (l.span.line, l.span.col));
+            (d.span.line, d.span.col, first)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        vec![
+            (11, 5, Some((9, 5))),
+            (13, 3, Some((7, 3))),
+            (14, 3, Some((2, 9))),
+        ],
+        "{:?}",
+        diags.as_slice()
+    );
+}
+
 #[test]
 fn sema_reports_let_declared_type_mismatch() {
     let src = r#"
@@ -921,6 +1237,68 @@ fn main() -> Int {
     assert_sema_success(&result, &diags);
 }
 
+#[test]
+fn sema_accepts_defer_at_function_top_level() {
+    let src = r#"
+fn cleanup(x: Int) -> Int {
+  return x;
+}
+
+fn main() -> Int {
+  defer cleanup(1);
+  let f: Fn() -> Int = fn() -> Int {
+    defer cleanup(2);
+    return 0;
+  };
+  return f();
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_defer_inside_nested_block() {
+    let src = r#"
+fn cleanup() -> Int {
+  return 0;
+}
+
+fn main() -> Int {
+  if (true) {
+    defer cleanup();
+  }
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert!(diags.as_slice().iter().any(|d| {
+        d.message
+            .contains("`defer` is only allowed at the top level of a function body")
+    }));
+}
+
+#[test]
+fn sema_rejects_try_inside_defer() {
+    let src = r#"
+fn step() -> Option[Int] {
+  return Some(1);
+}
+
+fn main() -> Option[Int] {
+  defer step()?;
+  return Some(0);
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert!(diags.as_slice().iter().any(|d| {
+        d.message
+            .contains("`?` is not allowed inside a `defer` expression")
+    }));
+}
+
 #[test]
 fn sema_accepts_int_modulo() {
     let src = r#"
@@ -1147,6 +1525,128 @@ fn main() -> Int {
     assert_has_diag(&diags, "Wildcard match arm `_` must be last");
 }
 
+#[test]
+fn sema_types_struct_array_and_binding_match_patterns() {
+    let src = r#"
+struct User { id: Int, name: String }
+
+fn main() -> Int {
+  let u = User { id: 1, name: "a" };
+  let label = match (u) {
+    User { id: 1, name } => name,
+    User { id, .. } => "other",
+  };
+  let xs = [1, 2, 3];
+  match (5) {
+    0 => { return 1; }
+    n => { xs[0] = n; }
+  }
+  match (xs) {
+    [1, second, rest..] => { return second + rest[0]; }
+    [_, _..] => { return 0; }
+  }
+}
+"#;
+    let _ = sema_ok(src);
+}
+
+#[test]
+fn sema_rejects_invalid_destructuring_match_patterns() {
+    let src = r#"
+import vec;
+
+struct User { id: Int, name: String }
+
+fn main() -> Int {
+  let u = User { id: 1, name: "a" };
+  match (u) {
+    User { id: true } => { return 1; }
+    User { idd: 1, .. } => { return 2; }
+    _ => { return 0; }
+  }
+  match ([1, 2]) {
+    [a, b, c] => { return a; }
+    [a, a] => { return a; }
+    x => { return 0; }
+    _ => { return 1; }
+  }
+  let v: Vec[Int] = vec.new();
+  match (v) {
+    [a, ..] => { return a; }
+    _ => { return 0; }
+  }
+  match (1) {
+    User { .. } => { return 1; }
+    _ => { return 0; }
+  }
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Struct pattern `User` is missing field(s) `name`");
+    assert_has_diag(&diags, "Unknown field `idd` in struct `User` pattern");
+    assert!(
+        !diags
+            .as_slice()
+            .iter()
+            .any(|d| d.message.starts_with("Unknown variable")),
+        "rejected patterns should still bind their names: {diags:?}"
+    );
+    assert_has_diag(
+        &diags,
+        "Array pattern lists 3 element(s) but the target is [Int; 2]",
+    );
+    assert_has_diag(&diags, "Match pattern binds `a` more than once");
+    assert_has_diag(&diags, "Catch-all match arm must be last");
+    assert_has_diag(
+        &diags,
+        "Array pattern requires a fixed-size array, got Vec[Int]",
+    );
+    assert_has_diag(
+        &diags,
+        "Struct pattern `User` cannot match a value of type Int",
+    );
+}
+
+#[test]
+fn sema_checks_match_guards() {
+    let ok = r#"
+fn main() -> Int {
+  let n = 12;
+  match (n) {
+    x if x > 10 => { return x; }
+    1 if n == 1 => { return 1; }
+    1 => { return 2; }
+    _ => { return 0; }
+  }
+}
+"#;
+    let _ = sema_ok(ok);
+
+    let src = r#"
+fn main() -> Int {
+  let b = true;
+  match (b) {
+    true if 1 => { return 1; }
+    false => { return 0; }
+  }
+  let label = match (3) {
+    x if x > 0 => "pos",
+    y if y < 0 => "neg",
+  };
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Match guard must be Bool, got Int");
+    assert_has_diag(
+        &diags,
+        "Non-exhaustive match on Bool: add both `true` and `false` arms, or add a wildcard arm `_`",
+    );
+    assert_has_diag(&diags, "Non-exhaustive match on Int");
+}
+
 #[test]
 fn sema_rejects_match_duplicate_wildcard() {
     let src = r#"
@@ -1746,3 +2246,416 @@ fn main() -> Int {
     assert!(result.has_errors);
     assert_has_diag(&diags, "`?` result ok type mismatch");
 }
+
+#[test]
+fn sema_reports_unrelated_errors_after_a_typo_in_the_same_function() {
+    let src = r#"
+fn main() -> Int {
+  let a: Strng = "x";
+  let b: Int = "y";
+  let c = undefinedFn(1) + true;
+  let d: Int = a;
+  let f = [1, 2][true];
+  let h: Bool = 1 + "s";
+  return "no";
+}
+"#;
+    let (result, diags) = sema_err(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Unknown type in let `a`: `Strng`",
+            "Type mismatch in let `b`: declared Int, got String",
+            "Unknown function `undefinedFn`",
+            "Array index must be Int",
+            "Invalid operands for Add: left Int, right String",
+            "Return type mismatch: expected Int, got String",
+        ]
+    );
+}
+
+#[test]
+fn sema_poisons_parameters_with_unknown_types() {
+    let src = r#"
+fn takes(p: Missing, n: Int) -> Int {
+  let x: Int = p;
+  let y: Int = p.field + 1;
+  let z: Int = n + true;
+  return 0;
+}
+
+fn main() -> Int {
+  return takes(1, 2);
+}
+"#;
+    let (result, diags) = sema_err(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "Unknown type in function `takes` parameter `p`: `Missing`",
+    );
+    assert_has_diag(&diags, "Invalid operands for Add: left Int, right Bool");
+    assert_eq!(diags.len(), 2, "{:?}", diags.as_slice());
+}
+
+#[test]
+fn sema_checks_arguments_of_calls_it_cannot_resolve() {
+    let src = r#"
+fn two(a: Int, b: Int) -> Int {
+  return a + b;
+}
+
+fn main() -> Int {
+  let f: Fn(Int) -> Int = fn(x: Int) -> Int { return x; };
+  missing(1 + true);
+  f(1, "a" + 2);
+  two(1, 2, 3 + false);
+  return 0;
+}
+"#;
+    let (result, diags) = sema_err(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Unknown function `missing`");
+    assert_has_diag(&diags, "Invalid operands for Add: left Int, right Bool");
+    assert_has_diag(&diags, "Arity mismatch for function value call");
+    assert_has_diag(&diags, "Invalid operands for Add: left String, right Int");
+    assert_has_diag(&diags, "Arity mismatch for `two`");
+    let bool_operands = diags
+        .as_slice()
+        .iter()
+        .filter(|d| d.message == "Invalid operands for Add: left Int, right Bool")
+        .count();
+    assert_eq!(bool_operands, 2, "{:?}", diags.as_slice());
+    assert!(
+        !diags
+            .as_slice()
+            .iter()
+            .any(|d| d.message.contains("Unknown variable `missing`")),
+        "{:?}",
+        diags.as_slice()
+    );
+}
+
+#[test]
+fn sema_suggests_close_variable_and_function_names() {
+    let src = r#"
+fn compute(a: Int) -> Int {
+  return a;
+}
+
+fn main() -> Int {
+  let counter = 1;
+  let a = conter + 1;
+  let b = comptue(2);
+  let c = zz;
+  return 0;
+}
+"#;
+    let (result, diags) = sema_err(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Unknown variable `conter`; did you mean `counter`?");
+    assert_has_diag(
+        &diags,
+        "Unknown function `comptue`; did you mean `compute`?",
+    );
+    assert!(
+        diags
+            .as_slice()
+            .iter()
+            .any(|d| d.message == "Unknown variable `zz`"),
+        "{:?}",
+        diags.as_slice()
+    );
+}
+
+#[test]
+fn sema_treats_panic_and_exit_as_terminal_statements() {
+    let src = r#"
+import os;
+
+fn pick(flag: Bool) -> Int {
+  if (flag) {
+    return 1;
+  }
+  panic("unreachable flag state");
+}
+
+fn bail() -> Int {
+  os.exit(3);
+}
+
+fn main() -> Int {
+  return pick(true) + bail();
+}
+"#;
+    let (result, diags) = sema_ok(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_panic_misuse() {
+    let src = r#"
+fn panic(message: String) -> Int {
+  return 0;
+}
+
+fn main() -> Int {
+  panic();
+  panic(1);
+  return 0;
+}
+"#;
+    let (result, diags) = sema_err(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "Function name `panic` is reserved for the builtin `panic(message)`",
+    );
+    assert_has_diag(&diags, "panic expects 1 argument, got 0");
+    assert_has_diag(&diags, "panic argument 1 expects String, got Int");
+}
+
+#[test]
+fn sema_must_return_understands_infinite_loops_and_match() {
+    let src = r#"
+fn spin(n: Int) -> Int {
+  let i = 0;
+  while (true) {
+    i = i + 1;
+    if (i > n) {
+      return i;
+    }
+    for (let j = 0; j < 3; j = j + 1) {
+      break;
+    }
+  }
+}
+
+fn forever(n: Int) -> Int {
+  for (;;) {
+    if (n > 0) {
+      return n;
+    }
+    continue;
+  }
+}
+
+fn classify(n: Int) -> Int {
+  match (n) {
+    0 => { return 0; }
+    _ => { return 1; }
+  }
+}
+
+fn main() -> Int {
+  return spin(2) + forever(1) + classify(3);
+}
+"#;
+    let (result, diags) = sema_ok(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_must_return_respects_break_and_continue_flow() {
+    let src = r#"
+fn escapes(n: Int) -> Int {
+  while (true) {
+    if (n > 0) {
+      break;
+    }
+    return n;
+  }
+}
+
+fn skipped(n: Int) -> Int {
+  while (n > 0) {
+    continue;
+    return n;
+  }
+}
+
+fn bounded(n: Int) -> Int {
+  while (n > 0) {
+    return n;
+  }
+}
+
+fn main() -> Int {
+  return 0;
+}
+"#;
+    let (result, diags) = sema_err(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Function `escapes` may exit without returning");
+    assert_has_diag(&diags, "Function `skipped` may exit without returning");
+    assert_has_diag(&diags, "Function `bounded` may exit without returning");
+}
+
+#[test]
+fn sema_warns_about_unreachable_statements_at_the_dead_statement() {
+    let src = r#"
+fn pick(n: Int) -> Int {
+  while (n > 0) {
+    if (n == 1) {
+      break;
+      n = 0;
+    }
+    continue;
+    n = n - 1;
+  }
+  match (n) {
+    0 => { return 0; }
+    _ => { return 1; }
+  }
+  let dead = 2;
+  return dead;
+}
+
+fn main() -> Int {
+  return pick(3);
+  let unused = 1;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(!result.has_errors, "{:?}", diags.as_slice());
+    let warnings = diags
+        .as_slice()
+        .iter()
+        .map(|d| {
+            assert_eq!(d.level, DiagnosticLevel::Warning, "{d:?}");
+            (d.message.as_str(), d.span.line, d.span.col)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        warnings,
+        vec![
+            ("Unreachable statement after `break`", 6, 7),
+            ("Unreachable statement after `continue`", 9, 5),
+            (
+                "Unreachable statement after `match` whose every arm exits",
+                15,
+                3
+            ),
+            ("Unreachable statement after `return`", 21, 3),
+        ]
+    );
+    assert_eq!(
+        &src[diags.as_slice()[3].span.start..diags.as_slice()[3].span.end],
+        "let unused = 1;"
+    );
+}
+
+#[test]
+fn sema_warns_after_diverging_calls_infinite_loops_and_exiting_if_chains() {
+    let src = r#"
+import os;
+
+fn a(flag: Bool) -> Int {
+  if (flag) {
+    return 1;
+  } else if (!flag) {
+    panic("impossible");
+    return 2;
+  } else {
+    os.exit(1);
+  }
+  return 3;
+}
+
+fn b() -> Int {
+  for (;;) {
+    let f = fn(x: Int) -> Int {
+      return x;
+      x = 1;
+    };
+  }
+  return 0;
+}
+
+fn main() -> Int {
+  return a(true) + b();
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(!result.has_errors, "{:?}", diags.as_slice());
+    let found = |needle: &str| {
+        diags
+            .as_slice()
+            .iter()
+            .find(|d| d.message == needle)
+            .unwrap_or_else(|| panic!("missing `{needle}` in {:?}", diags.as_slice()))
+            .span
+            .line
+    };
+    assert_eq!(found("Unreachable statement after `panic(...)`"), 9);
+    assert_eq!(
+        found("Unreachable statement after `if` whose every branch exits"),
+        13
+    );
+    assert_eq!(found("Unreachable statement after infinite `for` loop"), 23);
+    assert_eq!(found("Unreachable statement after `return`"), 20);
+    assert_eq!(diags.len(), 4, "{:?}", diags.as_slice());
+}
+
+#[test]
+fn sema_does_not_count_guarded_or_non_exhaustive_matches_as_exiting() {
+    let src = r#"
+fn guarded(n: Int) -> Int {
+  match (n) {
+    0 if n > 5 => { return 0; }
+    _ => { return 1; }
+  }
+  return 2;
+}
+
+fn partial(flag: Bool) -> Int {
+  match (flag) {
+    true => { return 0; }
+  }
+  return 1;
+}
+
+fn main() -> Int {
+  return guarded(0) + partial(true);
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Non-exhaustive match on Bool");
+    assert!(
+        diags
+            .as_slice()
+            .iter()
+            .all(|d| !d.message.starts_with("Unreachable statement")),
+        "{:?}",
+        diags.as_slice()
+    );
+}
+
+#[test]
+fn sema_streams_diagnostics_to_a_sink_in_the_same_order_as_the_bag() {
+    let src = r#"
+fn main() -> Int {
+  let x: Int = "no";
+  return 0;
+  return 1;
+}
+"#;
+    let mut streamed = Vec::new();
+    let result = skeplib::sema::analyze_source_with_sink(src, &mut |d| streamed.push(d));
+    let (bag_result, bag) = analyze_source(src);
+    assert_eq!(result, bag_result);
+    assert!(result.has_errors);
+    assert_eq!(streamed, bag.into_vec());
+    assert!(streamed.iter().any(|d| d.level == DiagnosticLevel::Warning));
+
+    let mut parse_errors = Vec::new();
+    skeplib::parser::Parser::parse_source_with_sink("fn main( {", &mut |d| parse_errors.push(d));
+    assert!(!parse_errors.is_empty());
+}
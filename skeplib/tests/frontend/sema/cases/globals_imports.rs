@@ -61,6 +61,37 @@ fn main() -> Int { return b; }
     );
 }
 
+#[test]
+fn sema_rejects_global_initializer_reading_later_global_through_calls() {
+    let src = r#"
+let total: Int = outer();
+fn outer() -> Int { return inner(); }
+fn inner() -> Int { return base; }
+let base: Int = 2;
+fn main() -> Int { return total; }
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert!(diags.as_slice().iter().any(|d| {
+        d.message.contains(
+            "Initializer of global `total` reads global `base` through `outer` before it is initialized",
+        )
+    }));
+}
+
+#[test]
+fn sema_accepts_global_initializer_calling_functions_that_read_earlier_globals() {
+    let src = r#"
+let base: Int = 2;
+fn read(base2: Int) -> Int { return base + base2; }
+fn shadow(total: Int) -> Int { return total; }
+let total: Int = read(1) + shadow(3);
+fn main() -> Int { return total; }
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
 #[test]
 fn sema_rejects_exporting_unknown_name() {
     let src = r#"
@@ -67,6 +67,159 @@ fn main() -> Int {
     );
 }
 
+#[test]
+fn sema_types_string_index_and_slices_as_string() {
+    let src = r#"
+fn main() -> Int {
+  let s = "hello";
+  let c: String = s[0];
+  let mid: String = s[1..3];
+  let tail: String = s[2..];
+  let head: String = s[..2];
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_accepts_char_comparisons_and_conversions() {
+    let src = r#"
+import char;
+
+fn main() -> Int {
+  let c: Char = 'x';
+  let ordered = c >= 'a' && c <= 'z' && c != 'y';
+  let code: Int = char.toInt(c);
+  let back: Option[Char] = char.fromInt(code);
+  let single: Option[Char] = char.fromString("q");
+  let text: String = char.toString(c);
+  let flags = char.isDigit(c) || char.isAlpha(c) || char.isWhitespace(c);
+  match (c) {
+    'x' => { return 1; }
+    _ => { return 0; }
+  }
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_mixing_char_with_int_and_string() {
+    let src = r#"
+import char;
+
+fn main() -> Int {
+  let c = 'a';
+  let _x = c < 1;
+  let _y = c == "a";
+  let _z = char.toInt("a");
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Invalid comparison operands: left Char, right Int");
+    assert_has_diag(&diags, "Cannot compare Char and String with `==` or `!=`");
+    assert_has_diag(&diags, "char.toInt argument 1 expects Char, got String");
+}
+
+#[test]
+fn sema_checks_math_wrapping_builtins() {
+    let ok = r#"
+import math;
+
+fn main() -> Int {
+  return math.wrappingAdd(1, 2) + math.wrappingSub(3, 4) + math.wrappingMul(5, 6);
+}
+"#;
+    let (result, diags) = analyze_source(ok);
+    assert_sema_success(&result, &diags);
+
+    let bad = r#"
+import math;
+
+fn main() -> Int {
+  let _x = math.wrappingAdd(1);
+  let _y = math.wrappingMul(1.0, 2);
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(bad);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "math.wrappingAdd expects 2 argument(s), got 1");
+    assert_has_diag(&diags, "math.wrappingMul argument 1 expects Int, got Float");
+}
+
+#[test]
+fn sema_checks_math_checked_division_builtins() {
+    let ok = r#"
+import math;
+
+fn main() -> Int {
+  let q: Option[Int] = math.checkedDiv(10, 0);
+  return math.divOr(10, 0, -1);
+}
+"#;
+    let (result, diags) = analyze_source(ok);
+    assert_sema_success(&result, &diags);
+
+    let bad = r#"
+import math;
+
+fn main() -> Int {
+  let _x: Int = math.checkedDiv(1, 2);
+  let _y = math.divOr(1, 2);
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(bad);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "Type mismatch in let `_x`: declared Int, got Option { value: Int }",
+    );
+    assert_has_diag(&diags, "math.divOr expects 3 argument(s), got 2");
+}
+
+#[test]
+fn sema_rejects_bad_slice_bounds_and_non_string_slices() {
+    let src = r#"
+fn main() -> Int {
+  let s = "hello";
+  let a: [Int; 2] = [1, 2];
+  let _x = s["a"..2];
+  let _y = a[0..1];
+  s[0] = "j";
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("Slice bound must be Int, got String"))
+    );
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("Range slicing is only supported on String, got [Int; 2]"))
+    );
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("Cannot index-assign into non-array type String"))
+    );
+}
+
 #[test]
 fn sema_rejects_index_assignment_type_mismatch() {
     let src = r#"
@@ -165,6 +318,42 @@ fn main() -> Int {
     assert_sema_success(&result, &diags);
 }
 
+#[test]
+fn sema_accepts_bytes_hex_and_encoding_builtins() {
+    let src = r#"
+import bytes;
+import result;
+
+fn main() -> Int {
+  let b: Bytes = result.unwrapOk(bytes.fromHex("cafe"));
+  let hex: String = bytes.toHex(b);
+  let latin: Bytes = result.unwrapOk(bytes.encode("café", "latin1"));
+  let text: String = result.unwrapOk(bytes.decode(latin, "latin1"));
+  if (hex == "cafe" && text == "café") {
+    return 1;
+  }
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_bytes_decode_result_used_as_string() {
+    let src = r#"
+import bytes;
+
+fn main() -> Int {
+  let text: String = bytes.decode(bytes.fromString("a"), "ascii");
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Type mismatch in let `text`");
+}
+
 #[test]
 fn sema_accepts_map_builtins() {
     let src = r#"
@@ -1039,6 +1228,87 @@ fn main() -> Int {
     }));
 }
 
+#[test]
+fn sema_accepts_datetime_format_and_pattern_parse() {
+    let src = r#"
+import datetime;
+import result;
+fn main() -> Int {
+  let text: String = datetime.format(0, "YYYY-MM-DD hh:mm:ss");
+  let ts: Int = result.unwrapOk(datetime.parse("YYYY-MM-DD hh:mm:ss", text));
+  return ts;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_datetime_parse_with_swapped_arguments() {
+    let src = r#"
+import datetime;
+fn main() -> Int {
+  let ts = datetime.parse(0, "YYYY");
+  let _ = ts;
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert!(diags.as_slice().iter().any(|d| {
+        d.message
+            .contains("datetime.parse argument 1 expects String")
+    }));
+}
+
+#[test]
+fn sema_accepts_datetime_local_time_helpers() {
+    let src = r#"
+import datetime;
+fn main() -> Int {
+  let now: String = datetime.nowLocal();
+  let then: String = datetime.toLocal(0);
+  let clock: String = datetime.formatLocal(0, "hh:mm Z");
+  let offset: Int = datetime.offsetMinutes();
+  return offset;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_accepts_datetime_arithmetic_helpers() {
+    let src = r#"
+import datetime;
+fn main() -> Int {
+  let ts: Int = datetime.addHours(datetime.addDays(0, 1), 2);
+  let gap: Int = datetime.diffSeconds(ts, 0);
+  let day: Int = datetime.weekday(ts);
+  let len: Int = datetime.daysInMonth(2024, 2);
+  return gap + day + len;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_datetime_add_days_with_string_count() {
+    let src = r#"
+import datetime;
+fn main() -> Int {
+  return datetime.addDays(0, "1");
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert!(diags.as_slice().iter().any(|d| {
+        d.message
+            .contains("datetime.addDays argument 2 expects Int")
+    }));
+}
+
 #[test]
 fn sema_accepts_datetime_component_extractors() {
     let src = r#"
@@ -1179,30 +1449,16 @@ fn main() -> Int {
 }
 
 #[test]
-fn sema_accepts_minimal_os_builtin_signatures() {
+fn sema_accepts_random_choice_shuffle_bool_and_gaussian() {
     let src = r#"
-import str;
-import os;
-import option;
-import result;
-import vec;
+import random;
 fn main() -> Int {
-  let plat: String = os.platform();
-  let arch: String = os.arch();
-  let arg0: String = option.unwrapSome(os.arg(0));
-  let hasHome: Bool = os.envHas("HOME");
-  let home: Option[String] = os.envGet("HOME");
-  os.envSet("MODE", "debug");
-  os.envRemove("MODE");
-  os.sleep(1);
-  let args: Vec[String] = vec.new();
-  vec.push(args, "status");
-  let code: Int = result.unwrapOk(os.exec("git", args));
-  let out: String = result.unwrapOk(os.execOut("git", args));
-  if (str.len(plat) >= 0 && str.len(arch) >= 0 && str.len(arg0) >= 0 && hasHome && option.isSome(home) && code >= 0 && str.len(out) >= 0) {
-    return 0;
-  }
-  return 1;
+  let names: [String; 3] = ["a", "b", "c"];
+  let picked: Option[String] = random.choice(names);
+  let mixed: [String; 3] = random.shuffle(names);
+  let coin: Bool = random.bool(0.5);
+  let noise: Float = random.gaussian(0.0, 1.0);
+  return 0;
 }
 "#;
     let (result, diags) = analyze_source(src);
@@ -1210,14 +1466,42 @@ fn main() -> Int {
 }
 
 #[test]
-fn sema_accepts_builtin_opaque_net_types_in_annotations() {
+fn sema_rejects_random_choice_on_non_array() {
     let src = r#"
-fn takes_socket(s: net.Socket) -> Void {
-  return;
+import random;
+fn main() -> Int {
+  let _x = random.choice("abc");
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert!(
+        diags
+            .as_slice()
+            .iter()
+            .any(|d| d.message.contains("random.choice argument 1 expects Array"))
+    );
 }
 
-fn main() -> Void {
-  return;
+#[test]
+fn sema_accepts_vec_and_arr_callback_builtins() {
+    let src = r#"
+import arr;
+import str;
+import vec;
+fn double(x: Int) -> Int {
+  return x * 2;
+}
+fn main() -> Int {
+  let v: Vec[Int] = vec.new();
+  let doubled: Vec[Int] = vec.map(v, double);
+  let evens: Vec[Int] = vec.filter(v, fn(x: Int) -> Bool { return x % 2 == 0; });
+  let total: Int = vec.reduce(v, 0, fn(acc: Int, x: Int) -> Int { return acc + x; });
+  let words: [String; 2] = ["a", "bb"];
+  let lens: [Int; 2] = arr.map(words, fn(w: String) -> Int { return str.len(w); });
+  let long: Vec[String] = arr.filter(words, fn(w: String) -> Bool { return str.len(w) > 1; });
+  return total;
 }
 "#;
     let (result, diags) = analyze_source(src);
@@ -1225,32 +1509,168 @@ fn main() -> Void {
 }
 
 #[test]
-fn sema_rejects_unknown_builtin_opaque_net_type_name() {
+fn sema_rejects_mismatched_callback_shapes() {
     let src = r#"
-fn main(x: net.Server) -> Void {
-  return;
+import arr;
+import vec;
+fn main() -> Int {
+  let v: Vec[Int] = vec.new();
+  let _a = vec.filter(v, fn(x: Int) -> Int { return x; });
+  let _b = vec.reduce(v, "", fn(acc: Int, x: Int) -> Int { return acc + x; });
+  let _c = arr.map(v, fn(x: Int) -> Int { return x; });
+  return 0;
 }
 "#;
     let (result, diags) = analyze_source(src);
     assert!(result.has_errors);
-    assert!(diags.as_slice().iter().any(|d| {
-        d.message
-            .contains("Unknown type in function `main` parameter `x`: `net.Server`")
-    }));
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    assert!(
+        messages.iter().any(
+            |m| m.contains("vec.filter argument 2 expects Fn(Int) -> Bool, got Fn(Int) -> Int")
+        )
+    );
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("vec.reduce argument 3 expects Fn(String, Int) -> String"))
+    );
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("arr.map argument 1 expects Array"))
+    );
 }
 
 #[test]
-fn sema_accepts_minimal_net_listener_and_socket_builtins() {
+fn sema_accepts_sort_comparators_over_structs() {
     let src = r#"
-import net;
-import bytes;
-import map;
-import option;
-import result;
+import arr;
+import vec;
+struct User {
+  age: Int
+}
+fn byAge(a: User, b: User) -> Int {
+  return a.age - b.age;
+}
+fn main() -> Int {
+  let users: [User; 2] = [User { age: 3 }, User { age: 1 }];
+  let sorted: [User; 2] = arr.sortBy(users, byAge);
+  let v: Vec[User] = vec.new();
+  vec.sort(v, byAge);
+  return sorted[0].age;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
 
-fn main() -> Void {
-  let parsed: Result[Map[String, String], String] = net.parseUrl("https://example.com:443/a?x=1#frag");
-  let parts: Map[String, String] = result.unwrapOk(parsed);
+#[test]
+fn sema_rejects_sort_comparator_for_wrong_element_type() {
+    let src = r#"
+import arr;
+import vec;
+fn main() -> Int {
+  let words: [String; 2] = ["b", "a"];
+  let _s = arr.sortBy(words, fn(a: Int, b: Int) -> Int { return a - b; });
+  let v: Vec[Int] = vec.new();
+  vec.sort(v, fn(a: Int, b: Int) -> Bool { return a < b; });
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    assert!(messages.iter().any(|m| m.contains(
+        "arr.sortBy argument 2 expects Fn(String, String) -> Int, got Fn(Int, Int) -> Int"
+    )));
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("vec.sort argument 2 expects Fn(Int, Int) -> Int"))
+    );
+}
+
+#[test]
+fn sema_accepts_minimal_os_builtin_signatures() {
+    let src = r#"
+import str;
+import os;
+import option;
+import result;
+import vec;
+fn main() -> Int {
+  let plat: String = os.platform();
+  let arch: String = os.arch();
+  let arg0: String = option.unwrapSome(os.arg(0));
+  let hasHome: Bool = os.envHas("HOME");
+  let home: Option[String] = os.envGet("HOME");
+  os.envSet("MODE", "debug");
+  os.envRemove("MODE");
+  os.sleep(1);
+  let args: Vec[String] = vec.new();
+  vec.push(args, "status");
+  let code: Int = result.unwrapOk(os.exec("git", args));
+  let out: String = result.unwrapOk(os.execOut("git", args));
+  if (str.len(plat) >= 0 && str.len(arch) >= 0 && str.len(arg0) >= 0 && hasHome && option.isSome(home) && code >= 0 && str.len(out) >= 0) {
+    return 0;
+  }
+  return 1;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_accepts_builtin_opaque_net_types_in_annotations() {
+    let src = r#"
+fn takes_socket(s: net.Socket) -> Void {
+  return;
+}
+
+fn main() -> Void {
+  return;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_unknown_builtin_opaque_net_type_name() {
+    let src = r#"
+fn main(x: net.Server) -> Void {
+  return;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert!(diags.as_slice().iter().any(|d| {
+        d.message
+            .contains("Unknown type in function `main` parameter `x`: `net.Server`")
+    }));
+}
+
+#[test]
+fn sema_accepts_minimal_net_listener_and_socket_builtins() {
+    let src = r#"
+import net;
+import bytes;
+import map;
+import option;
+import result;
+
+fn main() -> Void {
+  let parsed: Result[Map[String, String], String] = net.parseUrl("https://example.com:443/a?x=1#frag");
+  let parts: Map[String, String] = result.unwrapOk(parsed);
   let fetchOptions: Map[String, String] = map.new();
   map.insert(fetchOptions, "method", "POST");
   map.insert(fetchOptions, "body", "{}");
@@ -1665,6 +2085,150 @@ fn main() -> Int {
     );
 }
 
+#[test]
+fn sema_rejects_spawning_tasks_that_assign_globals() {
+    let src = r#"
+import task;
+
+let counter: Int = 0;
+let totals: [Int; 2] = [0, 0];
+
+fn bump() -> Int {
+  counter = counter + 1;
+  return counter;
+}
+
+fn viaCall() -> Int {
+  return bump();
+}
+
+fn shadowed() -> Int {
+  let counter = 5;
+  counter = counter + 1;
+  return counter;
+}
+
+fn reads() -> Int {
+  return counter + totals[0];
+}
+
+fn main() -> Int {
+  let a: task.Task[Int] = task.spawn(bump);
+  let b: task.Task[Int] = task.spawn(viaCall);
+  let c: task.Task[Int] = task.spawn(fn() -> Int {
+    totals[1] = 3;
+    return 0;
+  });
+  let d: task.Task[Int] = task.spawn(shadowed);
+  let e: task.Task[Int] = task.spawn(reads);
+  return task.join(a) + task.join(b) + task.join(c) + task.join(d) + task.join(e);
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .filter(|m| m.starts_with("task.spawn argument 1 assigns global"))
+        .collect::<Vec<_>>();
+    assert_eq!(messages.len(), 3, "{messages:?}");
+    assert!(messages[0].contains("`counter`") && messages[1].contains("`counter`"));
+    assert!(messages[2].contains("`totals`"), "{messages:?}");
+}
+
+#[test]
+fn sema_rejects_spawned_global_writes_after_block_scoped_shadowing() {
+    let src = r#"
+import task;
+
+let counter: Int = 0;
+
+fn shadowInBlock() -> Int {
+  if (true) {
+    let counter = 1;
+    counter = counter + 1;
+  }
+  counter = counter + 1;
+  return 0;
+}
+
+fn shadowInArm(x: Option[Int]) -> Int {
+  match (x) {
+    Some(counter) => {
+      return counter;
+    }
+    None => {}
+  }
+  counter = 2;
+  return 0;
+}
+
+fn main() -> Int {
+  let a: task.Task[Int] = task.spawn(shadowInBlock);
+  let b: task.Task[Int] = task.spawn(fn() -> Int {
+    return shadowInArm(None());
+  });
+  return task.join(a) + task.join(b);
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .filter(|m| m.starts_with("task.spawn argument 1 assigns global `counter`"))
+        .collect::<Vec<_>>();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+}
+
+#[test]
+fn sema_rejects_spawning_tasks_that_mutate_globals_through_builtins() {
+    let src = r#"
+import map;
+import task;
+import vec;
+
+let log: Vec[Int] = vec.new();
+let seen: Map[String, Int] = map.new();
+
+fn record() -> Int {
+  vec.push(log, 1);
+  return 0;
+}
+
+fn remember() -> Int {
+  map.insert(seen, "a", 1);
+  return 0;
+}
+
+fn localOnly() -> Int {
+  let log: Vec[Int] = vec.new();
+  vec.push(log, 1);
+  return vec.len(log);
+}
+
+fn main() -> Int {
+  let a: task.Task[Int] = task.spawn(record);
+  let b: task.Task[Int] = task.spawn(remember);
+  let c: task.Task[Int] = task.spawn(localOnly);
+  return task.join(a) + task.join(b) + task.join(c);
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .filter(|m| m.starts_with("task.spawn argument 1 assigns global"))
+        .collect::<Vec<_>>();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+    assert!(messages[0].contains("`log`"), "{messages:?}");
+    assert!(messages[1].contains("`seen`"), "{messages:?}");
+}
+
 #[test]
 fn sema_rejects_task_close_type_mismatches() {
     let src = r#"
@@ -1873,6 +2437,50 @@ fn main() -> Void {
     );
 }
 
+#[test]
+fn sema_rejects_udp_calls_on_tcp_sockets_and_bad_payloads() {
+    let src = r#"
+import bytes;
+import net;
+import result;
+
+fn main() -> Void {
+  let tcp: net.Socket = result.unwrapOk(net.connect("127.0.0.1:9"));
+  let udp: net.UdpSocket = result.unwrapOk(net.bindUdp("127.0.0.1:0"));
+  net.recv(tcp);
+  net.send(udp, "text");
+  net.sendTo(udp, 9, bytes.fromString("x"));
+  net.read(udp);
+  net.recvFrom(tcp, 512);
+  net.recvFrom(udp, "512");
+  let (data, sender) = result.unwrapOk(net.recvFrom(udp, 512));
+  let n: Int = bytes.len(data) + sender;
+  return;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    for expected in [
+        "net.recv argument 1 expects Opaque(\"net.UdpSocket\")",
+        "net.send argument 2 expects Bytes, got String",
+        "net.sendTo argument 2 expects String, got Int",
+        "net.read argument 1 expects Opaque(\"net.Socket\")",
+        "net.recvFrom argument 1 expects Opaque(\"net.UdpSocket\")",
+        "net.recvFrom argument 2 expects Int, got String",
+        "Invalid operands for Add: left Int, right String",
+    ] {
+        assert!(
+            messages.iter().any(|m| m.contains(expected)),
+            "missing `{expected}` in {messages:?}"
+        );
+    }
+}
+
 #[test]
 fn sema_rejects_net_close_type_mismatch() {
     let src = r#"
@@ -2158,6 +2766,48 @@ fn main() -> Int {
     );
 }
 
+#[test]
+fn sema_accepts_os_exec_capture_with_options_map() {
+    let src = r#"
+import map;
+import os;
+import result;
+import vec;
+fn main() -> Int {
+  let args: Vec[String] = vec.new();
+  vec.push(args, "-c");
+  vec.push(args, "echo hi");
+  let options: Map[String, String] = map.new();
+  map.insert(options, "cwd", ".");
+  let response: Map[String, String] = result.unwrapOk(os.execCapture("sh", args, options));
+  let _out: Option[String] = map.get(response, "stdout");
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_os_exec_capture_non_map_options() {
+    let src = r#"
+import os;
+import result;
+import vec;
+fn main() -> Int {
+  let args: Vec[String] = vec.new();
+  let _x = result.unwrapOk(os.execCapture("sh", args, "cwd=."));
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert!(diags.as_slice().iter().any(|d| {
+        d.message
+            .contains("os.execCapture argument 3 expects Map[String, String]")
+    }));
+}
+
 #[test]
 fn sema_rejects_removed_os_legacy_builtins() {
     let src = r#"
@@ -2234,6 +2884,43 @@ fn main() -> Int {
     assert_sema_success(&result, &diags);
 }
 
+#[test]
+fn sema_accepts_fs_copy_move_and_bytes_signatures() {
+    let src = r#"
+import bytes;
+import fs;
+import result;
+fn main() -> Int {
+  result.unwrapOk(fs.writeBytes("a.bin", bytes.fromString("hi")));
+  result.unwrapOk(fs.copyFile("a.bin", "b.bin"));
+  result.unwrapOk(fs.moveFile("b.bin", "c.bin"));
+  let data: Bytes = result.unwrapOk(fs.readBytes("c.bin"));
+  return bytes.len(data);
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_fs_write_bytes_with_string_data() {
+    let src = r#"
+import fs;
+fn main() -> Int {
+  fs.writeBytes("a.bin", "hi");
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert!(
+        diags
+            .as_slice()
+            .iter()
+            .any(|d| d.message.contains("fs.writeBytes argument 2 expects Bytes"))
+    );
+}
+
 #[test]
 fn sema_rejects_fs_without_import() {
     let src = r#"
@@ -2611,6 +3298,8 @@ extern("libc.so.6") fn tty() -> Bool;
 extern("libc.so.6") fn pos(flag: Int) -> Bool;
 extern("libc.so.6") fn add(a: Int, b: Int) -> Int;
 extern("libc.so.6") fn count(raw: Bytes, n: Int) -> Int;
+extern("libm.so.6") fn fabs(x: Float) -> Float;
+extern("libm.so.6") fn pow(x: Float, y: Float) -> Float;
 
 fn main() -> Int {
   let raw: Bytes = bytes.fromString("hello");
@@ -2619,6 +3308,7 @@ fn main() -> Int {
   let _b: Bool = pos(1);
   let _c: Int = add(2, 3);
   let _d: Int = count(raw, 3);
+  let _e: Float = fabs(-1.5) + pow(2.0, 3.0);
   return 0;
 }
 "#;
@@ -2722,3 +3412,381 @@ fn main() -> Int {
             .contains("`result.*` used without `import result;`")
     }));
 }
+
+#[test]
+fn sema_suggests_close_builtin_names() {
+    let src = r#"
+import io;
+import vec;
+fn main() -> Int {
+  let xs: Vec[Int] = vec.new();
+  vec.psh(xs, 1);
+  io.prinln("a");
+  return 0;
+}
+"#;
+    let (result, diags) = sema_err(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "Unknown builtin `io.prinln`; did you mean `io.println`?",
+    );
+    assert_has_diag(
+        &diags,
+        "Unknown builtin `vec.psh`; did you mean `vec.push`?",
+    );
+}
+
+#[test]
+fn sema_rejects_calls_into_unknown_packages() {
+    let src = r#"
+import io;
+fn main() -> Int {
+  pkg.work(1 + true);
+  mth.sqrt(2.0);
+  io.nothing(2);
+  return 0;
+}
+"#;
+    let (result, diags) = sema_err(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "Unknown package `pkg` in call `pkg.work`");
+    assert_has_diag(&diags, "Invalid operands for Add: left Int, right Bool");
+    assert_has_diag(
+        &diags,
+        "Unknown package `mth` in call `mth.sqrt`; did you mean `math`?",
+    );
+    assert_has_diag(&diags, "Unknown builtin `io.nothing`");
+    assert!(
+        !diags
+            .as_slice()
+            .iter()
+            .any(|d| d.message.contains("Unknown variable")),
+        "{:?}",
+        diags.as_slice()
+    );
+}
+
+#[test]
+fn sema_checks_builtin_arity_and_argument_types_across_packages() {
+    let src = r#"
+import fs;
+import io;
+import os;
+import random;
+import vec;
+fn main() -> Int {
+  io.print();
+  os.sleep(true);
+  fs.exists(1);
+  random.int(1, "a");
+  let v: Vec[Int] = vec.new();
+  vec.push(v, "s");
+  return 0;
+}
+"#;
+    let (result, diags) = sema_err(src);
+    assert!(result.has_errors);
+    assert_has_diag(&diags, "io.print expects 1 argument(s), got 0");
+    assert_has_diag(&diags, "os.sleep argument 1 expects Int, got Bool");
+    assert_has_diag(&diags, "fs.exists argument 1 expects String, got Int");
+    assert_has_diag(&diags, "random.int argument 2 expects Int, got String");
+    assert_has_diag(&diags, "vec.push argument 2 expects Int, got String");
+    assert_eq!(diags.len(), 5, "{:?}", diags.as_slice());
+}
+
+#[test]
+fn sema_types_for_in_loops_over_arrays_vecs_and_iter_pipelines() {
+    let src = r#"
+import iter;
+import str;
+
+fn label(x: Int) -> String { return "n"; }
+fn short(s: String) -> Bool { return str.len(s) < 3; }
+
+fn main() -> Int {
+  let total = 0;
+  for (x in [1, 2, 3]) {
+    total = total + x;
+  }
+  let names: Vec[String] = iter.collect(iter.filter(iter.map(iter.range(0, 5), label), short));
+  for (name in names) {
+    total = total + str.len(name);
+  }
+  for (s in iter.take(iter.map([4, 5], label), 1)) {
+    total = total + str.len(s);
+    break;
+  }
+  return total;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_misused_iter_pipelines_and_for_in_sources() {
+    let src = r#"
+import iter;
+
+fn main() -> Int {
+  let r = iter.range(0, 3);
+  for (x in 5) { }
+  for (s in iter.map([1, 2], 3)) { }
+  for (y in [1, 2]) {
+    let z: String = y;
+  }
+  return y;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    for expected in [
+        "iter.range must be consumed directly by a `for-in` loop",
+        "for-in iterable expects an Array, Vec, or iter pipeline, got Int",
+        "iter.map argument 2 expects Fn(Int) -> T, got Int",
+        "Type mismatch in let `z`",
+        "Unknown variable `y`",
+    ] {
+        assert!(
+            messages.iter().any(|m| m.contains(expected)),
+            "missing `{expected}` in {messages:?}"
+        );
+    }
+}
+
+#[test]
+fn sema_accepts_serde_values_decoded_into_annotated_lets() {
+    let src = r#"
+import serde;
+
+struct Point { x: Int, y: Float }
+
+fn main() -> Int {
+  let p = Point { x: 1, y: 2.0 };
+  let json: String = serde.toJson(p);
+  let back: Result[Point, String] = serde.fromJson(json);
+  let raw: Result[Vec[Option[Point]], String] = serde.decode(serde.encode([Some(p)]));
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_serde_calls_without_a_decodable_type() {
+    let src = r#"
+import serde;
+
+fn id(x: Int) -> Int { return x; }
+
+fn main() -> Int {
+  let f = id;
+  let a = serde.fromJson("1");
+  let b: Int = serde.decode(serde.encode(1));
+  let c: Result[Fn(Int) -> Int, String] = serde.fromJson("1");
+  let s = serde.toJson(f);
+  serde.fromJson("2");
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    for expected in [
+        "Cannot infer decoded type for let `a`; annotate as `Result[T, String]`",
+        "Type mismatch in let `b`: declared Int, serde decoding gives `Result[T, String]`",
+        "Cannot decode `Fn(Int) -> Int` in let `c`",
+        "serde.toJson cannot serialize `Fn(Int) -> Int`",
+        "serde.fromJson must initialize a local `let` annotated as `Result[T, String]`",
+    ] {
+        assert!(
+            messages.iter().any(|m| m.contains(expected)),
+            "missing `{expected}` in {messages:?}"
+        );
+    }
+}
+
+#[test]
+fn sema_rejects_nested_options_in_json_but_not_binary_serde() {
+    let src = r#"
+import serde;
+
+struct Patch { value: Option[Option[Int]] }
+struct Maybe { value: Option[Int] }
+
+fn main() -> Int {
+  let nested: Option[Option[Int]] = Some(None());
+  let text = serde.toJson(nested);
+  let a: Result[Patch, String] = serde.fromJson("{}");
+  let b: Result[Option[Option[Int]], String] = serde.decode(serde.encode(nested));
+  let wrapped: Option[Maybe] = Some(Maybe { value: None() });
+  let c: Result[Option[Maybe], String] = serde.fromJson(serde.toJson(wrapped));
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+    assert!(
+        messages[0].starts_with(
+            "serde.toJson cannot serialize `Option[Option[Int]]`: `Option[Option[Int]]` is ambiguous"
+        ),
+        "{messages:?}"
+    );
+    assert!(
+        messages[1].starts_with(
+            "Cannot decode `Patch` in let `a` from JSON: `Option[Option[Int]]` is ambiguous"
+        ),
+        "{messages:?}"
+    );
+}
+
+#[test]
+fn sema_checks_store_handles_and_argument_types() {
+    let src = r#"
+import net;
+import result;
+import store;
+
+fn main() -> Void {
+  let db: store.Store = result.unwrapOk(store.open("state.db"));
+  let value: Result[Option[String], String] = store.get(db, "k");
+  let keys: Result[Vec[String], String] = store.keys(db);
+  let udp: net.UdpSocket = result.unwrapOk(net.bindUdp("127.0.0.1:0"));
+  store.set(udp, "k", "v");
+  store.set(db, "k", 1);
+  store.delete(db);
+  store.close(db);
+  return;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(messages.len(), 3, "{messages:?}");
+    for expected in [
+        "store.set argument 1 expects Opaque(\"store.Store\"), got Opaque(\"net.UdpSocket\")",
+        "store.set argument 3 expects String, got Int",
+        "store.delete expects 2 argument(s), got 1",
+    ] {
+        assert!(
+            messages.iter().any(|m| m.contains(expected)),
+            "missing `{expected}` in {messages:?}"
+        );
+    }
+}
+
+#[test]
+fn sema_rejects_strings_where_encoding_expects_bytes() {
+    let src = r#"
+import encoding;
+
+fn main() -> Int {
+  let _a: String = encoding.base64Encode("text");
+  let _b: Bool = encoding.isValidUtf8("text");
+  let _c: Bytes = encoding.urlDecode("a%20b");
+  let _d: Bytes = encoding.hexDecode("00");
+  let _e: Result[Bytes, String] = encoding.base64Decode("Zg==");
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "encoding.base64Encode argument 1 expects Bytes, got String",
+    );
+    assert_has_diag(
+        &diags,
+        "encoding.isValidUtf8 argument 1 expects Bytes, got String",
+    );
+    assert_has_diag(&diags, "Type mismatch in let `_c`");
+    assert_has_diag(&diags, "Type mismatch in let `_d`");
+    assert!(
+        !diags
+            .as_slice()
+            .iter()
+            .any(|d| d.message.contains("let `_e`")),
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn sema_checks_hash_inputs_are_strings_or_bytes() {
+    let ok = r#"
+import bytes;
+import hash;
+
+fn main() -> Int {
+  let _a: String = hash.sha256("text");
+  let _b: String = hash.md5(bytes.fromString("text"));
+  return hash.crc32("text") + hash.fnv64(bytes.fromString("text"));
+}
+"#;
+    let (result, diags) = analyze_source(ok);
+    assert_sema_success(&result, &diags);
+
+    let bad = r#"
+import hash;
+
+fn main() -> Int {
+  let _a = hash.sha256(42);
+  let _b = hash.crc32("a", "b");
+  let _c: String = hash.fnv64("text");
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(bad);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "hash.sha256 argument 1 expects String or Bytes, got Int",
+    );
+    assert_has_diag(&diags, "hash.crc32 expects 1 argument(s), got 2");
+    assert_has_diag(&diags, "Type mismatch in let `_c`");
+}
+
+#[test]
+fn sema_types_csv_rows_and_rejects_non_string_tables() {
+    let src = r#"
+import csv;
+import vec;
+
+fn main() -> Int {
+  let _rows: Result[Vec[Vec[String]], String] = csv.parse("a,b");
+  let _maps: Result[Vec[Map[String, String]], String] = csv.parseWithHeader("a\n1");
+  let ints: Vec[Vec[Int]] = vec.new();
+  let _text = csv.stringify(ints);
+  let _bad: Vec[Vec[String]] = csv.parse("a,b");
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "csv.stringify argument 1 expects Vec { elem: Vec { elem: String } }, got Vec { elem: Vec { elem: Int } }",
+    );
+    assert_has_diag(&diags, "Type mismatch in let `_bad`");
+    assert_eq!(diags.as_slice().len(), 2, "{:?}", diags.as_slice());
+}
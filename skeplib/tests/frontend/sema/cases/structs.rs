@@ -610,3 +610,134 @@ fn main() -> Int {
         "Method `User.bad` must declare `self: User` as first parameter",
     );
 }
+
+#[test]
+fn sema_resolves_arithmetic_operators_to_struct_methods() {
+    let src = r#"
+struct Vec2 { x: Int, y: Int }
+
+impl Vec2 {
+  fn add(self, other: Vec2) -> Vec2 { return Vec2 { x: self.x + other.x, y: self.y + other.y }; }
+  fn mul(self, k: Int) -> Vec2 { return Vec2 { x: self.x * k, y: self.y * k }; }
+}
+
+fn main() -> Int {
+  let a = Vec2 { x: 1, y: 2 };
+  let b: Vec2 = a + a * 3;
+  return b.x;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_struct_operators_without_matching_method() {
+    let src = r#"
+struct Vec2 { x: Int, y: Int }
+
+impl Vec2 {
+  fn add(self, other: Vec2) -> Vec2 { return other; }
+  fn sub(self) -> Vec2 { return self; }
+}
+
+fn main() -> Int {
+  let a = Vec2 { x: 1, y: 2 };
+  let _b = a + 1;
+  let _c = a * a;
+  let _d = a - a;
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "Right operand for `+` on `Vec2`: expected Vec2, got Int",
+    );
+    assert_has_diag(
+        &diags,
+        "Operator `*` is not defined for struct `Vec2`; add `fn mul(self, other: ...)` to `impl Vec2`",
+    );
+    assert_has_diag(
+        &diags,
+        "Operator method `Vec2.sub` must take `self` and exactly one other parameter",
+    );
+}
+
+#[test]
+fn sema_suggests_close_field_method_and_struct_names() {
+    let src = r#"
+struct Point { x: Int, total: Int }
+impl Point {
+  fn norm(self) -> Int {
+    return self.x;
+  }
+}
+fn main() -> Int {
+  let p = Point { x: 1, totl: 2 };
+  let q = Point { x: 1, total: 2 };
+  let s = q.totl;
+  q.totl = 3;
+  let t = q.nrom();
+  let u = Pont { x: 1, total: 2 };
+  return 0;
+}
+"#;
+    let (result, diags) = sema_err(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "Unknown field `totl` in struct `Point` literal; did you mean `total`?",
+    );
+    assert_has_diag(
+        &diags,
+        "Unknown field `totl` on struct `Point`; did you mean `total`?",
+    );
+    assert_has_diag(
+        &diags,
+        "Unknown method `nrom` on struct `Point`; did you mean `norm`?",
+    );
+    assert_has_diag(&diags, "Unknown struct `Pont`; did you mean `Point`?");
+}
+
+#[test]
+fn sema_types_destructuring_let_bindings() {
+    let src = r#"
+struct User { id: Int, name: String }
+
+fn main() -> Int {
+  let u = User { id: 1, name: "a" };
+  let User { id, name } = u;
+  let [first, rest..] = [1, 2, 3];
+  let label: String = name;
+  let tail: [Int; 2] = rest;
+  return id + first + tail[0];
+}
+"#;
+    let _ = sema_ok(src);
+}
+
+#[test]
+fn sema_rejects_refutable_or_clashing_let_patterns() {
+    let src = r#"
+struct User { id: Int, name: String }
+
+fn main() -> Int {
+  let u = User { id: 1, name: "a" };
+  let User { id: 1, .. } = u;
+  let id = 2;
+  let User { id, .. } = u;
+  let User { name } = u;
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    assert_has_diag(
+        &diags,
+        "`let` patterns must always match; use `match` for patterns with literals or variants",
+    );
+    assert_has_diag(&diags, "Duplicate local binding `id` in the same scope");
+    assert_has_diag(&diags, "Struct pattern `User` is missing field(s) `id`");
+}
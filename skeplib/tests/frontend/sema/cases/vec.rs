@@ -16,6 +16,53 @@ fn main() -> Int {
     assert_sema_success(&result, &diags);
 }
 
+#[test]
+fn sema_infers_vec_literal_element_type() {
+    let src = r#"
+import vec;
+fn main() -> Int {
+  let xs = vec![1, 2, 3];
+  let names: Vec[String] = vec!["a"];
+  let empty: Vec[Int] = vec![];
+  vec.push(xs, 4);
+  vec.push(empty, xs[0]);
+  return vec.len(xs) + vec.len(names);
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert_sema_success(&result, &diags);
+}
+
+#[test]
+fn sema_rejects_mixed_and_untyped_vec_literals() {
+    let src = r#"
+import vec;
+fn main() -> Int {
+  let mixed = vec![1, "two"];
+  let empty = vec![];
+  let xs = vec![1];
+  vec.push(xs, "three");
+  return 0;
+}
+"#;
+    let (result, diags) = analyze_source(src);
+    assert!(result.has_errors);
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("Vec literal element type mismatch: expected Int, got String"))
+    );
+    assert!(messages.iter().any(|m| {
+        m.contains("Cannot infer vector element type for let `empty`; annotate as `Vec[T]`")
+    }));
+    assert!(messages.iter().any(|m| m.contains("vec.push")));
+}
+
 #[test]
 fn sema_rejects_untyped_vec_new() {
     let src = r#"
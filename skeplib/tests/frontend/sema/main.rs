@@ -3,6 +3,7 @@ mod common;
 
 mod cases {
     use super::common::{assert_has_diag, assert_sema_success, sema_err, sema_ok};
+    use skeplib::diagnostic::DiagnosticLevel;
     use skeplib::sema::analyze_source;
 
     mod core;
@@ -0,0 +1,2 @@
+#[path = "frontend/diagnostic/main.rs"]
+mod frontend_diagnostic;
@@ -0,0 +1,180 @@
+//! Golden-file tests: each fixture under `tests/fixtures/golden` is compiled and
+//! its rendered diagnostics or pretty-printed IR is compared with the checked-in
+//! expected file next to it. Run with `SKEPA_BLESS=1` to rewrite the expected
+//! files after a deliberate change, then review the diff.
+
+mod common;
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use skeplib::diagnostic::{Diagnostic, DiagnosticLevel, Span, render_snippet};
+use skeplib::driver::{Compiler, DriverDiagnostic, Emit};
+use skeplib::ir::PrettyIr;
+use skeplib::resolver::ResolveError;
+
+fn blessing() -> bool {
+    std::env::var_os("SKEPA_BLESS").is_some_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Fixture cases in `dir`: single-file `name.sk` programs and `name/` project
+/// directories whose entry is `name/main.sk`.
+fn cases(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut out = Vec::new();
+    for entry in fs::read_dir(dir).expect("golden fixture directory exists") {
+        let path = entry.expect("dir entry").path();
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if path.is_dir() {
+            out.push((stem.to_string(), path.join("main.sk")));
+        } else if path.extension().is_some_and(|e| e == "sk") {
+            out.push((stem.to_string(), path));
+        }
+    }
+    out.sort();
+    out
+}
+
+/// Compares every case in `group` against `<name>.<extension>` and reports all
+/// mismatches at once, or rewrites the expected files when blessing.
+fn check_group(group: &str, extension: &str, render: impl Fn(&Path, &Path) -> String) {
+    let dir = common::fixtures_dir("golden").join(group);
+    let mut failures = Vec::new();
+    for (name, entry) in cases(&dir) {
+        let actual = render(&dir, &entry);
+        let expected_path = dir.join(format!("{name}.{extension}"));
+        if blessing() {
+            fs::write(&expected_path, &actual).expect("write golden file");
+            continue;
+        }
+        match fs::read_to_string(&expected_path) {
+            Ok(expected) if expected.replace("\r\n", "\n") == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{group}/{name}.{extension} differs\n--- expected\n{expected}--- actual\n{actual}"
+            )),
+            Err(_) => failures.push(format!(
+                "{group}/{name}.{extension} is missing; run with SKEPA_BLESS=1 to create it"
+            )),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{}\n\nRe-run with SKEPA_BLESS=1 to accept the new output.",
+        failures.join("\n")
+    );
+}
+
+fn display_path(root: &Path, path: Option<&Path>) -> Option<String> {
+    let path = path?;
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    Some(relative.display().to_string().replace('\\', "/"))
+}
+
+/// Replaces absolute fixture paths embedded in messages with `$DIR`.
+fn normalize_message(root: &Path, message: &str) -> String {
+    message
+        .replace(&root.display().to_string(), "$DIR")
+        .replace('\\', "/")
+}
+
+fn source_of(path: Option<&Path>) -> Option<String> {
+    fs::read_to_string(path?).ok()
+}
+
+/// Renders diagnostics the way `skepac` prints them without color, with paths
+/// relative to the fixture directory.
+fn render_diagnostics(root: &Path, entry: &Path) -> String {
+    let output = Compiler::entry(entry).emit(Emit::Ir).run();
+    let mut out = String::new();
+    for diagnostic in &output.diagnostics {
+        match diagnostic {
+            DriverDiagnostic::Resolve(err) => render_resolve(&mut out, root, err),
+            DriverDiagnostic::Sema(diag) => render_sema(&mut out, root, diag),
+            DriverDiagnostic::Codegen(message) => {
+                let _ = writeln!(out, "[E-CODEGEN][codegen] {message}");
+            }
+        }
+    }
+    if output.diagnostics.is_empty() {
+        out.push_str("(no diagnostics)\n");
+    }
+    out
+}
+
+fn render_resolve(out: &mut String, root: &Path, err: &ResolveError) {
+    let tag = format!("[{}][resolve]", err.code);
+    let message = normalize_message(root, &err.message);
+    let path = display_path(root, err.path.as_deref());
+    match (path, err.line.zip(err.col)) {
+        (Some(path), Some((line, col))) => {
+            let _ = writeln!(out, "{tag} {path}:{line}:{col}: {message}");
+            if let Some(source) = source_of(err.path.as_deref()) {
+                out.push_str(&render_snippet(
+                    &source,
+                    Span::new(0, 1, line, col),
+                    &[],
+                    false,
+                ));
+            }
+        }
+        (Some(path), None) => {
+            let _ = writeln!(out, "{tag} {path}: {message}");
+        }
+        (None, _) => {
+            let _ = writeln!(out, "{tag} {message}");
+        }
+    }
+}
+
+fn render_sema(out: &mut String, root: &Path, diag: &Diagnostic) {
+    let code = diag.code.unwrap_or("E-SEMA");
+    let tag = match diag.level {
+        DiagnosticLevel::Error => format!("[{code}][sema]"),
+        DiagnosticLevel::Warning => format!("[{code}][sema] warning:"),
+    };
+    match display_path(root, diag.path.as_deref()) {
+        Some(path) if diag.span.line > 0 => {
+            let _ = writeln!(
+                out,
+                "{tag} {path}:{}:{}: {}",
+                diag.span.line, diag.span.col, diag.message
+            );
+        }
+        Some(path) => {
+            let _ = writeln!(out, "{tag} {path}: {}", diag.message);
+        }
+        None => {
+            let _ = writeln!(
+                out,
+                "{tag} {}:{}: {}",
+                diag.span.line, diag.span.col, diag.message
+            );
+        }
+    }
+    if let Some(source) = source_of(diag.path.as_deref()) {
+        out.push_str(&diag.render_snippet(&source, false));
+    }
+}
+
+fn render_ir(_root: &Path, entry: &Path) -> String {
+    let output = Compiler::entry(entry).emit(Emit::Ir).run();
+    assert!(
+        output.succeeded(),
+        "IR golden fixture {} should compile: {:?}",
+        entry.display(),
+        output.diagnostics
+    );
+    PrettyIr::new(&output.ir.expect("emitted IR")).to_string()
+}
+
+#[test]
+fn diagnostics_match_golden_files() {
+    check_group("diagnostics", "stderr", render_diagnostics);
+}
+
+#[test]
+fn ir_matches_golden_files() {
+    check_group("ir", "ir", render_ir);
+}
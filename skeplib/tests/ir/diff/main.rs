@@ -181,6 +181,33 @@ fn main() -> Int {
     );
 }
 
+#[test]
+fn native_and_ir_keep_stdout_and_stderr_separate() {
+    let source = r#"
+import io;
+
+fn main() -> Int {
+  io.println("result");
+  io.eprint("warning: ");
+  io.eprintln("low disk");
+  return 0;
+}
+"#;
+
+    let native = common::native_run_structured(source);
+    native.expect_success();
+    assert_eq!(native.stdout_lossy(), "result\n");
+    assert_eq!(native.stderr_lossy(), "warning: low disk\n");
+
+    let host = common::DeterministicHost::default();
+    let out = host.captured_output();
+    let err = host.captured_errors();
+    let value = common::ir_run_ok_with_host(source, Box::new(host));
+    assert_eq!(value, IrValue::Int(0));
+    assert_eq!(&*out.lock().expect("lock stdout"), "result\n");
+    assert_eq!(&*err.lock().expect("lock stderr"), "warning: low disk\n");
+}
+
 #[test]
 fn native_and_ir_accept_same_arr_fs_and_struct_project_sources() {
     let unique = SystemTime::now()
@@ -239,6 +266,327 @@ fn main() -> Int {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn native_and_ir_accept_same_fs_copy_move_and_bytes_source() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock should be monotonic enough for temp name")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("skepa_ir_diff_fs_bytes_{unique}"));
+    fs::create_dir_all(&root).expect("temp dir should be created");
+    let dir = root.to_string_lossy().replace('\\', "\\\\");
+    let source = format!(
+        r#"
+import bytes;
+import fs;
+import result;
+
+fn main() -> Int {{
+  let a = fs.join("{dir}", "a.bin");
+  let b = fs.join("{dir}", "b.bin");
+  let c = fs.join("{dir}", "c.bin");
+  let data = bytes.push(bytes.fromString("ok"), 255);
+  result.unwrapOk(fs.writeBytes(a, data));
+  result.unwrapOk(fs.copyFile(a, b));
+  result.unwrapOk(fs.moveFile(b, c));
+  let back = result.unwrapOk(fs.readBytes(c));
+  let moved = result.unwrapOk(fs.exists(b));
+  let failed = result.isErr(fs.copyFile(b, a));
+  if (back == data && !moved && failed) {{
+    return bytes.len(back);
+  }}
+  return 0;
+}}
+"#
+    );
+    assert_native_and_ir_accept_same_int_source(&source, 3);
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn native_and_ir_accept_same_bytes_hex_and_encoding_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import bytes;
+import result;
+
+fn main() -> Int {
+  let raw = result.unwrapOk(bytes.fromHex("636166e9"));
+  let text = result.unwrapOk(bytes.decode(raw, "latin1"));
+  let back = result.unwrapOk(bytes.encode(text, "latin1"));
+  let badHex = result.isErr(bytes.fromHex("zz"));
+  let notAscii = result.isErr(bytes.encode(text, "ascii"));
+  if (bytes.toHex(back) == "636166e9" && text == "café" && badHex && notAscii) {
+    return bytes.len(result.unwrapOk(bytes.encode(text, "utf-8")));
+  }
+  return 0;
+}
+"#,
+        5,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_datetime_pattern_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import datetime;
+import result;
+
+fn main() -> Int {
+  let text = datetime.format(1704112496, "DD/MM/YYYY hh:mm:ss");
+  let ts = result.unwrapOk(datetime.parse("DD/MM/YYYY hh:mm:ss", text));
+  let badPattern = result.isErr(datetime.parse("YYYY-MM-DDTHH", "2024"));
+  let badInput = result.isErr(datetime.parse("YYYY-MM-DD", "2024-13-01"));
+  if (text == "01/01/2024 12:34:56" && ts == 1704112496 && badPattern && badInput) {
+    return datetime.second(ts);
+  }
+  return 0;
+}
+"#,
+        56,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_datetime_arithmetic_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import datetime;
+
+fn main() -> Int {
+  let start = 1704067200;
+  let later = datetime.addHours(datetime.addDays(start, 31), 5);
+  if (datetime.diffSeconds(later, start) != 31 * 86400 + 5 * 3600) {
+    return 0;
+  }
+  return datetime.weekday(later) * 10 + datetime.daysInMonth(2024, 2) - 29;
+}
+"#,
+        40,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_local_datetime_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import datetime;
+import result;
+
+fn main() -> Int {
+  let ts = 1704112496;
+  let iso = datetime.toLocal(ts);
+  let viaIso = result.unwrapOk(datetime.parse("YYYY-MM-DD'T'hh:mm:ssZ", iso));
+  let custom = datetime.formatLocal(ts, "DD.MM.YYYY hh:mm:ss Z");
+  let viaCustom = result.unwrapOk(datetime.parse("DD.MM.YYYY hh:mm:ss Z", custom));
+  let offset = datetime.offsetMinutes();
+  if (viaIso == ts && viaCustom == ts && offset >= -1080 && offset <= 1080) {
+    return 7;
+  }
+  return 0;
+}
+"#,
+        7,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_random_collection_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import option;
+import random;
+
+fn main() -> Int {
+  random.seed(11);
+  let values: [Int; 4] = [1, 2, 3, 4];
+  let mixed = random.shuffle(values);
+  let total = mixed[0] + mixed[1] + mixed[2] + mixed[3];
+  let picked = option.unwrapSome(random.choice(mixed));
+  let centered = random.gaussian(2.0, 0.0);
+  if (picked >= 1 && picked <= 4 && random.bool(1.0) && !random.bool(0.0) && centered == 2.0) {
+    return total + values[0];
+  }
+  return 0;
+}
+"#,
+        11,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_collection_callback_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import arr;
+import vec;
+
+fn double(x: Int) -> Int {
+  return x * 2;
+}
+
+fn main() -> Int {
+  let v: Vec[Int] = vec.new();
+  vec.push(v, 1);
+  vec.push(v, 2);
+  vec.push(v, 3);
+  let doubled = vec.map(v, double);
+  let odds = vec.filter(v, fn(x: Int) -> Bool { return x % 2 == 1; });
+  let total = vec.reduce(doubled, 0, fn(acc: Int, x: Int) -> Int { return acc + x; });
+  let fixed: [Int; 3] = [4, 5, 6];
+  let squares = arr.map(fixed, fn(x: Int) -> Int { return x * x; });
+  let big = arr.filter(fixed, fn(x: Int) -> Bool { return x > 4; });
+  return total + vec.len(odds) * 10 + squares[2] + vec.len(big);
+}
+"#,
+        70,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_sort_comparator_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import arr;
+import option;
+import vec;
+
+struct Item {
+  key: Int,
+  tag: Int
+}
+
+fn byKey(a: Item, b: Item) -> Int {
+  return a.key - b.key;
+}
+
+fn main() -> Int {
+  let items: [Item; 3] = [Item { key: 2, tag: 1 }, Item { key: 1, tag: 2 }, Item { key: 2, tag: 3 }];
+  let sorted = arr.sortBy(items, byKey);
+  let v: Vec[Int] = vec.new();
+  vec.push(v, 1);
+  vec.push(v, 3);
+  vec.push(v, 2);
+  vec.sort(v, fn(a: Int, b: Int) -> Int { return b - a; });
+  let top = option.unwrapSome(vec.get(v, 0));
+  return sorted[0].tag * 100 + sorted[1].tag * 10 + sorted[2].tag + top;
+}
+"#,
+        216,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_vec_literal_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import vec;
+
+fn main() -> Int {
+  let xs = vec![4, 5, 6];
+  let empty: Vec[Int] = vec![];
+  vec.push(empty, xs[1]);
+  vec.push(xs, 7);
+  return vec.len(xs) * 10 + xs[3] + empty[0];
+}
+"#,
+        52,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_struct_operator_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+struct Vec2 {
+  x: Int,
+  y: Int
+}
+
+impl Vec2 {
+  fn add(self, other: Vec2) -> Vec2 {
+    return Vec2 { x: self.x + other.x, y: self.y + other.y };
+  }
+  fn mul(self, k: Int) -> Vec2 {
+    return Vec2 { x: self.x * k, y: self.y * k };
+  }
+}
+
+fn main() -> Int {
+  let a = Vec2 { x: 1, y: 2 };
+  let b = Vec2 { x: 3, y: 4 };
+  let c = a + b * 2;
+  return c.x * 10 + c.y;
+}
+"#,
+        80,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_string_index_and_slice_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import str;
+
+fn main() -> Int {
+  let s = "héllo world";
+  let mid = s[1..4];
+  let tail = s[6..];
+  if (s[1] == "é" && mid == "éll" && s[..5] == "héllo" && s[..] == s) {
+    return str.len(mid) * 10 + str.len(tail);
+  }
+  return 0;
+}
+"#,
+        35,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_char_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import char;
+import option;
+import str;
+
+fn classify(c: Char) -> Int {
+  match (c) {
+    '0' | '1' | '2' => { return 1; }
+    ' ' => { return 2; }
+    _ => { return 3; }
+  }
+}
+
+fn main() -> Int {
+  let s = "a1 b2";
+  let digits = 0;
+  let sum = 0;
+  let i = 0;
+  while (i < str.len(s)) {
+    let c = option.unwrapSome(char.fromString(s[i]));
+    if (char.isDigit(c)) {
+      digits = digits + 1;
+    }
+    sum = sum + classify(c);
+    i = i + 1;
+  }
+  let ok = 0;
+  if ('a' < 'b' && char.toInt('A') == 65 && char.toString('\n') == "\n") {
+    ok = 1;
+  }
+  if (option.isNone(char.fromInt(-1)) && option.isNone(char.fromString("ab"))) {
+    ok = ok + 1;
+  }
+  return ok * 100 + digits * 10 + sum;
+}
+"#,
+        230,
+    );
+}
+
 #[test]
 fn native_and_ir_accept_same_project_sources() {
     let unique = SystemTime::now()
@@ -487,6 +835,59 @@ fn main() -> String {
 "#,
         RtErrorKind::InvalidArgument,
     );
+    for body in [
+        "let x = 9223372036854775807; return x + 1;",
+        "let x = -9223372036854775807; return x - 2;",
+        "let x = 4611686018427387904; return x * 2;",
+        "let x = -9223372036854775807 - 1; return -x;",
+        "let x = -9223372036854775807 - 1; return x / -1;",
+    ] {
+        assert_ir_rejects_source(
+            &format!("fn main() -> Int {{ {body} }}"),
+            RtErrorKind::IntegerOverflow,
+        );
+    }
+}
+
+#[test]
+fn native_and_ir_accept_same_wrapping_math_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+import math;
+
+fn main() -> Int {
+  let max = 9223372036854775807;
+  let min = math.wrappingAdd(max, 1);
+  let ok = 0;
+  if (min == -9223372036854775807 - 1) { ok = ok + 1; }
+  if (math.wrappingSub(min, 1) == max) { ok = ok + 1; }
+  if (math.wrappingMul(max, 2) == -2) { ok = ok + 1; }
+  if (min % -1 == 0) { ok = ok + 1; }
+  if (max - 1 + 1 == max) { ok = ok + 1; }
+  return ok;
+}
+"#,
+        5,
+    );
+}
+
+#[test]
+fn native_and_ir_accept_same_prefixed_numeric_literal_source() {
+    assert_native_and_ir_accept_same_int_source(
+        r#"
+fn main() -> Int {
+  let ok = 0;
+  if (0xFF == 255) { ok = ok + 1; }
+  if (0o77 == 63) { ok = ok + 1; }
+  if (0b1010 == 10) { ok = ok + 1; }
+  if (1_000_000 == 1000000) { ok = ok + 1; }
+  if (1_000.5 == 1000.5) { ok = ok + 1; }
+  let arr: [Int; 0x3] = [0; 0b11];
+  return ok + arr[2];
+}
+"#,
+        5,
+    );
 }
 
 #[test]
@@ -517,3 +918,59 @@ fn main() -> Int {
 "#;
     assert_native_and_ir_accept_same_int_source(source, 42);
 }
+
+#[test]
+fn native_and_ir_accept_same_defer_source() {
+    let source = r#"
+let trace: Int = 0;
+
+fn record(n: Int) -> Int {
+  trace = trace * 10 + n;
+  return trace;
+}
+
+fn pick(flag: Bool) -> Int {
+  defer record(1);
+  defer record(2);
+  if (flag) {
+    return 7;
+  }
+  record(3);
+  return 8;
+}
+
+fn main() -> Int {
+  let a = pick(true);
+  let b = pick(false);
+  if (trace != 21321) {
+    return 0;
+  }
+  return a * 10 + b;
+}
+"#;
+    assert_native_and_ir_accept_same_int_source(source, 78);
+}
+
+#[test]
+fn native_and_ir_accept_same_block_shadowing_source() {
+    let source = r#"
+fn main() -> Int {
+  let x = 1;
+  if (true) {
+    let x = 2;
+  } else {
+    let x = 3;
+  }
+  let i = 0;
+  while (i < 2) {
+    let x = 10;
+    i = i + 1;
+  }
+  for (let i = 5; i < 6; i = i + 1) {
+    let x = 20;
+  }
+  return x * 10 + i;
+}
+"#;
+    assert_native_and_ir_accept_same_int_source(source, 12);
+}
@@ -114,6 +114,29 @@ impl RtHost for TestHost {
         Ok(value + 5)
     }
 
+    fn ffi_call_1_float(&mut self, symbol: skepart::RtHandle, value: f64) -> RtResult<f64> {
+        self.net_lookup_handle_kind(symbol)?;
+        self.out
+            .lock()
+            .expect("lock trace")
+            .push_str(&format!("[fficall1float {}={}]", symbol.id, value));
+        Ok(value * 2.0)
+    }
+
+    fn ffi_call_2_float_float(
+        &mut self,
+        symbol: skepart::RtHandle,
+        left: f64,
+        right: f64,
+    ) -> RtResult<f64> {
+        self.net_lookup_handle_kind(symbol)?;
+        self.out.lock().expect("lock trace").push_str(&format!(
+            "[fficall2floatfloat {}={}|{}]",
+            symbol.id, left, right
+        ));
+        Ok(left + right)
+    }
+
     fn ffi_call_1_int_void(&mut self, symbol: skepart::RtHandle, value: i64) -> RtResult<()> {
         self.net_lookup_handle_kind(symbol)?;
         self.out
@@ -524,6 +547,7 @@ fn interpreter_rejects_non_bool_branch_condition() {
                 terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
             },
         ],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
@@ -561,6 +585,7 @@ fn interpreter_rejects_indirect_call_on_non_closure() {
             }],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
@@ -601,6 +626,7 @@ fn interpreter_rejects_wrong_arity_direct_and_indirect_calls() {
             instrs: Vec::new(),
             terminator: Terminator::Return(Some(ir::Operand::Local(ir::LocalId(0)))),
         }],
+        inline_hint: false,
     };
     let direct = IrFunction {
         id: FunctionId(0),
@@ -627,6 +653,7 @@ fn interpreter_rejects_wrong_arity_direct_and_indirect_calls() {
             }],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let direct_program = IrProgram {
         functions: vec![direct, callee.clone()],
@@ -673,6 +700,7 @@ fn interpreter_rejects_wrong_arity_direct_and_indirect_calls() {
             ],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let indirect_program = IrProgram {
         functions: vec![indirect, callee],
@@ -713,6 +741,7 @@ fn interpreter_rejects_store_value_type_mismatch() {
             }],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
@@ -753,6 +782,7 @@ fn interpreter_initializes_parameter_backed_locals_without_name_matching() {
             instrs: Vec::new(),
             terminator: Terminator::Return(Some(ir::Operand::Local(ir::LocalId(0)))),
         }],
+        inline_hint: false,
     };
     let main = IrFunction {
         id: FunctionId(0),
@@ -776,6 +806,7 @@ fn interpreter_initializes_parameter_backed_locals_without_name_matching() {
             }],
             terminator: Terminator::Return(Some(ir::Operand::Temp(ir::TempId(0)))),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![main, callee],
@@ -968,6 +999,241 @@ fn main() -> Int {
     assert_eq!(value, IrValue::Int(3));
 }
 
+#[test]
+fn interpreter_round_trips_encoding_builtins_and_returns_decode_errors() {
+    let source = r#"
+import bytes;
+import encoding;
+import result;
+
+fn main() -> Int {
+  let raw = bytes.fromString("user:pässword");
+  let b64 = encoding.base64Encode(raw);
+  let hex = encoding.hexEncode(bytes.fromString("hi"));
+  let query = encoding.urlEncode("q=a b&x");
+  if (b64 == "dXNlcjpww6Rzc3dvcmQ="
+      && result.unwrapOk(encoding.base64Decode(b64)) == raw
+      && hex == "6869"
+      && result.unwrapOk(encoding.hexDecode("6869")) == bytes.fromString("hi")
+      && query == "q%3Da%20b%26x"
+      && result.unwrapOk(encoding.urlDecode(query)) == "q=a b&x"
+      && encoding.isValidUtf8(raw)) {
+    return 0;
+  }
+  return 1;
+}
+"#;
+    assert_eq!(common::ir_run_ok(source), IrValue::Int(0));
+
+    let source = r#"
+import encoding;
+import result;
+
+fn main() -> Int {
+  let hex = encoding.hexDecode("zz");
+  let url = encoding.urlDecode("100%");
+  if (result.isErr(hex)
+      && result.unwrapErr(hex) == "encoding.hexDecode found invalid hex digits `zz`"
+      && result.isErr(url)) {
+    return 0;
+  }
+  return 1;
+}
+"#;
+    assert_eq!(common::ir_run_ok(source), IrValue::Int(0));
+}
+
+#[test]
+fn interpreter_hashes_strings_and_bytes_alike() {
+    let source = r#"
+import bytes;
+import hash;
+
+fn main() -> Int {
+  let key = hash.sha256("abc");
+  if (key != "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad") {
+    return 1;
+  }
+  if (hash.sha256(bytes.fromString("abc")) != key || hash.md5("abc") != "900150983cd24fb0d6963f7d28e17f72") {
+    return 2;
+  }
+  if (hash.crc32(bytes.fromString("abc")) != 891568578 || hash.fnv64("abc") != hash.fnv64(bytes.fromString("abc"))) {
+    return 3;
+  }
+  return 0;
+}
+"#;
+    assert_eq!(common::ir_run_ok(source), IrValue::Int(0));
+}
+
+#[test]
+fn interpreter_round_trips_csv_rows_and_header_maps() {
+    let source = r#"
+import csv;
+import map;
+import option;
+import result;
+import vec;
+
+fn main() -> Int {
+  let table = result.unwrapOk(csv.parse("city,pop\n\"Paris, FR\",2100000\n"));
+  let header = option.unwrapSome(vec.get(table, 0));
+  if (vec.len(table) != 2 || option.unwrapSome(vec.get(header, 1)) != "pop") {
+    return 1;
+  }
+
+  let people = result.unwrapOk(csv.parseWithHeader("id,name\n7,\"O\"\"Neil\"\n"));
+  let first = option.unwrapSome(vec.get(people, 0));
+  if (option.unwrapSome(map.get(first, "name")) != "O\"Neil") {
+    return 2;
+  }
+
+  let row: Vec[String] = vec.new();
+  vec.push(row, "a,b");
+  vec.push(row, "plain");
+  let rows: Vec[Vec[String]] = vec.new();
+  vec.push(rows, row);
+  if (csv.stringify(rows) != "\"a,b\",plain\n") {
+    return 3;
+  }
+
+  match (csv.parse("\"open")) {
+    Ok(_) => { return 4; }
+    Err(message) => {
+      if (message != "unterminated quoted field on line 1") {
+        return 5;
+      }
+    }
+  }
+  return 0;
+}
+"#;
+    assert_eq!(common::ir_run_ok(source), IrValue::Int(0));
+}
+
+#[test]
+fn interpreter_supports_checked_division_builtins() {
+    let source = r#"
+import math;
+import option;
+
+fn main() -> Int {
+  let zero = 0;
+  let ok = 0;
+  if (option.isNone(math.checkedDiv(5, zero))) { ok = ok + 1; }
+  if (option.unwrapSome(math.checkedDiv(9, 2)) == 4) { ok = ok + 1; }
+  if (math.divOr(9, zero, -1) == -1) { ok = ok + 1; }
+  if (math.divOr(-9223372036854775807 - 1, -1, 7) == 7) { ok = ok + 1; }
+  return ok;
+}
+"#;
+
+    let value = common::ir_run_ok(source);
+    assert_eq!(value, IrValue::Int(4));
+}
+
+#[test]
+fn interpreter_round_trips_values_through_serde() {
+    let source = r#"
+import map;
+import option;
+import serde;
+import vec;
+
+struct Point { x: Int, y: Float }
+struct Shape { name: String, points: [Point; 2], tag: Option[Char], pair: (Int, Bool) }
+
+fn main() -> Int {
+  let shape = Shape {
+    name: "tri\"angle",
+    points: [Point { x: 1, y: 0.5 }, Point { x: -2, y: 3.0 }],
+    tag: None(),
+    pair: (7, true)
+  };
+  let json = serde.toJson(shape);
+  let back: Result[Shape, String] = serde.fromJson(json);
+  let back2: Result[Shape, String] = serde.decode(serde.encode(shape));
+
+  let counts: Vec[Map[String, Int]] = vec.new();
+  let m: Map[String, Int] = map.new();
+  map.insert(m, "b", 2);
+  map.insert(m, "a", -1);
+  vec.push(counts, m);
+  let counts_json = serde.toJson(counts);
+  let counts_back: Result[Vec[Map[String, Int]], String] = serde.decode(serde.encode(counts));
+
+  let ok = 0;
+  if (json == "{\"name\":\"tri\\\"angle\",\"points\":[{\"x\":1,\"y\":0.5},{\"x\":-2,\"y\":3.0}],\"tag\":null,\"pair\":[7,true]}") { ok = ok + 1; }
+  if (back == Ok(shape)) { ok = ok + 1; }
+  if (back2 == Ok(shape)) { ok = ok + 1; }
+  if (counts_json == "[{\"a\":-1,\"b\":2}]") { ok = ok + 1; }
+  match (counts_back) {
+    Ok(v) => { if (vec.len(v) == 1 && map.get(option.unwrapSome(vec.get(v, 0)), "a") == Some(-1)) { ok = ok + 1; } }
+    Err(e) => {}
+  }
+  return ok;
+}
+"#;
+
+    let value = common::ir_run_ok(source);
+    assert_eq!(value, IrValue::Int(5));
+}
+
+#[test]
+fn interpreter_keeps_nested_options_through_binary_serde() {
+    let source = r#"
+import serde;
+
+fn main() -> Int {
+  let inner: Option[Option[Int]] = Some(None());
+  let outer: Option[Option[Int]] = None();
+  let a: Result[Option[Option[Int]], String] = serde.decode(serde.encode(inner));
+  let b: Result[Option[Option[Int]], String] = serde.decode(serde.encode(outer));
+  if (a == Ok(inner) && b == Ok(outer)) {
+    return 0;
+  }
+  return 1;
+}
+"#;
+
+    let value = common::ir_run_ok(source);
+    assert_eq!(value, IrValue::Int(0));
+}
+
+#[test]
+fn interpreter_reports_serde_type_mismatches_as_errors() {
+    let source = r#"
+import serde;
+
+struct Point { x: Int, y: Int }
+
+fn main() -> String {
+  let wrong: Result[Point, String] = serde.fromJson("{\"x\": 1, \"y\": \"two\"}");
+  let missing: Result[Point, String] = serde.fromJson("{\"x\": 1}");
+  let bad: Result[[Int; 2], String] = serde.fromJson("[1, 2");
+  let other: Result[Point, String] = serde.decode(serde.encode("text"));
+  let out = "";
+  match (wrong) { Ok(p) => {} Err(e) => { out = out + e + "\n"; } }
+  match (missing) { Ok(p) => {} Err(e) => { out = out + e + "\n"; } }
+  match (bad) { Ok(p) => {} Err(e) => { out = out + e + "\n"; } }
+  match (other) { Ok(p) => {} Err(e) => { out = out + e; } }
+  return out;
+}
+"#;
+
+    let value = common::ir_run_ok(source);
+    assert_eq!(
+        value,
+        IrValue::String(
+            "type mismatch at `$.y`: expected Int, got a string\n\
+             missing field `y` at `$`\n\
+             invalid JSON at byte 5: expected `,` or `]`\n\
+             serde.decode found data encoded from a different type"
+                .into()
+        )
+    );
+}
+
 #[test]
 fn interpreter_supports_option_values_and_equality() {
     let source = r#"
@@ -1273,6 +1539,113 @@ fn main() -> Int {
     );
 }
 
+#[test]
+fn interpreter_exchanges_udp_datagrams_over_loopback() {
+    let source = r#"
+import bytes;
+import net;
+import result;
+
+fn main() -> Int {
+  let a: net.UdpSocket = result.unwrapOk(net.bindUdp("127.0.0.1:0"));
+  let b: net.UdpSocket = result.unwrapOk(net.bindUdp("127.0.0.1:0"));
+  let a_addr = result.unwrapOk(net.udpLocalAddr(a));
+  let b_addr = result.unwrapOk(net.udpLocalAddr(b));
+  result.unwrapOk(net.sendTo(a, b_addr, bytes.fromString("ping")));
+  let got = result.unwrapOk(bytes.toString(result.unwrapOk(net.recv(b))));
+  result.unwrapOk(net.udpConnect(b, a_addr));
+  result.unwrapOk(net.send(b, bytes.fromString("pong")));
+  let reply = result.unwrapOk(bytes.toString(result.unwrapOk(net.recv(a))));
+  net.closeUdp(a);
+  net.closeUdp(b);
+  if (got == "ping" && reply == "pong") {
+    return 0;
+  }
+  return 1;
+}
+"#;
+
+    let value = common::ir_run_ok(source);
+    assert_eq!(value, IrValue::Int(0));
+}
+
+#[test]
+fn interpreter_echoes_udp_datagrams_back_to_the_recv_from_sender() {
+    let source = r#"
+import bytes;
+import net;
+import result;
+
+fn main() -> Int {
+  let server: net.UdpSocket = result.unwrapOk(net.bindUdp("127.0.0.1:0"));
+  let client: net.UdpSocket = result.unwrapOk(net.bindUdp("127.0.0.1:0"));
+  let server_addr = result.unwrapOk(net.udpLocalAddr(server));
+  let client_addr = result.unwrapOk(net.udpLocalAddr(client));
+  result.unwrapOk(net.sendTo(client, server_addr, bytes.fromString("ping")));
+  let (data, sender) = result.unwrapOk(net.recvFrom(server, 1024));
+  result.unwrapOk(net.sendTo(server, sender, data));
+  let echo = result.unwrapOk(bytes.toString(result.unwrapOk(net.recv(client))));
+  net.closeUdp(server);
+  net.closeUdp(client);
+  if (sender == client_addr && echo == "ping") {
+    return 0;
+  }
+  return 1;
+}
+"#;
+
+    let value = common::ir_run_ok(source);
+    assert_eq!(value, IrValue::Int(0));
+}
+
+#[test]
+fn interpreter_persists_store_entries_between_opens() {
+    let dir = common::make_temp_dir("skepa_interp_store");
+    let path = dir.join("counts.db");
+    let source = r#"
+import option;
+import result;
+import store;
+import vec;
+
+fn bump(path: String) -> Int {
+  let db: store.Store = result.unwrapOk(store.open(path));
+  let current = match (result.unwrapOk(store.get(db, "runs"))) {
+    Some(text) => text,
+    None => "",
+  };
+  let next = current + "x";
+  result.unwrapOk(store.set(db, "runs", next));
+  result.unwrapOk(store.set(db, "scratch", "gone soon"));
+  let removed = result.unwrapOk(store.delete(db, "scratch"));
+  store.close(db);
+  if (!removed) {
+    return -1;
+  }
+  return 0;
+}
+
+fn main() -> Int {
+  let path = "PATH";
+  bump(path);
+  bump(path);
+  let db: store.Store = result.unwrapOk(store.open(path));
+  let keys = result.unwrapOk(store.keys(db));
+  let runs = option.unwrapSome(result.unwrapOk(store.get(db, "runs")));
+  store.close(db);
+  if (vec.len(keys) == 1 && runs == "xx") {
+    return 0;
+  }
+  return 1;
+}
+"#
+    .replace("PATH", &path.to_string_lossy());
+
+    let value = common::ir_run_ok(&source);
+    assert_eq!(value, IrValue::Int(0));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 #[test]
 fn interpreter_carries_ffi_library_and_symbol_handles() {
     let source = r#"
@@ -1332,6 +1705,36 @@ fn main() -> Int {
     assert!(trace.contains("[fficall1int 3=7]"), "trace was: {trace}");
 }
 
+#[test]
+fn interpreter_lowers_linked_extern_float_calls_through_ffi_runtime() {
+    let source = r#"
+extern("test-lib") fn twice(x: Float) -> Float;
+extern("test-lib") fn sum(a: Float, b: Float) -> Float;
+
+fn main() -> Float {
+  return twice(1.25) + sum(0.5, 2.0);
+}
+"#;
+
+    let program = ir::lowering::compile_source(source).expect("IR lowering should succeed");
+    let trace = Arc::new(Mutex::new(String::new()));
+    let host = TestHost {
+        out: Arc::clone(&trace),
+        next_handle_id: 0,
+    };
+    let result = IrInterpreter::with_host(&program, Box::new(host)).run_main();
+    assert_eq!(result.expect("program should run"), IrValue::Float(5.0));
+    let trace = trace.lock().expect("lock trace").clone();
+    assert!(
+        trace.contains("[fficall1float 1=1.25]"),
+        "trace was: {trace}"
+    );
+    assert!(
+        trace.contains("[fficall2floatfloat 3=0.5|2]"),
+        "trace was: {trace}"
+    );
+}
+
 #[test]
 fn interpreter_lowers_linked_extern_calls_through_ffi_builtins() {
     let source = r#"
@@ -1821,6 +2224,42 @@ fn main() -> Int {
     assert_eq!(value, IrValue::Int(0));
 }
 
+#[test]
+fn interpreter_recovers_from_failed_reads_and_zero_denominators() {
+    let source = r#"
+import fs;
+import math;
+
+fn read_config(path: String) -> Result[String, String] {
+  let text = fs.readText(path)?;
+  return Ok(text);
+}
+
+fn ratio(a: Int, b: Int) -> Option[Int] {
+  let q = math.checkedDiv(a, b)?;
+  return Some(q * 10);
+}
+
+fn main() -> Int {
+  let recovered: String = match (read_config("/definitely/missing/skepa/config.txt")) {
+    Ok(text) => text,
+    Err(e) => "default",
+  };
+  let r: Int = match (ratio(1, 0)) {
+    Some(v) => v,
+    None => -1,
+  };
+  if (recovered == "default" && r == -1 && ratio(9, 3) == Some(30)) {
+    return 0;
+  }
+  return 1;
+}
+"#;
+
+    let value = common::ir_run_ok(source);
+    assert_eq!(value, IrValue::Int(0));
+}
+
 #[test]
 fn interpreter_supports_match_expression() {
     let source = r#"
@@ -1844,3 +2283,735 @@ fn main() -> Int {
     let value = common::ir_run_ok(source);
     assert_eq!(value, IrValue::Int(7));
 }
+
+#[test]
+fn interpreter_runs_defers_in_reverse_order_on_every_exit() {
+    let source = r#"
+let trace: Int = 0;
+
+fn record(n: Int) -> Int {
+  trace = trace * 10 + n;
+  return trace;
+}
+
+fn early(flag: Bool) -> Int {
+  defer record(1);
+  defer record(2);
+  if (flag) {
+    return trace;
+  }
+  record(3);
+  return trace;
+}
+
+fn half(x: Int) -> Option[Int] {
+  if (x % 2 == 0) {
+    return Some(x / 2);
+  }
+  return None();
+}
+
+fn tried(x: Int) -> Option[Int] {
+  defer record(4);
+  let h = half(x)?;
+  return Some(h);
+}
+
+fn silent() -> Void {
+  defer record(5);
+}
+
+fn main() -> Int {
+  let returned = early(true);
+  if (returned != 0 || trace != 21) {
+    return 1;
+  }
+  trace = 0;
+  early(false);
+  if (trace != 321) {
+    return 2;
+  }
+  trace = 0;
+  tried(3);
+  tried(4);
+  silent();
+  return trace;
+}
+"#;
+
+    let value = common::ir_run_ok(source);
+    assert_eq!(value, IrValue::Int(445));
+}
+
+#[test]
+fn interpreter_resolves_defer_names_at_the_defer_statement() {
+    let source = r#"
+let trace: Int = 0;
+
+fn record(n: Int) -> Int {
+  trace = trace * 10 + n;
+  return trace;
+}
+
+fn shadowed_in_block() -> Int {
+  let x = 1;
+  defer record(x);
+  if (true) {
+    let x = 2;
+    return x;
+  }
+  return 0;
+}
+
+fn updated() -> Int {
+  let x = 5;
+  defer record(x);
+  x = 6;
+  return x;
+}
+
+fn main() -> Int {
+  let total = shadowed_in_block() + updated();
+  if (total != 8) {
+    return 0;
+  }
+  return trace;
+}
+"#;
+
+    let value = common::ir_run_ok(source);
+    assert_eq!(value, IrValue::Int(16));
+}
+
+#[test]
+fn interpreter_scopes_block_locals_to_their_block() {
+    let source = r#"
+fn main() -> Int {
+  let x = 1;
+  if (true) {
+    let x = 2;
+  } else {
+    let x = 3;
+  }
+  let i = 0;
+  while (i < 2) {
+    let x = 10;
+    i = i + 1;
+  }
+  for (let i = 5; i < 6; i = i + 1) {
+    let x = 20;
+  }
+  return x * 10 + i;
+}
+"#;
+
+    let value = common::ir_run_ok(source);
+    assert_eq!(value, IrValue::Int(12));
+}
+
+#[test]
+fn interpreter_reports_explicit_panics_with_their_message() {
+    let source = r#"
+fn check(n: Int) -> Int {
+  if (n > 0) {
+    return n;
+  }
+  panic("n must be positive");
+}
+
+fn main() -> Int {
+  return check(-1);
+}
+"#;
+
+    match common::ir_run_err(source) {
+        IrInterpError::Panic(message) => assert_eq!(message, "n must be positive"),
+        other => panic!("expected panic, got {other:?}"),
+    }
+}
+
+#[test]
+fn interpreter_runs_functions_ending_in_infinite_loops_and_match() {
+    let source = r#"
+fn firstOver(limit: Int) -> Int {
+  let i = 1;
+  while (true) {
+    if (i * i > limit) {
+      return i;
+    }
+    i = i + 1;
+  }
+}
+
+fn sign(n: Int) -> Int {
+  match (n) {
+    0 => { return 0; }
+    _ => {
+      if (n > 0) {
+        return 1;
+      }
+      return -1;
+    }
+  }
+}
+
+fn main() -> Int {
+  return firstOver(50) * 10 + sign(-4);
+}
+"#;
+
+    assert_eq!(common::ir_run_ok(source), IrValue::Int(79));
+}
+
+#[test]
+fn interpreter_calls_functions_by_name_and_keeps_globals_between_calls() {
+    let program = common::compile_ir_ok(
+        r#"
+let total: Int = 10;
+
+fn bump(by: Int) -> Int {
+  total = total + by;
+  return total;
+}
+
+fn main() -> Int {
+  return 0;
+}
+"#,
+    );
+    let mut interp = IrInterpreter::new(&program);
+    assert_eq!(interp.global("total"), Some(&IrValue::Unit));
+    assert_eq!(
+        interp.call("bump", vec![IrValue::Int(5)]),
+        Ok(IrValue::Int(15))
+    );
+    assert_eq!(
+        interp.call("bump", vec![IrValue::Int(2)]),
+        Ok(IrValue::Int(17))
+    );
+    assert_eq!(interp.global("total"), Some(&IrValue::Int(17)));
+    assert_eq!(interp.global("missing"), None);
+    assert_eq!(
+        interp.call("nope", Vec::new()),
+        Err(IrInterpError::UnknownFunction("nope".to_string()))
+    );
+}
+
+#[test]
+fn interpreter_calls_registered_host_closures_passed_as_function_values() {
+    let program = common::compile_ir_ok(
+        r#"
+import arr;
+
+fn apply(f: Fn(Int) -> Int, x: Int) -> Int {
+  return f(x);
+}
+
+fn mapped(f: Fn(Int) -> Int) -> Int {
+  let out = arr.map([1, 2, 3], f);
+  return out[0] + out[1] + out[2];
+}
+
+fn main() -> Int {
+  return 0;
+}
+"#,
+    );
+    let seen = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let recorded = seen.clone();
+    let mut functions = skepart::RtFunctionRegistry::new();
+    let double = functions.register_closure(move |_host, args| {
+        let value = args[0].expect_int()?;
+        recorded.borrow_mut().push(value);
+        Ok(IrValue::Int(value * 2))
+    });
+
+    let mut interp = IrInterpreter::new(&program);
+    interp.set_functions(functions);
+    assert_eq!(
+        interp.call("apply", vec![IrValue::Function(double), IrValue::Int(21)]),
+        Ok(IrValue::Int(42))
+    );
+    assert_eq!(
+        interp.call("mapped", vec![IrValue::Function(double)]),
+        Ok(IrValue::Int(12))
+    );
+    assert_eq!(*seen.borrow(), vec![21, 1, 2, 3]);
+}
+
+#[test]
+fn interpreter_resolves_calls_when_program_functions_are_out_of_id_order() {
+    let mut program = common::compile_ir_ok(
+        r#"
+fn twice(x: Int) -> Int {
+  return x * 2;
+}
+
+fn inc(x: Int) -> Int {
+  return twice(x) + 1;
+}
+
+fn main() -> Int {
+  return inc(20);
+}
+"#,
+    );
+    program.functions.reverse();
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(41))
+    );
+}
+
+#[test]
+fn interpreter_runs_for_in_loops_over_fused_iter_pipelines() {
+    let program = common::compile_ir_ok(
+        r#"
+import iter;
+import vec;
+
+let squared: Int = 0;
+
+fn square(x: Int) -> Int {
+  squared = squared + 1;
+  return x * x;
+}
+
+fn isOdd(x: Int) -> Bool {
+  return x % 2 == 1;
+}
+
+fn main() -> Int {
+  let total = 0;
+  for (x in iter.take(iter.filter(iter.map(iter.range(0, 1000), square), isOdd), 3)) {
+    total = total + x;
+  }
+  let picked = iter.collect(iter.filter([4, 5, 6, 7], isOdd));
+  let words: Vec[Int] = vec.new();
+  vec.push(words, 10);
+  vec.push(words, 20);
+  vec.push(words, 30);
+  for (w in words) {
+    if (w == 20) {
+      continue;
+    }
+    if (w == 30) {
+      break;
+    }
+    total = total + w;
+  }
+  return total * 1000 + squared * 10 + vec.len(picked);
+}
+"#,
+    );
+    // 1 + 9 + 25 = 35, plus 10 from the Vec; `take` stops after squaring 0..=5.
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(45_062))
+    );
+}
+
+#[test]
+fn interpreter_matches_struct_array_and_binding_patterns() {
+    let program = common::compile_ir_ok(
+        r#"
+struct Point { x: Int, y: Int }
+
+fn classify(p: Point) -> Int {
+  return match (p) {
+    Point { x: 0, y: 0 } => 0,
+    Point { x: 0, y } => y,
+    Point { x, y: 0 } => x * 10,
+    other => other.x + other.y,
+  };
+}
+
+fn tail(xs: [Int; 4]) -> Int {
+  match (xs) {
+    [0, ..] => { return -1; }
+    [first, second, rest..] => { return first * 100 + second * 10 + rest[0] + rest[1]; }
+  }
+}
+
+fn main() -> Int {
+  let a = classify(Point { x: 0, y: 0 });
+  let b = classify(Point { x: 0, y: 7 });
+  let c = classify(Point { x: 3, y: 0 });
+  let d = classify(Point { x: 2, y: 5 });
+  return a + b + c + d + tail([1, 2, 3, 4]) + tail([0, 9, 9, 9]);
+}
+"#,
+    );
+    // 0 + 7 + 30 + 7 + (100 + 20 + 7) - 1
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(170))
+    );
+}
+
+#[test]
+fn interpreter_falls_through_failed_match_guards() {
+    let program = common::compile_ir_ok(
+        r#"
+fn bucket(n: Int) -> Int {
+  match (n) {
+    x if x > 100 => { return 3; }
+    x if x > 10 => { return 2; }
+    0 => { return 0; }
+    _ => { return 1; }
+  }
+}
+
+fn main() -> Int {
+  let big = match (Some(150)) {
+    Some(v) if v < 100 => 0,
+    Some(v) => v,
+    None => -1,
+  };
+  return bucket(500) * 1000 + bucket(50) * 100 + bucket(5) * 10 + bucket(0) + big;
+}
+"#,
+    );
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(3_360))
+    );
+}
+
+#[test]
+fn interpreter_runs_destructuring_let_bindings() {
+    let program = common::compile_ir_ok(
+        r#"
+struct Pair { left: Int, right: Int }
+
+fn main() -> Int {
+  let Pair { left, right: r } = Pair { left: 4, right: 7 };
+  let [a, _, rest..] = [1, 2, 30, 400];
+  return left * 10 + r + a + rest[0] + rest[1];
+}
+"#,
+    );
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(478))
+    );
+}
+
+#[test]
+fn interpreter_builds_and_reads_tuples() {
+    let program = common::compile_ir_ok(
+        r#"
+struct Point { x: Int, y: Int }
+
+fn minmax(xs: [Int; 4]) -> (Int, Int) {
+  let lo = xs[0];
+  let hi = xs[0];
+  for (x in xs) {
+    if (x < lo) {
+      lo = x;
+    }
+    if (x > hi) {
+      hi = x;
+    }
+  }
+  return (lo, hi);
+}
+
+fn main() -> Int {
+  let bounds = minmax([5, 2, 9, 4]);
+  let nested: ((Int, String), Point) = ((7, "seven"), Point { x: 1, y: 2 });
+  let label = nested.0.1;
+  if (label != "seven") {
+    return -1;
+  }
+  return bounds.0 * 100 + bounds.1 * 10 + nested.0.0 + nested.1.y;
+}
+"#,
+    );
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(299))
+    );
+}
+
+#[test]
+fn interpreter_monomorphizes_generic_structs() {
+    let program = common::compile_ir_ok(
+        r#"
+import option;
+import str;
+
+struct Box[T] {
+  value: T,
+}
+
+struct Pair[A, B] {
+  first: A,
+  second: B,
+}
+
+struct Node[T] {
+  value: T,
+  next: Option[Node[T]],
+}
+
+fn unbox(b: Box[Int]) -> Int {
+  return b.value;
+}
+
+fn main() -> Int {
+  let b = Box[Int] { value: 3 };
+  let label: Box[String] = Box[String] { value: "four" };
+  let p = Pair[Int, Box[String]] { first: 40, second: label };
+  let n = Node[Int] { value: 1, next: Some(Node[Int] { value: 200, next: None() }) };
+  let Box { value } = b;
+  return unbox(b) + p.first + str.len(p.second.value) + option.unwrapSome(n.next).value + value * 1000;
+}
+"#,
+    );
+    assert_eq!(
+        program
+            .structs
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "Box[Int]",
+            "Box[String]",
+            "Pair[Int, Box[String]]",
+            "Node[Int]"
+        ]
+    );
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(3_247))
+    );
+}
+
+#[test]
+fn interpreter_walks_recursive_structs_behind_indirection() {
+    let program = common::compile_ir_ok(
+        r#"
+import option;
+import vec;
+
+struct Node {
+  value: Int,
+  next: Option[Node],
+}
+
+struct Tree {
+  value: Int,
+  children: Vec[Tree],
+}
+
+fn sumList(n: Node) -> Int {
+  if (option.isSome(n.next)) {
+    return n.value + sumList(option.unwrapSome(n.next));
+  }
+  return n.value;
+}
+
+fn sumTree(t: Tree) -> Int {
+  let total = t.value;
+  for (child in t.children) {
+    total = total + sumTree(child);
+  }
+  return total;
+}
+
+fn main() -> Int {
+  let list = Node { value: 1, next: Some(Node { value: 2, next: Some(Node { value: 3, next: None() }) }) };
+  let leaves: Vec[Tree] = vec.new();
+  vec.push(leaves, Tree { value: 100, children: vec.new() });
+  vec.push(leaves, Tree { value: 200, children: vec.new() });
+  let tree = Tree { value: 1000, children: leaves };
+  return sumList(list) + sumTree(tree);
+}
+"#,
+    );
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(1_306))
+    );
+}
+
+#[test]
+fn interpreter_stats_count_opcodes_functions_and_call_sites() {
+    let program = common::compile_ir_ok(
+        r#"
+import arr;
+
+fn fib(n: Int) -> Int {
+  if (n < 2) {
+    return n;
+  }
+  return fib(n - 1) + fib(n - 2);
+}
+
+fn inc(x: Int) -> Int {
+  return x + 1;
+}
+
+fn main() -> Int {
+  let bumped = arr.map([1, 2], inc);
+  return fib(5) + bumped[1];
+}
+"#,
+    );
+    let mut interp = IrInterpreter::new(&program);
+    assert!(interp.stats().is_none());
+    interp.enable_stats();
+    assert_eq!(interp.call("main", Vec::new()), Ok(IrValue::Int(8)));
+
+    let stats = interp.stats().expect("stats enabled");
+    assert_eq!(stats.functions["main"].calls, 1);
+    assert_eq!(stats.functions["fib"].calls, 15);
+    assert_eq!(stats.functions["inc"].calls, 2);
+    let site =
+        |caller: &str, callee: &str| stats.call_sites[&(caller.to_string(), callee.to_string())];
+    assert_eq!(site("<runtime>", "main"), 1);
+    assert_eq!(site("main", "fib"), 1);
+    assert_eq!(site("fib", "fib"), 14);
+    assert_eq!(site("main", "inc"), 2);
+    assert_eq!(stats.opcodes["CallBuiltin"], 1);
+    assert_eq!(
+        stats.opcodes.values().sum::<u64>(),
+        stats
+            .functions
+            .values()
+            .map(|f| f.instructions)
+            .sum::<u64>()
+    );
+
+    let report = stats.report(1);
+    assert!(report.starts_with("hottest opcodes:\n"), "{report}");
+    assert!(
+        report.contains("hottest functions:\n  fib calls=15 "),
+        "{report}"
+    );
+    assert!(
+        report.ends_with("hottest call sites:\n  fib -> fib 14\n"),
+        "{report}"
+    );
+}
+
+#[test]
+fn interpreter_renders_values_with_to_string_or_default_display() {
+    let program = common::compile_ir_ok(
+        r#"
+import io;
+
+struct User {
+  id: Int,
+  name: String,
+}
+
+struct Money {
+  cents: Int,
+}
+
+impl Money {
+  fn toString(self) -> String {
+    return io.format("$%d.%d", self.cents / 100, self.cents % 100);
+  }
+}
+
+struct Order {
+  owner: User,
+  total: Money,
+  tags: [String; 2],
+  note: Option[String],
+}
+
+fn main() -> Int {
+  let u = User { id: 1, name: "ada" };
+  let order = Order { owner: u, total: Money { cents: 5 }, tags: ["a", "b"], note: None() };
+  let score = 0;
+  if (io.format("%v", u) == "User { id: 1, name: \"ada\" }") { score = score + 1; }
+  if (io.format("%v|%v", Money { cents: 1250 }, "plain") == "$12.50|plain") { score = score + 10; }
+  if (io.format("%v", order) == "Order { owner: User { id: 1, name: \"ada\" }, total: Money { cents: 5 }, tags: [\"a\", \"b\"], note: None() }") {
+    score = score + 100;
+  }
+  if (io.format("%v %v", (1, 'x'), Some([1.5])) == "(1, 'x') Some([1.5])") { score = score + 1000; }
+  io.printAny(order.total);
+  return score;
+}
+"#,
+    );
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(1111))
+    );
+}
+
+#[test]
+fn interpreter_compares_structs_arrays_and_tuples_deeply() {
+    let program = common::compile_ir_ok(
+        r#"
+import arr;
+
+struct Point {
+  x: Int,
+  label: String,
+  tags: [Int; 2],
+}
+
+struct Segment {
+  start: Point,
+  end: Option[Point],
+}
+
+fn main() -> Int {
+  let a = Point { x: 1, label: "a", tags: [1, 2] };
+  let b = Point { x: 1, label: "a", tags: [1, 2] };
+  let c = Point { x: 1, label: "a", tags: [1, 3] };
+  let points = [a, c, b];
+  let score = 0;
+  if (a == b && a != c) { score = score + 1; }
+  let s1 = Segment { start: a, end: Some(c) };
+  let s2 = Segment { start: b, end: Some(c) };
+  let s3 = Segment { start: a, end: None() };
+  if (s1 == s2) { score = score + 10; }
+  if (s1 != s3) { score = score + 100; }
+  if ([[1, 2], [3, 4]] == [[1, 2], [3, 4]] && [[1, 2]] != [[2, 1]]) { score = score + 1000; }
+  if ((1, a) == (1, b) && (1, a) != (2, a)) { score = score + 10000; }
+  if (arr.contains(points, c)) { score = score + 100000; }
+  return score + arr.indexOf(points, c) * 1000000 + arr.count(points, a) * 10000000;
+}
+"#,
+    );
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(21_111_111))
+    );
+}
+
+#[test]
+fn interpreter_destructures_multiple_return_values() {
+    let program = common::compile_ir_ok(
+        r#"
+fn divmod(a: Int, b: Int) -> (Int, Int) {
+  return (a / b, a % b);
+}
+
+fn describe(pair: (Int, String)) -> Int {
+  match (pair) {
+    (0, _) => { return 0; }
+    (n, "double") => { return n * 2; }
+    (n, _) => { return n; }
+  }
+}
+
+fn main() -> Int {
+  let (q, r) = divmod(47, 10);
+  let (_, rem) = divmod(9, 4);
+  return q * 1000 + r * 100 + rem * 10 + describe((3, "double")) + describe((0, "x"));
+}
+"#,
+    );
+    assert_eq!(
+        IrInterpreter::new(&program).run_main(),
+        Ok(IrValue::Int(4_716))
+    );
+}
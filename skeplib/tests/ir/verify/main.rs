@@ -19,6 +19,7 @@ fn verifier_rejects_unknown_jump_target() {
             instrs: Vec::new(),
             terminator: Terminator::Jump(BlockId(99)),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
@@ -53,6 +54,7 @@ fn verifier_rejects_missing_entry_block_and_missing_terminator_shape() {
             }],
             terminator: Terminator::Unreachable,
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
@@ -101,6 +103,7 @@ fn verifier_rejects_unknown_direct_call_and_closure_targets() {
                 instrs: vec![instr.clone()],
                 terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
             }],
+            inline_hint: false,
         };
         let program = IrProgram {
             functions: vec![func],
@@ -167,6 +170,7 @@ fn verifier_rejects_duplicate_block_ids_unknown_structs_and_fields() {
                 terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
             },
         ],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
@@ -215,6 +219,7 @@ fn verifier_rejects_unknown_temp_local_global_and_module_init() {
             ],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
@@ -281,6 +286,7 @@ fn verifier_rejects_duplicate_param_local_and_temp_ids() {
             instrs: Vec::new(),
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
@@ -317,6 +323,7 @@ fn verifier_rejects_bad_call_signature_return_mismatch_and_bad_branch_operand_ty
             instrs: Vec::new(),
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(1)))),
         }],
+        inline_hint: false,
     };
     let main = IrFunction {
         id: FunctionId(0),
@@ -352,6 +359,7 @@ fn verifier_rejects_bad_call_signature_return_mismatch_and_bad_branch_operand_ty
                 terminator: Terminator::Return(None),
             },
         ],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![main, callee],
@@ -410,6 +418,7 @@ fn verifier_rejects_bad_direct_builtin_and_indirect_call_types() {
             ],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(1)))),
         }],
+        inline_hint: false,
     };
     let main = IrFunction {
         id: FunctionId(0),
@@ -468,6 +477,7 @@ fn verifier_rejects_bad_direct_builtin_and_indirect_call_types() {
             ],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![main, callee],
@@ -539,6 +549,7 @@ fn verifier_rejects_non_int_indexes_for_array_and_vec_ops() {
             ],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
@@ -577,6 +588,7 @@ fn verifier_rejects_bad_store_closure_array_struct_and_vec_element_types() {
             instrs: Vec::new(),
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let main = IrFunction {
         id: FunctionId(0),
@@ -671,6 +683,7 @@ fn verifier_rejects_bad_store_closure_array_struct_and_vec_element_types() {
             ],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![main, callee],
@@ -777,6 +790,7 @@ fn verifier_rejects_bad_load_get_and_operator_result_types() {
             ],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
@@ -863,6 +877,7 @@ fn verifier_rejects_bitwise_operators_on_non_int_types() {
             ],
             terminator: Terminator::Return(Some(ir::Operand::Const(ir::ConstValue::Int(0)))),
         }],
+        inline_hint: false,
     };
     let program = IrProgram {
         functions: vec![func],
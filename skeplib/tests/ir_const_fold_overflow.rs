@@ -1,7 +1,7 @@
 use skeplib::ir::{self, PrettyIr};
 
 #[test]
-fn const_fold_wraps_max_plus_one_without_debug_panic() {
+fn const_fold_leaves_overflowing_add_for_runtime_check() {
     let source = r#"
 fn main() -> Int {
   return 9223372036854775807 + 1;
@@ -11,23 +11,31 @@ fn main() -> Int {
     let program = ir::lowering::compile_source(source).expect("IR lowering should succeed");
     let printed = PrettyIr::new(&program).to_string();
     assert!(
-        printed.contains(&format!("Int({})", i64::MIN)),
-        "expected wrapped MIN constant, got:\n{printed}"
+        printed.contains("Binary") && printed.contains("Add"),
+        "overflowing add must stay unfolded, got:\n{printed}"
+    );
+    assert!(
+        !printed.contains(&format!("Int({})", i64::MIN)),
+        "overflowing add must not wrap to MIN, got:\n{printed}"
     );
 }
 
 #[test]
 fn const_fold_does_not_fold_min_div_neg_one() {
     // Avoid writing i64::MIN as a positive token (out of range for Int literals).
-    // Fold MAX+1 to MIN first, then ensure MIN / -1 stays unfolded.
+    // Fold -MAX - 1 to MIN first, then ensure MIN / -1 stays unfolded.
     let source = r#"
 fn main() -> Int {
-  return (9223372036854775807 + 1) / -1;
+  return (-9223372036854775807 - 1) / -1;
 }
 "#;
 
     let program = ir::lowering::compile_source(source).expect("IR lowering should succeed");
     let printed = PrettyIr::new(&program).to_string();
+    assert!(
+        printed.contains(&format!("Int({})", i64::MIN)),
+        "expected folded MIN constant, got:\n{printed}"
+    );
     assert!(
         printed.contains("Binary") && printed.contains("Div"),
         "MIN / -1 must stay unfolded, got:\n{printed}"
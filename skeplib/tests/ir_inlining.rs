@@ -0,0 +1,174 @@
+use skeplib::ir::{
+    self, BasicBlock, BlockId, ConstValue, FunctionId, Instr, IrFunction, IrLocal, IrParam,
+    IrProgram, IrTemp, IrType, IrValue, LocalId, Operand, ParamId, TempId, Terminator,
+};
+
+fn direct_callees(program: &IrProgram, caller: &str) -> Vec<FunctionId> {
+    program
+        .functions
+        .iter()
+        .find(|func| func.name == caller)
+        .expect("caller should exist")
+        .blocks
+        .iter()
+        .flat_map(|block| &block.instrs)
+        .filter_map(|instr| match instr {
+            Instr::CallDirect { function, .. } => Some(*function),
+            _ => None,
+        })
+        .collect()
+}
+
+fn function_id(program: &IrProgram, name: &str) -> FunctionId {
+    program
+        .functions
+        .iter()
+        .find(|func| func.name == name)
+        .map(|func| func.id)
+        .expect("function should exist")
+}
+
+#[test]
+fn inline_attribute_inlines_functions_that_make_calls() {
+    let source = r#"
+import str;
+
+fn plain(x: Int) -> Int {
+  return str.len("ab") + x;
+}
+
+@inline
+fn hinted(x: Int) -> Int {
+  return str.len("abc") + x;
+}
+
+fn main() -> Int {
+  return plain(1) + hinted(2);
+}
+"#;
+
+    let program = ir::lowering::compile_source(source).expect("IR lowering should succeed");
+    let value = ir::IrInterpreter::new(&program)
+        .run_main()
+        .expect("IR interpreter should run optimized source");
+    assert_eq!(value, IrValue::Int(8));
+
+    let callees = direct_callees(&program, "main");
+    let (plain, hinted) = (
+        function_id(&program, "plain"),
+        function_id(&program, "hinted"),
+    );
+    assert!(callees.contains(&plain), "{callees:?}");
+    assert!(!callees.contains(&hinted), "{callees:?}");
+}
+
+#[test]
+fn inlining_skips_large_leaf_functions_unless_marked_inline() {
+    let body = (1..=12)
+        .map(|k| format!("x * {k}"))
+        .collect::<Vec<_>>()
+        .join(" + ");
+    let source = format!(
+        "fn small(x: Int) -> Int {{ return x * 2 + 1; }}\n\
+         fn large(x: Int) -> Int {{ return {body}; }}\n\
+         @inline\nfn hinted(x: Int) -> Int {{ return {body}; }}\n\
+         fn main() -> Int {{ return small(1) + large(1) + hinted(1); }}\n"
+    );
+
+    let program = ir::lowering::compile_source(&source).expect("IR lowering should succeed");
+    let value = ir::IrInterpreter::new(&program)
+        .run_main()
+        .expect("IR interpreter should run optimized source");
+    assert_eq!(value, IrValue::Int(3 + 78 + 78));
+
+    let callees = direct_callees(&program, "main");
+    assert_eq!(callees, vec![function_id(&program, "large")]);
+}
+
+#[test]
+fn inlining_binds_parameters_by_local_slot_not_name() {
+    // `id(x)` keeps its parameter in slot 0 under another name, and a later
+    // local reuses the parameter's name.
+    let callee = IrFunction {
+        id: FunctionId(1),
+        name: "id".into(),
+        params: vec![IrParam {
+            id: ParamId(0),
+            name: "x".into(),
+            ty: IrType::Int,
+        }],
+        locals: vec![
+            IrLocal {
+                id: LocalId(0),
+                name: "__arg0".into(),
+                ty: IrType::Int,
+            },
+            IrLocal {
+                id: LocalId(1),
+                name: "x".into(),
+                ty: IrType::Int,
+            },
+        ],
+        temps: vec![IrTemp {
+            id: TempId(0),
+            ty: IrType::Int,
+        }],
+        ret_ty: IrType::Int,
+        entry: BlockId(0),
+        blocks: vec![BasicBlock {
+            id: BlockId(0),
+            name: "entry".into(),
+            instrs: vec![
+                Instr::StoreLocal {
+                    local: LocalId(1),
+                    ty: IrType::Int,
+                    value: Operand::Const(ConstValue::Int(100)),
+                },
+                Instr::LoadLocal {
+                    dst: TempId(0),
+                    ty: IrType::Int,
+                    local: LocalId(0),
+                },
+            ],
+            terminator: Terminator::Return(Some(Operand::Temp(TempId(0)))),
+        }],
+        inline_hint: false,
+    };
+    let main = IrFunction {
+        id: FunctionId(0),
+        name: "main".into(),
+        params: Vec::new(),
+        locals: Vec::new(),
+        temps: vec![IrTemp {
+            id: TempId(0),
+            ty: IrType::Int,
+        }],
+        ret_ty: IrType::Int,
+        entry: BlockId(0),
+        blocks: vec![BasicBlock {
+            id: BlockId(0),
+            name: "entry".into(),
+            instrs: vec![Instr::CallDirect {
+                dst: Some(TempId(0)),
+                ret_ty: IrType::Int,
+                function: FunctionId(1),
+                args: vec![Operand::Const(ConstValue::Int(9))],
+            }],
+            terminator: Terminator::Return(Some(Operand::Temp(TempId(0)))),
+        }],
+        inline_hint: false,
+    };
+    let mut program = IrProgram {
+        functions: vec![main, callee],
+        globals: Vec::new(),
+        structs: Vec::new(),
+        module_init: None,
+    };
+
+    ir::opt::optimize_program(&mut program);
+    assert!(direct_callees(&program, "main").is_empty());
+    let value = ir::IrInterpreter::new(&program)
+        .run_main()
+        .expect("IR interpreter should run inlined program");
+    assert_eq!(value, IrValue::Int(9));
+}
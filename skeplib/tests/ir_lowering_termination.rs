@@ -2,7 +2,7 @@ mod common;
 
 use skepart::RtValue;
 use skeplib::ir::IrInterpreter;
-use skeplib::ir::{ConstValue, Instr, lowering};
+use skeplib::ir::{ConstValue, Instr, Terminator, lowering};
 
 fn int_consts(program: &skeplib::ir::IrProgram) -> Vec<i64> {
     let mut out = Vec::new();
@@ -123,3 +123,40 @@ fn after_continue() -> Int {
         "dead post-continue const leaked into IR"
     );
 }
+
+#[test]
+fn lowering_continues_after_match_whose_last_arm_is_not_a_wildcard() {
+    let source = r#"
+fn main() -> Int {
+  let res: Result[Int, String] = Err("bad");
+  let total = 1;
+  match (res) {
+    Ok(v) => { total = total + v; }
+    Err(e) => {}
+  }
+  let flag: Option[Int] = Some(2);
+  match (flag) {
+    None => { return 90; }
+    Some(n) => { total = total + n; }
+  }
+  return total + 10;
+}
+"#;
+
+    let program = lowering::compile_source(source).expect("IR lowering should succeed");
+    for func in &program.functions {
+        for block in &func.blocks {
+            assert_ne!(
+                block.terminator,
+                Terminator::Jump(block.id),
+                "block `{}` of `{}` jumps to itself",
+                block.name,
+                func.name
+            );
+        }
+    }
+    let value = IrInterpreter::new(&program)
+        .run_main()
+        .expect("IR interpreter should run");
+    assert_eq!(value, RtValue::Int(13));
+}
@@ -35,3 +35,94 @@ fn main() -> Int { return answer; }
         .expect("IR interpreter should run");
     assert_eq!(value, RtValue::Int(42));
 }
+
+#[test]
+fn project_global_init_visits_shared_dependencies_once_in_module_id_order() {
+    let project = common::TempProject::new("project_global_init_diamond_order");
+    project.file(
+        "trace.sk",
+        r#"
+let order: Int = 0;
+fn mark(step: Int) -> Int {
+  order = order * 10 + step;
+  return step;
+}
+fn current() -> Int { return order; }
+export { mark, current };
+"#,
+    );
+    project.file(
+        "base.sk",
+        r#"
+from trace import mark;
+let base: Int = mark(1);
+export { base };
+"#,
+    );
+    project.file(
+        "left.sk",
+        r#"
+from base import base;
+from trace import mark;
+let left: Int = mark(2) + base;
+export { left };
+"#,
+    );
+    project.file(
+        "right.sk",
+        r#"
+from base import base;
+from trace import mark;
+let right: Int = mark(3) + base;
+export { right };
+"#,
+    );
+    let entry = project.file(
+        "main.sk",
+        r#"
+from right import right;
+from left import left;
+from trace import mark, current;
+let own: Int = mark(4);
+fn main() -> Int { return current(); }
+"#,
+    );
+
+    let program = lowering::compile_project_entry(&entry).expect("project lowering should succeed");
+    let value = IrInterpreter::new(&program)
+        .run_main()
+        .expect("IR interpreter should run");
+    assert_eq!(value, RtValue::Int(1234));
+}
+
+#[test]
+fn project_global_init_runs_reexported_sources_first() {
+    let project = common::TempProject::new("project_global_init_reexport_order");
+    project.file(
+        "inner.sk",
+        r#"
+let seed: Int = 5;
+export { seed };
+"#,
+    );
+    project.file(
+        "facade.sk",
+        r#"
+export { seed } from inner;
+"#,
+    );
+    let entry = project.file(
+        "main.sk",
+        r#"
+from facade import seed;
+let doubled: Int = seed * 2;
+fn main() -> Int { return doubled; }
+"#,
+    );
+
+    let program = lowering::compile_project_entry(&entry).expect("project lowering should succeed");
+    let value = IrInterpreter::new(&program)
+        .run_main()
+        .expect("IR interpreter should run");
+    assert_eq!(value, RtValue::Int(10));
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use skepart::RtValue;
+use skeplib::ir::{IrInterpreter, lowering};
+
+#[test]
+fn project_lowers_imported_type_aliases() {
+    let project = common::TempProject::new("project_imported_type_aliases");
+    project.file(
+        "models.sk",
+        r#"
+struct User { id: Int }
+type Users = Vec[User];
+type Pair = (User, Int);
+export { User, Users, Pair };
+"#,
+    );
+    let entry = project.file(
+        "main.sk",
+        r#"
+from models import User, Users, Pair;
+
+fn total(users: Users) -> Int {
+  let sum = 0;
+  for (u in users) {
+    sum = sum + u.id;
+  }
+  return sum;
+}
+
+fn main() -> Int {
+  let users: Users = vec![User { id: 40 }, User { id: 2 }];
+  let pair: Pair = (User { id: 100 }, 0);
+  return total(users) + pair.0.id;
+}
+"#,
+    );
+
+    let program = lowering::compile_project_entry(&entry).expect("project lowering should succeed");
+    let value = IrInterpreter::new(&program)
+        .run_main()
+        .expect("IR interpreter should run");
+    assert_eq!(value, RtValue::Int(142));
+}
@@ -1005,7 +1005,7 @@ fn main() -> Int {
 }
 
 #[test]
-fn codegen_rejects_panic_and_unreachable_terminators_in_invalid_ir() {
+fn codegen_lowers_panic_terminators_and_rejects_unreachable_ones() {
     let mut builder = ir::IrBuilder::new();
     let mut program = builder.begin_program();
 
@@ -1020,10 +1020,9 @@ fn codegen_rejects_panic_and_unreachable_terminators_in_invalid_ir() {
     );
     program.functions.push(panic_func);
 
-    let err =
-        codegen::compile_program_to_llvm_ir(&program).expect_err("panic terminator should fail");
-    let msg = err.to_string();
-    assert!(msg.contains("does not lower panic terminators"));
+    let llvm_ir =
+        codegen::compile_program_to_llvm_ir(&program).expect("panic terminator should lower");
+    assert!(llvm_ir.contains("call void @skp_rt_raise_unreachable()\n  unreachable"));
 
     let mut builder = ir::IrBuilder::new();
     let mut program = builder.begin_program();
@@ -2,7 +2,7 @@ mod common;
 
 use std::fs;
 
-use skeplib::resolver::{ResolveErrorKind, resolve_project};
+use skeplib::resolver::{ResolveErrorKind, resolve_directory, resolve_project};
 
 fn resolver_fixture_root() -> std::path::PathBuf {
     common::fixtures_dir("resolver")
@@ -77,3 +77,65 @@ fn all_invalid_resolver_fixtures_fail_with_expected_kind() {
         }
     }
 }
+
+#[test]
+fn resolve_directory_roots_every_sk_file_under_the_folder() {
+    let root = common::make_temp_dir("skepa_resolver_directory_roots");
+    fs::create_dir_all(root.join("lib").join("deep")).expect("create lib folders");
+    fs::write(
+        root.join("main.sk"),
+        "from lib.util import one;\nfn main() -> Int { return one(); }",
+    )
+    .expect("write main");
+    fs::write(
+        root.join("lib").join("util.sk"),
+        "fn one() -> Int { return 1; }\nexport { one };",
+    )
+    .expect("write util");
+    fs::write(
+        root.join("lib").join("deep").join("orphan.sk"),
+        "fn unused() -> Int { return 2; }",
+    )
+    .expect("write orphan");
+
+    let graph = resolve_directory(&root).expect("directory should resolve");
+    let mut ids = graph.modules.keys().cloned().collect::<Vec<_>>();
+    ids.sort();
+    assert_eq!(ids, vec!["lib.deep.orphan", "lib.util", "main"]);
+    assert_eq!(graph.modules["main"].imports, vec!["lib.util".to_string()]);
+
+    let empty = common::make_temp_dir("skepa_resolver_directory_empty");
+    let errs = resolve_directory(&empty).expect_err("no modules to check");
+    assert_eq!(errs[0].kind, ResolveErrorKind::MissingModule);
+    let _ = fs::remove_dir_all(root);
+    let _ = fs::remove_dir_all(empty);
+}
+
+#[test]
+fn resolve_project_reports_errors_from_every_phase_in_one_run() {
+    let root = common::make_temp_dir("skepa_resolver_all_phases");
+    fs::write(
+        root.join("main.sk"),
+        "import a;\nimport c;\nimport d;\nimport missing;\nfn main() -> Int { return 0; }",
+    )
+    .expect("write main");
+    fs::write(root.join("a.sk"), "import b;\nfn fa() -> Int { return 1; }").expect("write a");
+    fs::write(root.join("b.sk"), "import a;\nfn fb() -> Int { return 2; }").expect("write b");
+    fs::write(
+        root.join("c.sk"),
+        "from d import hidden;\nfn fc() -> Int { return 3; }",
+    )
+    .expect("write c");
+    fs::write(
+        root.join("d.sk"),
+        "fn hidden() -> Int { return 4; }\nfn shown() -> Int { return 5; }\nexport { shown };",
+    )
+    .expect("write d");
+
+    let errs = resolve_project(&root.join("main.sk")).expect_err("project is invalid");
+    let kinds = errs.iter().map(|e| e.kind).collect::<Vec<_>>();
+    assert!(kinds.contains(&ResolveErrorKind::MissingModule), "{errs:?}");
+    assert!(kinds.contains(&ResolveErrorKind::Cycle), "{errs:?}");
+    assert!(kinds.contains(&ResolveErrorKind::NotExported), "{errs:?}");
+    let _ = fs::remove_dir_all(root);
+}
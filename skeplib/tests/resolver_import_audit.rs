@@ -1,6 +1,6 @@
 mod common;
 
-use skeplib::resolver::{ResolveErrorKind, resolve_project};
+use skeplib::resolver::{ResolveErrorKind, check_capabilities, resolve_project};
 
 #[test]
 fn rejects_unaliased_module_namespace_conflict_with_direct_import() {
@@ -198,3 +198,101 @@ fn main() -> Int { return 0; }
             && e.message.contains("Cannot export module namespace `tools`")
     }));
 }
+
+#[test]
+fn check_capabilities_rejects_denied_package_imports_in_any_module() {
+    let project = common::TempProject::new("capability_denied_imports");
+    project.file(
+        "helper.sk",
+        "import fs;\nfn touch() -> Int { return 0; }\nexport { touch };\n",
+    );
+    let entry = project.file(
+        "main.sk",
+        "import io;\nimport helper;\nfn main() -> Int { return 0; }\n",
+    );
+
+    let graph = resolve_project(&entry).expect("resolve project");
+    assert!(check_capabilities(&graph, &[]).is_empty());
+    assert!(check_capabilities(&graph, &["os".to_string()]).is_empty());
+
+    let errs = check_capabilities(&graph, &["fs".to_string()]);
+    assert_eq!(errs.len(), 1, "{errs:?}");
+    assert_eq!(errs[0].kind, ResolveErrorKind::CapabilityDenied);
+    assert_eq!(errs[0].code, "E-CAP-DENIED");
+    assert!(errs[0].message.contains("module `helper` imports `fs`"));
+    assert_eq!((errs[0].line, errs[0].col), (Some(1), Some(1)));
+}
+
+#[test]
+fn check_capabilities_denies_store_with_fs() {
+    let project = common::TempProject::new("capability_denied_store");
+    let entry = project.file(
+        "main.sk",
+        "import io;\nimport store;\nfn main() -> Int { return 0; }\n",
+    );
+
+    let graph = resolve_project(&entry).expect("resolve project");
+    assert!(check_capabilities(&graph, &["net".to_string()]).is_empty());
+
+    let errs = check_capabilities(&graph, &["fs".to_string()]);
+    assert_eq!(errs.len(), 1, "{errs:?}");
+    assert_eq!(errs[0].kind, ResolveErrorKind::CapabilityDenied);
+    assert!(
+        errs[0]
+            .message
+            .contains("Capability `fs` is denied, but module `main` imports `store`")
+    );
+    assert_eq!((errs[0].line, errs[0].col), (Some(2), Some(1)));
+}
+
+#[test]
+fn check_capabilities_denies_store_and_task_by_name() {
+    let project = common::TempProject::new("capability_denied_store_task");
+    let entry = project.file(
+        "main.sk",
+        "import store;\nimport task;\nfn main() -> Int { return 0; }\n",
+    );
+
+    let graph = resolve_project(&entry).expect("resolve project");
+    let errs = check_capabilities(&graph, &["store".to_string()]);
+    assert_eq!(errs.len(), 1, "{errs:?}");
+    assert!(
+        errs[0]
+            .message
+            .contains("Capability `store` is denied, but module `main` imports `store`")
+    );
+
+    let errs = check_capabilities(&graph, &["task".to_string()]);
+    assert_eq!(errs.len(), 1, "{errs:?}");
+    assert!(errs[0].message.contains("imports `task`"));
+    assert_eq!((errs[0].line, errs[0].col), (Some(2), Some(1)));
+}
+
+#[test]
+fn check_capabilities_treats_extern_functions_as_ffi_use() {
+    let project = common::TempProject::new("capability_denied_extern");
+    let entry = project.file(
+        "main.sk",
+        "extern(\"c\") fn abs(x: Int) -> Int;\nfn main() -> Int { return abs(0); }\n",
+    );
+
+    let graph = resolve_project(&entry).expect("resolve project");
+    let errs = check_capabilities(&graph, &["ffi".to_string()]);
+    assert_eq!(errs.len(), 1, "{errs:?}");
+    assert!(errs[0].message.contains("extern function `abs`"));
+    assert_eq!(errs[0].line, Some(1));
+}
+
+#[test]
+fn check_capabilities_locates_multi_line_extern_functions() {
+    let project = common::TempProject::new("capability_denied_extern_multiline");
+    let entry = project.file(
+        "main.sk",
+        "extern(\"c\")\nfn abs(\n  x: Int\n) -> Int;\nfn main() -> Int { return abs(0); }\n",
+    );
+
+    let graph = resolve_project(&entry).expect("resolve project");
+    let errs = check_capabilities(&graph, &["ffi".to_string()]);
+    assert_eq!(errs.len(), 1, "{errs:?}");
+    assert_eq!((errs[0].line, errs[0].col), (Some(2), Some(4)));
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use skeplib::ast::{Attribute, deprecation};
+use skeplib::diagnostic::DiagnosticLevel;
+use skeplib::resolver::{SymbolKind, build_export_maps, resolve_project};
+use skeplib::sema::{analyze_project_entry, analyze_source};
+
+#[test]
+fn parses_attributes_on_functions_and_structs() {
+    let program = common::parse_ok(
+        "/// Adds.\n@inline\n@deprecated(\"use sum\")\nfn add(a: Int, b: Int) -> Int { return a + b; }\n@deprecated\nstruct Old { x: Int }\n",
+    );
+    let add = &program.functions[0];
+    assert_eq!(
+        add.attributes,
+        vec![
+            Attribute {
+                name: "inline".to_string(),
+                args: Vec::new(),
+            },
+            Attribute {
+                name: "deprecated".to_string(),
+                args: vec!["use sum".to_string()],
+            },
+        ]
+    );
+    assert_eq!(add.doc.as_deref(), Some("Adds."));
+    assert!(add.has_attribute("inline"));
+    assert_eq!(deprecation(&add.attributes), Some(Some("use sum")));
+    assert_eq!(deprecation(&program.structs[0].attributes), Some(None));
+}
+
+#[test]
+fn rejects_attributes_before_other_declarations() {
+    let diags = common::parse_err("@inline\nlet x = 1;\nfn main() -> Int { return 0; }\n");
+    common::assert_has_diag(&diags, "Expected `fn` or `struct` after attributes");
+}
+
+#[test]
+fn rejects_unknown_misplaced_and_malformed_attributes() {
+    for (src, needle) in [
+        (
+            "@fast\nfn f() -> Int { return 0; }\nfn main() -> Int { return 0; }\n",
+            "Unknown attribute `@fast`",
+        ),
+        (
+            "@inline\nstruct S { x: Int }\nfn main() -> Int { return 0; }\n",
+            "Attribute `@inline` cannot be used on a struct",
+        ),
+        (
+            "@inline\n@inline\nfn f() -> Int { return 0; }\nfn main() -> Int { return 0; }\n",
+            "Duplicate attribute `@inline`",
+        ),
+        (
+            "@test(\"x\")\nfn t() -> Int { return 0; }\nfn main() -> Int { return 0; }\n",
+            "Attribute `@test` takes no arguments",
+        ),
+        (
+            "@test\nfn t(n: Int) -> Int { return n; }\nfn main() -> Int { return 0; }\n",
+            "`@test` function `t` must take no parameters and return `Int` or `Void`",
+        ),
+        (
+            "@inline\nextern fn strlen(s: String) -> Int;\nfn main() -> Int { return 0; }\n",
+            "`@inline` cannot be used on extern function `strlen`",
+        ),
+    ] {
+        let (_res, diags) = common::sema_err(src);
+        common::assert_has_diag(&diags, needle);
+    }
+}
+
+#[test]
+fn deprecated_uses_warn_with_the_note_and_follow_lint_levels() {
+    let src = "@deprecated(\"use total\")\nfn sum(a: Int, b: Int) -> Int { return a + b; }\n@deprecated\nstruct Old { x: Int }\nfn main() -> Int {\n  let o = Old { x: 1 };\n  return sum(o.x, 2);\n}\n";
+    let (res, diags) = analyze_source(src);
+    assert!(!res.has_errors, "{diags:?}");
+    assert!(
+        diags
+            .as_slice()
+            .iter()
+            .all(|d| d.level == DiagnosticLevel::Warning)
+    );
+    common::assert_has_diag(&diags, "Function `sum` is deprecated: use total");
+    common::assert_has_diag(&diags, "Struct `Old` is deprecated");
+    assert_eq!(diags.as_slice()[0].span.line, 5);
+
+    common::sema_ok(&format!("#allow(deprecated)\n{src}"));
+    let (res, _diags) = analyze_source(&format!("#deny(deprecated)\n{src}"));
+    assert!(res.has_errors);
+}
+
+fn write_deprecated_library(project: &common::TempProject) {
+    project.file(
+        "lib/old.sk",
+        r#"
+@deprecated("use lib.new.total")
+fn sum(a: Int, b: Int) -> Int { return a + b; }
+@deprecated
+struct Point { x: Int }
+fn fresh() -> Int { return 1; }
+export { sum, Point, fresh };
+"#,
+    );
+    project.file("lib/new.sk", "export { sum as total } from lib.old;\n");
+}
+
+#[test]
+fn export_maps_carry_deprecation_through_re_exports() {
+    let project = common::TempProject::new("deprecated_export_maps");
+    write_deprecated_library(&project);
+    let entry = project.file(
+        "main.sk",
+        "import lib.new;\nfn main() -> Int { return lib.new.total(1, 2); }\n",
+    );
+
+    let graph = resolve_project(&entry).expect("resolve");
+    let maps = build_export_maps(&graph).expect("export maps");
+    let total = &maps["lib.new"]["total"];
+    assert_eq!(total.kind, SymbolKind::Fn);
+    assert_eq!(
+        total.deprecated,
+        Some(Some("use lib.new.total".to_string()))
+    );
+    assert_eq!(maps["lib.old"]["Point"].deprecated, Some(None));
+    assert_eq!(maps["lib.old"]["fresh"].deprecated, None);
+}
+
+#[test]
+fn deprecated_exports_warn_at_import_and_call_sites() {
+    let project = common::TempProject::new("deprecated_import_sites");
+    write_deprecated_library(&project);
+    let entry = project.file(
+        "main.sk",
+        r#"
+from lib.old import sum, Point, fresh;
+import lib.new;
+fn main() -> Int {
+  let p = Point { x: 1 };
+  return sum(p.x, fresh()) + lib.new.total(1, 2);
+}
+"#,
+    );
+
+    let (res, diags) = analyze_project_entry(&entry).expect("resolver/sema");
+    assert!(!res.has_errors, "{diags:?}");
+    common::assert_has_diag(
+        &diags,
+        "Imported function `sum` from `lib.old` is deprecated: use lib.new.total",
+    );
+    common::assert_has_diag(
+        &diags,
+        "Imported struct `Point` from `lib.old` is deprecated",
+    );
+    common::assert_has_diag(&diags, "Function `sum` is deprecated: use lib.new.total");
+    common::assert_has_diag(&diags, "Struct `Point` is deprecated");
+    common::assert_has_diag(
+        &diags,
+        "Function `lib.new.total` is deprecated: use lib.new.total",
+    );
+    assert!(
+        diags
+            .as_slice()
+            .iter()
+            .all(|d| d.level == DiagnosticLevel::Warning && !d.message.contains("fresh"))
+    );
+    assert_eq!(diags.as_slice()[0].span.line, 2);
+}
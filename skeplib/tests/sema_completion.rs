@@ -0,0 +1,162 @@
+mod common;
+
+use skeplib::resolver::resolve_project;
+use skeplib::sema::{CompletionItem, CompletionKind, Position, complete_at};
+
+/// Writes `main.sk` with the `$0` cursor marker removed and completes there.
+fn complete_main(project: &common::TempProject, source: &str) -> Vec<CompletionItem> {
+    let offset = source.find("$0").expect("cursor marker");
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let col = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+    let entry = project.file("main.sk", &source.replacen("$0", "", 1));
+    let graph = resolve_project(&entry).expect("resolve");
+    complete_at(&graph, "main", Position { line, col })
+}
+
+fn labels(items: &[CompletionItem]) -> Vec<&str> {
+    items.iter().map(|item| item.label.as_str()).collect()
+}
+
+#[test]
+fn completes_locals_before_module_items_with_type_labels() {
+    let project = common::TempProject::new("completion_locals");
+    let items = complete_main(
+        &project,
+        r#"
+let total: Int = 0;
+
+fn tally(count: Int) -> Int {
+  return count;
+}
+
+fn main() -> Int {
+  let tag = "x";
+  let tally2 = tally(1);
+  return ta$0lly2;
+}
+
+fn tardy() -> Int {
+  let tail = 2;
+  return tail;
+}
+"#,
+    );
+    assert_eq!(labels(&items), vec!["tag", "tally2", "tally", "tardy"]);
+    assert_eq!(items[0].kind, CompletionKind::Local);
+    assert_eq!(items[0].detail, "String");
+    assert_eq!(items[2].kind, CompletionKind::Function);
+    assert_eq!(items[2].detail, "fn(Int) -> Int");
+}
+
+#[test]
+fn completes_struct_fields_and_methods_after_dot() {
+    let project = common::TempProject::new("completion_fields");
+    let items = complete_main(
+        &project,
+        r#"
+struct Point { x: Int, y: Int }
+struct Line { start: Point, end: Point }
+
+impl Point {
+  fn scaled(self, by: Int) -> Point {
+    return Point { x: self.x * by, y: self.y * by };
+  }
+}
+
+fn main() -> Int {
+  let line = Line { start: Point { x: 1, y: 2 }, end: Point { x: 3, y: 4 } };
+  return line.start.$0x;
+}
+"#,
+    );
+    assert_eq!(
+        items,
+        vec![
+            CompletionItem {
+                label: "x".to_string(),
+                kind: CompletionKind::Field,
+                detail: "Int".to_string(),
+            },
+            CompletionItem {
+                label: "y".to_string(),
+                kind: CompletionKind::Field,
+                detail: "Int".to_string(),
+            },
+            CompletionItem {
+                label: "scaled".to_string(),
+                kind: CompletionKind::Method,
+                detail: "fn(Int) -> Point".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn completes_builtin_package_and_module_namespace_members() {
+    let project = common::TempProject::new("completion_namespaces");
+    project.file(
+        "utils/math.sk",
+        r#"
+fn add(a: Int, b: Int) -> Int {
+  return a + b;
+}
+
+fn abs(a: Int) -> Int {
+  return a;
+}
+
+export { add, abs };
+"#,
+    );
+    let builtin = complete_main(
+        &project,
+        r#"
+import io;
+import utils.math;
+
+fn main() -> Int {
+  io.printl$0n("x");
+  return utils.math.add(1, 2);
+}
+"#,
+    );
+    assert_eq!(labels(&builtin), vec!["println"]);
+    assert_eq!(builtin[0].detail, "fn(String) -> Void");
+
+    let module = complete_main(
+        &project,
+        r#"
+import io;
+import utils.math;
+
+fn main() -> Int {
+  io.println("x");
+  return utils.math.a$0dd(1, 2);
+}
+"#,
+    );
+    assert_eq!(labels(&module), vec!["abs", "add"]);
+    assert_eq!(module[1].detail, "fn(Int, Int) -> Int");
+
+    let namespaces = complete_main(
+        &project,
+        r#"
+import io;
+import utils.math;
+
+fn main() -> Int {
+  io.println("x");
+  return u$0tils.math.add(1, 2);
+}
+"#,
+    );
+    assert_eq!(labels(&namespaces), vec!["utils"]);
+    assert_eq!(namespaces[0].kind, CompletionKind::Module);
+}
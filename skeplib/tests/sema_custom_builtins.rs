@@ -0,0 +1,136 @@
+mod common;
+
+use skepart::{RtError, RtHost, RtResult, RtValue};
+use skeplib::builtins::{BuiltinKind, BuiltinSig, CustomBuiltinSig, CustomBuiltins};
+use skeplib::driver::{Compiler, Emit};
+use skeplib::ir::IrInterpreter;
+use skeplib::sema::analyze_source_with_builtins;
+use skeplib::types::TypeInfo;
+
+fn db_package() -> CustomBuiltins {
+    let mut builtins = CustomBuiltins::new();
+    builtins
+        .register(BuiltinSig {
+            package: "db",
+            name: "count",
+            params: &[TypeInfo::String],
+            ret: TypeInfo::Int,
+            kind: BuiltinKind::FixedArity,
+        })
+        .expect("register db.count");
+    builtins
+}
+
+#[test]
+fn custom_builtins_type_check_like_native_ones() {
+    let builtins = db_package();
+    let ok = r#"
+import db;
+
+fn main() -> Int {
+  let rows: Int = db.count("users");
+  return rows;
+}
+"#;
+    let (res, diags) = analyze_source_with_builtins(ok, &builtins);
+    assert!(!res.has_errors, "{:?}", diags.as_slice());
+
+    let bad = r#"
+import db;
+
+fn main() -> Int {
+  let rows: String = db.count(1);
+  return 0;
+}
+"#;
+    let (res, diags) = analyze_source_with_builtins(bad, &builtins);
+    assert!(res.has_errors);
+    common::assert_has_diag(&diags, "db.count argument 1 expects String, got Int");
+
+    let missing_import = "fn main() -> Int { return db.count(\"users\"); }";
+    let (res, diags) = analyze_source_with_builtins(missing_import, &builtins);
+    assert!(res.has_errors);
+    common::assert_has_diag(&diags, "`db.*` used without `import db;`");
+}
+
+#[test]
+fn custom_builtins_reject_native_packages_and_duplicates() {
+    let mut builtins = db_package();
+    let sig = |package| BuiltinSig {
+        package,
+        name: "count",
+        params: &[],
+        ret: TypeInfo::Int,
+        kind: BuiltinKind::FixedArity,
+    };
+    assert!(builtins.register(sig("str")).is_err());
+    assert!(builtins.register(sig("db")).is_err());
+}
+
+#[test]
+fn driver_resolves_and_checks_projects_importing_custom_packages() {
+    let project = common::TempProject::new("skepa_custom_builtins_project");
+    let entry = project.file(
+        "main.sk",
+        "import db;\nfn main() -> Int { return db.count(\"users\"); }",
+    );
+
+    let output = Compiler::entry(&entry).builtins(db_package()).run();
+    assert!(output.succeeded(), "{:?}", output.diagnostics);
+
+    let without = Compiler::entry(&entry).run();
+    assert!(!without.succeeded());
+}
+
+struct DbHost;
+
+impl RtHost for DbHost {
+    fn io_print(&mut self, _text: &str) -> RtResult<()> {
+        Ok(())
+    }
+
+    fn call_custom(&mut self, package: &str, name: &str, args: &[RtValue]) -> RtResult<RtValue> {
+        match (package, name, args) {
+            ("db", "count", [table]) => Ok(RtValue::Int(match table.expect_string()?.as_str() {
+                "users" => 3,
+                _ => 0,
+            })),
+            ("db", "limit", []) => Ok(RtValue::Int(10)),
+            _ => Err(RtError::unsupported_builtin(format!("{package}.{name}"))),
+        }
+    }
+}
+
+#[test]
+fn interpreter_runs_custom_builtins_through_the_host() {
+    let mut builtins = db_package();
+    let package = String::from("db");
+    builtins
+        .register(CustomBuiltinSig::new(
+            package,
+            "limit",
+            Vec::new(),
+            TypeInfo::Int,
+        ))
+        .expect("register runtime-built db.limit");
+    let project = common::TempProject::new("skepa_custom_builtins_run");
+    let entry = project.file(
+        "main.sk",
+        "import db;\nfn main() -> Int { return db.count(\"users\") * db.limit(); }",
+    );
+
+    let output = Compiler::entry(&entry)
+        .builtins(builtins)
+        .emit(Emit::Ir)
+        .run();
+    assert!(output.succeeded(), "{:?}", output.diagnostics);
+    let program = output.ir.expect("ir");
+
+    let interp = IrInterpreter::with_host(&program, Box::new(DbHost));
+    assert_eq!(interp.run_main(), Ok(RtValue::Int(30)));
+
+    let err = IrInterpreter::new(&program)
+        .run_main()
+        .expect_err("hosts without call_custom reject custom builtins");
+    assert!(err.to_string().contains("db.count"), "{err}");
+}
@@ -13,6 +13,15 @@ fn main() -> Int { return value(); }
     let (res, diags) = analyze_source(src);
     assert!(res.has_errors);
     common::assert_has_diag(&diags, "Duplicate function declaration `value`");
+    let dup = diags
+        .as_slice()
+        .iter()
+        .find(|d| d.message.contains("Duplicate function declaration"))
+        .expect("duplicate diagnostic");
+    assert_eq!((dup.span.line, dup.span.col), (3, 1));
+    assert_eq!(dup.labels.len(), 1);
+    assert_eq!(dup.labels[0].span.line, 2);
+    assert_eq!(dup.labels[0].message, "first declared here");
 }
 
 #[test]
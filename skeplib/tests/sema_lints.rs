@@ -0,0 +1,93 @@
+mod common;
+
+use skeplib::diagnostic::DiagnosticLevel;
+use skeplib::driver::{Compiler, Phase};
+use skeplib::lint::{Lint, LintConfig, LintDirective, LintLevel, LintTarget};
+use skeplib::sema::analyze_source;
+
+const DEAD_CODE: &str = "fn main() -> Int {\n  return 0;\n  let x = 1;\n}\n";
+
+#[test]
+fn parses_file_level_lint_directives() {
+    let program = common::parse_ok(
+        "#allow(unreachable_code);\n#deny(warnings, unreachable_code)\nfn main() -> Int { return 0; }\n",
+    );
+    assert_eq!(
+        program.lints,
+        vec![
+            LintDirective {
+                level: LintLevel::Allow,
+                targets: vec![LintTarget::Lint(Lint::UnreachableCode)],
+            },
+            LintDirective {
+                level: LintLevel::Deny,
+                targets: vec![
+                    LintTarget::Warnings,
+                    LintTarget::Lint(Lint::UnreachableCode)
+                ],
+            },
+        ]
+    );
+}
+
+#[test]
+fn rejects_unknown_lint_levels_and_names() {
+    let diags = common::parse_err("#forbid(warnings)\nfn main() -> Int { return 0; }\n");
+    common::assert_has_diag(&diags, "Unknown lint level `forbid`");
+    let diags = common::parse_err("#allow(unused_variable)\nfn main() -> Int { return 0; }\n");
+    common::assert_has_diag(&diags, "Unknown lint `unused_variable`");
+    assert_eq!(diags.as_slice()[0].span.col, 8);
+}
+
+#[test]
+fn file_directives_set_the_level_of_unreachable_code() {
+    let (res, diags) = analyze_source(DEAD_CODE);
+    assert!(!res.has_errors);
+    assert_eq!(diags.as_slice()[0].level, DiagnosticLevel::Warning);
+
+    common::sema_ok(&format!("#allow(unreachable_code)\n{DEAD_CODE}"));
+
+    let (res, diags) = analyze_source(&format!("#deny(warnings)\n{DEAD_CODE}"));
+    assert!(res.has_errors);
+    assert_eq!(diags.as_slice()[0].level, DiagnosticLevel::Error);
+    common::assert_has_diag(&diags, "Unreachable statement after `return`");
+}
+
+#[test]
+fn lint_config_applies_later_settings_and_warnings_to_warn_lints_only() {
+    let mut config = LintConfig::new();
+    config.set(LintTarget::Warnings, LintLevel::Deny);
+    assert_eq!(config.level(Lint::UnreachableCode), LintLevel::Deny);
+    config.set(LintTarget::Lint(Lint::UnreachableCode), LintLevel::Allow);
+    assert_eq!(config.level(Lint::UnreachableCode), LintLevel::Allow);
+
+    let overridden = config.with_directives(&[LintDirective {
+        level: LintLevel::Warn,
+        targets: vec![LintTarget::Lint(Lint::UnreachableCode)],
+    }]);
+    assert_eq!(overridden.level(Lint::UnreachableCode), LintLevel::Deny);
+}
+
+#[test]
+fn compiler_lints_fail_sema_unless_a_module_allows_the_lint() {
+    let project = common::TempProject::new("skepa_sema_lints");
+    project.file(
+        "util.sk",
+        "#allow(unreachable_code)\nfn one() -> Int {\n  return 1;\n  let x = 2;\n}\nexport { one };\n",
+    );
+    let entry = project.file(
+        "main.sk",
+        "from util import one;\nfn main() -> Int { return one(); }\n",
+    );
+    let mut deny = LintConfig::new();
+    deny.set(LintTarget::Warnings, LintLevel::Deny);
+
+    let output = Compiler::entry(&entry).lints(deny.clone()).run();
+    assert!(output.succeeded(), "{:?}", output.diagnostics);
+    assert!(output.diagnostics.is_empty());
+
+    let dead = project.file("dead.sk", DEAD_CODE);
+    assert!(Compiler::entry(&dead).run().succeeded());
+    let output = Compiler::entry(&dead).lints(deny).run();
+    assert_eq!(output.failed, Some(Phase::Sema));
+}
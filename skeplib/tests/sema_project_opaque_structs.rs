@@ -0,0 +1,143 @@
+mod common;
+
+use skeplib::sema::analyze_project_entry;
+
+const USER_MODULE: &str = r#"
+struct User { id: Int, name: String }
+
+impl User {
+  fn getId(self) -> Int {
+    return self.id;
+  }
+}
+
+fn make(id: Int) -> User {
+  return User { id: id, name: "anon" };
+}
+
+export { User(opaque), make };
+"#;
+
+#[test]
+fn accepts_opaque_struct_through_constructor_fn_and_methods() {
+    let project = common::TempProject::new("opaque_struct_methods");
+    project.file("models.sk", USER_MODULE);
+    let entry = project.file(
+        "main.sk",
+        r#"
+from models import User, make;
+
+fn idOf(user: User) -> Int {
+  return user.getId();
+}
+
+fn main() -> Int {
+  let u: User = make(7);
+  return idOf(u);
+}
+"#,
+    );
+
+    let (res, diags) = analyze_project_entry(&entry).expect("resolver/sema");
+    common::assert_sema_success(&res, &diags);
+}
+
+#[test]
+fn rejects_literal_construction_and_field_access_on_imported_opaque_struct() {
+    let project = common::TempProject::new("opaque_struct_field_access");
+    project.file("models.sk", USER_MODULE);
+    let entry = project.file(
+        "main.sk",
+        r#"
+from models import User, make;
+import models as m;
+
+fn main() -> Int {
+  let a = User { id: 1, name: "ana" };
+  let b = m.User { id: 2, name: "bo" };
+  let u = make(3);
+  u.id = 4;
+  return u.id;
+}
+"#,
+    );
+
+    let (res, diags) = analyze_project_entry(&entry).expect("resolver/sema");
+    assert!(res.has_errors);
+    common::assert_has_diag(
+        &diags,
+        "Cannot construct struct `User` outside module `models`: it is exported as opaque",
+    );
+    common::assert_has_diag(
+        &diags,
+        "Cannot construct struct `models.User` outside module `models`: it is exported as opaque",
+    );
+    common::assert_has_diag(
+        &diags,
+        "Cannot assign field `id` of struct `User` outside module `models`: it is exported as opaque",
+    );
+    common::assert_has_diag(
+        &diags,
+        "Cannot read field `id` of struct `User` outside module `models`: it is exported as opaque",
+    );
+}
+
+#[test]
+fn rejects_opaque_marker_on_non_struct_exports() {
+    let project = common::TempProject::new("opaque_marker_on_fn");
+    let entry = project.file(
+        "main.sk",
+        r#"
+fn helper() -> Int {
+  return 1;
+}
+
+export { helper(opaque) };
+
+fn main() -> Int {
+  return helper();
+}
+"#,
+    );
+
+    let (res, diags) = analyze_project_entry(&entry).expect("resolver/sema");
+    assert!(res.has_errors);
+    common::assert_has_diag(
+        &diags,
+        "`(opaque)` on `helper` is only allowed when exporting a struct declared in this module",
+    );
+}
+
+#[test]
+fn rejected_opaque_destructure_still_declares_its_bindings() {
+    let project = common::TempProject::new("opaque_struct_destructure");
+    project.file("models.sk", USER_MODULE);
+    let entry = project.file(
+        "main.sk",
+        r#"
+from models import User, make;
+
+fn main() -> Int {
+  let u = make(3);
+  let User { id, .. } = u;
+  let total = match (u) {
+    User { id: other, name } => other + id,
+  };
+  return total;
+}
+"#,
+    );
+
+    let (res, diags) = analyze_project_entry(&entry).expect("resolver/sema");
+    assert!(res.has_errors);
+    common::assert_has_diag(
+        &diags,
+        "Cannot destructure struct `User` outside module `models`: it is exported as opaque",
+    );
+    let messages = diags
+        .as_slice()
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+}
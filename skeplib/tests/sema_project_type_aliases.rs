@@ -0,0 +1,70 @@
+mod common;
+
+use skeplib::sema::analyze_project_entry;
+
+const IDS_MODULE: &str = r#"
+type UserId = Int;
+type Scores = Map[String, UserId];
+export { UserId, Scores };
+"#;
+
+#[test]
+fn resolves_type_aliases_through_from_and_module_imports() {
+    let project = common::TempProject::new("type_alias_imports");
+    project.file("lib/ids.sk", IDS_MODULE);
+    project.file(
+        "models.sk",
+        r#"
+from lib.ids import UserId;
+type Handler = Fn(UserId) -> UserId;
+fn apply(h: Handler, id: UserId) -> UserId {
+  return h(id);
+}
+export { Handler, apply };
+export { UserId as Id } from lib.ids;
+"#,
+    );
+    let entry = project.file(
+        "main.sk",
+        r#"
+import lib.ids as ids;
+import map;
+import models;
+from models import Handler, Id;
+
+fn main() -> Int {
+  let h: Handler = fn(x: ids.UserId) -> Id { return x + 1; };
+  let scores: ids.Scores = map.new();
+  let id: models.Id = models.apply(h, 41);
+  return id;
+}
+"#,
+    );
+
+    let (res, diags) = analyze_project_entry(&entry).expect("resolver/sema");
+    common::assert_sema_success(&res, &diags);
+}
+
+#[test]
+fn imported_type_aliases_keep_their_target_type() {
+    let project = common::TempProject::new("type_alias_import_mismatch");
+    project.file("ids.sk", IDS_MODULE);
+    let entry = project.file(
+        "main.sk",
+        r#"
+from ids import UserId;
+
+fn main() -> Int {
+  let id: UserId = "ada";
+  return 0;
+}
+"#,
+    );
+
+    let (res, diags) = analyze_project_entry(&entry).expect("resolver/sema");
+    assert!(res.has_errors);
+    common::assert_has_diag(
+        &diags,
+        "Type mismatch in let `id`: declared Int, got String",
+    );
+}
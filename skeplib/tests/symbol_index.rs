@@ -0,0 +1,221 @@
+mod common;
+
+use skeplib::index::{IndexedSymbolKind, SymbolIndex, source_hash};
+use skeplib::resolver::resolve_project;
+
+fn sample_project(prefix: &str) -> (common::TempProject, std::path::PathBuf) {
+    let project = common::TempProject::new(prefix);
+    project.file(
+        "models/user.sk",
+        r#"
+struct User { id: Int }
+
+impl User {
+  fn getId(self) -> Int {
+    return self.id;
+  }
+}
+
+fn makeUser(id: Int) -> User {
+  return User { id: id };
+}
+
+fn helper() -> Int {
+  return 1;
+}
+
+export { User, makeUser as make };
+"#,
+    );
+    let entry = project.file(
+        "main.sk",
+        r#"
+from models.user import make;
+
+let limit: Int = 3;
+
+fn main() -> Int {
+  return make(limit).getId();
+}
+
+fn mainHelper() -> Int {
+  return 0;
+}
+"#,
+    );
+    (project, entry)
+}
+
+#[test]
+fn symbol_index_records_declarations_exports_and_imports() {
+    let (_project, entry) = sample_project("symbol_index_build");
+    let graph = resolve_project(&entry).expect("resolve");
+    let index = SymbolIndex::build(&graph).expect("index");
+
+    let user = index.module("models.user").expect("models.user indexed");
+    let names = user
+        .symbols
+        .iter()
+        .map(|s| (s.kind, s.name.as_str(), s.line))
+        .collect::<Vec<_>>();
+    assert!(names.contains(&(IndexedSymbolKind::Struct, "User", 2)));
+    assert!(names.contains(&(IndexedSymbolKind::Method, "User.getId", 5)));
+    assert!(names.contains(&(IndexedSymbolKind::Fn, "makeUser", 10)));
+    let exports = user
+        .exports
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(exports, vec!["User", "make"]);
+
+    let main = index.module("main").expect("main indexed");
+    assert_eq!(main.imports, vec!["models.user".to_string()]);
+}
+
+#[test]
+fn symbol_index_answers_definition_and_completion_queries() {
+    let (_project, entry) = sample_project("symbol_index_queries");
+    let graph = resolve_project(&entry).expect("resolve");
+    let index = SymbolIndex::build(&graph).expect("index");
+
+    let def = index
+        .definition("main", "make")
+        .expect("imported definition");
+    assert_eq!(def.module_id, "models.user");
+    assert_eq!((def.line, def.col), (10, 1));
+    let local = index.definition("main", "limit").expect("local definition");
+    assert_eq!((local.module_id.as_str(), local.line), ("main", 4));
+    assert!(index.definition("main", "helper").is_none());
+
+    assert_eq!(
+        index.completions("main", "ma"),
+        vec![
+            "main".to_string(),
+            "mainHelper".to_string(),
+            "make".to_string()
+        ]
+    );
+}
+
+#[test]
+fn symbol_index_round_trips_through_disk_and_detects_stale_modules() {
+    let (project, entry) = sample_project("symbol_index_persist");
+    let graph = resolve_project(&entry).expect("resolve");
+    let index = SymbolIndex::build(&graph).expect("index");
+
+    let path = project.root().join(".skepac-cache").join("symbols.idx");
+    index.write_to(&path).expect("write index");
+    let loaded = SymbolIndex::read_from(&path).expect("read index");
+    assert_eq!(loaded, index);
+    assert!(loaded.stale_modules().is_empty());
+
+    project.file("main.sk", "fn main() -> Int { return 0; }\n");
+    assert_eq!(loaded.stale_modules(), vec!["main".to_string()]);
+}
+
+#[test]
+fn symbol_index_source_hash_is_stable_fnv1a() {
+    assert_eq!(source_hash(""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(
+        source_hash("fn main() -> Int { return 0; }\n"),
+        0x3d9b_2877_0355_168a
+    );
+}
+
+#[test]
+fn symbol_index_rejects_malformed_text() {
+    let err = SymbolIndex::from_text("not an index\n").expect_err("bad header");
+    assert!(err.contains("header"));
+    let err = SymbolIndex::from_text("skepa-symbol-index v3\nsymbol\tfn\tmain\t1\t1\n")
+        .expect_err("symbol before module");
+    assert!(err.contains("line 2"));
+    let err = SymbolIndex::from_text("skepa-symbol-index v3\nmodule\tmain\tbad\\q\t0\n")
+        .expect_err("unknown escape");
+    assert!(err.contains("line 2"));
+}
+
+mod text_round_trip {
+    use proptest::prelude::*;
+    use skeplib::index::{
+        IndexedExport, IndexedModule, IndexedSymbol, IndexedSymbolKind, SymbolIndex,
+    };
+    use std::path::PathBuf;
+
+    fn ident() -> impl Strategy<Value = String> {
+        "[A-Za-z_][A-Za-z0-9_]{0,8}"
+    }
+
+    fn module_id() -> impl Strategy<Value = String> {
+        prop::collection::vec(ident(), 1..4).prop_map(|parts| parts.join("."))
+    }
+
+    fn symbol_kind() -> impl Strategy<Value = IndexedSymbolKind> {
+        prop_oneof![
+            Just(IndexedSymbolKind::Fn),
+            Just(IndexedSymbolKind::Struct),
+            Just(IndexedSymbolKind::TypeAlias),
+            Just(IndexedSymbolKind::Method),
+            Just(IndexedSymbolKind::Global),
+            Just(IndexedSymbolKind::Operator),
+        ]
+    }
+
+    fn symbol() -> impl Strategy<Value = IndexedSymbol> {
+        let name = prop_oneof![
+            ident(),
+            (ident(), ident()).prop_map(|(ty, method)| format!("{ty}.{method}")),
+            "[-+*/%<>=!&|^~]{1,3}",
+            any::<String>(),
+        ];
+        (symbol_kind(), name, 1..10_000usize, 1..500usize).prop_map(|(kind, name, line, col)| {
+            IndexedSymbol {
+                kind,
+                name,
+                line,
+                col,
+            }
+        })
+    }
+
+    fn export() -> impl Strategy<Value = IndexedExport> {
+        (ident(), module_id(), ident()).prop_map(|(name, module_id, local_name)| IndexedExport {
+            name,
+            module_id,
+            local_name,
+        })
+    }
+
+    fn module() -> impl Strategy<Value = IndexedModule> {
+        (
+            module_id(),
+            any::<String>(),
+            any::<u64>(),
+            prop::collection::vec(module_id(), 0..4),
+            prop::collection::vec(export(), 0..4),
+            prop::collection::vec(symbol(), 0..6),
+        )
+            .prop_map(
+                |(id, path, source_hash, imports, exports, symbols)| IndexedModule {
+                    id,
+                    path: PathBuf::from(path),
+                    source_hash,
+                    imports,
+                    exports,
+                    symbols,
+                },
+            )
+    }
+
+    fn index() -> impl Strategy<Value = SymbolIndex> {
+        prop::collection::vec(module(), 0..5).prop_map(|modules| SymbolIndex { modules })
+    }
+
+    proptest! {
+        #[test]
+        fn symbol_index_text_round_trips(index in index()) {
+            let text = index.to_text();
+            let parsed = SymbolIndex::from_text(&text).expect("parse emitted index");
+            prop_assert_eq!(parsed, index);
+        }
+    }
+}